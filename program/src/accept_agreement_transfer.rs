@@ -0,0 +1,227 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::AgreementTransferred;
+use crate::state::{Payee, PaymentAgreement, PaymentTerms, RenewalQueue, VersionedAccount};
+use crate::utils::dequeue_renewal;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Arguments for accepting a payment agreement transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct AcceptAgreementTransferArgs {
+    /// Renewal queue bucket of the agreement's next payment
+    /// (`next_payment_ts / RENEWAL_BUCKET_SECS`), used to derive `renewal_queue` and
+    /// `new_renewal_queue`
+    pub renewal_bucket: u64,
+}
+
+/// Accounts required for accepting a payment agreement transfer
+#[derive(Accounts)]
+#[instruction(args: AcceptAgreementTransferArgs)]
+pub struct AcceptAgreementTransfer<'info> {
+    /// Agreement under the previous payer's PDA (closed, rent returned to the previous payer)
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), old_payer.key().as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized,
+        constraint = payment_agreement.payer == old_payer.key() @ RecurringPaymentError::Unauthorized,
+        close = old_payer
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    /// Agreement under the new payer's PDA (created, rent paid by the new payer)
+    #[account(
+        init,
+        payer = new_payer,
+        space = PaymentAgreement::SPACE,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), new_payer.key().as_ref()],
+        bump
+    )]
+    pub new_payment_agreement: Account<'info, PaymentAgreement>,
+
+    #[account(has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Previous payer, receives the closed agreement's rent
+    /// CHECK: Bound to the agreement by its PDA seeds and the payer constraint
+    #[account(mut)]
+    pub old_payer: UncheckedAccount<'info>,
+
+    /// New payer accepting the transfer (must sign, pays rent for the new agreement)
+    #[account(mut)]
+    pub new_payer: Signer<'info>,
+
+    /// New payer's USDC token account, which must approve the program delegate
+    /// CHECK: Validated as USDC token account in handler
+    pub new_payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// Program PDA that acts as delegate
+    /// CHECK: PDA derived from program, validated by seeds
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    /// Crank index shard listing the old agreement address. The old address is
+    /// removed from it if the queue exists.
+    /// CHECK: PDA derivation and ownership validated in handler
+    #[account(mut)]
+    pub renewal_queue: UncheckedAccount<'info>,
+
+    /// Crank index shard for the new agreement address in the same bucket
+    #[account(
+        init_if_needed,
+        payer = new_payer,
+        space = RenewalQueue::SPACE,
+        seeds = [
+            b"renewal_queue",
+            args.renewal_bucket.to_le_bytes().as_ref(),
+            RenewalQueue::shard_seed(&new_payment_agreement.key()).as_ref()
+        ],
+        bump
+    )]
+    pub new_renewal_queue: Account<'info, RenewalQueue>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for accepting a payment agreement transfer
+///
+/// Completes the two-step transfer started by `initiate_agreement_transfer`. Because
+/// agreement PDAs are derived from the payer, the agreement is copied to the new
+/// payer's PDA and the old account is closed. Renewal history (`payment_count`,
+/// `created_ts`), the billing schedule and the subscriber slot carry over unchanged.
+/// A held security deposit carries over too and is refunded to the new payer on close.
+/// Renewal queues index agreements by address, so the queue entry moves from the old
+/// address to the new one within the bucket of the next payment.
+///
+/// The new wallet must already have approved the program delegate for at least one
+/// period's amount, so the next `execute_payment` can pull from it.
+///
+/// # Errors
+/// Returns an error if:
+/// - No transfer is pending, or the signer is not the proposed payer
+/// - The new payer's token account is not a USDC account owned by the new payer
+/// - The program delegate is not approved for at least one period's amount
+/// - The new payer already has an agreement for these payment terms
+/// - `renewal_bucket` is not the bucket of the agreement's next payment, or
+///   `renewal_queue` is not the old address's shard for it
+pub fn handler(
+    ctx: Context<AcceptAgreementTransfer>,
+    args: AcceptAgreementTransferArgs,
+) -> Result<()> {
+    let payment_agreement = &ctx.accounts.payment_agreement;
+    let payment_terms = &ctx.accounts.payment_terms;
+    let new_payer = ctx.accounts.new_payer.key();
+
+    let pending_payer = payment_agreement
+        .pending_payer
+        .ok_or(RecurringPaymentError::NoPendingTransfer)?;
+    require!(new_payer == pending_payer, RecurringPaymentError::Unauthorized);
+
+    // The new wallet must be able to fund the next pull
+    let new_payer_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.new_payer_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    if new_payer_ata_data.owner != new_payer {
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    if new_payer_ata_data.mint != ctx.accounts.payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if Option::<Pubkey>::from(new_payer_ata_data.delegate)
+        != Some(ctx.accounts.program_delegate.key())
+        || new_payer_ata_data.delegated_amount < payment_terms.amount_usdc
+    {
+        return Err(RecurringPaymentError::InsufficientAllowance.into());
+    }
+
+    // Move the queue entry to the new address; the two shards may be the same account
+    require!(
+        RenewalQueue::bucket_for(payment_agreement.next_payment_ts) == Some(args.renewal_bucket),
+        RecurringPaymentError::InvalidRenewalBucket
+    );
+    let old_agreement = payment_agreement.key();
+    let new_agreement = ctx.accounts.new_payment_agreement.key();
+    if ctx.accounts.renewal_queue.key() == ctx.accounts.new_renewal_queue.key() {
+        require!(
+            RenewalQueue::shard_for(&old_agreement) == RenewalQueue::shard_for(&new_agreement),
+            RecurringPaymentError::InvalidRenewalBucket
+        );
+        ctx.accounts.new_renewal_queue.remove(&old_agreement);
+    } else {
+        dequeue_renewal(
+            &ctx.accounts.renewal_queue.to_account_info(),
+            args.renewal_bucket,
+            &old_agreement,
+            ctx.program_id,
+        )?;
+    }
+    let new_renewal_queue = &mut ctx.accounts.new_renewal_queue;
+    new_renewal_queue.bucket = args.renewal_bucket;
+    new_renewal_queue.shard = RenewalQueue::shard_for(&new_agreement);
+    new_renewal_queue.bump = ctx.bumps.new_renewal_queue;
+    // Paused and streaming agreements are not indexed
+    if payment_agreement.active && !payment_terms.is_streaming() {
+        new_renewal_queue.insert(new_agreement);
+    }
+
+    let new_payment_agreement = &mut ctx.accounts.new_payment_agreement;
+    new_payment_agreement.payment_terms = payment_agreement.payment_terms;
+    new_payment_agreement.payer = new_payer;
+    new_payment_agreement.next_payment_ts = payment_agreement.next_payment_ts;
+    new_payment_agreement.active = payment_agreement.active;
+    new_payment_agreement.payment_count = payment_agreement.payment_count;
+    new_payment_agreement.created_ts = payment_agreement.created_ts;
+    new_payment_agreement.last_amount = payment_agreement.last_amount;
+    new_payment_agreement.last_payment_ts = payment_agreement.last_payment_ts;
+    new_payment_agreement.last_pull_period_index = payment_agreement.last_pull_period_index;
+    new_payment_agreement.cancel_at_period_end = payment_agreement.cancel_at_period_end;
+    new_payment_agreement.pending_payer = None;
+    new_payment_agreement.refunded_amount = payment_agreement.refunded_amount;
+    new_payment_agreement.max_periods = payment_agreement.max_periods;
+    new_payment_agreement.periods_paid = payment_agreement.periods_paid;
+    new_payment_agreement.external_ref_hash = payment_agreement.external_ref_hash;
+    new_payment_agreement.paused_at_ts = payment_agreement.paused_at_ts;
+    new_payment_agreement.credit_amount = payment_agreement.credit_amount;
+    new_payment_agreement.escrow = payment_agreement.escrow;
+    new_payment_agreement.period_index = payment_agreement.period_index;
+    new_payment_agreement.suspension = payment_agreement.suspension;
+    new_payment_agreement.deposit_held = payment_agreement.deposit_held;
+    new_payment_agreement.bump = ctx.bumps.new_payment_agreement;
+    new_payment_agreement.version = PaymentAgreement::VERSION;
+    new_payment_agreement.last_sweep_ts = payment_agreement.last_sweep_ts;
+
+    emit!(AgreementTransferred {
+        payee: ctx.accounts.payee.key(),
+        payment_terms: payment_terms.key(),
+        old_payer: payment_agreement.payer,
+        new_payer,
+        payment_count: new_payment_agreement.payment_count,
+        original_created_ts: new_payment_agreement.created_ts,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_agreement_transfer_args_default() {
+        let args = AcceptAgreementTransferArgs::default();
+        // Verify default construction works
+        let _serialized = args.try_to_vec().unwrap();
+    }
+}
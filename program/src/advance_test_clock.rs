@@ -0,0 +1,90 @@
+use crate::errors::RecurringPaymentError;
+use crate::state::{Config, TestClock};
+use anchor_lang::prelude::*;
+
+/// Arguments for advancing the test clock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AdvanceTestClockArgs {
+    /// Seconds to move the test clock forward by
+    pub secs: u64,
+}
+
+/// Accounts required for advancing the test clock
+#[derive(Accounts)]
+pub struct AdvanceTestClock<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = platform_authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Test clock offset, created on first use
+    #[account(
+        init_if_needed,
+        payer = platform_authority,
+        space = TestClock::SPACE,
+        seeds = [b"test_clock"],
+        bump
+    )]
+    pub test_clock: Account<'info, TestClock>,
+
+    /// Platform authority (must sign); pays for the test clock if it doesn't exist yet
+    #[account(mut)]
+    pub platform_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for advancing the test clock
+///
+/// Only compiled into programs built with the `test-clock` feature, which must never
+/// be deployed to mainnet. `execute_payment` adds the accumulated offset to the
+/// cluster time when the test clock is passed as a remaining account, so integration
+/// tests can run a full billing lifecycle in seconds.
+///
+/// # Security
+/// - Only `platform_authority` can advance the clock
+/// - The clock only moves forward, so renewals already charged stay in the past
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the platform authority
+/// - `secs` is zero or the offset overflows
+pub fn handler(ctx: Context<AdvanceTestClock>, args: AdvanceTestClockArgs) -> Result<()> {
+    require!(args.secs > 0, RecurringPaymentError::InvalidConfiguration);
+    let secs = i64::try_from(args.secs).map_err(|_| RecurringPaymentError::ArithmeticError)?;
+
+    let test_clock = &mut ctx.accounts.test_clock;
+    test_clock.offset_secs = test_clock
+        .offset_secs
+        .checked_add(secs)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    test_clock.bump = ctx.bumps.test_clock;
+
+    let clock = Clock::get()?;
+    msg!(
+        "Test clock advanced by {}s to offset {}s (now {})",
+        secs,
+        test_clock.offset_secs,
+        test_clock.now(clock.unix_timestamp)
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advance_test_clock_args_serialization() {
+        let args = AdvanceTestClockArgs { secs: 2_592_000 };
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = AdvanceTestClockArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.secs, 2_592_000);
+    }
+}
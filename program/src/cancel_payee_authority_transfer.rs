@@ -0,0 +1,77 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::PayeeAuthorityTransferCancelled;
+use crate::state::Payee;
+use anchor_lang::prelude::*;
+
+/// Arguments for canceling a payee authority transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct CancelPayeeAuthorityTransferArgs {
+    // No arguments needed - signer validation is sufficient
+}
+
+/// Accounts required for canceling a payee authority transfer
+#[derive(Accounts)]
+pub struct CancelPayeeAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Current payee authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+/// Handler for canceling a payee authority transfer
+///
+/// This allows the current payee authority to revoke a pending transfer, e.g. when
+/// it was proposed to the wrong key.
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the payee authority
+/// - No pending transfer exists to cancel
+pub fn handler(
+    ctx: Context<CancelPayeeAuthorityTransfer>,
+    _args: CancelPayeeAuthorityTransferArgs,
+) -> Result<()> {
+    let payee = &mut ctx.accounts.payee;
+
+    // Ensure a pending transfer exists to cancel
+    let pending_authority = payee
+        .pending_authority
+        .ok_or(RecurringPaymentError::NoPendingTransfer)?;
+
+    payee.pending_authority = None;
+
+    let clock = Clock::get()?;
+
+    emit!(PayeeAuthorityTransferCancelled {
+        payee: payee.key(),
+        authority: payee.authority,
+        cancelled_authority: pending_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_payee_authority_transfer_args_serialization() {
+        let args = CancelPayeeAuthorityTransferArgs::default();
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: CancelPayeeAuthorityTransferArgs =
+            CancelPayeeAuthorityTransferArgs::try_from_slice(&serialized).unwrap();
+
+        // Since the struct has no fields, just verify it deserializes
+        let _ = deserialized;
+    }
+}
@@ -0,0 +1,170 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::DepositClaimed;
+use crate::state::{Payee, PaymentAgreement, PaymentTerms};
+use crate::utils::validate_escrow_ata;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Arguments for claiming damages from an agreement's security deposit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ClaimDepositArgs {
+    /// Damages to claim in USDC microlamports
+    pub amount: u64,
+}
+
+/// Accounts required for claiming damages from a security deposit
+#[derive(Accounts)]
+pub struct ClaimDeposit<'info> {
+    /// Payment agreement holding the security deposit
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payment_agreement.payer.as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized,
+        constraint = !payment_agreement.active @ RecurringPaymentError::AlreadyActive
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    #[account(has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority @ RecurringPaymentError::Unauthorized,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Payee authority (must sign)
+    pub authority: Signer<'info>,
+
+    /// Program delegate's USDC ATA holding the security deposit
+    /// CHECK: Validated as the delegate's USDC ATA in handler
+    #[account(mut)]
+    pub escrow_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as payee treasury ATA in handler
+    #[account(mut)]
+    pub payee_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as USDC mint in handler
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// Program PDA that owns the escrow ATA
+    /// CHECK: PDA derived from program seeds
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for the payee claiming damages from a held security deposit
+///
+/// Transfers `amount` from the deposit held in the delegate's escrow ATA to the payee
+/// treasury, signed by the payee authority. Claims can only be filed once the agreement
+/// is no longer active, and until `close_agreement` returns the rest of the deposit to
+/// the payer, which it only does once the last paid period has ended. Claimed damages
+/// are not revenue and carry no platform fee. Frozen payees cannot claim.
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the payee authority, or the payee is frozen
+/// - The agreement is still active
+/// - The agreement holds no deposit, or the amount is zero or exceeds the deposit held
+/// - Escrow, treasury or mint accounts are invalid or use the wrong mint
+pub fn handler(ctx: Context<ClaimDeposit>, args: ClaimDepositArgs) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    let payee = &ctx.accounts.payee;
+
+    require!(args.amount > 0, RecurringPaymentError::InvalidAmount);
+    require!(
+        payment_agreement.deposit_held > 0,
+        RecurringPaymentError::NoDepositHeld
+    );
+    require!(
+        args.amount <= payment_agreement.deposit_held,
+        RecurringPaymentError::DepositClaimExceedsHeld
+    );
+
+    validate_escrow_ata(
+        &ctx.accounts.escrow_ata,
+        &ctx.accounts.program_delegate.key(),
+        &payee.usdc_mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    let payee_treasury_data: TokenAccount = TokenAccount::try_deserialize(
+        &mut ctx.accounts.payee_treasury_ata.data.borrow().as_ref(),
+    )
+    .map_err(|_| RecurringPaymentError::InvalidPayeeTreasuryAccount)?;
+
+    let usdc_mint_data: Mint =
+        Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+    if ctx.accounts.payee_treasury_ata.key() != payee.treasury_ata {
+        return Err(RecurringPaymentError::BadSeeds.into());
+    }
+
+    if ctx.accounts.usdc_mint.key() != payee.usdc_mint || payee_treasury_data.mint != payee.usdc_mint
+    {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    let delegate_bump = ctx.bumps.program_delegate;
+    let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+
+    let transfer_to_payee = TransferChecked {
+        from: ctx.accounts.escrow_ata.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.payee_treasury_ata.to_account_info(),
+        authority: ctx.accounts.program_delegate.to_account_info(),
+    };
+
+    token::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_to_payee,
+            delegate_seeds,
+        ),
+        args.amount,
+        usdc_mint_data.decimals,
+    )?;
+
+    payment_agreement.deposit_held = payment_agreement
+        .deposit_held
+        .checked_sub(args.amount)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    emit!(DepositClaimed {
+        payee: payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: payment_agreement.payer,
+        amount: args.amount,
+        remaining: payment_agreement.deposit_held,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_deposit_args_serialization() {
+        let args = ClaimDepositArgs { amount: 50_000_000 };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: ClaimDepositArgs = ClaimDepositArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.amount, 50_000_000);
+    }
+}
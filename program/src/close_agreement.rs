@@ -1,5 +1,6 @@
-use crate::{errors::RecurringPaymentError, events::*, state::*};
+use crate::{errors::RecurringPaymentError, events::*, state::*, utils::validate_escrow_ata};
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct CloseAgreementArgs {
@@ -13,6 +14,7 @@ pub struct CloseAgreement<'info> {
         seeds = [b"payment_agreement", payment_agreement.payment_terms.as_ref(), payer.key().as_ref()],
         bump = payment_agreement.bump,
         has_one = payer @ RecurringPaymentError::Unauthorized,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized,
         constraint = !payment_agreement.active @ RecurringPaymentError::AlreadyActive,
         close = payer
     )]
@@ -20,16 +22,125 @@ pub struct CloseAgreement<'info> {
 
     #[account(mut)]
     pub payer: Signer<'info>,
+
+    /// Payment terms of the agreement, used to locate the payee
+    #[account(has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Program delegate's USDC ATA holding the security deposit
+    /// CHECK: Only used, and validated in handler, when the agreement holds a deposit
+    #[account(mut)]
+    pub escrow_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Only used, and validated in handler, when the agreement holds a deposit
+    #[account(mut)]
+    pub payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Only used, and validated in handler, when the agreement holds a deposit
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// Program PDA that owns the escrow ATA
+    /// CHECK: PDA derived from program seeds
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
 }
 
+/// Handler for closing an inactive payment agreement and reclaiming its rent
+///
+/// A security deposit still held for the agreement is returned to the payer, minus
+/// any damages the payee claimed with `claim_deposit`. The deposit stays locked until
+/// the last paid period ends, so the payee has until then to file a claim.
+///
+/// # Errors
+/// Returns an error if:
+/// - The agreement is still active
+/// - A deposit is held and the last paid period hasn't ended
+/// - Escrow, payer or mint accounts are invalid or use the wrong mint
 pub fn handler(ctx: Context<CloseAgreement>, _args: CloseAgreementArgs) -> Result<()> {
     let payment_agreement = &ctx.accounts.payment_agreement;
+    let deposit_held = payment_agreement.deposit_held;
+
+    if deposit_held > 0 {
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            current_time >= payment_agreement.next_payment_ts,
+            RecurringPaymentError::DepositLocked
+        );
+
+        let payee = &ctx.accounts.payee;
+        validate_escrow_ata(
+            &ctx.accounts.escrow_ata,
+            &ctx.accounts.program_delegate.key(),
+            &payee.usdc_mint,
+            &ctx.accounts.token_program,
+        )?;
+
+        let payer_ata_data: TokenAccount =
+            TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
+                .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+        let usdc_mint_data: Mint =
+            Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+                .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+        if payer_ata_data.owner != ctx.accounts.payer.key() {
+            return Err(RecurringPaymentError::WrongOwner.into());
+        }
+
+        if ctx.accounts.usdc_mint.key() != payee.usdc_mint || payer_ata_data.mint != payee.usdc_mint
+        {
+            return Err(RecurringPaymentError::WrongMint.into());
+        }
+
+        let delegate_bump = ctx.bumps.program_delegate;
+        let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+
+        let transfer_to_payer = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.payer_usdc_ata.to_account_info(),
+            authority: ctx.accounts.program_delegate.to_account_info(),
+        };
+
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_payer,
+                delegate_seeds,
+            ),
+            deposit_held,
+            usdc_mint_data.decimals,
+        )?;
+
+        emit!(DepositReleased {
+            payee: payee.key(),
+            payment_terms: payment_agreement.payment_terms,
+            payer: ctx.accounts.payer.key(),
+            amount: deposit_held,
+            timestamp: current_time,
+        });
+    }
 
     // Emit PaymentAgreementClosed event before account is closed
     emit!(PaymentAgreementClosed {
         payment_terms: payment_agreement.payment_terms,
         payer: ctx.accounts.payer.key(),
     });
+    emit!(AgreementSnapshot {
+        status: AgreementStatus::Closed,
+        ..payment_agreement.snapshot(payment_agreement.key())
+    });
 
     // The `close` constraint in the Accounts struct will:
     // 1. Transfer all lamports (rent) to payer account
@@ -0,0 +1,89 @@
+use crate::{
+    constants::MAX_CLOSE_BATCH_AGREEMENTS, errors::RecurringPaymentError, events::*, state::*,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CloseAgreementsBatchArgs {
+    // No args needed; the agreements are passed as remaining accounts
+}
+
+#[derive(Accounts)]
+pub struct CloseAgreementsBatch<'info> {
+    /// Payer of every agreement in the batch; receives the reclaimed rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/// Handler for closing several inactive payment agreements and reclaiming their rent
+///
+/// The agreements are passed as writable remaining accounts, up to
+/// `MAX_CLOSE_BATCH_AGREEMENTS`. Each is checked and closed as `close_agreement`
+/// would, emitting the same events, except that agreements still holding a security
+/// deposit are rejected: those need the token accounts of `close_agreement` for the
+/// refund.
+///
+/// # Errors
+/// Returns an error if:
+/// - No agreements or more than `MAX_CLOSE_BATCH_AGREEMENTS` are passed
+/// - An account is not a payment agreement of this program at its PDA
+/// - An agreement belongs to another payer
+/// - An agreement is still active or holds a security deposit
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseAgreementsBatch<'info>>,
+    _args: CloseAgreementsBatchArgs,
+) -> Result<()> {
+    let agreement_infos = ctx.remaining_accounts;
+    require!(
+        !agreement_infos.is_empty() && agreement_infos.len() <= MAX_CLOSE_BATCH_AGREEMENTS,
+        RecurringPaymentError::InvalidBatchSize
+    );
+
+    let payer = &ctx.accounts.payer;
+    for agreement_info in agreement_infos {
+        let payment_agreement: Account<'info, PaymentAgreement> =
+            Account::try_from(agreement_info)?;
+
+        require!(
+            payment_agreement.payer == payer.key(),
+            RecurringPaymentError::Unauthorized
+        );
+        let expected_address = Pubkey::create_program_address(
+            &[
+                b"payment_agreement",
+                payment_agreement.payment_terms.as_ref(),
+                payer.key().as_ref(),
+                &[payment_agreement.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| RecurringPaymentError::PaymentAgreementNotFound)?;
+        require!(
+            agreement_info.key() == expected_address,
+            RecurringPaymentError::PaymentAgreementNotFound
+        );
+        require!(
+            !payment_agreement.active,
+            RecurringPaymentError::AlreadyActive
+        );
+        require!(
+            payment_agreement.deposit_held == 0,
+            RecurringPaymentError::DepositHeld
+        );
+
+        emit!(PaymentAgreementClosed {
+            payment_terms: payment_agreement.payment_terms,
+            payer: payer.key(),
+        });
+        emit!(AgreementSnapshot {
+            status: AgreementStatus::Closed,
+            ..payment_agreement.snapshot(payment_agreement.key())
+        });
+
+        // Returns the rent to the payer and hands the account back to the System
+        // Program, as the `close` constraint of `close_agreement` does
+        payment_agreement.close(payer.to_account_info())?;
+    }
+
+    Ok(())
+}
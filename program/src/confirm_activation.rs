@@ -0,0 +1,223 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::EscrowReleased;
+use crate::state::{Config, Payee, PaymentAgreement, PaymentTerms};
+use crate::utils::{
+    calculate_fee_split, record_payee_volume, validate_escrow_ata, validate_platform_treasury,
+    FeeSplit,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Arguments for confirming activation of an agreement with an escrowed first payment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfirmActivationArgs {
+    // No args needed for confirming activation
+}
+
+/// Accounts required for releasing an escrowed first payment
+#[derive(Accounts)]
+pub struct ConfirmActivation<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payment agreement whose first payment is held in escrow
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payment_agreement.payer.as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    /// Mutable so the released payment counts towards the terms' revenue
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    /// Mutable so the released payment counts towards the payee's volume and revenue
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority @ RecurringPaymentError::Unauthorized,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Payee authority (must sign)
+    pub authority: Signer<'info>,
+
+    /// Program delegate's USDC ATA holding the escrowed payment
+    /// CHECK: Validated as the delegate's USDC ATA in handler
+    #[account(mut)]
+    pub escrow_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as payee treasury ATA in handler
+    #[account(mut)]
+    pub payee_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as platform treasury ATA in handler
+    #[account(mut)]
+    pub platform_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as USDC mint in handler
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// Program PDA that owns the escrow ATA
+    /// CHECK: PDA derived from program seeds
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for releasing an escrowed first payment to the treasuries
+///
+/// Signed by the payee authority before the activation window lapses. The escrowed
+/// amount is split at the payee's current platform fee, exactly as `start_agreement`
+/// splits a payment that isn't escrowed, and the agreement continues renewing as usual.
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the payee authority, or the payee is frozen
+/// - No first payment is held in escrow or the activation window has lapsed
+/// - Escrow, treasury or mint accounts are invalid or use the wrong mint
+pub fn handler(ctx: Context<ConfirmActivation>, _args: ConfirmActivationArgs) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let payee = &ctx.accounts.payee;
+
+    let escrow = ctx
+        .accounts
+        .payment_agreement
+        .escrow
+        .ok_or(RecurringPaymentError::NoEscrowPending)?;
+    require!(
+        current_time < escrow.expires_ts,
+        RecurringPaymentError::EscrowExpired
+    );
+
+    validate_escrow_ata(
+        &ctx.accounts.escrow_ata,
+        &ctx.accounts.program_delegate.key(),
+        &payee.usdc_mint,
+        &ctx.accounts.token_program,
+    )?;
+    validate_platform_treasury(
+        &ctx.accounts.platform_treasury_ata,
+        &ctx.accounts.config.platform_authority,
+        &ctx.accounts.config.allowed_mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    let payee_treasury_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payee_treasury_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayeeTreasuryAccount)?;
+
+    let usdc_mint_data: Mint =
+        Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+    if ctx.accounts.payee_treasury_ata.key() != payee.treasury_ata {
+        return Err(RecurringPaymentError::BadSeeds.into());
+    }
+
+    if ctx.accounts.usdc_mint.key() != payee.usdc_mint
+        || payee_treasury_data.mint != payee.usdc_mint
+    {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    // The released payment counts towards the payee's volume, as renewals do
+    record_payee_volume(&mut ctx.accounts.payee, escrow.amount, current_time);
+    let FeeSplit {
+        platform_fee,
+        payee_amount,
+        ..
+    } = calculate_fee_split(
+        escrow.amount,
+        0,
+        ctx.accounts.payee.platform_fee_bps(current_time),
+    )?;
+
+    let delegate_bump = ctx.bumps.program_delegate;
+    let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+
+    if payee_amount > 0 {
+        let transfer_to_payee = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.payee_treasury_ata.to_account_info(),
+            authority: ctx.accounts.program_delegate.to_account_info(),
+        };
+
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_payee,
+                delegate_seeds,
+            ),
+            payee_amount,
+            usdc_mint_data.decimals,
+        )?;
+    }
+
+    if platform_fee > 0 {
+        let transfer_to_platform = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.platform_treasury_ata.to_account_info(),
+            authority: ctx.accounts.program_delegate.to_account_info(),
+        };
+
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_platform,
+                delegate_seeds,
+            ),
+            platform_fee,
+            usdc_mint_data.decimals,
+        )?;
+    }
+
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    payment_agreement.escrow = None;
+    ctx.accounts.payment_terms.record_payment(escrow.amount, false);
+    ctx.accounts.payee.record_payment(escrow.amount, false);
+
+    emit!(EscrowReleased {
+        payee: ctx.accounts.payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: payment_agreement.payer,
+        amount: escrow.amount,
+        platform_fee,
+        payee_amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_activation_args_serialization() {
+        let args = ConfirmActivationArgs {};
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: ConfirmActivationArgs =
+            ConfirmActivationArgs::try_from_slice(&serialized).unwrap();
+
+        // ConfirmActivationArgs has no fields, so just verify it deserializes successfully
+        let _ = deserialized;
+    }
+}
@@ -213,8 +213,91 @@ pub const SCALE_TIER_THRESHOLD_USDC: u64 = 100_000_000_000;
 
 /// Rolling window period for volume calculations (in seconds)
 ///
-/// Volume is tracked over a 30-day rolling window. After this period without
-/// payments, volume resets and tier returns to Standard.
+/// Volume is tracked in 30-day windows. When a window ends, volume resets and the
+/// tier is re-evaluated against the completed window's volume, so payees whose
+/// volume drops are demoted. After a full window without payments, the tier
+/// returns to Standard.
 ///
 /// # Value: 2,592,000 seconds = 30 days
 pub const VOLUME_WINDOW_SECONDS: i64 = 2_592_000;
+
+/// Maximum discount a token gate can grant on payment terms (in basis points)
+///
+/// Payees may attach a gate mint to their payment terms so that payers holding
+/// that token receive a discount. The discount is capped so that gated pricing
+/// can never reduce a payment to zero.
+///
+/// # Value: 5,000 basis points = 50%
+pub const MAX_GATE_DISCOUNT_BPS: u16 = 5_000;
+
+/// Maximum number of keepers a payee can authorize for permissioned execution
+///
+/// Bounds the `authorized_keepers` list stored on each `Payee` account, which
+/// determines the account's fixed size. A handful of keys covers a payee's own
+/// bots plus failover.
+///
+/// # Value: 5 keepers
+pub const MAX_AUTHORIZED_KEEPERS: usize = 5;
+
+/// Width of a renewal queue time bucket (in seconds)
+///
+/// Agreements are indexed in the `RenewalQueue` whose bucket contains their next
+/// payment timestamp, so a keeper reads one account per bucket to find due work.
+///
+/// # Value: 86,400 seconds = 1 day
+pub const RENEWAL_BUCKET_SECS: i64 = 86_400;
+
+/// Maximum clock-skew tolerance for renewals (in seconds)
+///
+/// `execute_payment` accepts a renewal up to `Config::renewal_tolerance_secs` before
+/// `next_payment_ts`, so keepers submitting exactly at the due time are not rejected
+/// when the cluster clock lags wall time. The ceiling keeps a misconfigured tolerance
+/// from letting renewals be charged meaningfully early.
+///
+/// # Value: 3,600 seconds = 1 hour
+pub const MAX_RENEWAL_TOLERANCE_SECS: u64 = 3_600;
+
+/// Number of `RenewalQueue` shards per time bucket
+///
+/// Each agreement is indexed in the shard selected by the first byte of its address,
+/// so a bucket holds up to `RENEWAL_QUEUE_SHARDS * MAX_RENEWAL_QUEUE_ENTRIES`
+/// agreements before any shard overflows. A power of two keeps agreement addresses
+/// spread evenly across shards, and a keeper still reads every shard of a bucket in
+/// one `getMultipleAccounts` call.
+///
+/// # Value: 64 shards (4,096 agreements per day)
+pub const RENEWAL_QUEUE_SHARDS: u8 = 64;
+
+/// Maximum number of agreements indexed by a single renewal queue shard
+///
+/// Bounds the `agreements` list stored on each `RenewalQueue` account, which
+/// determines the account's fixed size. Agreements that do not fit are counted in
+/// `overflow_count` so keepers know to fall back to a program scan for that bucket.
+///
+/// # Value: 64 agreements
+pub const MAX_RENEWAL_QUEUE_ENTRIES: usize = 64;
+
+/// Maximum number of agreements closed by a single `close_agreements_batch` call
+///
+/// Each agreement is passed as a remaining account, so the limit keeps a full batch
+/// within the transaction size and compute limits with room for a compute budget
+/// instruction.
+///
+/// # Value: 20 agreements
+pub const MAX_CLOSE_BATCH_AGREEMENTS: usize = 20;
+
+/// USDC micro-units per whole USDC (6 decimals)
+///
+/// Keeper SOL rates are set in lamports per whole USDC, so a USDC keeper fee in
+/// micro-units is converted with `fee * lamports_per_usdc / USDC_UNITS`.
+///
+/// # Value: 1,000,000 micro-units
+pub const USDC_UNITS: u64 = 1_000_000;
+
+/// Maximum age of the keeper SOL rate in seconds
+///
+/// Keeper fees are only paid in SOL at a rate the platform authority set within this
+/// window, so a rate the authority stopped updating cannot drift far from the market.
+///
+/// # Value: 86,400 seconds (1 day)
+pub const MAX_KEEPER_SOL_RATE_AGE_SECS: i64 = 86_400;
@@ -1,6 +1,6 @@
-use crate::constants::MAX_PLAN_PRICE_USDC;
+use crate::constants::{MAX_GATE_DISCOUNT_BPS, MAX_PLAN_PRICE_USDC};
 use crate::errors::RecurringPaymentError;
-use crate::state::{Payee, PaymentTerms};
+use crate::state::{Payee, PaymentTerms, VersionedAccount};
 use anchor_lang::prelude::*;
 
 /// Arguments for creating payment terms.
@@ -51,6 +51,12 @@ pub struct CreatePaymentTermsArgs {
     pub terms_id_bytes: [u8; 32], // Padded terms_id bytes for PDA seeds (must match SDK calculation)
     pub amount_usdc: u64,         // Amount in USDC microlamports
     pub period_secs: u64,         // Payment period in seconds
+    pub gate_mint: Option<Pubkey>, // Optional token gate mint for discounted pricing
+    pub gate_discount_bps: u16,   // Discount for gate holders (must be 0 without a gate mint)
+    pub max_subscribers: Option<u32>, // Optional cap on active agreements (must be > 0 when set)
+    pub escrow_window_secs: Option<u64>, // Optional first-payment escrow window (0 < window <= period)
+    pub deposit_usdc: Option<u64>, // Optional refundable security deposit (0 < deposit <= MAX_PLAN_PRICE_USDC)
+    pub accrual_rate_per_sec: Option<u64>, // Optional streaming mode (rate * period_secs == amount_usdc)
 }
 
 #[derive(Accounts)]
@@ -60,7 +66,7 @@ pub struct CreatePaymentTerms<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = !config.paused @ RecurringPaymentError::Inactive
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
     )]
     pub config: Account<'info, crate::state::Config>,
 
@@ -74,7 +80,7 @@ pub struct CreatePaymentTerms<'info> {
     pub payment_terms: Account<'info, PaymentTerms>,
 
     #[account(
-        seeds = [b"payee", authority.key().as_ref()],
+        seeds = [b"payee", payee.original_authority.as_ref()],
         bump = payee.bump,
         has_one = authority
     )]
@@ -147,11 +153,75 @@ pub fn handler(ctx: Context<CreatePaymentTerms>, args: CreatePaymentTermsArgs) -
         RecurringPaymentError::InvalidPaymentTerms
     );
 
+    // Validate token gate configuration
+    //
+    // A gate mint requires a non-zero discount capped at MAX_GATE_DISCOUNT_BPS, and a
+    // discount without a gate mint is rejected so terms never carry dead configuration.
+    match args.gate_mint {
+        Some(_) => require!(
+            args.gate_discount_bps > 0 && args.gate_discount_bps <= MAX_GATE_DISCOUNT_BPS,
+            RecurringPaymentError::InvalidPaymentTerms
+        ),
+        None => require!(
+            args.gate_discount_bps == 0,
+            RecurringPaymentError::InvalidPaymentTerms
+        ),
+    }
+
+    // Validate subscriber cap: a cap of zero could never accept a payer
+    require!(
+        args.max_subscribers != Some(0),
+        RecurringPaymentError::InvalidPaymentTerms
+    );
+
+    // Validate escrow window: the first payment must be released or refunded before
+    // the second one falls due
+    if let Some(escrow_window_secs) = args.escrow_window_secs {
+        require!(
+            escrow_window_secs > 0 && escrow_window_secs <= args.period_secs,
+            RecurringPaymentError::InvalidPaymentTerms
+        );
+    }
+
+    // Validate security deposit: bounded like the price itself
+    if let Some(deposit_usdc) = args.deposit_usdc {
+        require!(
+            deposit_usdc > 0 && deposit_usdc <= MAX_PLAN_PRICE_USDC,
+            RecurringPaymentError::InvalidPaymentTerms
+        );
+    }
+
+    // Validate streaming mode: the first period is charged up front at `amount_usdc`,
+    // so it must price `period_secs` exactly at the accrual rate. Gate discounts and
+    // escrowed first payments apply to period billing only.
+    if let Some(accrual_rate_per_sec) = args.accrual_rate_per_sec {
+        require!(
+            accrual_rate_per_sec > 0
+                && accrual_rate_per_sec.checked_mul(args.period_secs) == Some(args.amount_usdc)
+                && args.gate_mint.is_none()
+                && args.escrow_window_secs.is_none(),
+            RecurringPaymentError::InvalidPaymentTerms
+        );
+    }
+
     let payment_terms = &mut ctx.accounts.payment_terms;
     payment_terms.payee = ctx.accounts.payee.key();
     payment_terms.terms_id = args.terms_id_bytes;
     payment_terms.amount_usdc = args.amount_usdc;
     payment_terms.period_secs = args.period_secs;
+    payment_terms.gate_mint = args.gate_mint;
+    payment_terms.gate_discount_bps = args.gate_discount_bps;
+    payment_terms.pending_update = None;
+    payment_terms.max_subscribers = args.max_subscribers;
+    payment_terms.active_agreements = 0;
+    payment_terms.waitlist_len = 0;
+    payment_terms.sunset_ts = None;
+    payment_terms.escrow_window_secs = args.escrow_window_secs;
+    payment_terms.lifetime_revenue_usdc = 0;
+    payment_terms.lifetime_renewals = 0;
+    payment_terms.deposit_usdc = args.deposit_usdc;
+    payment_terms.version = PaymentTerms::VERSION;
+    payment_terms.accrual_rate_per_sec = args.accrual_rate_per_sec;
 
     // Get current timestamp for event
     let clock = Clock::get()?;
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::RecurringPaymentError,
+    events::PaymentTermsDeactivated,
+    state::{Config, Payee, PaymentTerms},
+};
+
+/// Arguments for deactivating payment terms
+///
+/// Deactivation is final: the terms stop accepting agreements immediately, and
+/// existing agreements run until their first payment due at or after `sunset_ts`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct DeactivatePaymentTermsArgs {
+    pub sunset_ts: i64, // Unix timestamp from which agreements stop renewing
+}
+
+#[derive(Accounts)]
+pub struct DeactivatePaymentTerms<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_terms", payee.key().as_ref(), payment_terms.terms_id.as_ref()],
+        bump,
+        has_one = payee @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority
+    )]
+    pub payee: Account<'info, Payee>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<DeactivatePaymentTerms>,
+    args: DeactivatePaymentTermsArgs,
+) -> Result<()> {
+    let payment_terms = &mut ctx.accounts.payment_terms;
+
+    require!(
+        !payment_terms.is_deactivated(),
+        RecurringPaymentError::TermsDeactivated
+    );
+
+    // Consumer protection: with active agreements, the sunset must leave at least one
+    // full period of notice, so every payer's paid period is honored and each is told
+    // before its agreement stops renewing. Terms without active agreements can be
+    // deactivated immediately.
+    let clock = Clock::get()?;
+    if payment_terms.active_agreements > 0 {
+        let period_i64 = i64::try_from(payment_terms.period_secs)
+            .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+        let earliest_sunset_ts = clock
+            .unix_timestamp
+            .checked_add(period_i64)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        require!(
+            args.sunset_ts >= earliest_sunset_ts,
+            RecurringPaymentError::InsufficientNoticePeriod
+        );
+    }
+
+    payment_terms.sunset_ts = Some(args.sunset_ts);
+
+    emit!(PaymentTermsDeactivated {
+        payment_terms: payment_terms.key(),
+        payee: ctx.accounts.payee.key(),
+        sunset_ts: args.sunset_ts,
+        active_agreements: payment_terms.active_agreements,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deactivate_payment_terms_args_serialization() {
+        let args = DeactivatePaymentTermsArgs {
+            sunset_ts: 1_702_592_000,
+        };
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = DeactivatePaymentTermsArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.sunset_ts, 1_702_592_000);
+    }
+}
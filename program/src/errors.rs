@@ -145,4 +145,191 @@ pub enum RecurringPaymentError {
     /// When global configuration parameters are invalid or inconsistent
     #[msg("Invalid configuration parameters. Ensure min/max fee bounds are consistent and all values are within acceptable ranges.")]
     InvalidConfiguration,
+
+    /// Error Code: 6027
+    /// When the gate token account supplied for token-gated pricing is invalid
+    #[msg("Invalid gate token account. The account must be an SPL token account owned by the payer for the payment terms' gate mint.")]
+    InvalidGateTokenAccount,
+
+    /// Error Code: 6028
+    /// When a scheduled terms update does not give payers at least one full period of notice
+    #[msg("Insufficient notice period. Scheduled terms updates must take effect at least one full payment period in the future.")]
+    InsufficientNoticePeriod,
+
+    /// Error Code: 6029
+    /// When the payee has been frozen by the platform authority
+    #[msg("Payee is frozen. The platform authority has suspended agreements and payments for this payee.")]
+    PayeeFrozen,
+
+    /// Error Code: 6030
+    /// When a pull would exceed the per-period cap (a second pull for the same billing
+    /// period)
+    #[msg("Per-period pull cap exceeded. At most one pull is allowed per billing period.")]
+    PeriodPullCapExceeded,
+
+    /// Error Code: 6031
+    /// When payment terms have reached their `max_subscribers` cap
+    #[msg("Payment terms are full. The maximum number of active subscribers has been reached; reserve a waitlist slot instead.")]
+    TermsFull,
+
+    /// Error Code: 6032
+    /// When a refund would return more than the last charged amount
+    #[msg("Refund exceeds last payment. Total refunds for a billing period cannot exceed the amount last charged to the payer.")]
+    RefundExceedsLastPayment,
+
+    /// Error Code: 6033
+    /// When a keeper policy lists more than `MAX_AUTHORIZED_KEEPERS` keepers
+    #[msg("Too many authorized keepers. A payee can authorize at most 5 keepers.")]
+    TooManyKeepers,
+
+    /// Error Code: 6034
+    /// When a keeper not on the payee's allow-list attempts permissioned execution
+    #[msg("Unauthorized keeper. This payee only accepts payment execution from its authorized keepers.")]
+    UnauthorizedKeeper,
+
+    /// Error Code: 6035
+    /// When the renewal queue passed to an instruction does not cover the agreement's next payment
+    #[msg("Invalid renewal bucket. The renewal queue must match the bucket of the agreement's next payment.")]
+    InvalidRenewalBucket,
+
+    /// Error Code: 6036
+    /// When an agreement is started with a billing period limit below two
+    #[msg("Invalid max periods. A limited agreement must cover at least 2 billing periods.")]
+    InvalidMaxPeriods,
+
+    /// Error Code: 6037
+    /// When a payment is attempted after every billing period of a limited agreement was charged
+    #[msg("Max periods reached. This agreement has already charged all of its billing periods.")]
+    MaxPeriodsReached,
+
+    /// Error Code: 6038
+    /// When a paused agreement holding pause credit is reactivated through `start_agreement`
+    #[msg("Resume required. This agreement holds pause credit and must be resumed with resume_agreement.")]
+    ResumeRequired,
+
+    /// Error Code: 6039
+    /// When `resume_agreement` is called for an agreement the payer did not pause
+    #[msg("Agreement not paused. Only agreements paused by the payer can be resumed.")]
+    NotPaused,
+
+    /// Error Code: 6040
+    /// When an agreement is started or resumed on payment terms the payee deactivated
+    #[msg("Payment terms deactivated. These payment terms no longer accept agreements.")]
+    TermsDeactivated,
+
+    /// Error Code: 6041
+    /// When an agreement is charged, paused, transferred or refunded while its first payment is held in escrow
+    #[msg("Escrow pending. The first payment is held in escrow until the payee confirms activation or the window lapses.")]
+    EscrowPending,
+
+    /// Error Code: 6042
+    /// When activation is confirmed or an escrow refunded for an agreement with nothing in escrow
+    #[msg("No escrow pending. This agreement has no first payment held in escrow.")]
+    NoEscrowPending,
+
+    /// Error Code: 6043
+    /// When the payee confirms activation after the escrow window lapsed
+    #[msg("Escrow expired. The activation window has lapsed; the escrowed payment can only be refunded.")]
+    EscrowExpired,
+
+    /// Error Code: 6044
+    /// When an escrowed payment is refunded before the activation window lapsed
+    #[msg("Escrow not expired. The escrowed payment can only be refunded once the activation window lapses.")]
+    EscrowNotExpired,
+
+    /// Error Code: 6045
+    /// When the escrow token account is not the program delegate's USDC token account
+    #[msg("Invalid escrow account. Ensure the account is the program delegate's USDC associated token account.")]
+    InvalidEscrowAccount,
+
+    /// Error Code: 6046
+    /// When a keeper asks for its fee in SOL while SOL keeper fees are disabled or the platform's rate is stale
+    #[msg("Keeper SOL rate unavailable. SOL keeper fees are disabled or the platform's rate is out of date; take the fee in USDC instead.")]
+    KeeperSolRateUnavailable,
+
+    /// Error Code: 6047
+    /// When the keeper fee vault cannot cover a SOL keeper fee and stay rent exempt
+    #[msg("Keeper fee vault underfunded. The vault cannot cover this SOL keeper fee; take the fee in USDC instead.")]
+    KeeperFeeVaultUnderfunded,
+
+    /// Error Code: 6048
+    /// When the payer token account is not owned by the agreement's payer
+    #[msg("Wrong token account owner. The payer token account must be owned by the agreement's payer.")]
+    WrongOwner,
+
+    /// Error Code: 6049
+    /// When `resume_after_unfreeze` is called on an agreement that is not suspended
+    #[msg("Agreement not suspended. Only agreements suspended for a frozen token account can be resumed after unfreeze.")]
+    NotSuspended,
+
+    /// Error Code: 6050
+    /// When a suspended agreement is resumed while the payer token account is still frozen
+    #[msg("Payer token account frozen. The account must be thawed by the mint's freeze authority first.")]
+    PayerAccountFrozen,
+
+    /// Error Code: 6051
+    /// When `claim_deposit` is called on an agreement holding no security deposit
+    #[msg("No deposit held. This agreement holds no security deposit to claim from.")]
+    NoDepositHeld,
+
+    /// Error Code: 6052
+    /// When an agreement holding a deposit is closed before its last paid period ends
+    #[msg("Deposit locked. The security deposit is refunded once the last paid period ends.")]
+    DepositLocked,
+
+    /// Error Code: 6053
+    /// When a damages claim exceeds the security deposit still held
+    #[msg("Deposit claim exceeds the security deposit still held for this agreement.")]
+    DepositClaimExceedsHeld,
+
+    /// Error Code: 6054
+    /// When a payment's keeper, platform and payee shares do not sum to the amount charged
+    #[msg("Fee split mismatch. The keeper fee, platform fee and payee amount must sum to the amount charged.")]
+    FeeSplitMismatch,
+
+    /// Error Code: 6055
+    /// When `close_agreements_batch` is given an agreement still holding a security deposit
+    #[msg("Deposit held. Close agreements holding a security deposit with close_agreement so the deposit is refunded.")]
+    DepositHeld,
+
+    /// Error Code: 6056
+    /// When `close_agreements_batch` is given no agreements or more than the batch limit
+    #[msg("Invalid batch size. Pass between 1 and MAX_CLOSE_BATCH_AGREEMENTS agreements.")]
+    InvalidBatchSize,
+
+    /// Error Code: 6057
+    /// When `migrate_account` is called on an account already at the current layout version
+    #[msg("Already migrated. The account already uses the current layout version.")]
+    AlreadyMigrated,
+
+    /// Error Code: 6058
+    /// When `migrate_account` is called on an account type without a versioned layout
+    #[msg("Not migratable. Only Config, Payee, PaymentTerms and PaymentAgreement accounts can be migrated.")]
+    NotMigratable,
+
+    /// Error Code: 6059
+    /// When an instruction for period billing is used on streaming payment terms
+    #[msg("Streaming terms. Agreements on streaming payment terms are charged with sweep_accrued and end by pausing.")]
+    StreamingTerms,
+
+    /// Error Code: 6060
+    /// When `sweep_accrued` is called for an agreement on non-streaming payment terms
+    #[msg("Not streaming terms. Only agreements on payment terms with an accrual rate can be swept.")]
+    NotStreamingTerms,
+
+    /// Error Code: 6061
+    /// When `sweep_accrued` finds no accrual the allowance covers
+    #[msg("Nothing to sweep. No accrual is due, or the allowance doesn't cover a second of it.")]
+    NothingToSweep,
+
+    /// Error Code: 6062
+    /// When `execute_payment` is called for token-gated payment terms without the
+    /// payer's gate token account
+    #[msg("Gate token account required. Pass the payer's associated token account for the payment terms' gate mint.")]
+    GateTokenAccountRequired,
+
+    /// Error Code: 6063
+    /// When a keeper asks for its fee in SOL without passing the keeper fee vault
+    #[msg("Keeper fee vault required. Pass the keeper fee vault to take the keeper fee in SOL.")]
+    KeeperFeeVaultRequired,
 }
@@ -11,6 +11,11 @@ pub struct PaymentAgreementStarted {
     pub payer: Pubkey,
     /// The amount paid for the payment agreement (in USDC micro-units)
     pub amount: u64,
+    /// Salted hash of the payee's internal customer reference, if supplied
+    pub external_ref_hash: Option<[u8; 32]>,
+    /// Delegate allowance the payer approved (in USDC micro-units), at least
+    /// `config.default_allowance_periods` times the payment terms price
+    pub delegated_amount: u64,
 }
 
 /// Event emitted when a previously paused payment agreement is reactivated
@@ -28,6 +33,11 @@ pub struct PaymentAgreementReactivated {
     pub total_payments: u32,
     /// Original payment agreement creation timestamp (preserved from first session)
     pub original_created_ts: i64,
+    /// Salted hash of the payee's internal customer reference, if supplied
+    pub external_ref_hash: Option<[u8; 32]>,
+    /// Delegate allowance the payer approved (in USDC micro-units), at least
+    /// `config.default_allowance_periods` times the payment terms price
+    pub delegated_amount: u64,
 }
 
 /// Event emitted when a recurring payment is successfully executed
@@ -45,6 +55,8 @@ pub struct PaymentExecuted {
     pub keeper: Pubkey,
     /// The fee paid to the keeper (in USDC micro-units)
     pub keeper_fee: u64,
+    /// The agreement's renewal counter after this payment (`PaymentAgreement::period_index`)
+    pub period_index: u64,
 }
 
 /// Event emitted when a payment agreement is paused
@@ -58,6 +70,47 @@ pub struct PaymentAgreementPaused {
     pub payer: Pubkey,
 }
 
+/// Event emitted when a payer schedules cancellation at the end of the current period
+#[event]
+pub struct CancellationScheduled {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer's public key
+    pub payer: Pubkey,
+    /// Unix timestamp at which the agreement stops renewing (end of the paid period)
+    pub effective_ts: i64,
+}
+
+/// Event emitted when an agreement with a scheduled cancellation is paused at period end
+#[event]
+pub struct CanceledAtPeriodEnd {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer's public key
+    pub payer: Pubkey,
+    /// Unix timestamp when the agreement was paused
+    pub timestamp: i64,
+}
+
+/// Event emitted when a limited agreement charges its final billing period and completes
+#[event]
+pub struct AgreementCompleted {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer's public key
+    pub payer: Pubkey,
+    /// Number of billing periods charged, equal to the agreement's `max_periods`
+    pub periods_paid: u16,
+    /// Unix timestamp of the final payment
+    pub timestamp: i64,
+}
+
 /// Event emitted when a payment agreement account is closed and rent is reclaimed
 #[event]
 pub struct PaymentAgreementClosed {
@@ -159,6 +212,10 @@ pub struct ProgramPaused {
     pub authority: Pubkey,
     /// Unix timestamp when program was paused
     pub timestamp: i64,
+    /// Reason for the pause as zero-padded UTF-8
+    pub reason: [u8; 64],
+    /// Unix timestamp at which the pause lapses automatically, if scheduled
+    pub auto_unpause_ts: Option<i64>,
 }
 
 /// Event emitted when the program is unpaused
@@ -170,6 +227,62 @@ pub struct ProgramUnpaused {
     pub timestamp: i64,
 }
 
+/// Event emitted when the platform authority freezes a payee
+#[event]
+pub struct PayeeFrozen {
+    /// The frozen payee account
+    pub payee: Pubkey,
+    /// Platform authority who froze the payee
+    pub authority: Pubkey,
+    /// Unix timestamp when the payee was frozen
+    pub timestamp: i64,
+}
+
+/// Event emitted when the platform authority unfreezes a payee
+#[event]
+pub struct PayeeUnfrozen {
+    /// The unfrozen payee account
+    pub payee: Pubkey,
+    /// Platform authority who unfroze the payee
+    pub authority: Pubkey,
+    /// Unix timestamp when the payee was unfrozen
+    pub timestamp: i64,
+}
+
+/// Event emitted when the platform authority sets or ends a payee's fee holiday
+#[event]
+pub struct FeeHolidaySet {
+    /// The payee account
+    pub payee: Pubkey,
+    /// Platform authority who set the fee holiday
+    pub authority: Pubkey,
+    /// Unix timestamp at which the discount ends
+    pub until_ts: i64,
+    /// Basis points subtracted from the tier fee (zero when the holiday was ended)
+    pub rebate_bps: u16,
+    /// Unix timestamp when the fee holiday was set
+    pub timestamp: i64,
+}
+
+/// Event emitted when a payment is charged a platform fee reduced by a fee holiday
+#[event]
+pub struct FeeHolidayApplied {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The payment terms account
+    pub payment_terms: Pubkey,
+    /// The payer whose payment was executed
+    pub payer: Pubkey,
+    /// Platform fee of the payee's volume tier in basis points
+    pub tier_fee_bps: u16,
+    /// Platform fee actually charged in basis points
+    pub platform_fee_bps: u16,
+    /// Platform fee charged in USDC microlamports
+    pub platform_fee: u64,
+    /// Unix timestamp at which the fee holiday ends
+    pub until_ts: i64,
+}
+
 /// Event emitted when a payment execution succeeds but remaining allowance is low
 ///
 /// This warning event alerts off-chain systems and users when the delegate allowance
@@ -279,6 +392,8 @@ pub struct ConfigUpdated {
     pub min_platform_fee_bps: u16,
     /// Maximum platform fee in basis points
     pub max_platform_fee_bps: u16,
+    /// Seconds before `next_payment_ts` from which renewals are accepted
+    pub renewal_tolerance_secs: u64,
     /// Platform authority who made the update
     pub updated_by: Pubkey,
 }
@@ -288,7 +403,8 @@ pub struct ConfigUpdated {
 /// Volume tiers upgrade automatically based on 30-day rolling payment volume.
 /// This event provides transparency and auditability for tier changes.
 ///
-/// Tier upgrades immediately affect the platform fee rate applied to future payments:
+/// Tier upgrades immediately affect the platform fee rate, starting with the payment
+/// that triggered them:
 /// - Standard → Growth: $10K monthly volume reached (2.5% → 2.0% fee)
 /// - Growth → Scale: $100K monthly volume reached (2.0% → 1.5% fee)
 ///
@@ -311,6 +427,25 @@ pub struct VolumeTierUpgraded {
     pub new_platform_fee_bps: u16,
 }
 
+/// Event emitted when a payee's volume tier is downgraded
+///
+/// Tiers are re-evaluated when a 30-day volume window ends. Payees whose completed
+/// window's volume falls below their tier's threshold move down to the tier that
+/// volume qualifies for.
+#[event]
+pub struct VolumeTierDowngraded {
+    /// The payee account whose tier downgraded
+    pub payee: Pubkey,
+    /// The previous tier before the downgrade
+    pub old_tier: crate::state::VolumeTier,
+    /// The new tier after the downgrade
+    pub new_tier: crate::state::VolumeTier,
+    /// Volume of the new 30-day window, including the payment that triggered the downgrade
+    pub monthly_volume_usdc: u64,
+    /// The new platform fee in basis points corresponding to the new tier
+    pub new_platform_fee_bps: u16,
+}
+
 /// Event emitted when payment terms' pricing or period are updated
 ///
 /// This event provides transparency for all payment term modifications made by payee authority.
@@ -338,3 +473,433 @@ pub struct PaymentTermsUpdated {
     pub updated_by: Pubkey,
 }
 
+/// Event emitted when token-gated pricing discounts a payment
+///
+/// Emitted by `start_agreement` and `execute_payment` when the payment terms
+/// reference a gate mint and the payer holds at least one token of that mint.
+#[event]
+pub struct GateDiscountApplied {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms granting the discount
+    pub payment_terms: Pubkey,
+    /// The payer receiving the discount
+    pub payer: Pubkey,
+    /// The gate mint held by the payer
+    pub gate_mint: Pubkey,
+    /// Discount applied in basis points
+    pub discount_bps: u16,
+    /// Undiscounted payment amount (in USDC micro-units)
+    pub original_amount: u64,
+    /// Amount actually charged after the discount (in USDC micro-units)
+    pub discounted_amount: u64,
+}
+
+/// Event emitted when a payee schedules a future price or period change
+///
+/// The change takes effect at `effective_ts`, which is at least one full payment
+/// period in the future so payers receive notice before being charged new terms.
+#[event]
+pub struct PaymentTermsUpdateScheduled {
+    /// The payment terms account with the scheduled change
+    pub payment_terms: Pubkey,
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// Current payment amount (in USDC micro-units)
+    pub current_amount: u64,
+    /// Scheduled payment amount (in USDC micro-units)
+    pub new_amount: u64,
+    /// Current payment period in seconds
+    pub current_period: u64,
+    /// Scheduled payment period in seconds
+    pub new_period: u64,
+    /// Unix timestamp from which the new terms apply
+    pub effective_ts: i64,
+}
+
+/// Event emitted on each payment while a scheduled terms change is pending
+///
+/// Gives payers on-chain notice of upcoming price or period changes. The payment
+/// that emits this event was charged at the current terms.
+#[event]
+pub struct PendingTermsChangeNotice {
+    /// The payment terms account with the pending change
+    pub payment_terms: Pubkey,
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payer receiving notice
+    pub payer: Pubkey,
+    /// Current payment amount (in USDC micro-units)
+    pub current_amount: u64,
+    /// Payment amount once the change takes effect (in USDC micro-units)
+    pub new_amount: u64,
+    /// Current payment period in seconds
+    pub current_period: u64,
+    /// Payment period once the change takes effect
+    pub new_period: u64,
+    /// Unix timestamp from which the new terms apply
+    pub effective_ts: i64,
+}
+
+/// Event emitted when a payer reserves a waitlist slot on capped payment terms
+#[event]
+pub struct SlotReserved {
+    /// The payment terms account being waitlisted
+    pub payment_terms: Pubkey,
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payer holding the reservation
+    pub payer: Pubkey,
+    /// Zero-based waitlist position
+    pub position: u32,
+    /// Unix timestamp of the reservation
+    pub timestamp: i64,
+}
+
+/// Event emitted when a payer proposes moving an agreement to another wallet
+#[event]
+pub struct AgreementTransferInitiated {
+    /// The payee receiving payments under the agreement
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The current payer
+    pub payer: Pubkey,
+    /// The wallet that must accept the transfer
+    pub new_payer: Pubkey,
+}
+
+/// Event emitted when a new wallet accepts an agreement transfer
+///
+/// The agreement now lives at the new payer's PDA; the old PDA is closed.
+#[event]
+pub struct AgreementTransferred {
+    /// The payee receiving payments under the agreement
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The previous payer
+    pub old_payer: Pubkey,
+    /// The payer from now on
+    pub new_payer: Pubkey,
+    /// Payments executed so far (preserved across the transfer)
+    pub payment_count: u32,
+    /// Original agreement creation timestamp (preserved across the transfer)
+    pub original_created_ts: i64,
+}
+
+/// Event emitted when a payee refunds part or all of the last payment to the payer
+#[event]
+pub struct Refunded {
+    /// The payee issuing the refund
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer receiving the refund
+    pub payer: Pubkey,
+    /// Amount refunded in USDC microlamports
+    pub amount: u64,
+    /// Period index of the refunded payment, as carried in its `PaymentExecuted`
+    pub period_index: u64,
+    /// Total refunded against the last payment, including this refund
+    pub total_refunded: u64,
+}
+
+/// Event emitted when a payee changes which keepers may execute its payments
+#[event]
+pub struct KeeperPolicyUpdated {
+    /// The payee account
+    pub payee: Pubkey,
+    /// Whether any keeper may execute payments
+    pub open_execution: bool,
+    /// Keepers allowed to execute payments when execution is permissioned
+    pub authorized_keepers: Vec<Pubkey>,
+    /// Unix timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when a payee authority proposes handing the payee to a new key
+#[event]
+pub struct PayeeAuthorityTransferInitiated {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The current payee authority
+    pub authority: Pubkey,
+    /// The authority that must accept the transfer
+    pub new_authority: Pubkey,
+    /// Unix timestamp when the transfer was initiated
+    pub timestamp: i64,
+}
+
+/// Event emitted when a new authority accepts a payee authority transfer
+///
+/// The payee PDA is unchanged; its treasury moves to the new authority's ATA.
+#[event]
+pub struct PayeeAuthorityTransferred {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The previous payee authority
+    pub old_authority: Pubkey,
+    /// The payee authority from now on
+    pub new_authority: Pubkey,
+    /// The new treasury ATA receiving payments
+    pub treasury_ata: Pubkey,
+    /// Unix timestamp when the transfer completed
+    pub timestamp: i64,
+}
+
+/// Event emitted when a payee authority cancels a pending authority transfer
+#[event]
+pub struct PayeeAuthorityTransferCancelled {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The payee authority who cancelled the transfer
+    pub authority: Pubkey,
+    /// The authority whose pending transfer was revoked
+    pub cancelled_authority: Pubkey,
+    /// Unix timestamp when the transfer was cancelled
+    pub timestamp: i64,
+}
+
+/// Event emitted when `resume_agreement` applies pause credit to the first charge after a pause
+#[event]
+pub struct CreditApplied {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms being resumed
+    pub payment_terms: Pubkey,
+    /// The payer's public key
+    pub payer: Pubkey,
+    /// Price of the billing period before credit (in USDC micro-units)
+    pub amount: u64,
+    /// Credit applied against the charge (in USDC micro-units)
+    pub credit_applied: u64,
+    /// Amount actually charged to the payer (in USDC micro-units)
+    pub amount_charged: u64,
+    /// Credit left on the agreement for a later resume (in USDC micro-units)
+    pub remaining_credit: u64,
+    /// Unix timestamp when the agreement was paused
+    pub paused_at_ts: i64,
+}
+
+/// Event emitted when a payee deactivates payment terms
+#[event]
+pub struct PaymentTermsDeactivated {
+    /// The deactivated payment terms account
+    pub payment_terms: Pubkey,
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// Unix timestamp from which agreements stop renewing
+    pub sunset_ts: i64,
+    /// Agreements still active when the terms were deactivated
+    pub active_agreements: u32,
+    /// Unix timestamp when the terms were deactivated
+    pub timestamp: i64,
+}
+
+/// Event emitted when `execute_payment` pauses an agreement because its payment
+/// terms reached their sunset
+#[event]
+pub struct TermsSunset {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The deactivated payment terms
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was paused
+    pub payer: Pubkey,
+    /// Sunset timestamp set by the payee
+    pub sunset_ts: i64,
+    /// Unix timestamp when the agreement was paused
+    pub timestamp: i64,
+}
+
+/// Event emitted when `execute_payment` suspends an agreement instead of charging it
+#[event]
+pub struct AgreementSuspended {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was suspended
+    pub payer: Pubkey,
+    /// Why the agreement was suspended
+    pub reason: crate::state::SuspensionReason,
+    /// The payer token account that caused the suspension
+    pub payer_token_account: Pubkey,
+    /// Unix timestamp when the agreement was suspended
+    pub timestamp: i64,
+}
+
+/// Compact view of an agreement's state, emitted after every lifecycle mutation
+///
+/// Emitted by `start_agreement`, `execute_payment`, `pause_agreement`,
+/// `resume_agreement`, `resume_after_unfreeze` and `close_agreement` alongside their
+/// own events, so indexers can keep agreement views current from events alone.
+#[event]
+pub struct AgreementSnapshot {
+    /// The payment agreement account
+    pub payment_agreement: Pubkey,
+    /// Lifecycle status after the mutation
+    pub status: crate::state::AgreementStatus,
+    /// Unix timestamp of the next payment
+    pub next_payment_ts: i64,
+    /// Number of renewals charged by `execute_payment`
+    pub period_index: u64,
+}
+
+/// Event emitted when `resume_after_unfreeze` reinstates a suspended agreement
+#[event]
+pub struct AgreementUnsuspended {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was reinstated
+    pub payer: Pubkey,
+    /// Unix timestamp of the agreement's next payment
+    pub next_payment_ts: i64,
+    /// Unix timestamp when the agreement was reinstated
+    pub timestamp: i64,
+}
+
+/// Event emitted when the payee confirms activation and the escrowed first payment
+/// is released to the treasuries
+#[event]
+pub struct EscrowReleased {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was activated
+    pub payer: Pubkey,
+    /// Amount released from escrow (in USDC micro-units)
+    pub amount: u64,
+    /// Platform fee sent to the platform treasury (in USDC micro-units)
+    pub platform_fee: u64,
+    /// Amount sent to the payee treasury (in USDC micro-units)
+    pub payee_amount: u64,
+    /// Unix timestamp when the escrow was released
+    pub timestamp: i64,
+}
+
+/// Event emitted when an escrowed first payment is returned to the payer after the
+/// activation window lapsed
+#[event]
+pub struct EscrowRefunded {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer who was refunded
+    pub payer: Pubkey,
+    /// Amount returned to the payer (in USDC micro-units)
+    pub amount: u64,
+    /// Unix timestamp at which the activation window lapsed
+    pub expires_ts: i64,
+    /// Unix timestamp when the escrow was refunded
+    pub timestamp: i64,
+}
+
+/// Event emitted when `start_agreement` pulls a security deposit into escrow
+#[event]
+pub struct DepositHeld {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms requiring the deposit
+    pub payment_terms: Pubkey,
+    /// The payer who paid the deposit
+    pub payer: Pubkey,
+    /// Amount pulled into escrow by this start (in USDC micro-units)
+    pub amount: u64,
+    /// Total deposit now held for the agreement (in USDC micro-units)
+    pub total_held: u64,
+    /// Unix timestamp when the deposit was pulled
+    pub timestamp: i64,
+}
+
+/// Event emitted when the payee claims damages from a held security deposit
+#[event]
+pub struct DepositClaimed {
+    /// The payee who claimed the damages
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose deposit was claimed from
+    pub payer: Pubkey,
+    /// Amount sent to the payee treasury (in USDC micro-units)
+    pub amount: u64,
+    /// Deposit still held after the claim (in USDC micro-units)
+    pub remaining: u64,
+    /// Unix timestamp of the claim
+    pub timestamp: i64,
+}
+
+/// Event emitted when `close_agreement` returns the unclaimed deposit to the payer
+#[event]
+pub struct DepositReleased {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer who was refunded
+    pub payer: Pubkey,
+    /// Amount returned to the payer (in USDC micro-units)
+    pub amount: u64,
+    /// Unix timestamp when the deposit was released
+    pub timestamp: i64,
+}
+
+/// Event emitted when `execute_payment` pays the keeper fee in SOL from the keeper
+/// fee vault
+///
+/// The USDC keeper fee goes to the platform treasury instead of the keeper.
+#[event]
+pub struct KeeperPaidInSol {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms being executed
+    pub payment_terms: Pubkey,
+    /// The payer who was charged
+    pub payer: Pubkey,
+    /// The keeper who executed the payment
+    pub keeper: Pubkey,
+    /// Keeper fee the payer paid, sent to the platform treasury (in USDC micro-units)
+    pub keeper_fee: u64,
+    /// Platform rate the fee was converted at, in lamports per whole USDC
+    pub lamports_per_usdc: u64,
+    /// Lamports paid to the keeper from the vault
+    pub lamports: u64,
+}
+
+/// Event emitted when the platform authority sets or disables SOL keeper fees
+#[event]
+pub struct KeeperSolRateSet {
+    /// Rate in lamports per whole USDC (zero when disabled)
+    pub lamports_per_usdc: u64,
+    /// Platform authority who made the change
+    pub updated_by: Pubkey,
+    /// Unix timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when `sweep_accrued` collects the accrual of a streaming agreement, or
+/// `resume_agreement` collects accrual left unswept at the pause
+#[event]
+pub struct Swept {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The streaming payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer who was charged
+    pub payer: Pubkey,
+    /// The keeper who swept the accrual (the payer for accrual collected on resume)
+    pub keeper: Pubkey,
+    /// Amount charged to the payer (in USDC micro-units)
+    pub amount: u64,
+    /// The fee paid to the keeper out of `amount` (in USDC micro-units)
+    pub keeper_fee: u64,
+    /// Start of the accrual swept (the agreement's previous `last_sweep_ts`)
+    pub from_ts: i64,
+    /// End of the accrual swept (the agreement's new `last_sweep_ts`)
+    pub to_ts: i64,
+}
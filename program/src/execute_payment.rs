@@ -1,22 +1,35 @@
 use crate::{
-    constants::FEE_BASIS_POINTS_DIVISOR, errors::RecurringPaymentError, events::*, state::*,
-    utils::validate_platform_treasury,
+    errors::RecurringPaymentError,
+    events::*,
+    state::*,
+    utils::{
+        apply_gate_discount, calculate_fee_split, dequeue_renewal, due_check_time,
+        payer_gate_mint, record_payee_volume, validate_platform_treasury, FeeSplit,
+    },
 };
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
 use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct ExecutePaymentArgs {
-    // No args needed - renewal driven by executor
+    /// Renewal queue bucket of the payment after this one
+    /// (`(next_payment_ts + period_secs) / RENEWAL_BUCKET_SECS`), used to derive
+    /// `next_renewal_queue`
+    pub next_renewal_bucket: u64,
+    /// Take the keeper fee in SOL from the keeper fee vault at the platform's rate
+    /// (`Config::keeper_sol_rate`); `false` pays the fee in USDC to `keeper_usdc_ata`
+    pub keeper_fee_in_sol: bool,
 }
 
 #[derive(Accounts)]
+#[instruction(args: ExecutePaymentArgs)]
 pub struct ExecutePayment<'info> {
     /// Global configuration account
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = !config.paused @ RecurringPaymentError::Inactive
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
     )]
     pub config: Account<'info, Config>,
 
@@ -28,11 +41,16 @@ pub struct ExecutePayment<'info> {
     )]
     pub payment_agreement: Account<'info, PaymentAgreement>,
 
+    /// Mutable so a scheduled terms update can be applied once effective
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
     pub payment_terms: Account<'info, PaymentTerms>,
 
+    /// Mutable so the payee's volume and tier can be updated
     #[account(
-        seeds = [b"payee", payee.authority.as_ref()],
-        bump = payee.bump
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
     )]
     pub payee: Account<'info, Payee>,
 
@@ -53,11 +71,21 @@ pub struct ExecutePayment<'info> {
     #[account(mut)]
     pub executor: Signer<'info>,
 
-    /// Keeper's USDC ATA where executor fee will be sent
+    /// Keeper's USDC ATA where executor fee will be sent (unused when the fee is
+    /// taken in SOL)
     /// CHECK: Validated as executor's USDC token account in handler
     #[account(mut)]
     pub keeper_usdc_ata: UncheckedAccount<'info>,
 
+    /// Vault paying keeper fees taken in SOL, funded by the platform with plain SOL
+    /// transfers; only needed when the keeper takes its fee in SOL
+    #[account(
+        mut,
+        seeds = [b"keeper_fee_vault"],
+        bump
+    )]
+    pub keeper_fee_vault: Option<SystemAccount<'info>>,
+
     /// CHECK: Validated as USDC mint in handler
     pub usdc_mint: UncheckedAccount<'info>,
 
@@ -70,23 +98,169 @@ pub struct ExecutePayment<'info> {
     pub program_delegate: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Crank index shard for the bucket of the payment being executed. The agreement
+    /// is removed from it if the queue exists; agreements started before renewal queues
+    /// were introduced may not be listed anywhere.
+    /// CHECK: PDA derivation and ownership validated in handler
+    #[account(mut)]
+    pub current_renewal_queue: UncheckedAccount<'info>,
+
+    /// Crank index shard for the bucket of the following payment
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = RenewalQueue::SPACE,
+        seeds = [
+            b"renewal_queue",
+            args.next_renewal_bucket.to_le_bytes().as_ref(),
+            RenewalQueue::shard_seed(&payment_agreement.key()).as_ref()
+        ],
+        bump
+    )]
+    pub next_renewal_queue: Account<'info, RenewalQueue>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Payer's associated token account for the payment terms' gate mint; required
+    /// for token-gated terms so the keeper cannot leave the discount out
+    /// CHECK: Validated as the payer's gate ATA in handler
+    pub payer_gate_ata: Option<UncheckedAccount<'info>>,
 }
 
 #[allow(clippy::too_many_lines)]
-pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Result<()> {
-    let payment_agreement = &mut ctx.accounts.payment_agreement;
-    let payment_terms = &ctx.accounts.payment_terms;
+pub fn handler(ctx: Context<ExecutePayment>, args: ExecutePaymentArgs) -> Result<()> {
+    // Get current timestamp, shifted by the test clock in `test-clock` builds
+    let clock = Clock::get()?;
+    let current_time =
+        due_check_time(clock.unix_timestamp, ctx.remaining_accounts, ctx.program_id)?;
+
+    // Apply a scheduled terms update once its effective timestamp has been reached.
+    // Before then, the payment below is charged at the current terms.
+    if let Some((old_amount, old_period)) =
+        ctx.accounts.payment_terms.apply_due_update(current_time)
+    {
+        let payment_terms = &ctx.accounts.payment_terms;
+        emit!(PaymentTermsUpdated {
+            payment_terms: payment_terms.key(),
+            payee: ctx.accounts.payee.key(),
+            old_amount: Some(old_amount),
+            new_amount: Some(payment_terms.amount_usdc),
+            old_period: Some(old_period),
+            new_period: Some(payment_terms.period_secs),
+            updated_by: ctx.accounts.payee.authority,
+        });
+    }
+
+    // Streaming agreements are charged by the second with sweep_accrued
+    require!(
+        !ctx.accounts.payment_terms.is_streaming(),
+        RecurringPaymentError::StreamingTerms
+    );
+
+    let payment_agreement = &ctx.accounts.payment_agreement;
     let payee = &ctx.accounts.payee;
 
-    // Get current timestamp
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+    // Permissioned execution: payees may restrict renewals to their own keepers
+    require!(
+        payee.is_keeper_authorized(&ctx.accounts.executor.key()),
+        RecurringPaymentError::UnauthorizedKeeper
+    );
 
-    // Check timing: payment is due when current time >= next_payment_ts
-    if current_time < payment_agreement.next_payment_ts {
+    // Check timing: payment is due when current time >= next_payment_ts, less the
+    // configured tolerance for skew between the cluster clock and wall time
+    let renewal_check_time = ctx.accounts.config.renewal_check_time(current_time);
+    if renewal_check_time < payment_agreement.next_payment_ts {
+        let tolerance = renewal_check_time.saturating_sub(current_time);
+        msg!(
+            "Payment not due: renewal window opens at {} (next_payment_ts {} less {}s tolerance), cluster time {}",
+            payment_agreement.next_payment_ts.saturating_sub(tolerance),
+            payment_agreement.next_payment_ts,
+            tolerance,
+            current_time
+        );
         return Err(RecurringPaymentError::NotDue.into());
     }
 
+    // The first payment must be released or refunded before the next one is charged
+    require!(
+        !payment_agreement.escrow_pending(),
+        RecurringPaymentError::EscrowPending
+    );
+
+    // This payment leaves its renewal queue bucket whether it is charged or canceled
+    let current_bucket = RenewalQueue::bucket_for(payment_agreement.next_payment_ts)
+        .ok_or(RecurringPaymentError::InvalidRenewalBucket)?;
+    if current_bucket != args.next_renewal_bucket {
+        dequeue_renewal(
+            &ctx.accounts.current_renewal_queue.to_account_info(),
+            current_bucket,
+            &payment_agreement.key(),
+            ctx.program_id,
+        )?;
+    }
+
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    let payment_terms = &mut ctx.accounts.payment_terms;
+    let payee = &mut ctx.accounts.payee;
+
+    // Cancellation scheduled by the payer: the paid period has ended, so pause the
+    // agreement instead of charging for another one
+    if payment_agreement.cancel_at_period_end {
+        payment_agreement.active = false;
+        payment_agreement.cancel_at_period_end = false;
+        payment_terms.release_subscriber_slot();
+        payee.release_subscriber_slot();
+        // The current and next buckets may share a queue; a paused agreement has no
+        // following payment to index
+        ctx.accounts
+            .next_renewal_queue
+            .remove(&payment_agreement.key());
+
+        emit!(CanceledAtPeriodEnd {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            timestamp: current_time,
+        });
+
+        emit!(payment_agreement.snapshot(payment_agreement.key()));
+
+        return Ok(());
+    }
+
+    // Deactivated terms: the last paid period ended at or after the sunset, so pause
+    // the agreement instead of charging for another one
+    if let Some(sunset_ts) = payment_terms
+        .sunset_ts
+        .filter(|ts| renewal_check_time >= *ts)
+    {
+        payment_agreement.active = false;
+        payment_terms.release_subscriber_slot();
+        payee.release_subscriber_slot();
+        ctx.accounts
+            .next_renewal_queue
+            .remove(&payment_agreement.key());
+
+        emit!(TermsSunset {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            sunset_ts,
+            timestamp: current_time,
+        });
+
+        emit!(payment_agreement.snapshot(payment_agreement.key()));
+
+        return Ok(());
+    }
+
+    // Limited agreements complete on their final charge; never charge past the limit
+    require!(
+        !payment_agreement.periods_exhausted(),
+        RecurringPaymentError::MaxPeriodsReached
+    );
+
     // Prevent double-renewal attack: ensure sufficient time has passed since last renewal
     // This prevents multiple renewals within the same period
     let period_i64 =
@@ -96,11 +270,22 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
         .checked_add(period_i64)
         .ok_or(RecurringPaymentError::ArithmeticError)?;
 
-    if current_time < min_next_renewal_time {
+    if renewal_check_time < min_next_renewal_time {
         return Err(RecurringPaymentError::NotDue.into());
     }
 
-    // Deserialize and validate token accounts with specific error handling
+    // Per-period pull cap (defense in depth): each billing period may be pulled at most
+    // once. The period is identified by its due date relative to `created_ts`, not by
+    // `last_payment_ts`, so the cap holds even if the timing checks above are bypassed
+    let pull_period_index = payment_agreement.next_pull_period_index()?;
+
+    // Deserialize and validate token accounts with specific error handling. The payer
+    // account is supplied by the keeper, so it must belong to the token program before
+    // its data is trusted
+    require!(
+        ctx.accounts.payer_usdc_ata.owner == &ctx.accounts.token_program.key(),
+        RecurringPaymentError::InvalidPayerTokenAccount
+    );
     let subscriber_ata_data: TokenAccount =
         TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
             .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
@@ -115,9 +300,15 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
     )
     .map_err(|_| RecurringPaymentError::InvalidPlatformTreasuryAccount)?;
 
-    let keeper_ata_data: TokenAccount =
-        TokenAccount::try_deserialize(&mut ctx.accounts.keeper_usdc_ata.data.borrow().as_ref())
-            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+    // Keepers taking their fee in SOL need no USDC token account
+    let keeper_ata_data: Option<TokenAccount> = if args.keeper_fee_in_sol {
+        None
+    } else {
+        Some(
+            TokenAccount::try_deserialize(&mut ctx.accounts.keeper_usdc_ata.data.borrow().as_ref())
+                .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?,
+        )
+    };
 
     let usdc_mint_data: Mint =
         Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
@@ -133,19 +324,28 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
         &ctx.accounts.token_program,
     )?;
 
-    // Validate token account ownership and mints
+    // Validate token account ownership and mints, checking the payer account first so
+    // a hostile keeper gets the precise error
     if subscriber_ata_data.owner != payment_agreement.payer {
-        return Err(RecurringPaymentError::Unauthorized.into());
+        return Err(RecurringPaymentError::WrongOwner.into());
+    }
+
+    if subscriber_ata_data.mint != payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
     }
 
-    if keeper_ata_data.owner != ctx.accounts.executor.key() {
+    if keeper_ata_data
+        .as_ref()
+        .is_some_and(|keeper_ata| keeper_ata.owner != ctx.accounts.executor.key())
+    {
         return Err(RecurringPaymentError::Unauthorized.into());
     }
 
-    if subscriber_ata_data.mint != payee.usdc_mint
-        || payee_treasury_data.mint != payee.usdc_mint
+    if payee_treasury_data.mint != payee.usdc_mint
         || platform_treasury_data.mint != payee.usdc_mint
-        || keeper_ata_data.mint != payee.usdc_mint
+        || keeper_ata_data
+            .as_ref()
+            .is_some_and(|keeper_ata| keeper_ata.mint != payee.usdc_mint)
     {
         return Err(RecurringPaymentError::WrongMint.into());
     }
@@ -158,6 +358,55 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
         return Err(RecurringPaymentError::BadSeeds.into());
     }
 
+    // A payer token account frozen by the mint's freeze authority rejects every
+    // transfer until it is thawed, so suspend the agreement instead of failing and
+    // letting keepers pay fees to retry it. Only the account the program is delegate
+    // of counts, so a keeper cannot suspend an agreement with another frozen account
+    // of the payer.
+    if subscriber_ata_data.is_frozen()
+        && Option::<Pubkey>::from(subscriber_ata_data.delegate)
+            == Some(ctx.accounts.program_delegate.key())
+    {
+        payment_agreement.active = false;
+        payment_agreement.suspension = Some(SuspensionReason::FrozenAccount);
+        payment_terms.release_subscriber_slot();
+        payee.release_subscriber_slot();
+        // The current and next buckets may share a queue; a suspended agreement is
+        // indexed again by resume_after_unfreeze
+        ctx.accounts
+            .next_renewal_queue
+            .remove(&payment_agreement.key());
+
+        emit!(AgreementSuspended {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            reason: SuspensionReason::FrozenAccount,
+            payer_token_account: ctx.accounts.payer_usdc_ata.key(),
+            timestamp: current_time,
+        });
+
+        emit!(payment_agreement.snapshot(payment_agreement.key()));
+
+        return Ok(());
+    }
+
+    // Token-gated pricing: the gate is re-checked on every payment, so payers only
+    // receive the discount while they still hold the gate token. The keeper must pass
+    // the payer's gate ATA, so a gated payer is never charged the full price because
+    // the account was left out.
+    let gate_mint = payer_gate_mint(
+        payment_terms,
+        &payment_agreement.payer,
+        ctx.accounts.payer_gate_ata.as_ref().map(AsRef::as_ref),
+        &ctx.accounts.token_program.key(),
+    )?;
+    let payment_amount = if gate_mint.is_some() {
+        apply_gate_discount(payment_terms.amount_usdc, payment_terms.gate_discount_bps)?
+    } else {
+        payment_terms.amount_usdc
+    };
+
     // Check delegate allowance for single-period renewal
     //
     // ALLOWANCE MANAGEMENT (Audit L-3):
@@ -173,7 +422,7 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
     // This prevents the UX friction identified in audit finding L-3 where users
     // may successfully start subscriptions but encounter unexpected renewal failures
     // when allowance depletes.
-    if subscriber_ata_data.delegated_amount < payment_terms.amount_usdc {
+    if subscriber_ata_data.delegated_amount < payment_amount {
         return Err(RecurringPaymentError::InsufficientAllowance.into());
     }
 
@@ -238,40 +487,71 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
     }
 
     // Check sufficient funds
-    if subscriber_ata_data.amount < payment_terms.amount_usdc {
+    if subscriber_ata_data.amount < payment_amount {
         return Err(RecurringPaymentError::InsufficientFunds.into());
     }
 
-    // Calculate executor fee first (deducted from total amount)
-    let keeper_fee = u64::try_from(
-        u128::from(payment_terms.amount_usdc)
-            .checked_mul(u128::from(ctx.accounts.config.keeper_fee_bps))
-            .ok_or(RecurringPaymentError::ArithmeticError)?
-            .checked_div(FEE_BASIS_POINTS_DIVISOR)
-            .ok_or(RecurringPaymentError::ArithmeticError)?,
-    )
-    .map_err(|_| RecurringPaymentError::ArithmeticError)?;
-
-    // Calculate remaining amount after executor fee
-    let remaining_after_keeper = payment_terms
-        .amount_usdc
-        .checked_sub(keeper_fee)
-        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    // Automatic volume tier evaluation: count this payment towards the payee's 30-day
+    // volume so the fee split below uses the tier the payee now qualifies for
+    record_payee_volume(payee, payment_amount, current_time);
 
-    // Calculate platform fee from remaining amount (fee rate determined by payee's volume tier)
-    let platform_fee = u64::try_from(
-        u128::from(remaining_after_keeper)
-            .checked_mul(u128::from(payee.volume_tier.platform_fee_bps()))
-            .ok_or(RecurringPaymentError::ArithmeticError)?
-            .checked_div(FEE_BASIS_POINTS_DIVISOR)
-            .ok_or(RecurringPaymentError::ArithmeticError)?,
-    )
-    .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+    // Split the payment: executor fee first (deducted from total amount), then the
+    // platform fee from the remainder (fee rate determined by payee's volume tier,
+    // less any active fee holiday)
+    let platform_fee_bps = payee.platform_fee_bps(current_time);
+    let FeeSplit {
+        keeper_fee,
+        platform_fee,
+        payee_amount: merchant_amount,
+    } = calculate_fee_split(
+        payment_amount,
+        ctx.accounts.config.keeper_fee_bps,
+        platform_fee_bps,
+    )?;
 
-    // Calculate payee amount from remaining amount
-    let merchant_amount = remaining_after_keeper
-        .checked_sub(platform_fee)
-        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    // SOL keeper fee: the payer's USDC keeper fee goes to the platform treasury, and
+    // the platform-funded vault pays the keeper in lamports at the platform's rate
+    let keeper_sol_rate = if args.keeper_fee_in_sol {
+        Some(
+            ctx.accounts
+                .config
+                .keeper_sol_rate
+                .ok_or(RecurringPaymentError::KeeperSolRateUnavailable)?,
+        )
+    } else {
+        None
+    };
+    let keeper_lamports = match keeper_sol_rate {
+        Some(rate) => {
+            let lamports = rate
+                .lamports_for(keeper_fee, clock.unix_timestamp)
+                .ok_or(RecurringPaymentError::KeeperSolRateUnavailable)?;
+            let keeper_fee_vault = ctx
+                .accounts
+                .keeper_fee_vault
+                .as_ref()
+                .ok_or(RecurringPaymentError::KeeperFeeVaultRequired)?;
+            let vault_minimum = Rent::get()?.minimum_balance(0);
+            require!(
+                lamports == 0
+                    || keeper_fee_vault
+                        .lamports()
+                        .checked_sub(lamports)
+                        .is_some_and(|remaining| remaining >= vault_minimum),
+                RecurringPaymentError::KeeperFeeVaultUnderfunded
+            );
+            Some(lamports)
+        }
+        None => None,
+    };
+    let (platform_treasury_amount, keeper_usdc_fee) = if keeper_lamports.is_some() {
+        let platform_treasury_amount = platform_fee
+            .checked_add(keeper_fee)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        (platform_treasury_amount, 0)
+    } else {
+        (platform_fee, keeper_fee)
+    };
 
     // Prepare delegate signer seeds
     let delegate_bump = ctx.bumps.program_delegate;
@@ -300,8 +580,8 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
         )?;
     }
 
-    // Transfer platform fee to platform treasury (via delegate)
-    if platform_fee > 0 {
+    // Transfer platform fee (and a keeper fee taken in SOL) to platform treasury (via delegate)
+    if platform_treasury_amount > 0 {
         let transfer_to_platform = TransferChecked {
             from: ctx.accounts.payer_usdc_ata.to_account_info(),
             mint: ctx.accounts.usdc_mint.to_account_info(),
@@ -315,13 +595,13 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
                 transfer_to_platform,
                 delegate_seeds,
             ),
-            platform_fee,
+            platform_treasury_amount,
             usdc_decimals,
         )?;
     }
 
     // Transfer executor fee to executor's ATA (via delegate)
-    if keeper_fee > 0 {
+    if keeper_usdc_fee > 0 {
         let transfer_to_keeper = TransferChecked {
             from: ctx.accounts.payer_usdc_ata.to_account_info(),
             mint: ctx.accounts.usdc_mint.to_account_info(),
@@ -335,11 +615,32 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
                 transfer_to_keeper,
                 delegate_seeds,
             ),
-            keeper_fee,
+            keeper_usdc_fee,
             usdc_decimals,
         )?;
     }
 
+    // Pay a keeper fee taken in SOL from the vault
+    if let (Some(lamports), Some(keeper_fee_vault), Some(vault_bump)) = (
+        keeper_lamports.filter(|lamports| *lamports > 0),
+        ctx.accounts.keeper_fee_vault.as_ref(),
+        ctx.bumps.keeper_fee_vault,
+    ) {
+        let vault_seeds: &[&[&[u8]]] = &[&[b"keeper_fee_vault", &[vault_bump]]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: keeper_fee_vault.to_account_info(),
+                    to: ctx.accounts.executor.to_account_info(),
+                },
+                vault_seeds,
+            ),
+            lamports,
+        )?;
+    }
+
     // Update payment_agreement fields
     payment_agreement.next_payment_ts = payment_agreement
         .next_payment_ts
@@ -351,18 +652,118 @@ pub fn handler(ctx: Context<ExecutePayment>, _args: ExecutePaymentArgs) -> Resul
         .checked_add(1)
         .ok_or(RecurringPaymentError::ArithmeticError)?;
 
-    payment_agreement.last_amount = payment_terms.amount_usdc;
+    payment_agreement.last_amount = payment_amount;
     payment_agreement.last_payment_ts = current_time;
+    payment_agreement.last_pull_period_index = pull_period_index;
+    payment_agreement.period_index = payment_agreement
+        .period_index
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    payment_agreement.refunded_amount = 0;
+    payment_agreement.periods_paid = payment_agreement
+        .periods_paid
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    payment_terms.record_payment(payment_amount, true);
+    payee.record_payment(payment_amount, true);
+
+    // Final billing period of a limited agreement: complete it instead of scheduling
+    // another payment
+    let completed = payment_agreement.periods_exhausted();
+    if completed {
+        payment_agreement.active = false;
+        payment_terms.release_subscriber_slot();
+        payee.release_subscriber_slot();
+        // The current and next buckets may share a queue
+        ctx.accounts
+            .next_renewal_queue
+            .remove(&payment_agreement.key());
+    } else {
+        // Index the agreement under the bucket of its following payment for keepers
+        require!(
+            RenewalQueue::bucket_for(payment_agreement.next_payment_ts)
+                == Some(args.next_renewal_bucket),
+            RecurringPaymentError::InvalidRenewalBucket
+        );
+        let next_renewal_queue = &mut ctx.accounts.next_renewal_queue;
+        next_renewal_queue.bucket = args.next_renewal_bucket;
+        next_renewal_queue.shard = RenewalQueue::shard_for(&payment_agreement.key());
+        next_renewal_queue.bump = ctx.bumps.next_renewal_queue;
+        next_renewal_queue.insert(payment_agreement.key());
+    }
 
     // Emit PaymentExecuted event
     emit!(PaymentExecuted {
         payee: payee.key(),
         payment_terms: payment_terms.key(),
         payer: payment_agreement.payer,
-        amount: payment_terms.amount_usdc,
+        amount: payment_amount,
         keeper: ctx.accounts.executor.key(),
         keeper_fee,
+        period_index: payment_agreement.period_index,
     });
 
+    if let (Some(lamports), Some(rate)) = (keeper_lamports, keeper_sol_rate) {
+        emit!(KeeperPaidInSol {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            keeper: ctx.accounts.executor.key(),
+            keeper_fee,
+            lamports_per_usdc: rate.lamports_per_usdc,
+            lamports,
+        });
+    }
+
+    if let Some(holiday) = payee.active_fee_holiday(current_time) {
+        emit!(FeeHolidayApplied {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            tier_fee_bps: payee.volume_tier.platform_fee_bps(),
+            platform_fee_bps,
+            platform_fee,
+            until_ts: holiday.until_ts,
+        });
+    }
+
+    // Give the payer on-chain notice of an upcoming terms change
+    if let Some(pending) = payment_terms.pending_update {
+        emit!(PendingTermsChangeNotice {
+            payment_terms: payment_terms.key(),
+            payee: payee.key(),
+            payer: payment_agreement.payer,
+            current_amount: payment_terms.amount_usdc,
+            new_amount: pending.amount_usdc,
+            current_period: payment_terms.period_secs,
+            new_period: pending.period_secs,
+            effective_ts: pending.effective_ts,
+        });
+    }
+
+    if let Some(gate_mint) = gate_mint {
+        emit!(GateDiscountApplied {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            gate_mint,
+            discount_bps: payment_terms.gate_discount_bps,
+            original_amount: payment_terms.amount_usdc,
+            discounted_amount: payment_amount,
+        });
+    }
+
+    if completed {
+        emit!(AgreementCompleted {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            periods_paid: payment_agreement.periods_paid,
+            timestamp: current_time,
+        });
+    }
+
+    emit!(payment_agreement.snapshot(payment_agreement.key()));
+
     Ok(())
 }
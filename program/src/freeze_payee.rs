@@ -0,0 +1,85 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::PayeeFrozen;
+use crate::state::{Config, Payee};
+use anchor_lang::prelude::*;
+
+/// Arguments for freezing a payee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FreezePayeeArgs {}
+
+/// Accounts required for freezing a payee
+#[derive(Accounts)]
+pub struct FreezePayee<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = platform_authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payee account to be frozen
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Platform authority (must sign)
+    pub platform_authority: Signer<'info>,
+}
+
+/// Handler for freezing a payee
+///
+/// Blocks `start_agreement` and `execute_payment` for a single payee (e.g. during a
+/// fraud investigation) while the rest of the platform keeps running. Existing
+/// agreements are left untouched and resume once the payee is unfrozen.
+///
+/// # Security
+/// - Only `platform_authority` can freeze a payee
+/// - Freeze state is stored in the Payee account
+/// - Events are emitted for transparency and off-chain monitoring
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the platform authority
+pub fn handler(ctx: Context<FreezePayee>, _args: FreezePayeeArgs) -> Result<()> {
+    let payee = &mut ctx.accounts.payee;
+
+    payee.frozen = true;
+
+    // Get current timestamp for event
+    let clock = Clock::get()?;
+
+    emit!(PayeeFrozen {
+        payee: payee.key(),
+        authority: ctx.accounts.platform_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Payee {} frozen by platform authority: {}",
+        payee.key(),
+        ctx.accounts.platform_authority.key()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freeze_payee_args_serialization() {
+        let args = FreezePayeeArgs {};
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: FreezePayeeArgs = FreezePayeeArgs::try_from_slice(&serialized).unwrap();
+
+        // FreezePayeeArgs has no fields, so just verify it deserializes successfully
+        let _ = deserialized;
+    }
+}
@@ -1,3 +1,4 @@
+use crate::state::VersionedAccount;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
 use anchor_lang::solana_program::program_pack::Pack;
@@ -399,8 +400,13 @@ pub fn handler(ctx: Context<InitConfig>, args: InitConfigArgs) -> Result<()> {
     config.max_withdrawal_amount = args.max_withdrawal_amount;
     config.max_grace_period_seconds = args.max_grace_period_seconds;
     config.paused = false; // Program starts in unpaused state
+    config.pause_reason = [0; 64];
+    config.auto_unpause_ts = None;
+    config.keeper_sol_rate = None; // Keeper fees are paid in USDC until enabled
+    config.renewal_tolerance_secs = 0; // Renewals are due exactly at next_payment_ts until configured
     config.keeper_fee_bps = args.keeper_fee_bps;
     config.bump = ctx.bumps.config;
+    config.version = crate::state::Config::VERSION;
 
     // Get current timestamp for event
     let clock = Clock::get()?;
@@ -1,3 +1,4 @@
+use crate::state::VersionedAccount;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_pack::Pack;
 use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
@@ -126,7 +127,17 @@ pub fn handler(ctx: Context<InitPayee>, args: InitPayeeArgs) -> Result<()> {
     payee.volume_tier = default_tier;
     payee.monthly_volume_usdc = 0;
     payee.last_volume_update_ts = clock.unix_timestamp;
+    payee.frozen = false;
+    payee.open_execution = true;
+    payee.authorized_keepers = Vec::new();
+    payee.original_authority = ctx.accounts.authority.key();
+    payee.pending_authority = None;
+    payee.fee_holiday = None;
+    payee.active_agreements = 0;
+    payee.lifetime_revenue_usdc = 0;
+    payee.lifetime_renewals = 0;
     payee.bump = ctx.bumps.payee;
+    payee.version = crate::state::Payee::VERSION;
 
     // Emit PayeeInitialized event
     emit!(crate::events::PayeeInitialized {
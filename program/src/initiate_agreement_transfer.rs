@@ -0,0 +1,95 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::AgreementTransferInitiated;
+use crate::state::{Payee, PaymentAgreement, PaymentTerms};
+use anchor_lang::prelude::*;
+
+/// Arguments for proposing a new payer wallet for a payment agreement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InitiateAgreementTransferArgs {
+    /// The wallet that will take over the agreement
+    pub new_payer: Pubkey,
+}
+
+/// Accounts required for initiating a payment agreement transfer
+#[derive(Accounts)]
+pub struct InitiateAgreementTransfer<'info> {
+    /// Payment agreement to hand over
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payer.key().as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payer @ RecurringPaymentError::Unauthorized,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    #[account(has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Current payer (must sign)
+    pub payer: Signer<'info>,
+}
+
+/// Handler for initiating a payment agreement transfer to another wallet
+///
+/// This is the first step of a two-step transfer: the current payer proposes the new
+/// wallet, which must then approve the program delegate and call
+/// `accept_agreement_transfer`. Initiating again replaces the proposed wallet, which
+/// is how a mistaken proposal is redirected.
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the agreement's payer
+/// - New payer is the current payer
+pub fn handler(
+    ctx: Context<InitiateAgreementTransfer>,
+    args: InitiateAgreementTransferArgs,
+) -> Result<()> {
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+
+    // An escrow refund goes to the original payer, so settle the escrow first
+    require!(
+        !payment_agreement.escrow_pending(),
+        RecurringPaymentError::EscrowPending
+    );
+
+    require!(
+        args.new_payer != payment_agreement.payer,
+        RecurringPaymentError::InvalidTransferTarget
+    );
+
+    payment_agreement.pending_payer = Some(args.new_payer);
+
+    emit!(AgreementTransferInitiated {
+        payee: ctx.accounts.payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: ctx.accounts.payer.key(),
+        new_payer: args.new_payer,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initiate_agreement_transfer_args_serialization() {
+        let new_payer = Pubkey::new_unique();
+        let args = InitiateAgreementTransferArgs { new_payer };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: InitiateAgreementTransferArgs =
+            InitiateAgreementTransferArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.new_payer, new_payer);
+    }
+}
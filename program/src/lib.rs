@@ -25,41 +25,95 @@
 
 use anchor_lang::prelude::*;
 
+mod accept_agreement_transfer;
 mod accept_authority;
+mod accept_payee_authority;
 mod admin_withdraw_fees;
+#[cfg(feature = "test-clock")]
+mod advance_test_clock;
 mod cancel_authority_transfer;
+mod cancel_payee_authority_transfer;
+mod claim_deposit;
 mod close_agreement;
+mod close_agreements_batch;
+mod confirm_activation;
 pub mod constants;
 mod create_payment_terms;
+mod deactivate_payment_terms;
 pub mod errors;
 pub mod events;
 mod execute_payment;
+mod freeze_payee;
 mod init_config;
 mod init_payee;
+mod initiate_agreement_transfer;
+mod migrate_account;
 mod pause;
 mod pause_agreement;
+mod refund_escrow;
+mod refund_payment;
+mod reserve_slot;
+mod resume_after_unfreeze;
+mod resume_agreement;
+mod schedule_cancellation;
+mod schedule_terms_update;
+mod set_fee_holiday;
+mod set_keeper_policy;
+mod set_keeper_sol_rate;
 mod start_agreement;
 pub mod state;
+mod sweep_accrued;
 mod transfer_authority;
+mod transfer_payee_authority;
+mod unfreeze_payee;
 mod unpause;
 mod update_config;
 pub mod utils;
 
+use accept_agreement_transfer::*;
 use accept_authority::*;
+use accept_payee_authority::*;
 use admin_withdraw_fees::*;
+#[cfg(feature = "test-clock")]
+use advance_test_clock::*;
 use cancel_authority_transfer::*;
+use cancel_payee_authority_transfer::*;
+use claim_deposit::*;
 use close_agreement::*;
+use close_agreements_batch::*;
+use confirm_activation::*;
 use create_payment_terms::*;
+use deactivate_payment_terms::*;
 use execute_payment::*;
+use freeze_payee::*;
 use init_config::*;
 use init_payee::*;
+use initiate_agreement_transfer::*;
+use migrate_account::*;
 use pause::*;
 use pause_agreement::*;
+use refund_escrow::*;
+use refund_payment::*;
+use reserve_slot::*;
+use resume_after_unfreeze::*;
+use resume_agreement::*;
+use schedule_cancellation::*;
+use schedule_terms_update::*;
+use set_fee_holiday::*;
+use set_keeper_policy::*;
+use set_keeper_sol_rate::*;
 use start_agreement::*;
+use sweep_accrued::*;
 use transfer_authority::*;
+use transfer_payee_authority::*;
+use unfreeze_payee::*;
 use unpause::*;
 use update_config::*;
 
+// The test clock lets anyone holding the platform authority move due dates forward
+#[cfg(all(feature = "test-clock", feature = "mainnet-beta"))]
+compile_error!("the `test-clock` feature must not be enabled for mainnet builds");
+
 // Program ID is loaded from TALLY_PROGRAM_ID environment variable at compile time
 // The build script (build.rs) converts the base58 program ID to bytes
 // This approach ensures the program ID comes from the environment while satisfying
@@ -101,6 +155,12 @@ pub mod tally_protocol {
     /// - Price is zero or exceeds maximum
     /// - Period is invalid (too short or too long)
     /// - Grace period exceeds the period duration
+    /// - Gate discount is missing, exceeds the maximum, or is set without a gate mint
+    /// - Subscriber cap is set to zero
+    /// - Escrow window is zero or longer than the period
+    /// - Security deposit is zero or exceeds the maximum price
+    /// - Accrual rate is zero, doesn't price `period_secs` at `amount_usdc`, or is set
+    ///   with a gate mint or escrow window
     /// - Account creation fails
     pub fn create_payment_terms(ctx: Context<CreatePaymentTerms>, args: CreatePaymentTermsArgs) -> Result<()> {
         create_payment_terms::handler(ctx, args)
@@ -113,8 +173,16 @@ pub mod tally_protocol {
     /// - Payment agreement already exists for this user and payment terms
     /// - Insufficient USDC balance in user's account
     /// - Token transfer operations fail
-    /// - Delegate approval amount is insufficient
-    /// - Payment terms are inactive or expired
+    /// - Delegate approval covers fewer than `config.default_allowance_periods` periods
+    /// - Payment terms are inactive, expired, or deactivated by the payee
+    /// - Supplied gate token account is invalid for token-gated payment terms
+    /// - Payee has been frozen by the platform authority
+    /// - Payment terms have reached their subscriber cap
+    /// - Renewal bucket does not match the agreement's next payment
+    /// - Billing period limit is set below two periods, or set on streaming terms
+    /// - Reactivated agreement holds pause credit (use `resume_agreement`)
+    /// - Escrow account is invalid for payment terms with an escrow window or deposit
+    /// - Insufficient USDC balance for the security deposit
     /// - Account creation fails
     pub fn start_agreement(
         ctx: Context<StartAgreement>,
@@ -128,11 +196,28 @@ pub mod tally_protocol {
     /// # Errors
     /// Returns an error if:
     /// - Payment agreement is not active or has been paused
-    /// - Payment is not yet due (before `next_renewal_ts`)
+    /// - Payment is not yet due (before `next_renewal_ts` less the config's
+    ///   `renewal_tolerance_secs`)
     /// - Insufficient USDC balance for payment
     /// - Token transfer operations fail
     /// - Payment agreement has exceeded grace period
     /// - Delegate approval is insufficient or revoked
+    /// - Supplied gate token account is invalid for token-gated payment terms
+    /// - Payee has been frozen by the platform authority
+    /// - Billing period has already been pulled (at most one pull per period)
+    /// - Renewal queue accounts do not match the current or following payment
+    /// - Every billing period of a limited agreement has already been charged
+    /// - The first payment is still held in escrow
+    /// - The payment terms are streaming terms (use `sweep_accrued`)
+    /// - A SOL keeper fee was requested at a rejected rate or the vault can't cover it
+    /// - The payer token account is not a token account owned by the payer for the
+    ///   payee's USDC mint
+    ///
+    /// If the payer scheduled cancellation, the agreement is paused without charging.
+    /// Agreements on deactivated terms are likewise paused once the sunset is reached.
+    /// A limited agreement completes (and is paused) after charging its final period.
+    /// If the payer token account is frozen, the agreement is suspended without
+    /// charging and emits `AgreementSuspended`.
     pub fn execute_payment(
         ctx: Context<ExecutePayment>,
         args: ExecutePaymentArgs,
@@ -140,12 +225,38 @@ pub mod tally_protocol {
         execute_payment::handler(ctx, args)
     }
 
+    /// Collect the accrual of an agreement on streaming payment terms
+    ///
+    /// Charges `accrual_rate_per_sec` for every second since the agreement's
+    /// `last_sweep_ts`, up to now (or the pause, or the terms' sunset) and as far as
+    /// the payer's allowance covers, splitting it like a renewal. Keepers may sweep at
+    /// any time; there is no due date.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The payment terms are not streaming terms
+    /// - No accrual is due, or the allowance doesn't cover a second of it
+    /// - The agreement is paused without a recorded pause time
+    /// - Insufficient USDC balance for the sweep
+    /// - Delegate approval is revoked
+    /// - Token accounts are invalid for the payer, payee or keeper
+    /// - Payee has been frozen or has not authorized the keeper
+    pub fn sweep_accrued(ctx: Context<SweepAccrued>, args: SweepAccruedArgs) -> Result<()> {
+        sweep_accrued::handler(ctx, args)
+    }
+
     /// Pause a payment agreement and revoke delegate approval
     ///
+    /// Pausing an active agreement credits the unused part of the current billing
+    /// period, which `resume_agreement` applies to the first charge after the pause.
+    /// Agreements on streaming terms keep the delegate so their accrual up to the
+    /// pause can still be swept.
+    ///
     /// # Errors
     /// Returns an error if:
     /// - Payment agreement does not exist or is already paused
     /// - Unauthorized pause attempt (wrong payer)
+    /// - The first payment is still held in escrow
     /// - Token revoke operation fails
     /// - Account update operations fail
     pub fn pause_agreement(
@@ -155,15 +266,251 @@ pub mod tally_protocol {
         pause_agreement::handler(ctx, args)
     }
 
+    /// Resume a payment agreement paused mid-period, applying its pause credit
+    ///
+    /// The credit accrued by `pause_agreement` for the unused part of the paused period
+    /// offsets the charge for the new billing period. Streaming accrual up to the pause
+    /// that was not swept is charged along with it.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Payment agreement is active or was not paused by the payer
+    /// - Program is paused, the payee is frozen, or the payment terms are full
+    /// - Payment terms were deactivated and their sunset has passed
+    /// - Token accounts or the delegate PDA are invalid
+    /// - Delegate approval is insufficient
+    /// - Renewal queue does not match the agreement's next payment
+    pub fn resume_agreement(
+        ctx: Context<ResumeAgreement>,
+        args: ResumeAgreementArgs,
+    ) -> Result<()> {
+        resume_agreement::handler(ctx, args)
+    }
+
+    /// Reinstate an agreement suspended because the payer token account was frozen
+    ///
+    /// Permissionless once the account is thawed. Nothing is charged; the agreement
+    /// is due at its original next payment, or immediately if that has passed, and
+    /// emits `AgreementUnsuspended`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Payment agreement was not suspended for a frozen token account
+    /// - Payer token account is invalid, not owned by the payer, or still frozen
+    /// - Program is paused, the payee is frozen, or the payment terms are full
+    /// - Payment terms were deactivated and their sunset has passed
+    /// - Renewal queue does not match the agreement's next payment
+    pub fn resume_after_unfreeze(
+        ctx: Context<ResumeAfterUnfreeze>,
+        args: ResumeAfterUnfreezeArgs,
+    ) -> Result<()> {
+        resume_after_unfreeze::handler(ctx, args)
+    }
+
+    /// Schedule cancellation of a payment agreement at the end of the current period
+    ///
+    /// The agreement keeps running until `next_payment_ts`, after which the next
+    /// `execute_payment` pauses it instead of charging.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Payment agreement does not exist or is already paused
+    /// - Unauthorized attempt (wrong payer)
+    /// - The payment terms are streaming terms (pause the agreement instead)
+    pub fn schedule_cancellation(
+        ctx: Context<ScheduleCancellation>,
+        args: ScheduleCancellationArgs,
+    ) -> Result<()> {
+        schedule_cancellation::handler(ctx, args)
+    }
+
+    /// Propose moving a payment agreement to another payer wallet
+    ///
+    /// First step of a two-step transfer; the new wallet completes it with
+    /// `accept_agreement_transfer`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Unauthorized attempt (wrong payer)
+    /// - New payer is the current payer
+    /// - The first payment is still held in escrow
+    pub fn initiate_agreement_transfer(
+        ctx: Context<InitiateAgreementTransfer>,
+        args: InitiateAgreementTransferArgs,
+    ) -> Result<()> {
+        initiate_agreement_transfer::handler(ctx, args)
+    }
+
+    /// Accept a payment agreement transfer as the new payer wallet
+    ///
+    /// Moves the agreement to the new payer's PDA, preserving its payment history,
+    /// closes the old account and re-indexes the new address in the renewal queue.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No transfer is pending or the signer is not the proposed payer
+    /// - New payer's USDC account is invalid or hasn't approved the program delegate
+    /// - New payer already has an agreement for the payment terms
+    /// - `renewal_bucket` is not the bucket of the agreement's next payment
+    pub fn accept_agreement_transfer(
+        ctx: Context<AcceptAgreementTransfer>,
+        args: AcceptAgreementTransferArgs,
+    ) -> Result<()> {
+        accept_agreement_transfer::handler(ctx, args)
+    }
+
+    /// Refund part or all of the last payment from the payee treasury to the payer
+    ///
+    /// Signed by the payee authority. The refund is recorded on the agreement and
+    /// emits `Refunded`, keeping chargeback-like flows visible to analytics.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Unauthorized attempt (wrong payee authority)
+    /// - Amount is zero or total refunds would exceed the last payment
+    /// - The payment is still held in escrow (use `refund_escrow`)
+    /// - Token accounts are invalid or the treasury has insufficient funds
+    pub fn refund_payment(ctx: Context<RefundPayment>, args: RefundPaymentArgs) -> Result<()> {
+        refund_payment::handler(ctx, args)
+    }
+
+    /// Release an escrowed first payment to the payee and platform treasuries
+    ///
+    /// Signed by the payee authority to confirm activation of an agreement started on
+    /// payment terms with an escrow window. Emits `EscrowReleased`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Unauthorized attempt (wrong payee authority) or the payee is frozen
+    /// - No first payment is held in escrow or the activation window has lapsed
+    /// - Escrow, treasury or mint accounts are invalid
+    pub fn confirm_activation(
+        ctx: Context<ConfirmActivation>,
+        args: ConfirmActivationArgs,
+    ) -> Result<()> {
+        confirm_activation::handler(ctx, args)
+    }
+
+    /// Refund an escrowed first payment once the activation window has lapsed
+    ///
+    /// Permissionless. Returns the payment to the payer, deactivates the agreement and
+    /// emits `EscrowRefunded`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No first payment is held in escrow or the activation window hasn't lapsed
+    /// - Escrow, payer or mint accounts are invalid
+    pub fn refund_escrow(ctx: Context<RefundEscrow>, args: RefundEscrowArgs) -> Result<()> {
+        refund_escrow::handler(ctx, args)
+    }
+
+    /// Claim damages from an agreement's security deposit
+    ///
+    /// Signed by the payee authority once the agreement is no longer active. Moves
+    /// `amount` of the held deposit to the payee treasury and emits `DepositClaimed`;
+    /// `close_agreement` refunds the rest.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Unauthorized attempt (wrong payee authority) or the payee is frozen
+    /// - The agreement is still active
+    /// - The agreement holds no deposit, or the amount is zero or exceeds it
+    /// - Escrow, treasury or mint accounts are invalid
+    pub fn claim_deposit(ctx: Context<ClaimDeposit>, args: ClaimDepositArgs) -> Result<()> {
+        claim_deposit::handler(ctx, args)
+    }
+
+    /// Set which keepers may execute a payee's payments
+    ///
+    /// With `open_execution` off and a non-empty allow-list, `execute_payment`
+    /// rejects keepers that are not on the list.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Unauthorized attempt (wrong payee authority)
+    /// - Too many keepers are listed
+    pub fn set_keeper_policy(
+        ctx: Context<SetKeeperPolicy>,
+        args: SetKeeperPolicyArgs,
+    ) -> Result<()> {
+        set_keeper_policy::handler(ctx, args)
+    }
+
+    /// Initiate payee authority transfer
+    ///
+    /// This begins a two-step transfer of a payee to a new authority, mirroring the
+    /// platform authority flow. The new authority must accept the transfer.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the payee authority
+    /// - A pending transfer already exists
+    /// - New authority is the same as current authority
+    pub fn transfer_payee_authority(
+        ctx: Context<TransferPayeeAuthority>,
+        args: TransferPayeeAuthorityArgs,
+    ) -> Result<()> {
+        transfer_payee_authority::handler(ctx, args)
+    }
+
+    /// Accept payee authority transfer
+    ///
+    /// This completes a two-step payee authority transfer. The new authority must
+    /// sign and provide its USDC ATA, which becomes the payee treasury.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No pending transfer exists
+    /// - Caller is not the pending authority
+    /// - New treasury is not the new authority's canonical USDC ATA
+    pub fn accept_payee_authority(
+        ctx: Context<AcceptPayeeAuthority>,
+        args: AcceptPayeeAuthorityArgs,
+    ) -> Result<()> {
+        accept_payee_authority::handler(ctx, args)
+    }
+
+    /// Cancel payee authority transfer
+    ///
+    /// This allows the current payee authority to cancel a pending authority transfer.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the payee authority
+    /// - No pending transfer exists to cancel
+    pub fn cancel_payee_authority_transfer(
+        ctx: Context<CancelPayeeAuthorityTransfer>,
+        args: CancelPayeeAuthorityTransferArgs,
+    ) -> Result<()> {
+        cancel_payee_authority_transfer::handler(ctx, args)
+    }
+
+    /// Reserve a waitlist slot on payment terms with a subscriber cap
+    ///
+    /// Creates a `SlotReservation` recording the payer's waitlist position, so
+    /// limited-capacity offerings can queue payers while the terms are full.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Program is paused or the payee is frozen
+    /// - Payment terms have no subscriber cap
+    /// - Payer already holds a reservation for these terms
+    pub fn reserve_slot(ctx: Context<ReserveSlot>, args: ReserveSlotArgs) -> Result<()> {
+        reserve_slot::handler(ctx, args)
+    }
+
     /// Close a paused payment agreement account and reclaim rent
     ///
     /// This instruction allows payers to close their payment agreement accounts
     /// after pausing to reclaim the rent (~0.00099792 SOL). The payment agreement
-    /// must be inactive (paused) before it can be closed.
+    /// must be inactive (paused) before it can be closed. A held security deposit,
+    /// minus claimed damages, is refunded to the payer and emits `DepositReleased`.
     ///
     /// # Errors
     /// Returns an error if:
     /// - Payment agreement is still active (must be paused first)
+    /// - A deposit is held and the last paid period hasn't ended
+    /// - Deposit escrow, payer or mint accounts are invalid
     /// - Unauthorized closure attempt (wrong payer)
     /// - Payment agreement does not exist or is invalid
     /// - Account closure operations fail
@@ -174,6 +521,25 @@ pub mod tally_protocol {
         close_agreement::handler(ctx, args)
     }
 
+    /// Close several inactive payment agreements of the signer and reclaim their rent
+    ///
+    /// The agreements are passed as writable remaining accounts, up to
+    /// `MAX_CLOSE_BATCH_AGREEMENTS` per transaction. Agreements holding a security
+    /// deposit must be closed with `close_agreement` so the deposit is refunded.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No agreements or more than `MAX_CLOSE_BATCH_AGREEMENTS` are passed
+    /// - An agreement does not exist, is not at its PDA or belongs to another payer
+    /// - An agreement is still active (must be paused first)
+    /// - An agreement holds a security deposit
+    pub fn close_agreements_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseAgreementsBatch<'info>>,
+        args: CloseAgreementsBatchArgs,
+    ) -> Result<()> {
+        close_agreements_batch::handler(ctx, args)
+    }
+
     /// Admin function to withdraw accumulated platform fees
     ///
     /// # Errors
@@ -243,11 +609,13 @@ pub mod tally_protocol {
     ///
     /// This enables the emergency pause mechanism, disabling all user-facing operations
     /// (`start_agreement`, `execute_payment`, `create_payment_terms`) while allowing admin
-    /// operations to continue for emergency fund recovery.
+    /// operations to continue for emergency fund recovery. The reason is stored for
+    /// integrators to display, and an optional `auto_unpause_ts` ends the pause on schedule.
     ///
     /// # Errors
     /// Returns an error if:
     /// - Caller is not the platform authority
+    /// - Reason is not valid UTF-8 or `auto_unpause_ts` is not in the future
     pub fn pause(ctx: Context<Pause>, args: PauseArgs) -> Result<()> {
         pause::handler(ctx, args)
     }
@@ -264,6 +632,74 @@ pub mod tally_protocol {
         unpause::handler(ctx, args)
     }
 
+    /// Freeze a single payee
+    ///
+    /// Blocks `start_agreement` and `execute_payment` for the payee (e.g. during a
+    /// fraud investigation) without pausing the rest of the platform.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the platform authority
+    pub fn freeze_payee(ctx: Context<FreezePayee>, args: FreezePayeeArgs) -> Result<()> {
+        freeze_payee::handler(ctx, args)
+    }
+
+    /// Unfreeze a previously frozen payee
+    ///
+    /// Re-enables `start_agreement` and `execute_payment` for the payee.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the platform authority
+    pub fn unfreeze_payee(ctx: Context<UnfreezePayee>, args: UnfreezePayeeArgs) -> Result<()> {
+        unfreeze_payee::handler(ctx, args)
+    }
+
+    /// Set or end a payee's fee holiday
+    ///
+    /// Lowers the payee's platform fee by `rebate_bps` until `until_ts` for promotional
+    /// or enterprise deals, without changing its volume tier.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the platform authority
+    /// - `rebate_bps` exceeds the maximum platform fee
+    pub fn set_fee_holiday(ctx: Context<SetFeeHoliday>, args: SetFeeHolidayArgs) -> Result<()> {
+        set_fee_holiday::handler(ctx, args)
+    }
+
+    /// Set or disable the rate for keepers taking their fee in SOL
+    ///
+    /// Keepers taking their fee in SOL are paid from the keeper fee vault in lamports at
+    /// this rate, and their USDC fee goes to the platform treasury.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the platform authority
+    /// - The rate is zero
+    pub fn set_keeper_sol_rate(
+        ctx: Context<SetKeeperSolRate>,
+        args: SetKeeperSolRateArgs,
+    ) -> Result<()> {
+        set_keeper_sol_rate::handler(ctx, args)
+    }
+
+    /// Move the test clock used by `execute_payment` due-date checks forward
+    ///
+    /// Only available in localnet and devnet builds with the `test-clock` feature.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the platform authority
+    /// - `secs` is zero or the offset overflows
+    #[cfg(feature = "test-clock")]
+    pub fn advance_test_clock(
+        ctx: Context<AdvanceTestClock>,
+        args: AdvanceTestClockArgs,
+    ) -> Result<()> {
+        advance_test_clock::handler(ctx, args)
+    }
+
     /// Update global configuration parameters
     ///
     /// This allows the platform authority to update global configuration parameters
@@ -274,6 +710,7 @@ pub mod tally_protocol {
     /// - Caller is not the platform authority
     /// - `keeper_fee_bps` exceeds 100 (1%)
     /// - `min_platform_fee_bps` > `max_platform_fee_bps`
+    /// - `renewal_tolerance_secs` exceeds `MAX_RENEWAL_TOLERANCE_SECS` (1 hour)
     /// - Any value is zero where positive values are required
     /// - No fields are provided for update
     pub fn update_config(
@@ -283,6 +720,62 @@ pub mod tally_protocol {
         update_config::handler(ctx, args)
     }
 
+    /// Schedule a future price and/or period change for payment terms
+    ///
+    /// The change takes effect at `effective_ts`, which must be at least one full
+    /// current payment period in the future. Payments before then are charged at
+    /// the current terms and emit `PendingTermsChangeNotice`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the payee authority
+    /// - No fields are provided for update (at least one required)
+    /// - New price is zero or exceeds maximum
+    /// - New period is below minimum period from config
+    /// - `effective_ts` is less than one full period in the future
+    /// - The payment terms are streaming terms
+    pub fn schedule_terms_update(
+        ctx: Context<ScheduleTermsUpdate>,
+        args: ScheduleTermsUpdateArgs,
+    ) -> Result<()> {
+        schedule_terms_update::handler(ctx, args)
+    }
+
+    /// Deactivate payment terms with notice to active payers
+    ///
+    /// The terms stop accepting new agreements immediately. Existing agreements keep
+    /// renewing until their first payment due at or after `sunset_ts`, which
+    /// `execute_payment` pauses instead of charging, emitting `TermsSunset`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Caller is not the payee authority
+    /// - Payment terms are already deactivated
+    /// - Terms have active agreements and `sunset_ts` is less than one full period
+    ///   in the future
+    pub fn deactivate_payment_terms(
+        ctx: Context<DeactivatePaymentTerms>,
+        args: DeactivatePaymentTermsArgs,
+    ) -> Result<()> {
+        deactivate_payment_terms::handler(ctx, args)
+    }
+
+    /// Upgrade a Config, Payee, `PaymentTerms` or `PaymentAgreement` account to the
+    /// current layout version
+    ///
+    /// Accounts created by earlier program versions are resized to the current layout,
+    /// with the payer covering the extra rent, and get their `version` byte set.
+    /// Permissionless, since the upgrade doesn't change what the account means.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The account is not one of the versioned account types
+    /// - The account is already at the current layout version
+    /// - The payer cannot cover the rent for the larger account
+    pub fn migrate_account(ctx: Context<MigrateAccount>, args: MigrateAccountArgs) -> Result<()> {
+        migrate_account::handler(ctx, args)
+    }
+
     // TODO: Implement update_payment_terms instruction
     // /// Update payment terms pricing and period
     // ///
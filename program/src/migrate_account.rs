@@ -0,0 +1,109 @@
+use crate::{errors::RecurringPaymentError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MigrateAccountArgs {
+    // No args needed; the layout is chosen by the account discriminator
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// Config, Payee, `PaymentTerms` or `PaymentAgreement` account to upgrade
+    /// CHECK: Owner is checked here; the type is matched by discriminator and the data
+    /// deserialized in the handler
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// Pays the rent for the bytes the current layout adds
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for upgrading an account to the current layout version
+///
+/// Permissionless: the upgrade only resizes the account and rewrites it in the current
+/// layout, so anyone willing to pay the extra rent can migrate accounts ahead of the
+/// instructions that read them.
+///
+/// # Errors
+/// Returns an error if:
+/// - The account is not a Config, Payee, `PaymentTerms` or `PaymentAgreement`
+/// - The account is already at the current layout version
+/// - The payer cannot cover the rent for the larger account
+pub fn handler(ctx: Context<MigrateAccount>, _args: MigrateAccountArgs) -> Result<()> {
+    let account_info = ctx.accounts.account.to_account_info();
+    let discriminator: [u8; 8] = account_info
+        .try_borrow_data()?
+        .get(..8)
+        .and_then(|discriminator| discriminator.try_into().ok())
+        .ok_or(RecurringPaymentError::NotMigratable)?;
+
+    let (from_version, to_version) = match discriminator {
+        d if d == Config::DISCRIMINATOR => migrate::<Config>(&ctx, &account_info)?,
+        d if d == Payee::DISCRIMINATOR => migrate::<Payee>(&ctx, &account_info)?,
+        d if d == PaymentTerms::DISCRIMINATOR => migrate::<PaymentTerms>(&ctx, &account_info)?,
+        d if d == PaymentAgreement::DISCRIMINATOR => {
+            migrate::<PaymentAgreement>(&ctx, &account_info)?
+        }
+        _ => return Err(RecurringPaymentError::NotMigratable.into()),
+    };
+
+    msg!(
+        "Account {} migrated from layout version {} to {}",
+        account_info.key(),
+        from_version,
+        to_version
+    );
+
+    Ok(())
+}
+
+/// Grows the account to `T::LEN`, topping up its rent, and upgrades its data
+fn migrate<'info, T: VersionedAccount>(
+    ctx: &Context<MigrateAccount<'info>>,
+    account_info: &AccountInfo<'info>,
+) -> Result<(u8, u8)> {
+    if account_info.data_len() < T::LEN {
+        let rent_shortfall = Rent::get()?
+            .minimum_balance(T::LEN)
+            .saturating_sub(account_info.lamports());
+        if rent_shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: account_info.clone(),
+                    },
+                ),
+                rent_shortfall,
+            )?;
+        }
+        account_info.resize(T::LEN)?;
+    }
+
+    let from_version = T::upgrade(&mut account_info.try_borrow_mut_data()?)?;
+    Ok((from_version, T::VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_account_args_serialization() {
+        let args = MigrateAccountArgs {};
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = MigrateAccountArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(
+            serialized,
+            deserialized.try_to_vec().unwrap(),
+            "Empty args should round-trip"
+        );
+    }
+}
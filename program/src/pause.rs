@@ -5,7 +5,12 @@ use anchor_lang::prelude::*;
 
 /// Arguments for pausing the program
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct PauseArgs {}
+pub struct PauseArgs {
+    /// Reason for the pause as zero-padded UTF-8 (e.g. "Scheduled maintenance")
+    pub reason: [u8; 64],
+    /// Optional Unix timestamp at which the pause lapses automatically
+    pub auto_unpause_ts: Option<i64>,
+}
 
 /// Accounts required for pausing the program
 #[derive(Accounts)]
@@ -29,6 +34,10 @@ pub struct Pause<'info> {
 /// (`start_agreement`, `execute_payment`, `create_payment_terms`) while allowing admin
 /// operations to continue for emergency fund recovery.
 ///
+/// The reason is stored on the Config account so integrators can show why the platform
+/// is paused. When `auto_unpause_ts` is set, the pause lapses at that time without an
+/// `unpause` call. Pausing while already paused replaces the reason and resume time.
+///
 /// # Security
 /// - Only `platform_authority` can pause the program
 /// - Pause state is stored in the Config account
@@ -37,19 +46,36 @@ pub struct Pause<'info> {
 /// # Errors
 /// Returns an error if:
 /// - Caller is not the platform authority
-pub fn handler(ctx: Context<Pause>, _args: PauseArgs) -> Result<()> {
+/// - Reason is not valid UTF-8
+/// - `auto_unpause_ts` is not in the future
+pub fn handler(ctx: Context<Pause>, args: PauseArgs) -> Result<()> {
     let config = &mut ctx.accounts.config;
 
+    // Get current timestamp for validation and event
+    let clock = Clock::get()?;
+
+    require!(
+        core::str::from_utf8(&args.reason).is_ok(),
+        RecurringPaymentError::InvalidConfiguration
+    );
+    if let Some(auto_unpause_ts) = args.auto_unpause_ts {
+        require!(
+            auto_unpause_ts > clock.unix_timestamp,
+            RecurringPaymentError::InvalidConfiguration
+        );
+    }
+
     // Set paused state to true
     config.paused = true;
-
-    // Get current timestamp for event
-    let clock = Clock::get()?;
+    config.pause_reason = args.reason;
+    config.auto_unpause_ts = args.auto_unpause_ts;
 
     // Emit ProgramPaused event
     emit!(ProgramPaused {
         authority: ctx.accounts.platform_authority.key(),
         timestamp: clock.unix_timestamp,
+        reason: args.reason,
+        auto_unpause_ts: args.auto_unpause_ts,
     });
 
     msg!(
@@ -64,21 +90,31 @@ pub fn handler(ctx: Context<Pause>, _args: PauseArgs) -> Result<()> {
 mod tests {
     use super::*;
 
+    fn args() -> PauseArgs {
+        let mut reason = [0u8; 64];
+        reason[..21].copy_from_slice(b"Scheduled maintenance");
+        PauseArgs {
+            reason,
+            auto_unpause_ts: Some(1_700_003_600),
+        }
+    }
+
     #[test]
     fn test_pause_args_serialization() {
-        let args = PauseArgs {};
+        let args = args();
 
         // Test serialization round-trip
         let serialized = args.try_to_vec().unwrap();
         let deserialized: PauseArgs = PauseArgs::try_from_slice(&serialized).unwrap();
 
-        // PauseArgs has no fields, so just verify it deserializes successfully
-        let _ = deserialized;
+        assert_eq!(serialized.len(), 64 + 9);
+        assert_eq!(deserialized.reason, args.reason);
+        assert_eq!(deserialized.auto_unpause_ts, Some(1_700_003_600));
     }
 
     #[test]
     fn test_pause_args_clone() {
-        let args = PauseArgs {};
+        let args = args();
 
         // Verify clone trait is implemented
         #[allow(clippy::redundant_clone)]
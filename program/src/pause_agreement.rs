@@ -88,10 +88,14 @@ pub struct PauseAgreement<'info> {
     )]
     pub payment_agreement: Account<'info, PaymentAgreement>,
 
+    /// Mutable so the agreement's subscriber slot can be released
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
     pub payment_terms: Account<'info, PaymentTerms>,
 
+    /// Mutable so the payee-wide agreement count can be updated
     #[account(
-        seeds = [b"payee", payee.authority.as_ref()],
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
         bump = payee.bump
     )]
     pub payee: Account<'info, Payee>,
@@ -116,8 +120,15 @@ pub struct PauseAgreement<'info> {
 
 pub fn handler(ctx: Context<PauseAgreement>, _args: PauseAgreementArgs) -> Result<()> {
     let payment_agreement = &mut ctx.accounts.payment_agreement;
-    let payment_terms = &ctx.accounts.payment_terms;
-    let payee = &ctx.accounts.payee;
+    let payment_terms = &mut ctx.accounts.payment_terms;
+    let payee = &mut ctx.accounts.payee;
+
+    // An escrowed first payment is resolved by confirm_activation or refund_escrow;
+    // pausing would credit a period the payee has not been paid for
+    require!(
+        !payment_agreement.escrow_pending(),
+        RecurringPaymentError::EscrowPending
+    );
 
     // Deserialize and validate payer's token account
     let subscriber_ata_data: TokenAccount =
@@ -162,23 +173,43 @@ pub fn handler(ctx: Context<PauseAgreement>, _args: PauseAgreementArgs) -> Resul
     //
     // We only revoke if the current delegate matches our program's global delegate PDA.
     // This prevents revoking unrelated delegations to other programs.
-    if let Some(current_delegate) = Option::<Pubkey>::from(subscriber_ata_data.delegate) {
-        if current_delegate == expected_delegate_pda {
-            let revoke_accounts = Revoke {
-                source: ctx.accounts.payer_usdc_ata.to_account_info(),
-                authority: ctx.accounts.payer.to_account_info(),
-            };
-
-            token::revoke(CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                revoke_accounts,
-            ))?;
-        }
-        // If delegate is not ours, skip revocation (already revoked or delegated elsewhere)
+    //
+    // Streaming agreements keep the delegate so keepers can still sweep the accrual up
+    // to the pause; accrual stops at `paused_at_ts`, and resume_agreement collects
+    // whatever is left unswept.
+    let current_delegate = Option::<Pubkey>::from(subscriber_ata_data.delegate);
+    if !payment_terms.is_streaming() && current_delegate == Some(expected_delegate_pda) {
+        let revoke_accounts = Revoke {
+            source: ctx.accounts.payer_usdc_ata.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+
+        token::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            revoke_accounts,
+        ))?;
     }
+    // If delegate is not ours, skip revocation (already revoked or delegated elsewhere)
 
     // Make it idempotent - it's safe to "cancel" an already canceled payment_agreement
-    // No need to check if already canceled, just set active = false
+    // No need to check if already canceled, just set active = false. Only an active
+    // agreement holds a subscriber slot, so only release it on the first pause.
+    if payment_agreement.active {
+        payment_terms.release_subscriber_slot();
+        payee.release_subscriber_slot();
+
+        // Credit the unused part of the current period so it isn't lost; resume_agreement
+        // applies it against the first charge after the pause
+        let current_time = Clock::get()?.unix_timestamp;
+        let credit = payment_agreement
+            .unused_period_value(current_time, payment_terms.period_secs)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        payment_agreement.credit_amount = payment_agreement
+            .credit_amount
+            .checked_add(credit)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        payment_agreement.paused_at_ts = Some(current_time);
+    }
     payment_agreement.active = false;
 
     // Emit PaymentAgreementPaused event
@@ -187,6 +218,7 @@ pub fn handler(ctx: Context<PauseAgreement>, _args: PauseAgreementArgs) -> Resul
         payment_terms: payment_terms.key(),
         payer: ctx.accounts.payer.key(),
     });
+    emit!(payment_agreement.snapshot(payment_agreement.key()));
 
     Ok(())
 }
@@ -0,0 +1,173 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::EscrowRefunded;
+use crate::state::{Payee, PaymentAgreement, PaymentTerms};
+use crate::utils::validate_escrow_ata;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Arguments for refunding an escrowed first payment after the activation window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RefundEscrowArgs {
+    // No args needed for refunding an escrow
+}
+
+/// Accounts required for refunding an escrowed first payment
+#[derive(Accounts)]
+pub struct RefundEscrow<'info> {
+    /// Payment agreement whose first payment is held in escrow
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payment_agreement.payer.as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    /// Mutable so the agreement's subscriber slot can be released
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    /// Mutable so the payee-wide agreement count can be updated
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Anyone may return a lapsed escrow to the payer
+    pub caller: Signer<'info>,
+
+    /// Program delegate's USDC ATA holding the escrowed payment
+    /// CHECK: Validated as the delegate's USDC ATA in handler
+    #[account(mut)]
+    pub escrow_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as the payer's USDC token account in handler
+    #[account(mut)]
+    pub payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as USDC mint in handler
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// Program PDA that owns the escrow ATA
+    /// CHECK: PDA derived from program seeds
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for returning an escrowed first payment to the payer
+///
+/// Permissionless once the activation window has lapsed without the payee confirming,
+/// so payers (or keepers on their behalf) can always recover the payment. The agreement
+/// is deactivated and releases its subscriber slot; the refund is recorded against
+/// `last_amount` like a payee refund.
+///
+/// # Errors
+/// Returns an error if:
+/// - No first payment is held in escrow or the activation window hasn't lapsed
+/// - Escrow, payer or mint accounts are invalid or use the wrong mint
+pub fn handler(ctx: Context<RefundEscrow>, _args: RefundEscrowArgs) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let payee = &ctx.accounts.payee;
+
+    let escrow = ctx
+        .accounts
+        .payment_agreement
+        .escrow
+        .ok_or(RecurringPaymentError::NoEscrowPending)?;
+    require!(
+        current_time >= escrow.expires_ts,
+        RecurringPaymentError::EscrowNotExpired
+    );
+
+    validate_escrow_ata(
+        &ctx.accounts.escrow_ata,
+        &ctx.accounts.program_delegate.key(),
+        &payee.usdc_mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    let payer_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    let usdc_mint_data: Mint =
+        Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+    if payer_ata_data.owner != ctx.accounts.payment_agreement.payer {
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    if ctx.accounts.usdc_mint.key() != payee.usdc_mint || payer_ata_data.mint != payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    let delegate_bump = ctx.bumps.program_delegate;
+    let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+
+    let transfer_to_payer = TransferChecked {
+        from: ctx.accounts.escrow_ata.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.payer_usdc_ata.to_account_info(),
+        authority: ctx.accounts.program_delegate.to_account_info(),
+    };
+
+    token::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_to_payer,
+            delegate_seeds,
+        ),
+        escrow.amount,
+        usdc_mint_data.decimals,
+    )?;
+
+    // The agreement never activated: stop it renewing and free its slot. Like a
+    // paused agreement, it stays in its renewal queue bucket and keepers skip it.
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    if payment_agreement.active {
+        ctx.accounts.payment_terms.release_subscriber_slot();
+        ctx.accounts.payee.release_subscriber_slot();
+    }
+    payment_agreement.active = false;
+    payment_agreement.escrow = None;
+    payment_agreement.refunded_amount = payment_agreement
+        .refunded_amount
+        .saturating_add(escrow.amount)
+        .min(payment_agreement.last_amount);
+
+    emit!(EscrowRefunded {
+        payee: ctx.accounts.payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: payment_agreement.payer,
+        amount: escrow.amount,
+        expires_ts: escrow.expires_ts,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refund_escrow_args_serialization() {
+        let args = RefundEscrowArgs {};
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: RefundEscrowArgs = RefundEscrowArgs::try_from_slice(&serialized).unwrap();
+
+        // RefundEscrowArgs has no fields, so just verify it deserializes successfully
+        let _ = deserialized;
+    }
+}
@@ -0,0 +1,163 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::Refunded;
+use crate::state::{Payee, PaymentAgreement, PaymentTerms};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Arguments for refunding part or all of the last payment to the payer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct RefundPaymentArgs {
+    /// Amount to refund in USDC microlamports
+    pub amount: u64,
+}
+
+/// Accounts required for refunding a payment
+#[derive(Accounts)]
+pub struct RefundPayment<'info> {
+    /// Payment agreement the refunded payment was charged under
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payment_agreement.payer.as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    #[account(has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Payee authority (must sign, owns the treasury ATA)
+    pub authority: Signer<'info>,
+
+    /// CHECK: Validated as payee treasury ATA in handler
+    #[account(mut)]
+    pub payee_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as the payer's USDC token account in handler
+    #[account(mut)]
+    pub payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as USDC mint in handler
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for refunding part or all of the last payment to the payer
+///
+/// Transfers `amount` from the payee treasury back to the payer's USDC account,
+/// signed by the payee authority. Refunds are recorded on the agreement so the total
+/// refunded against the last payment never exceeds `last_amount`; the next charge
+/// resets the allowance. Refunds are allowed while the payee is frozen or the
+/// agreement is paused so payers can always be made whole.
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the payee authority
+/// - Amount is zero or would take total refunds above the last payment
+/// - Treasury or payer token accounts are invalid or use the wrong mint
+/// - Treasury balance is insufficient
+pub fn handler(ctx: Context<RefundPayment>, args: RefundPaymentArgs) -> Result<()> {
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    let payee = &ctx.accounts.payee;
+
+    require!(args.amount > 0, RecurringPaymentError::InvalidAmount);
+
+    // An escrowed payment hasn't reached the treasury; refund_escrow returns it instead
+    require!(
+        !payment_agreement.escrow_pending(),
+        RecurringPaymentError::EscrowPending
+    );
+
+    let total_refunded = payment_agreement
+        .refunded_amount
+        .checked_add(args.amount)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    require!(
+        total_refunded <= payment_agreement.last_amount,
+        RecurringPaymentError::RefundExceedsLastPayment
+    );
+
+    // Deserialize and validate token accounts with specific error handling
+    let payee_treasury_data: TokenAccount = TokenAccount::try_deserialize(
+        &mut ctx.accounts.payee_treasury_ata.data.borrow().as_ref(),
+    )
+    .map_err(|_| RecurringPaymentError::InvalidPayeeTreasuryAccount)?;
+
+    let payer_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    let usdc_mint_data: Mint =
+        Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+    if ctx.accounts.payee_treasury_ata.key() != payee.treasury_ata {
+        return Err(RecurringPaymentError::BadSeeds.into());
+    }
+
+    if payer_ata_data.owner != payment_agreement.payer {
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    if ctx.accounts.usdc_mint.key() != payee.usdc_mint
+        || payee_treasury_data.mint != payee.usdc_mint
+        || payer_ata_data.mint != payee.usdc_mint
+    {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if payee_treasury_data.amount < args.amount {
+        return Err(RecurringPaymentError::InsufficientFunds.into());
+    }
+
+    let transfer_to_payer = TransferChecked {
+        from: ctx.accounts.payee_treasury_ata.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.payer_usdc_ata.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+
+    token::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_to_payer),
+        args.amount,
+        usdc_mint_data.decimals,
+    )?;
+
+    payment_agreement.refunded_amount = total_refunded;
+
+    emit!(Refunded {
+        payee: payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: payment_agreement.payer,
+        amount: args.amount,
+        period_index: payment_agreement.period_index,
+        total_refunded,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refund_payment_args_serialization() {
+        let args = RefundPaymentArgs { amount: 2_500_000 };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: RefundPaymentArgs =
+            RefundPaymentArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.amount, 2_500_000);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::SlotReserved;
+use crate::state::{Config, Payee, PaymentTerms, SlotReservation};
+use anchor_lang::prelude::*;
+
+/// Arguments for reserving a waitlist slot on capped payment terms
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReserveSlotArgs {}
+
+/// Accounts required for reserving a waitlist slot
+#[derive(Accounts)]
+pub struct ReserveSlot<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Waitlist reservation for this payer (one per payment terms and payer)
+    #[account(
+        init,
+        payer = payer,
+        space = SlotReservation::SPACE,
+        seeds = [b"slot_reservation", payment_terms.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub slot_reservation: Account<'info, SlotReservation>,
+
+    /// Capped payment terms being waitlisted (mutable to assign the next position)
+    #[account(
+        mut,
+        has_one = payee @ RecurringPaymentError::Unauthorized,
+        constraint = payment_terms.max_subscribers.is_some() @ RecurringPaymentError::InvalidPaymentTerms
+    )]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Payer reserving the slot (pays rent for the reservation)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for reserving a waitlist slot on capped payment terms
+///
+/// Records the payer's place in line as a `SlotReservation` PDA so limited-capacity
+/// offerings (cohorts, communities) can queue payers on-chain. Positions are assigned
+/// in reservation order; payees admit waitlisted payers off-chain as slots open. The
+/// reservation itself does not hold a slot, so `start_agreement` still returns
+/// `TermsFull` while the terms are at capacity.
+///
+/// # Errors
+/// Returns an error if:
+/// - The program is paused
+/// - Payment terms have no subscriber cap
+/// - Payee is frozen
+/// - The payer already holds a reservation for these terms
+pub fn handler(ctx: Context<ReserveSlot>, _args: ReserveSlotArgs) -> Result<()> {
+    let clock = Clock::get()?;
+    let payment_terms = &mut ctx.accounts.payment_terms;
+
+    let position = payment_terms.waitlist_len;
+    payment_terms.waitlist_len = position
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    let slot_reservation = &mut ctx.accounts.slot_reservation;
+    slot_reservation.payment_terms = payment_terms.key();
+    slot_reservation.payer = ctx.accounts.payer.key();
+    slot_reservation.position = position;
+    slot_reservation.reserved_ts = clock.unix_timestamp;
+    slot_reservation.bump = ctx.bumps.slot_reservation;
+
+    emit!(SlotReserved {
+        payment_terms: payment_terms.key(),
+        payee: ctx.accounts.payee.key(),
+        payer: ctx.accounts.payer.key(),
+        position,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_slot_args_serialization() {
+        let args = ReserveSlotArgs {};
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: ReserveSlotArgs = ReserveSlotArgs::try_from_slice(&serialized).unwrap();
+
+        // ReserveSlotArgs has no fields, so just verify it deserializes successfully
+        let _ = deserialized;
+    }
+}
@@ -0,0 +1,184 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::AgreementUnsuspended;
+use crate::state::{Config, Payee, PaymentAgreement, PaymentTerms, RenewalQueue, SuspensionReason};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Arguments for reinstating an agreement suspended for a frozen token account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ResumeAfterUnfreezeArgs {
+    /// Renewal queue bucket of the agreement's next payment
+    /// (`max(next_payment_ts, now) / RENEWAL_BUCKET_SECS`), used to derive
+    /// `renewal_queue`
+    pub renewal_bucket: u64,
+}
+
+/// Accounts required for reinstating a suspended agreement
+#[derive(Accounts)]
+#[instruction(args: ResumeAfterUnfreezeArgs)]
+pub struct ResumeAfterUnfreeze<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payment agreement suspended by `execute_payment`
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payment_agreement.payer.as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    /// Mutable so the agreement's subscriber slot can be claimed again
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    /// Mutable so the payee-wide agreement count can be updated
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Anyone may reinstate an agreement once the payer's account is thawed; pays for
+    /// the renewal queue if it doesn't exist yet
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// CHECK: Validated as the payer's USDC token account in handler
+    pub payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// Crank index shard for the bucket of the agreement's next payment
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = RenewalQueue::SPACE,
+        seeds = [
+            b"renewal_queue",
+            args.renewal_bucket.to_le_bytes().as_ref(),
+            RenewalQueue::shard_seed(&payment_agreement.key()).as_ref()
+        ],
+        bump
+    )]
+    pub renewal_queue: Account<'info, RenewalQueue>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for reinstating an agreement suspended for a frozen payer token account
+///
+/// Permissionless, so keepers can reinstate agreements as soon as the freeze authority
+/// thaws the payer's account. Nothing is charged: the agreement becomes due at its
+/// original `next_payment_ts`, or immediately if that has passed, so billing periods
+/// missed while the account was frozen are not charged retroactively.
+///
+/// # Errors
+/// Returns an error if:
+/// - The agreement was not suspended for a frozen token account
+/// - The payer token account is invalid, not the payer's, or still frozen
+/// - The payment terms are full or past their sunset
+/// - The renewal queue does not match the agreement's next payment
+pub fn handler(ctx: Context<ResumeAfterUnfreeze>, args: ResumeAfterUnfreezeArgs) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let payee = &ctx.accounts.payee;
+
+    require!(
+        ctx.accounts.payment_agreement.suspension == Some(SuspensionReason::FrozenAccount),
+        RecurringPaymentError::NotSuspended
+    );
+
+    require!(
+        ctx.accounts.payer_usdc_ata.owner == &ctx.accounts.token_program.key(),
+        RecurringPaymentError::InvalidPayerTokenAccount
+    );
+    let payer_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    if payer_ata_data.owner != ctx.accounts.payment_agreement.payer {
+        return Err(RecurringPaymentError::WrongOwner.into());
+    }
+
+    if payer_ata_data.mint != payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    require!(
+        !payer_ata_data.is_frozen(),
+        RecurringPaymentError::PayerAccountFrozen
+    );
+
+    // Claim the subscriber slot released on suspension, as resume_agreement does
+    {
+        let payment_terms = &mut ctx.accounts.payment_terms;
+        require!(
+            !payment_terms.is_sunset(current_time),
+            RecurringPaymentError::TermsDeactivated
+        );
+        require!(!payment_terms.is_full(), RecurringPaymentError::TermsFull);
+        payment_terms.active_agreements = payment_terms
+            .active_agreements
+            .checked_add(1)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        let payee = &mut ctx.accounts.payee;
+        payee.active_agreements = payee
+            .active_agreements
+            .checked_add(1)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+    }
+
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    let next_payment_ts = payment_agreement.next_payment_ts.max(current_time);
+    payment_agreement.active = true;
+    payment_agreement.suspension = None;
+    payment_agreement.next_payment_ts = next_payment_ts;
+
+    // Index the agreement under the bucket of its next payment for keepers
+    require!(
+        RenewalQueue::bucket_for(next_payment_ts) == Some(args.renewal_bucket),
+        RecurringPaymentError::InvalidRenewalBucket
+    );
+    let renewal_queue = &mut ctx.accounts.renewal_queue;
+    renewal_queue.bucket = args.renewal_bucket;
+    renewal_queue.shard = RenewalQueue::shard_for(&payment_agreement.key());
+    renewal_queue.bump = ctx.bumps.renewal_queue;
+    renewal_queue.insert(payment_agreement.key());
+
+    emit!(AgreementUnsuspended {
+        payee: ctx.accounts.payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: payment_agreement.payer,
+        next_payment_ts,
+        timestamp: current_time,
+    });
+    emit!(payment_agreement.snapshot(payment_agreement.key()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_after_unfreeze_args_serialization() {
+        let args = ResumeAfterUnfreezeArgs {
+            renewal_bucket: 19_675,
+        };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: ResumeAfterUnfreezeArgs =
+            ResumeAfterUnfreezeArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.renewal_bucket, 19_675);
+    }
+}
@@ -0,0 +1,471 @@
+use crate::{
+    errors::RecurringPaymentError,
+    events::*,
+    state::*,
+    utils::{
+        apply_gate_discount, calculate_fee_split, qualifying_gate_mint, record_payee_volume,
+        validate_platform_treasury, FeeSplit,
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Arguments for resuming a payment agreement the payer paused mid-period
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ResumeAgreementArgs {
+    /// Multiplier for allowance, raised to `config.default_allowance_periods` if lower,
+    /// as in `start_agreement`
+    pub allowance_periods: u8,
+    /// Renewal queue bucket of the agreement's next payment
+    /// (`(now + period_secs) / RENEWAL_BUCKET_SECS`), used to derive `renewal_queue`
+    pub renewal_bucket: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ResumeAgreementArgs)]
+pub struct ResumeAgreement<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payer.key().as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payer @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    /// Mutable so a scheduled terms update can be applied and a subscriber slot claimed
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    /// Mutable so the payee-wide agreement count and revenue can be updated
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+    )]
+    pub payee: Account<'info, Payee>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Validated as USDC token account in handler
+    #[account(mut)]
+    pub payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as payee treasury ATA in handler
+    #[account(mut)]
+    pub payee_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as platform treasury ATA in handler
+    #[account(mut)]
+    pub platform_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as USDC mint in handler
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA derived from program, validated in handler
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    /// Crank index shard for the bucket of the agreement's next payment
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RenewalQueue::SPACE,
+        seeds = [
+            b"renewal_queue",
+            args.renewal_bucket.to_le_bytes().as_ref(),
+            RenewalQueue::shard_seed(&payment_agreement.key()).as_ref()
+        ],
+        bump
+    )]
+    pub renewal_queue: Account<'info, RenewalQueue>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Resumes a paused agreement, charging a new billing period less the pause credit
+///
+/// On streaming terms, accrual from `last_sweep_ts` to the pause that no keeper swept
+/// is charged with the new period, so resuming never forgives it.
+///
+/// # Security
+///
+/// Token accounts, the platform treasury and the delegate PDA are validated exactly as
+/// in `start_agreement`. The credit applied is capped at the price of the period, so
+/// a resume never moves funds from the payee to the payer; any excess credit stays on
+/// the agreement for a later resume.
+///
+/// # Errors
+///
+/// Returns an error if the agreement is active or was not paused by the payer, the
+/// payment terms are full or past their sunset, token accounts or the delegate are invalid, the allowance
+/// is insufficient, or the renewal queue does not match the next payment.
+#[allow(clippy::too_many_lines)]
+pub fn handler(ctx: Context<ResumeAgreement>, args: ResumeAgreementArgs) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Apply a scheduled terms update once its effective timestamp has been reached
+    if let Some((old_amount, old_period)) =
+        ctx.accounts.payment_terms.apply_due_update(current_time)
+    {
+        let payment_terms = &ctx.accounts.payment_terms;
+        emit!(PaymentTermsUpdated {
+            payment_terms: payment_terms.key(),
+            payee: ctx.accounts.payee.key(),
+            old_amount: Some(old_amount),
+            new_amount: Some(payment_terms.amount_usdc),
+            old_period: Some(old_period),
+            new_period: Some(payment_terms.period_secs),
+            updated_by: ctx.accounts.payee.authority,
+        });
+    }
+
+    require!(
+        !ctx.accounts.payment_agreement.active,
+        RecurringPaymentError::AlreadyActive
+    );
+    let paused_at_ts = ctx
+        .accounts
+        .payment_agreement
+        .paused_at_ts
+        .ok_or(RecurringPaymentError::NotPaused)?;
+
+    // Enforce the subscriber cap and claim a slot. Payers may still resume on
+    // deactivated terms until the sunset, like any other agreement still renewing.
+    {
+        let payment_terms = &mut ctx.accounts.payment_terms;
+        require!(
+            !payment_terms.is_sunset(current_time),
+            RecurringPaymentError::TermsDeactivated
+        );
+        require!(!payment_terms.is_full(), RecurringPaymentError::TermsFull);
+        payment_terms.active_agreements = payment_terms
+            .active_agreements
+            .checked_add(1)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        let payee = &mut ctx.accounts.payee;
+        payee.active_agreements = payee
+            .active_agreements
+            .checked_add(1)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+    }
+
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    let payment_terms = &ctx.accounts.payment_terms;
+    let payee = &ctx.accounts.payee;
+
+    // Deserialize and validate token accounts with specific error handling
+    let subscriber_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    let merchant_treasury_data: TokenAccount = TokenAccount::try_deserialize(
+        &mut ctx.accounts.payee_treasury_ata.data.borrow().as_ref(),
+    )
+    .map_err(|_| RecurringPaymentError::InvalidPayeeTreasuryAccount)?;
+
+    let platform_treasury_data: TokenAccount = TokenAccount::try_deserialize(
+        &mut ctx.accounts.platform_treasury_ata.data.borrow().as_ref(),
+    )
+    .map_err(|_| RecurringPaymentError::InvalidPlatformTreasuryAccount)?;
+
+    let usdc_mint_data: Mint =
+        Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+    validate_platform_treasury(
+        &ctx.accounts.platform_treasury_ata,
+        &ctx.accounts.config.platform_authority,
+        &ctx.accounts.config.allowed_mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    if subscriber_ata_data.owner != ctx.accounts.payer.key() {
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    if subscriber_ata_data.mint != payee.usdc_mint
+        || merchant_treasury_data.mint != payee.usdc_mint
+        || platform_treasury_data.mint != payee.usdc_mint
+    {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if ctx.accounts.usdc_mint.key() != payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if ctx.accounts.payee_treasury_ata.key() != payee.treasury_ata {
+        return Err(RecurringPaymentError::BadSeeds.into());
+    }
+
+    // Token-gated pricing, as in start_agreement
+    let gate_mint = qualifying_gate_mint(
+        payment_terms,
+        &ctx.accounts.payer.key(),
+        ctx.remaining_accounts.first(),
+        &ctx.accounts.token_program.key(),
+    )?;
+    let payment_amount = if gate_mint.is_some() {
+        apply_gate_discount(payment_terms.amount_usdc, payment_terms.gate_discount_bps)?
+    } else {
+        payment_terms.amount_usdc
+    };
+
+    // Pausing revoked the delegate, so the payer must have re-approved a multi-period
+    // allowance just like on start
+    let allowance_periods = ctx.accounts.config.allowance_periods(args.allowance_periods);
+    let required_allowance = payment_terms
+        .amount_usdc
+        .checked_mul(u64::from(allowance_periods))
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    if subscriber_ata_data.delegated_amount < required_allowance {
+        return Err(RecurringPaymentError::InsufficientAllowance.into());
+    }
+
+    let (expected_delegate_pda, _expected_bump) =
+        Pubkey::find_program_address(&[b"delegate"], ctx.program_id);
+    require!(
+        ctx.accounts.program_delegate.key() == expected_delegate_pda,
+        RecurringPaymentError::BadSeeds
+    );
+
+    let actual_delegate = Option::<Pubkey>::from(subscriber_ata_data.delegate);
+    if actual_delegate != Some(expected_delegate_pda) {
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    // Offset the charge with the credit accrued when the agreement was paused
+    let credit_applied = payment_agreement.credit_amount.min(payment_amount);
+    let amount_charged = payment_amount
+        .checked_sub(credit_applied)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    // Streaming accrual up to the pause that no keeper swept is collected with the
+    // charge, since accrual restarts from the end of the new period
+    let swept_from_ts = payment_agreement.last_sweep_ts;
+    let (unswept_accrual, _) = match payment_terms.accrual_rate_per_sec {
+        Some(rate_per_sec) => payment_agreement
+            .sweepable(paused_at_ts, rate_per_sec, u64::MAX)
+            .ok_or(RecurringPaymentError::ArithmeticError)?,
+        None => (0, 0),
+    };
+    let total_charged = amount_charged
+        .checked_add(unswept_accrual)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    // PAYMENT PROCESSING
+    {
+        // The charge counts towards the payee's volume, as renewals do. No keeper is
+        // involved in a resume
+        record_payee_volume(&mut ctx.accounts.payee, total_charged, current_time);
+        let platform_fee_bps = ctx.accounts.payee.platform_fee_bps(current_time);
+        let FeeSplit {
+            platform_fee,
+            payee_amount: merchant_amount,
+            ..
+        } = calculate_fee_split(total_charged, 0, platform_fee_bps)?;
+
+        let delegate_bump = ctx.bumps.program_delegate;
+        let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+        let usdc_decimals = usdc_mint_data.decimals;
+
+        if merchant_amount > 0 {
+            let transfer_to_merchant = TransferChecked {
+                from: ctx.accounts.payer_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.payee_treasury_ata.to_account_info(),
+                authority: ctx.accounts.program_delegate.to_account_info(),
+            };
+
+            token::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    transfer_to_merchant,
+                    delegate_seeds,
+                ),
+                merchant_amount,
+                usdc_decimals,
+            )?;
+        }
+
+        if platform_fee > 0 {
+            let transfer_to_platform = TransferChecked {
+                from: ctx.accounts.payer_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.platform_treasury_ata.to_account_info(),
+                authority: ctx.accounts.program_delegate.to_account_info(),
+            };
+
+            token::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    transfer_to_platform,
+                    delegate_seeds,
+                ),
+                platform_fee,
+                usdc_decimals,
+            )?;
+        }
+    }
+
+    let period_i64 =
+        i64::try_from(payment_terms.period_secs).map_err(|_| RecurringPaymentError::ArithmeticError)?;
+    let next_payment_ts = current_time
+        .checked_add(period_i64)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    // Resuming continues the current session: the charge counts towards `max_periods`.
+    // `last_amount` records the full price of the period so a later pause credits
+    // its unused part at full value.
+    payment_agreement.active = true;
+    payment_agreement.next_payment_ts = next_payment_ts;
+    payment_agreement.last_amount = payment_amount;
+    payment_agreement.last_payment_ts = current_time;
+    payment_agreement.last_pull_period_index = payment_agreement
+        .pull_period_index(current_time)
+        .ok_or(RecurringPaymentError::ArithmeticError)?
+        .max(payment_agreement.last_pull_period_index);
+    payment_agreement.cancel_at_period_end = false;
+    payment_agreement.refunded_amount = 0;
+    payment_agreement.periods_paid = payment_agreement
+        .periods_paid
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    payment_agreement.paused_at_ts = None;
+    payment_agreement.credit_amount = payment_agreement
+        .credit_amount
+        .checked_sub(credit_applied)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    // Streaming agreements accrue again from the end of the period just charged
+    if payment_terms.is_streaming() {
+        payment_agreement.last_sweep_ts = next_payment_ts;
+    }
+    ctx.accounts.payment_terms.record_payment(total_charged, false);
+    ctx.accounts.payee.record_payment(total_charged, false);
+
+    // Final billing period of a limited agreement: complete it instead of scheduling
+    // another payment
+    let completed = payment_agreement.periods_exhausted();
+    if completed {
+        payment_agreement.active = false;
+        ctx.accounts.payment_terms.release_subscriber_slot();
+        ctx.accounts.payee.release_subscriber_slot();
+    } else {
+        // Index the agreement under the bucket of its next payment for keepers; streaming
+        // agreements are swept instead
+        require!(
+            RenewalQueue::bucket_for(next_payment_ts) == Some(args.renewal_bucket),
+            RecurringPaymentError::InvalidRenewalBucket
+        );
+        let renewal_queue = &mut ctx.accounts.renewal_queue;
+        renewal_queue.bucket = args.renewal_bucket;
+        renewal_queue.shard = RenewalQueue::shard_for(&payment_agreement.key());
+        renewal_queue.bump = ctx.bumps.renewal_queue;
+        if !ctx.accounts.payment_terms.is_streaming() {
+            renewal_queue.insert(payment_agreement.key());
+        }
+    }
+
+    let payment_agreement = &ctx.accounts.payment_agreement;
+    let payment_terms = &ctx.accounts.payment_terms;
+    let payee = &ctx.accounts.payee;
+
+    emit!(PaymentAgreementReactivated {
+        payee: payee.key(),
+        payment_terms: payment_terms.key(),
+        payer: ctx.accounts.payer.key(),
+        amount: total_charged,
+        total_payments: payment_agreement.payment_count,
+        original_created_ts: payment_agreement.created_ts,
+        external_ref_hash: payment_agreement.external_ref_hash,
+        delegated_amount: subscriber_ata_data.delegated_amount,
+    });
+
+    if unswept_accrual > 0 {
+        emit!(Swept {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: ctx.accounts.payer.key(),
+            keeper: ctx.accounts.payer.key(),
+            amount: unswept_accrual,
+            keeper_fee: 0,
+            from_ts: swept_from_ts,
+            to_ts: paused_at_ts,
+        });
+    }
+
+    if credit_applied > 0 {
+        emit!(CreditApplied {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: ctx.accounts.payer.key(),
+            amount: payment_amount,
+            credit_applied,
+            amount_charged,
+            remaining_credit: payment_agreement.credit_amount,
+            paused_at_ts,
+        });
+    }
+
+    if let Some(gate_mint) = gate_mint {
+        emit!(GateDiscountApplied {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: ctx.accounts.payer.key(),
+            gate_mint,
+            discount_bps: payment_terms.gate_discount_bps,
+            original_amount: payment_terms.amount_usdc,
+            discounted_amount: payment_amount,
+        });
+    }
+
+    if completed {
+        emit!(AgreementCompleted {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: ctx.accounts.payer.key(),
+            periods_paid: payment_agreement.periods_paid,
+            timestamp: current_time,
+        });
+    }
+
+    emit!(payment_agreement.snapshot(payment_agreement.key()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_agreement_args_serialization() {
+        let args = ResumeAgreementArgs {
+            allowance_periods: 3,
+            renewal_bucket: 19_675,
+        };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: ResumeAgreementArgs =
+            ResumeAgreementArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.allowance_periods, 3);
+        assert_eq!(deserialized.renewal_bucket, 19_675);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::CancellationScheduled;
+use crate::state::{Payee, PaymentAgreement, PaymentTerms};
+use anchor_lang::prelude::*;
+
+/// Arguments for scheduling cancellation at the end of the current period
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ScheduleCancellationArgs {}
+
+/// Accounts required for scheduling cancellation of a payment agreement
+#[derive(Accounts)]
+pub struct ScheduleCancellation<'info> {
+    /// Payment agreement to cancel at period end (must be active)
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payer.key().as_ref()],
+        bump = payment_agreement.bump,
+        has_one = payer @ RecurringPaymentError::Unauthorized,
+        has_one = payment_terms @ RecurringPaymentError::Unauthorized,
+        constraint = payment_agreement.active @ RecurringPaymentError::Inactive
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    #[account(has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Payer who owns the agreement (must sign)
+    pub payer: Signer<'info>,
+}
+
+/// Handler for scheduling cancellation at the end of the current period
+///
+/// Unlike `pause_agreement`, which stops the agreement immediately, the payer keeps
+/// the period they already paid for. The agreement stays active until
+/// `next_payment_ts`; the first `execute_payment` after that pauses it without
+/// charging. The delegate approval is left in place, since the program cannot
+/// revoke it without the payer's signature.
+///
+/// Scheduling again is idempotent.
+///
+/// # Errors
+/// Returns an error if:
+/// - Payment agreement is not active
+/// - Caller is not the agreement's payer
+/// - The payment terms are streaming terms, whose agreements have no period end and
+///   stop accruing when paused
+pub fn handler(ctx: Context<ScheduleCancellation>, _args: ScheduleCancellationArgs) -> Result<()> {
+    require!(
+        !ctx.accounts.payment_terms.is_streaming(),
+        RecurringPaymentError::StreamingTerms
+    );
+
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+
+    payment_agreement.cancel_at_period_end = true;
+
+    emit!(CancellationScheduled {
+        payee: ctx.accounts.payee.key(),
+        payment_terms: ctx.accounts.payment_terms.key(),
+        payer: ctx.accounts.payer.key(),
+        effective_ts: payment_agreement.next_payment_ts,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_cancellation_args_serialization() {
+        let args = ScheduleCancellationArgs {};
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: ScheduleCancellationArgs =
+            ScheduleCancellationArgs::try_from_slice(&serialized).unwrap();
+
+        // ScheduleCancellationArgs has no fields, so just verify it deserializes successfully
+        let _ = deserialized;
+    }
+}
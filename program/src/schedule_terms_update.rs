@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_PLAN_PRICE_USDC,
+    errors::RecurringPaymentError,
+    events::PaymentTermsUpdateScheduled,
+    state::{Config, Payee, PaymentTerms, PendingTermsUpdate},
+};
+
+/// Arguments for scheduling a future price and/or period change.
+///
+/// Fields left as `None` keep their current value. Scheduling again before the
+/// change takes effect replaces the pending update, subject to the same notice rule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ScheduleTermsUpdateArgs {
+    pub new_amount_usdc: Option<u64>, // New amount in USDC microlamports
+    pub new_period_secs: Option<u64>, // New payment period in seconds
+    pub effective_ts: i64,            // Unix timestamp from which the new terms apply
+}
+
+#[derive(Accounts)]
+pub struct ScheduleTermsUpdate<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_terms", payee.key().as_ref(), payment_terms.terms_id.as_ref()],
+        bump,
+        has_one = payee @ RecurringPaymentError::Unauthorized
+    )]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    #[account(
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority
+    )]
+    pub payee: Account<'info, Payee>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ScheduleTermsUpdate>, args: ScheduleTermsUpdateArgs) -> Result<()> {
+    let payment_terms = &mut ctx.accounts.payment_terms;
+
+    // Streaming terms price by the second; a period change would break the accrual
+    // rate that `amount_usdc` is derived from
+    require!(
+        !payment_terms.is_streaming(),
+        RecurringPaymentError::StreamingTerms
+    );
+
+    // Require at least one field to be updated
+    require!(
+        args.new_amount_usdc.is_some() || args.new_period_secs.is_some(),
+        RecurringPaymentError::InvalidPaymentTerms
+    );
+
+    // Validate new amount with the same bounds as create_payment_terms
+    let new_amount = args.new_amount_usdc.unwrap_or(payment_terms.amount_usdc);
+    require!(
+        new_amount > 0 && new_amount <= MAX_PLAN_PRICE_USDC,
+        RecurringPaymentError::InvalidPaymentTerms
+    );
+
+    // Validate new period against the configured minimum
+    let new_period = args.new_period_secs.unwrap_or(payment_terms.period_secs);
+    require!(
+        new_period >= ctx.accounts.config.min_period_seconds,
+        RecurringPaymentError::InvalidPaymentTerms
+    );
+
+    // Consumer protection: payers must get at least one full current period of notice,
+    // so every active agreement is charged at least once more at the current terms
+    // (emitting PendingTermsChangeNotice) before the change applies.
+    let clock = Clock::get()?;
+    let current_period_i64 = i64::try_from(payment_terms.period_secs)
+        .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+    let earliest_effective_ts = clock
+        .unix_timestamp
+        .checked_add(current_period_i64)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    require!(
+        args.effective_ts >= earliest_effective_ts,
+        RecurringPaymentError::InsufficientNoticePeriod
+    );
+
+    payment_terms.pending_update = Some(PendingTermsUpdate {
+        amount_usdc: new_amount,
+        period_secs: new_period,
+        effective_ts: args.effective_ts,
+    });
+
+    emit!(PaymentTermsUpdateScheduled {
+        payment_terms: payment_terms.key(),
+        payee: ctx.accounts.payee.key(),
+        current_amount: payment_terms.amount_usdc,
+        new_amount,
+        current_period: payment_terms.period_secs,
+        new_period,
+        effective_ts: args.effective_ts,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_terms_update_args_serialization() {
+        let args = ScheduleTermsUpdateArgs {
+            new_amount_usdc: Some(12_000_000),
+            new_period_secs: None,
+            effective_ts: 1_700_000_000,
+        };
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = ScheduleTermsUpdateArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.new_amount_usdc, Some(12_000_000));
+        assert_eq!(deserialized.new_period_secs, None);
+        assert_eq!(deserialized.effective_ts, 1_700_000_000);
+    }
+}
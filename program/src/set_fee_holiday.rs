@@ -0,0 +1,104 @@
+use crate::constants::MAX_PLATFORM_FEE_BPS;
+use crate::errors::RecurringPaymentError;
+use crate::events::FeeHolidaySet;
+use crate::state::{Config, FeeHoliday, Payee};
+use anchor_lang::prelude::*;
+
+/// Arguments for granting a payee a fee holiday
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetFeeHolidayArgs {
+    /// Unix timestamp at which the discount ends; a past timestamp ends any current holiday
+    pub until_ts: i64,
+    /// Basis points subtracted from the payee's tier fee; zero ends any current holiday
+    pub rebate_bps: u16,
+}
+
+/// Accounts required for setting a payee's fee holiday
+#[derive(Accounts)]
+pub struct SetFeeHoliday<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = platform_authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payee account receiving the fee holiday
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Platform authority (must sign)
+    pub platform_authority: Signer<'info>,
+}
+
+/// Handler for setting a payee's fee holiday
+///
+/// Grants a time-boxed platform fee discount for promotional or enterprise deals
+/// without touching the payee's volume tier. Setting a new holiday replaces the
+/// current one; a zero rebate or an `until_ts` that has already passed ends it.
+///
+/// # Security
+/// - Only `platform_authority` can set a fee holiday
+/// - The rebate cannot exceed `MAX_PLATFORM_FEE_BPS`
+/// - Events are emitted for transparency and off-chain monitoring
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the platform authority
+/// - `rebate_bps` exceeds `MAX_PLATFORM_FEE_BPS`
+pub fn handler(ctx: Context<SetFeeHoliday>, args: SetFeeHolidayArgs) -> Result<()> {
+    require!(
+        args.rebate_bps <= MAX_PLATFORM_FEE_BPS,
+        RecurringPaymentError::InvalidConfiguration
+    );
+
+    let payee = &mut ctx.accounts.payee;
+    let clock = Clock::get()?;
+
+    payee.fee_holiday =
+        (args.rebate_bps > 0 && args.until_ts > clock.unix_timestamp).then_some(FeeHoliday {
+            until_ts: args.until_ts,
+            rebate_bps: args.rebate_bps,
+        });
+
+    emit!(FeeHolidaySet {
+        payee: payee.key(),
+        authority: ctx.accounts.platform_authority.key(),
+        until_ts: args.until_ts,
+        rebate_bps: payee.fee_holiday.map_or(0, |holiday| holiday.rebate_bps),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Fee holiday for payee {} set to {} bps until {}",
+        payee.key(),
+        args.rebate_bps,
+        args.until_ts
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_fee_holiday_args_serialization() {
+        let args = SetFeeHolidayArgs {
+            until_ts: 1_702_592_000,
+            rebate_bps: 10,
+        };
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = SetFeeHolidayArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.until_ts, 1_702_592_000);
+        assert_eq!(deserialized.rebate_bps, 10);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::constants::MAX_AUTHORIZED_KEEPERS;
+use crate::errors::RecurringPaymentError;
+use crate::events::KeeperPolicyUpdated;
+use crate::state::Payee;
+use anchor_lang::prelude::*;
+
+/// Arguments for setting which keepers may execute a payee's payments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct SetKeeperPolicyArgs {
+    /// Whether any keeper may execute payments
+    pub open_execution: bool,
+    /// Keepers allowed to execute payments when execution is permissioned
+    /// (replaces the current list, at most `MAX_AUTHORIZED_KEEPERS`)
+    pub authorized_keepers: Vec<Pubkey>,
+}
+
+/// Accounts required for setting a payee's keeper policy
+#[derive(Accounts)]
+pub struct SetKeeperPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Payee authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+/// Handler for setting a payee's keeper policy
+///
+/// Payees that want to control memo content and keeper fee capture can restrict
+/// `execute_payment` to their own bots by turning `open_execution` off and listing
+/// the bots' keys. Execution stays open while the list is empty.
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the payee authority
+/// - More than `MAX_AUTHORIZED_KEEPERS` keepers are listed
+pub fn handler(ctx: Context<SetKeeperPolicy>, args: SetKeeperPolicyArgs) -> Result<()> {
+    require!(
+        args.authorized_keepers.len() <= MAX_AUTHORIZED_KEEPERS,
+        RecurringPaymentError::TooManyKeepers
+    );
+
+    let payee = &mut ctx.accounts.payee;
+    payee.open_execution = args.open_execution;
+    payee.authorized_keepers = args.authorized_keepers;
+
+    let clock = Clock::get()?;
+
+    emit!(KeeperPolicyUpdated {
+        payee: payee.key(),
+        open_execution: payee.open_execution,
+        authorized_keepers: payee.authorized_keepers.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_keeper_policy_args_serialization() {
+        let keeper = Pubkey::new_unique();
+        let args = SetKeeperPolicyArgs {
+            open_execution: false,
+            authorized_keepers: vec![keeper],
+        };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: SetKeeperPolicyArgs =
+            SetKeeperPolicyArgs::try_from_slice(&serialized).unwrap();
+
+        assert!(!deserialized.open_execution);
+        assert_eq!(deserialized.authorized_keepers, vec![keeper]);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::KeeperSolRateSet;
+use crate::state::{Config, KeeperSolRate};
+use anchor_lang::prelude::*;
+
+/// Arguments for setting the rate for SOL keeper fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetKeeperSolRateArgs {
+    /// Rate in lamports per whole USDC; `None` pays every keeper fee in USDC again
+    pub lamports_per_usdc: Option<u64>,
+}
+
+/// Accounts required for setting the rate for SOL keeper fees
+#[derive(Accounts)]
+pub struct SetKeeperSolRate<'info> {
+    /// Global configuration account
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = platform_authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Platform authority (must sign)
+    pub platform_authority: Signer<'info>,
+}
+
+/// Handler for setting the rate for SOL keeper fees
+///
+/// Keepers executing a payment may take their fee in SOL from the keeper fee vault,
+/// converted at this rate in lamports per whole USDC. The rate is only used for
+/// `MAX_KEEPER_SOL_RATE_AGE_SECS` after it is set, so the platform authority should
+/// refresh it with the SOL price.
+///
+/// # Security
+/// - Only `platform_authority` can set the rate
+/// - The rate must be positive
+/// - Events are emitted for transparency and off-chain monitoring
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the platform authority
+/// - The rate is zero
+pub fn handler(ctx: Context<SetKeeperSolRate>, args: SetKeeperSolRateArgs) -> Result<()> {
+    require!(
+        args.lamports_per_usdc != Some(0),
+        RecurringPaymentError::InvalidConfiguration
+    );
+
+    let clock = Clock::get()?;
+
+    let config = &mut ctx.accounts.config;
+    config.keeper_sol_rate = args.lamports_per_usdc.map(|lamports_per_usdc| KeeperSolRate {
+        lamports_per_usdc,
+        updated_ts: clock.unix_timestamp,
+    });
+
+    emit!(KeeperSolRateSet {
+        lamports_per_usdc: args.lamports_per_usdc.unwrap_or(0),
+        updated_by: ctx.accounts.platform_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_keeper_sol_rate_args_serialization() {
+        let args = SetKeeperSolRateArgs {
+            lamports_per_usdc: Some(5_000_000),
+        };
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = SetKeeperSolRateArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.lamports_per_usdc, args.lamports_per_usdc);
+    }
+}
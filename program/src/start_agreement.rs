@@ -1,9 +1,11 @@
 use crate::{
-    constants::FEE_BASIS_POINTS_DIVISOR,
     errors::RecurringPaymentError,
     events::*,
     state::*,
-    utils::validate_platform_treasury,
+    utils::{
+        apply_gate_discount, calculate_fee_split, qualifying_gate_mint, record_payee_volume,
+        validate_escrow_ata, validate_platform_treasury, FeeSplit,
+    },
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
@@ -17,7 +19,7 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 ///
 /// ## Economic Deterrence
 /// - **Transaction Fee**: 0.000005 SOL (~$0.0007) per payment agreement start
-/// - **Rent Deposit**: 0.00078 SOL (~$0.11) per new payment agreement (110 bytes account size)
+/// - **Rent Deposit**: 0.00269 SOL (~$0.37) per new payment agreement (259 bytes account size)
 /// - **USDC Payment**: Requires actual USDC transfer for initial payment
 /// - **Delegate Approval**: Requires pre-approval of USDC token delegate
 ///
@@ -71,19 +73,31 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 /// See `/docs/OPERATIONAL_PROCEDURES.md` for incident response procedures.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct StartAgreementArgs {
-    /// Multiplier for allowance (default from config if 0)
+    /// Multiplier for allowance, raised to `config.default_allowance_periods` if lower
+    /// (including 0)
     /// Example: If payment terms price is 10 USDC and `allowance_periods` is 3,
     /// the user must approve a delegate allowance of 30 USDC
     pub allowance_periods: u8,
+    /// Renewal queue bucket of the agreement's next payment
+    /// (`(now + period_secs) / RENEWAL_BUCKET_SECS`), used to derive `renewal_queue`
+    pub renewal_bucket: u64,
+    /// Total number of billing periods to charge, including the initial payment
+    /// (`None` renews until paused). Must be at least 2 when set.
+    pub max_periods: Option<u16>,
+    /// Salted hash of the payee's internal customer reference, stored on the agreement
+    /// and emitted in `PaymentAgreementStarted`/`PaymentAgreementReactivated`.
+    /// On reactivation, `None` keeps the previously stored hash.
+    pub external_ref_hash: Option<[u8; 32]>,
 }
 
 #[derive(Accounts)]
+#[instruction(args: StartAgreementArgs)]
 pub struct StartAgreement<'info> {
     /// Global configuration account
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = !config.paused @ RecurringPaymentError::Inactive
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
     )]
     pub config: Account<'info, Config>,
 
@@ -96,11 +110,16 @@ pub struct StartAgreement<'info> {
     )]
     pub payment_agreement: Account<'info, PaymentAgreement>,
 
+    /// Mutable so a scheduled terms update can be applied once effective
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
     pub payment_terms: Account<'info, PaymentTerms>,
 
+    /// Mutable so the payee-wide agreement count and revenue can be updated
     #[account(
-        seeds = [b"payee", payee.authority.as_ref()],
-        bump = payee.bump
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
     )]
     pub payee: Account<'info, Payee>,
 
@@ -120,6 +139,11 @@ pub struct StartAgreement<'info> {
     #[account(mut)]
     pub platform_treasury_ata: UncheckedAccount<'info>,
 
+    /// Program delegate's USDC ATA holding the first payment of escrowed payment terms
+    /// CHECK: Only used, and validated in handler, when the terms have an escrow window
+    #[account(mut)]
+    pub escrow_ata: UncheckedAccount<'info>,
+
     /// CHECK: Validated as USDC mint in handler
     pub usdc_mint: UncheckedAccount<'info>,
 
@@ -131,12 +155,67 @@ pub struct StartAgreement<'info> {
     )]
     pub program_delegate: UncheckedAccount<'info>,
 
+    /// Crank index shard for the bucket of the agreement's next payment
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RenewalQueue::SPACE,
+        seeds = [
+            b"renewal_queue",
+            args.renewal_bucket.to_le_bytes().as_ref(),
+            RenewalQueue::shard_seed(&payment_agreement.key()).as_ref()
+        ],
+        bump
+    )]
+    pub renewal_queue: Account<'info, RenewalQueue>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[allow(clippy::too_many_lines)]
 pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result<()> {
+    // Get current time
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Apply a scheduled terms update once its effective timestamp has been reached
+    if let Some((old_amount, old_period)) =
+        ctx.accounts.payment_terms.apply_due_update(current_time)
+    {
+        let payment_terms = &ctx.accounts.payment_terms;
+        emit!(PaymentTermsUpdated {
+            payment_terms: payment_terms.key(),
+            payee: ctx.accounts.payee.key(),
+            old_amount: Some(old_amount),
+            new_amount: Some(payment_terms.amount_usdc),
+            old_period: Some(old_period),
+            new_period: Some(payment_terms.period_secs),
+            updated_by: ctx.accounts.payee.authority,
+        });
+    }
+
+    // Enforce the subscriber cap and claim a slot. An agreement that is already
+    // active holds its slot and is rejected below with AlreadyActive.
+    // Deactivated terms accept no new or reactivated agreements.
+    if !ctx.accounts.payment_agreement.active {
+        let payment_terms = &mut ctx.accounts.payment_terms;
+        require!(
+            !payment_terms.is_deactivated(),
+            RecurringPaymentError::TermsDeactivated
+        );
+        require!(!payment_terms.is_full(), RecurringPaymentError::TermsFull);
+        payment_terms.active_agreements = payment_terms
+            .active_agreements
+            .checked_add(1)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        let payee = &mut ctx.accounts.payee;
+        payee.active_agreements = payee
+            .active_agreements
+            .checked_add(1)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+    }
+
     let payment_agreement = &mut ctx.accounts.payment_agreement;
     let payment_terms = &ctx.accounts.payment_terms;
     let payee = &ctx.accounts.payee;
@@ -160,6 +239,12 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
             payment_agreement.payer == ctx.accounts.payer.key(),
             RecurringPaymentError::Unauthorized
         );
+
+        // Pause credit is only applied by resume_agreement; don't let it be dropped here
+        require!(
+            payment_agreement.credit_amount == 0,
+            RecurringPaymentError::ResumeRequired
+        );
     }
 
     // Deserialize and validate token accounts with specific error handling
@@ -211,16 +296,34 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
         return Err(RecurringPaymentError::BadSeeds.into());
     }
 
-    // Use default from config if allowance_periods is 0
-    let allowance_periods = if args.allowance_periods == 0 {
-        ctx.accounts.config.default_allowance_periods
+    // Token-gated pricing: payers holding the gate mint receive the configured discount.
+    // The payer's gate token account is passed as the first remaining account; the
+    // allowance requirement below stays based on the undiscounted price so the
+    // agreement keeps renewing if the payer later stops holding the gate token.
+    let gate_mint = qualifying_gate_mint(
+        payment_terms,
+        &ctx.accounts.payer.key(),
+        ctx.remaining_accounts.first(),
+        &ctx.accounts.token_program.key(),
+    )?;
+    let payment_amount = if gate_mint.is_some() {
+        apply_gate_discount(payment_terms.amount_usdc, payment_terms.gate_discount_bps)?
     } else {
-        args.allowance_periods
+        payment_terms.amount_usdc
     };
 
-    // Get current time
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+    // Installment-style plans: the initial payment is the first of `max_periods` charges
+    // Streaming agreements have no billing periods after the first to limit
+    if let Some(max_periods) = args.max_periods {
+        require!(
+            max_periods >= 2 && !payment_terms.is_streaming(),
+            RecurringPaymentError::InvalidMaxPeriods
+        );
+    }
+
+    // The delegated allowance must cover at least the configured number of periods;
+    // requests below the default (including 0) are raised to it
+    let allowance_periods = ctx.accounts.config.allowance_periods(args.allowance_periods);
 
     // Validate allowance calculation won't overflow
     // Ensure price_usdc * allowance_periods <= u64::MAX
@@ -239,7 +342,8 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
     // ALLOWANCE MANAGEMENT EXPECTATIONS (Audit L-3):
     //
     // For payment_agreement initiation, we require allowance for multiple periods
-    // (default 3x, configurable via allowance_periods parameter) to ensure
+    // (at least config.default_allowance_periods, raised via the allowance_periods
+    // parameter) to ensure
     // seamless renewals without immediate allowance exhaustion.
     //
     // IMPORTANT: Subsequent renewals check allowance >= payment_terms.amount_usdc (single period).
@@ -300,21 +404,57 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
     //
     // Core protocol always processes initial payment on payment_agreement start.
     // (Trial support is handled by payment_agreement extension layer)
-    {
-        // Calculate platform fee using checked arithmetic (fee rate determined by payee's volume tier)
-        let platform_fee = u64::try_from(
-            u128::from(payment_terms.amount_usdc)
-                .checked_mul(u128::from(payee.volume_tier.platform_fee_bps()))
-                .ok_or(RecurringPaymentError::ArithmeticError)?
-                .checked_div(FEE_BASIS_POINTS_DIVISOR)
-                .ok_or(RecurringPaymentError::ArithmeticError)?,
-        )
-        .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+    //
+    // Payment terms with an escrow window hold the whole initial payment in the
+    // delegate's escrow ATA instead; confirm_activation splits it between the
+    // treasuries and refund_escrow returns it once the window lapses.
+    let escrow = if let Some(escrow_window_secs) = payment_terms.escrow_window_secs {
+        validate_escrow_ata(
+            &ctx.accounts.escrow_ata,
+            &expected_delegate_pda,
+            &payee.usdc_mint,
+            &ctx.accounts.token_program,
+        )?;
 
-        let merchant_amount = payment_terms
-            .amount_usdc
-            .checked_sub(platform_fee)
-            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        let delegate_bump = ctx.bumps.program_delegate;
+        let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+
+        let transfer_to_escrow = TransferChecked {
+            from: ctx.accounts.payer_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.escrow_ata.to_account_info(),
+            authority: ctx.accounts.program_delegate.to_account_info(),
+        };
+
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_escrow,
+                delegate_seeds,
+            ),
+            payment_amount,
+            usdc_mint_data.decimals,
+        )?;
+
+        let window_i64 = i64::try_from(escrow_window_secs)
+            .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+        Some(AgreementEscrow {
+            amount: payment_amount,
+            expires_ts: current_time
+                .checked_add(window_i64)
+                .ok_or(RecurringPaymentError::ArithmeticError)?,
+        })
+    } else {
+        // Count the initial payment towards the payee's volume, as renewals do, before
+        // calculating the platform fee using checked arithmetic (fee rate determined by
+        // payee's volume tier). No keeper is involved in the initial payment.
+        record_payee_volume(&mut ctx.accounts.payee, payment_amount, current_time);
+        let platform_fee_bps = ctx.accounts.payee.platform_fee_bps(current_time);
+        let FeeSplit {
+            platform_fee,
+            payee_amount: merchant_amount,
+            ..
+        } = calculate_fee_split(payment_amount, 0, platform_fee_bps)?;
 
         // Prepare delegate signer seeds
         let delegate_bump = ctx.bumps.program_delegate;
@@ -363,6 +503,41 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
                 usdc_decimals,
             )?;
         }
+
+        None
+    };
+
+    // SECURITY DEPOSIT
+    //
+    // Payment terms with a deposit hold it in the delegate's escrow ATA for the
+    // agreement's lifetime; close_agreement returns whatever the payee hasn't claimed
+    // with claim_deposit. The payer signs the transfer directly so the deposit doesn't
+    // use up the delegated allowance reserved for renewals. A reactivated agreement
+    // only tops up what damages claims took from its deposit.
+    let deposit_top_up = payment_terms
+        .deposit_usdc
+        .unwrap_or(0)
+        .saturating_sub(payment_agreement.deposit_held);
+    if deposit_top_up > 0 {
+        validate_escrow_ata(
+            &ctx.accounts.escrow_ata,
+            &expected_delegate_pda,
+            &ctx.accounts.payee.usdc_mint,
+            &ctx.accounts.token_program,
+        )?;
+
+        let transfer_deposit = TransferChecked {
+            from: ctx.accounts.payer_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.escrow_ata.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+
+        token::transfer_checked(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_deposit),
+            deposit_top_up,
+            usdc_mint_data.decimals,
+        )?;
     }
 
     // Calculate next renewal timestamp
@@ -374,6 +549,20 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
         .checked_add(period_i64)
         .ok_or(RecurringPaymentError::ArithmeticError)?;
 
+    // Index the agreement under the bucket of its next payment for keepers. Streaming
+    // agreements have no renewals; keepers sweep them instead.
+    require!(
+        RenewalQueue::bucket_for(next_renewal_ts) == Some(args.renewal_bucket),
+        RecurringPaymentError::InvalidRenewalBucket
+    );
+    let renewal_queue = &mut ctx.accounts.renewal_queue;
+    renewal_queue.bucket = args.renewal_bucket;
+    renewal_queue.shard = RenewalQueue::shard_for(&payment_agreement.key());
+    renewal_queue.bump = ctx.bumps.renewal_queue;
+    if !payment_terms.is_streaming() {
+        renewal_queue.insert(payment_agreement.key());
+    }
+
     // Update payment_agreement account based on whether this is new or reactivation
     if is_reactivation {
         // ============================================================================
@@ -459,8 +648,29 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
 
         payment_agreement.active = true;
         payment_agreement.next_payment_ts = next_renewal_ts;
-        payment_agreement.last_amount = payment_terms.amount_usdc;
+        payment_agreement.last_amount = payment_amount;
         payment_agreement.last_payment_ts = current_time;
+        // Reactivation charges for a new billing period falling due now
+        payment_agreement.last_pull_period_index = payment_agreement
+            .pull_period_index(current_time)
+            .ok_or(RecurringPaymentError::ArithmeticError)?
+            .max(payment_agreement.last_pull_period_index);
+        // A cancellation scheduled before the agreement was paused no longer applies
+        payment_agreement.cancel_at_period_end = false;
+        payment_agreement.refunded_amount = 0;
+        // A reactivated agreement starts a new billing period limit
+        payment_agreement.max_periods = args.max_periods;
+        payment_agreement.periods_paid = 1;
+        if args.external_ref_hash.is_some() {
+            payment_agreement.external_ref_hash = args.external_ref_hash;
+        }
+        payment_agreement.paused_at_ts = None;
+        payment_agreement.escrow = escrow;
+        payment_agreement.suspension = None;
+        payment_agreement.deposit_held = payment_agreement
+            .deposit_held
+            .checked_add(deposit_top_up)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
     } else {
         // NEW PAYMENT AGREEMENT: Initialize all fields
         payment_agreement.payment_terms = payment_terms.key();
@@ -469,11 +679,33 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
         payment_agreement.active = true;
         payment_agreement.payment_count = 0;
         payment_agreement.created_ts = current_time;
-        payment_agreement.last_amount = payment_terms.amount_usdc;
+        payment_agreement.last_amount = payment_amount;
         payment_agreement.last_payment_ts = current_time;
+        payment_agreement.last_pull_period_index = 0; // Initial payment falls due at created_ts
+        payment_agreement.cancel_at_period_end = false;
+        payment_agreement.pending_payer = None;
+        payment_agreement.refunded_amount = 0;
+        payment_agreement.max_periods = args.max_periods;
+        payment_agreement.periods_paid = 1;
+        payment_agreement.external_ref_hash = args.external_ref_hash;
+        payment_agreement.paused_at_ts = None;
+        payment_agreement.credit_amount = 0;
+        payment_agreement.escrow = escrow;
+        payment_agreement.period_index = 0;
+        payment_agreement.suspension = None;
+        payment_agreement.deposit_held = deposit_top_up;
         payment_agreement.bump = ctx.bumps.payment_agreement;
+        payment_agreement.version = PaymentAgreement::VERSION;
     }
 
+    // Streaming agreements accrue from the end of the prepaid first period
+    payment_agreement.last_sweep_ts = if payment_terms.is_streaming() {
+        next_renewal_ts
+    } else {
+        0
+    };
+    let payee = &ctx.accounts.payee;
+
     // Initialize trial fields (trials not supported in core protocol)
             // Emit appropriate event based on whether this is a new payment_agreement or reactivation
     if is_reactivation {
@@ -481,9 +713,11 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
             payee: payee.key(),
             payment_terms: payment_terms.key(),
             payer: ctx.accounts.payer.key(),
-            amount: payment_terms.amount_usdc,
+            amount: payment_amount,
             total_payments: payment_agreement.payment_count,
             original_created_ts: payment_agreement.created_ts,
+            external_ref_hash: payment_agreement.external_ref_hash,
+            delegated_amount: subscriber_ata_data.delegated_amount,
         });
     } else {
         // Emit PaymentAgreementStarted event for new paid subscriptions
@@ -492,9 +726,57 @@ pub fn handler(ctx: Context<StartAgreement>, args: StartAgreementArgs) -> Result
             payee: payee.key(),
             payment_terms: payment_terms.key(),
             payer: ctx.accounts.payer.key(),
-            amount: payment_terms.amount_usdc,
+            amount: payment_amount,
+            external_ref_hash: payment_agreement.external_ref_hash,
+            delegated_amount: subscriber_ata_data.delegated_amount,
+        });
+    }
+
+    // Give the payer on-chain notice of an upcoming terms change
+    if let Some(pending) = payment_terms.pending_update {
+        emit!(PendingTermsChangeNotice {
+            payment_terms: payment_terms.key(),
+            payee: payee.key(),
+            payer: ctx.accounts.payer.key(),
+            current_amount: payment_terms.amount_usdc,
+            new_amount: pending.amount_usdc,
+            current_period: payment_terms.period_secs,
+            new_period: pending.period_secs,
+            effective_ts: pending.effective_ts,
+        });
+    }
+
+    if deposit_top_up > 0 {
+        emit!(DepositHeld {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: ctx.accounts.payer.key(),
+            amount: deposit_top_up,
+            total_held: payment_agreement.deposit_held,
+            timestamp: current_time,
         });
     }
 
+    if let Some(gate_mint) = gate_mint {
+        emit!(GateDiscountApplied {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: ctx.accounts.payer.key(),
+            gate_mint,
+            discount_bps: payment_terms.gate_discount_bps,
+            original_amount: payment_terms.amount_usdc,
+            discounted_amount: payment_amount,
+        });
+    }
+
+    // Escrowed first payments count towards revenue once confirm_activation releases them
+    if ctx.accounts.payment_agreement.escrow.is_none() {
+        ctx.accounts.payment_terms.record_payment(payment_amount, false);
+        ctx.accounts.payee.record_payment(payment_amount, false);
+    }
+
+    let payment_agreement = &ctx.accounts.payment_agreement;
+    emit!(payment_agreement.snapshot(payment_agreement.key()));
+
     Ok(())
 }
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
 use crate::constants::{
-    GROWTH_TIER_THRESHOLD_USDC, MAX_PLATFORM_FEE_BPS, MIN_PLATFORM_FEE_BPS,
-    SCALE_TIER_THRESHOLD_USDC,
+    GROWTH_TIER_THRESHOLD_USDC, MAX_AUTHORIZED_KEEPERS, MAX_PLATFORM_FEE_BPS,
+    MAX_KEEPER_SOL_RATE_AGE_SECS, MAX_RENEWAL_QUEUE_ENTRIES, MIN_PLATFORM_FEE_BPS, RENEWAL_BUCKET_SECS,
+    RENEWAL_QUEUE_SHARDS, SCALE_TIER_THRESHOLD_USDC, USDC_UNITS, VOLUME_WINDOW_SECONDS,
 };
+use crate::events::AgreementSnapshot;
 
 /// Volume tier determines platform fee rate based on 30-day rolling payment volume
 ///
@@ -42,7 +44,9 @@ use crate::constants::{
 /// - Employee (Standard, $5K): 0.25%
 /// - Vendor (Standard, $1K): 0.25%
 /// - Total overhead: 0.85% + keeper fees = ~1.45%
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, InitSpace,
+)]
 pub enum VolumeTier {
     /// Standard tier: Up to $10K monthly volume, 0.25% platform fee
     Standard,
@@ -125,16 +129,22 @@ impl VolumeTier {
 }
 
 /// Payee account stores payment recipient configuration and settings
-/// PDA seeds: ["payee", authority]
+/// PDA seeds: ["payee", `original_authority`]
+///
+/// The PDA stays derived from the authority that created the payee, so payment
+/// terms and agreements keep working after `accept_payee_authority` hands the
+/// payee to a new authority.
 ///
 /// # Volume Tracking
 ///
-/// The Payee account tracks rolling 30-day payment volume to automatically
-/// determine the payee's fee tier. Volume resets after 30 days of inactivity.
+/// The Payee account tracks payment volume in 30-day windows to automatically
+/// determine the payee's fee tier. Payees are promoted as soon as the current
+/// window's volume crosses a threshold, and re-evaluated against the completed
+/// window's volume when a new window starts.
 ///
 /// # Account Size
 ///
-/// Total: 122 bytes (14 bytes larger than v1.x.x due to volume tracking)
+/// Total: 385 bytes
 /// - Discriminator: 8 bytes
 /// - `authority`: 32 bytes
 /// - `usdc_mint`: 32 bytes
@@ -143,8 +153,18 @@ impl VolumeTier {
 /// - `monthly_volume_usdc`: 8 bytes
 /// - `last_volume_update_ts`: 8 bytes
 /// - bump: 1 byte
+/// - `frozen`: 1 byte
+/// - `open_execution`: 1 byte
+/// - `authorized_keepers`: 164 bytes (4 byte Vec length + 5 * 32 bytes)
+/// - `original_authority`: 32 bytes
+/// - `pending_authority`: 33 bytes (1 byte Option discriminator + 32 bytes Pubkey)
+/// - `fee_holiday`: 11 bytes (1 byte Option discriminator + 10 bytes `FeeHoliday`)
+/// - `active_agreements`: 4 bytes
+/// - `lifetime_revenue_usdc`: 8 bytes
+/// - `lifetime_renewals`: 8 bytes
+/// - `version`: 1 byte
 ///
-/// Additional rent: ~0.000098 SOL (~$0.004 at $45/SOL)
+/// Rent-exempt minimum: ~0.0036 SOL
 #[account]
 #[derive(InitSpace)]
 pub struct Payee {
@@ -168,8 +188,7 @@ pub struct Payee {
     /// Rolling 30-day payment volume in USDC microlamports (6 decimals)
     ///
     /// This field accumulates total payment volume processed by this payee
-    /// over a rolling 30-day window. It resets to zero if no payments are
-    /// processed for 30 days.
+    /// in the current 30-day window. It resets to zero when the window ends.
     ///
     /// # Update Frequency
     ///
@@ -180,25 +199,313 @@ pub struct Payee {
     /// Used to determine `volume_tier` via `VolumeTier::from_monthly_volume()`.
     pub monthly_volume_usdc: u64, // 8 bytes
 
-    /// Unix timestamp of last volume calculation
+    /// Unix timestamp at which the current 30-day volume window started
     ///
     /// Used to determine if 30-day window has elapsed and volume should reset.
-    /// Updated on every payment execution.
+    /// A new window starts with the first payment executed after it elapses.
     pub last_volume_update_ts: i64, // 8 bytes
 
     /// PDA bump seed
     pub bump: u8, // 1 byte
+
+    /// Whether the platform authority has frozen this payee
+    ///
+    /// A frozen payee cannot start new agreements or receive recurring payments
+    /// until it is unfrozen. Set by `freeze_payee`, cleared by `unfreeze_payee`.
+    pub frozen: bool, // 1 byte
+
+    /// Whether any keeper may execute this payee's payments
+    ///
+    /// When `false` and `authorized_keepers` is non-empty, `execute_payment` only
+    /// accepts keepers on the list. Set by `set_keeper_policy`.
+    pub open_execution: bool, // 1 byte
+
+    /// Keepers allowed to execute payments when execution is permissioned
+    #[max_len(MAX_AUTHORIZED_KEEPERS)]
+    pub authorized_keepers: Vec<Pubkey>, // 4 + 32 * 5 bytes
+
+    /// Authority the payee was created with; the PDA is derived from this key
+    pub original_authority: Pubkey, // 32 bytes
+
+    /// Authority proposed by `transfer_payee_authority`, awaiting acceptance
+    pub pending_authority: Option<Pubkey>, // 33 bytes
+
+    /// Promotional platform fee discount, set by `set_fee_holiday`
+    pub fee_holiday: Option<FeeHoliday>, // 11 bytes
+
+    /// Number of currently active agreements across all of the payee's terms
+    pub active_agreements: u32, // 4 bytes
+
+    /// Total USDC collected from payers across all terms, before fees and refunds
+    pub lifetime_revenue_usdc: u64, // 8 bytes
+
+    /// Number of recurring payments executed across all terms
+    pub lifetime_renewals: u64, // 8 bytes
+
+    /// Schema version of the account layout (see `VersionedAccount`)
+    pub version: u8, // 1 byte
+}
+
+/// Time-boxed platform fee discount granted to a payee by the platform authority
+///
+/// Until `until_ts`, payments are charged the payee's volume tier fee minus
+/// `rebate_bps` (floored at zero). The tier itself keeps evolving with volume, so the
+/// payee returns to its earned fee when the holiday ends.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeHoliday {
+    /// Unix timestamp at which the discount ends
+    pub until_ts: i64, // 8 bytes
+    /// Basis points subtracted from the tier's platform fee
+    pub rebate_bps: u16, // 2 bytes
+}
+
+/// Conversion rate for paying keeper fees in SOL
+///
+/// The platform authority sets the rate in lamports per whole USDC and keeps it close
+/// to the market price. Keepers taking their fee in SOL are paid at this rate and
+/// cannot choose their own; a rate older than `MAX_KEEPER_SOL_RATE_AGE_SECS` is not used.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct KeeperSolRate {
+    /// Rate in lamports per whole USDC
+    pub lamports_per_usdc: u64, // 8 bytes
+    /// Unix timestamp at which the platform authority set the rate
+    pub updated_ts: i64, // 8 bytes
+}
+
+impl KeeperSolRate {
+    /// Returns the lamports owed for a USDC `keeper_fee` at this rate at time `now`
+    ///
+    /// `None` if the rate is older than `MAX_KEEPER_SOL_RATE_AGE_SECS` or the conversion
+    /// overflows.
+    #[must_use]
+    pub fn lamports_for(&self, keeper_fee: u64, now: i64) -> Option<u64> {
+        if now.checked_sub(self.updated_ts)? > MAX_KEEPER_SOL_RATE_AGE_SECS {
+            return None;
+        }
+        let lamports = u128::from(keeper_fee)
+            .checked_mul(u128::from(self.lamports_per_usdc))?
+            .checked_div(u128::from(USDC_UNITS))?;
+        u64::try_from(lamports).ok()
+    }
+}
+
+/// `SlotReservation` account records a payer's place on the waitlist of capped payment terms
+/// PDA seeds: ["`slot_reservation`", `payment_terms`, payer]
+///
+/// # Account Size: 85 bytes
+/// - Discriminator: 8 bytes
+/// - `payment_terms`: 32 bytes
+/// - payer: 32 bytes
+/// - position: 4 bytes
+/// - `reserved_ts`: 8 bytes
+/// - bump: 1 byte
+#[account]
+#[derive(InitSpace)]
+pub struct SlotReservation {
+    /// Reference to the payment terms PDA
+    pub payment_terms: Pubkey, // 32 bytes
+    /// Payer holding the reservation
+    pub payer: Pubkey, // 32 bytes
+    /// Zero-based waitlist position (order in which reservations were made)
+    pub position: u32, // 4 bytes
+    /// Unix timestamp of the reservation
+    pub reserved_ts: i64, // 8 bytes
+    /// PDA bump seed
+    pub bump: u8, // 1 byte
+}
+
+impl SlotReservation {
+    /// Total space: 8 (discriminator) + 32 + 32 + 4 + 8 + 1 = 85 bytes
+    pub const SPACE: usize = 8 + Self::INIT_SPACE;
+}
+
+/// Crank index of agreements whose next payment falls within one time bucket
+/// PDA seeds: `["renewal_queue", bucket.to_le_bytes(), [shard]]`
+///
+/// `start_agreement` inserts an agreement under the bucket of its next payment and
+/// `execute_payment` moves it to the bucket of the following payment, so a keeper
+/// reads the `RENEWAL_QUEUE_SHARDS` small accounts of a bucket instead of scanning
+/// every agreement. Each bucket is split into shards keyed by agreement address
+/// (see [`RenewalQueue::shard_for`]) so one busy day does not overflow a single queue.
+///
+/// Entries are a hint, not a source of truth: agreements paused or canceled outside
+/// `execute_payment` stay listed until their bucket is processed, so keepers must
+/// re-check each agreement before executing it.
+///
+/// # Account Size: 2074 bytes
+/// - Discriminator: 8 bytes
+/// - bucket: 8 bytes
+/// - shard: 1 byte
+/// - agreements: 4 + 64 * 32 bytes
+/// - `overflow_count`: 4 bytes
+/// - bump: 1 byte
+#[account]
+#[derive(InitSpace)]
+pub struct RenewalQueue {
+    /// Bucket index (`next_payment_ts / RENEWAL_BUCKET_SECS`)
+    pub bucket: u64, // 8 bytes
+    /// Shard index within the bucket (`RenewalQueue::shard_for(agreement)`)
+    pub shard: u8, // 1 byte
+    /// Payment agreement PDAs due within this bucket
+    #[max_len(MAX_RENEWAL_QUEUE_ENTRIES)]
+    pub agreements: Vec<Pubkey>, // 4 + 64 * 32 bytes
+    /// Number of insertions dropped because the queue was full
+    pub overflow_count: u32, // 4 bytes
+    /// PDA bump seed
+    pub bump: u8, // 1 byte
+}
+
+impl RenewalQueue {
+    /// Total space: 8 (discriminator) + 8 + 1 + (4 + 64 * 32) + 4 + 1 = 2074 bytes
+    pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Returns the bucket index containing timestamp `ts`
+    ///
+    /// Returns `None` for timestamps before the Unix epoch.
+    #[must_use]
+    pub fn bucket_for(ts: i64) -> Option<u64> {
+        u64::try_from(ts.checked_div(RENEWAL_BUCKET_SECS)?).ok()
+    }
+
+    /// Returns the shard of each bucket that indexes `agreement`
+    #[must_use]
+    pub const fn shard_for(agreement: &Pubkey) -> u8 {
+        agreement.to_bytes()[0] % RENEWAL_QUEUE_SHARDS
+    }
+
+    /// PDA seed for the shard indexing `agreement`
+    #[must_use]
+    pub const fn shard_seed(agreement: &Pubkey) -> [u8; 1] {
+        [Self::shard_for(agreement)]
+    }
+
+    /// Adds `agreement` to the queue
+    ///
+    /// Inserting an agreement that is already listed is a no-op. Returns `false` and
+    /// increments `overflow_count` if the queue is full.
+    pub fn insert(&mut self, agreement: Pubkey) -> bool {
+        if self.agreements.contains(&agreement) {
+            return true;
+        }
+        if self.agreements.len() >= MAX_RENEWAL_QUEUE_ENTRIES {
+            self.overflow_count = self.overflow_count.saturating_add(1);
+            return false;
+        }
+        self.agreements.push(agreement);
+        true
+    }
+
+    /// Removes `agreement` from the queue, returning whether it was listed
+    pub fn remove(&mut self, agreement: &Pubkey) -> bool {
+        match self.agreements.iter().position(|key| key == agreement) {
+            Some(index) => {
+                self.agreements.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Offset applied to the cluster clock in due-date checks of test deployments
+/// PDA seeds: `["test_clock"]`
+///
+/// Only programs built with the `test-clock` feature can create this account or read
+/// it, through `advance_test_clock` and `execute_payment`; other builds ignore it.
+/// Integration tests on localnet or devnet use it to reach the next billing period
+/// without waiting.
+///
+/// # Account Size: 17 bytes
+/// - Discriminator: 8 bytes
+/// - `offset_secs`: 8 bytes
+/// - bump: 1 byte
+#[account]
+#[derive(InitSpace)]
+pub struct TestClock {
+    /// Seconds added to the cluster clock; only ever moves forward
+    pub offset_secs: i64, // 8 bytes
+    /// PDA bump seed
+    pub bump: u8, // 1 byte
+}
+
+impl TestClock {
+    /// Total space: 8 (discriminator) + 8 + 1 = 17 bytes
+    pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Returns the cluster time `now` shifted by the offset
+    #[must_use]
+    pub const fn now(&self, now: i64) -> i64 {
+        now.saturating_add(self.offset_secs)
+    }
+}
+
+/// Price and/or period change scheduled by the payee for existing payment terms
+///
+/// Stored on `PaymentTerms` until `effective_ts` is reached, at which point the next
+/// `start_agreement` or `execute_payment` promotes it to the active terms. Until then
+/// payments continue at the current terms and emit `PendingTermsChangeNotice`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PendingTermsUpdate {
+    /// New payment amount in USDC microlamports (6 decimals)
+    pub amount_usdc: u64, // 8 bytes
+    /// New payment period in seconds
+    pub period_secs: u64, // 8 bytes
+    /// Unix timestamp from which the new terms apply
+    pub effective_ts: i64, // 8 bytes
+}
+
+/// First payment held in the program's escrow account until the agreement is activated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct AgreementEscrow {
+    /// Amount held in escrow in USDC microlamports (6 decimals)
+    pub amount: u64, // 8 bytes
+    /// Unix timestamp from which the payee can no longer confirm activation and the
+    /// payment can be refunded
+    pub expires_ts: i64, // 8 bytes
+}
+
+/// Why `execute_payment` suspended an agreement instead of charging it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum SuspensionReason {
+    /// The payer's token account was frozen by the mint's freeze authority
+    FrozenAccount,
+}
+
+/// Lifecycle status of a payment agreement, as reported by `AgreementSnapshot`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AgreementStatus {
+    /// Renewals are charged as they fall due
+    Active,
+    /// Paused by the payer, a scheduled cancellation, a sunset or completion
+    Paused,
+    /// Suspended by `execute_payment`; see `PaymentAgreement::suspension`
+    Suspended,
+    /// The agreement account was closed
+    Closed,
 }
 
 /// `PaymentTerms` account defines payment schedule and amount for recurring payments
 /// PDA seeds: ["`payment_terms`", payee, `terms_id`]
 ///
-/// # Account Size: 80 bytes
+/// # Account Size: 214 bytes
 /// - Discriminator: 8 bytes
 /// - payee: 32 bytes
 /// - `terms_id`: 32 bytes
 /// - `amount_usdc`: 8 bytes
 /// - `period_secs`: 8 bytes
+/// - `gate_mint`: 33 bytes (1 byte Option discriminator + 32 bytes Pubkey)
+/// - `gate_discount_bps`: 2 bytes
+/// - `pending_update`: 25 bytes (1 byte Option discriminator + 24 bytes `PendingTermsUpdate`)
+/// - `max_subscribers`: 5 bytes (1 byte Option discriminator + 4 bytes u32)
+/// - `active_agreements`: 4 bytes
+/// - `waitlist_len`: 4 bytes
+/// - `sunset_ts`: 9 bytes (1 byte Option discriminator + 8 bytes i64)
+/// - `escrow_window_secs`: 9 bytes (1 byte Option discriminator + 8 bytes u64)
+/// - `lifetime_revenue_usdc`: 8 bytes
+/// - `lifetime_renewals`: 8 bytes
+/// - `deposit_usdc`: 9 bytes (1 byte Option discriminator + 8 bytes u64)
+/// - `version`: 1 byte
+/// - `accrual_rate_per_sec`: 9 bytes (1 byte Option discriminator + 8 bytes u64)
 ///
 /// Reduced from 129 bytes in v1.x.x by removing subscription-specific fields:
 /// - `grace_secs`: 8 bytes (moved to subscription extension)
@@ -215,6 +522,44 @@ pub struct PaymentTerms {
     pub amount_usdc: u64, // 8 bytes
     /// Payment period in seconds (payment frequency)
     pub period_secs: u64, // 8 bytes
+    /// Optional token gate: payers holding at least one token of this mint
+    /// receive `gate_discount_bps` off `amount_usdc`
+    pub gate_mint: Option<Pubkey>, // 33 bytes
+    /// Discount applied to gate holders in basis points (0 when no gate is set)
+    pub gate_discount_bps: u16, // 2 bytes
+    /// Scheduled price/period change awaiting its effective timestamp
+    pub pending_update: Option<PendingTermsUpdate>, // 25 bytes
+    /// Optional cap on concurrently active agreements; `start_agreement` rejects
+    /// new payers with `TermsFull` once `active_agreements` reaches it
+    pub max_subscribers: Option<u32>, // 5 bytes
+    /// Number of currently active agreements for these terms
+    pub active_agreements: u32, // 4 bytes
+    /// Number of waitlist reservations made via `reserve_slot`
+    pub waitlist_len: u32, // 4 bytes
+    /// Set by `deactivate_payment_terms`: no new agreements are accepted, and
+    /// agreements stop renewing once their first payment due at or after this
+    /// timestamp is reached
+    pub sunset_ts: Option<i64>, // 9 bytes
+    /// Optional activation window: when set, `start_agreement` holds the first
+    /// payment in escrow until the payee confirms activation or the window lapses
+    pub escrow_window_secs: Option<u64>, // 9 bytes
+    /// Total USDC collected from payers under these terms, before fees and refunds
+    pub lifetime_revenue_usdc: u64, // 8 bytes
+    /// Number of recurring payments executed under these terms
+    pub lifetime_renewals: u64, // 8 bytes
+    /// Optional refundable security deposit: `start_agreement` holds it in the
+    /// program delegate's escrow ATA for the agreement's lifetime, and
+    /// `close_agreement` returns whatever the payee hasn't claimed as damages
+    pub deposit_usdc: Option<u64>, // 9 bytes
+    /// Schema version of the account layout (see `VersionedAccount`)
+    pub version: u8, // 1 byte
+    /// Streaming mode: cost accrued per second in USDC microlamports
+    ///
+    /// When set, `amount_usdc` is the price of one `period_secs` at this rate.
+    /// `start_agreement` charges the first period up front, after which agreements
+    /// accrue continuously and keepers collect with `sweep_accrued` instead of
+    /// `execute_payment`. Added in layout version 2.
+    pub accrual_rate_per_sec: Option<u64>, // 9 bytes
 }
 
 /// `PaymentAgreement` account tracks recurring payment relationship between payer and payee
@@ -280,27 +625,346 @@ pub struct PaymentAgreement {
     pub last_payment_ts: i64, // 8 bytes
     /// PDA bump seed
     pub bump: u8, // 1 byte
+    /// Index of the billing period most recently pulled by `execute_payment`
+    ///
+    /// Billing periods are indexed by the offset in seconds of their due date from
+    /// `created_ts` (see `pull_period_index`). A pull whose period index is not
+    /// greater than this value is rejected with `PeriodPullCapExceeded`.
+    pub last_pull_period_index: u64, // 8 bytes
+    /// Whether the payer scheduled cancellation for the end of the current period
+    ///
+    /// Set by `schedule_cancellation`. The next `execute_payment` once `next_payment_ts`
+    /// has passed pauses the agreement instead of charging it.
+    pub cancel_at_period_end: bool, // 1 byte
+    /// Wallet the payer proposed to hand this agreement to
+    ///
+    /// Set by `initiate_agreement_transfer`. `accept_agreement_transfer`, signed by this
+    /// wallet, moves the agreement to the new wallet's PDA.
+    pub pending_payer: Option<Pubkey>, // 33 bytes
+    /// Amount the payee has refunded against the last payment (`last_amount`)
+    ///
+    /// Incremented by `refund_payment`, which rejects refunds that would take it above
+    /// `last_amount`. Reset to zero whenever a new payment is charged.
+    pub refunded_amount: u64, // 8 bytes
+    /// Total number of billing periods to charge in the current session, if limited
+    ///
+    /// Set by `start_agreement` for installment-style plans. Once `periods_paid` reaches
+    /// it, `execute_payment` completes the agreement and emits `AgreementCompleted`.
+    pub max_periods: Option<u16>, // 3 bytes
+    /// Billing periods charged in the current session, including the initial payment
+    pub periods_paid: u16, // 2 bytes
+    /// Hash of the payee's internal customer reference, if supplied at start
+    ///
+    /// Lets payees link the agreement to their own records without publishing the
+    /// customer id. Computed off-chain, e.g. as `sha256(salt || customer_id)`.
+    pub external_ref_hash: Option<[u8; 32]>, // 33 bytes
+    /// Unix timestamp at which the payer paused the agreement mid-period
+    ///
+    /// Set by `pause_agreement` when it pauses an active agreement and cleared when the
+    /// agreement is resumed or reactivated.
+    pub paused_at_ts: Option<i64>, // 9 bytes
+    /// Unused value of paused billing periods, in USDC microlamports
+    ///
+    /// Accrued by `pause_agreement` for the part of the current period left unused and
+    /// applied by `resume_agreement` against the first charge after the pause.
+    pub credit_amount: u64, // 8 bytes
+    /// First payment held in escrow awaiting activation, if any
+    ///
+    /// Set by `start_agreement` on payment terms with an escrow window and cleared by
+    /// `confirm_activation` (released to the treasuries) or `refund_escrow` (returned
+    /// to the payer once the window lapses).
+    pub escrow: Option<AgreementEscrow>, // 17 bytes
+    /// Number of renewals charged by `execute_payment`, never reset
+    ///
+    /// Incremented by every successful `execute_payment` and carried in
+    /// `PaymentExecuted`, so off-chain reconciliation can key each renewal on
+    /// `(agreement, period_index)` instead of timestamps, which collide when retries
+    /// land in the same second. Kept across pauses, reactivations and transfers.
+    pub period_index: u64, // 8 bytes
+    /// Why the agreement is suspended, if `execute_payment` suspended it
+    ///
+    /// Set together with `active = false` when a renewal finds the payer's token
+    /// account frozen, so keepers stop retrying a payment that cannot succeed. Cleared
+    /// by `resume_after_unfreeze` once the account is thawed, or by a reactivation.
+    pub suspension: Option<SuspensionReason>, // 2 bytes
+    /// Security deposit currently held in the program delegate's escrow ATA
+    ///
+    /// Topped up to the terms' `deposit_usdc` by `start_agreement`, reduced by
+    /// `claim_deposit` and returned to the payer by `close_agreement`.
+    pub deposit_held: u64, // 8 bytes
+    /// Schema version of the account layout (see `VersionedAccount`)
+    pub version: u8, // 1 byte
+    /// Unix timestamp up to which accrual has been collected, for streaming terms
+    ///
+    /// Set to the end of the prepaid first period by `start_agreement` and
+    /// `resume_agreement`, and advanced by each `sweep_accrued` over the seconds it
+    /// charged for. Zero for agreements on non-streaming terms. Added in layout
+    /// version 2.
+    pub last_sweep_ts: i64, // 8 bytes
 }
 
 impl Payee {
-    /// Total space: 8 (discriminator) + 32 + 32 + 32 + 1 + 8 + 8 + 1 = 122 bytes
-    /// Note: Previous version was 108 bytes. New version adds:
-    /// - `monthly_volume_usdc`: 8 bytes
-    /// - `last_volume_update_ts`: 8 bytes
-    /// - Removes `platform_fee_bps`: 2 bytes (fee now derived from tier)
-    ///
-    ///   Net increase: 14 bytes (~0.000098 SOL additional rent)
+    /// Total space: 8 (discriminator) + 32 + 32 + 32 + 1 + 8 + 8 + 1 + 1 + 1 + (4 + 32 * 5) + 32 + 33 + 11 + 4 + 8 + 8 + 1 = 385 bytes
+    /// Note: The unversioned layout ended at `bump` (122 bytes); later fields follow it.
     pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Frees the slot of an agreement that is no longer active
+    ///
+    /// Mirrors `PaymentTerms::release_subscriber_slot` for the payee-wide count and
+    /// saturates at zero for agreements started before the counter existed.
+    pub const fn release_subscriber_slot(&mut self) {
+        self.active_agreements = self.active_agreements.saturating_sub(1);
+    }
+
+    /// Adds a payment collected from a payer to the lifetime totals
+    ///
+    /// `amount` is what the payer was charged, before platform and keeper fees.
+    /// Renewals are payments executed by `execute_payment`.
+    pub const fn record_payment(&mut self, amount: u64, is_renewal: bool) {
+        self.lifetime_revenue_usdc = self.lifetime_revenue_usdc.saturating_add(amount);
+        if is_renewal {
+            self.lifetime_renewals = self.lifetime_renewals.saturating_add(1);
+        }
+    }
+
+    /// The fee holiday in effect at `now`, if any
+    #[must_use]
+    pub fn active_fee_holiday(&self, now: i64) -> Option<FeeHoliday> {
+        self.fee_holiday.filter(|holiday| now < holiday.until_ts)
+    }
+
+    /// Platform fee in basis points charged at `now`
+    ///
+    /// The volume tier's fee, less the rebate of any active fee holiday.
+    #[must_use]
+    pub fn platform_fee_bps(&self, now: i64) -> u16 {
+        let tier_fee_bps = self.volume_tier.platform_fee_bps();
+        self.active_fee_holiday(now)
+            .map_or(tier_fee_bps, |holiday| {
+                tier_fee_bps.saturating_sub(holiday.rebate_bps)
+            })
+    }
+
+    /// Whether `keeper` may execute payments for this payee
+    ///
+    /// Execution is open unless `open_execution` is off and at least one keeper
+    /// has been authorized.
+    #[must_use]
+    pub fn is_keeper_authorized(&self, keeper: &Pubkey) -> bool {
+        self.open_execution
+            || self.authorized_keepers.is_empty()
+            || self.authorized_keepers.contains(keeper)
+    }
+
+    /// Adds a payment to the payee's volume and re-evaluates its tier
+    ///
+    /// When the current 30-day window has elapsed, a new window starts at `now` and
+    /// the tier is reset to the one earned by the completed window's volume (or
+    /// Standard if a whole window passed without payments). Within a window, the
+    /// tier is promoted as soon as the running volume crosses a threshold.
+    ///
+    /// Returns the previous tier when it changed.
+    pub fn record_volume(&mut self, amount: u64, now: i64) -> Option<VolumeTier> {
+        let previous_tier = self.volume_tier;
+        let elapsed = now.saturating_sub(self.last_volume_update_ts);
+
+        let base_tier = if elapsed >= VOLUME_WINDOW_SECONDS {
+            let completed_volume = if elapsed >= VOLUME_WINDOW_SECONDS.saturating_mul(2) {
+                0
+            } else {
+                self.monthly_volume_usdc
+            };
+            self.monthly_volume_usdc = 0;
+            self.last_volume_update_ts = now;
+            VolumeTier::from_monthly_volume(completed_volume)
+        } else {
+            previous_tier
+        };
+
+        self.monthly_volume_usdc = self.monthly_volume_usdc.saturating_add(amount);
+        self.volume_tier = base_tier.max(VolumeTier::from_monthly_volume(self.monthly_volume_usdc));
+
+        (self.volume_tier != previous_tier).then_some(previous_tier)
+    }
 }
 
 impl PaymentTerms {
-    /// Total space: 8 (discriminator) + 32 + 32 + 8 + 8 = 80 bytes
+    /// Total space: 8 (discriminator) + 32 + 32 + 8 + 8 + 33 + 2 + 25 + 5 + 4 + 4 + 9 + 9 + 8 + 8 + 9 + 1 + 9 = 214 bytes
+    /// Note: The unversioned layout ended at `period_secs` (88 bytes); later fields
+    /// follow it. Layout version 2 adds `accrual_rate_per_sec` (previously 205 bytes).
     pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Whether agreements on these terms accrue per second (see `accrual_rate_per_sec`)
+    #[must_use]
+    pub const fn is_streaming(&self) -> bool {
+        self.accrual_rate_per_sec.is_some()
+    }
+
+    /// Whether the subscriber cap has been reached
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.max_subscribers
+            .is_some_and(|max_subscribers| self.active_agreements >= max_subscribers)
+    }
+
+    /// Whether the payee has deactivated these terms
+    #[must_use]
+    pub const fn is_deactivated(&self) -> bool {
+        self.sunset_ts.is_some()
+    }
+
+    /// Whether the sunset timestamp of deactivated terms has been reached
+    #[must_use]
+    pub fn is_sunset(&self, now: i64) -> bool {
+        self.sunset_ts.is_some_and(|sunset_ts| now >= sunset_ts)
+    }
+
+    /// Frees the subscriber slot held by an agreement that is no longer active
+    ///
+    /// Saturates at zero so agreements started before the counter existed can't
+    /// underflow it.
+    pub const fn release_subscriber_slot(&mut self) {
+        self.active_agreements = self.active_agreements.saturating_sub(1);
+    }
+
+    /// Adds a payment collected from a payer to the lifetime totals
+    ///
+    /// `amount` is what the payer was charged, before platform and keeper fees.
+    /// Renewals are payments executed by `execute_payment`.
+    pub const fn record_payment(&mut self, amount: u64, is_renewal: bool) {
+        self.lifetime_revenue_usdc = self.lifetime_revenue_usdc.saturating_add(amount);
+        if is_renewal {
+            self.lifetime_renewals = self.lifetime_renewals.saturating_add(1);
+        }
+    }
+
+    /// Promotes the pending terms update once its effective timestamp is reached
+    ///
+    /// Returns the previous `(amount_usdc, period_secs)` when an update was applied.
+    pub fn apply_due_update(&mut self, now: i64) -> Option<(u64, u64)> {
+        let pending = self
+            .pending_update
+            .filter(|pending| now >= pending.effective_ts)?;
+        let previous = (self.amount_usdc, self.period_secs);
+
+        self.amount_usdc = pending.amount_usdc;
+        self.period_secs = pending.period_secs;
+        self.pending_update = None;
+
+        Some(previous)
+    }
 }
 
 impl PaymentAgreement {
-    /// Total space: 8 (discriminator) + 32 + 32 + 8 + 1 + 4 + 8 + 8 + 8 + 1 = 110 bytes
+    /// Total space: 8 (discriminator) + 32 + 32 + 8 + 1 + 4 + 8 + 8 + 8 + 1 + 8 + 1 + 33 + 8 + 3 + 2 + 33 + 9 + 8 + 17 + 8 + 2 + 8 + 1 + 8 = 259 bytes
+    /// Note: The unversioned layout ended at `bump` (110 bytes); later fields follow it.
+    /// Layout version 2 adds `last_sweep_ts`.
     pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Returns whether every billing period allowed by `max_periods` has been charged
+    #[must_use]
+    pub const fn periods_exhausted(&self) -> bool {
+        match self.max_periods {
+            Some(max_periods) => self.periods_paid >= max_periods,
+            None => false,
+        }
+    }
+
+    /// Returns whether the first payment is still held in escrow awaiting activation
+    #[must_use]
+    pub const fn escrow_pending(&self) -> bool {
+        self.escrow.is_some()
+    }
+
+    /// Returns whether `execute_payment` suspended the agreement
+    #[must_use]
+    pub const fn is_suspended(&self) -> bool {
+        self.suspension.is_some()
+    }
+
+    /// Returns the agreement's lifecycle status; closed agreements no longer exist
+    #[must_use]
+    pub const fn status(&self) -> AgreementStatus {
+        if self.is_suspended() {
+            AgreementStatus::Suspended
+        } else if self.active {
+            AgreementStatus::Active
+        } else {
+            AgreementStatus::Paused
+        }
+    }
+
+    /// Returns the `AgreementSnapshot` emitted after every mutation of the agreement
+    #[must_use]
+    pub const fn snapshot(&self, payment_agreement: Pubkey) -> AgreementSnapshot {
+        AgreementSnapshot {
+            payment_agreement,
+            status: self.status(),
+            next_payment_ts: self.next_payment_ts,
+            period_index: self.period_index,
+        }
+    }
+
+    /// Returns the index of the billing period falling due at `due_ts`
+    ///
+    /// The index is the offset of the due date from `created_ts`, a fixed anchor, in
+    /// seconds. Every billing period of the agreement falls due at a distinct time, so
+    /// it has its own index whenever keepers execute it and whatever period a terms
+    /// update sets. Returns `None` if `due_ts` precedes `created_ts`.
+    #[must_use]
+    pub fn pull_period_index(&self, due_ts: i64) -> Option<u64> {
+        u64::try_from(due_ts.checked_sub(self.created_ts)?).ok()
+    }
+
+    /// Returns the index of the billing period due at `next_payment_ts`, the one
+    /// `execute_payment` is about to pull
+    ///
+    /// # Errors
+    ///
+    /// Returns `PeriodPullCapExceeded` if that period's index does not exceed
+    /// `last_pull_period_index`, and `ArithmeticError` if `next_payment_ts` precedes
+    /// `created_ts`.
+    pub fn next_pull_period_index(&self) -> Result<u64> {
+        let index = self
+            .pull_period_index(self.next_payment_ts)
+            .ok_or(crate::errors::RecurringPaymentError::ArithmeticError)?;
+        require!(
+            index > self.last_pull_period_index,
+            crate::errors::RecurringPaymentError::PeriodPullCapExceeded
+        );
+        Ok(index)
+    }
+
+    /// Returns the accrual a sweep up to `end_ts` collects, bounded by `allowance`
+    ///
+    /// Accrual runs from `last_sweep_ts` at `rate_per_sec`. Only whole seconds the
+    /// allowance covers are charged, so the remainder stays sweepable once the payer
+    /// raises the allowance. Returns `(amount, covered_secs)`, or `None` if the rate is
+    /// zero or the amount overflows.
+    #[must_use]
+    pub fn sweepable(&self, end_ts: i64, rate_per_sec: u64, allowance: u64) -> Option<(u64, i64)> {
+        let elapsed = u64::try_from(end_ts.saturating_sub(self.last_sweep_ts)).unwrap_or(0);
+        let covered_secs = elapsed.min(allowance.checked_div(rate_per_sec)?);
+        let amount = covered_secs.checked_mul(rate_per_sec)?;
+        Some((amount, i64::try_from(covered_secs).ok()?))
+    }
+
+    /// Returns the value of the current billing period left unused at `now`
+    ///
+    /// Prorates the net amount of the last payment (`last_amount` less refunds) by the
+    /// share of the period remaining until `next_payment_ts`. Returns zero once the
+    /// next payment is due and `None` if `period_secs` is zero.
+    #[must_use]
+    pub fn unused_period_value(&self, now: i64, period_secs: u64) -> Option<u64> {
+        let remaining = u64::try_from(self.next_payment_ts.saturating_sub(now))
+            .unwrap_or(0)
+            .min(period_secs);
+        let net_amount = self.last_amount.saturating_sub(self.refunded_amount);
+        let value = u128::from(net_amount)
+            .checked_mul(u128::from(remaining))?
+            .checked_div(u128::from(period_secs))?;
+        u64::try_from(value).ok()
+    }
 }
 
 /// Global configuration account for recurring payments protocol
@@ -341,9 +1005,237 @@ pub struct Config {
     pub keeper_fee_bps: u16, // 2 bytes
     /// PDA bump seed
     pub bump: u8, // 1 byte
+    /// Reason for the current pause as zero-padded UTF-8, shown to integrators
+    /// Cleared on unpause
+    pub pause_reason: [u8; 64], // 64 bytes
+    /// Unix timestamp at which the current pause lapses without an `unpause` call
+    /// `None` keeps the program paused until the platform authority unpauses it
+    pub auto_unpause_ts: Option<i64>, // 9 bytes (1 byte discriminator + 8 bytes i64)
+    /// Rate at which keepers taking their fee in SOL are paid from the keeper fee vault
+    /// `None` pays every keeper fee in USDC
+    pub keeper_sol_rate: Option<KeeperSolRate>, // 17 bytes (1 byte discriminator + 16 bytes)
+    /// Seconds before `next_payment_ts` from which `execute_payment` accepts a renewal
+    /// Absorbs skew between the cluster clock and wall time; at most `MAX_RENEWAL_TOLERANCE_SECS`
+    pub renewal_tolerance_secs: u64, // 8 bytes
+    /// Schema version of the account layout (see `VersionedAccount`)
+    pub version: u8, // 1 byte
 }
 
 impl Config {
-    /// Total space: 8 (discriminator) + 32 + 33 + 2 + 2 + 8 + 1 + 32 + 8 + 8 + 1 + 2 + 1 = 138 bytes
+    /// Total space: 8 (discriminator) + 32 + 33 + 2 + 2 + 8 + 1 + 32 + 8 + 8 + 1 + 2 + 1 + 64 + 9 + 17 + 8 + 1 = 237 bytes
+    /// Note: The unversioned layout ended at `bump` (138 bytes); later fields follow it.
     pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Timestamp renewal due checks compare against at `now`
+    ///
+    /// Shifts `now` forward by the renewal tolerance, so a payment due at
+    /// `next_payment_ts` is accepted from `next_payment_ts - renewal_tolerance_secs`.
+    #[must_use]
+    pub fn renewal_check_time(&self, now: i64) -> i64 {
+        now.saturating_add(i64::try_from(self.renewal_tolerance_secs).unwrap_or(i64::MAX))
+    }
+
+    /// Returns whether user-facing operations are paused at `now`
+    ///
+    /// A pause with `auto_unpause_ts` set lapses once `now` reaches that timestamp, even
+    /// though `paused` stays set until the next `pause` or `unpause` call.
+    #[must_use]
+    pub const fn is_paused(&self, now: i64) -> bool {
+        match self.auto_unpause_ts {
+            Some(auto_unpause_ts) => self.paused && now < auto_unpause_ts,
+            None => self.paused,
+        }
+    }
+
+    /// Returns the allowance multiplier enforced when an agreement starts
+    ///
+    /// Payers may require a larger allowance than `default_allowance_periods`, but
+    /// never a smaller one; `0` selects the default.
+    #[must_use]
+    pub const fn allowance_periods(&self, requested: u8) -> u8 {
+        if requested > self.default_allowance_periods {
+            requested
+        } else {
+            self.default_allowance_periods
+        }
+    }
+}
+
+/// Accounts whose layout carries a schema `version` byte
+///
+/// Fields are only ever appended. The unversioned (version 0) layouts deployed before
+/// the byte existed end at `bump` (at `period_secs` for `PaymentTerms`); every field
+/// added since, `version` included, follows them. Once `migrate_account` has
+/// zero-extended an unversioned account to `LEN`, its fields keep their offsets and the
+/// version byte reads as 0. Later schema changes append their fields after `version`,
+/// bump `VERSION`, and convert older layouts in `upgrade`, so existing accounts are
+/// never stranded.
+pub trait VersionedAccount: AccountSerialize + AccountDeserialize {
+    /// Layout version written by this program
+    const VERSION: u8;
+    /// Size of the current layout, including the discriminator
+    const LEN: usize;
+
+    /// Layout version the account was written with
+    fn version(&self) -> u8;
+
+    /// Records the layout version of the account
+    fn set_version(&mut self, version: u8);
+
+    /// Sets the fields the unversioned layout lacks to the values new accounts start with
+    ///
+    /// Called when upgrading from version 0. The zero-extension already reads as unset
+    /// for most fields; this also overwrites any stale bytes an unversioned account may
+    /// have left past its serialized data when an option shrank.
+    fn init_unversioned_fields(&mut self);
+
+    /// Sets the fields added after layout `version` to the values new accounts start with
+    ///
+    /// Called when upgrading from a version above 0. Those fields trail `version`, where
+    /// an option that shrank may have left stale bytes, so they are reset rather than
+    /// read from the zero-extension. Types still at their first versioned layout have
+    /// none.
+    fn init_fields_added_after(&mut self, _version: u8) {}
+
+    /// Upgrades account data already resized to `LEN` to `VERSION`
+    ///
+    /// Returns the version the account had before the upgrade.
+    ///
+    /// # Errors
+    /// Returns an error if the data doesn't deserialize as this account type or is
+    /// already at `VERSION`
+    fn upgrade(data: &mut [u8]) -> Result<u8> {
+        let mut account = Self::try_deserialize(&mut &data[..])?;
+        let from_version = account.version();
+        require!(
+            from_version < Self::VERSION,
+            crate::errors::RecurringPaymentError::AlreadyMigrated
+        );
+
+        // Fields the old layout lacks are reset explicitly, since stale bytes past its
+        // serialized data would otherwise be read as their values
+        if from_version == 0 {
+            account.init_unversioned_fields();
+        } else {
+            account.init_fields_added_after(from_version);
+        }
+        account.set_version(Self::VERSION);
+        account.try_serialize(&mut &mut data[..])?;
+        Ok(from_version)
+    }
+}
+
+impl VersionedAccount for Config {
+    const VERSION: u8 = 1;
+    const LEN: usize = Self::SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn init_unversioned_fields(&mut self) {
+        self.pause_reason = [0; 64];
+        self.auto_unpause_ts = None;
+        self.keeper_sol_rate = None;
+        self.renewal_tolerance_secs = 0;
+    }
+}
+
+impl VersionedAccount for Payee {
+    const VERSION: u8 = 1;
+    const LEN: usize = Self::SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn init_unversioned_fields(&mut self) {
+        self.frozen = false;
+        self.open_execution = true;
+        self.authorized_keepers = Vec::new();
+        self.original_authority = self.authority;
+        self.pending_authority = None;
+        self.fee_holiday = None;
+        self.active_agreements = 0;
+        self.lifetime_revenue_usdc = 0;
+        self.lifetime_renewals = 0;
+    }
+}
+
+impl VersionedAccount for PaymentTerms {
+    const VERSION: u8 = 2;
+    const LEN: usize = Self::SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn init_unversioned_fields(&mut self) {
+        self.gate_mint = None;
+        self.gate_discount_bps = 0;
+        self.pending_update = None;
+        self.max_subscribers = None;
+        self.active_agreements = 0;
+        self.waitlist_len = 0;
+        self.sunset_ts = None;
+        self.escrow_window_secs = None;
+        self.lifetime_revenue_usdc = 0;
+        self.lifetime_renewals = 0;
+        self.deposit_usdc = None;
+        self.accrual_rate_per_sec = None;
+    }
+
+    fn init_fields_added_after(&mut self, version: u8) {
+        if version < 2 {
+            self.accrual_rate_per_sec = None;
+        }
+    }
+}
+
+impl VersionedAccount for PaymentAgreement {
+    const VERSION: u8 = 2;
+    const LEN: usize = Self::SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn init_unversioned_fields(&mut self) {
+        self.last_pull_period_index = 0;
+        self.cancel_at_period_end = false;
+        self.pending_payer = None;
+        self.refunded_amount = 0;
+        self.max_periods = None;
+        self.periods_paid = 0;
+        self.external_ref_hash = None;
+        self.paused_at_ts = None;
+        self.credit_amount = 0;
+        self.escrow = None;
+        self.period_index = 0;
+        self.suspension = None;
+        self.deposit_held = 0;
+        self.last_sweep_ts = 0;
+    }
+
+    fn init_fields_added_after(&mut self, version: u8) {
+        if version < 2 {
+            self.last_sweep_ts = 0;
+        }
+    }
 }
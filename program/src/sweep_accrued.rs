@@ -0,0 +1,344 @@
+use crate::{
+    errors::RecurringPaymentError,
+    events::*,
+    state::*,
+    utils::{
+        calculate_fee_split, due_check_time, record_payee_volume, validate_platform_treasury,
+        FeeSplit,
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct SweepAccruedArgs {
+    // No args needed; the amount is derived from the agreement's accrual
+}
+
+#[derive(Accounts)]
+pub struct SweepAccrued<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Agreement to sweep; paused agreements can still be swept up to the pause
+    #[account(
+        mut,
+        seeds = [b"payment_agreement", payment_terms.key().as_ref(), payment_agreement.payer.as_ref()],
+        bump = payment_agreement.bump
+    )]
+    pub payment_agreement: Account<'info, PaymentAgreement>,
+
+    /// Mutable so the lifetime revenue can be updated
+    #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+    pub payment_terms: Account<'info, PaymentTerms>,
+
+    /// Mutable so the payee's volume, tier and revenue can be updated
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// CHECK: Validated as USDC token account in handler
+    #[account(mut)]
+    pub payer_usdc_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as payee treasury ATA in handler
+    #[account(mut)]
+    pub payee_treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as platform treasury ATA in handler
+    #[account(mut)]
+    pub platform_treasury_ata: UncheckedAccount<'info>,
+
+    /// Keeper (transaction caller) who sweeps the accrual
+    pub executor: Signer<'info>,
+
+    /// Keeper's USDC ATA where the keeper fee will be sent
+    /// CHECK: Validated as executor's USDC token account in handler
+    #[account(mut)]
+    pub keeper_usdc_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as USDC mint in handler
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    // Program PDA that acts as delegate
+    /// CHECK: PDA derived from program, validated in handler
+    #[account(
+        seeds = [b"delegate"],
+        bump
+    )]
+    pub program_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for collecting the accrual of a streaming agreement
+///
+/// Accrual runs from `last_sweep_ts` at the terms' `accrual_rate_per_sec` until now,
+/// the pause of a paused agreement, or the sunset of deactivated terms, whichever
+/// comes first. Only the seconds the payer's remaining allowance covers are charged;
+/// `last_sweep_ts` advances by exactly those, so the rest stays sweepable once the
+/// allowance is raised. The amount is split between keeper, platform and payee like
+/// a renewal.
+///
+/// # Errors
+/// Returns an error if:
+/// - The payment terms are not streaming terms
+/// - The agreement is paused without a recorded pause time (suspended)
+/// - No accrual is due, or the allowance doesn't cover a second of it
+/// - Token accounts, the platform treasury or the delegate are invalid
+/// - The payer's balance doesn't cover the sweep
+/// - The payee has not authorized the keeper
+#[allow(clippy::too_many_lines)]
+pub fn handler(ctx: Context<SweepAccrued>, _args: SweepAccruedArgs) -> Result<()> {
+    // Get current timestamp, shifted by the test clock in `test-clock` builds
+    let clock = Clock::get()?;
+    let current_time =
+        due_check_time(clock.unix_timestamp, ctx.remaining_accounts, ctx.program_id)?;
+
+    let payment_agreement = &ctx.accounts.payment_agreement;
+    let payment_terms = &ctx.accounts.payment_terms;
+    let payee = &ctx.accounts.payee;
+
+    // Permissioned execution: payees may restrict sweeps to their own keepers
+    require!(
+        payee.is_keeper_authorized(&ctx.accounts.executor.key()),
+        RecurringPaymentError::UnauthorizedKeeper
+    );
+
+    let accrual_rate_per_sec = payment_terms
+        .accrual_rate_per_sec
+        .ok_or(RecurringPaymentError::NotStreamingTerms)?;
+
+    // Accrual stops when the payer pauses and at the sunset of deactivated terms
+    let accrual_end_ts = if payment_agreement.active {
+        current_time
+    } else {
+        payment_agreement
+            .paused_at_ts
+            .ok_or(RecurringPaymentError::Inactive)?
+    };
+    let accrual_end_ts = payment_terms
+        .sunset_ts
+        .map_or(accrual_end_ts, |sunset_ts| accrual_end_ts.min(sunset_ts));
+
+    // Deserialize and validate token accounts with specific error handling. The payer
+    // account is supplied by the keeper, so it must belong to the token program before
+    // its data is trusted
+    require!(
+        ctx.accounts.payer_usdc_ata.owner == &ctx.accounts.token_program.key(),
+        RecurringPaymentError::InvalidPayerTokenAccount
+    );
+    let subscriber_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payer_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    let payee_treasury_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.payee_treasury_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayeeTreasuryAccount)?;
+
+    let platform_treasury_data: TokenAccount = TokenAccount::try_deserialize(
+        &mut ctx.accounts.platform_treasury_ata.data.borrow().as_ref(),
+    )
+    .map_err(|_| RecurringPaymentError::InvalidPlatformTreasuryAccount)?;
+
+    let keeper_ata_data: TokenAccount =
+        TokenAccount::try_deserialize(&mut ctx.accounts.keeper_usdc_ata.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidPayerTokenAccount)?;
+
+    let usdc_mint_data: Mint =
+        Mint::try_deserialize(&mut ctx.accounts.usdc_mint.data.borrow().as_ref())
+            .map_err(|_| RecurringPaymentError::InvalidUsdcMint)?;
+
+    // Runtime validation: Ensure platform treasury ATA remains valid (audit finding L-4)
+    validate_platform_treasury(
+        &ctx.accounts.platform_treasury_ata,
+        &ctx.accounts.config.platform_authority,
+        &ctx.accounts.config.allowed_mint,
+        &ctx.accounts.token_program,
+    )?;
+
+    // Validate token account ownership and mints, checking the payer account first so
+    // a hostile keeper gets the precise error
+    if subscriber_ata_data.owner != payment_agreement.payer {
+        return Err(RecurringPaymentError::WrongOwner.into());
+    }
+
+    if subscriber_ata_data.mint != payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if keeper_ata_data.owner != ctx.accounts.executor.key() {
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    if payee_treasury_data.mint != payee.usdc_mint
+        || platform_treasury_data.mint != payee.usdc_mint
+        || keeper_ata_data.mint != payee.usdc_mint
+    {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if ctx.accounts.usdc_mint.key() != payee.usdc_mint {
+        return Err(RecurringPaymentError::WrongMint.into());
+    }
+
+    if ctx.accounts.payee_treasury_ata.key() != payee.treasury_ata {
+        return Err(RecurringPaymentError::BadSeeds.into());
+    }
+
+    // Explicitly validate PDA derivation to ensure the delegate PDA was derived with expected seeds
+    let (expected_delegate_pda, _expected_bump) =
+        Pubkey::find_program_address(&[b"delegate"], ctx.program_id);
+    require!(
+        ctx.accounts.program_delegate.key() == expected_delegate_pda,
+        RecurringPaymentError::BadSeeds
+    );
+
+    // The sweep is pulled through the global delegate, as in execute_payment
+    let actual_delegate = Option::<Pubkey>::from(subscriber_ata_data.delegate);
+    if actual_delegate != Some(expected_delegate_pda) {
+        emit!(DelegateMismatchWarning {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            expected_delegate: expected_delegate_pda,
+            actual_delegate,
+        });
+        return Err(RecurringPaymentError::Unauthorized.into());
+    }
+
+    // Allowance ceiling: only whole seconds the remaining allowance covers are swept
+    let (amount, covered_secs) = payment_agreement
+        .sweepable(
+            accrual_end_ts,
+            accrual_rate_per_sec,
+            subscriber_ata_data.delegated_amount,
+        )
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    require!(amount > 0, RecurringPaymentError::NothingToSweep);
+
+    // Check sufficient funds
+    if subscriber_ata_data.amount < amount {
+        return Err(RecurringPaymentError::InsufficientFunds.into());
+    }
+
+    // Warn when the allowance left after this sweep covers less than a period
+    let remaining_allowance = subscriber_ata_data.delegated_amount.saturating_sub(amount);
+    if remaining_allowance < payment_terms.amount_usdc {
+        emit!(LowAllowanceWarning {
+            payee: payee.key(),
+            payment_terms: payment_terms.key(),
+            payer: payment_agreement.payer,
+            current_allowance: remaining_allowance,
+            recommended_allowance: payment_terms.amount_usdc,
+            payment_price: payment_terms.amount_usdc,
+        });
+    }
+
+    let payment_agreement = &mut ctx.accounts.payment_agreement;
+    let payment_terms = &mut ctx.accounts.payment_terms;
+    let payee = &mut ctx.accounts.payee;
+
+    // Automatic volume tier evaluation, as in execute_payment
+    record_payee_volume(payee, amount, current_time);
+
+    // Split the sweep like a renewal: keeper fee first, then the platform fee
+    let FeeSplit {
+        keeper_fee,
+        platform_fee,
+        payee_amount: merchant_amount,
+    } = calculate_fee_split(
+        amount,
+        ctx.accounts.config.keeper_fee_bps,
+        payee.platform_fee_bps(current_time),
+    )?;
+
+    // Prepare delegate signer seeds
+    let delegate_bump = ctx.bumps.program_delegate;
+    let delegate_seeds: &[&[&[u8]]] = &[&[b"delegate", &[delegate_bump]]];
+    let usdc_decimals = usdc_mint_data.decimals;
+
+    for (destination, transfer_amount) in [
+        (&ctx.accounts.payee_treasury_ata, merchant_amount),
+        (&ctx.accounts.platform_treasury_ata, platform_fee),
+        (&ctx.accounts.keeper_usdc_ata, keeper_fee),
+    ] {
+        if transfer_amount == 0 {
+            continue;
+        }
+        let transfer = TransferChecked {
+            from: ctx.accounts.payer_usdc_ata.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: destination.to_account_info(),
+            authority: ctx.accounts.program_delegate.to_account_info(),
+        };
+
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer,
+                delegate_seeds,
+            ),
+            transfer_amount,
+            usdc_decimals,
+        )?;
+    }
+
+    // Update payment_agreement fields
+    let from_ts = payment_agreement.last_sweep_ts;
+    payment_agreement.last_sweep_ts = from_ts
+        .checked_add(covered_secs)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    payment_agreement.payment_count = payment_agreement
+        .payment_count
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    payment_agreement.last_amount = amount;
+    payment_agreement.last_payment_ts = current_time;
+    payment_agreement.refunded_amount = 0;
+    payment_terms.record_payment(amount, false);
+    payee.record_payment(amount, false);
+
+    emit!(Swept {
+        payee: payee.key(),
+        payment_terms: payment_terms.key(),
+        payer: payment_agreement.payer,
+        keeper: ctx.accounts.executor.key(),
+        amount,
+        keeper_fee,
+        from_ts,
+        to_ts: payment_agreement.last_sweep_ts,
+    });
+
+    emit!(payment_agreement.snapshot(payment_agreement.key()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_accrued_args_serialization() {
+        let args = SweepAccruedArgs {};
+
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized = SweepAccruedArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(
+            serialized,
+            deserialized.try_to_vec().unwrap(),
+            "Empty args should round-trip"
+        );
+    }
+}
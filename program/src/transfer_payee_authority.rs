@@ -0,0 +1,89 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::PayeeAuthorityTransferInitiated;
+use crate::state::Payee;
+use anchor_lang::prelude::*;
+
+/// Arguments for initiating a payee authority transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TransferPayeeAuthorityArgs {
+    /// The new authority to transfer the payee to
+    pub new_authority: Pubkey,
+}
+
+/// Accounts required for initiating a payee authority transfer
+#[derive(Accounts)]
+pub struct TransferPayeeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump,
+        has_one = authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Current payee authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+/// Handler for initiating a payee authority transfer
+///
+/// This initiates a two-step transfer mirroring the platform authority flow. The
+/// current payee authority proposes a new authority, which must then accept the
+/// transfer with `accept_payee_authority`.
+///
+/// # Security
+/// - Only the current payee `authority` can initiate the transfer
+/// - Cannot overwrite an existing pending transfer
+/// - New authority must be different from the current authority
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the payee authority
+/// - A pending transfer already exists
+/// - New authority is the same as the current authority
+pub fn handler(ctx: Context<TransferPayeeAuthority>, args: TransferPayeeAuthorityArgs) -> Result<()> {
+    let payee = &mut ctx.accounts.payee;
+
+    // Ensure no pending transfer exists
+    require!(
+        payee.pending_authority.is_none(),
+        RecurringPaymentError::TransferAlreadyPending
+    );
+
+    // Ensure new authority is different from current
+    require!(
+        args.new_authority != payee.authority,
+        RecurringPaymentError::InvalidTransferTarget
+    );
+
+    payee.pending_authority = Some(args.new_authority);
+
+    let clock = Clock::get()?;
+
+    emit!(PayeeAuthorityTransferInitiated {
+        payee: payee.key(),
+        authority: payee.authority,
+        new_authority: args.new_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_payee_authority_args_serialization() {
+        let new_authority = Pubkey::new_unique();
+        let args = TransferPayeeAuthorityArgs { new_authority };
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: TransferPayeeAuthorityArgs =
+            TransferPayeeAuthorityArgs::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(deserialized.new_authority, new_authority);
+    }
+}
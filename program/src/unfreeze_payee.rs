@@ -0,0 +1,85 @@
+use crate::errors::RecurringPaymentError;
+use crate::events::PayeeUnfrozen;
+use crate::state::{Config, Payee};
+use anchor_lang::prelude::*;
+
+/// Arguments for unfreezing a payee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UnfreezePayeeArgs {}
+
+/// Accounts required for unfreezing a payee
+#[derive(Accounts)]
+pub struct UnfreezePayee<'info> {
+    /// Global configuration account
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = platform_authority @ RecurringPaymentError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Payee account to be unfrozen
+    #[account(
+        mut,
+        seeds = [b"payee", payee.original_authority.as_ref()],
+        bump = payee.bump
+    )]
+    pub payee: Account<'info, Payee>,
+
+    /// Platform authority (must sign)
+    pub platform_authority: Signer<'info>,
+}
+
+/// Handler for unfreezing a payee
+///
+/// Re-enables `start_agreement` and `execute_payment` for a payee previously frozen
+/// with `freeze_payee`.
+///
+/// # Security
+/// - Only `platform_authority` can unfreeze a payee
+/// - Freeze state is stored in the Payee account
+/// - Events are emitted for transparency and off-chain monitoring
+///
+/// # Errors
+/// Returns an error if:
+/// - Caller is not the platform authority
+pub fn handler(ctx: Context<UnfreezePayee>, _args: UnfreezePayeeArgs) -> Result<()> {
+    let payee = &mut ctx.accounts.payee;
+
+    payee.frozen = false;
+
+    // Get current timestamp for event
+    let clock = Clock::get()?;
+
+    emit!(PayeeUnfrozen {
+        payee: payee.key(),
+        authority: ctx.accounts.platform_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Payee {} unfrozen by platform authority: {}",
+        payee.key(),
+        ctx.accounts.platform_authority.key()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unfreeze_payee_args_serialization() {
+        let args = UnfreezePayeeArgs {};
+
+        // Test serialization round-trip
+        let serialized = args.try_to_vec().unwrap();
+        let deserialized: UnfreezePayeeArgs =
+            UnfreezePayeeArgs::try_from_slice(&serialized).unwrap();
+
+        // UnfreezePayeeArgs has no fields, so just verify it deserializes successfully
+        let _ = deserialized;
+    }
+}
@@ -26,7 +26,8 @@ pub struct Unpause<'info> {
 /// Handler for unpausing the program
 ///
 /// This disables the emergency pause mechanism, re-enabling all user-facing operations
-/// (`start_agreement`, `execute_payment`, `create_payment_terms`).
+/// (`start_agreement`, `execute_payment`, `create_payment_terms`), and clears the pause
+/// reason and any scheduled auto-unpause time.
 ///
 /// # Security
 /// - Only `platform_authority` can unpause the program
@@ -39,8 +40,10 @@ pub struct Unpause<'info> {
 pub fn handler(ctx: Context<Unpause>, _args: UnpauseArgs) -> Result<()> {
     let config = &mut ctx.accounts.config;
 
-    // Set paused state to false
+    // Set paused state to false and clear the pause details
     config.paused = false;
+    config.pause_reason = [0; 64];
+    config.auto_unpause_ts = None;
 
     // Get current timestamp for event
     let clock = Clock::get()?;
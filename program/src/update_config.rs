@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::RecurringPaymentError, events::ConfigUpdated, state::Config};
+use crate::{
+    constants::MAX_RENEWAL_TOLERANCE_SECS, errors::RecurringPaymentError, events::ConfigUpdated,
+    state::Config,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct UpdateConfigArgs {
@@ -11,6 +14,7 @@ pub struct UpdateConfigArgs {
     pub max_platform_fee_bps: Option<u16>,
     pub min_period_seconds: Option<u64>,
     pub default_allowance_periods: Option<u8>,
+    pub renewal_tolerance_secs: Option<u64>,
 }
 
 #[derive(Accounts)]
@@ -41,7 +45,8 @@ pub fn handler(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()>
         || args.min_platform_fee_bps.is_some()
         || args.max_platform_fee_bps.is_some()
         || args.min_period_seconds.is_some()
-        || args.default_allowance_periods.is_some();
+        || args.default_allowance_periods.is_some()
+        || args.renewal_tolerance_secs.is_some();
 
     // Require at least one field to be updated
     require!(has_update, RecurringPaymentError::InvalidConfiguration);
@@ -106,6 +111,15 @@ pub fn handler(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()>
         config.default_allowance_periods = allowance_periods;
     }
 
+    // Update renewal tolerance if provided (zero disables it)
+    if let Some(tolerance) = args.renewal_tolerance_secs {
+        require!(
+            tolerance <= MAX_RENEWAL_TOLERANCE_SECS,
+            RecurringPaymentError::InvalidConfiguration
+        );
+        config.renewal_tolerance_secs = tolerance;
+    }
+
     // Emit comprehensive update event
     emit!(ConfigUpdated {
         keeper_fee_bps: config.keeper_fee_bps,
@@ -113,6 +127,7 @@ pub fn handler(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()>
         max_grace_period_seconds: config.max_grace_period_seconds,
         min_platform_fee_bps: config.min_platform_fee_bps,
         max_platform_fee_bps: config.max_platform_fee_bps,
+        renewal_tolerance_secs: config.renewal_tolerance_secs,
         updated_by: ctx.accounts.platform_authority.key(),
     });
 
@@ -3,7 +3,12 @@ use anchor_lang::solana_program::program_pack::Pack;
 use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::{spl_token::state::Account as TokenAccount, Token};
 
+use crate::constants::FEE_BASIS_POINTS_DIVISOR;
 use crate::errors::RecurringPaymentError;
+use crate::events::{VolumeTierDowngraded, VolumeTierUpgraded};
+use crate::state::{Payee, PaymentTerms, RenewalQueue};
+#[cfg(feature = "test-clock")]
+use crate::state::TestClock;
 
 /// Validates that the platform treasury ATA is valid and correctly configured.
 ///
@@ -88,6 +93,354 @@ pub fn validate_platform_treasury<'info>(
     Ok(())
 }
 
+/// Validates that the escrow account is the program delegate's USDC ATA.
+///
+/// Escrowed first payments are held in the associated token account of the global
+/// delegate PDA, so only the program can move them out. The account must already exist;
+/// clients create it idempotently before the first escrowed `start_agreement`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The address isn't the delegate's ATA for `usdc_mint` (`InvalidEscrowAccount`)
+/// - The account isn't an initialized SPL token account (`InvalidEscrowAccount`)
+/// - The mint doesn't match `usdc_mint` (`WrongMint`)
+pub fn validate_escrow_ata<'info>(
+    escrow_ata: &UncheckedAccount<'info>,
+    program_delegate: &Pubkey,
+    usdc_mint: &Pubkey,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require!(
+        escrow_ata.key() == get_associated_token_address(program_delegate, usdc_mint),
+        RecurringPaymentError::InvalidEscrowAccount
+    );
+
+    let escrow_ata_data = escrow_ata.try_borrow_data()?;
+    require!(
+        escrow_ata_data.len() == TokenAccount::LEN && escrow_ata.owner == &token_program.key(),
+        RecurringPaymentError::InvalidEscrowAccount
+    );
+
+    let token_account = TokenAccount::unpack(&escrow_ata_data)
+        .map_err(|_| RecurringPaymentError::InvalidEscrowAccount)?;
+    require!(
+        token_account.mint == *usdc_mint,
+        RecurringPaymentError::WrongMint
+    );
+
+    Ok(())
+}
+
+/// Fee split for a single payment, in USDC micro-units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSplit {
+    /// Fee paid to the keeper executing the payment
+    pub keeper_fee: u64,
+    /// Fee paid to the platform treasury
+    pub platform_fee: u64,
+    /// Amount paid to the payee treasury
+    pub payee_amount: u64,
+}
+
+/// Splits a payment into keeper fee, platform fee, and payee amount.
+///
+/// The rounding is specified so clients can reproduce every split exactly:
+///
+/// 1. `keeper_fee = floor(amount * keeper_fee_bps / 10_000)`, taken from the full amount
+/// 2. `payee_amount = floor(remaining * (10_000 - platform_fee_bps) / 10_000)`, where
+///    `remaining = amount - keeper_fee`
+/// 3. `platform_fee = remaining - payee_amount`
+///
+/// The payee's share rounds down and the platform receives the rounding remainder,
+/// so the platform fee is the pro-rata fee rounded up. The parts are checked to sum
+/// to exactly `amount`; there is no referral fee, so it is always zero.
+/// `start_agreement` passes a keeper fee of zero.
+///
+/// # Errors
+///
+/// Returns `ArithmeticError` if the calculation overflows or a rate exceeds 100%, and
+/// `FeeSplitMismatch` if the parts do not sum to `amount`.
+pub fn calculate_fee_split(
+    amount: u64,
+    keeper_fee_bps: u16,
+    platform_fee_bps: u16,
+) -> Result<FeeSplit> {
+    let keeper_fee = u64::try_from(
+        u128::from(amount)
+            .checked_mul(u128::from(keeper_fee_bps))
+            .ok_or(RecurringPaymentError::ArithmeticError)?
+            .checked_div(FEE_BASIS_POINTS_DIVISOR)
+            .ok_or(RecurringPaymentError::ArithmeticError)?,
+    )
+    .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+
+    let remaining_after_keeper = amount
+        .checked_sub(keeper_fee)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    let payee_share_bps = FEE_BASIS_POINTS_DIVISOR
+        .checked_sub(u128::from(platform_fee_bps))
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    let payee_amount = u64::try_from(
+        u128::from(remaining_after_keeper)
+            .checked_mul(payee_share_bps)
+            .ok_or(RecurringPaymentError::ArithmeticError)?
+            .checked_div(FEE_BASIS_POINTS_DIVISOR)
+            .ok_or(RecurringPaymentError::ArithmeticError)?,
+    )
+    .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+
+    let platform_fee = remaining_after_keeper
+        .checked_sub(payee_amount)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    // Invariant: keeper + platform + payee (+ zero referral) == amount charged
+    let total = keeper_fee
+        .checked_add(platform_fee)
+        .and_then(|sum| sum.checked_add(payee_amount))
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    require!(total == amount, RecurringPaymentError::FeeSplitMismatch);
+
+    Ok(FeeSplit {
+        keeper_fee,
+        platform_fee,
+        payee_amount,
+    })
+}
+
+/// Counts a payment towards the payee's 30-day volume and emits the tier change.
+///
+/// Every instruction that moves USDC to the payee treasury calls this before
+/// computing its fee split, so the split uses the tier the payee now qualifies for
+/// and the volume window sees every payment, not only renewals.
+pub fn record_payee_volume(payee: &mut Account<Payee>, amount: u64, now: i64) {
+    let Some(old_tier) = payee.record_volume(amount, now) else {
+        return;
+    };
+    let new_tier = payee.volume_tier;
+    let new_platform_fee_bps = new_tier.platform_fee_bps();
+    if new_tier > old_tier {
+        emit!(VolumeTierUpgraded {
+            payee: payee.key(),
+            old_tier,
+            new_tier,
+            monthly_volume_usdc: payee.monthly_volume_usdc,
+            new_platform_fee_bps,
+        });
+    } else {
+        emit!(VolumeTierDowngraded {
+            payee: payee.key(),
+            old_tier,
+            new_tier,
+            monthly_volume_usdc: payee.monthly_volume_usdc,
+            new_platform_fee_bps,
+        });
+    }
+}
+
+/// Returns the gate mint if the payer qualifies for token-gated pricing.
+///
+/// Payment terms may reference a gate mint; payers holding at least one token of
+/// that mint receive `gate_discount_bps` off the payment amount. In instructions the
+/// payer signs, the payer's gate token account is supplied as the first remaining
+/// account. Omitting it simply means no discount is applied, so existing clients keep
+/// working unchanged.
+///
+/// # Arguments
+///
+/// * `payment_terms` - The payment terms being paid
+/// * `payer` - The payer whose holdings are checked
+/// * `gate_token_account` - Optional token account of the payer for the gate mint
+/// * `token_program_id` - The SPL Token program ID
+///
+/// # Errors
+///
+/// Returns `InvalidGateTokenAccount` if a gate token account is supplied but is not
+/// an SPL token account owned by the payer for the gate mint.
+pub fn qualifying_gate_mint(
+    payment_terms: &PaymentTerms,
+    payer: &Pubkey,
+    gate_token_account: Option<&AccountInfo>,
+    token_program_id: &Pubkey,
+) -> Result<Option<Pubkey>> {
+    let (Some(gate_mint), Some(gate_token_account)) = (payment_terms.gate_mint, gate_token_account)
+    else {
+        return Ok(None);
+    };
+
+    require!(
+        gate_token_account.owner == token_program_id,
+        RecurringPaymentError::InvalidGateTokenAccount
+    );
+
+    let gate_data = gate_token_account.try_borrow_data()?;
+    require!(
+        gate_data.len() == TokenAccount::LEN,
+        RecurringPaymentError::InvalidGateTokenAccount
+    );
+    let token_account = TokenAccount::unpack(&gate_data)
+        .map_err(|_| RecurringPaymentError::InvalidGateTokenAccount)?;
+
+    require!(
+        token_account.mint == gate_mint && token_account.owner == *payer,
+        RecurringPaymentError::InvalidGateTokenAccount
+    );
+
+    Ok((token_account.amount > 0).then_some(gate_mint))
+}
+
+/// Returns the gate mint if the payer qualifies for token-gated pricing, checking
+/// the payer's canonical gate token account.
+///
+/// Used where the caller is not the payer, such as a keeper executing a renewal, so
+/// the caller cannot skip the discount by leaving the gate account out or passing
+/// another account of the payer. For token-gated terms the payer's associated token
+/// account for the gate mint is required; if it was never created, the payer holds
+/// no gate tokens and pays the full price.
+///
+/// # Errors
+///
+/// Returns `GateTokenAccountRequired` if the terms are token-gated and no account is
+/// supplied, and `InvalidGateTokenAccount` if the account is not the payer's gate ATA
+/// or not a valid gate token account.
+pub fn payer_gate_mint(
+    payment_terms: &PaymentTerms,
+    payer: &Pubkey,
+    payer_gate_ata: Option<&AccountInfo>,
+    token_program_id: &Pubkey,
+) -> Result<Option<Pubkey>> {
+    let Some(gate_mint) = payment_terms.gate_mint else {
+        return Ok(None);
+    };
+    let payer_gate_ata = payer_gate_ata.ok_or(RecurringPaymentError::GateTokenAccountRequired)?;
+    require!(
+        payer_gate_ata.key() == get_associated_token_address(payer, &gate_mint),
+        RecurringPaymentError::InvalidGateTokenAccount
+    );
+
+    if payer_gate_ata.data_is_empty() {
+        return Ok(None);
+    }
+    qualifying_gate_mint(payment_terms, payer, Some(payer_gate_ata), token_program_id)
+}
+
+/// Returns the time used by due-date checks.
+///
+/// Programs built with the `test-clock` feature shift the cluster time `now` by the
+/// offset of the `TestClock` account when it is supplied among the remaining
+/// accounts, after the gate token account if any. Other builds, and test builds
+/// without the account, return `now` unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the supplied `TestClock` account cannot be deserialized.
+#[cfg(feature = "test-clock")]
+pub fn due_check_time(
+    now: i64,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<i64> {
+    let (test_clock_address, _bump) = Pubkey::find_program_address(&[b"test_clock"], program_id);
+    let Some(test_clock_info) = remaining_accounts
+        .iter()
+        .find(|account| account.key() == test_clock_address)
+    else {
+        return Ok(now);
+    };
+
+    if test_clock_info.owner != program_id || test_clock_info.data_is_empty() {
+        return Ok(now);
+    }
+
+    let test_clock = TestClock::try_deserialize(&mut test_clock_info.data.borrow().as_ref())?;
+    msg!(
+        "Test clock: shifting cluster time {} by {}s",
+        now,
+        test_clock.offset_secs
+    );
+    Ok(test_clock.now(now))
+}
+
+/// Returns the time used by due-date checks.
+///
+/// Without the `test-clock` feature this is always the cluster time `now`.
+///
+/// # Errors
+///
+/// Never returns an error; the signature matches the `test-clock` build.
+#[cfg(not(feature = "test-clock"))]
+pub const fn due_check_time(
+    now: i64,
+    _remaining_accounts: &[AccountInfo],
+    _program_id: &Pubkey,
+) -> Result<i64> {
+    Ok(now)
+}
+
+/// Applies a token-gate discount to a payment amount.
+///
+/// The discount is rounded down, so the payer is never charged less than the
+/// exact discounted price.
+///
+/// # Errors
+///
+/// Returns `ArithmeticError` if the calculation overflows.
+pub fn apply_gate_discount(amount: u64, discount_bps: u16) -> Result<u64> {
+    let discount = u64::try_from(
+        u128::from(amount)
+            .checked_mul(u128::from(discount_bps))
+            .ok_or(RecurringPaymentError::ArithmeticError)?
+            .checked_div(FEE_BASIS_POINTS_DIVISOR)
+            .ok_or(RecurringPaymentError::ArithmeticError)?,
+    )
+    .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+
+    Ok(amount
+        .checked_sub(discount)
+        .ok_or(RecurringPaymentError::ArithmeticError)?)
+}
+
+/// Removes `agreement` from its renewal queue shard for `bucket`
+///
+/// `queue_info` must be the shard PDA derived from `bucket` and `agreement`. Queues
+/// that were never created are skipped, so agreements predating renewal queues (or
+/// whose shard was never initialized) can still be processed.
+///
+/// # Errors
+///
+/// Returns `InvalidRenewalBucket` if `queue_info` is not the expected shard PDA, or
+/// a deserialization error if the queue account data is invalid.
+pub fn dequeue_renewal(
+    queue_info: &AccountInfo,
+    bucket: u64,
+    agreement: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let (expected_queue, _bump) = Pubkey::find_program_address(
+        &[
+            b"renewal_queue",
+            bucket.to_le_bytes().as_ref(),
+            RenewalQueue::shard_seed(agreement).as_ref(),
+        ],
+        program_id,
+    );
+    require!(
+        queue_info.key() == expected_queue,
+        RecurringPaymentError::InvalidRenewalBucket
+    );
+
+    if queue_info.owner != program_id || queue_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut queue = RenewalQueue::try_deserialize(&mut queue_info.data.borrow().as_ref())?;
+    if queue.remove(agreement) {
+        queue.try_serialize(&mut queue_info.data.borrow_mut().as_mut())?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +487,37 @@ mod tests {
 
         assert_ne!(ata1, ata2);
     }
+
+    #[test]
+    fn test_apply_gate_discount() {
+        assert_eq!(apply_gate_discount(10_000_000, 1_000).unwrap(), 9_000_000);
+        assert_eq!(apply_gate_discount(10_000_000, 0).unwrap(), 10_000_000);
+        // Discount rounds down: 249.75 micro-units off becomes 249
+        assert_eq!(apply_gate_discount(999, 2_500).unwrap(), 750);
+    }
+
+    #[test]
+    fn test_calculate_fee_split() {
+        let split = calculate_fee_split(100_000_000, 15, 25).unwrap();
+
+        assert_eq!(split.keeper_fee, 150_000);
+        assert_eq!(split.platform_fee, 249_625);
+        assert_eq!(split.payee_amount, 99_600_375);
+    }
+
+    #[test]
+    fn test_calculate_fee_split_remainder_to_platform() {
+        // 0.25% of 399 is 0.9975 micro-units; the payee share rounds down to 398
+        let split = calculate_fee_split(399, 0, 25).unwrap();
+
+        assert_eq!(split.platform_fee, 1);
+        assert_eq!(split.payee_amount, 398);
+        assert_eq!(calculate_fee_split(399, 0, 0).unwrap().platform_fee, 0);
+    }
+
+    #[test]
+    fn test_calculate_fee_split_rejects_rate_over_100_percent() {
+        assert!(calculate_fee_split(1_000, 0, 10_001).is_err());
+    }
 }
+
@@ -0,0 +1,483 @@
+//! Unit tests for account layout versions and `migrate_account`
+//!
+//! This test suite validates the `version` byte of Config, Payee, `PaymentTerms` and
+//! `PaymentAgreement` and the data upgrade `migrate_account` applies through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Unversioned accounts, written field by field in the layouts deployed before the
+//!   version byte, read as version 0 once zero-extended and not at all before
+//! - Migrating an unversioned account keeps every field it had, gives the fields it
+//!   lacked their initial values and sets `VERSION`
+//! - Stale bytes an unversioned config left past its data when an option shrank are
+//!   discarded
+//! - Version 1 terms and agreements upgrade to version 2 with streaming accrual unset,
+//!   even over stale bytes an option that shrank left behind
+//! - Accounts already at `VERSION` are rejected (`AlreadyMigrated` 6057)
+//! - The account sizes after the version byte and the streaming fields
+//!
+//! Migration Context:
+//! Fields are only ever appended, so an older account is a prefix of the current
+//! layout: unversioned accounts end at `bump` (at `period_secs` for terms), and
+//! version 1 terms and agreements end before the streaming fields. The handler grows
+//! them, with the payer covering the extra rent, before upgrading the data in place:
+//! ```rust
+//! if account_info.data_len() < T::LEN {
+//!     account_info.resize(T::LEN)?;
+//! }
+//! let from_version = T::upgrade(&mut account_info.try_borrow_mut_data()?)?;
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorSerialize, Discriminator};
+use common::NOW;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{
+    Config, KeeperSolRate, Payee, PaymentAgreement, PaymentTerms, PendingTermsUpdate,
+    VersionedAccount, VolumeTier,
+};
+
+/// Serialized size of the fields version 2 added to agreements: `last_sweep_ts`
+const AGREEMENT_V2_BYTES: usize = 8;
+/// Allocated size of the fields version 2 added to terms: `accrual_rate_per_sec`
+const TERMS_V2_BYTES: usize = 9;
+
+/// Allocated sizes of the unversioned layouts, including the discriminator
+const UNVERSIONED_CONFIG_LEN: usize = 138;
+const UNVERSIONED_PAYEE_LEN: usize = 122;
+const UNVERSIONED_TERMS_LEN: usize = 88;
+const UNVERSIONED_AGREEMENT_LEN: usize = 110;
+
+/// Discriminator followed by `write`'s fields, as serialized by the unversioned program
+fn unversioned_data(
+    discriminator: &[u8],
+    write: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>,
+) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    write(&mut data).unwrap();
+    data
+}
+
+/// Zero-fills `data` up to the size `init` allocated for it
+fn allocate(mut data: Vec<u8>, len: usize) -> Vec<u8> {
+    assert!(data.len() <= len);
+    data.resize(len, 0);
+    data
+}
+
+/// Config in the unversioned layout, which ended at `bump`
+fn unversioned_config(config: &Config) -> Vec<u8> {
+    unversioned_data(Config::DISCRIMINATOR, |data| {
+        config.platform_authority.serialize(data)?;
+        config.pending_authority.serialize(data)?;
+        config.max_platform_fee_bps.serialize(data)?;
+        config.min_platform_fee_bps.serialize(data)?;
+        config.min_period_seconds.serialize(data)?;
+        config.default_allowance_periods.serialize(data)?;
+        config.allowed_mint.serialize(data)?;
+        config.max_withdrawal_amount.serialize(data)?;
+        config.max_grace_period_seconds.serialize(data)?;
+        config.paused.serialize(data)?;
+        config.keeper_fee_bps.serialize(data)?;
+        config.bump.serialize(data)
+    })
+}
+
+/// Payee in the unversioned layout, which ended at `bump`
+fn unversioned_payee(payee: &Payee) -> Vec<u8> {
+    unversioned_data(Payee::DISCRIMINATOR, |data| {
+        payee.authority.serialize(data)?;
+        payee.usdc_mint.serialize(data)?;
+        payee.treasury_ata.serialize(data)?;
+        payee.volume_tier.serialize(data)?;
+        payee.monthly_volume_usdc.serialize(data)?;
+        payee.last_volume_update_ts.serialize(data)?;
+        payee.bump.serialize(data)
+    })
+}
+
+/// Payment terms in the unversioned layout, which ended at `period_secs`
+fn unversioned_terms(terms: &PaymentTerms) -> Vec<u8> {
+    unversioned_data(PaymentTerms::DISCRIMINATOR, |data| {
+        terms.payee.serialize(data)?;
+        terms.terms_id.serialize(data)?;
+        terms.amount_usdc.serialize(data)?;
+        terms.period_secs.serialize(data)
+    })
+}
+
+/// Payment agreement in the unversioned layout, which ended at `bump`
+fn unversioned_agreement(agreement: &PaymentAgreement) -> Vec<u8> {
+    unversioned_data(PaymentAgreement::DISCRIMINATOR, |data| {
+        agreement.payment_terms.serialize(data)?;
+        agreement.payer.serialize(data)?;
+        agreement.next_payment_ts.serialize(data)?;
+        agreement.active.serialize(data)?;
+        agreement.payment_count.serialize(data)?;
+        agreement.created_ts.serialize(data)?;
+        agreement.last_amount.serialize(data)?;
+        agreement.last_payment_ts.serialize(data)?;
+        agreement.bump.serialize(data)
+    })
+}
+
+/// Serialized data of `account` in layout version 1, the current layout without its
+/// last `trailing` serialized bytes
+fn version_one_bytes<T: VersionedAccount>(account: &T, trailing: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.truncate(data.len().checked_sub(trailing).unwrap());
+    data
+}
+
+/// Account data as written by the program with layout version 1, allocated at `len`
+fn version_one_data<T: VersionedAccount>(account: &T, trailing: usize, len: usize) -> Vec<u8> {
+    allocate(version_one_bytes(account, trailing), len)
+}
+
+/// Simulate `migrate_account.rs`: zero-extend to the current size, then upgrade
+fn migrate<T: VersionedAccount>(data: &mut Vec<u8>) -> anchor_lang::Result<u8> {
+    if data.len() < T::LEN {
+        data.resize(T::LEN, 0);
+    }
+    T::upgrade(data)
+}
+
+// ============================================================================
+// Unversioned Accounts
+// ============================================================================
+
+/// Test that unversioned accounts only read once zero-extended, as version 0
+#[test]
+fn test_unversioned_accounts_read_as_version_zero() {
+    let payee = common::payee();
+    let data = allocate(unversioned_payee(&payee), UNVERSIONED_PAYEE_LEN);
+    assert!(Payee::try_deserialize(&mut data.as_slice()).is_err());
+
+    let extended = allocate(data, Payee::LEN);
+    let read = Payee::try_deserialize(&mut extended.as_slice()).unwrap();
+    assert_eq!(read.version, 0);
+    assert_eq!(read.authority, payee.authority);
+    assert_eq!(read.last_volume_update_ts, payee.last_volume_update_ts);
+    assert_eq!(read.bump, payee.bump);
+
+    let agreement = common::agreement();
+    let data = allocate(unversioned_agreement(&agreement), UNVERSIONED_AGREEMENT_LEN);
+    assert!(PaymentAgreement::try_deserialize(&mut data.as_slice()).is_err());
+
+    let extended = allocate(data, PaymentAgreement::LEN);
+    let read = PaymentAgreement::try_deserialize(&mut extended.as_slice()).unwrap();
+    assert_eq!(read.version, 0);
+    assert_eq!(read.last_payment_ts, agreement.last_payment_ts);
+    assert_eq!(read.bump, agreement.bump);
+}
+
+/// Test that migrating an unversioned config keeps every field and sets the version
+#[test]
+fn test_migrate_unversioned_config() {
+    let config = Config {
+        pending_authority: Some(Pubkey::new_unique()),
+        keeper_fee_bps: 40,
+        bump: 251,
+        ..common::config()
+    };
+    let mut data = allocate(unversioned_config(&config), UNVERSIONED_CONFIG_LEN);
+
+    assert_eq!(migrate::<Config>(&mut data).unwrap(), 0);
+    assert_eq!(data.len(), Config::SPACE);
+
+    let migrated = Config::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.version, Config::VERSION);
+    assert_eq!(migrated.platform_authority, config.platform_authority);
+    assert_eq!(migrated.pending_authority, config.pending_authority);
+    assert_eq!(migrated.allowed_mint, config.allowed_mint);
+    assert_eq!(migrated.keeper_fee_bps, 40);
+    assert_eq!(migrated.bump, 251);
+    assert_eq!(migrated.pause_reason, [0; 64]);
+    assert_eq!(migrated.keeper_sol_rate, None);
+    assert_eq!(migrated.renewal_tolerance_secs, 0);
+}
+
+/// Test that stale bytes past an unversioned config's data are discarded
+///
+/// Accepting a pending authority shrank the serialized config by 32 bytes in place,
+/// leaving the tail of the previous data in the allocation where `pause_reason` now
+/// starts.
+#[test]
+fn test_migrate_unversioned_config_discards_stale_bytes() {
+    let proposed = Config {
+        pending_authority: Some(Pubkey::new_unique()),
+        ..common::config()
+    };
+    let mut data = allocate(unversioned_config(&proposed), UNVERSIONED_CONFIG_LEN);
+    let accepted = Config {
+        platform_authority: proposed.pending_authority.unwrap(),
+        pending_authority: None,
+        ..proposed
+    };
+    let shrunk = unversioned_config(&accepted);
+    data[..shrunk.len()].copy_from_slice(&shrunk);
+    assert!(data[shrunk.len()..].iter().any(|byte| *byte != 0));
+
+    assert_eq!(migrate::<Config>(&mut data).unwrap(), 0);
+
+    let migrated = Config::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.platform_authority, accepted.platform_authority);
+    assert_eq!(migrated.pending_authority, None);
+    assert_eq!(migrated.bump, accepted.bump);
+    assert_eq!(migrated.pause_reason, [0; 64]);
+}
+
+/// Test that migrating an unversioned payee pins its PDA authority and opens execution
+#[test]
+fn test_migrate_unversioned_payee() {
+    let payee = Payee {
+        volume_tier: VolumeTier::Growth,
+        monthly_volume_usdc: 25_000_000_000,
+        last_volume_update_ts: NOW,
+        bump: 254,
+        ..common::payee()
+    };
+    let mut data = allocate(unversioned_payee(&payee), UNVERSIONED_PAYEE_LEN);
+
+    assert_eq!(migrate::<Payee>(&mut data).unwrap(), 0);
+
+    let migrated = Payee::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.version, Payee::VERSION);
+    assert_eq!(migrated.authority, payee.authority);
+    assert_eq!(migrated.treasury_ata, payee.treasury_ata);
+    assert_eq!(migrated.volume_tier, VolumeTier::Growth);
+    assert_eq!(migrated.monthly_volume_usdc, 25_000_000_000);
+    assert_eq!(migrated.bump, 254);
+    assert_eq!(migrated.original_authority, payee.authority);
+    assert!(migrated.open_execution);
+    assert!(migrated.authorized_keepers.is_empty());
+}
+
+/// Test that migrating unversioned terms keeps them renewing and sets the version
+#[test]
+fn test_migrate_unversioned_terms() {
+    let terms = PaymentTerms {
+        terms_id: [7; 32],
+        ..common::terms()
+    };
+    let mut data = allocate(unversioned_terms(&terms), UNVERSIONED_TERMS_LEN);
+
+    assert_eq!(migrate::<PaymentTerms>(&mut data).unwrap(), 0);
+
+    let migrated = PaymentTerms::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.version, PaymentTerms::VERSION);
+    assert_eq!(migrated.payee, terms.payee);
+    assert_eq!(migrated.terms_id, [7; 32]);
+    assert_eq!(migrated.amount_usdc, terms.amount_usdc);
+    assert_eq!(migrated.period_secs, terms.period_secs);
+    assert_eq!(migrated.max_subscribers, None);
+    assert!(!migrated.is_streaming());
+}
+
+/// Test that migrating an unversioned agreement keeps its schedule and bump
+#[test]
+fn test_migrate_unversioned_agreement() {
+    let agreement = PaymentAgreement {
+        payment_count: 3,
+        bump: 253,
+        ..common::agreement()
+    };
+    let mut data = allocate(unversioned_agreement(&agreement), UNVERSIONED_AGREEMENT_LEN);
+
+    assert_eq!(migrate::<PaymentAgreement>(&mut data).unwrap(), 0);
+
+    let migrated = PaymentAgreement::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.version, PaymentAgreement::VERSION);
+    assert_eq!(migrated.payment_terms, agreement.payment_terms);
+    assert_eq!(migrated.payer, agreement.payer);
+    assert_eq!(migrated.next_payment_ts, agreement.next_payment_ts);
+    assert!(migrated.active);
+    assert_eq!(migrated.payment_count, 3);
+    assert_eq!(migrated.last_payment_ts, agreement.last_payment_ts);
+    assert_eq!(migrated.bump, 253);
+    assert_eq!(migrated.max_periods, None);
+    assert_eq!(migrated.last_sweep_ts, 0);
+}
+
+// ============================================================================
+// Version 1 Accounts
+// ============================================================================
+
+/// Test that version 1 agreements upgrade with no sweep recorded
+#[test]
+fn test_migrate_version_one_agreement() {
+    let v1 = PaymentAgreement {
+        max_periods: Some(12),
+        periods_paid: 3,
+        version: 1,
+        ..common::agreement()
+    };
+    let mut data = version_one_data(
+        &v1,
+        AGREEMENT_V2_BYTES,
+        PaymentAgreement::LEN - AGREEMENT_V2_BYTES,
+    );
+
+    assert_eq!(migrate::<PaymentAgreement>(&mut data).unwrap(), 1);
+    assert_eq!(data.len(), PaymentAgreement::SPACE);
+
+    let migrated = PaymentAgreement::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.version, PaymentAgreement::VERSION);
+    assert_eq!(migrated.payer, v1.payer);
+    assert_eq!(migrated.max_periods, Some(12));
+    assert_eq!(migrated.periods_paid, 3);
+    assert_eq!(migrated.last_sweep_ts, 0);
+}
+
+/// Test that upgrading a version 1 agreement discards stale bytes a shrunk option left
+/// where `last_sweep_ts` now starts
+///
+/// Accepting a transfer target cleared `pending_payer`, shrinking the serialized
+/// agreement by 32 bytes in place.
+#[test]
+fn test_migrate_version_one_agreement_discards_stale_bytes() {
+    let pending = PaymentAgreement {
+        pending_payer: Some(Pubkey::new_unique()),
+        credit_amount: 4_000_000,
+        period_index: 7,
+        deposit_held: 5_000_000,
+        version: 1,
+        ..common::agreement()
+    };
+    let mut data = version_one_data(
+        &pending,
+        AGREEMENT_V2_BYTES,
+        PaymentAgreement::LEN - AGREEMENT_V2_BYTES,
+    );
+    let cleared = PaymentAgreement {
+        pending_payer: None,
+        ..pending
+    };
+    let shrunk = version_one_bytes(&cleared, AGREEMENT_V2_BYTES);
+    data[..shrunk.len()].copy_from_slice(&shrunk);
+    let stale = &data[shrunk.len()..shrunk.len().checked_add(AGREEMENT_V2_BYTES).unwrap()];
+    assert!(stale.iter().any(|byte| *byte != 0));
+
+    assert_eq!(migrate::<PaymentAgreement>(&mut data).unwrap(), 1);
+
+    let migrated = PaymentAgreement::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.pending_payer, None);
+    assert_eq!(migrated.credit_amount, 4_000_000);
+    assert_eq!(migrated.deposit_held, 5_000_000);
+    assert_eq!(migrated.last_sweep_ts, 0);
+}
+
+/// Test that version 1 terms upgrade as renewing terms
+#[test]
+fn test_migrate_version_one_terms() {
+    let v1 = PaymentTerms {
+        terms_id: [7; 32],
+        max_subscribers: Some(100),
+        version: 1,
+        ..common::terms()
+    };
+    let mut data = version_one_data(&v1, 1, PaymentTerms::LEN - TERMS_V2_BYTES);
+
+    assert_eq!(migrate::<PaymentTerms>(&mut data).unwrap(), 1);
+
+    let migrated = PaymentTerms::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.version, PaymentTerms::VERSION);
+    assert_eq!(migrated.terms_id, [7; 32]);
+    assert_eq!(migrated.max_subscribers, Some(100));
+    assert_eq!(migrated.accrual_rate_per_sec, None);
+    assert!(!migrated.is_streaming());
+}
+
+/// Test that upgrading version 1 terms discards stale bytes a shrunk option left where
+/// `accrual_rate_per_sec` now starts
+///
+/// Applying a scheduled update cleared `pending_update`, shrinking the serialized
+/// terms by 24 bytes in place. The stale tail starts with `waitlist_len`, whose low
+/// byte reads as a set option.
+#[test]
+fn test_migrate_version_one_terms_discards_stale_bytes() {
+    let scheduled = PaymentTerms {
+        pending_update: Some(PendingTermsUpdate {
+            amount_usdc: 20_000_000,
+            period_secs: 2_592_000,
+            effective_ts: NOW,
+        }),
+        waitlist_len: 1,
+        sunset_ts: None,
+        escrow_window_secs: None,
+        lifetime_revenue_usdc: 123_456_789,
+        deposit_usdc: None,
+        version: 1,
+        ..common::terms()
+    };
+    let mut data = version_one_data(&scheduled, 1, PaymentTerms::LEN - TERMS_V2_BYTES);
+    let applied = PaymentTerms {
+        pending_update: None,
+        ..scheduled
+    };
+    let shrunk = version_one_bytes(&applied, 1);
+    data[..shrunk.len()].copy_from_slice(&shrunk);
+    assert_eq!(data[shrunk.len()], 1);
+
+    assert_eq!(migrate::<PaymentTerms>(&mut data).unwrap(), 1);
+
+    let migrated = PaymentTerms::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(migrated.pending_update, None);
+    assert_eq!(migrated.waitlist_len, 1);
+    assert_eq!(migrated.lifetime_revenue_usdc, 123_456_789);
+    assert_eq!(migrated.accrual_rate_per_sec, None);
+    assert!(!migrated.is_streaming());
+}
+
+// ============================================================================
+// Current Accounts
+// ============================================================================
+
+/// Test that accounts at the current version are not migrated again
+#[test]
+fn test_current_account_rejected() {
+    let mut data = Vec::new();
+    common::agreement().try_serialize(&mut data).unwrap();
+
+    assert_eq!(
+        migrate::<PaymentAgreement>(&mut data).unwrap_err(),
+        RecurringPaymentError::AlreadyMigrated.into()
+    );
+
+    // A migrated account can't be migrated twice either, even with every option set
+    let config = Config {
+        auto_unpause_ts: Some(NOW),
+        keeper_sol_rate: Some(KeeperSolRate {
+            lamports_per_usdc: 5_000_000,
+            updated_ts: NOW,
+        }),
+        ..common::config()
+    };
+    let mut data = allocate(unversioned_config(&config), UNVERSIONED_CONFIG_LEN);
+    assert!(migrate::<Config>(&mut data).is_ok());
+    assert!(migrate::<Config>(&mut data).is_err());
+}
+
+/// Test the current layout versions and account sizes
+#[test]
+fn test_versions_and_sizes() {
+    assert_eq!(Config::VERSION, 1);
+    assert_eq!(Payee::VERSION, 1);
+    assert_eq!(PaymentTerms::VERSION, 2);
+    assert_eq!(PaymentAgreement::VERSION, 2);
+
+    assert_eq!(Config::LEN, 237);
+    assert_eq!(Payee::LEN, 385);
+    assert_eq!(PaymentTerms::LEN, 214);
+    assert_eq!(PaymentAgreement::LEN, 259);
+}
+
+/// Test the error codes clients decode
+#[test]
+fn test_error_codes() {
+    assert_eq!(common::error_code(RecurringPaymentError::AlreadyMigrated), 6057);
+    assert_eq!(common::error_code(RecurringPaymentError::NotMigratable), 6058);
+}
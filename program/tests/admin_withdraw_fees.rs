@@ -33,6 +33,8 @@
 //! Note: These are unit tests that validate the business logic.
 //! Full end-to-end integration tests should be run with `anchor test`.
 
+mod common;
+
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::get_associated_token_address;
 use std::str::FromStr;
@@ -363,17 +365,8 @@ fn test_config_state_structure() {
 
     let config = Config {
         platform_authority,
-        pending_authority: None,
-        max_platform_fee_bps: 1000,
-        min_platform_fee_bps: 50,
-        min_period_seconds: 86400,
-        default_allowance_periods: 3,
         allowed_mint: usdc_mint,
-        max_withdrawal_amount: 1_000_000_000,
-        max_grace_period_seconds: 604_800, // 7 days
-        paused: false,
-        keeper_fee_bps: 25,
-        bump: 255,
+        ..common::config()
     };
 
     // Verify platform authority is stored correctly
@@ -404,16 +397,8 @@ fn test_config_state_with_pending_authority() {
     let config = Config {
         platform_authority,
         pending_authority: Some(pending_authority),
-        max_platform_fee_bps: 1000,
-        min_platform_fee_bps: 50,
-        min_period_seconds: 86400,
-        default_allowance_periods: 3,
         allowed_mint: usdc_mint,
-        max_withdrawal_amount: 1_000_000_000,
-        max_grace_period_seconds: 604_800, // 7 days
-        paused: false,
-        keeper_fee_bps: 25,
-        bump: 255,
+        ..common::config()
     };
 
     // Verify both authorities are stored correctly
@@ -0,0 +1,86 @@
+//! Unit tests for the `AgreementSnapshot` event
+//!
+//! This test suite validates the agreement state that lifecycle instructions emit in
+//! `AgreementSnapshot` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Status is derived from `active` and `suspension`, with suspension taking precedence
+//! - Snapshots carry the agreement address, next payment and period index
+//! - `close_agreement` reports the closed status over the final account state
+//!
+//! Indexer Context:
+//! Every instruction that mutates an agreement's lifecycle emits a snapshot next to its
+//! own event, so views can be kept current from events alone:
+//! ```rust
+//! emit!(payment_agreement.snapshot(payment_agreement.key()));
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::events::AgreementSnapshot;
+use tally_protocol::state::{AgreementStatus, PaymentAgreement, SuspensionReason};
+
+const START: i64 = 1_700_000_000;
+const PERIOD_SECS: i64 = 2_592_000;
+
+fn create_agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: START + PERIOD_SECS,
+        created_ts: START,
+        last_payment_ts: START,
+        ..common::agreement()
+    }
+}
+
+// ============================================================================
+// Status
+// ============================================================================
+
+/// Test the status of active, paused and suspended agreements
+#[test]
+fn test_status() {
+    let mut agreement = create_agreement();
+    assert_eq!(agreement.status(), AgreementStatus::Active);
+
+    agreement.active = false;
+    assert_eq!(agreement.status(), AgreementStatus::Paused);
+
+    agreement.suspension = Some(SuspensionReason::FrozenAccount);
+    assert_eq!(agreement.status(), AgreementStatus::Suspended);
+}
+
+// ============================================================================
+// Snapshot
+// ============================================================================
+
+/// Test that the snapshot reflects the agreement after a renewal
+#[test]
+fn test_snapshot_after_renewal() {
+    let address = Pubkey::new_unique();
+    let mut agreement = create_agreement();
+    agreement.next_payment_ts = START.saturating_add(PERIOD_SECS.saturating_mul(2));
+    agreement.period_index = 1;
+
+    let snapshot = agreement.snapshot(address);
+    assert_eq!(snapshot.payment_agreement, address);
+    assert_eq!(snapshot.status, AgreementStatus::Active);
+    assert_eq!(snapshot.next_payment_ts, agreement.next_payment_ts);
+    assert_eq!(snapshot.period_index, 1);
+}
+
+/// Test the snapshot emitted by `close_agreement`
+#[test]
+fn test_snapshot_on_close() {
+    let address = Pubkey::new_unique();
+    let mut agreement = create_agreement();
+    agreement.active = false;
+
+    let snapshot = AgreementSnapshot {
+        status: AgreementStatus::Closed,
+        ..agreement.snapshot(address)
+    };
+    assert_eq!(snapshot.status, AgreementStatus::Closed);
+    assert_eq!(snapshot.next_payment_ts, agreement.next_payment_ts);
+}
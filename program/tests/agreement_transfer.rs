@@ -0,0 +1,203 @@
+//! Unit tests for moving a payment agreement between payer wallets
+//!
+//! This test suite validates `initiate_agreement_transfer` and
+//! `accept_agreement_transfer` through unit tests. For full integration tests with
+//! BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Initiation records the proposed wallet and rejects the current payer
+//! - Re-initiating redirects a pending transfer
+//! - Acceptance requires a pending transfer and the proposed wallet's signature
+//! - Acceptance requires a delegate approval covering one period from the new wallet
+//! - Renewal history and billing schedule carry over to the new agreement
+//!
+//! Business Context:
+//! Agreement PDAs are derived from the payer, so a transfer copies the agreement to
+//! the new payer's PDA and closes the old one:
+//! ```rust
+//! seeds = [b"payment_agreement", payment_terms.key().as_ref(), new_payer.key().as_ref()]
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const AMOUNT: u64 = 10 * ONE_USDC;
+const CREATED: i64 = 1_690_000_000;
+const LAST_PAYMENT: i64 = 1_700_000_000;
+
+fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: LAST_PAYMENT + 2_592_000,
+        payment_count: 4,
+        created_ts: CREATED,
+        last_amount: AMOUNT,
+        last_payment_ts: LAST_PAYMENT,
+        last_pull_period_index: 4,
+        ..common::agreement()
+    }
+}
+
+/// Delegate approval on the new payer's USDC account
+struct Approval {
+    delegated_to_program: bool,
+    delegated_amount: u64,
+}
+
+/// Simulate `initiate_agreement_transfer.rs`
+fn initiate(
+    agreement: &mut PaymentAgreement,
+    new_payer: Pubkey,
+) -> Result<(), RecurringPaymentError> {
+    if new_payer == agreement.payer {
+        return Err(RecurringPaymentError::InvalidTransferTarget);
+    }
+    agreement.pending_payer = Some(new_payer);
+    Ok(())
+}
+
+/// Simulate `accept_agreement_transfer.rs`, returning the agreement at the new PDA
+fn accept(
+    agreement: &PaymentAgreement,
+    signer: Pubkey,
+    approval: &Approval,
+) -> Result<PaymentAgreement, RecurringPaymentError> {
+    let pending_payer = agreement
+        .pending_payer
+        .ok_or(RecurringPaymentError::NoPendingTransfer)?;
+    if signer != pending_payer {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    if !approval.delegated_to_program || approval.delegated_amount < AMOUNT {
+        return Err(RecurringPaymentError::InsufficientAllowance);
+    }
+    Ok(PaymentAgreement {
+        payer: signer,
+        pending_payer: None,
+        ..agreement.clone()
+    })
+}
+
+const fn approved(delegated_amount: u64) -> Approval {
+    Approval {
+        delegated_to_program: true,
+        delegated_amount,
+    }
+}
+
+// ============================================================================
+// Initiation Tests
+// ============================================================================
+
+/// Test that initiation records the proposed wallet without moving the agreement
+#[test]
+fn test_initiate_records_pending_payer() {
+    let mut agreement = agreement();
+    let payer = agreement.payer;
+    let new_payer = Pubkey::new_unique();
+
+    initiate(&mut agreement, new_payer).unwrap();
+
+    assert_eq!(agreement.pending_payer, Some(new_payer));
+    assert_eq!(agreement.payer, payer);
+    assert!(agreement.active);
+}
+
+/// Test that the current payer cannot be proposed
+#[test]
+fn test_initiate_rejects_current_payer() {
+    let mut agreement = agreement();
+    let payer = agreement.payer;
+
+    let result = initiate(&mut agreement, payer);
+    assert!(matches!(
+        result,
+        Err(RecurringPaymentError::InvalidTransferTarget)
+    ));
+}
+
+/// Test that re-initiating redirects the transfer to the latest wallet
+#[test]
+fn test_reinitiate_redirects_transfer() {
+    let mut agreement = agreement();
+    let mistaken = Pubkey::new_unique();
+    let intended = Pubkey::new_unique();
+
+    initiate(&mut agreement, mistaken).unwrap();
+    initiate(&mut agreement, intended).unwrap();
+
+    assert!(matches!(
+        accept(&agreement, mistaken, &approved(AMOUNT)),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+    assert!(accept(&agreement, intended, &approved(AMOUNT)).is_ok());
+}
+
+// ============================================================================
+// Acceptance Tests
+// ============================================================================
+
+/// Test that acceptance without a pending transfer fails
+#[test]
+fn test_accept_without_pending_transfer() {
+    let agreement = agreement();
+
+    let result = accept(&agreement, Pubkey::new_unique(), &approved(AMOUNT));
+    assert!(matches!(
+        result,
+        Err(RecurringPaymentError::NoPendingTransfer)
+    ));
+}
+
+/// Test that only the proposed wallet can accept
+#[test]
+fn test_accept_by_other_wallet_unauthorized() {
+    let mut agreement = agreement();
+    initiate(&mut agreement, Pubkey::new_unique()).unwrap();
+
+    let result = accept(&agreement, Pubkey::new_unique(), &approved(AMOUNT));
+    assert!(matches!(result, Err(RecurringPaymentError::Unauthorized)));
+}
+
+/// Test that the new wallet must approve the program delegate for one period
+#[test]
+fn test_accept_requires_delegate_approval() {
+    let mut agreement = agreement();
+    let new_payer = Pubkey::new_unique();
+    initiate(&mut agreement, new_payer).unwrap();
+
+    let not_delegated = Approval {
+        delegated_to_program: false,
+        delegated_amount: AMOUNT,
+    };
+    assert!(matches!(
+        accept(&agreement, new_payer, &not_delegated),
+        Err(RecurringPaymentError::InsufficientAllowance)
+    ));
+    assert!(matches!(
+        accept(&agreement, new_payer, &approved(AMOUNT - 1)),
+        Err(RecurringPaymentError::InsufficientAllowance)
+    ));
+}
+
+/// Test that history and schedule carry over to the new payer's agreement
+#[test]
+fn test_accept_preserves_history() {
+    let mut agreement = agreement();
+    let new_payer = Pubkey::new_unique();
+    initiate(&mut agreement, new_payer).unwrap();
+
+    let transferred = accept(&agreement, new_payer, &approved(3 * AMOUNT)).unwrap();
+
+    assert_eq!(transferred.payer, new_payer);
+    assert_eq!(transferred.pending_payer, None);
+    assert_eq!(transferred.payment_terms, agreement.payment_terms);
+    assert_eq!(transferred.payment_count, 4);
+    assert_eq!(transferred.created_ts, CREATED);
+    assert_eq!(transferred.next_payment_ts, agreement.next_payment_ts);
+    assert_eq!(transferred.last_pull_period_index, 4);
+    assert!(transferred.active);
+}
@@ -5,6 +5,7 @@
 //!
 //! Test coverage:
 //! - Start subscription multi-period allowance validation
+//! - Requested allowance periods are raised to the platform default
 //! - Renewal single-period allowance validation
 //! - Low allowance warning threshold detection (2x plan price)
 //! - Arithmetic overflow safety in allowance calculations
@@ -23,6 +24,10 @@
 //! flexibility in allowance management. The `LowAllowanceWarning` event provides
 //! proactive UX to prevent renewal interruptions.
 
+mod common;
+
+use tally_protocol::state::Config;
+
 /// Test that start subscription requires multi-period allowance (default 3x)
 #[test]
 fn test_start_subscription_requires_multi_period_allowance() {
@@ -480,3 +485,37 @@ fn test_low_allowance_warning_event_data() {
         "User needs 5 USDC more to reach recommended"
     );
 }
+
+// ============================================================================
+// Platform Default Allowance Periods
+// ============================================================================
+
+fn config(default_allowance_periods: u8) -> Config {
+    Config {
+        default_allowance_periods,
+        ..common::config()
+    }
+}
+
+/// Test that requests below the platform default are raised to it
+#[test]
+fn test_allowance_periods_enforces_platform_default() {
+    let config = config(3);
+    assert_eq!(config.allowance_periods(0), 3, "Zero falls back to default");
+    assert_eq!(config.allowance_periods(1), 3, "Below default is raised");
+    assert_eq!(config.allowance_periods(3), 3, "Default is kept");
+}
+
+/// Test that payers can still request more periods than the default
+#[test]
+fn test_allowance_periods_keeps_higher_request() {
+    let config = config(3);
+    assert_eq!(config.allowance_periods(6), 6);
+    assert_eq!(config.allowance_periods(u8::MAX), u8::MAX);
+
+    // Required allowance for a 10 USDC plan with the enforced periods
+    let required = u64::from(config.allowance_periods(1))
+        .checked_mul(10_000_000)
+        .unwrap();
+    assert_eq!(required, 30_000_000, "One period request still needs 3x");
+}
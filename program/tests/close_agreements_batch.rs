@@ -0,0 +1,193 @@
+//! Unit tests for closing payment agreements in batches
+//!
+//! This test suite validates the checks `close_agreements_batch` applies to each
+//! agreement passed as a remaining account through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Batches hold between 1 and `MAX_CLOSE_BATCH_AGREEMENTS` agreements (`InvalidBatchSize` 6056)
+//! - Every agreement must belong to the signer and sit at its PDA
+//! - Active agreements are rejected (`AlreadyActive`)
+//! - Agreements holding a security deposit are left to `close_agreement` (`DepositHeld` 6055)
+//! - One invalid agreement fails the whole batch
+//!
+//! Security Context:
+//! Remaining accounts are not validated by Anchor, so the handler re-derives each
+//! agreement's PDA from its stored terms, the signer and its bump:
+//! ```rust
+//! let expected_address = Pubkey::create_program_address(
+//!     &[b"payment_agreement", payment_agreement.payment_terms.as_ref(), payer.key().as_ref(), &[payment_agreement.bump]],
+//!     ctx.program_id,
+//! )?;
+//! require!(agreement_info.key() == expected_address, RecurringPaymentError::PaymentAgreementNotFound);
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::constants::MAX_CLOSE_BATCH_AGREEMENTS;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const START: i64 = 1_700_000_000;
+
+fn create_agreement(program_id: &Pubkey, payer: Pubkey) -> (Pubkey, PaymentAgreement) {
+    let payment_terms = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"payment_agreement", payment_terms.as_ref(), payer.as_ref()],
+        program_id,
+    );
+    let agreement = PaymentAgreement {
+        payment_terms,
+        payer,
+        next_payment_ts: START,
+        active: false,
+        payment_count: 3,
+        created_ts: START,
+        last_payment_ts: START,
+        periods_paid: 3,
+        paused_at_ts: Some(START),
+        bump,
+        ..common::agreement()
+    };
+    (address, agreement)
+}
+
+/// Simulate the checks of `close_agreements_batch.rs`
+fn check_batch(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    agreements: &[(Pubkey, PaymentAgreement)],
+) -> Result<(), RecurringPaymentError> {
+    if agreements.is_empty() || agreements.len() > MAX_CLOSE_BATCH_AGREEMENTS {
+        return Err(RecurringPaymentError::InvalidBatchSize);
+    }
+    for (address, agreement) in agreements {
+        if agreement.payer != *payer {
+            return Err(RecurringPaymentError::Unauthorized);
+        }
+        let expected_address = Pubkey::create_program_address(
+            &[
+                b"payment_agreement",
+                agreement.payment_terms.as_ref(),
+                payer.as_ref(),
+                &[agreement.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| RecurringPaymentError::PaymentAgreementNotFound)?;
+        if *address != expected_address {
+            return Err(RecurringPaymentError::PaymentAgreementNotFound);
+        }
+        if agreement.active {
+            return Err(RecurringPaymentError::AlreadyActive);
+        }
+        if agreement.deposit_held > 0 {
+            return Err(RecurringPaymentError::DepositHeld);
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Batch Size
+// ============================================================================
+
+/// Test that a full batch of the payer's paused agreements passes
+#[test]
+fn test_full_batch_closes() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let agreements: Vec<_> = (0..MAX_CLOSE_BATCH_AGREEMENTS)
+        .map(|_| create_agreement(&program_id, payer))
+        .collect();
+
+    assert!(check_batch(&program_id, &payer, &agreements).is_ok());
+}
+
+/// Test that empty and oversized batches are rejected
+#[test]
+fn test_batch_size_bounds() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let agreements: Vec<_> = (0..=MAX_CLOSE_BATCH_AGREEMENTS)
+        .map(|_| create_agreement(&program_id, payer))
+        .collect();
+
+    assert!(matches!(
+        check_batch(&program_id, &payer, &[]),
+        Err(RecurringPaymentError::InvalidBatchSize)
+    ));
+    assert!(matches!(
+        check_batch(&program_id, &payer, &agreements),
+        Err(RecurringPaymentError::InvalidBatchSize)
+    ));
+}
+
+// ============================================================================
+// Agreement Checks
+// ============================================================================
+
+/// Test that another payer's agreement fails the whole batch
+#[test]
+fn test_other_payer_rejected() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let agreements = vec![
+        create_agreement(&program_id, payer),
+        create_agreement(&program_id, Pubkey::new_unique()),
+    ];
+
+    assert!(matches!(
+        check_batch(&program_id, &payer, &agreements),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+}
+
+/// Test that an agreement passed at an address other than its PDA is rejected
+#[test]
+fn test_wrong_address_rejected() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let (_, agreement) = create_agreement(&program_id, payer);
+
+    assert!(matches!(
+        check_batch(&program_id, &payer, &[(Pubkey::new_unique(), agreement)]),
+        Err(RecurringPaymentError::PaymentAgreementNotFound)
+    ));
+}
+
+/// Test that active agreements must be paused first
+#[test]
+fn test_active_agreement_rejected() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let (address, mut agreement) = create_agreement(&program_id, payer);
+    agreement.active = true;
+
+    assert!(matches!(
+        check_batch(&program_id, &payer, &[(address, agreement)]),
+        Err(RecurringPaymentError::AlreadyActive)
+    ));
+}
+
+/// Test that agreements holding a deposit are left to `close_agreement`
+#[test]
+fn test_deposit_held_rejected() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let (address, mut agreement) = create_agreement(&program_id, payer);
+    agreement.deposit_held = 5_000_000;
+
+    assert!(matches!(
+        check_batch(&program_id, &payer, &[(address, agreement)]),
+        Err(RecurringPaymentError::DepositHeld)
+    ));
+}
+
+/// Test the error codes clients decode
+#[test]
+fn test_error_codes() {
+    assert_eq!(common::error_code(RecurringPaymentError::DepositHeld), 6055);
+    assert_eq!(common::error_code(RecurringPaymentError::InvalidBatchSize), 6056);
+}
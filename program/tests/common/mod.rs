@@ -0,0 +1,140 @@
+//! Account factories shared by the program unit tests
+//!
+//! Each factory returns an account with neutral values. Tests override only the
+//! fields they exercise with struct update syntax, so adding a field to an account
+//! only touches this module:
+//! ```ignore
+//! let agreement = PaymentAgreement {
+//!     last_pull_period_index: 3,
+//!     ..common::agreement()
+//! };
+//! ```
+
+// Every test binary compiles this module but uses only some of the factories
+#![allow(dead_code)]
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{
+    Config, Payee, PaymentAgreement, PaymentTerms, VersionedAccount, VolumeTier,
+};
+
+/// 1 USDC with 6 decimals
+pub const ONE_USDC: u64 = 1_000_000;
+/// Default payment period
+pub const THIRTY_DAYS: u64 = 2_592_000;
+/// Timestamp the factories use for creation and the last payment
+pub const NOW: i64 = 1_700_000_000;
+
+/// Config with the documented defaults and no pause
+pub fn config() -> Config {
+    Config {
+        platform_authority: Pubkey::new_unique(),
+        pending_authority: None,
+        max_platform_fee_bps: 1000,
+        min_platform_fee_bps: 50,
+        min_period_seconds: 86_400,
+        default_allowance_periods: 3,
+        allowed_mint: Pubkey::new_unique(),
+        max_withdrawal_amount: 1_000_000_000,
+        max_grace_period_seconds: 604_800,
+        paused: false,
+        keeper_fee_bps: 25,
+        pause_reason: [0; 64],
+        auto_unpause_ts: None,
+        keeper_sol_rate: None,
+        renewal_tolerance_secs: 0,
+        bump: 255,
+        version: Config::VERSION,
+    }
+}
+
+/// Standard-tier payee with open execution and no volume
+pub fn payee() -> Payee {
+    let authority = Pubkey::new_unique();
+    Payee {
+        authority,
+        usdc_mint: Pubkey::new_unique(),
+        treasury_ata: Pubkey::new_unique(),
+        volume_tier: VolumeTier::Standard,
+        monthly_volume_usdc: 0,
+        last_volume_update_ts: 0,
+        frozen: false,
+        open_execution: true,
+        authorized_keepers: Vec::new(),
+        original_authority: authority,
+        pending_authority: None,
+        fee_holiday: None,
+        active_agreements: 0,
+        lifetime_revenue_usdc: 0,
+        lifetime_renewals: 0,
+        bump: 255,
+        version: Payee::VERSION,
+    }
+}
+
+/// 10 USDC every 30 days, with no gate, cap, escrow, deposit or streaming
+pub fn terms() -> PaymentTerms {
+    PaymentTerms {
+        payee: Pubkey::new_unique(),
+        terms_id: [0; 32],
+        amount_usdc: 10 * ONE_USDC,
+        period_secs: THIRTY_DAYS,
+        gate_mint: None,
+        gate_discount_bps: 0,
+        pending_update: None,
+        max_subscribers: None,
+        active_agreements: 0,
+        waitlist_len: 0,
+        sunset_ts: None,
+        escrow_window_secs: None,
+        lifetime_revenue_usdc: 0,
+        lifetime_renewals: 0,
+        deposit_usdc: None,
+        version: PaymentTerms::VERSION,
+        accrual_rate_per_sec: None,
+    }
+}
+
+/// Active agreement whose first 10 USDC payment was charged at `NOW`
+pub fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        payment_terms: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        next_payment_ts: NOW.checked_add(THIRTY_DAYS.try_into().unwrap()).unwrap(),
+        active: true,
+        payment_count: 1,
+        created_ts: NOW,
+        last_amount: 10 * ONE_USDC,
+        last_payment_ts: NOW,
+        last_pull_period_index: 0,
+        cancel_at_period_end: false,
+        pending_payer: None,
+        refunded_amount: 0,
+        max_periods: None,
+        periods_paid: 1,
+        external_ref_hash: None,
+        paused_at_ts: None,
+        credit_amount: 0,
+        escrow: None,
+        period_index: 0,
+        suspension: None,
+        deposit_held: 0,
+        bump: 255,
+        version: PaymentAgreement::VERSION,
+        last_sweep_ts: 0,
+    }
+}
+
+/// Anchor error code number clients decode for `error`
+pub fn error_code(error: RecurringPaymentError) -> u32 {
+    anchor_error_code(error.into())
+}
+
+/// Error code number of an error returned by program code
+pub fn anchor_error_code(error: anchor_lang::error::Error) -> u32 {
+    match error {
+        anchor_lang::error::Error::AnchorError(anchor_err) => anchor_err.error_code_number,
+        anchor_lang::error::Error::ProgramError(_) => panic!("Expected an AnchorError"),
+    }
+}
@@ -0,0 +1,388 @@
+//! Unit tests for escrowed first payments with an activation window
+//!
+//! This test suite validates the escrow path of `start_agreement`, `confirm_activation`
+//! and `refund_escrow` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Escrow window must be non-zero and no longer than the period
+//! - Terms with an escrow window hold the whole first payment until activation
+//! - The payee can confirm activation until the window lapses, splitting the escrow
+//! - A frozen payee cannot confirm activation
+//! - Lapsed escrows are refunded to the payer and the agreement is deactivated
+//! - Payments, pauses and transfers are rejected while the escrow is pending
+//! - `PaymentTerms::SPACE` covers the new field
+//! - Escrow error codes
+//! - Payee authority and escrow account validation (not tested here, enforced by
+//!   `has_one` and `validate_escrow_ata`)
+//!
+//! Business Context:
+//! Merchants selling high-ticket plans want the first payment held briefly before it
+//! reaches their treasury. Escrowed payments are released by the payee or returned
+//! to the payer, never both:
+//! ```rust
+//! let escrow = payment_agreement.escrow.ok_or(RecurringPaymentError::NoEscrowPending)?;
+//! require!(current_time < escrow.expires_ts, RecurringPaymentError::EscrowExpired);
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{AgreementEscrow, Payee, PaymentAgreement, PaymentTerms, VolumeTier};
+use tally_protocol::utils::{calculate_fee_split, FeeSplit};
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: u64 = 2_592_000;
+const THREE_DAYS: u64 = 259_200;
+const NOW: i64 = 1_700_000_000;
+
+fn terms(escrow_window_secs: Option<u64>) -> PaymentTerms {
+    PaymentTerms {
+        amount_usdc: 5_000 * ONE_USDC,
+        escrow_window_secs,
+        ..common::terms()
+    }
+}
+
+/// Simulate the escrow window validation of `create_payment_terms.rs`
+const fn validate_escrow_window(
+    period_secs: u64,
+    escrow_window_secs: Option<u64>,
+) -> Result<(), RecurringPaymentError> {
+    if let Some(escrow_window_secs) = escrow_window_secs {
+        if escrow_window_secs == 0 || escrow_window_secs > period_secs {
+            return Err(RecurringPaymentError::InvalidPaymentTerms);
+        }
+    }
+    Ok(())
+}
+
+/// Simulate `start_agreement.rs` for a new agreement, returning the agreement and the
+/// amount transferred to the escrow account
+fn start(
+    terms: &mut PaymentTerms,
+    now: i64,
+) -> Result<(PaymentAgreement, u64), RecurringPaymentError> {
+    terms.active_agreements = terms
+        .active_agreements
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    let payment_amount = terms.amount_usdc;
+    let escrow = match terms.escrow_window_secs {
+        Some(escrow_window_secs) => {
+            let window_i64 = i64::try_from(escrow_window_secs)
+                .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+            Some(AgreementEscrow {
+                amount: payment_amount,
+                expires_ts: now
+                    .checked_add(window_i64)
+                    .ok_or(RecurringPaymentError::ArithmeticError)?,
+            })
+        }
+        None => None,
+    };
+    let period_i64 =
+        i64::try_from(terms.period_secs).map_err(|_| RecurringPaymentError::ArithmeticError)?;
+
+    let agreement = PaymentAgreement {
+        next_payment_ts: now
+            .checked_add(period_i64)
+            .ok_or(RecurringPaymentError::ArithmeticError)?,
+        payment_count: 0,
+        created_ts: now,
+        last_amount: payment_amount,
+        last_payment_ts: now,
+        escrow,
+        ..common::agreement()
+    };
+    let escrowed = escrow.map_or(0, |escrow| escrow.amount);
+    Ok((agreement, escrowed))
+}
+
+/// Simulate `confirm_activation.rs`, returning the fee split released from escrow
+fn confirm_activation(
+    payee: &Payee,
+    agreement: &mut PaymentAgreement,
+    now: i64,
+    platform_fee_bps: u16,
+) -> Result<FeeSplit, RecurringPaymentError> {
+    if payee.frozen {
+        return Err(RecurringPaymentError::PayeeFrozen);
+    }
+    let escrow = agreement
+        .escrow
+        .ok_or(RecurringPaymentError::NoEscrowPending)?;
+    if now >= escrow.expires_ts {
+        return Err(RecurringPaymentError::EscrowExpired);
+    }
+    let split = calculate_fee_split(escrow.amount, 0, platform_fee_bps)
+        .map_err(|_| RecurringPaymentError::ArithmeticError)?;
+    agreement.escrow = None;
+    Ok(split)
+}
+
+/// Simulate `refund_escrow.rs`, returning the amount returned to the payer
+fn refund_escrow(
+    agreement: &mut PaymentAgreement,
+    terms: &mut PaymentTerms,
+    now: i64,
+) -> Result<u64, RecurringPaymentError> {
+    let escrow = agreement
+        .escrow
+        .ok_or(RecurringPaymentError::NoEscrowPending)?;
+    if now < escrow.expires_ts {
+        return Err(RecurringPaymentError::EscrowNotExpired);
+    }
+    if agreement.active {
+        terms.release_subscriber_slot();
+    }
+    agreement.active = false;
+    agreement.escrow = None;
+    agreement.refunded_amount = agreement
+        .refunded_amount
+        .saturating_add(escrow.amount)
+        .min(agreement.last_amount);
+    Ok(escrow.amount)
+}
+
+/// Simulate the escrow check shared by `execute_payment.rs`, `pause_agreement.rs`,
+/// `initiate_agreement_transfer.rs` and `refund_payment.rs`
+const fn check_no_escrow(agreement: &PaymentAgreement) -> Result<(), RecurringPaymentError> {
+    if agreement.escrow_pending() {
+        return Err(RecurringPaymentError::EscrowPending);
+    }
+    Ok(())
+}
+
+fn window_end() -> i64 {
+    NOW.checked_add(i64::try_from(THREE_DAYS).unwrap()).unwrap()
+}
+
+// ============================================================================
+// Escrow Window Configuration
+// ============================================================================
+
+/// Test that the escrow window must be non-zero and no longer than the period
+#[test]
+fn test_escrow_window_bounds() {
+    assert!(validate_escrow_window(THIRTY_DAYS, None).is_ok());
+    assert!(validate_escrow_window(THIRTY_DAYS, Some(THREE_DAYS)).is_ok());
+    assert!(validate_escrow_window(THIRTY_DAYS, Some(THIRTY_DAYS)).is_ok());
+    assert!(matches!(
+        validate_escrow_window(THIRTY_DAYS, Some(0)),
+        Err(RecurringPaymentError::InvalidPaymentTerms)
+    ));
+    assert!(matches!(
+        validate_escrow_window(THIRTY_DAYS, Some(THIRTY_DAYS.checked_add(1).unwrap())),
+        Err(RecurringPaymentError::InvalidPaymentTerms)
+    ));
+}
+
+// ============================================================================
+// Start
+// ============================================================================
+
+/// Test that escrowed terms hold the whole first payment until the window ends
+#[test]
+fn test_start_escrows_first_payment() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (agreement, escrowed) = start(&mut terms, NOW).unwrap();
+
+    assert_eq!(escrowed, 5_000 * ONE_USDC);
+    assert_eq!(
+        agreement.escrow,
+        Some(AgreementEscrow {
+            amount: 5_000 * ONE_USDC,
+            expires_ts: window_end(),
+        })
+    );
+    assert!(agreement.escrow_pending());
+    assert!(agreement.active);
+    assert_eq!(terms.active_agreements, 1);
+}
+
+/// Test that terms without an escrow window pay the treasuries directly
+#[test]
+fn test_start_without_window_skips_escrow() {
+    let mut terms = terms(None);
+    let (agreement, escrowed) = start(&mut terms, NOW).unwrap();
+
+    assert_eq!(escrowed, 0);
+    assert!(!agreement.escrow_pending());
+    assert!(check_no_escrow(&agreement).is_ok());
+}
+
+// ============================================================================
+// Confirm Activation
+// ============================================================================
+
+/// Test that confirming activation splits the escrow at the payee's fee rate
+#[test]
+fn test_confirm_activation_releases_escrow() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+
+    let split = confirm_activation(
+        &common::payee(),
+        &mut agreement,
+        window_end().checked_sub(1).unwrap(),
+        VolumeTier::Standard.platform_fee_bps(),
+    )
+    .unwrap();
+
+    assert_eq!(split.platform_fee, 12_500_000); // 0.25%
+    assert_eq!(split.payee_amount, 4_987_500_000);
+    assert_eq!(split.keeper_fee, 0);
+    assert!(!agreement.escrow_pending());
+    assert!(agreement.active, "Confirmed agreements keep renewing");
+    assert!(check_no_escrow(&agreement).is_ok());
+}
+
+/// Test that the payee cannot confirm once the window has lapsed
+#[test]
+fn test_confirm_activation_after_window_rejected() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+
+    assert!(matches!(
+        confirm_activation(&common::payee(), &mut agreement, window_end(), 25),
+        Err(RecurringPaymentError::EscrowExpired)
+    ));
+    assert!(agreement.escrow_pending());
+}
+
+/// Test that an escrow cannot be released twice
+#[test]
+fn test_confirm_activation_without_escrow_rejected() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+    confirm_activation(&common::payee(), &mut agreement, NOW, 25).unwrap();
+
+    assert!(matches!(
+        confirm_activation(&common::payee(), &mut agreement, NOW, 25),
+        Err(RecurringPaymentError::NoEscrowPending)
+    ));
+}
+
+/// Test that a frozen payee cannot release the escrow to its treasury
+#[test]
+fn test_confirm_activation_frozen_payee_rejected() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+    let frozen = Payee {
+        frozen: true,
+        ..common::payee()
+    };
+
+    assert!(matches!(
+        confirm_activation(&frozen, &mut agreement, NOW, 25),
+        Err(RecurringPaymentError::PayeeFrozen)
+    ));
+    assert!(agreement.escrow_pending());
+}
+
+// ============================================================================
+// Refund
+// ============================================================================
+
+/// Test that a lapsed escrow is refunded and the agreement deactivated
+#[test]
+fn test_refund_after_window() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+
+    let refunded = refund_escrow(&mut agreement, &mut terms, window_end()).unwrap();
+
+    assert_eq!(refunded, 5_000 * ONE_USDC);
+    assert!(!agreement.active);
+    assert!(!agreement.escrow_pending());
+    assert_eq!(agreement.refunded_amount, agreement.last_amount);
+    assert_eq!(terms.active_agreements, 0, "Subscriber slot is released");
+}
+
+/// Test that the escrow cannot be refunded while the payee can still confirm
+#[test]
+fn test_refund_before_window_rejected() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+
+    assert!(matches!(
+        refund_escrow(
+            &mut agreement,
+            &mut terms,
+            window_end().checked_sub(1).unwrap()
+        ),
+        Err(RecurringPaymentError::EscrowNotExpired)
+    ));
+    assert!(agreement.active);
+}
+
+/// Test that a released escrow cannot also be refunded
+#[test]
+fn test_refund_after_release_rejected() {
+    let mut terms = terms(Some(THREE_DAYS));
+    let (mut agreement, _) = start(&mut terms, NOW).unwrap();
+    confirm_activation(&common::payee(), &mut agreement, NOW, 25).unwrap();
+
+    assert!(matches!(
+        refund_escrow(&mut agreement, &mut terms, window_end()),
+        Err(RecurringPaymentError::NoEscrowPending)
+    ));
+    assert!(agreement.active);
+}
+
+// ============================================================================
+// Pending Escrow
+// ============================================================================
+
+/// Test that agreement operations wait until the escrow is settled
+#[test]
+fn test_operations_blocked_while_pending() {
+    let mut terms = terms(Some(THIRTY_DAYS));
+    let (agreement, _) = start(&mut terms, NOW).unwrap();
+
+    assert!(matches!(
+        check_no_escrow(&agreement),
+        Err(RecurringPaymentError::EscrowPending)
+    ));
+    // The longest window ends no later than the second payment falls due
+    assert!(agreement.escrow.unwrap().expires_ts <= agreement.next_payment_ts);
+}
+
+// ============================================================================
+// Account Size and Error Codes
+// ============================================================================
+
+/// Test that the account size covers the escrow window
+#[test]
+fn test_payment_terms_space() {
+    use anchor_lang::AnchorSerialize;
+
+    let mut terms = terms(Some(THREE_DAYS));
+    terms.gate_mint = Some(Pubkey::new_unique());
+    terms.max_subscribers = Some(100);
+    terms.sunset_ts = Some(NOW);
+    terms.deposit_usdc = Some(50 * ONE_USDC);
+    terms.accrual_rate_per_sec = Some(1);
+    terms.pending_update = Some(tally_protocol::state::PendingTermsUpdate {
+        amount_usdc: ONE_USDC,
+        period_secs: THIRTY_DAYS,
+        effective_ts: NOW,
+    });
+
+    let serialized_len = terms.try_to_vec().unwrap().len();
+    assert_eq!(PaymentTerms::SPACE, 214);
+    assert_eq!(serialized_len + 8, PaymentTerms::SPACE);
+}
+
+/// Test that the escrow errors have stable error codes
+#[test]
+fn test_escrow_error_codes() {
+    assert_eq!(u32::from(RecurringPaymentError::EscrowPending), 6041);
+    assert_eq!(u32::from(RecurringPaymentError::NoEscrowPending), 6042);
+    assert_eq!(u32::from(RecurringPaymentError::EscrowExpired), 6043);
+    assert_eq!(u32::from(RecurringPaymentError::EscrowNotExpired), 6044);
+    assert_eq!(u32::from(RecurringPaymentError::InvalidEscrowAccount), 6045);
+}
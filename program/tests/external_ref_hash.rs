@@ -0,0 +1,129 @@
+//! Unit tests for external customer reference hashes on payment agreements
+//!
+//! This test suite validates `external_ref_hash` handling in `start_agreement` and
+//! `accept_agreement_transfer` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - New agreements store the supplied hash
+//! - Reactivation without a hash keeps the stored one
+//! - Reactivation with a hash replaces the stored one
+//! - Agreement transfers carry the hash to the new payer's agreement
+//! - `PaymentAgreement::SPACE` covers the new field
+//!
+//! Business Context:
+//! Payees link agreements to their internal customer ids without publishing them.
+//! The payee hashes the id with a secret salt off-chain and passes the hash to
+//! `start_agreement`, which stores it and emits it in `PaymentAgreementStarted`:
+//! ```rust
+//! payment_agreement.external_ref_hash = args.external_ref_hash;
+//! emit!(PaymentAgreementStarted {
+//!     // ...
+//!     external_ref_hash: payment_agreement.external_ref_hash,
+//! });
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::state::{AgreementEscrow, PaymentAgreement, SuspensionReason};
+
+const START: i64 = 1_700_000_000;
+const THIRTY_DAYS: i64 = 2_592_000;
+const CUSTOMER_REF: [u8; 32] = [0xAB; 32];
+
+/// Simulate `start_agreement.rs` for a new agreement
+fn start(external_ref_hash: Option<[u8; 32]>) -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: START + THIRTY_DAYS,
+        payment_count: 0,
+        created_ts: START,
+        last_payment_ts: START,
+        external_ref_hash,
+        ..common::agreement()
+    }
+}
+
+/// Simulate the reactivation path of `start_agreement.rs`
+const fn reactivate(agreement: &mut PaymentAgreement, external_ref_hash: Option<[u8; 32]>) {
+    agreement.active = true;
+    if external_ref_hash.is_some() {
+        agreement.external_ref_hash = external_ref_hash;
+    }
+}
+
+// ============================================================================
+// Start Agreement
+// ============================================================================
+
+/// Test that new agreements store the supplied hash
+#[test]
+fn test_start_stores_hash() {
+    assert_eq!(start(Some(CUSTOMER_REF)).external_ref_hash, Some(CUSTOMER_REF));
+    assert_eq!(start(None).external_ref_hash, None);
+}
+
+/// Test that reactivation without a hash keeps the stored one
+#[test]
+fn test_reactivation_keeps_hash() {
+    let mut agreement = start(Some(CUSTOMER_REF));
+    agreement.active = false;
+
+    reactivate(&mut agreement, None);
+    assert_eq!(agreement.external_ref_hash, Some(CUSTOMER_REF));
+}
+
+/// Test that reactivation with a hash replaces the stored one
+#[test]
+fn test_reactivation_replaces_hash() {
+    let mut agreement = start(Some(CUSTOMER_REF));
+    agreement.active = false;
+
+    reactivate(&mut agreement, Some([0xCD; 32]));
+    assert_eq!(agreement.external_ref_hash, Some([0xCD; 32]));
+}
+
+// ============================================================================
+// Agreement Transfer
+// ============================================================================
+
+/// Test that transfers carry the hash to the new payer's agreement
+#[test]
+fn test_transfer_keeps_hash() {
+    let agreement = start(Some(CUSTOMER_REF));
+
+    // accept_agreement_transfer.rs copies the agreement to the new payer's PDA
+    let new_payer = Pubkey::new_unique();
+    let new_agreement = PaymentAgreement {
+        payer: new_payer,
+        pending_payer: None,
+        ..agreement
+    };
+    assert_eq!(new_agreement.payer, new_payer);
+    assert_eq!(new_agreement.external_ref_hash, Some(CUSTOMER_REF));
+}
+
+// ============================================================================
+// Account Size
+// ============================================================================
+
+/// Test that the account size covers the external reference hash
+#[test]
+fn test_payment_agreement_space() {
+    use anchor_lang::AnchorSerialize;
+
+    let mut agreement = start(Some(CUSTOMER_REF));
+    agreement.pending_payer = Some(Pubkey::new_unique());
+    agreement.max_periods = Some(12);
+    agreement.paused_at_ts = Some(START);
+    agreement.escrow = Some(AgreementEscrow {
+        amount: 1_000_000,
+        expires_ts: START,
+    });
+    agreement.suspension = Some(SuspensionReason::FrozenAccount);
+    agreement.deposit_held = 50_000_000;
+
+    let serialized_len = agreement.try_to_vec().unwrap().len();
+    assert_eq!(PaymentAgreement::SPACE, 259);
+    assert_eq!(serialized_len + 8, PaymentAgreement::SPACE);
+}
@@ -0,0 +1,151 @@
+//! Unit tests for the `set_fee_holiday` instruction
+//!
+//! This test suite validates time-boxed platform fee discounts through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Rebate lowers the tier fee until `until_ts`, then the tier fee applies again
+//! - Rebate larger than the tier fee waives the platform fee without underflow
+//! - Zero rebate or past `until_ts` ends the holiday
+//! - Rebate bounded by `MAX_PLATFORM_FEE_BPS`
+//! - The volume tier is unaffected by the holiday
+//! - Platform authority authorization (not tested here, enforced by `has_one` on config)
+//!
+//! Business Context:
+//! Growth and enterprise deals need temporary fee discounts without changing the
+//! payee's earned tier. `execute_payment`, `start_agreement` and `resume_agreement`
+//! charge the tier fee less the active rebate:
+//! ```rust
+//! let platform_fee_bps = payee.platform_fee_bps(current_time);
+//! ```
+
+mod common;
+
+use tally_protocol::constants::MAX_PLATFORM_FEE_BPS;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{FeeHoliday, Payee, VolumeTier};
+use tally_protocol::utils::calculate_fee_split;
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: i64 = 2_592_000;
+const NOW: i64 = 1_700_000_000;
+
+fn payee(volume_tier: VolumeTier) -> Payee {
+    Payee {
+        volume_tier,
+        last_volume_update_ts: NOW,
+        ..common::payee()
+    }
+}
+
+/// Simulate `set_fee_holiday.rs`
+fn set_fee_holiday(
+    payee: &mut Payee,
+    now: i64,
+    until_ts: i64,
+    rebate_bps: u16,
+) -> Result<(), RecurringPaymentError> {
+    if rebate_bps > MAX_PLATFORM_FEE_BPS {
+        return Err(RecurringPaymentError::InvalidConfiguration);
+    }
+    payee.fee_holiday = (rebate_bps > 0 && until_ts > now).then_some(FeeHoliday {
+        until_ts,
+        rebate_bps,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Effective Platform Fee
+// ============================================================================
+
+/// Test that the rebate applies until `until_ts` and the tier fee afterwards
+#[test]
+fn test_rebate_applies_until_end() {
+    let mut payee = payee(VolumeTier::Standard);
+    let until_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+    set_fee_holiday(&mut payee, NOW, until_ts, 10).unwrap();
+
+    assert_eq!(payee.platform_fee_bps(NOW), 15);
+    assert_eq!(payee.platform_fee_bps(until_ts.checked_sub(1).unwrap()), 15);
+    assert!(payee.active_fee_holiday(until_ts).is_none());
+    assert_eq!(payee.platform_fee_bps(until_ts), 25);
+}
+
+/// Test that the charged platform fee follows the reduced rate
+#[test]
+fn test_fee_split_uses_reduced_rate() {
+    let mut payee = payee(VolumeTier::Standard);
+    set_fee_holiday(&mut payee, NOW, NOW.checked_add(THIRTY_DAYS).unwrap(), 10).unwrap();
+
+    let amount = 100 * ONE_USDC;
+    let discounted = calculate_fee_split(amount, 0, payee.platform_fee_bps(NOW)).unwrap();
+    let regular = calculate_fee_split(amount, 0, VolumeTier::Standard.platform_fee_bps()).unwrap();
+
+    assert_eq!(discounted.platform_fee, 150_000); // 0.15%
+    assert_eq!(regular.platform_fee, 250_000); // 0.25%
+    assert_eq!(discounted.payee_amount, 99_850_000);
+}
+
+/// Test that a rebate above the tier fee waives the fee instead of underflowing
+#[test]
+fn test_rebate_floors_at_zero() {
+    let mut payee = payee(VolumeTier::Scale);
+    set_fee_holiday(
+        &mut payee,
+        NOW,
+        NOW.checked_add(THIRTY_DAYS).unwrap(),
+        MAX_PLATFORM_FEE_BPS,
+    )
+    .unwrap();
+
+    assert_eq!(payee.platform_fee_bps(NOW), 0);
+}
+
+/// Test that the holiday does not change the payee's earned tier
+#[test]
+fn test_tier_unaffected() {
+    let mut payee = payee(VolumeTier::Growth);
+    set_fee_holiday(&mut payee, NOW, NOW.checked_add(THIRTY_DAYS).unwrap(), 5).unwrap();
+
+    assert_eq!(payee.volume_tier, VolumeTier::Growth);
+    assert_eq!(payee.platform_fee_bps(NOW), 15);
+}
+
+// ============================================================================
+// Setting and Ending
+// ============================================================================
+
+/// Test that a zero rebate or a past end time ends the current holiday
+#[test]
+fn test_holiday_can_be_ended() {
+    let mut payee = payee(VolumeTier::Standard);
+    let until_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+
+    set_fee_holiday(&mut payee, NOW, until_ts, 10).unwrap();
+    set_fee_holiday(&mut payee, NOW, until_ts, 0).unwrap();
+    assert!(payee.fee_holiday.is_none());
+
+    set_fee_holiday(&mut payee, NOW, until_ts, 10).unwrap();
+    set_fee_holiday(&mut payee, NOW, NOW, 10).unwrap();
+    assert!(payee.fee_holiday.is_none());
+    assert_eq!(payee.platform_fee_bps(NOW), 25);
+}
+
+/// Test that the rebate cannot exceed the maximum platform fee
+#[test]
+fn test_rebate_bounded_by_max_platform_fee() {
+    let mut payee = payee(VolumeTier::Standard);
+    let until_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+
+    assert!(matches!(
+        set_fee_holiday(
+            &mut payee,
+            NOW,
+            until_ts,
+            MAX_PLATFORM_FEE_BPS.checked_add(1).unwrap()
+        ),
+        Err(RecurringPaymentError::InvalidConfiguration)
+    ));
+    assert!(payee.fee_holiday.is_none());
+}
@@ -0,0 +1,144 @@
+//! Property tests for the fee rounding specification of `calculate_fee_split`
+//!
+//! This test suite fuzzes the fee split with deterministic pseudo-random inputs so
+//! failures reproduce exactly. No extra dependencies are needed to run it.
+//!
+//! Test coverage:
+//! - Keeper fee, platform fee and payee amount always sum to the amount charged
+//! - The keeper fee and the payee's share are floored; the platform gets the remainder
+//! - The platform fee is the pro-rata fee rounded up, never more than one micro-unit over
+//! - Fee rates above 100% are rejected instead of underflowing
+//! - `FeeSplitMismatch` error code for clients (6054)
+//!
+//! Accounting Context:
+//! Every renewal, initial payment and escrow release moves exactly the amount charged.
+//! The split is checked on-chain before any transfer:
+//! ```rust
+//! require!(total == amount, RecurringPaymentError::FeeSplitMismatch);
+//! ```
+
+mod common;
+
+use tally_protocol::constants::{FEE_BASIS_POINTS_DIVISOR, MAX_PLATFORM_FEE_BPS};
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::utils::calculate_fee_split;
+
+const ITERATIONS: usize = 100_000;
+
+/// Minimal xorshift generator so fuzz cases are reproducible without a seed file
+struct XorShift(u64);
+
+impl XorShift {
+    const fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Amounts spread across every magnitude from one micro-unit to `u64::MAX`
+    const fn amount(&mut self) -> u64 {
+        let shift = self.next_u64() % 64;
+        self.next_u64() >> shift
+    }
+
+    fn bps(&mut self, max: u16) -> u16 {
+        let range = u64::from(max).saturating_add(1);
+        u16::try_from(self.next_u64().checked_rem(range).unwrap()).unwrap()
+    }
+}
+
+/// Reference implementation of the rounding spec in exact rational arithmetic
+fn expected_split(amount: u64, keeper_fee_bps: u16, platform_fee_bps: u16) -> (u64, u64, u64) {
+    let divisor = FEE_BASIS_POINTS_DIVISOR;
+    let keeper_fee = u128::from(amount)
+        .checked_mul(u128::from(keeper_fee_bps))
+        .and_then(|value| value.checked_div(divisor))
+        .unwrap();
+    let remaining = u128::from(amount).checked_sub(keeper_fee).unwrap();
+    let payee_amount = divisor
+        .checked_sub(u128::from(platform_fee_bps))
+        .and_then(|share| remaining.checked_mul(share))
+        .and_then(|value| value.checked_div(divisor))
+        .unwrap();
+    let platform_fee = remaining.checked_sub(payee_amount).unwrap();
+    (
+        u64::try_from(keeper_fee).unwrap(),
+        u64::try_from(platform_fee).unwrap(),
+        u64::try_from(payee_amount).unwrap(),
+    )
+}
+
+// ============================================================================
+// Fuzzed Invariants
+// ============================================================================
+
+/// Test that the parts sum to the amount and match the spec for random inputs
+#[test]
+fn test_fuzz_split_sums_to_amount() {
+    let mut rng = XorShift(0x5eed_f00d_cafe_beef);
+
+    for _ in 0..ITERATIONS {
+        let amount = rng.amount();
+        let keeper_fee_bps = rng.bps(100);
+        let platform_fee_bps = rng.bps(MAX_PLATFORM_FEE_BPS);
+
+        let split = calculate_fee_split(amount, keeper_fee_bps, platform_fee_bps).unwrap();
+        let total = u128::from(split.keeper_fee)
+            + u128::from(split.platform_fee)
+            + u128::from(split.payee_amount);
+
+        assert_eq!(total, u128::from(amount), "split of {amount} leaks funds");
+        assert_eq!(
+            (split.keeper_fee, split.platform_fee, split.payee_amount),
+            expected_split(amount, keeper_fee_bps, platform_fee_bps),
+            "split of {amount} at {keeper_fee_bps}/{platform_fee_bps} bps"
+        );
+    }
+}
+
+/// Test that the platform fee is the pro-rata fee rounded up
+#[test]
+fn test_fuzz_platform_fee_rounds_up() {
+    let mut rng = XorShift(0x0123_4567_89ab_cdef);
+
+    for _ in 0..ITERATIONS {
+        let amount = rng.amount();
+        let platform_fee_bps = rng.bps(MAX_PLATFORM_FEE_BPS);
+
+        let split = calculate_fee_split(amount, 0, platform_fee_bps).unwrap();
+        let exact = u128::from(amount) * u128::from(platform_fee_bps);
+        let fee = u128::from(split.platform_fee) * FEE_BASIS_POINTS_DIVISOR;
+
+        assert!(fee >= exact, "platform fee of {amount} rounded down");
+        assert!(
+            fee < exact + FEE_BASIS_POINTS_DIVISOR,
+            "platform fee of {amount} over by a micro-unit or more"
+        );
+    }
+}
+
+// ============================================================================
+// Boundaries
+// ============================================================================
+
+/// Test the boundary rates and amounts
+#[test]
+fn test_boundary_rates() {
+    for amount in [0, 1, 9_999, 10_000, 10_001, u64::MAX] {
+        let free = calculate_fee_split(amount, 0, 0).unwrap();
+        assert_eq!(free.payee_amount, amount);
+
+        let all_platform = calculate_fee_split(amount, 0, 10_000).unwrap();
+        assert_eq!(all_platform.platform_fee, amount);
+        assert_eq!(all_platform.payee_amount, 0);
+    }
+
+    assert!(calculate_fee_split(1_000, 0, 10_001).is_err());
+}
+
+/// Test the error code clients decode
+#[test]
+fn test_error_code() {
+    assert_eq!(common::error_code(RecurringPaymentError::FeeSplitMismatch), 6054);
+}
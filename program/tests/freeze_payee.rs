@@ -0,0 +1,129 @@
+//! Unit tests for the `freeze_payee` and `unfreeze_payee` instructions
+//!
+//! This test suite validates per-payee emergency freezes through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Freeze state transitions (unfrozen -> frozen -> unfrozen)
+//! - Platform authority authorization (not tested here, enforced by `has_one` on config)
+//! - Freeze enforcement isolated to the frozen payee
+//! - Substituting an unfrozen payee for the terms' frozen payee is rejected
+//! - Error code for frozen payees
+//!
+//! Business Context:
+//! The global pause is all-or-nothing. Freezing blocks `start_agreement` and
+//! `execute_payment` for a single payee (e.g. during a fraud investigation) while
+//! every other payee keeps operating. The check is enforced at the account
+//! constraint level, with the payee bound to the payment terms so a caller cannot
+//! pass another, unfrozen payee:
+//! ```rust
+//! #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+//! pub payment_terms: Account<'info, PaymentTerms>,
+//!
+//! #[account(
+//!     seeds = [b"payee", payee.original_authority.as_ref()],
+//!     bump = payee.bump,
+//!     constraint = !payee.frozen @ RecurringPaymentError::PayeeFrozen
+//! )]
+//! pub payee: Account<'info, Payee>,
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{Payee, PaymentTerms};
+
+fn payee(frozen: bool) -> Payee {
+    Payee {
+        frozen,
+        ..common::payee()
+    }
+}
+
+/// Simulate the payee constraint from `start_agreement.rs` and `execute_payment.rs`
+const fn check_payee_not_frozen(payee: &Payee) -> Result<(), RecurringPaymentError> {
+    if payee.frozen {
+        Err(RecurringPaymentError::PayeeFrozen)
+    } else {
+        Ok(())
+    }
+}
+
+/// Simulate the `payment_terms` and `payee` constraints from `start_agreement.rs`
+/// and `execute_payment.rs`, for a payee account passed at `payee_key`
+fn check_payee_accounts(
+    terms: &PaymentTerms,
+    payee_key: &Pubkey,
+    payee: &Payee,
+) -> Result<(), RecurringPaymentError> {
+    if terms.payee != *payee_key {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    check_payee_not_frozen(payee)
+}
+
+// ============================================================================
+// Freeze State Tests
+// ============================================================================
+
+/// Test that freezing and unfreezing toggles the payee's frozen flag
+#[test]
+fn test_freeze_state_transitions() {
+    let mut payee = payee(false);
+    assert!(check_payee_not_frozen(&payee).is_ok());
+
+    // Simulate freeze_payee
+    payee.frozen = true;
+    assert!(check_payee_not_frozen(&payee).is_err());
+
+    // Simulate unfreeze_payee
+    payee.frozen = false;
+    assert!(check_payee_not_frozen(&payee).is_ok());
+}
+
+// ============================================================================
+// Enforcement Tests
+// ============================================================================
+
+/// Test that freezing one payee leaves other payees operational
+#[test]
+fn test_freeze_is_isolated_to_one_payee() {
+    let frozen = payee(true);
+    let other = payee(false);
+
+    assert!(check_payee_not_frozen(&frozen).is_err());
+    assert!(check_payee_not_frozen(&other).is_ok());
+}
+
+/// Test that a frozen payee maps to the dedicated error code
+#[test]
+fn test_payee_frozen_error_code() {
+    let error = check_payee_not_frozen(&payee(true)).unwrap_err();
+
+    assert!(matches!(error, RecurringPaymentError::PayeeFrozen));
+    assert_eq!(u32::from(error), 6029);
+}
+
+/// Test that a frozen payee cannot be bypassed by passing another payee's account
+#[test]
+fn test_substituted_payee_is_rejected() {
+    let frozen_key = Pubkey::new_unique();
+    let terms = PaymentTerms {
+        payee: frozen_key,
+        ..common::terms()
+    };
+    let frozen = payee(true);
+    let other_key = Pubkey::new_unique();
+    let other = payee(false);
+
+    assert!(matches!(
+        check_payee_accounts(&terms, &frozen_key, &frozen),
+        Err(RecurringPaymentError::PayeeFrozen)
+    ));
+    // The unfrozen payee passes the freeze check but is not the terms' payee
+    assert!(matches!(
+        check_payee_accounts(&terms, &other_key, &other),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+}
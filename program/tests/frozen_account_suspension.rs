@@ -0,0 +1,292 @@
+//! Unit tests for suspending agreements whose payer token account is frozen
+//!
+//! This test suite validates how `execute_payment` suspends an agreement when the
+//! payer's token account is frozen, and how `resume_after_unfreeze` reinstates it
+//! once the account is thawed, through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - A frozen account delegated to the program suspends the agreement
+//! - A frozen account not delegated to the program is rejected instead
+//! - Suspension releases the subscriber slot and clears `active`
+//! - Resuming requires a suspended agreement and a thawed account
+//! - Resuming never charges missed periods retroactively
+//! - Account space and error codes
+//!
+//! Business Context:
+//! A USDC freeze authority may freeze a payer's token account at any time. Instead of
+//! failing every renewal attempt, `execute_payment` marks the agreement suspended so
+//! keepers stop retrying it:
+//! ```rust
+//! if subscriber_ata_data.is_frozen() && subscriber_ata_data.delegate == program_delegate {
+//!     payment_agreement.active = false;
+//!     payment_agreement.suspension = Some(SuspensionReason::FrozenAccount);
+//! }
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{PaymentAgreement, SuspensionReason};
+
+const PERIOD_SECS: i64 = 2_592_000;
+
+/// Payer token account as seen by the suspension checks
+struct PayerAta {
+    frozen: bool,
+    delegate: Option<Pubkey>,
+}
+
+/// Subscriber slot counters of the payment terms and payee
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Slots {
+    terms_active: u32,
+    payee_active: u64,
+}
+
+/// Outcome of the frozen account check in `execute_payment`
+#[derive(Debug, PartialEq, Eq)]
+enum ExecuteOutcome {
+    Suspended,
+    Charge,
+}
+
+fn agreement(next_payment_ts: i64) -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts,
+        created_ts: 0,
+        last_payment_ts: 0,
+        ..common::agreement()
+    }
+}
+
+/// Simulate the frozen account check of `execute_payment.rs`
+fn simulate_execute_payment(
+    agreement: &mut PaymentAgreement,
+    slots: &mut Slots,
+    ata: &PayerAta,
+    program_delegate: &Pubkey,
+) -> Result<ExecuteOutcome, RecurringPaymentError> {
+    if ata.frozen && ata.delegate == Some(*program_delegate) {
+        agreement.active = false;
+        agreement.suspension = Some(SuspensionReason::FrozenAccount);
+        slots.terms_active = slots.terms_active.saturating_sub(1);
+        slots.payee_active = slots.payee_active.saturating_sub(1);
+        return Ok(ExecuteOutcome::Suspended);
+    }
+    if ata.delegate != Some(*program_delegate) {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    Ok(ExecuteOutcome::Charge)
+}
+
+/// Simulate the checks and state changes of `resume_after_unfreeze.rs`
+fn simulate_resume_after_unfreeze(
+    agreement: &mut PaymentAgreement,
+    slots: &mut Slots,
+    ata: &PayerAta,
+    current_time: i64,
+) -> Result<(), RecurringPaymentError> {
+    if agreement.suspension != Some(SuspensionReason::FrozenAccount) {
+        return Err(RecurringPaymentError::NotSuspended);
+    }
+    if ata.frozen {
+        return Err(RecurringPaymentError::PayerAccountFrozen);
+    }
+    slots.terms_active = slots.terms_active.saturating_add(1);
+    slots.payee_active = slots.payee_active.saturating_add(1);
+    agreement.next_payment_ts = agreement.next_payment_ts.max(current_time);
+    agreement.active = true;
+    agreement.suspension = None;
+    Ok(())
+}
+
+// ============================================================================
+// Suspension
+// ============================================================================
+
+/// Test that a frozen account delegated to the program suspends the agreement
+#[test]
+fn test_frozen_account_suspends_agreement() {
+    let program_delegate = Pubkey::new_unique();
+    let mut agreement = agreement(PERIOD_SECS);
+    let mut slots = Slots {
+        terms_active: 5,
+        payee_active: 8,
+    };
+    let ata = PayerAta {
+        frozen: true,
+        delegate: Some(program_delegate),
+    };
+
+    let outcome =
+        simulate_execute_payment(&mut agreement, &mut slots, &ata, &program_delegate).unwrap();
+
+    assert_eq!(outcome, ExecuteOutcome::Suspended);
+    assert!(!agreement.active);
+    assert!(agreement.is_suspended());
+    assert_eq!(
+        slots,
+        Slots {
+            terms_active: 4,
+            payee_active: 7,
+        }
+    );
+}
+
+/// Test that a keeper can't suspend an agreement with an unrelated frozen account
+#[test]
+fn test_frozen_account_without_program_delegate_is_rejected() {
+    let program_delegate = Pubkey::new_unique();
+    let mut agreement = agreement(PERIOD_SECS);
+    let mut slots = Slots {
+        terms_active: 1,
+        payee_active: 1,
+    };
+    let ata = PayerAta {
+        frozen: true,
+        delegate: None,
+    };
+
+    assert!(matches!(
+        simulate_execute_payment(&mut agreement, &mut slots, &ata, &program_delegate),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+    assert!(agreement.active);
+    assert!(!agreement.is_suspended());
+}
+
+/// Test that a thawed account is charged as usual
+#[test]
+fn test_thawed_account_is_charged() {
+    let program_delegate = Pubkey::new_unique();
+    let mut agreement = agreement(PERIOD_SECS);
+    let mut slots = Slots {
+        terms_active: 1,
+        payee_active: 1,
+    };
+    let ata = PayerAta {
+        frozen: false,
+        delegate: Some(program_delegate),
+    };
+
+    let outcome =
+        simulate_execute_payment(&mut agreement, &mut slots, &ata, &program_delegate).unwrap();
+
+    assert_eq!(outcome, ExecuteOutcome::Charge);
+    assert!(agreement.suspension.is_none());
+}
+
+// ============================================================================
+// Resume After Unfreeze
+// ============================================================================
+
+/// Test that a thawed account reinstates the agreement without back-charging
+#[test]
+fn test_resume_clamps_missed_payment_to_now() {
+    let program_delegate = Pubkey::new_unique();
+    let mut agreement = agreement(PERIOD_SECS);
+    let mut slots = Slots {
+        terms_active: 1,
+        payee_active: 1,
+    };
+    let frozen = PayerAta {
+        frozen: true,
+        delegate: Some(program_delegate),
+    };
+    simulate_execute_payment(&mut agreement, &mut slots, &frozen, &program_delegate).unwrap();
+
+    // Thawed three periods later
+    let now = PERIOD_SECS * 4;
+    let thawed = PayerAta {
+        frozen: false,
+        delegate: Some(program_delegate),
+    };
+    simulate_resume_after_unfreeze(&mut agreement, &mut slots, &thawed, now).unwrap();
+
+    assert!(agreement.active);
+    assert!(!agreement.is_suspended());
+    assert_eq!(agreement.next_payment_ts, now);
+    assert_eq!(
+        slots,
+        Slots {
+            terms_active: 1,
+            payee_active: 1,
+        }
+    );
+}
+
+/// Test that a thaw before the next payment keeps the original schedule
+#[test]
+fn test_resume_keeps_future_payment() {
+    let mut agreement = agreement(PERIOD_SECS);
+    agreement.active = false;
+    agreement.suspension = Some(SuspensionReason::FrozenAccount);
+    let mut slots = Slots {
+        terms_active: 0,
+        payee_active: 0,
+    };
+    let thawed = PayerAta {
+        frozen: false,
+        delegate: None,
+    };
+
+    simulate_resume_after_unfreeze(&mut agreement, &mut slots, &thawed, PERIOD_SECS / 2).unwrap();
+
+    assert_eq!(agreement.next_payment_ts, PERIOD_SECS);
+}
+
+/// Test that resuming requires a thawed account
+#[test]
+fn test_resume_rejects_frozen_account() {
+    let mut agreement = agreement(PERIOD_SECS);
+    agreement.active = false;
+    agreement.suspension = Some(SuspensionReason::FrozenAccount);
+    let mut slots = Slots {
+        terms_active: 0,
+        payee_active: 0,
+    };
+    let frozen = PayerAta {
+        frozen: true,
+        delegate: None,
+    };
+
+    assert!(matches!(
+        simulate_resume_after_unfreeze(&mut agreement, &mut slots, &frozen, PERIOD_SECS),
+        Err(RecurringPaymentError::PayerAccountFrozen)
+    ));
+    assert!(agreement.is_suspended());
+}
+
+/// Test that only suspended agreements can be resumed this way
+#[test]
+fn test_resume_rejects_agreement_not_suspended() {
+    let mut agreement = agreement(PERIOD_SECS);
+    agreement.active = false;
+    let mut slots = Slots {
+        terms_active: 0,
+        payee_active: 0,
+    };
+    let thawed = PayerAta {
+        frozen: false,
+        delegate: None,
+    };
+
+    assert!(matches!(
+        simulate_resume_after_unfreeze(&mut agreement, &mut slots, &thawed, PERIOD_SECS),
+        Err(RecurringPaymentError::NotSuspended)
+    ));
+    assert!(!agreement.active);
+}
+
+// ============================================================================
+// Error Codes
+// ============================================================================
+
+/// Test the error codes clients decode
+#[test]
+fn test_error_codes() {
+    assert_eq!(common::error_code(RecurringPaymentError::NotSuspended), 6049);
+    assert_eq!(common::error_code(RecurringPaymentError::PayerAccountFrozen), 6050);
+}
@@ -0,0 +1,237 @@
+//! Unit tests for token-gated payment terms pricing
+//!
+//! This test suite validates the gate discount configuration rules enforced by
+//! `create_payment_terms` and the discounted amount charged by `start_agreement`
+//! and `execute_payment` when the payer holds the gate mint.
+//!
+//! Test coverage:
+//! - Gate configuration: discount required with a gate mint, forbidden without one
+//! - Discount bounds: zero and above `MAX_GATE_DISCOUNT_BPS` rejected
+//! - Discount math: exact percentages, rounding, maximum price overflow safety
+//! - Fee split: keeper, platform, and payee amounts computed on the discounted price
+//! - Gate token account: `execute_payment` requires the payer's gate ATA for gated terms
+
+mod common;
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_spl::associated_token::get_associated_token_address;
+use tally_protocol::constants::{
+    FEE_BASIS_POINTS_DIVISOR, MAX_GATE_DISCOUNT_BPS, MAX_PLAN_PRICE_USDC,
+};
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentTerms;
+use tally_protocol::utils::payer_gate_mint;
+
+// ============================================================================
+// Constants for Testing
+// ============================================================================
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Simulate gate validation from `create_payment_terms.rs`
+const fn is_valid_gate_config(gate_mint: Option<Pubkey>, gate_discount_bps: u16) -> bool {
+    match gate_mint {
+        Some(_) => gate_discount_bps > 0 && gate_discount_bps <= MAX_GATE_DISCOUNT_BPS,
+        None => gate_discount_bps == 0,
+    }
+}
+
+/// Simulate `apply_gate_discount` from `utils.rs`
+fn apply_gate_discount(amount: u64, discount_bps: u16) -> Option<u64> {
+    let discount = u64::try_from(
+        u128::from(amount)
+            .checked_mul(u128::from(discount_bps))?
+            .checked_div(FEE_BASIS_POINTS_DIVISOR)?,
+    )
+    .ok()?;
+    amount.checked_sub(discount)
+}
+
+/// Simulate fee calculation on a given amount (keeper fee first, then platform fee)
+fn split_fees(amount: u64, keeper_fee_bps: u16, platform_fee_bps: u16) -> (u64, u64, u64) {
+    let keeper_fee = u64::try_from(
+        u128::from(amount)
+            .checked_mul(u128::from(keeper_fee_bps))
+            .unwrap()
+            .checked_div(FEE_BASIS_POINTS_DIVISOR)
+            .unwrap(),
+    )
+    .unwrap();
+    let remaining = amount.checked_sub(keeper_fee).unwrap();
+    let platform_fee = u64::try_from(
+        u128::from(remaining)
+            .checked_mul(u128::from(platform_fee_bps))
+            .unwrap()
+            .checked_div(FEE_BASIS_POINTS_DIVISOR)
+            .unwrap(),
+    )
+    .unwrap();
+    (keeper_fee, platform_fee, remaining.checked_sub(platform_fee).unwrap())
+}
+
+// ============================================================================
+// Gate Configuration Tests
+// ============================================================================
+
+/// Test that terms without a gate and without a discount are valid
+#[test]
+fn test_no_gate_no_discount_passes() {
+    assert!(is_valid_gate_config(None, 0));
+}
+
+/// Test that a discount without a gate mint is rejected
+#[test]
+fn test_discount_without_gate_fails() {
+    assert!(!is_valid_gate_config(None, 1_000));
+}
+
+/// Test that a gate mint with a zero discount is rejected
+#[test]
+fn test_gate_with_zero_discount_fails() {
+    assert!(!is_valid_gate_config(Some(Pubkey::new_unique()), 0));
+}
+
+/// Test discount bounds at and above `MAX_GATE_DISCOUNT_BPS`
+#[test]
+fn test_gate_discount_bounds() {
+    let gate_mint = Some(Pubkey::new_unique());
+
+    assert!(is_valid_gate_config(gate_mint, 1));
+    assert!(is_valid_gate_config(gate_mint, MAX_GATE_DISCOUNT_BPS));
+    assert!(!is_valid_gate_config(gate_mint, MAX_GATE_DISCOUNT_BPS + 1));
+}
+
+// ============================================================================
+// Discount Calculation Tests
+// ============================================================================
+
+/// Test a 10% discount on a 10 USDC payment
+#[test]
+fn test_ten_percent_discount() {
+    assert_eq!(apply_gate_discount(10 * ONE_USDC, 1_000), Some(9 * ONE_USDC));
+}
+
+/// Test that the maximum discount never reduces a payment to zero
+#[test]
+fn test_max_discount_leaves_nonzero_amount() {
+    let discounted = apply_gate_discount(1, MAX_GATE_DISCOUNT_BPS).unwrap();
+    assert_eq!(discounted, 1, "Discount rounds down so tiny payments stay payable");
+
+    let discounted = apply_gate_discount(10 * ONE_USDC, MAX_GATE_DISCOUNT_BPS).unwrap();
+    assert_eq!(discounted, 5 * ONE_USDC);
+}
+
+/// Test that discounting the maximum plan price cannot overflow
+#[test]
+fn test_discount_on_max_price_does_not_overflow() {
+    let discounted = apply_gate_discount(MAX_PLAN_PRICE_USDC, MAX_GATE_DISCOUNT_BPS);
+    assert_eq!(discounted, Some(MAX_PLAN_PRICE_USDC / 2));
+}
+
+// ============================================================================
+// Fee Split Tests
+// ============================================================================
+
+/// Test that fees are computed on the discounted amount and sum to it exactly
+#[test]
+fn test_fee_split_uses_discounted_amount() {
+    let discounted = apply_gate_discount(100 * ONE_USDC, 2_000).unwrap();
+    let (keeper_fee, platform_fee, payee_amount) = split_fees(discounted, 15, 25);
+
+    assert_eq!(discounted, 80 * ONE_USDC);
+    assert_eq!(keeper_fee, 120_000);
+    assert_eq!(platform_fee, 199_700);
+    assert_eq!(
+        keeper_fee.checked_add(platform_fee).unwrap().checked_add(payee_amount),
+        Some(discounted)
+    );
+}
+
+// ============================================================================
+// Gate Token Account Tests
+// ============================================================================
+
+fn gate_error(
+    terms: &PaymentTerms,
+    payer: &Pubkey,
+    payer_gate_ata: Option<&AccountInfo>,
+) -> u32 {
+    common::anchor_error_code(
+        payer_gate_mint(terms, payer, payer_gate_ata, &anchor_spl::token::ID).unwrap_err(),
+    )
+}
+
+fn gated_terms(gate_mint: Pubkey) -> PaymentTerms {
+    PaymentTerms {
+        gate_mint: Some(gate_mint),
+        gate_discount_bps: 1_000,
+        ..common::terms()
+    }
+}
+
+/// Test that ungated terms need no gate token account
+#[test]
+fn test_ungated_terms_need_no_gate_account() {
+    let result = payer_gate_mint(
+        &common::terms(),
+        &Pubkey::new_unique(),
+        None,
+        &anchor_spl::token::ID,
+    );
+    assert_eq!(result.unwrap(), None);
+}
+
+/// Test that leaving the gate token account out of gated terms is rejected
+/// instead of charging the full price
+#[test]
+fn test_gated_terms_require_gate_account() {
+    let terms = gated_terms(Pubkey::new_unique());
+
+    assert_eq!(
+        gate_error(&terms, &Pubkey::new_unique(), None),
+        common::error_code(RecurringPaymentError::GateTokenAccountRequired)
+    );
+}
+
+/// Test that a gate token account other than the payer's ATA is rejected
+#[test]
+fn test_gate_account_must_be_payer_ata() {
+    let payer = Pubkey::new_unique();
+    let terms = gated_terms(Pubkey::new_unique());
+    let other = Pubkey::new_unique();
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let mut data = [];
+    let account = AccountInfo::new(&other, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+    assert_eq!(
+        gate_error(&terms, &payer, Some(&account)),
+        common::error_code(RecurringPaymentError::InvalidGateTokenAccount)
+    );
+}
+
+/// Test that a payer without a gate ATA pays the full price
+#[test]
+fn test_missing_gate_ata_charges_full_price() {
+    let payer = Pubkey::new_unique();
+    let gate_mint = Pubkey::new_unique();
+    let terms = gated_terms(gate_mint);
+    let ata = get_associated_token_address(&payer, &gate_mint);
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let mut data = [];
+    let account = AccountInfo::new(&ata, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+    let result = payer_gate_mint(&terms, &payer, Some(&account), &anchor_spl::token::ID);
+    assert_eq!(result.unwrap(), None);
+}
+
+/// Test that the missing gate account maps to the dedicated error code
+#[test]
+fn test_gate_token_account_required_error_code() {
+    assert_eq!(common::error_code(RecurringPaymentError::GateTokenAccountRequired), 6062);
+}
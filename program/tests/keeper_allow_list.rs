@@ -0,0 +1,179 @@
+//! Unit tests for permissioned payment execution (keeper allow-lists)
+//!
+//! This test suite validates the `set_keeper_policy` instruction and the keeper check
+//! in `execute_payment` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - New payees accept any keeper
+//! - Permissioned payees only accept keepers on their allow-list
+//! - An empty allow-list keeps execution open even with `open_execution` off
+//! - Turning `open_execution` back on reopens execution without clearing the list
+//! - An open payee cannot stand in for the terms' permissioned payee
+//! - Allow-lists are bounded by `MAX_AUTHORIZED_KEEPERS`
+//! - `Payee::SPACE` reserves room for a full allow-list
+//!
+//! Business Context:
+//! Some payees want only their own bot to execute renewals, to control memo content
+//! and capture the keeper fee. `execute_payment` binds the payee to the payment
+//! terms and checks its policy before anything else:
+//! ```rust
+//! #[account(mut, has_one = payee @ RecurringPaymentError::Unauthorized)]
+//! pub payment_terms: Account<'info, PaymentTerms>,
+//!
+//! require!(
+//!     payee.is_keeper_authorized(&ctx.accounts.executor.key()),
+//!     RecurringPaymentError::UnauthorizedKeeper
+//! );
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::constants::MAX_AUTHORIZED_KEEPERS;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{FeeHoliday, Payee, PaymentTerms};
+
+/// Simulate `set_keeper_policy.rs`
+fn set_keeper_policy(
+    payee: &mut Payee,
+    open_execution: bool,
+    authorized_keepers: Vec<Pubkey>,
+) -> Result<(), RecurringPaymentError> {
+    if authorized_keepers.len() > MAX_AUTHORIZED_KEEPERS {
+        return Err(RecurringPaymentError::TooManyKeepers);
+    }
+    payee.open_execution = open_execution;
+    payee.authorized_keepers = authorized_keepers;
+    Ok(())
+}
+
+/// Simulate the keeper check at the start of `execute_payment.rs`
+fn check_keeper(payee: &Payee, executor: &Pubkey) -> Result<(), RecurringPaymentError> {
+    if payee.is_keeper_authorized(executor) {
+        Ok(())
+    } else {
+        Err(RecurringPaymentError::UnauthorizedKeeper)
+    }
+}
+
+/// Simulate the `payment_terms` constraint and keeper check of `execute_payment.rs`,
+/// for a payee account passed at `payee_key`
+fn check_execution(
+    terms: &PaymentTerms,
+    payee_key: &Pubkey,
+    payee: &Payee,
+    executor: &Pubkey,
+) -> Result<(), RecurringPaymentError> {
+    if terms.payee != *payee_key {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    check_keeper(payee, executor)
+}
+
+// ============================================================================
+// Execution Checks
+// ============================================================================
+
+/// Test that new payees accept any keeper
+#[test]
+fn test_new_payee_accepts_any_keeper() {
+    let payee = common::payee();
+    assert!(check_keeper(&payee, &Pubkey::new_unique()).is_ok());
+}
+
+/// Test that permissioned payees only accept listed keepers
+#[test]
+fn test_permissioned_payee_rejects_unlisted_keeper() {
+    let mut payee = common::payee();
+    let own_bot = Pubkey::new_unique();
+    set_keeper_policy(&mut payee, false, vec![own_bot]).unwrap();
+
+    assert!(check_keeper(&payee, &own_bot).is_ok());
+    assert!(matches!(
+        check_keeper(&payee, &Pubkey::new_unique()),
+        Err(RecurringPaymentError::UnauthorizedKeeper)
+    ));
+}
+
+/// Test that an empty allow-list keeps execution open
+#[test]
+fn test_empty_allow_list_keeps_execution_open() {
+    let mut payee = common::payee();
+    set_keeper_policy(&mut payee, false, Vec::new()).unwrap();
+
+    assert!(check_keeper(&payee, &Pubkey::new_unique()).is_ok());
+}
+
+/// Test that turning open execution back on reopens execution and keeps the list
+#[test]
+fn test_open_execution_overrides_allow_list() {
+    let mut payee = common::payee();
+    let own_bot = Pubkey::new_unique();
+    set_keeper_policy(&mut payee, false, vec![own_bot]).unwrap();
+    set_keeper_policy(&mut payee, true, vec![own_bot]).unwrap();
+
+    assert!(check_keeper(&payee, &Pubkey::new_unique()).is_ok());
+    assert_eq!(payee.authorized_keepers, vec![own_bot]);
+}
+
+/// Test that a keeper cannot pass an open payee to skip the terms' allow-list
+#[test]
+fn test_substituted_payee_is_rejected() {
+    let permissioned_key = Pubkey::new_unique();
+    let mut permissioned = common::payee();
+    set_keeper_policy(&mut permissioned, false, vec![Pubkey::new_unique()]).unwrap();
+    let terms = PaymentTerms {
+        payee: permissioned_key,
+        ..common::terms()
+    };
+    let keeper = Pubkey::new_unique();
+
+    assert!(matches!(
+        check_execution(&terms, &permissioned_key, &permissioned, &keeper),
+        Err(RecurringPaymentError::UnauthorizedKeeper)
+    ));
+    // The open payee accepts the keeper but is not the terms' payee
+    assert!(matches!(
+        check_execution(&terms, &Pubkey::new_unique(), &common::payee(), &keeper),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+}
+
+// ============================================================================
+// Allow-List Bounds
+// ============================================================================
+
+/// Test that allow-lists above the maximum are rejected
+#[test]
+fn test_allow_list_is_bounded() {
+    let mut payee = common::payee();
+    let full: Vec<Pubkey> = (0..MAX_AUTHORIZED_KEEPERS).map(|_| Pubkey::new_unique()).collect();
+    assert!(set_keeper_policy(&mut payee, false, full.clone()).is_ok());
+
+    let mut too_many = full.clone();
+    too_many.push(Pubkey::new_unique());
+    assert!(matches!(
+        set_keeper_policy(&mut payee, false, too_many),
+        Err(RecurringPaymentError::TooManyKeepers)
+    ));
+    assert_eq!(payee.authorized_keepers, full, "Rejected policy must not be stored");
+}
+
+/// Test that the account size reserves room for a full allow-list
+#[test]
+fn test_payee_space_fits_full_allow_list() {
+    use anchor_lang::AnchorSerialize;
+
+    let mut payee = common::payee();
+    payee.authorized_keepers = (0..MAX_AUTHORIZED_KEEPERS).map(|_| Pubkey::new_unique()).collect();
+    payee.pending_authority = Some(Pubkey::new_unique());
+    payee.fee_holiday = Some(FeeHoliday {
+        until_ts: 1_700_000_000,
+        rebate_bps: 10,
+    });
+
+    let serialized_len = payee.try_to_vec().unwrap().len();
+    assert_eq!(Payee::SPACE, 385);
+    assert_eq!(serialized_len + 8, Payee::SPACE);
+}
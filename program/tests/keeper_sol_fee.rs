@@ -0,0 +1,160 @@
+//! Unit tests for paying the keeper fee in SOL from the keeper fee vault
+//!
+//! This test suite validates the rate set by `set_keeper_sol_rate` and the SOL payout
+//! of `execute_payment` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Keepers are paid at the platform's rate; a disabled or stale rate is rejected
+//! - Lamports are the USDC keeper fee converted at the rate, rounded down
+//! - Conversion never overflows
+//! - The vault must stay rent-exempt after paying the keeper
+//! - The payer is charged the same; the USDC keeper fee goes to the platform treasury
+//!
+//! Business Context:
+//! Keepers pay transaction fees in SOL, so many prefer to be paid in SOL. The platform
+//! funds a system-owned vault PDA and sets the rate keepers are paid at, so a keeper
+//! cannot choose a rate that favours it:
+//! ```rust
+//! let lamports = rate
+//!     .lamports_for(keeper_fee, clock.unix_timestamp)
+//!     .ok_or(RecurringPaymentError::KeeperSolRateUnavailable)?;
+//! ```
+
+use tally_protocol::constants::MAX_KEEPER_SOL_RATE_AGE_SECS;
+use tally_protocol::state::KeeperSolRate;
+use tally_protocol::utils::calculate_fee_split;
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+/// Rent-exempt minimum of a zero-data system account
+const VAULT_MINIMUM: u64 = 890_880;
+const NOW: i64 = 1_700_000_000;
+
+/// 200 USDC per SOL, set at `NOW`
+const RATE: KeeperSolRate = KeeperSolRate {
+    lamports_per_usdc: 5_000_000,
+    updated_ts: NOW,
+};
+
+/// Simulate the vault balance check of `execute_payment.rs`
+fn vault_can_pay(vault_lamports: u64, lamports: u64) -> bool {
+    lamports == 0
+        || vault_lamports
+            .checked_sub(lamports)
+            .is_some_and(|remaining| remaining >= VAULT_MINIMUM)
+}
+
+/// Simulate the treasury amounts of `execute_payment.rs`: (platform, keeper USDC)
+const fn treasury_amounts(platform_fee: u64, keeper_fee: u64, paid_in_sol: bool) -> (u64, u64) {
+    if paid_in_sol {
+        (platform_fee.checked_add(keeper_fee).unwrap(), 0)
+    } else {
+        (platform_fee, keeper_fee)
+    }
+}
+
+// ============================================================================
+// Platform Rate
+// ============================================================================
+
+/// Test that the rate is used until it is older than the maximum age
+#[test]
+fn test_rate_expires() {
+    let keeper_fee = 15_000; // 0.015 USDC
+
+    assert!(RATE.lamports_for(keeper_fee, NOW).is_some());
+    assert!(RATE
+        .lamports_for(keeper_fee, NOW + MAX_KEEPER_SOL_RATE_AGE_SECS)
+        .is_some());
+    assert!(RATE
+        .lamports_for(keeper_fee, NOW + MAX_KEEPER_SOL_RATE_AGE_SECS + 1)
+        .is_none());
+}
+
+/// Test that a config without a rate rejects every SOL keeper fee
+#[test]
+fn test_disabled_rate_rejects_sol_fees() {
+    let keeper_sol_rate: Option<KeeperSolRate> = None;
+    assert!(keeper_sol_rate
+        .and_then(|rate| rate.lamports_for(15_000, NOW))
+        .is_none());
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+/// Test that the fee converts at the platform's rate and rounds down
+#[test]
+fn test_lamports_conversion() {
+    // 1 USDC at 200 USDC per SOL is 0.005 SOL
+    assert_eq!(RATE.lamports_for(ONE_USDC, NOW), Some(5_000_000));
+    // 0.015 USDC at the same rate
+    assert_eq!(RATE.lamports_for(15_000, NOW), Some(75_000));
+    // One USDC base unit is worth 5 lamports; less than a lamport rounds to zero
+    assert_eq!(RATE.lamports_for(1, NOW), Some(5));
+    let tiny = KeeperSolRate {
+        lamports_per_usdc: 1,
+        updated_ts: NOW,
+    };
+    assert_eq!(tiny.lamports_for(999_999, NOW), Some(0));
+}
+
+/// Test that extreme fees and rates fail instead of overflowing
+#[test]
+fn test_conversion_overflow() {
+    let extreme = KeeperSolRate {
+        lamports_per_usdc: u64::MAX,
+        updated_ts: NOW,
+    };
+    assert!(extreme.lamports_for(u64::MAX, NOW).is_none());
+
+    let lowest = KeeperSolRate {
+        lamports_per_usdc: 1,
+        updated_ts: NOW,
+    };
+    assert_eq!(lowest.lamports_for(u64::MAX, NOW), Some(u64::MAX / ONE_USDC));
+
+    // An age that overflows counts as stale
+    assert!(RATE.lamports_for(15_000, i64::MIN).is_none());
+    let ancient = KeeperSolRate {
+        updated_ts: i64::MIN,
+        ..RATE
+    };
+    assert!(ancient.lamports_for(15_000, NOW).is_none());
+}
+
+// ============================================================================
+// Vault and Treasury
+// ============================================================================
+
+/// Test that the vault must stay rent-exempt after paying the keeper
+#[test]
+fn test_vault_stays_rent_exempt() {
+    assert!(vault_can_pay(VAULT_MINIMUM + 75_000, 75_000));
+    assert!(!vault_can_pay(VAULT_MINIMUM + 74_999, 75_000));
+    assert!(!vault_can_pay(0, 75_000));
+    // A fee rounding to zero lamports needs no vault balance
+    assert!(vault_can_pay(0, 0));
+}
+
+/// Test that the payer pays the same and the platform keeps the USDC keeper fee
+#[test]
+fn test_keeper_fee_goes_to_platform() {
+    let amount = 10 * ONE_USDC;
+    let split = calculate_fee_split(amount, 15, 25).unwrap();
+
+    let (platform_usdc, keeper_usdc) =
+        treasury_amounts(split.platform_fee, split.keeper_fee, false);
+    assert_eq!(platform_usdc + keeper_usdc + split.payee_amount, amount);
+    assert_eq!(keeper_usdc, 15_000);
+
+    let (platform_usdc, keeper_usdc) = treasury_amounts(split.platform_fee, split.keeper_fee, true);
+    assert_eq!(keeper_usdc, 0);
+    assert_eq!(platform_usdc, split.platform_fee + split.keeper_fee);
+    assert_eq!(
+        platform_usdc + split.payee_amount,
+        amount,
+        "Payer is charged the same"
+    );
+}
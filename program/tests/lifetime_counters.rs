@@ -0,0 +1,240 @@
+//! Unit tests for the agreement and revenue counters on `Payee` and `PaymentTerms`
+//!
+//! This test suite validates how `start_agreement`, `execute_payment`, `pause_agreement`,
+//! `resume_agreement` and the escrow instructions maintain `active_agreements`,
+//! `lifetime_revenue_usdc` and `lifetime_renewals` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Starting and resuming claim a slot on both the terms and the payee
+//! - Pausing and completing release both slots exactly once
+//! - Revenue counts the amount charged to the payer, before fees
+//! - Only `execute_payment` counts as a renewal
+//! - Escrowed first payments count as revenue only once released
+//! - Payee totals aggregate every terms of the payee
+//! - Counters saturate instead of overflowing or underflowing
+//! - `Payee::SPACE` and `PaymentTerms::SPACE` cover the new fields
+//!
+//! Business Context:
+//! Dashboards need subscriber and revenue totals without scanning every agreement.
+//! Each handler that changes an agreement's state or collects a payment updates both
+//! accounts together:
+//! ```rust
+//! payment_terms.record_payment(payment_amount, true);
+//! payee.record_payment(payment_amount, true);
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use tally_protocol::state::{Payee, PaymentTerms};
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: u64 = 2_592_000;
+const NOW: i64 = 1_700_000_000;
+
+fn payee() -> Payee {
+    Payee {
+        last_volume_update_ts: NOW,
+        ..common::payee()
+    }
+}
+
+fn terms(payee: Pubkey, amount_usdc: u64) -> PaymentTerms {
+    PaymentTerms {
+        payee,
+        amount_usdc,
+        ..common::terms()
+    }
+}
+
+/// Simulate the slot claim of `start_agreement.rs` and `resume_agreement.rs`
+const fn claim_slot(terms: &mut PaymentTerms, payee: &mut Payee) {
+    terms.active_agreements = terms.active_agreements.checked_add(1).unwrap();
+    payee.active_agreements = payee.active_agreements.checked_add(1).unwrap();
+}
+
+/// Simulate `start_agreement.rs`, returning whether the agreement is active
+const fn start(terms: &mut PaymentTerms, payee: &mut Payee, escrowed: bool) -> bool {
+    claim_slot(terms, payee);
+    if !escrowed {
+        terms.record_payment(terms.amount_usdc, false);
+        payee.record_payment(terms.amount_usdc, false);
+    }
+    true
+}
+
+/// Simulate `execute_payment.rs` for a due agreement
+const fn execute(terms: &mut PaymentTerms, payee: &mut Payee) {
+    let amount = terms.amount_usdc;
+    terms.record_payment(amount, true);
+    payee.record_payment(amount, true);
+}
+
+/// Simulate `pause_agreement.rs`, which only releases the slot of an active agreement
+const fn pause(terms: &mut PaymentTerms, payee: &mut Payee, active: &mut bool) {
+    if *active {
+        terms.release_subscriber_slot();
+        payee.release_subscriber_slot();
+    }
+    *active = false;
+}
+
+/// Simulate `resume_agreement.rs` charging `amount_charged` after pause credit
+const fn resume(terms: &mut PaymentTerms, payee: &mut Payee, active: &mut bool, amount_charged: u64) {
+    claim_slot(terms, payee);
+    terms.record_payment(amount_charged, false);
+    payee.record_payment(amount_charged, false);
+    *active = true;
+}
+
+/// Simulate `confirm_activation.rs` releasing an escrowed first payment
+const fn confirm_activation(terms: &mut PaymentTerms, payee: &mut Payee, amount: u64) {
+    terms.record_payment(amount, false);
+    payee.record_payment(amount, false);
+}
+
+// ============================================================================
+// Active Agreements
+// ============================================================================
+
+/// Test that the terms and payee counts move together through the lifecycle
+#[test]
+fn test_active_agreements_follow_lifecycle() {
+    let mut payee = payee();
+    let mut terms = terms(Pubkey::new_unique(), 10 * ONE_USDC);
+
+    let mut active = start(&mut terms, &mut payee, false);
+    assert_eq!(terms.active_agreements, 1);
+    assert_eq!(payee.active_agreements, 1);
+
+    pause(&mut terms, &mut payee, &mut active);
+    pause(&mut terms, &mut payee, &mut active);
+    assert_eq!(terms.active_agreements, 0, "Pausing twice releases once");
+    assert_eq!(payee.active_agreements, 0);
+
+    resume(&mut terms, &mut payee, &mut active, 10 * ONE_USDC);
+    assert_eq!(terms.active_agreements, 1);
+    assert_eq!(payee.active_agreements, 1);
+}
+
+/// Test that the payee count spans all of the payee's terms
+#[test]
+fn test_payee_counts_all_terms() {
+    let mut payee = payee();
+    let mut basic = terms(Pubkey::new_unique(), 10 * ONE_USDC);
+    let mut pro = terms(Pubkey::new_unique(), 50 * ONE_USDC);
+
+    start(&mut basic, &mut payee, false);
+    start(&mut basic, &mut payee, false);
+    let mut pro_active = start(&mut pro, &mut payee, false);
+    execute(&mut basic, &mut payee);
+    execute(&mut pro, &mut payee);
+    pause(&mut pro, &mut payee, &mut pro_active);
+
+    assert_eq!(basic.active_agreements, 2);
+    assert_eq!(pro.active_agreements, 0);
+    assert_eq!(payee.active_agreements, 2);
+    assert_eq!(
+        payee.lifetime_revenue_usdc,
+        basic
+            .lifetime_revenue_usdc
+            .checked_add(pro.lifetime_revenue_usdc)
+            .unwrap()
+    );
+    assert_eq!(payee.lifetime_renewals, 2);
+}
+
+/// Test that releasing a slot never underflows for agreements predating the counter
+#[test]
+fn test_release_saturates_at_zero() {
+    let mut payee = payee();
+    payee.release_subscriber_slot();
+    assert_eq!(payee.active_agreements, 0);
+}
+
+// ============================================================================
+// Revenue and Renewals
+// ============================================================================
+
+/// Test that revenue counts gross charges and only executed payments are renewals
+#[test]
+fn test_revenue_and_renewals() {
+    let mut payee = payee();
+    let mut terms = terms(Pubkey::new_unique(), 10 * ONE_USDC);
+
+    let mut active = start(&mut terms, &mut payee, false);
+    execute(&mut terms, &mut payee);
+    execute(&mut terms, &mut payee);
+    pause(&mut terms, &mut payee, &mut active);
+    // Pause credit reduced the resume charge
+    resume(&mut terms, &mut payee, &mut active, 4 * ONE_USDC);
+
+    assert_eq!(terms.lifetime_revenue_usdc, 34 * ONE_USDC);
+    assert_eq!(terms.lifetime_renewals, 2);
+    assert_eq!(payee.lifetime_revenue_usdc, 34 * ONE_USDC);
+    assert_eq!(payee.lifetime_renewals, 2);
+}
+
+/// Test that an escrowed first payment counts once released, not when escrowed
+#[test]
+fn test_escrowed_payment_counts_on_release() {
+    let mut payee = payee();
+    let mut terms = terms(Pubkey::new_unique(), 5_000 * ONE_USDC);
+
+    start(&mut terms, &mut payee, true);
+    assert_eq!(terms.lifetime_revenue_usdc, 0);
+    assert_eq!(payee.lifetime_revenue_usdc, 0);
+    assert_eq!(payee.active_agreements, 1);
+
+    confirm_activation(&mut terms, &mut payee, 5_000 * ONE_USDC);
+    assert_eq!(terms.lifetime_revenue_usdc, 5_000 * ONE_USDC);
+    assert_eq!(payee.lifetime_revenue_usdc, 5_000 * ONE_USDC);
+    assert_eq!(payee.lifetime_renewals, 0);
+}
+
+/// Test that the totals saturate instead of overflowing
+#[test]
+fn test_revenue_saturates() {
+    let mut payee = payee();
+    payee.lifetime_revenue_usdc = u64::MAX - 1;
+    payee.lifetime_renewals = u64::MAX;
+
+    payee.record_payment(10 * ONE_USDC, true);
+    assert_eq!(payee.lifetime_revenue_usdc, u64::MAX);
+    assert_eq!(payee.lifetime_renewals, u64::MAX);
+}
+
+// ============================================================================
+// Account Size
+// ============================================================================
+
+/// Test that both account sizes cover the counters
+#[test]
+fn test_account_space() {
+    let mut payee = payee();
+    payee.authorized_keepers = vec![Pubkey::new_unique(); 5];
+    payee.pending_authority = Some(Pubkey::new_unique());
+    payee.fee_holiday = Some(tally_protocol::state::FeeHoliday {
+        until_ts: NOW,
+        rebate_bps: 10,
+    });
+    assert_eq!(Payee::SPACE, 385);
+    assert_eq!(payee.try_to_vec().unwrap().len() + 8, Payee::SPACE);
+
+    let mut terms = terms(Pubkey::new_unique(), 10 * ONE_USDC);
+    terms.gate_mint = Some(Pubkey::new_unique());
+    terms.max_subscribers = Some(100);
+    terms.sunset_ts = Some(NOW);
+    terms.escrow_window_secs = Some(THIRTY_DAYS);
+    terms.deposit_usdc = Some(50 * ONE_USDC);
+    terms.accrual_rate_per_sec = Some(1);
+    terms.pending_update = Some(tally_protocol::state::PendingTermsUpdate {
+        amount_usdc: ONE_USDC,
+        period_secs: THIRTY_DAYS,
+        effective_ts: NOW,
+    });
+    assert_eq!(PaymentTerms::SPACE, 214);
+    assert_eq!(terms.try_to_vec().unwrap().len() + 8, PaymentTerms::SPACE);
+}
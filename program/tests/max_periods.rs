@@ -0,0 +1,195 @@
+//! Unit tests for billing period limits (installment-style agreements)
+//!
+//! This test suite validates `max_periods` handling in `start_agreement` and
+//! `execute_payment` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Limits below two billing periods are rejected at start
+//! - The initial payment counts as the first billing period
+//! - The final billing period completes and pauses the agreement
+//! - Charges beyond the limit are rejected
+//! - Unlimited agreements never complete
+//! - Reactivation starts a new billing period limit
+//! - `PaymentAgreement::SPACE` covers the new fields
+//!
+//! Business Context:
+//! Installment plans ("pay in 12") charge a fixed number of periods and then stop.
+//! `execute_payment` completes the agreement on its final charge instead of
+//! scheduling another payment:
+//! ```rust
+//! let completed = payment_agreement.periods_exhausted();
+//! if completed {
+//!     payment_agreement.active = false;
+//!     payment_terms.release_subscriber_slot();
+//! }
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{AgreementEscrow, PaymentAgreement, SuspensionReason};
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: i64 = 2_592_000;
+const START: i64 = 1_700_000_000;
+
+/// Outcome of a simulated `execute_payment` call
+#[derive(Debug, PartialEq, Eq)]
+enum Execution {
+    Charged,
+    Completed,
+}
+
+/// Simulate `start_agreement.rs` for a new agreement
+fn start(max_periods: Option<u16>) -> Result<PaymentAgreement, RecurringPaymentError> {
+    if let Some(max_periods) = max_periods {
+        if max_periods < 2 {
+            return Err(RecurringPaymentError::InvalidMaxPeriods);
+        }
+    }
+    Ok(PaymentAgreement {
+        next_payment_ts: START + THIRTY_DAYS,
+        payment_count: 0,
+        created_ts: START,
+        last_payment_ts: START,
+        max_periods,
+        ..common::agreement()
+    })
+}
+
+/// Simulate the billing period steps of `execute_payment.rs`
+fn execute(agreement: &mut PaymentAgreement) -> Result<Execution, RecurringPaymentError> {
+    if !agreement.active {
+        return Err(RecurringPaymentError::Inactive);
+    }
+    if agreement.periods_exhausted() {
+        return Err(RecurringPaymentError::MaxPeriodsReached);
+    }
+    agreement.last_payment_ts = agreement.next_payment_ts;
+    agreement.next_payment_ts = agreement
+        .next_payment_ts
+        .checked_add(THIRTY_DAYS)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    agreement.payment_count = agreement
+        .payment_count
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    agreement.periods_paid = agreement
+        .periods_paid
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    if agreement.periods_exhausted() {
+        agreement.active = false;
+        Ok(Execution::Completed)
+    } else {
+        Ok(Execution::Charged)
+    }
+}
+
+// ============================================================================
+// Start Validation
+// ============================================================================
+
+/// Test that limits below two billing periods are rejected
+#[test]
+fn test_start_rejects_limit_below_two() {
+    assert!(matches!(start(Some(0)), Err(RecurringPaymentError::InvalidMaxPeriods)));
+    assert!(matches!(start(Some(1)), Err(RecurringPaymentError::InvalidMaxPeriods)));
+    assert!(start(Some(2)).is_ok());
+    assert!(start(None).is_ok());
+}
+
+/// Test that the initial payment counts as the first billing period
+#[test]
+fn test_initial_payment_counts_as_first_period() {
+    let agreement = start(Some(12)).unwrap();
+    assert_eq!(agreement.periods_paid, 1);
+    assert!(!agreement.periods_exhausted());
+}
+
+// ============================================================================
+// Completion
+// ============================================================================
+
+/// Test that a pay-in-12 plan charges 11 renewals and completes on the last one
+#[test]
+fn test_final_period_completes_agreement() {
+    let mut agreement = start(Some(12)).unwrap();
+
+    for _ in 0..10 {
+        assert!(matches!(execute(&mut agreement), Ok(Execution::Charged)));
+    }
+    assert!(matches!(execute(&mut agreement), Ok(Execution::Completed)));
+    assert_eq!(agreement.periods_paid, 12);
+    assert_eq!(agreement.payment_count, 11);
+    assert!(!agreement.active);
+}
+
+/// Test that charges beyond the limit are rejected even if the agreement is active
+#[test]
+fn test_charges_beyond_limit_rejected() {
+    let mut agreement = start(Some(2)).unwrap();
+    assert!(matches!(execute(&mut agreement), Ok(Execution::Completed)));
+
+    // Defense in depth: the limit holds even if the agreement were reactivated by hand
+    agreement.active = true;
+    assert!(matches!(
+        execute(&mut agreement),
+        Err(RecurringPaymentError::MaxPeriodsReached)
+    ));
+    assert_eq!(agreement.periods_paid, 2);
+}
+
+/// Test that unlimited agreements never complete
+#[test]
+fn test_unlimited_agreement_never_completes() {
+    let mut agreement = start(None).unwrap();
+    for _ in 0..24 {
+        assert!(matches!(execute(&mut agreement), Ok(Execution::Charged)));
+    }
+    assert!(agreement.active);
+}
+
+/// Test that reactivation starts a new billing period limit
+#[test]
+fn test_reactivation_resets_limit() {
+    let mut agreement = start(Some(2)).unwrap();
+    assert!(matches!(execute(&mut agreement), Ok(Execution::Completed)));
+
+    // Reactivation path of start_agreement.rs
+    agreement.active = true;
+    agreement.max_periods = Some(3);
+    agreement.periods_paid = 1;
+
+    assert!(matches!(execute(&mut agreement), Ok(Execution::Charged)));
+    assert!(matches!(execute(&mut agreement), Ok(Execution::Completed)));
+    assert_eq!(agreement.payment_count, 3, "Payment count stays cumulative");
+}
+
+// ============================================================================
+// Account Size
+// ============================================================================
+
+/// Test that the account size covers the billing period limit fields
+#[test]
+fn test_payment_agreement_space() {
+    use anchor_lang::AnchorSerialize;
+
+    let mut agreement = start(Some(12)).unwrap();
+    agreement.pending_payer = Some(Pubkey::new_unique());
+    agreement.external_ref_hash = Some([7; 32]);
+    agreement.paused_at_ts = Some(START);
+    agreement.escrow = Some(AgreementEscrow {
+        amount: ONE_USDC,
+        expires_ts: START,
+    });
+    agreement.suspension = Some(SuspensionReason::FrozenAccount);
+    agreement.deposit_held = 50_000_000;
+
+    let serialized_len = agreement.try_to_vec().unwrap().len();
+    assert_eq!(PaymentAgreement::SPACE, 259);
+    assert_eq!(serialized_len + 8, PaymentAgreement::SPACE);
+}
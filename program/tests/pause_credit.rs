@@ -0,0 +1,213 @@
+//! Unit tests for partial-period pause credit
+//!
+//! This test suite validates credit accrual in `pause_agreement` and its application
+//! in `resume_agreement` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Pausing mid-period credits the unused part of the period
+//! - Pausing once the next payment is due accrues no credit
+//! - Refunds reduce the credited value
+//! - Pausing an already paused agreement accrues nothing
+//! - Resume charges the period price less the credit
+//! - Credit above the period price carries over to a later resume
+//! - Only agreements paused by the payer can be resumed
+//! - `start_agreement` refuses to reactivate an agreement holding credit
+//!
+//! Business Context:
+//! A payer pausing halfway through a paid month used to lose the second half. Pausing
+//! now records `paused_at_ts` and credits the unused value, which the first charge
+//! after resume is offset by:
+//! ```rust
+//! let credit_applied = payment_agreement.credit_amount.min(payment_amount);
+//! let amount_charged = payment_amount.checked_sub(credit_applied)?;
+//! ```
+
+mod common;
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: u64 = 2_592_000;
+const FIFTEEN_DAYS: i64 = 1_296_000;
+const LAST_PAYMENT: i64 = 1_700_000_000;
+const NEXT_PAYMENT: i64 = 1_702_592_000;
+
+fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: NEXT_PAYMENT,
+        payment_count: 3,
+        created_ts: LAST_PAYMENT,
+        last_payment_ts: LAST_PAYMENT,
+        last_pull_period_index: 3,
+        periods_paid: 4,
+        ..common::agreement()
+    }
+}
+
+/// Simulate the credit accrual of `pause_agreement.rs`
+fn pause(agreement: &mut PaymentAgreement, now: i64) -> Result<(), RecurringPaymentError> {
+    if agreement.active {
+        let credit = agreement
+            .unused_period_value(now, THIRTY_DAYS)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        agreement.credit_amount = agreement
+            .credit_amount
+            .checked_add(credit)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        agreement.paused_at_ts = Some(now);
+    }
+    agreement.active = false;
+    Ok(())
+}
+
+/// Simulate the charge of `resume_agreement.rs`, returning `(credit_applied, amount_charged)`
+fn resume(
+    agreement: &mut PaymentAgreement,
+    payment_amount: u64,
+) -> Result<(u64, u64), RecurringPaymentError> {
+    if agreement.active {
+        return Err(RecurringPaymentError::AlreadyActive);
+    }
+    agreement.paused_at_ts.ok_or(RecurringPaymentError::NotPaused)?;
+
+    let credit_applied = agreement.credit_amount.min(payment_amount);
+    let amount_charged = payment_amount
+        .checked_sub(credit_applied)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+
+    agreement.active = true;
+    agreement.last_amount = payment_amount;
+    agreement.refunded_amount = 0;
+    agreement.paused_at_ts = None;
+    agreement.credit_amount = agreement
+        .credit_amount
+        .checked_sub(credit_applied)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok((credit_applied, amount_charged))
+}
+
+/// Simulate the pause credit check on the reactivation path of `start_agreement.rs`
+const fn check_reactivation(agreement: &PaymentAgreement) -> Result<(), RecurringPaymentError> {
+    if agreement.credit_amount > 0 {
+        return Err(RecurringPaymentError::ResumeRequired);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Credit Accrual
+// ============================================================================
+
+/// Test that pausing halfway through a period credits half of the last payment
+#[test]
+fn test_pause_mid_period_accrues_credit() {
+    let mut agreement = agreement();
+    let paused_at = LAST_PAYMENT.checked_add(FIFTEEN_DAYS).unwrap();
+
+    pause(&mut agreement, paused_at).unwrap();
+    assert!(!agreement.active);
+    assert_eq!(agreement.paused_at_ts, Some(paused_at));
+    assert_eq!(agreement.credit_amount, 5 * ONE_USDC);
+}
+
+/// Test that pausing once the next payment is due accrues no credit
+#[test]
+fn test_pause_after_due_accrues_nothing() {
+    let mut agreement = agreement();
+    pause(&mut agreement, NEXT_PAYMENT.checked_add(60).unwrap()).unwrap();
+    assert_eq!(agreement.credit_amount, 0);
+    assert!(agreement.paused_at_ts.is_some());
+}
+
+/// Test that refunded amounts are not credited a second time
+#[test]
+fn test_refunds_reduce_credit() {
+    let mut agreement = agreement();
+    agreement.refunded_amount = 4 * ONE_USDC;
+
+    pause(&mut agreement, LAST_PAYMENT.checked_add(FIFTEEN_DAYS).unwrap()).unwrap();
+    assert_eq!(agreement.credit_amount, 3 * ONE_USDC);
+}
+
+/// Test that pausing an already paused agreement keeps the original credit
+#[test]
+fn test_repeated_pause_is_idempotent() {
+    let mut agreement = agreement();
+    let paused_at = LAST_PAYMENT.checked_add(FIFTEEN_DAYS).unwrap();
+    pause(&mut agreement, paused_at).unwrap();
+    pause(&mut agreement, paused_at.checked_add(60).unwrap()).unwrap();
+
+    assert_eq!(agreement.credit_amount, 5 * ONE_USDC);
+    assert_eq!(agreement.paused_at_ts, Some(paused_at));
+}
+
+// ============================================================================
+// Resume
+// ============================================================================
+
+/// Test that resume charges the period price less the credit and clears it
+#[test]
+fn test_resume_applies_credit() {
+    let mut agreement = agreement();
+    pause(&mut agreement, LAST_PAYMENT.checked_add(FIFTEEN_DAYS).unwrap()).unwrap();
+
+    let (credit_applied, amount_charged) = resume(&mut agreement, 10 * ONE_USDC).unwrap();
+    assert_eq!(credit_applied, 5 * ONE_USDC);
+    assert_eq!(amount_charged, 5 * ONE_USDC);
+    assert!(agreement.active);
+    assert_eq!(agreement.credit_amount, 0);
+    assert_eq!(agreement.paused_at_ts, None);
+    assert_eq!(agreement.last_amount, 10 * ONE_USDC, "Records the full period price");
+}
+
+/// Test that credit above a reduced price carries over instead of being paid out
+#[test]
+fn test_excess_credit_carries_over() {
+    let mut agreement = agreement();
+    pause(&mut agreement, LAST_PAYMENT.checked_add(FIFTEEN_DAYS).unwrap()).unwrap();
+
+    // The payee lowered the price to 3 USDC while the agreement was paused
+    let (credit_applied, amount_charged) = resume(&mut agreement, 3 * ONE_USDC).unwrap();
+    assert_eq!(credit_applied, 3 * ONE_USDC);
+    assert_eq!(amount_charged, 0);
+    assert_eq!(agreement.credit_amount, 2 * ONE_USDC);
+
+    // The remainder is added to the credit of the next pause
+    pause(&mut agreement, NEXT_PAYMENT).unwrap();
+    assert_eq!(agreement.credit_amount, 2 * ONE_USDC);
+}
+
+/// Test that only agreements paused by the payer can be resumed
+#[test]
+fn test_resume_requires_pause() {
+    let mut active = agreement();
+    assert!(matches!(
+        resume(&mut active, 10 * ONE_USDC),
+        Err(RecurringPaymentError::AlreadyActive)
+    ));
+
+    // Completed or cancelled at period end: inactive without a pause timestamp
+    let mut completed = agreement();
+    completed.active = false;
+    assert!(matches!(
+        resume(&mut completed, 10 * ONE_USDC),
+        Err(RecurringPaymentError::NotPaused)
+    ));
+}
+
+/// Test that `start_agreement` doesn't reactivate an agreement holding credit
+#[test]
+fn test_start_reactivation_requires_resume() {
+    let mut agreement = agreement();
+    pause(&mut agreement, LAST_PAYMENT.checked_add(FIFTEEN_DAYS).unwrap()).unwrap();
+    assert!(matches!(
+        check_reactivation(&agreement),
+        Err(RecurringPaymentError::ResumeRequired)
+    ));
+
+    let mut due = self::agreement();
+    pause(&mut due, NEXT_PAYMENT).unwrap();
+    assert!(check_reactivation(&due).is_ok());
+}
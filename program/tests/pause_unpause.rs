@@ -10,6 +10,8 @@
 //! - Pause state enforcement on user-facing instructions
 //! - Admin operations continue during pause (not tested here, validated in integration tests)
 //! - Event emission for pause/unpause operations
+//! - Pause reason and scheduled auto-unpause stored on Config
+//! - Pauses with `auto_unpause_ts` lapse at that time without an `unpause` call
 //!
 //! Security Context (M-2):
 //! The emergency pause mechanism allows the platform authority to halt all user-facing
@@ -28,11 +30,22 @@
 //! pub config: Account<'info, Config>,
 //! ```
 //!
+//! Pauses may carry a reason and an `auto_unpause_ts`, so the gate is time-aware:
+//! ```rust
+//! constraint = !config.is_paused(Clock::get()?.unix_timestamp) @ RecurringPaymentError::Inactive
+//! ```
+//!
 //! Note: These are unit tests that validate the business logic.
 //! Full end-to-end integration tests should be run with `anchor test`.
 
+mod common;
+
 use anchor_lang::prelude::*;
 use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{Config, KeeperSolRate};
+
+const NOW: i64 = 1_700_000_000;
+const ONE_HOUR: i64 = 3_600;
 
 /// Test that Config paused field defaults to false on initialization
 #[test]
@@ -301,3 +314,115 @@ fn test_config_space_includes_paused() {
 
     assert_eq!(total, 136, "Config space should be 136 bytes with paused field");
 }
+
+// ============================================================================
+// Pause Reason and Auto-Unpause
+// ============================================================================
+
+fn reason(text: &str) -> [u8; 64] {
+    let mut reason = [0u8; 64];
+    reason[..text.len()].copy_from_slice(text.as_bytes());
+    reason
+}
+
+/// Simulate `pause.rs`
+fn pause(
+    config: &mut Config,
+    reason: [u8; 64],
+    auto_unpause_ts: Option<i64>,
+    now: i64,
+) -> std::result::Result<(), RecurringPaymentError> {
+    if core::str::from_utf8(&reason).is_err() {
+        return Err(RecurringPaymentError::InvalidConfiguration);
+    }
+    if auto_unpause_ts.is_some_and(|ts| ts <= now) {
+        return Err(RecurringPaymentError::InvalidConfiguration);
+    }
+    config.paused = true;
+    config.pause_reason = reason;
+    config.auto_unpause_ts = auto_unpause_ts;
+    Ok(())
+}
+
+/// Simulate `unpause.rs`
+const fn unpause(config: &mut Config) {
+    config.paused = false;
+    config.pause_reason = [0; 64];
+    config.auto_unpause_ts = None;
+}
+
+/// Test that the pause reason and resume time are stored on Config
+#[test]
+fn test_pause_stores_reason_and_auto_unpause() {
+    let mut config = common::config();
+    pause(&mut config, reason("Scheduled maintenance"), Some(NOW + ONE_HOUR), NOW).unwrap();
+
+    assert!(config.paused);
+    assert_eq!(&config.pause_reason[..21], b"Scheduled maintenance");
+    assert_eq!(config.auto_unpause_ts, Some(NOW + ONE_HOUR));
+}
+
+/// Test that a scheduled pause blocks operations until the resume time
+#[test]
+fn test_auto_unpause_lapses_at_scheduled_time() {
+    let mut config = common::config();
+    pause(&mut config, reason("Upgrade"), Some(NOW + ONE_HOUR), NOW).unwrap();
+
+    assert!(config.is_paused(NOW));
+    assert!(config.is_paused(NOW + ONE_HOUR - 1));
+    assert!(!config.is_paused(NOW + ONE_HOUR), "Pause should lapse at auto_unpause_ts");
+    assert!(config.paused, "Stored flag stays set until the next pause or unpause");
+}
+
+/// Test that a pause without a resume time lasts until unpause
+#[test]
+fn test_pause_without_auto_unpause_is_indefinite() {
+    let mut config = common::config();
+    pause(&mut config, reason("Incident"), None, NOW).unwrap();
+
+    assert!(config.is_paused(i64::MAX));
+
+    unpause(&mut config);
+    assert!(!config.is_paused(NOW));
+    assert_eq!(config.pause_reason, [0; 64], "Unpause clears the reason");
+    assert_eq!(config.auto_unpause_ts, None);
+}
+
+/// Test that the resume time must be in the future
+#[test]
+fn test_auto_unpause_must_be_in_future() {
+    let mut config = common::config();
+
+    let result = pause(&mut config, reason("Upgrade"), Some(NOW), NOW);
+    assert!(matches!(result, Err(RecurringPaymentError::InvalidConfiguration)));
+    assert!(!config.paused, "Rejected pause must not be applied");
+}
+
+/// Test that the pause reason must be valid UTF-8
+#[test]
+fn test_pause_reason_must_be_utf8() {
+    let mut config = common::config();
+    let mut invalid = [0u8; 64];
+    invalid[0] = 0xFF;
+
+    let result = pause(&mut config, invalid, None, NOW);
+    assert!(matches!(result, Err(RecurringPaymentError::InvalidConfiguration)));
+}
+
+/// Test that the account size includes the pause reason and resume time
+#[test]
+fn test_config_space_includes_pause_details() {
+    use anchor_lang::AnchorSerialize;
+
+    let mut config = common::config();
+    config.pending_authority = Some(Pubkey::new_unique());
+    config.auto_unpause_ts = Some(NOW + ONE_HOUR);
+    config.keeper_sol_rate = Some(KeeperSolRate {
+        lamports_per_usdc: 1,
+        updated_ts: NOW,
+    });
+
+    let serialized_len = config.try_to_vec().unwrap().len();
+    assert_eq!(Config::SPACE, 237);
+    assert_eq!(serialized_len + 8, Config::SPACE);
+}
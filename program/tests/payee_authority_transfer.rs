@@ -0,0 +1,265 @@
+//! Unit tests for the two-step payee authority transfer
+//!
+//! This test suite validates `transfer_payee_authority`, `accept_payee_authority`
+//! and `cancel_payee_authority_transfer` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Transfer proposal, acceptance and cancellation state transitions
+//! - Only the pending authority can accept
+//! - Pending transfers cannot be overwritten or targeted at the current authority
+//! - The payee PDA stays derived from `original_authority` across transfers
+//! - The treasury moves to the new authority's ATA
+//! - `Payee::SPACE` covers the transfer fields
+//!
+//! Business Context:
+//! Losing a merchant key used to strand the payee and its treasury configuration.
+//! The payee PDA is derived from the authority it was created with, so the transfer
+//! only swaps the signing key and treasury while every payment terms and agreement
+//! account keeps pointing at the same payee:
+//! ```rust
+//! #[account(
+//!     seeds = [b"payee", payee.original_authority.as_ref()],
+//!     bump = payee.bump,
+//!     has_one = authority @ RecurringPaymentError::Unauthorized
+//! )]
+//! pub payee: Account<'info, Payee>,
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{FeeHoliday, Payee};
+
+/// Payee as created by `init_payee.rs`
+fn payee() -> Payee {
+    let authority = Pubkey::new_unique();
+    let usdc_mint = Pubkey::new_unique();
+    Payee {
+        usdc_mint,
+        treasury_ata: get_associated_token_address(&authority, &usdc_mint),
+        ..common::payee()
+    }
+}
+
+/// Simulate `transfer_payee_authority.rs`
+fn transfer(
+    payee: &mut Payee,
+    signer: &Pubkey,
+    new_authority: Pubkey,
+) -> Result<(), RecurringPaymentError> {
+    if *signer != payee.authority {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    if payee.pending_authority.is_some() {
+        return Err(RecurringPaymentError::TransferAlreadyPending);
+    }
+    if new_authority == payee.authority {
+        return Err(RecurringPaymentError::InvalidTransferTarget);
+    }
+    payee.pending_authority = Some(new_authority);
+    Ok(())
+}
+
+/// Simulate `accept_payee_authority.rs`
+fn accept(
+    payee: &mut Payee,
+    signer: &Pubkey,
+    new_treasury_ata: &Pubkey,
+) -> Result<(), RecurringPaymentError> {
+    let pending_authority = payee
+        .pending_authority
+        .ok_or(RecurringPaymentError::NoPendingTransfer)?;
+    if *signer != pending_authority {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    if *new_treasury_ata != get_associated_token_address(&pending_authority, &payee.usdc_mint) {
+        return Err(RecurringPaymentError::BadSeeds);
+    }
+    payee.authority = pending_authority;
+    payee.treasury_ata = *new_treasury_ata;
+    payee.pending_authority = None;
+    Ok(())
+}
+
+/// Simulate `cancel_payee_authority_transfer.rs`
+fn cancel(payee: &mut Payee, signer: &Pubkey) -> Result<(), RecurringPaymentError> {
+    if *signer != payee.authority {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    payee
+        .pending_authority
+        .take()
+        .ok_or(RecurringPaymentError::NoPendingTransfer)?;
+    Ok(())
+}
+
+/// Payee PDA as derived by every instruction that takes the payee account
+fn payee_pda(payee: &Payee, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"payee", payee.original_authority.as_ref()], program_id).0
+}
+
+// ============================================================================
+// Transfer Flow Tests
+// ============================================================================
+
+/// Test that an accepted transfer hands the payee and its treasury to the new key
+#[test]
+fn test_transfer_and_accept() {
+    let mut payee = payee();
+    let old_authority = payee.authority;
+    let new_authority = Pubkey::new_unique();
+    let new_treasury_ata = get_associated_token_address(&new_authority, &payee.usdc_mint);
+
+    transfer(&mut payee, &old_authority, new_authority).unwrap();
+    assert_eq!(payee.authority, old_authority, "Authority changes only on accept");
+    assert_eq!(payee.pending_authority, Some(new_authority));
+
+    accept(&mut payee, &new_authority, &new_treasury_ata).unwrap();
+    assert_eq!(payee.authority, new_authority);
+    assert_eq!(payee.treasury_ata, new_treasury_ata);
+    assert_eq!(payee.pending_authority, None);
+    assert_eq!(payee.original_authority, old_authority);
+}
+
+/// Test that the payee PDA is unchanged by a transfer
+#[test]
+fn test_payee_pda_stable_across_transfer() {
+    let program_id = Pubkey::new_unique();
+    let mut payee = payee();
+    let before = payee_pda(&payee, &program_id);
+
+    let new_authority = Pubkey::new_unique();
+    let new_treasury_ata = get_associated_token_address(&new_authority, &payee.usdc_mint);
+    let authority = payee.authority;
+    transfer(&mut payee, &authority, new_authority).unwrap();
+    accept(&mut payee, &new_authority, &new_treasury_ata).unwrap();
+
+    assert_eq!(payee_pda(&payee, &program_id), before);
+}
+
+/// Test that a cancelled transfer can no longer be accepted
+#[test]
+fn test_cancel_revokes_pending_transfer() {
+    let mut payee = payee();
+    let authority = payee.authority;
+    let new_authority = Pubkey::new_unique();
+    let new_treasury_ata = get_associated_token_address(&new_authority, &payee.usdc_mint);
+
+    transfer(&mut payee, &authority, new_authority).unwrap();
+    cancel(&mut payee, &authority).unwrap();
+
+    assert!(matches!(
+        accept(&mut payee, &new_authority, &new_treasury_ata),
+        Err(RecurringPaymentError::NoPendingTransfer)
+    ));
+    assert!(matches!(
+        cancel(&mut payee, &authority),
+        Err(RecurringPaymentError::NoPendingTransfer)
+    ));
+    assert_eq!(payee.authority, authority);
+}
+
+// ============================================================================
+// Authorization Tests
+// ============================================================================
+
+/// Test that only the current authority can propose or cancel a transfer
+#[test]
+fn test_only_authority_can_transfer_or_cancel() {
+    let mut payee = payee();
+    let stranger = Pubkey::new_unique();
+
+    assert!(matches!(
+        transfer(&mut payee, &stranger, stranger),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+
+    let authority = payee.authority;
+    transfer(&mut payee, &authority, Pubkey::new_unique()).unwrap();
+    assert!(matches!(
+        cancel(&mut payee, &stranger),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+}
+
+/// Test that only the pending authority can accept
+#[test]
+fn test_only_pending_authority_can_accept() {
+    let mut payee = payee();
+    let new_authority = Pubkey::new_unique();
+    let stranger = Pubkey::new_unique();
+    let authority = payee.authority;
+    transfer(&mut payee, &authority, new_authority).unwrap();
+
+    let stranger_ata = get_associated_token_address(&stranger, &payee.usdc_mint);
+    assert!(matches!(
+        accept(&mut payee, &stranger, &stranger_ata),
+        Err(RecurringPaymentError::Unauthorized)
+    ));
+    assert_eq!(payee.pending_authority, Some(new_authority));
+}
+
+/// Test that the new treasury must be the new authority's canonical ATA
+#[test]
+fn test_accept_requires_new_authority_ata() {
+    let mut payee = payee();
+    let old_treasury_ata = payee.treasury_ata;
+    let new_authority = Pubkey::new_unique();
+    let authority = payee.authority;
+    transfer(&mut payee, &authority, new_authority).unwrap();
+
+    assert!(matches!(
+        accept(&mut payee, &new_authority, &old_treasury_ata),
+        Err(RecurringPaymentError::BadSeeds)
+    ));
+    assert!(matches!(
+        accept(&mut payee, &new_authority, &Pubkey::new_unique()),
+        Err(RecurringPaymentError::BadSeeds)
+    ));
+}
+
+/// Test that pending transfers cannot be overwritten or target the current authority
+#[test]
+fn test_transfer_target_validation() {
+    let mut payee = payee();
+    let authority = payee.authority;
+
+    assert!(matches!(
+        transfer(&mut payee, &authority, authority),
+        Err(RecurringPaymentError::InvalidTransferTarget)
+    ));
+
+    let first = Pubkey::new_unique();
+    transfer(&mut payee, &authority, first).unwrap();
+    assert!(matches!(
+        transfer(&mut payee, &authority, Pubkey::new_unique()),
+        Err(RecurringPaymentError::TransferAlreadyPending)
+    ));
+    assert_eq!(payee.pending_authority, Some(first));
+}
+
+// ============================================================================
+// Account Size
+// ============================================================================
+
+/// Test that the account size covers the transfer fields
+#[test]
+fn test_payee_space() {
+    use anchor_lang::AnchorSerialize;
+    use tally_protocol::constants::MAX_AUTHORIZED_KEEPERS;
+
+    let mut payee = payee();
+    payee.pending_authority = Some(Pubkey::new_unique());
+    payee.fee_holiday = Some(FeeHoliday {
+        until_ts: 1_700_000_000,
+        rebate_bps: 10,
+    });
+    payee.authorized_keepers = (0..MAX_AUTHORIZED_KEEPERS).map(|_| Pubkey::new_unique()).collect();
+
+    let serialized_len = payee.try_to_vec().unwrap().len();
+    assert_eq!(Payee::SPACE, 385);
+    assert_eq!(serialized_len + 8, Payee::SPACE);
+}
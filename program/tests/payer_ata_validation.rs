@@ -0,0 +1,134 @@
+//! Unit tests for the payer token account checks in `execute_payment`
+//!
+//! This test suite validates how `execute_payment` rejects payer token accounts
+//! supplied by a keeper through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Accounts not owned by the token program are rejected before deserialization
+//! - Token accounts of another wallet fail with `WrongOwner` (6048)
+//! - Token accounts of another mint fail with `WrongMint` (6003)
+//! - The owner is checked before the mint
+//!
+//! Security Context:
+//! Keepers choose the accounts of `execute_payment`, so a hostile keeper can pass any
+//! account as the payer's token account. Each check fails with a precise error code
+//! instead of the generic `Unauthorized`:
+//! ```rust
+//! if subscriber_ata_data.owner != payment_agreement.payer {
+//!     return Err(RecurringPaymentError::WrongOwner.into());
+//! }
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+
+/// Payer token account as seen by `execute_payment`
+struct PayerAta {
+    program_owner: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+}
+
+/// Simulate the payer token account checks of `execute_payment.rs`
+fn validate_payer_ata(
+    ata: &PayerAta,
+    token_program: &Pubkey,
+    payer: &Pubkey,
+    usdc_mint: &Pubkey,
+) -> Result<(), RecurringPaymentError> {
+    if ata.program_owner != *token_program {
+        return Err(RecurringPaymentError::InvalidPayerTokenAccount);
+    }
+    if ata.owner != *payer {
+        return Err(RecurringPaymentError::WrongOwner);
+    }
+    if ata.mint != *usdc_mint {
+        return Err(RecurringPaymentError::WrongMint);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Test that each substituted field fails with its own error
+#[test]
+fn test_rejects_substituted_accounts() {
+    let token_program = anchor_spl::token::ID;
+    let payer = Pubkey::new_unique();
+    let usdc_mint = Pubkey::new_unique();
+    let valid = || PayerAta {
+        program_owner: token_program,
+        owner: payer,
+        mint: usdc_mint,
+    };
+
+    assert!(validate_payer_ata(&valid(), &token_program, &payer, &usdc_mint).is_ok());
+
+    let fake = PayerAta {
+        program_owner: Pubkey::new_unique(),
+        ..valid()
+    };
+    assert!(matches!(
+        validate_payer_ata(&fake, &token_program, &payer, &usdc_mint),
+        Err(RecurringPaymentError::InvalidPayerTokenAccount)
+    ));
+
+    let other_wallet = PayerAta {
+        owner: Pubkey::new_unique(),
+        ..valid()
+    };
+    assert!(matches!(
+        validate_payer_ata(&other_wallet, &token_program, &payer, &usdc_mint),
+        Err(RecurringPaymentError::WrongOwner)
+    ));
+
+    let other_mint = PayerAta {
+        mint: Pubkey::new_unique(),
+        ..valid()
+    };
+    assert!(matches!(
+        validate_payer_ata(&other_mint, &token_program, &payer, &usdc_mint),
+        Err(RecurringPaymentError::WrongMint)
+    ));
+}
+
+/// Test that an account wrong on both owner and mint reports the owner
+#[test]
+fn test_owner_checked_before_mint() {
+    let token_program = anchor_spl::token::ID;
+    let ata = PayerAta {
+        program_owner: token_program,
+        owner: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+    };
+
+    assert!(matches!(
+        validate_payer_ata(
+            &ata,
+            &token_program,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique()
+        ),
+        Err(RecurringPaymentError::WrongOwner)
+    ));
+}
+
+// ============================================================================
+// Error Codes
+// ============================================================================
+
+/// Test the error codes clients decode
+#[test]
+fn test_error_codes() {
+    assert_eq!(common::error_code(RecurringPaymentError::WrongMint), 6003);
+    assert_eq!(
+        common::error_code(RecurringPaymentError::InvalidPayerTokenAccount),
+        6011
+    );
+    assert_eq!(common::error_code(RecurringPaymentError::WrongOwner), 6048);
+}
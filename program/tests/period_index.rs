@@ -0,0 +1,128 @@
+//! Unit tests for the renewal counter `PaymentAgreement::period_index`
+//!
+//! This test suite validates how `execute_payment`, `start_agreement` and
+//! `accept_agreement_transfer` maintain `period_index` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - New agreements start at zero and every renewal increments the counter by one
+//! - Renewals landing in the same second still get distinct indices
+//! - Pausing, reactivating and transferring never move the counter backwards
+//! - The counter fails instead of overflowing
+//!
+//! Business Context:
+//! Off-chain reconciliation keyed renewals on timestamps, which collide when retries
+//! land in the same second. Every renewal now carries a counter unique per agreement
+//! in `PaymentExecuted` and in keeper receipt memos:
+//! ```rust
+//! payment_agreement.period_index = payment_agreement
+//!     .period_index
+//!     .checked_add(1)
+//!     .ok_or(RecurringPaymentError::ArithmeticError)?;
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const START: i64 = 1_700_000_000;
+
+fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: START,
+        payment_count: 0,
+        created_ts: START,
+        last_payment_ts: START,
+        ..common::agreement()
+    }
+}
+
+/// Simulate the counter update of `execute_payment.rs`, returning the emitted index
+fn execute(
+    agreement: &mut PaymentAgreement,
+    now: i64,
+) -> Result<(i64, u64), RecurringPaymentError> {
+    agreement.period_index = agreement
+        .period_index
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    agreement.last_payment_ts = now;
+    Ok((agreement.last_payment_ts, agreement.period_index))
+}
+
+/// Simulate a reactivation by `start_agreement.rs`, which keeps the counter
+const fn reactivate(agreement: &mut PaymentAgreement) {
+    agreement.active = true;
+    agreement.periods_paid = 1;
+}
+
+/// Simulate `accept_agreement_transfer.rs` copying the agreement to a new wallet
+fn transfer(agreement: &PaymentAgreement) -> PaymentAgreement {
+    PaymentAgreement {
+        payer: Pubkey::new_unique(),
+        ..agreement.clone()
+    }
+}
+
+// ============================================================================
+// Renewals
+// ============================================================================
+
+/// Test that every renewal advances the counter by exactly one
+#[test]
+fn test_renewals_increment_counter() {
+    let mut agreement = agreement();
+    assert_eq!(agreement.period_index, 0);
+
+    for expected in 1..=3 {
+        let (_, index) = execute(&mut agreement, START).unwrap();
+        assert_eq!(index, expected);
+    }
+}
+
+/// Test that renewals in the same second collide on timestamp but not on index
+#[test]
+fn test_same_second_renewals_are_distinct() {
+    let mut agreement = agreement();
+
+    let first = execute(&mut agreement, START).unwrap();
+    let retry = execute(&mut agreement, START).unwrap();
+
+    assert_eq!(first.0, retry.0, "Timestamps collide");
+    assert_ne!(first.1, retry.1, "Indices stay unique");
+}
+
+/// Test that the counter fails instead of wrapping around
+#[test]
+fn test_counter_overflow() {
+    let mut agreement = agreement();
+    agreement.period_index = u64::MAX;
+
+    assert!(matches!(
+        execute(&mut agreement, START),
+        Err(RecurringPaymentError::ArithmeticError)
+    ));
+}
+
+// ============================================================================
+// Lifecycle
+// ============================================================================
+
+/// Test that pausing, reactivating and transferring keep the counter
+#[test]
+fn test_counter_survives_lifecycle() {
+    let mut agreement = agreement();
+    execute(&mut agreement, START).unwrap();
+    execute(&mut agreement, START).unwrap();
+
+    agreement.active = false;
+    reactivate(&mut agreement);
+    assert_eq!(agreement.period_index, 2);
+
+    let mut transferred = transfer(&agreement);
+    assert_eq!(transferred.period_index, 2);
+    let (_, index) = execute(&mut transferred, START).unwrap();
+    assert_eq!(index, 3, "Indices continue after a transfer");
+}
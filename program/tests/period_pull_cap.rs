@@ -0,0 +1,175 @@
+//! Unit tests for the per-period pull cap in `execute_payment`
+//!
+//! This test suite validates the defense-in-depth pull cap through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Billing periods are indexed by their due date relative to `created_ts`
+//! - A pull records its index and a second pull for the same period is rejected,
+//!   whatever `last_payment_ts` says
+//! - Late pulls and terms updates changing the period never block the next period
+//! - Reactivation records the period falling due at reactivation
+//! - Arithmetic edge cases (due date before creation)
+//! - Error code for exceeding the pull cap
+//!
+//! Security Context:
+//! A delegate approval could in theory be pulled more than once within a period by
+//! racing keepers. The timing checks measure from `last_payment_ts`; the pull cap
+//! instead records the index of every pulled billing period on the agreement
+//! (`last_pull_period_index`), anchored at `created_ts`, and rejects any pull that
+//! does not advance it:
+//! ```rust
+//! let pull_period_index = payment_agreement.next_pull_period_index()?;
+//! // ... after the transfers
+//! payment_agreement.last_pull_period_index = pull_period_index;
+//! ```
+
+mod common;
+
+use common::{NOW, THIRTY_DAYS};
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const PERIOD: i64 = 2_592_000;
+
+fn pull_error(agreement: &PaymentAgreement) -> u32 {
+    common::anchor_error_code(agreement.next_pull_period_index().unwrap_err())
+}
+
+/// Simulate the agreement updates of a successful `execute_payment.rs` at `now`
+fn pull(agreement: &mut PaymentAgreement, now: i64, period: i64) {
+    let index = agreement.next_pull_period_index().unwrap();
+    agreement.last_pull_period_index = index;
+    agreement.last_payment_ts = now;
+    agreement.next_payment_ts = agreement.next_payment_ts.checked_add(period).unwrap();
+}
+
+// ============================================================================
+// Period Index Tests
+// ============================================================================
+
+/// Test that a period's index is its due date's offset from creation
+#[test]
+fn test_period_index_anchored_at_creation() {
+    let agreement = common::agreement();
+
+    assert_eq!(agreement.pull_period_index(NOW), Some(0));
+    assert_eq!(agreement.pull_period_index(NOW + PERIOD), Some(THIRTY_DAYS));
+    assert_eq!(
+        agreement.next_pull_period_index().unwrap(),
+        THIRTY_DAYS,
+        "The first renewal falls due one period after creation"
+    );
+}
+
+/// Test that the index ignores when earlier payments were executed
+#[test]
+fn test_period_index_independent_of_last_payment() {
+    let mut agreement = common::agreement();
+    let index = agreement.next_pull_period_index().unwrap();
+
+    agreement.last_payment_ts = NOW + PERIOD + 86_400;
+    assert_eq!(agreement.next_pull_period_index().unwrap(), index);
+}
+
+/// Test that a due date before creation returns `None` instead of panicking
+#[test]
+fn test_period_index_before_creation() {
+    let agreement = common::agreement();
+
+    assert_eq!(agreement.pull_period_index(NOW - 1), None);
+
+    let corrupted = PaymentAgreement {
+        next_payment_ts: NOW - 1,
+        ..common::agreement()
+    };
+    assert_eq!(
+        pull_error(&corrupted),
+        common::error_code(RecurringPaymentError::ArithmeticError)
+    );
+}
+
+// ============================================================================
+// Pull Cap Enforcement Tests
+// ============================================================================
+
+/// Test that consecutive renewals each pull a new period
+#[test]
+fn test_consecutive_renewals_accepted() {
+    let mut agreement = common::agreement();
+
+    for renewal in 1..=12 {
+        pull(&mut agreement, NOW + PERIOD * renewal, PERIOD);
+    }
+    assert_eq!(agreement.last_pull_period_index, THIRTY_DAYS * 12);
+}
+
+/// Test that a second pull of the same period is rejected even when the timing
+/// state says a full period has passed
+#[test]
+fn test_second_pull_same_period_rejected() {
+    let mut agreement = common::agreement();
+    pull(&mut agreement, NOW + PERIOD, PERIOD);
+
+    // A schedule that failed to advance, with a stale last payment timestamp
+    agreement.next_payment_ts = agreement.next_payment_ts.checked_sub(PERIOD).unwrap();
+    agreement.last_payment_ts = NOW;
+
+    assert_eq!(
+        pull_error(&agreement),
+        common::error_code(RecurringPaymentError::PeriodPullCapExceeded)
+    );
+}
+
+/// Test that a keeper executing late does not block the following period
+#[test]
+fn test_late_pull_does_not_block_next_period() {
+    let mut agreement = common::agreement();
+    pull(&mut agreement, NOW + PERIOD * 2 - 1, PERIOD);
+
+    assert!(agreement.next_pull_period_index().is_ok());
+}
+
+/// Test that a terms update lengthening the period never moves the index backwards
+#[test]
+fn test_longer_period_after_terms_update() {
+    let mut agreement = common::agreement();
+    pull(&mut agreement, NOW + PERIOD, PERIOD);
+
+    // The update applies at the next renewal, which schedules the longer period
+    let longer = PERIOD * 12;
+    pull(&mut agreement, NOW + PERIOD * 2, longer);
+    pull(&mut agreement, NOW + PERIOD * 2 + longer, longer);
+
+    assert_eq!(
+        agreement.last_pull_period_index,
+        u64::try_from(PERIOD * 2 + longer).unwrap()
+    );
+}
+
+/// Test that reactivation records the period falling due at reactivation
+#[test]
+fn test_reactivation_records_current_period() {
+    let mut agreement = common::agreement();
+    pull(&mut agreement, NOW + PERIOD, PERIOD);
+
+    // Simulate the reactivation branch of `start_agreement.rs`
+    let reactivated_at = NOW + PERIOD * 5;
+    agreement.last_pull_period_index = agreement
+        .pull_period_index(reactivated_at)
+        .unwrap()
+        .max(agreement.last_pull_period_index);
+    agreement.next_payment_ts = reactivated_at + PERIOD;
+
+    assert_eq!(
+        agreement.last_pull_period_index,
+        u64::try_from(PERIOD * 5).unwrap()
+    );
+    assert!(agreement.next_pull_period_index().is_ok());
+}
+
+/// Test that exceeding the pull cap maps to the dedicated error code
+#[test]
+fn test_period_pull_cap_error_code() {
+    assert_eq!(common::error_code(RecurringPaymentError::PeriodPullCapExceeded), 6030);
+}
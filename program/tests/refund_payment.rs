@@ -0,0 +1,183 @@
+//! Unit tests for the `refund_payment` instruction
+//!
+//! This test suite validates payee-initiated refunds through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Partial refunds accumulate on the agreement up to the last charged amount
+//! - Refunds beyond the last charged amount are rejected
+//! - Zero-amount refunds are rejected
+//! - A new charge resets the refund allowance
+//! - The event carries the refunded payment's `PaymentExecuted` period index
+//! - Only the payee authority may refund
+//! - Refunds require sufficient treasury balance
+//!
+//! Business Context:
+//! Chargeback-like flows used to happen out-of-band as plain token transfers, which
+//! analytics could not attribute to an agreement. `refund_payment` moves the funds from
+//! the payee treasury via the program, records the running total on the agreement and
+//! emits `Refunded { amount, period_index }`:
+//! ```rust
+//! let total_refunded = payment_agreement.refunded_amount.checked_add(args.amount)?;
+//! require!(
+//!     total_refunded <= payment_agreement.last_amount,
+//!     RecurringPaymentError::RefundExceedsLastPayment
+//! );
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: i64 = 2_592_000;
+const LAST_PAYMENT: i64 = 1_700_000_000;
+
+fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: LAST_PAYMENT.checked_add(THIRTY_DAYS).unwrap(),
+        payment_count: 3,
+        created_ts: LAST_PAYMENT,
+        last_payment_ts: LAST_PAYMENT,
+        period_index: 2,
+        ..common::agreement()
+    }
+}
+
+/// Simulate `refund_payment.rs`, returning the emitted `(amount, period_index)`
+fn refund_payment(
+    agreement: &mut PaymentAgreement,
+    signer: Pubkey,
+    payee_authority: Pubkey,
+    treasury_balance: u64,
+    amount: u64,
+) -> Result<(u64, u64), RecurringPaymentError> {
+    if signer != payee_authority {
+        return Err(RecurringPaymentError::Unauthorized);
+    }
+    if amount == 0 {
+        return Err(RecurringPaymentError::InvalidAmount);
+    }
+    let total_refunded = agreement
+        .refunded_amount
+        .checked_add(amount)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    if total_refunded > agreement.last_amount {
+        return Err(RecurringPaymentError::RefundExceedsLastPayment);
+    }
+    if treasury_balance < amount {
+        return Err(RecurringPaymentError::InsufficientFunds);
+    }
+
+    agreement.refunded_amount = total_refunded;
+    Ok((amount, agreement.period_index))
+}
+
+/// Simulate the agreement updates of a successful `execute_payment.rs` charge
+const fn charge(agreement: &mut PaymentAgreement, amount: u64) {
+    agreement.payment_count = agreement.payment_count.checked_add(1).unwrap();
+    agreement.last_amount = amount;
+    agreement.period_index = agreement.period_index.checked_add(1).unwrap();
+    agreement.refunded_amount = 0;
+}
+
+// ============================================================================
+// Refund Allowance
+// ============================================================================
+
+/// Test that partial refunds accumulate and report the refunded period
+#[test]
+fn test_partial_refunds_accumulate() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = agreement();
+
+    let event = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 3 * ONE_USDC);
+    assert!(matches!(event, Ok((amount, 2)) if amount == 3 * ONE_USDC));
+    assert_eq!(agreement.refunded_amount, 3 * ONE_USDC);
+
+    let event = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 7 * ONE_USDC);
+    assert!(matches!(event, Ok((amount, 2)) if amount == 7 * ONE_USDC));
+    assert_eq!(agreement.refunded_amount, agreement.last_amount);
+}
+
+/// Test that total refunds cannot exceed the last charged amount
+#[test]
+fn test_refund_above_last_payment_rejected() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = agreement();
+
+    let result = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 11 * ONE_USDC);
+    assert!(matches!(result, Err(RecurringPaymentError::RefundExceedsLastPayment)));
+
+    refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 6 * ONE_USDC).unwrap();
+    let result = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 5 * ONE_USDC);
+    assert!(matches!(result, Err(RecurringPaymentError::RefundExceedsLastPayment)));
+    assert_eq!(agreement.refunded_amount, 6 * ONE_USDC, "Rejected refund must not be recorded");
+}
+
+/// Test that zero-amount refunds are rejected
+#[test]
+fn test_zero_refund_rejected() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = agreement();
+
+    let result = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 0);
+    assert!(matches!(result, Err(RecurringPaymentError::InvalidAmount)));
+}
+
+/// Test that a new charge resets the refund allowance to the new amount
+#[test]
+fn test_new_charge_resets_refund_allowance() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = agreement();
+
+    refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 10 * ONE_USDC).unwrap();
+    charge(&mut agreement, 12 * ONE_USDC);
+    assert_eq!(agreement.refunded_amount, 0);
+
+    let event = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, 12 * ONE_USDC);
+    assert!(matches!(event, Ok((amount, 3)) if amount == 12 * ONE_USDC));
+}
+
+/// Test that the refund reports the renewal counter of `PaymentExecuted`, not the
+/// pull cap's period offset
+#[test]
+fn test_refund_reports_payment_period_index() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = PaymentAgreement {
+        last_pull_period_index: 2_592_000,
+        ..agreement()
+    };
+
+    let event = refund_payment(&mut agreement, authority, authority, 100 * ONE_USDC, ONE_USDC);
+    assert!(matches!(event, Ok((_, 2))));
+}
+
+// ============================================================================
+// Authorization and Funds
+// ============================================================================
+
+/// Test that only the payee authority can refund
+#[test]
+fn test_refund_requires_payee_authority() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = agreement();
+    let payer = agreement.payer;
+
+    let result = refund_payment(&mut agreement, payer, authority, 100 * ONE_USDC, ONE_USDC);
+    assert!(matches!(result, Err(RecurringPaymentError::Unauthorized)));
+    assert_eq!(agreement.refunded_amount, 0);
+}
+
+/// Test that refunds require sufficient treasury balance
+#[test]
+fn test_refund_requires_treasury_balance() {
+    let authority = Pubkey::new_unique();
+    let mut agreement = agreement();
+
+    let result = refund_payment(&mut agreement, authority, authority, ONE_USDC, 2 * ONE_USDC);
+    assert!(matches!(result, Err(RecurringPaymentError::InsufficientFunds)));
+    assert_eq!(agreement.refunded_amount, 0);
+}
@@ -0,0 +1,260 @@
+//! Unit tests for renewal queue crank index accounts
+//!
+//! This test suite validates the `RenewalQueue` bucketing used by `start_agreement`
+//! and `execute_payment` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Timestamps map to day-sized buckets
+//! - Pre-epoch timestamps have no bucket
+//! - Starting an agreement indexes it under its next payment's bucket
+//! - A mismatched renewal bucket is rejected
+//! - Executing a payment moves the agreement to the following bucket
+//! - Agreements are spread across the shards of a bucket by address
+//! - Accepting a transfer moves the entry to the new agreement address
+//! - Inserting an already listed agreement is a no-op
+//! - Full queues count dropped insertions in `overflow_count`
+//! - `RenewalQueue::SPACE` reserves room for a full queue
+//!
+//! Business Context:
+//! Keepers previously had to scan every payment agreement to find due payments.
+//! Each agreement is now listed in one of the `RENEWAL_QUEUE_SHARDS` queues for the
+//! day its next payment falls due, so a keeper reads a few small accounts per day:
+//! ```rust
+//! require!(
+//!     RenewalQueue::bucket_for(next_renewal_ts) == Some(args.renewal_bucket),
+//!     RecurringPaymentError::InvalidRenewalBucket
+//! );
+//! renewal_queue.insert(payment_agreement.key());
+//! ```
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::constants::{
+    MAX_RENEWAL_QUEUE_ENTRIES, RENEWAL_BUCKET_SECS, RENEWAL_QUEUE_SHARDS,
+};
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::RenewalQueue;
+
+const NOW: i64 = 1_700_000_000;
+const MONTH: i64 = 2_592_000;
+
+/// Empty queue as created by `init_if_needed`
+const fn queue(bucket: u64) -> RenewalQueue {
+    RenewalQueue {
+        bucket,
+        shard: 0,
+        agreements: Vec::new(),
+        overflow_count: 0,
+        bump: 255,
+    }
+}
+
+/// Simulate the renewal queue step of `start_agreement.rs`
+fn start_agreement(
+    queue: &mut RenewalQueue,
+    agreement: Pubkey,
+    next_renewal_ts: i64,
+    renewal_bucket: u64,
+) -> Result<(), RecurringPaymentError> {
+    if RenewalQueue::bucket_for(next_renewal_ts) != Some(renewal_bucket) {
+        return Err(RecurringPaymentError::InvalidRenewalBucket);
+    }
+    queue.insert(agreement);
+    Ok(())
+}
+
+/// Simulate the renewal queue steps of `execute_payment.rs`
+fn execute_payment(
+    current: &mut RenewalQueue,
+    next: &mut RenewalQueue,
+    agreement: Pubkey,
+    next_payment_ts: i64,
+) -> Result<(), RecurringPaymentError> {
+    if RenewalQueue::bucket_for(next_payment_ts) != Some(next.bucket) {
+        return Err(RecurringPaymentError::InvalidRenewalBucket);
+    }
+    current.remove(&agreement);
+    next.insert(agreement);
+    Ok(())
+}
+
+// ============================================================================
+// Bucket Math
+// ============================================================================
+
+/// Test that timestamps map to day-sized buckets
+#[test]
+fn test_bucket_for_groups_by_day() {
+    let day = RenewalQueue::bucket_for(NOW).unwrap();
+    let start_of_day = i64::try_from(day).unwrap() * RENEWAL_BUCKET_SECS;
+
+    assert_eq!(RenewalQueue::bucket_for(start_of_day), Some(day));
+    assert_eq!(RenewalQueue::bucket_for(start_of_day + RENEWAL_BUCKET_SECS - 1), Some(day));
+    assert_eq!(RenewalQueue::bucket_for(start_of_day + RENEWAL_BUCKET_SECS), Some(day + 1));
+}
+
+/// Test that pre-epoch timestamps have no bucket
+#[test]
+fn test_bucket_for_rejects_pre_epoch() {
+    assert_eq!(RenewalQueue::bucket_for(-RENEWAL_BUCKET_SECS), None);
+}
+
+// ============================================================================
+// Instruction Flow
+// ============================================================================
+
+/// Test that starting an agreement indexes it under its next payment's bucket
+#[test]
+fn test_start_agreement_indexes_next_payment() {
+    let agreement = Pubkey::new_unique();
+    let bucket = RenewalQueue::bucket_for(NOW + MONTH).unwrap();
+    let mut queue = queue(bucket);
+
+    start_agreement(&mut queue, agreement, NOW + MONTH, bucket).unwrap();
+    assert_eq!(queue.agreements, vec![agreement]);
+}
+
+/// Test that a mismatched renewal bucket is rejected
+#[test]
+fn test_start_agreement_rejects_wrong_bucket() {
+    let bucket = RenewalQueue::bucket_for(NOW).unwrap();
+    let mut queue = queue(bucket);
+
+    assert!(matches!(
+        start_agreement(&mut queue, Pubkey::new_unique(), NOW + MONTH, bucket),
+        Err(RecurringPaymentError::InvalidRenewalBucket)
+    ));
+    assert!(queue.agreements.is_empty());
+}
+
+/// Test that executing a payment moves the agreement to the following bucket
+#[test]
+fn test_execute_payment_moves_agreement() {
+    let agreement = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let mut current = queue(RenewalQueue::bucket_for(NOW).unwrap());
+    current.insert(agreement);
+    current.insert(other);
+    let mut next = queue(RenewalQueue::bucket_for(NOW + MONTH).unwrap());
+
+    execute_payment(&mut current, &mut next, agreement, NOW + MONTH).unwrap();
+    assert_eq!(current.agreements, vec![other]);
+    assert_eq!(next.agreements, vec![agreement]);
+}
+
+/// Simulate the renewal queue steps of `accept_agreement_transfer.rs` with the old
+/// and new addresses in different shards
+fn accept_agreement_transfer(
+    old_queue: &mut RenewalQueue,
+    new_queue: &mut RenewalQueue,
+    old_agreement: Pubkey,
+    new_agreement: Pubkey,
+    next_payment_ts: i64,
+) -> Result<(), RecurringPaymentError> {
+    if RenewalQueue::bucket_for(next_payment_ts) != Some(new_queue.bucket) {
+        return Err(RecurringPaymentError::InvalidRenewalBucket);
+    }
+    old_queue.remove(&old_agreement);
+    new_queue.insert(new_agreement);
+    Ok(())
+}
+
+/// Test that accepting a transfer moves the entry to the new agreement address
+#[test]
+fn test_accept_transfer_moves_entry() {
+    let old_agreement = Pubkey::new_unique();
+    let new_agreement = Pubkey::new_unique();
+    let bucket = RenewalQueue::bucket_for(NOW + MONTH).unwrap();
+    let mut old_queue = queue(bucket);
+    old_queue.insert(old_agreement);
+    let mut new_queue = queue(bucket);
+
+    accept_agreement_transfer(
+        &mut old_queue,
+        &mut new_queue,
+        old_agreement,
+        new_agreement,
+        NOW + MONTH,
+    )
+    .unwrap();
+    assert!(old_queue.agreements.is_empty());
+    assert_eq!(new_queue.agreements, vec![new_agreement]);
+
+    assert!(matches!(
+        accept_agreement_transfer(&mut old_queue, &mut new_queue, old_agreement, new_agreement, NOW),
+        Err(RecurringPaymentError::InvalidRenewalBucket)
+    ));
+}
+
+// ============================================================================
+// Sharding
+// ============================================================================
+
+/// Test that the shard is taken from the agreement address
+#[test]
+fn test_shard_for_uses_address() {
+    let mut bytes = [0u8; 32];
+    bytes[0] = RENEWAL_QUEUE_SHARDS + 3;
+    let agreement = Pubkey::new_from_array(bytes);
+
+    assert_eq!(RenewalQueue::shard_for(&agreement), 3);
+    assert_eq!(RenewalQueue::shard_seed(&agreement), [3]);
+}
+
+/// Test that agreements are spread across every shard of a bucket
+#[test]
+fn test_agreements_spread_across_shards() {
+    let mut counts = vec![0usize; usize::from(RENEWAL_QUEUE_SHARDS)];
+    for first_byte in 0..=u8::MAX {
+        let mut bytes = [0u8; 32];
+        bytes[0] = first_byte;
+        let shard = RenewalQueue::shard_for(&Pubkey::new_from_array(bytes));
+        assert!(shard < RENEWAL_QUEUE_SHARDS);
+        counts[usize::from(shard)] += 1;
+    }
+
+    assert!(counts.iter().all(|count| *count == 256 / usize::from(RENEWAL_QUEUE_SHARDS)));
+}
+
+// ============================================================================
+// Queue Bounds
+// ============================================================================
+
+/// Test that inserting an already listed agreement is a no-op
+#[test]
+fn test_insert_is_idempotent() {
+    let agreement = Pubkey::new_unique();
+    let mut queue = queue(0);
+
+    assert!(queue.insert(agreement));
+    assert!(queue.insert(agreement));
+    assert_eq!(queue.agreements.len(), 1);
+    assert!(queue.remove(&agreement));
+    assert!(!queue.remove(&agreement));
+}
+
+/// Test that full queues count dropped insertions
+#[test]
+fn test_full_queue_counts_overflow() {
+    let mut queue = queue(0);
+    for _ in 0..MAX_RENEWAL_QUEUE_ENTRIES {
+        assert!(queue.insert(Pubkey::new_unique()));
+    }
+
+    assert!(!queue.insert(Pubkey::new_unique()));
+    assert_eq!(queue.agreements.len(), MAX_RENEWAL_QUEUE_ENTRIES);
+    assert_eq!(queue.overflow_count, 1);
+}
+
+/// Test that the account size reserves room for a full queue
+#[test]
+fn test_renewal_queue_space_fits_full_queue() {
+    use anchor_lang::AnchorSerialize;
+
+    let mut queue = queue(u64::MAX);
+    queue.agreements = (0..MAX_RENEWAL_QUEUE_ENTRIES).map(|_| Pubkey::new_unique()).collect();
+
+    let serialized_len = queue.try_to_vec().unwrap().len();
+    assert_eq!(RenewalQueue::SPACE, 2074);
+    assert_eq!(serialized_len + 8, RenewalQueue::SPACE);
+}
@@ -0,0 +1,173 @@
+//! Unit tests for the clock-skew tolerant renewal window
+//!
+//! This test suite validates `Config::renewal_tolerance_secs` and its use in the due
+//! checks of `execute_payment` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Without a tolerance, renewals are due exactly at `next_payment_ts`
+//! - A tolerance opens the renewal window early by that many seconds, and no earlier
+//! - The double-renewal check uses the same shifted time, and early renewals pass
+//!   the per-period pull cap
+//! - `update_config` bounds the tolerance by `MAX_RENEWAL_TOLERANCE_SECS`
+//! - The account size grows to 237 bytes
+//!
+//! Keeper Context:
+//! The cluster clock can lag wall time, so keepers submitting exactly at the due time
+//! intermittently failed with `NotDue`. Due checks now compare the shifted time:
+//! ```rust
+//! let renewal_check_time = ctx.accounts.config.renewal_check_time(current_time);
+//! if renewal_check_time < payment_agreement.next_payment_ts {
+//!     return Err(RecurringPaymentError::NotDue.into());
+//! }
+//! ```
+//! The rejection logs when the window opens so skew is visible in transaction logs.
+
+mod common;
+
+use tally_protocol::constants::MAX_RENEWAL_TOLERANCE_SECS;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{Config, PaymentAgreement};
+
+const START: i64 = 1_700_000_000;
+const PERIOD_SECS: u64 = 2_592_000;
+const PERIOD: i64 = 2_592_000;
+
+fn config(renewal_tolerance_secs: u64) -> Config {
+    Config {
+        renewal_tolerance_secs,
+        ..common::config()
+    }
+}
+
+fn create_agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: START + PERIOD,
+        created_ts: START,
+        last_payment_ts: START,
+        ..common::agreement()
+    }
+}
+
+/// Simulate the timing checks of `execute_payment.rs`
+fn check_due(
+    config: &Config,
+    agreement: &PaymentAgreement,
+    current_time: i64,
+) -> Result<(), RecurringPaymentError> {
+    let renewal_check_time = config.renewal_check_time(current_time);
+    if renewal_check_time < agreement.next_payment_ts {
+        return Err(RecurringPaymentError::NotDue);
+    }
+    let min_next_renewal_time = agreement
+        .last_payment_ts
+        .checked_add(PERIOD)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    if renewal_check_time < min_next_renewal_time {
+        return Err(RecurringPaymentError::NotDue);
+    }
+    let pull_period_index = agreement
+        .pull_period_index(agreement.next_payment_ts)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    if pull_period_index <= agreement.last_pull_period_index {
+        return Err(RecurringPaymentError::PeriodPullCapExceeded);
+    }
+    Ok(())
+}
+
+/// Simulate the tolerance validation of `update_config.rs`
+const fn update_tolerance(
+    config: &mut Config,
+    tolerance: u64,
+) -> Result<(), RecurringPaymentError> {
+    if tolerance > MAX_RENEWAL_TOLERANCE_SECS {
+        return Err(RecurringPaymentError::InvalidConfiguration);
+    }
+    config.renewal_tolerance_secs = tolerance;
+    Ok(())
+}
+
+// ============================================================================
+// Due Checks
+// ============================================================================
+
+/// Test that renewals are due exactly at `next_payment_ts` without a tolerance
+#[test]
+fn test_no_tolerance_due_at_next_payment() {
+    let config = config(0);
+    let agreement = create_agreement();
+
+    assert!(matches!(
+        check_due(&config, &agreement, agreement.next_payment_ts - 1),
+        Err(RecurringPaymentError::NotDue)
+    ));
+    assert!(check_due(&config, &agreement, agreement.next_payment_ts).is_ok());
+}
+
+/// Test that a tolerance opens the renewal window early, and no earlier
+#[test]
+fn test_tolerance_opens_window_early() {
+    let config = config(30);
+    let agreement = create_agreement();
+
+    assert!(check_due(&config, &agreement, agreement.next_payment_ts - 30).is_ok());
+    assert!(matches!(
+        check_due(&config, &agreement, agreement.next_payment_ts - 31),
+        Err(RecurringPaymentError::NotDue)
+    ));
+}
+
+/// Test that an early renewal still counts as the next billing period
+#[test]
+fn test_early_renewal_passes_pull_cap() {
+    let config = config(MAX_RENEWAL_TOLERANCE_SECS);
+    let mut agreement = create_agreement();
+    let early = agreement.next_payment_ts - 3_600;
+    check_due(&config, &agreement, early).unwrap();
+
+    // Record the renewal as `execute_payment` does; the schedule does not drift
+    agreement.last_pull_period_index = agreement
+        .pull_period_index(agreement.next_payment_ts)
+        .unwrap();
+    agreement.last_payment_ts = early;
+    agreement.next_payment_ts += PERIOD;
+
+    assert_eq!(agreement.last_pull_period_index, PERIOD_SECS);
+    assert!(matches!(
+        check_due(&config, &agreement, early + 1),
+        Err(RecurringPaymentError::NotDue)
+    ));
+    assert!(check_due(&config, &agreement, agreement.next_payment_ts - 3_600).is_ok());
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Test that `update_config` bounds the tolerance
+#[test]
+fn test_update_tolerance_bounds() {
+    let mut config = config(0);
+
+    update_tolerance(&mut config, MAX_RENEWAL_TOLERANCE_SECS).unwrap();
+    assert_eq!(config.renewal_tolerance_secs, 3_600);
+    assert!(matches!(
+        update_tolerance(&mut config, MAX_RENEWAL_TOLERANCE_SECS + 1),
+        Err(RecurringPaymentError::InvalidConfiguration)
+    ));
+
+    update_tolerance(&mut config, 0).unwrap();
+    assert_eq!(config.renewal_check_time(START), START);
+}
+
+/// Test that an out-of-range stored tolerance cannot overflow the check time
+#[test]
+fn test_check_time_saturates() {
+    assert_eq!(config(u64::MAX).renewal_check_time(START), i64::MAX);
+}
+
+/// Test the account size grown by the tolerance field
+#[test]
+fn test_account_size() {
+    assert_eq!(Config::SPACE, 237);
+}
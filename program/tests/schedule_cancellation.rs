@@ -0,0 +1,199 @@
+//! Unit tests for the `schedule_cancellation` instruction
+//!
+//! This test suite validates cancellation at period end through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Scheduling keeps the agreement active for the period already paid for
+//! - `execute_payment` pauses instead of charging once `next_payment_ts` passes
+//! - Payments before `next_payment_ts` are still rejected as not due
+//! - Agreements without a scheduled cancellation are charged as usual
+//! - Reactivation clears a stale scheduled cancellation
+//!
+//! Business Context:
+//! `pause_agreement` takes effect immediately, cutting off access the payer already
+//! paid for. `schedule_cancellation` sets `cancel_at_period_end` instead, and
+//! `execute_payment` pauses the agreement at the end of the paid period:
+//! ```rust
+//! if payment_agreement.cancel_at_period_end {
+//!     payment_agreement.active = false;
+//!     payment_agreement.cancel_at_period_end = false;
+//!     emit!(CanceledAtPeriodEnd { .. });
+//!     return Ok(());
+//! }
+//! ```
+
+mod common;
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentAgreement;
+
+const THIRTY_DAYS: i64 = 2_592_000;
+const LAST_PAYMENT: i64 = 1_700_000_000;
+
+/// Outcome of a simulated `execute_payment` call
+#[derive(Debug, PartialEq, Eq)]
+enum Execution {
+    Charged,
+    CanceledAtPeriodEnd,
+}
+
+fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: LAST_PAYMENT.checked_add(THIRTY_DAYS).unwrap(),
+        created_ts: LAST_PAYMENT,
+        last_payment_ts: LAST_PAYMENT,
+        ..common::agreement()
+    }
+}
+
+/// Simulate `schedule_cancellation.rs`, returning the event's `effective_ts`
+const fn schedule_cancellation(
+    agreement: &mut PaymentAgreement,
+) -> Result<i64, RecurringPaymentError> {
+    if !agreement.active {
+        return Err(RecurringPaymentError::Inactive);
+    }
+    agreement.cancel_at_period_end = true;
+    Ok(agreement.next_payment_ts)
+}
+
+/// Simulate the timing and cancellation checks at the start of `execute_payment.rs`
+const fn execute_payment(
+    agreement: &mut PaymentAgreement,
+    now: i64,
+) -> Result<Execution, RecurringPaymentError> {
+    if !agreement.active {
+        return Err(RecurringPaymentError::Inactive);
+    }
+    if now < agreement.next_payment_ts {
+        return Err(RecurringPaymentError::NotDue);
+    }
+    if agreement.cancel_at_period_end {
+        agreement.active = false;
+        agreement.cancel_at_period_end = false;
+        return Ok(Execution::CanceledAtPeriodEnd);
+    }
+    Ok(Execution::Charged)
+}
+
+// ============================================================================
+// Scheduling Tests
+// ============================================================================
+
+/// Test that scheduling keeps the agreement active until the end of the paid period
+#[test]
+fn test_schedule_keeps_agreement_active() {
+    let mut agreement = agreement();
+
+    let effective_ts = schedule_cancellation(&mut agreement).unwrap();
+
+    assert!(agreement.active);
+    assert!(agreement.cancel_at_period_end);
+    assert_eq!(effective_ts, agreement.next_payment_ts);
+}
+
+/// Test that scheduling twice is idempotent
+#[test]
+fn test_schedule_is_idempotent() {
+    let mut agreement = agreement();
+
+    schedule_cancellation(&mut agreement).unwrap();
+    schedule_cancellation(&mut agreement).unwrap();
+
+    assert!(agreement.active);
+    assert!(agreement.cancel_at_period_end);
+}
+
+/// Test that a paused agreement cannot schedule cancellation
+#[test]
+fn test_schedule_rejected_when_paused() {
+    let mut agreement = agreement();
+    agreement.active = false;
+
+    let result = schedule_cancellation(&mut agreement);
+    assert!(matches!(result, Err(RecurringPaymentError::Inactive)));
+}
+
+// ============================================================================
+// Period End Tests
+// ============================================================================
+
+/// Test that the agreement is paused without a charge once the paid period ends
+#[test]
+fn test_execute_after_period_end_pauses() {
+    let mut agreement = agreement();
+    schedule_cancellation(&mut agreement).unwrap();
+    let now = agreement.next_payment_ts;
+
+    assert_eq!(
+        execute_payment(&mut agreement, now).unwrap(),
+        Execution::CanceledAtPeriodEnd
+    );
+    assert!(!agreement.active);
+    assert!(!agreement.cancel_at_period_end);
+    assert_eq!(agreement.payment_count, 1);
+    assert_eq!(agreement.last_payment_ts, LAST_PAYMENT);
+}
+
+/// Test that the paid period is honored: execution before period end is still not due
+#[test]
+fn test_execute_before_period_end_not_due() {
+    let mut agreement = agreement();
+    schedule_cancellation(&mut agreement).unwrap();
+    let now = agreement.next_payment_ts.checked_sub(1).unwrap();
+
+    let result = execute_payment(&mut agreement, now);
+    assert!(matches!(result, Err(RecurringPaymentError::NotDue)));
+    assert!(agreement.active);
+    assert!(agreement.cancel_at_period_end);
+}
+
+/// Test that a canceled agreement rejects further executions
+#[test]
+fn test_execute_after_cancellation_inactive() {
+    let mut agreement = agreement();
+    schedule_cancellation(&mut agreement).unwrap();
+    let now = agreement.next_payment_ts;
+    execute_payment(&mut agreement, now).unwrap();
+
+    let result = execute_payment(&mut agreement, now);
+    assert!(matches!(result, Err(RecurringPaymentError::Inactive)));
+}
+
+/// Test that agreements without a scheduled cancellation are charged as usual
+#[test]
+fn test_execute_without_schedule_charges() {
+    let mut agreement = agreement();
+    let now = agreement.next_payment_ts;
+
+    assert_eq!(
+        execute_payment(&mut agreement, now).unwrap(),
+        Execution::Charged
+    );
+    assert!(agreement.active);
+}
+
+// ============================================================================
+// Reactivation Tests
+// ============================================================================
+
+/// Test that reactivation via `start_agreement` clears a stale scheduled cancellation
+#[test]
+fn test_reactivation_clears_schedule() {
+    let mut agreement = agreement();
+    schedule_cancellation(&mut agreement).unwrap();
+
+    // Payer pauses immediately instead of waiting for period end
+    agreement.active = false;
+
+    // Simulate the reactivation branch of start_agreement
+    agreement.active = true;
+    agreement.cancel_at_period_end = false;
+
+    let now = agreement.next_payment_ts;
+    assert_eq!(
+        execute_payment(&mut agreement, now).unwrap(),
+        Execution::Charged
+    );
+}
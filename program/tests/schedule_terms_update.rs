@@ -0,0 +1,115 @@
+//! Unit tests for scheduled payment terms updates
+//!
+//! This test suite validates the consumer-protection rules for scheduled price and
+//! period changes through unit tests.
+//!
+//! Test coverage:
+//! - Notice period: `effective_ts` must be at least one full current period ahead
+//! - Pending updates are not applied before `effective_ts`
+//! - Pending updates are applied exactly at and after `effective_ts`
+//! - Applying clears the pending update and returns the previous terms
+//! - Error code for insufficient notice
+//!
+//! Business Context:
+//! Several jurisdictions require advance notice before recurring charges increase.
+//! `schedule_terms_update` records the change on `PaymentTerms`; every payment made
+//! before it takes effect is charged at the current terms and emits
+//! `PendingTermsChangeNotice`.
+
+mod common;
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{PaymentTerms, PendingTermsUpdate};
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const THIRTY_DAYS: u64 = 2_592_000;
+const NOW: i64 = 1_700_000_000;
+
+fn monthly_terms(pending_update: Option<PendingTermsUpdate>) -> PaymentTerms {
+    PaymentTerms {
+        pending_update,
+        ..common::terms()
+    }
+}
+
+/// Simulate the notice validation from `schedule_terms_update.rs`
+fn has_sufficient_notice(now: i64, current_period_secs: u64, effective_ts: i64) -> bool {
+    let period = i64::try_from(current_period_secs).unwrap();
+    effective_ts >= now.checked_add(period).unwrap()
+}
+
+// ============================================================================
+// Notice Period Tests
+// ============================================================================
+
+/// Test that exactly one full period of notice is accepted
+#[test]
+fn test_notice_of_exactly_one_period_passes() {
+    let effective_ts = NOW.checked_add(i64::try_from(THIRTY_DAYS).unwrap()).unwrap();
+    assert!(has_sufficient_notice(NOW, THIRTY_DAYS, effective_ts));
+}
+
+/// Test that notice one second short of a full period is rejected
+#[test]
+fn test_notice_shorter_than_one_period_fails() {
+    let effective_ts = NOW
+        .checked_add(i64::try_from(THIRTY_DAYS).unwrap())
+        .unwrap()
+        .checked_sub(1)
+        .unwrap();
+    assert!(!has_sufficient_notice(NOW, THIRTY_DAYS, effective_ts));
+}
+
+/// Test that insufficient notice maps to the dedicated error code
+#[test]
+fn test_insufficient_notice_error_code() {
+    let error = RecurringPaymentError::InsufficientNoticePeriod;
+    assert_eq!(u32::from(error), 6028);
+}
+
+// ============================================================================
+// Apply Pending Update Tests
+// ============================================================================
+
+/// Test that a pending update is not applied before its effective timestamp
+#[test]
+fn test_pending_update_not_applied_early() {
+    let pending = PendingTermsUpdate {
+        amount_usdc: 12 * ONE_USDC,
+        period_secs: THIRTY_DAYS,
+        effective_ts: NOW,
+    };
+    let mut terms = monthly_terms(Some(pending));
+
+    assert_eq!(terms.apply_due_update(NOW.checked_sub(1).unwrap()), None);
+    assert_eq!(terms.amount_usdc, 10 * ONE_USDC);
+    assert_eq!(terms.pending_update, Some(pending));
+}
+
+/// Test that a pending update is applied at its effective timestamp
+#[test]
+fn test_pending_update_applied_at_effective_ts() {
+    let mut terms = monthly_terms(Some(PendingTermsUpdate {
+        amount_usdc: 12 * ONE_USDC,
+        period_secs: THIRTY_DAYS.checked_mul(2).unwrap(),
+        effective_ts: NOW,
+    }));
+
+    assert_eq!(
+        terms.apply_due_update(NOW),
+        Some((10 * ONE_USDC, THIRTY_DAYS))
+    );
+    assert_eq!(terms.amount_usdc, 12 * ONE_USDC);
+    assert_eq!(terms.period_secs, THIRTY_DAYS.checked_mul(2).unwrap());
+    assert_eq!(terms.pending_update, None);
+}
+
+/// Test that applying is a no-op when nothing is pending
+#[test]
+fn test_apply_without_pending_update() {
+    let mut terms = monthly_terms(None);
+
+    assert_eq!(terms.apply_due_update(NOW), None);
+    assert_eq!(terms.amount_usdc, 10 * ONE_USDC);
+    assert_eq!(terms.period_secs, THIRTY_DAYS);
+}
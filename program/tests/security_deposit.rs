@@ -0,0 +1,214 @@
+//! Unit tests for refundable security deposits
+//!
+//! This test suite validates the deposit accounting of `start_agreement`,
+//! `claim_deposit` and `close_agreement` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Payment terms store an optional deposit and the account size grows to 214 bytes
+//! - Starting an agreement holds the deposit; reactivation only tops up claimed damages
+//! - Claims wait until the agreement is no longer active (`AlreadyActive` 6007)
+//! - Claims are bounded by the deposit held (`NoDepositHeld` 6051, `DepositClaimExceedsHeld` 6053)
+//! - Closing returns the rest of the deposit once the last paid period ends (`DepositLocked` 6052)
+//!
+//! Security Context:
+//! The deposit sits in the program delegate's escrow ATA, shared by every agreement, so
+//! each agreement tracks its own share in `deposit_held`. Claims and refunds never move
+//! more than that share:
+//! ```rust
+//! require!(
+//!     args.amount <= payment_agreement.deposit_held,
+//!     RecurringPaymentError::DepositClaimExceedsHeld
+//! );
+//! ```
+
+mod common;
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{PaymentAgreement, PaymentTerms};
+
+const ONE_USDC: u64 = 1_000_000;
+const START: i64 = 1_700_000_000;
+const PERIOD_SECS: i64 = 2_592_000;
+
+fn create_terms(deposit_usdc: Option<u64>) -> PaymentTerms {
+    PaymentTerms {
+        deposit_usdc,
+        ..common::terms()
+    }
+}
+
+fn create_agreement(deposit_held: u64) -> PaymentAgreement {
+    PaymentAgreement {
+        active: false,
+        next_payment_ts: START + PERIOD_SECS,
+        created_ts: START,
+        last_payment_ts: START,
+        deposit_held,
+        ..common::agreement()
+    }
+}
+
+/// Simulate the deposit top-up of `start_agreement.rs`
+fn deposit_top_up(terms: &PaymentTerms, agreement: &PaymentAgreement) -> u64 {
+    terms
+        .deposit_usdc
+        .unwrap_or(0)
+        .saturating_sub(agreement.deposit_held)
+}
+
+/// Simulate the claim checks and accounting of `claim_deposit.rs`
+fn claim_deposit(
+    agreement: &mut PaymentAgreement,
+    amount: u64,
+) -> Result<(), RecurringPaymentError> {
+    if agreement.active {
+        return Err(RecurringPaymentError::AlreadyActive);
+    }
+    if amount == 0 {
+        return Err(RecurringPaymentError::InvalidAmount);
+    }
+    if agreement.deposit_held == 0 {
+        return Err(RecurringPaymentError::NoDepositHeld);
+    }
+    if amount > agreement.deposit_held {
+        return Err(RecurringPaymentError::DepositClaimExceedsHeld);
+    }
+    agreement.deposit_held = agreement
+        .deposit_held
+        .checked_sub(amount)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok(())
+}
+
+/// Simulate the deposit release of `close_agreement.rs`, returning the refunded amount
+const fn release_deposit(
+    agreement: &PaymentAgreement,
+    current_time: i64,
+) -> Result<u64, RecurringPaymentError> {
+    if agreement.deposit_held > 0 && current_time < agreement.next_payment_ts {
+        return Err(RecurringPaymentError::DepositLocked);
+    }
+    Ok(agreement.deposit_held)
+}
+
+// ============================================================================
+// Holding the Deposit
+// ============================================================================
+
+/// Test that a new agreement holds the full deposit and terms without one hold nothing
+#[test]
+fn test_new_agreement_holds_deposit() {
+    let terms = create_terms(Some(50 * ONE_USDC));
+    let agreement = create_agreement(0);
+    assert_eq!(deposit_top_up(&terms, &agreement), 50 * ONE_USDC);
+
+    let no_deposit = create_terms(None);
+    assert_eq!(deposit_top_up(&no_deposit, &agreement), 0);
+}
+
+/// Test that reactivation only tops up what claims took from the deposit
+#[test]
+fn test_reactivation_tops_up_claimed_damages() {
+    let terms = create_terms(Some(50 * ONE_USDC));
+    let mut agreement = create_agreement(50 * ONE_USDC);
+    assert_eq!(deposit_top_up(&terms, &agreement), 0);
+
+    claim_deposit(&mut agreement, 15 * ONE_USDC).unwrap();
+    assert_eq!(deposit_top_up(&terms, &agreement), 15 * ONE_USDC);
+}
+
+// ============================================================================
+// Claims
+// ============================================================================
+
+/// Test that claims draw down the deposit and cannot exceed it
+#[test]
+fn test_claims_bounded_by_deposit_held() {
+    let mut agreement = create_agreement(50 * ONE_USDC);
+
+    claim_deposit(&mut agreement, 20 * ONE_USDC).unwrap();
+    assert_eq!(agreement.deposit_held, 30 * ONE_USDC);
+
+    assert!(matches!(
+        claim_deposit(&mut agreement, 31 * ONE_USDC),
+        Err(RecurringPaymentError::DepositClaimExceedsHeld)
+    ));
+    assert!(matches!(
+        claim_deposit(&mut agreement, 0),
+        Err(RecurringPaymentError::InvalidAmount)
+    ));
+
+    claim_deposit(&mut agreement, 30 * ONE_USDC).unwrap();
+    assert_eq!(agreement.deposit_held, 0);
+    assert!(matches!(
+        claim_deposit(&mut agreement, 1),
+        Err(RecurringPaymentError::NoDepositHeld)
+    ));
+}
+
+/// Test that the payee cannot claim while the agreement is still active
+#[test]
+fn test_claim_rejected_while_active() {
+    let mut agreement = PaymentAgreement {
+        active: true,
+        ..create_agreement(50 * ONE_USDC)
+    };
+    assert!(matches!(
+        claim_deposit(&mut agreement, 10 * ONE_USDC),
+        Err(RecurringPaymentError::AlreadyActive)
+    ));
+    assert_eq!(agreement.deposit_held, 50 * ONE_USDC);
+
+    agreement.active = false;
+    claim_deposit(&mut agreement, 10 * ONE_USDC).unwrap();
+    assert_eq!(agreement.deposit_held, 40 * ONE_USDC);
+}
+
+// ============================================================================
+// Release on Close
+// ============================================================================
+
+/// Test that the deposit is locked until the last paid period ends
+#[test]
+fn test_close_locked_until_period_ends() {
+    let mut agreement = create_agreement(50 * ONE_USDC);
+    let period_end = agreement.next_payment_ts;
+
+    assert!(matches!(
+        release_deposit(&agreement, period_end.saturating_sub(1)),
+        Err(RecurringPaymentError::DepositLocked)
+    ));
+
+    claim_deposit(&mut agreement, 5 * ONE_USDC).unwrap();
+    assert_eq!(release_deposit(&agreement, period_end).unwrap(), 45 * ONE_USDC);
+}
+
+/// Test that agreements without a deposit can close at any time
+#[test]
+fn test_close_without_deposit_not_locked() {
+    let agreement = create_agreement(0);
+    assert_eq!(release_deposit(&agreement, 0).unwrap(), 0);
+}
+
+// ============================================================================
+// Account Size and Error Codes
+// ============================================================================
+
+/// Test the account sizes grown by the deposit fields
+#[test]
+fn test_account_sizes() {
+    assert_eq!(PaymentTerms::SPACE, 214);
+    assert_eq!(PaymentAgreement::SPACE, 259);
+}
+
+/// Test the error codes clients decode
+#[test]
+fn test_error_codes() {
+    assert_eq!(common::error_code(RecurringPaymentError::NoDepositHeld), 6051);
+    assert_eq!(common::error_code(RecurringPaymentError::DepositLocked), 6052);
+    assert_eq!(
+        common::error_code(RecurringPaymentError::DepositClaimExceedsHeld),
+        6053
+    );
+}
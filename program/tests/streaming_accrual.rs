@@ -0,0 +1,344 @@
+//! Unit tests for streaming payment terms and `sweep_accrued`
+//!
+//! This test suite validates the per-second accrual mode through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Streaming terms must price `period_secs` exactly at the accrual rate and can't
+//!   combine with token gates or escrow windows
+//! - Accrual runs from `last_sweep_ts` until now, the pause or the terms' sunset
+//! - Sweeps are capped at the whole seconds the allowance covers; the remainder stays
+//!   sweepable
+//! - Consecutive sweeps collect the same total as one sweep
+//! - Resuming charges the accrual up to the pause that no keeper swept
+//! - Period billing instructions reject streaming terms (`StreamingTerms` 6059)
+//! - Sweeping renewing terms is rejected (`NotStreamingTerms` 6060)
+//! - Sweeps without covered accrual are rejected (`NothingToSweep` 6061)
+//!
+//! Accrual Context:
+//! The handler charges whole seconds only and advances `last_sweep_ts` by the seconds
+//! it charged, so a sweep capped by the allowance loses nothing:
+//! ```rust
+//! let (amount, covered_secs) = payment_agreement
+//!     .sweepable(accrual_end_ts, accrual_rate_per_sec, subscriber_ata_data.delegated_amount)
+//!     .ok_or(RecurringPaymentError::ArithmeticError)?;
+//! require!(amount > 0, RecurringPaymentError::NothingToSweep);
+//! ```
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::{PaymentAgreement, PaymentTerms};
+
+const THIRTY_DAYS: u64 = 2_592_000;
+const ONE_HOUR: i64 = 3_600;
+const LAST_SWEEP: i64 = 1_700_000_000;
+/// 1 micro-USDC per second, 2.592 USDC per thirty days
+const RATE: u64 = 1;
+
+fn streaming_terms(accrual_rate_per_sec: Option<u64>) -> PaymentTerms {
+    PaymentTerms {
+        amount_usdc: THIRTY_DAYS,
+        active_agreements: 1,
+        accrual_rate_per_sec,
+        ..common::terms()
+    }
+}
+
+fn agreement() -> PaymentAgreement {
+    PaymentAgreement {
+        next_payment_ts: LAST_SWEEP,
+        created_ts: LAST_SWEEP,
+        last_amount: THIRTY_DAYS,
+        last_payment_ts: LAST_SWEEP,
+        last_sweep_ts: LAST_SWEEP,
+        ..common::agreement()
+    }
+}
+
+const fn after_last_sweep(seconds: i64) -> i64 {
+    LAST_SWEEP.checked_add(seconds).unwrap()
+}
+
+/// Simulate the streaming validation from `create_payment_terms.rs`
+fn check_streaming_terms(
+    amount_usdc: u64,
+    period_secs: u64,
+    accrual_rate_per_sec: u64,
+    gate_mint: Option<Pubkey>,
+    escrow_window_secs: Option<u64>,
+) -> Result<(), RecurringPaymentError> {
+    if accrual_rate_per_sec > 0
+        && accrual_rate_per_sec.checked_mul(period_secs) == Some(amount_usdc)
+        && gate_mint.is_none()
+        && escrow_window_secs.is_none()
+    {
+        Ok(())
+    } else {
+        Err(RecurringPaymentError::InvalidPaymentTerms)
+    }
+}
+
+/// Simulate the accrual end from `sweep_accrued.rs`
+fn accrual_end_ts(
+    agreement: &PaymentAgreement,
+    terms: &PaymentTerms,
+    now: i64,
+) -> Result<i64, RecurringPaymentError> {
+    let end_ts = if agreement.active {
+        now
+    } else {
+        agreement
+            .paused_at_ts
+            .ok_or(RecurringPaymentError::Inactive)?
+    };
+    Ok(terms
+        .sunset_ts
+        .map_or(end_ts, |sunset_ts| end_ts.min(sunset_ts)))
+}
+
+/// Agreement paused at `paused_at` with nothing swept since `LAST_SWEEP`
+fn paused_agreement(paused_at: i64) -> PaymentAgreement {
+    PaymentAgreement {
+        active: false,
+        paused_at_ts: Some(paused_at),
+        ..agreement()
+    }
+}
+
+/// Simulate the charge and accrual restart of `resume_agreement.rs`, returning the
+/// amount charged
+fn resume(
+    agreement: &mut PaymentAgreement,
+    terms: &PaymentTerms,
+    now: i64,
+) -> Result<u64, RecurringPaymentError> {
+    let paused_at_ts = agreement
+        .paused_at_ts
+        .ok_or(RecurringPaymentError::NotPaused)?;
+    let credit_applied = agreement.credit_amount.min(terms.amount_usdc);
+    let (unswept_accrual, _) = match terms.accrual_rate_per_sec {
+        Some(rate_per_sec) => agreement
+            .sweepable(paused_at_ts, rate_per_sec, u64::MAX)
+            .ok_or(RecurringPaymentError::ArithmeticError)?,
+        None => (0, 0),
+    };
+    let period_i64 =
+        i64::try_from(terms.period_secs).map_err(|_| RecurringPaymentError::ArithmeticError)?;
+
+    agreement.active = true;
+    agreement.paused_at_ts = None;
+    agreement.next_payment_ts = now
+        .checked_add(period_i64)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    agreement.last_sweep_ts = agreement.next_payment_ts;
+    agreement.credit_amount = agreement
+        .credit_amount
+        .checked_sub(credit_applied)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    terms
+        .amount_usdc
+        .checked_sub(credit_applied)
+        .and_then(|charged| charged.checked_add(unswept_accrual))
+        .ok_or(RecurringPaymentError::ArithmeticError)
+}
+
+// ============================================================================
+// Streaming Terms
+// ============================================================================
+
+/// Test that the accrual rate must price a period exactly
+#[test]
+fn test_streaming_terms_validation() {
+    assert!(check_streaming_terms(THIRTY_DAYS, THIRTY_DAYS, RATE, None, None).is_ok());
+    assert!(check_streaming_terms(10 * THIRTY_DAYS, THIRTY_DAYS, 10, None, None).is_ok());
+
+    // The period price must match the rate
+    assert!(check_streaming_terms(10_000_000, THIRTY_DAYS, 4, None, None).is_err());
+    assert!(check_streaming_terms(0, THIRTY_DAYS, 0, None, None).is_err());
+    // A rate so high the period price overflows
+    assert!(check_streaming_terms(u64::MAX, THIRTY_DAYS, u64::MAX, None, None).is_err());
+}
+
+/// Test that gate discounts and escrowed first payments are period billing only
+#[test]
+fn test_streaming_terms_reject_gates_and_escrow() {
+    assert!(matches!(
+        check_streaming_terms(
+            THIRTY_DAYS,
+            THIRTY_DAYS,
+            RATE,
+            Some(Pubkey::new_unique()),
+            None
+        ),
+        Err(RecurringPaymentError::InvalidPaymentTerms)
+    ));
+    assert!(matches!(
+        check_streaming_terms(THIRTY_DAYS, THIRTY_DAYS, RATE, None, Some(86_400)),
+        Err(RecurringPaymentError::InvalidPaymentTerms)
+    ));
+}
+
+/// Test that only terms with an accrual rate are streaming
+#[test]
+fn test_is_streaming() {
+    assert!(streaming_terms(Some(RATE)).is_streaming());
+    assert!(!streaming_terms(None).is_streaming());
+}
+
+// ============================================================================
+// Accrual Window
+// ============================================================================
+
+/// Test that accrual ends now, at the pause or at the sunset
+#[test]
+fn test_accrual_end() {
+    let now = after_last_sweep(ONE_HOUR);
+    let terms = streaming_terms(Some(RATE));
+    assert_eq!(accrual_end_ts(&agreement(), &terms, now).unwrap(), now);
+
+    // A paused agreement accrued until it was paused
+    let paused_at = after_last_sweep(600);
+    let mut paused = agreement();
+    paused.active = false;
+    paused.paused_at_ts = Some(paused_at);
+    assert_eq!(accrual_end_ts(&paused, &terms, now).unwrap(), paused_at);
+
+    // An inactive agreement without a pause has nothing left to accrue
+    paused.paused_at_ts = None;
+    assert!(matches!(
+        accrual_end_ts(&paused, &terms, now),
+        Err(RecurringPaymentError::Inactive)
+    ));
+
+    // Sunset terms stop accruing at the sunset
+    let sunset = after_last_sweep(60);
+    let mut sunset_terms = terms;
+    sunset_terms.sunset_ts = Some(sunset);
+    assert_eq!(
+        accrual_end_ts(&agreement(), &sunset_terms, now).unwrap(),
+        sunset
+    );
+}
+
+// ============================================================================
+// Sweepable Amount
+// ============================================================================
+
+/// Test that an uncapped sweep collects every second since the last sweep
+#[test]
+fn test_sweep_collects_elapsed_accrual() {
+    let agreement = agreement();
+    let rate = 10;
+
+    assert_eq!(
+        agreement.sweepable(after_last_sweep(ONE_HOUR), rate, u64::MAX),
+        Some((36_000, ONE_HOUR))
+    );
+}
+
+/// Test that the allowance caps a sweep at the whole seconds it covers
+#[test]
+fn test_sweep_capped_by_allowance() {
+    let mut agreement = agreement();
+    let rate = 10;
+    let end_ts = after_last_sweep(ONE_HOUR);
+
+    // 1_005 covers 100 seconds; the odd 5 micro-USDC stay with the payer
+    let (amount, covered_secs) = agreement.sweepable(end_ts, rate, 1_005).unwrap();
+    assert_eq!((amount, covered_secs), (1_000, 100));
+
+    // The uncovered accrual is still sweepable once the allowance is raised
+    agreement.last_sweep_ts = agreement.last_sweep_ts.checked_add(covered_secs).unwrap();
+    assert_eq!(
+        agreement.sweepable(end_ts, rate, u64::MAX),
+        Some((35_000, ONE_HOUR.checked_sub(100).unwrap()))
+    );
+
+    // An allowance below the rate covers nothing
+    assert_eq!(agreement.sweepable(end_ts, rate, 9), Some((0, 0)));
+}
+
+/// Test that two sweeps collect the same total as one
+#[test]
+fn test_consecutive_sweeps_add_up() {
+    let rate = 7;
+    let end_ts = after_last_sweep(ONE_HOUR);
+    let (single, _) = agreement().sweepable(end_ts, rate, u64::MAX).unwrap();
+
+    let mut agreement = agreement();
+    let (first, covered_secs) = agreement
+        .sweepable(after_last_sweep(1_234), rate, u64::MAX)
+        .unwrap();
+    agreement.last_sweep_ts = agreement.last_sweep_ts.checked_add(covered_secs).unwrap();
+    let (second, _) = agreement.sweepable(end_ts, rate, u64::MAX).unwrap();
+
+    assert_eq!(first.checked_add(second), Some(single));
+}
+
+/// Test accrual edge cases: an end before the last sweep and a zero rate
+#[test]
+fn test_sweep_edge_cases() {
+    let agreement = agreement();
+
+    // Paused before the accrual started, e.g. during the first prepaid period
+    assert_eq!(
+        agreement.sweepable(after_last_sweep(-ONE_HOUR), RATE, u64::MAX),
+        Some((0, 0))
+    );
+    assert_eq!(
+        agreement.sweepable(LAST_SWEEP, RATE, u64::MAX),
+        Some((0, 0))
+    );
+    assert_eq!(
+        agreement.sweepable(after_last_sweep(ONE_HOUR), 0, u64::MAX),
+        None
+    );
+}
+
+// ============================================================================
+// Resume
+// ============================================================================
+
+/// Test that resuming charges the accrual up to the pause that no keeper swept
+#[test]
+fn test_resume_collects_unswept_accrual() {
+    let terms = streaming_terms(Some(RATE));
+    let mut agreement = paused_agreement(after_last_sweep(ONE_HOUR));
+    let resume_ts = after_last_sweep(ONE_HOUR.checked_mul(5).unwrap());
+
+    let charged = resume(&mut agreement, &terms, resume_ts).unwrap();
+
+    // The new prepaid period plus the hour accrued before the pause
+    assert_eq!(charged, THIRTY_DAYS.checked_add(3_600).unwrap());
+    assert_eq!(agreement.last_sweep_ts, agreement.next_payment_ts);
+}
+
+/// Test that accrual swept after the pause is not charged again on resume
+#[test]
+fn test_resume_after_sweep_charges_period_only() {
+    let terms = streaming_terms(Some(RATE));
+    let paused_at = after_last_sweep(ONE_HOUR);
+    let mut agreement = paused_agreement(paused_at);
+
+    // The delegate survives the pause, so a keeper sweeps up to it
+    let now = after_last_sweep(ONE_HOUR.checked_mul(2).unwrap());
+    let end_ts = accrual_end_ts(&agreement, &terms, now).unwrap();
+    let (swept, covered_secs) = agreement.sweepable(end_ts, RATE, u64::MAX).unwrap();
+    assert_eq!(swept, 3_600);
+    agreement.last_sweep_ts = agreement.last_sweep_ts.checked_add(covered_secs).unwrap();
+    assert_eq!(agreement.last_sweep_ts, paused_at);
+
+    let resume_ts = after_last_sweep(ONE_HOUR.checked_mul(5).unwrap());
+    let charged = resume(&mut agreement, &terms, resume_ts).unwrap();
+    assert_eq!(charged, THIRTY_DAYS);
+}
+
+/// Test the error codes clients decode
+#[test]
+fn test_error_codes() {
+    assert_eq!(common::error_code(RecurringPaymentError::StreamingTerms), 6059);
+    assert_eq!(common::error_code(RecurringPaymentError::NotStreamingTerms), 6060);
+    assert_eq!(common::error_code(RecurringPaymentError::NothingToSweep), 6061);
+}
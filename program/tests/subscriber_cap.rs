@@ -0,0 +1,179 @@
+//! Unit tests for the per-terms subscriber cap and waitlist
+//!
+//! This test suite validates `max_subscribers` enforcement and `reserve_slot` through
+//! unit tests. For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Uncapped terms accept any number of agreements
+//! - `start_agreement` rejects new payers with `TermsFull` once the cap is reached
+//! - Pausing (or canceling at period end) frees a slot exactly once
+//! - The slot counter never underflows for agreements predating it
+//! - Waitlist reservations are assigned sequential positions and require a cap
+//! - `TermsFull` error code
+//!
+//! Business Context:
+//! Limited-capacity offerings (cohorts, communities) cap the number of concurrently
+//! active agreements. `start_agreement` claims a slot:
+//! ```rust
+//! require!(!payment_terms.is_full(), RecurringPaymentError::TermsFull);
+//! payment_terms.active_agreements += 1;
+//! ```
+//! and `pause_agreement` releases it via `release_subscriber_slot()`.
+
+mod common;
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentTerms;
+
+fn terms(max_subscribers: Option<u32>) -> PaymentTerms {
+    PaymentTerms {
+        max_subscribers,
+        ..common::terms()
+    }
+}
+
+/// Simulate the slot claim at the start of `start_agreement.rs` for an inactive agreement
+fn start_agreement(terms: &mut PaymentTerms) -> Result<(), RecurringPaymentError> {
+    if terms.is_full() {
+        return Err(RecurringPaymentError::TermsFull);
+    }
+    terms.active_agreements = terms
+        .active_agreements
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok(())
+}
+
+/// Simulate `pause_agreement.rs`, which only releases the slot of an active agreement
+const fn pause_agreement(terms: &mut PaymentTerms, active: &mut bool) {
+    if *active {
+        terms.release_subscriber_slot();
+    }
+    *active = false;
+}
+
+/// Simulate `reserve_slot.rs`, returning the assigned waitlist position
+fn reserve_slot(terms: &mut PaymentTerms) -> Result<u32, RecurringPaymentError> {
+    if terms.max_subscribers.is_none() {
+        return Err(RecurringPaymentError::InvalidPaymentTerms);
+    }
+    let position = terms.waitlist_len;
+    terms.waitlist_len = position
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok(position)
+}
+
+// ============================================================================
+// Cap Enforcement Tests
+// ============================================================================
+
+/// Test that uncapped terms never fill up
+#[test]
+fn test_uncapped_terms_accept_agreements() {
+    let mut terms = terms(None);
+    terms.active_agreements = u32::MAX - 1;
+
+    assert!(!terms.is_full());
+    assert!(start_agreement(&mut terms).is_ok());
+}
+
+/// Test that the cap admits exactly `max_subscribers` agreements
+#[test]
+fn test_cap_rejects_when_full() {
+    let mut terms = terms(Some(2));
+
+    start_agreement(&mut terms).unwrap();
+    start_agreement(&mut terms).unwrap();
+    assert!(terms.is_full());
+
+    let result = start_agreement(&mut terms);
+    assert!(matches!(result, Err(RecurringPaymentError::TermsFull)));
+    assert_eq!(terms.active_agreements, 2);
+}
+
+/// Test that pausing frees a slot for the next payer
+#[test]
+fn test_pause_frees_slot() {
+    let mut terms = terms(Some(1));
+    let mut active = true;
+    start_agreement(&mut terms).unwrap();
+
+    pause_agreement(&mut terms, &mut active);
+
+    assert_eq!(terms.active_agreements, 0);
+    assert!(start_agreement(&mut terms).is_ok());
+}
+
+/// Test that repeated pauses (idempotent) only free the slot once
+#[test]
+fn test_repeated_pause_frees_slot_once() {
+    let mut terms = terms(Some(3));
+    let mut active = true;
+    start_agreement(&mut terms).unwrap();
+    start_agreement(&mut terms).unwrap();
+
+    pause_agreement(&mut terms, &mut active);
+    pause_agreement(&mut terms, &mut active);
+
+    assert_eq!(terms.active_agreements, 1);
+}
+
+/// Test that releasing a slot never underflows for agreements predating the counter
+#[test]
+fn test_release_saturates_at_zero() {
+    let mut terms = terms(Some(1));
+
+    terms.release_subscriber_slot();
+
+    assert_eq!(terms.active_agreements, 0);
+}
+
+// ============================================================================
+// Waitlist Tests
+// ============================================================================
+
+/// Test that reservations are assigned sequential positions
+#[test]
+fn test_reservations_are_sequential() {
+    let mut terms = terms(Some(1));
+
+    assert_eq!(reserve_slot(&mut terms).unwrap(), 0);
+    assert_eq!(reserve_slot(&mut terms).unwrap(), 1);
+    assert_eq!(reserve_slot(&mut terms).unwrap(), 2);
+    assert_eq!(terms.waitlist_len, 3);
+}
+
+/// Test that reservations don't consume active slots
+#[test]
+fn test_reservation_does_not_claim_slot() {
+    let mut terms = terms(Some(1));
+
+    reserve_slot(&mut terms).unwrap();
+
+    assert_eq!(terms.active_agreements, 0);
+    assert!(!terms.is_full());
+}
+
+/// Test that uncapped terms have no waitlist
+#[test]
+fn test_reservation_requires_cap() {
+    let mut terms = terms(None);
+
+    let result = reserve_slot(&mut terms);
+    assert!(matches!(
+        result,
+        Err(RecurringPaymentError::InvalidPaymentTerms)
+    ));
+}
+
+// ============================================================================
+// Error Code Tests
+// ============================================================================
+
+/// Test that `TermsFull` has a stable error code
+#[test]
+fn test_terms_full_error_code() {
+    let error = RecurringPaymentError::TermsFull;
+    assert_eq!(u32::from(error), 6031);
+}
@@ -0,0 +1,213 @@
+//! Unit tests for minimum-notice deactivation of payment terms
+//!
+//! This test suite validates `deactivate_payment_terms` and the sunset handling in
+//! `start_agreement`, `resume_agreement` and `execute_payment` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Deactivation with active agreements requires one full period of notice
+//! - Terms without active agreements can be deactivated immediately
+//! - Deactivation is final and cannot be rescheduled
+//! - Deactivated terms reject new and reactivated agreements
+//! - Resume is allowed until the sunset, then rejected
+//! - Payments due before the sunset are still charged
+//! - The first payment due at or after the sunset pauses the agreement instead
+//! - `TermsDeactivated` error code
+//!
+//! Business Context:
+//! Payees used to be able to withdraw an offering without warning. Deactivation now
+//! sets `sunset_ts`, at least one period out while payers are active, and
+//! `execute_payment` honors each paid period before pausing the agreement:
+//! ```rust
+//! if let Some(sunset_ts) = payment_terms.sunset_ts.filter(|ts| current_time >= *ts) {
+//!     payment_agreement.active = false;
+//!     payment_terms.release_subscriber_slot();
+//! }
+//! ```
+
+mod common;
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::PaymentTerms;
+
+const THIRTY_DAYS: i64 = 2_592_000;
+const NOW: i64 = 1_700_000_000;
+
+fn terms(active_agreements: u32) -> PaymentTerms {
+    PaymentTerms {
+        active_agreements,
+        ..common::terms()
+    }
+}
+
+/// Simulate the notice check of `deactivate_payment_terms.rs`
+fn deactivate(
+    terms: &mut PaymentTerms,
+    now: i64,
+    sunset_ts: i64,
+) -> Result<(), RecurringPaymentError> {
+    if terms.is_deactivated() {
+        return Err(RecurringPaymentError::TermsDeactivated);
+    }
+    if terms.active_agreements > 0 {
+        let period_i64 =
+            i64::try_from(terms.period_secs).map_err(|_| RecurringPaymentError::ArithmeticError)?;
+        let earliest_sunset_ts = now
+            .checked_add(period_i64)
+            .ok_or(RecurringPaymentError::ArithmeticError)?;
+        if sunset_ts < earliest_sunset_ts {
+            return Err(RecurringPaymentError::InsufficientNoticePeriod);
+        }
+    }
+    terms.sunset_ts = Some(sunset_ts);
+    Ok(())
+}
+
+/// Simulate the slot claim of `start_agreement.rs`
+fn start(terms: &mut PaymentTerms) -> Result<(), RecurringPaymentError> {
+    if terms.is_deactivated() {
+        return Err(RecurringPaymentError::TermsDeactivated);
+    }
+    terms.active_agreements = terms
+        .active_agreements
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok(())
+}
+
+/// Simulate the slot claim of `resume_agreement.rs`
+fn resume(terms: &mut PaymentTerms, now: i64) -> Result<(), RecurringPaymentError> {
+    if terms.is_sunset(now) {
+        return Err(RecurringPaymentError::TermsDeactivated);
+    }
+    terms.active_agreements = terms
+        .active_agreements
+        .checked_add(1)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok(())
+}
+
+/// Simulate `execute_payment.rs` at a due payment, returning whether it charged
+fn execute_payment(terms: &mut PaymentTerms, active: &mut bool, now: i64) -> bool {
+    if terms.is_sunset(now) {
+        *active = false;
+        terms.release_subscriber_slot();
+        return false;
+    }
+    true
+}
+
+// ============================================================================
+// Deactivation Notice
+// ============================================================================
+
+/// Test that active payers get at least one full period of notice
+#[test]
+fn test_deactivation_requires_one_period_notice() {
+    let mut terms = terms(3);
+
+    let too_soon = NOW
+        .checked_add(THIRTY_DAYS)
+        .unwrap()
+        .checked_sub(1)
+        .unwrap();
+    assert!(matches!(
+        deactivate(&mut terms, NOW, too_soon),
+        Err(RecurringPaymentError::InsufficientNoticePeriod)
+    ));
+    assert!(!terms.is_deactivated());
+
+    let sunset_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+    deactivate(&mut terms, NOW, sunset_ts).unwrap();
+    assert_eq!(terms.sunset_ts, Some(sunset_ts));
+}
+
+/// Test that terms nobody is subscribed to can be deactivated immediately
+#[test]
+fn test_deactivation_without_agreements_is_immediate() {
+    let mut terms = terms(0);
+    deactivate(&mut terms, NOW, NOW).unwrap();
+    assert!(terms.is_sunset(NOW));
+}
+
+/// Test that deactivation cannot be rescheduled
+#[test]
+fn test_deactivation_is_final() {
+    let mut terms = terms(1);
+    let sunset_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+    deactivate(&mut terms, NOW, sunset_ts).unwrap();
+
+    let later = sunset_ts.checked_add(THIRTY_DAYS).unwrap();
+    assert!(matches!(
+        deactivate(&mut terms, NOW, later),
+        Err(RecurringPaymentError::TermsDeactivated)
+    ));
+    assert_eq!(terms.sunset_ts, Some(sunset_ts));
+}
+
+// ============================================================================
+// New Agreements
+// ============================================================================
+
+/// Test that deactivated terms accept no new agreements even before the sunset
+#[test]
+fn test_start_rejected_once_deactivated() {
+    let mut terms = terms(1);
+    start(&mut terms).unwrap();
+
+    deactivate(&mut terms, NOW, NOW.checked_add(THIRTY_DAYS).unwrap()).unwrap();
+    assert!(matches!(
+        start(&mut terms),
+        Err(RecurringPaymentError::TermsDeactivated)
+    ));
+    assert_eq!(terms.active_agreements, 2);
+}
+
+/// Test that paused payers may resume until the sunset
+#[test]
+fn test_resume_allowed_until_sunset() {
+    let mut terms = terms(1);
+    let sunset_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+    deactivate(&mut terms, NOW, sunset_ts).unwrap();
+
+    resume(&mut terms, sunset_ts.checked_sub(1).unwrap()).unwrap();
+    assert!(matches!(
+        resume(&mut terms, sunset_ts),
+        Err(RecurringPaymentError::TermsDeactivated)
+    ));
+}
+
+// ============================================================================
+// Sunset
+// ============================================================================
+
+/// Test that payments due before the sunset are charged and the next one pauses
+#[test]
+fn test_execute_payment_honors_period_then_pauses() {
+    let mut terms = terms(1);
+    let mut active = true;
+    let sunset_ts = NOW.checked_add(THIRTY_DAYS).unwrap();
+    deactivate(&mut terms, NOW, sunset_ts).unwrap();
+
+    // Payment due halfway to the sunset is still charged; its period runs past it
+    let due = NOW.checked_add(THIRTY_DAYS / 2).unwrap();
+    assert!(execute_payment(&mut terms, &mut active, due));
+    assert!(active);
+
+    // The following payment falls after the sunset and pauses the agreement
+    let next_due = due.checked_add(THIRTY_DAYS).unwrap();
+    assert!(!execute_payment(&mut terms, &mut active, next_due));
+    assert!(!active);
+    assert_eq!(terms.active_agreements, 0, "Slot released on sunset");
+}
+
+// ============================================================================
+// Error Code Tests
+// ============================================================================
+
+/// Test that `TermsDeactivated` has a stable error code
+#[test]
+fn test_terms_deactivated_error_code() {
+    let error = RecurringPaymentError::TermsDeactivated;
+    assert_eq!(u32::from(error), 6040);
+}
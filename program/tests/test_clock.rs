@@ -0,0 +1,126 @@
+//! Unit tests for the test clock used by localnet and devnet deployments
+//!
+//! This test suite validates the `TestClock` offset and how it moves `execute_payment`
+//! due-date checks through unit tests.
+//! For full integration tests with BPF runtime, build the program with the
+//! `test-clock` feature and use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - The offset shifts the cluster time, saturating instead of overflowing
+//! - Advancing by one period makes the next renewal due without waiting
+//! - Advancing only moves the clock forward
+//! - The account size is 17 bytes
+//!
+//! Testing Context:
+//! `execute_payment` reads the time for its checks through `due_check_time`, which in
+//! `test-clock` builds adds the offset of the `TestClock` PDA passed as a remaining
+//! account:
+//! ```rust
+//! let current_time =
+//!     due_check_time(clock.unix_timestamp, ctx.remaining_accounts, ctx.program_id)?;
+//! ```
+//! Programs built without the feature ignore the account.
+
+use tally_protocol::errors::RecurringPaymentError;
+use tally_protocol::state::TestClock;
+
+const START: i64 = 1_700_000_000;
+const PERIOD: i64 = 2_592_000;
+
+const fn test_clock(offset_secs: i64) -> TestClock {
+    TestClock {
+        offset_secs,
+        bump: 255,
+    }
+}
+
+/// Simulate the validation and update of `advance_test_clock.rs`
+fn advance(test_clock: &mut TestClock, secs: u64) -> Result<(), RecurringPaymentError> {
+    if secs == 0 {
+        return Err(RecurringPaymentError::InvalidConfiguration);
+    }
+    let secs = i64::try_from(secs).map_err(|_| RecurringPaymentError::ArithmeticError)?;
+    test_clock.offset_secs = test_clock
+        .offset_secs
+        .checked_add(secs)
+        .ok_or(RecurringPaymentError::ArithmeticError)?;
+    Ok(())
+}
+
+/// Simulate the due check of `execute_payment.rs`
+const fn check_due(
+    test_clock: &TestClock,
+    cluster_time: i64,
+    next_payment_ts: i64,
+) -> Result<(), RecurringPaymentError> {
+    if test_clock.now(cluster_time) < next_payment_ts {
+        return Err(RecurringPaymentError::NotDue);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Time Shift
+// ============================================================================
+
+/// Test that the offset shifts the cluster time
+#[test]
+fn test_now_adds_offset() {
+    assert_eq!(test_clock(0).now(START), START);
+    assert_eq!(test_clock(PERIOD).now(START), START + PERIOD);
+}
+
+/// Test that a huge offset saturates instead of overflowing
+#[test]
+fn test_now_saturates() {
+    assert_eq!(test_clock(i64::MAX).now(START), i64::MAX);
+}
+
+// ============================================================================
+// Advancing
+// ============================================================================
+
+/// Test that advancing by one period makes the next renewal due immediately
+#[test]
+fn test_advance_makes_renewal_due() {
+    let mut test_clock = test_clock(0);
+    let next_payment_ts = START + PERIOD;
+
+    assert!(matches!(
+        check_due(&test_clock, START, next_payment_ts),
+        Err(RecurringPaymentError::NotDue)
+    ));
+
+    advance(&mut test_clock, 2_592_000).unwrap();
+    assert!(check_due(&test_clock, START, next_payment_ts).is_ok());
+
+    // Advances accumulate across billing periods
+    advance(&mut test_clock, 2_592_000).unwrap();
+    assert_eq!(test_clock.now(START), START + 2 * PERIOD);
+}
+
+/// Test that the clock cannot stand still or overflow
+#[test]
+fn test_advance_rejects_invalid_secs() {
+    let mut test_clock = test_clock(PERIOD);
+
+    assert!(matches!(
+        advance(&mut test_clock, 0),
+        Err(RecurringPaymentError::InvalidConfiguration)
+    ));
+    assert!(matches!(
+        advance(&mut test_clock, u64::MAX),
+        Err(RecurringPaymentError::ArithmeticError)
+    ));
+    assert!(matches!(
+        advance(&mut test_clock, i64::MAX.unsigned_abs()),
+        Err(RecurringPaymentError::ArithmeticError)
+    ));
+    assert_eq!(test_clock.offset_secs, PERIOD);
+}
+
+/// Test the account size
+#[test]
+fn test_account_size() {
+    assert_eq!(TestClock::SPACE, 17);
+}
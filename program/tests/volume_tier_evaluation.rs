@@ -0,0 +1,148 @@
+//! Unit tests for automatic volume tier evaluation in `execute_payment`
+//!
+//! This test suite validates `Payee::record_volume` through unit tests.
+//! For full integration tests with BPF runtime, use the TypeScript test suite.
+//!
+//! Test coverage:
+//! - Volume accumulates within the current 30-day window
+//! - Payees are promoted as soon as the window's volume crosses a threshold
+//! - A new window re-evaluates the tier against the completed window's volume
+//! - A whole idle window resets the tier to Standard
+//! - Payments that don't change the tier report no change
+//!
+//! Business Context:
+//! `execute_payment` records every payment against the payee before splitting fees,
+//! so the platform fee always reflects the payee's current tier:
+//! ```rust
+//! if let Some(old_tier) = payee.record_volume(payment_amount, current_time) {
+//!     // emit VolumeTierUpgraded or VolumeTierDowngraded
+//! }
+//! ```
+
+mod common;
+
+use tally_protocol::constants::VOLUME_WINDOW_SECONDS;
+use tally_protocol::state::{Payee, VolumeTier};
+
+const ONE_USDC: u64 = 1_000_000; // 1 USDC with 6 decimals
+const WINDOW_START: i64 = 1_700_000_000;
+const MID_WINDOW: i64 = WINDOW_START + 60;
+
+fn payee(volume_tier: VolumeTier, monthly_volume_usdc: u64) -> Payee {
+    Payee {
+        volume_tier,
+        monthly_volume_usdc,
+        last_volume_update_ts: WINDOW_START,
+        ..common::payee()
+    }
+}
+
+fn after_windows(windows: i64) -> i64 {
+    VOLUME_WINDOW_SECONDS
+        .checked_mul(windows)
+        .and_then(|elapsed| WINDOW_START.checked_add(elapsed))
+        .unwrap()
+}
+
+// ============================================================================
+// Within-Window Tests
+// ============================================================================
+
+/// Test that volume accumulates without changing the tier below a threshold
+#[test]
+fn test_volume_accumulates_without_tier_change() {
+    let mut payee = payee(VolumeTier::Standard, 1_000 * ONE_USDC);
+
+    let changed = payee.record_volume(500 * ONE_USDC, MID_WINDOW);
+
+    assert_eq!(changed, None);
+    assert_eq!(payee.monthly_volume_usdc, 1_500 * ONE_USDC);
+    assert_eq!(payee.volume_tier, VolumeTier::Standard);
+    assert_eq!(payee.last_volume_update_ts, WINDOW_START);
+}
+
+/// Test that crossing the Growth threshold promotes the payee immediately
+#[test]
+fn test_promotion_to_growth() {
+    let mut payee = payee(VolumeTier::Standard, 9_999 * ONE_USDC);
+
+    let changed = payee.record_volume(ONE_USDC, MID_WINDOW);
+
+    assert_eq!(changed, Some(VolumeTier::Standard));
+    assert_eq!(payee.volume_tier, VolumeTier::Growth);
+    assert_eq!(payee.volume_tier.platform_fee_bps(), 20);
+}
+
+/// Test that a single large payment can skip straight to Scale
+#[test]
+fn test_promotion_to_scale() {
+    let mut payee = payee(VolumeTier::Standard, 0);
+
+    let changed = payee.record_volume(100_000 * ONE_USDC, MID_WINDOW);
+
+    assert_eq!(changed, Some(VolumeTier::Standard));
+    assert_eq!(payee.volume_tier, VolumeTier::Scale);
+}
+
+/// Test that a tier earned in the previous window isn't lost mid-window
+#[test]
+fn test_no_demotion_within_window() {
+    let mut payee = payee(VolumeTier::Scale, 0);
+
+    let changed = payee.record_volume(ONE_USDC, MID_WINDOW);
+
+    assert_eq!(changed, None);
+    assert_eq!(payee.volume_tier, VolumeTier::Scale);
+}
+
+// ============================================================================
+// Window Rollover Tests
+// ============================================================================
+
+/// Test that a new window keeps the tier earned by the completed window
+#[test]
+fn test_rollover_keeps_earned_tier() {
+    let mut payee = payee(VolumeTier::Growth, 50_000 * ONE_USDC);
+    let now = after_windows(1);
+
+    let changed = payee.record_volume(ONE_USDC, now);
+
+    assert_eq!(changed, None);
+    assert_eq!(payee.volume_tier, VolumeTier::Growth);
+    assert_eq!(payee.monthly_volume_usdc, ONE_USDC);
+    assert_eq!(payee.last_volume_update_ts, now);
+}
+
+/// Test that a new window demotes a payee whose completed window fell short
+#[test]
+fn test_rollover_demotes() {
+    let mut payee = payee(VolumeTier::Scale, 20_000 * ONE_USDC);
+
+    let changed = payee.record_volume(ONE_USDC, after_windows(1));
+
+    assert_eq!(changed, Some(VolumeTier::Scale));
+    assert_eq!(payee.volume_tier, VolumeTier::Growth);
+}
+
+/// Test that a whole idle window resets the payee to Standard
+#[test]
+fn test_idle_window_resets_to_standard() {
+    let mut payee = payee(VolumeTier::Scale, 200_000 * ONE_USDC);
+
+    let changed = payee.record_volume(ONE_USDC, after_windows(2));
+
+    assert_eq!(changed, Some(VolumeTier::Scale));
+    assert_eq!(payee.volume_tier, VolumeTier::Standard);
+    assert_eq!(payee.monthly_volume_usdc, ONE_USDC);
+}
+
+/// Test that the payment starting a new window counts toward promotion
+#[test]
+fn test_rollover_payment_can_promote() {
+    let mut payee = payee(VolumeTier::Standard, 0);
+
+    let changed = payee.record_volume(10_000 * ONE_USDC, after_windows(3));
+
+    assert_eq!(changed, Some(VolumeTier::Standard));
+    assert_eq!(payee.volume_tier, VolumeTier::Growth);
+}
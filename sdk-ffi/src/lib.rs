@@ -0,0 +1,500 @@
+//! Tally SDK FFI - `UniFFI` bindings for mobile wallets
+//!
+//! iOS and Android wallets integrate the subscription start and pause flows through
+//! these exports instead of reimplementing PDA seeds, borsh layouts and instruction
+//! discriminators. The surface is deliberately small and uses only FFI-friendly
+//! types: addresses are base58 strings, account data and instruction data are raw
+//! bytes, and parsed events are returned as JSON.
+//!
+//! The bindings live in their own crate so that applications depending on
+//! `tally-sdk` never compile `UniFFI`. The crate is not a default workspace member, so
+//! a plain `cargo build` at the repository root skips it. Build the library for the target platform
+//! (a `staticlib` for iOS, a `cdylib` for Android) and generate Swift or Kotlin
+//! bindings from it with `uniffi-bindgen`:
+//!
+//! ```text
+//! cargo build -p tally-sdk-ffi --release --target aarch64-apple-ios
+//! cargo build -p tally-sdk-ffi --release --target aarch64-linux-android
+//! ```
+//!
+//! Every function takes an optional `program_id` and falls back to the SDK's
+//! configured program ID when it is `None`.
+
+use tally_sdk::ata::TokenProgram;
+use tally_sdk::program_types::{Payee, PaymentTerms};
+use tally_sdk::transaction_builder::{pause_agreement, start_agreement};
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_lang::AnchorDeserialize;
+use std::str::FromStr;
+
+uniffi::setup_scaffolding!();
+
+/// Error returned across the FFI boundary
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum FfiError {
+    /// A string was not a valid base58 address
+    #[error("Invalid address: {message}")]
+    InvalidAddress {
+        /// Description of the invalid input
+        message: String,
+    },
+    /// Account data could not be decoded
+    #[error("Invalid account data: {message}")]
+    InvalidAccountData {
+        /// Description of the decoding failure
+        message: String,
+    },
+    /// The SDK rejected the request
+    #[error("{message}")]
+    Sdk {
+        /// Error message from the SDK
+        message: String,
+    },
+}
+
+impl From<tally_sdk::TallyError> for FfiError {
+    fn from(error: tally_sdk::TallyError) -> Self {
+        Self::Sdk {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Account referenced by an instruction
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct FfiAccountMeta {
+    /// Account address (base58)
+    pub pubkey: String,
+    /// Whether the account must sign the transaction
+    pub is_signer: bool,
+    /// Whether the instruction writes to the account
+    pub is_writable: bool,
+}
+
+/// Instruction ready to be added to a transaction by the wallet
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct FfiInstruction {
+    /// Program to invoke (base58)
+    pub program_id: String,
+    /// Accounts in instruction order
+    pub accounts: Vec<FfiAccountMeta>,
+    /// Serialized instruction data, including the discriminator
+    pub data: Vec<u8>,
+}
+
+impl From<Instruction> for FfiInstruction {
+    fn from(instruction: Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .into_iter()
+                .map(|meta| FfiAccountMeta {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data,
+        }
+    }
+}
+
+/// Inputs for [`build_start_agreement`]
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct FfiStartAgreement {
+    /// Payment terms to agree to (base58)
+    pub payment_terms: String,
+    /// Payer wallet (base58)
+    pub payer: String,
+    /// Raw payee account data, as returned by `getAccountInfo`
+    pub payee_account_data: Vec<u8>,
+    /// Raw payment terms account data, as returned by `getAccountInfo`
+    pub payment_terms_account_data: Vec<u8>,
+    /// Platform treasury token account (base58)
+    pub platform_treasury_ata: String,
+    /// Number of periods to pre-approve (defaults to 3)
+    pub allowance_periods: Option<u8>,
+    /// Number of billing periods after which the agreement completes
+    pub max_periods: Option<u16>,
+    /// Whether the USDC mint uses the Token-2022 program
+    pub token_2022: bool,
+    /// Program ID override (base58)
+    pub program_id: Option<String>,
+}
+
+/// Inputs for [`build_pause_agreement`]
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct FfiPauseAgreement {
+    /// Payment terms of the agreement (base58)
+    pub payment_terms: String,
+    /// Payer wallet (base58)
+    pub payer: String,
+    /// Raw payee account data, as returned by `getAccountInfo`
+    pub payee_account_data: Vec<u8>,
+    /// Whether the USDC mint uses the Token-2022 program
+    pub token_2022: bool,
+    /// Program ID override (base58)
+    pub program_id: Option<String>,
+}
+
+/// Event parsed from transaction logs
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct FfiEvent {
+    /// Event name, e.g. `PaymentAgreementStarted`
+    pub name: String,
+    /// Event fields as a JSON object, with addresses encoded in base58
+    pub json: String,
+}
+
+/// Derive the payee PDA for a payee authority
+#[uniffi::export]
+pub fn payee_address(authority: String, program_id: Option<String>) -> Result<String, FfiError> {
+    let authority = parse_address("authority", &authority)?;
+    let program_id = resolve_program_id(program_id)?;
+    Ok(tally_sdk::pda::payee_address_with_program_id(&authority, &program_id).to_string())
+}
+
+/// Derive the payment terms PDA for a payee and terms ID string
+#[uniffi::export]
+pub fn payment_terms_address(
+    payee: String,
+    terms_id: String,
+    program_id: Option<String>,
+) -> Result<String, FfiError> {
+    let payee = parse_address("payee", &payee)?;
+    let program_id = resolve_program_id(program_id)?;
+    Ok(
+        tally_sdk::pda::payment_terms_address_from_string_with_program_id(&payee, &terms_id, &program_id)
+            .to_string(),
+    )
+}
+
+/// Derive the payment agreement PDA for payment terms and a payer
+#[uniffi::export]
+pub fn payment_agreement_address(
+    payment_terms: String,
+    payer: String,
+    program_id: Option<String>,
+) -> Result<String, FfiError> {
+    let payment_terms = parse_address("payment_terms", &payment_terms)?;
+    let payer = parse_address("payer", &payer)?;
+    let program_id = resolve_program_id(program_id)?;
+    Ok(
+        tally_sdk::pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id)
+            .to_string(),
+    )
+}
+
+/// Derive the global config PDA
+#[uniffi::export]
+pub fn config_address(program_id: Option<String>) -> Result<String, FfiError> {
+    let program_id = resolve_program_id(program_id)?;
+    Ok(tally_sdk::pda::config_address_with_program_id(&program_id).to_string())
+}
+
+/// Derive the delegate PDA that payers approve for token transfers
+#[uniffi::export]
+pub fn delegate_address(program_id: Option<String>) -> Result<String, FfiError> {
+    let program_id = resolve_program_id(program_id)?;
+    Ok(tally_sdk::pda::delegate_address_with_program_id(&program_id).to_string())
+}
+
+/// Build the approve → `start_agreement` instructions
+#[uniffi::export]
+pub fn build_start_agreement(args: FfiStartAgreement) -> Result<Vec<FfiInstruction>, FfiError> {
+    let payee: Payee = decode_account("payee", &args.payee_account_data)?;
+    let terms: PaymentTerms = decode_account("payment terms", &args.payment_terms_account_data)?;
+    let platform_treasury_ata = parse_address("platform_treasury_ata", &args.platform_treasury_ata)?;
+
+    let mut builder = start_agreement()
+        .payment_terms(parse_address("payment_terms", &args.payment_terms)?)
+        .payer(parse_address("payer", &args.payer)?)
+        .program_id(resolve_program_id(args.program_id)?)
+        .token_program(token_program(args.token_2022));
+    if let Some(allowance_periods) = args.allowance_periods {
+        builder = builder.allowance_periods(allowance_periods);
+    }
+    if let Some(max_periods) = args.max_periods {
+        builder = builder.max_periods(max_periods);
+    }
+
+    let instructions = builder.build_instructions(&payee, &terms, &platform_treasury_ata)?;
+    Ok(instructions.into_iter().map(FfiInstruction::from).collect())
+}
+
+/// Build the `pause_agreement` instructions
+#[uniffi::export]
+pub fn build_pause_agreement(args: FfiPauseAgreement) -> Result<Vec<FfiInstruction>, FfiError> {
+    let payee: Payee = decode_account("payee", &args.payee_account_data)?;
+
+    let instructions = pause_agreement()
+        .payment_terms(parse_address("payment_terms", &args.payment_terms)?)
+        .payer(parse_address("payer", &args.payer)?)
+        .program_id(resolve_program_id(args.program_id)?)
+        .token_program(token_program(args.token_2022))
+        .build_instructions(&payee)?;
+    Ok(instructions.into_iter().map(FfiInstruction::from).collect())
+}
+
+/// Parse Tally events from a transaction's log messages
+#[uniffi::export]
+pub fn parse_events(logs: Vec<String>, program_id: Option<String>) -> Result<Vec<FfiEvent>, FfiError> {
+    let program_id = resolve_program_id(program_id)?;
+    tally_sdk::events::parse_events_from_logs(&logs, &program_id)?
+        .iter()
+        .map(|event| {
+            let value = serde_json::to_value(event).map_err(|e| FfiError::Sdk {
+                message: format!("Failed to encode event: {e}"),
+            })?;
+            // Externally tagged: {"EventName": {...fields}}
+            let serde_json::Value::Object(tagged) = value else {
+                return Err(FfiError::Sdk {
+                    message: "Unexpected event encoding".to_string(),
+                });
+            };
+            let (name, fields) = tagged.into_iter().next().ok_or_else(|| FfiError::Sdk {
+                message: "Unexpected event encoding".to_string(),
+            })?;
+            Ok(FfiEvent {
+                name,
+                json: encode_addresses(fields).to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_address(field: &str, value: &str) -> Result<Pubkey, FfiError> {
+    Pubkey::from_str(value).map_err(|e| FfiError::InvalidAddress {
+        message: format!("{field} '{value}': {e}"),
+    })
+}
+
+fn resolve_program_id(program_id: Option<String>) -> Result<Pubkey, FfiError> {
+    program_id.map_or_else(
+        || Ok(tally_sdk::program_id()),
+        |program_id| parse_address("program_id", &program_id),
+    )
+}
+
+const fn token_program(token_2022: bool) -> TokenProgram {
+    if token_2022 {
+        TokenProgram::Token2022
+    } else {
+        TokenProgram::Token
+    }
+}
+
+/// Decode an Anchor account, skipping the discriminator and ignoring trailing padding
+fn decode_account<T: AnchorDeserialize>(name: &str, data: &[u8]) -> Result<T, FfiError> {
+    let body = data.get(8..).ok_or_else(|| FfiError::InvalidAccountData {
+        message: format!("{name} account data is shorter than the discriminator"),
+    })?;
+    T::deserialize(&mut &body[..]).map_err(|e| FfiError::InvalidAccountData {
+        message: format!("{name}: {e}"),
+    })
+}
+
+/// Replace serialized `Pubkey` byte arrays with base58 strings
+///
+/// Event structs contain no other 32-byte arrays, so any array of exactly 32 bytes
+/// is an address.
+fn encode_addresses(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Array(items) => {
+            let bytes: Option<Vec<u8>> = items
+                .iter()
+                .map(|item| item.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                .collect();
+            bytes.and_then(|bytes| <[u8; 32]>::try_from(bytes).ok()).map_or_else(
+                || Value::Array(items.into_iter().map(encode_addresses).collect()),
+                |bytes| Value::String(Pubkey::from(bytes).to_string()),
+            )
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, field)| (key, encode_addresses(field)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tally_sdk::program_types::VolumeTier;
+    use anchor_lang::AnchorSerialize;
+    use base64::Engine;
+
+    fn payee() -> Payee {
+        let authority = Pubkey::new_unique();
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn terms(payee: Pubkey) -> PaymentTerms {
+        PaymentTerms {
+            payee,
+            terms_id: [0; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        }
+    }
+
+    /// Account data as stored on-chain: discriminator, borsh body, zero padding
+    fn account_data<T: AnchorSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![0u8; 8];
+        data.extend(account.try_to_vec().unwrap());
+        data.extend([0u8; 16]);
+        data
+    }
+
+    #[test]
+    fn test_addresses_match_pda_module() {
+        let program_id = tally_sdk::program_id();
+        let authority = Pubkey::new_unique();
+        let payee = tally_sdk::pda::payee_address_with_program_id(&authority, &program_id);
+
+        assert_eq!(payee_address(authority.to_string(), None).unwrap(), payee.to_string());
+        assert_eq!(
+            payment_terms_address(payee.to_string(), "premium".to_string(), None).unwrap(),
+            tally_sdk::pda::payment_terms_address_from_string_with_program_id(&payee, "premium", &program_id)
+                .to_string()
+        );
+        assert_eq!(
+            config_address(Some(program_id.to_string())).unwrap(),
+            tally_sdk::pda::config_address_with_program_id(&program_id).to_string()
+        );
+        assert!(matches!(
+            payee_address("not-an-address".to_string(), None),
+            Err(FfiError::InvalidAddress { .. })
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn test_build_start_and_pause_agreement() {
+        let payee = payee();
+        let payee_pda = tally_sdk::pda::payee_address_with_program_id(&payee.original_authority, &tally_sdk::program_id());
+        let payment_terms = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let start = build_start_agreement(FfiStartAgreement {
+            payment_terms: payment_terms.to_string(),
+            payer: payer.to_string(),
+            payee_account_data: account_data(&payee),
+            payment_terms_account_data: account_data(&terms(payee_pda)),
+            platform_treasury_ata: Pubkey::new_unique().to_string(),
+            allowance_periods: None,
+            max_periods: Some(12),
+            token_2022: false,
+            program_id: None,
+        })
+        .unwrap();
+        assert_eq!(start.len(), 2);
+        assert_eq!(start[1].program_id, tally_sdk::program_id().to_string());
+        assert!(start[1].accounts.iter().any(|meta| meta.pubkey == payer.to_string() && meta.is_signer));
+
+        let pause = build_pause_agreement(FfiPauseAgreement {
+            payment_terms: payment_terms.to_string(),
+            payer: payer.to_string(),
+            payee_account_data: account_data(&payee),
+            token_2022: false,
+            program_id: None,
+        })
+        .unwrap();
+        assert_eq!(pause.len(), 1);
+        assert_eq!(pause[0].program_id, tally_sdk::program_id().to_string());
+    }
+
+    #[test]
+    fn test_build_start_agreement_rejects_bad_input() {
+        let args = FfiStartAgreement {
+            payment_terms: Pubkey::new_unique().to_string(),
+            payer: Pubkey::new_unique().to_string(),
+            payee_account_data: vec![0; 4],
+            payment_terms_account_data: Vec::new(),
+            platform_treasury_ata: Pubkey::new_unique().to_string(),
+            allowance_periods: None,
+            max_periods: None,
+            token_2022: false,
+            program_id: None,
+        };
+        assert!(matches!(
+            build_start_agreement(args.clone()),
+            Err(FfiError::InvalidAccountData { .. })
+        ));
+
+        let payee = payee();
+        let result = build_start_agreement(FfiStartAgreement {
+            payee_account_data: account_data(&payee),
+            payment_terms_account_data: account_data(&terms(Pubkey::new_unique())),
+            max_periods: Some(1),
+            ..args
+        });
+        assert!(matches!(result, Err(FfiError::Sdk { message }) if message.contains("Max periods")));
+    }
+
+    #[test]
+    fn test_parse_events_encodes_addresses() {
+        let program_id = tally_sdk::program_id();
+        let event = tally_sdk::events::PaymentAgreementStarted {
+            payee: Pubkey::new_unique(),
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            amount: 10_000_000,
+            external_ref_hash: None,
+            delegated_amount: 0,
+        };
+        let mut data =
+            anchor_lang::solana_program::hash::hash(b"event:PaymentAgreementStarted").to_bytes()[..8].to_vec();
+        data.extend(event.try_to_vec().unwrap());
+        let logs = vec![format!(
+            "Program data: {program_id} {}",
+            base64::prelude::BASE64_STANDARD.encode(data)
+        )];
+
+        let events = parse_events(logs, None).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "PaymentAgreementStarted");
+
+        let fields: serde_json::Value = serde_json::from_str(&events[0].json).unwrap();
+        assert_eq!(fields["payer"], event.payer.to_string());
+        assert_eq!(fields["amount"], 10_000_000);
+    }
+}
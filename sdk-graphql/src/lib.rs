@@ -0,0 +1,410 @@
+//! Tally SDK GraphQL - `async-graphql` object types for dashboard data
+//!
+//! Mirrors of the SDK's dashboard types that derive `async-graphql` output types, so
+//! payee backends can return SDK data from their resolvers without writing a mapping
+//! layer.
+//! Each type converts from its dashboard counterpart with `From`. Addresses and
+//! signatures are exposed as base58 strings, amounts in USDC microlamports as in the
+//! source types, and event metadata as a list of key/value pairs sorted by key.
+//!
+//! ```no_run
+//! use async_graphql::{Context, Object};
+//! use tally_sdk_graphql as graphql;
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn overview(&self, ctx: &Context<'_>) -> graphql::Overview {
+//!         let overview = ctx.data_unchecked::<tally_sdk::dashboard_types::Overview>();
+//!         graphql::Overview::from(overview)
+//!     }
+//! }
+//! ```
+//!
+//! The types live in their own crate because the `async-graphql` derives allow
+//! `clippy::all` on their generated code, which the workspace's forbidden
+//! `missing_safety_doc` lint does not permit in `tally-sdk`.
+
+#![forbid(unsafe_code)]
+
+use async_graphql::{Enum, SimpleObject};
+use tally_sdk::dashboard_types as dashboard;
+
+/// Overview statistics for a payee dashboard
+#[allow(clippy::derive_partial_eq_without_eq)] // Contains f64 fields
+#[derive(Clone, Debug, PartialEq, SimpleObject)]
+pub struct Overview {
+    /// Total revenue earned (in USDC microlamports)
+    pub total_revenue: u64,
+    /// Number of active payment agreements
+    pub active_agreements: u32,
+    /// Number of inactive payment agreements
+    pub inactive_agreements: u32,
+    /// Total number of payment terms
+    pub total_payment_terms: u32,
+    /// Revenue this month (in USDC microlamports)
+    pub monthly_revenue: u64,
+    /// New payment agreements this month
+    pub monthly_new_agreements: u32,
+    /// Paused payment agreements this month
+    pub monthly_paused_agreements: u32,
+    /// Average revenue per payer (in USDC microlamports)
+    pub average_revenue_per_payer: u64,
+    /// Payee authority address
+    pub payee_authority: String,
+    /// USDC mint being used
+    pub usdc_mint: String,
+    /// Number of unique payers with at least one active payment agreement
+    pub active_payers: u32,
+    /// Recurring revenue of active agreements normalized to a 30-day month (in USDC microlamports)
+    pub monthly_recurring_revenue: u64,
+    /// Active payment agreements renewing within the next 7 days, soonest first
+    pub upcoming_renewals: Vec<UpcomingRenewal>,
+    /// Revenue charged to payers since the payee was created (in USDC microlamports)
+    pub lifetime_revenue: u64,
+    /// Renewal payments collected since the payee was created
+    pub lifetime_renewals: u64,
+    /// Share of inactive payment agreements as a percentage
+    pub churn_rate: f64,
+    /// Data source of the metrics that can be derived from event history
+    pub sources: OverviewSources,
+}
+
+impl From<&dashboard::Overview> for Overview {
+    fn from(overview: &dashboard::Overview) -> Self {
+        Self {
+            total_revenue: overview.total_revenue,
+            active_agreements: overview.active_agreements,
+            inactive_agreements: overview.inactive_agreements,
+            total_payment_terms: overview.total_payment_terms,
+            monthly_revenue: overview.monthly_revenue,
+            monthly_new_agreements: overview.monthly_new_agreements,
+            monthly_paused_agreements: overview.monthly_paused_agreements,
+            average_revenue_per_payer: overview.average_revenue_per_payer,
+            payee_authority: overview.payee_authority.to_string(),
+            usdc_mint: overview.usdc_mint.to_string(),
+            active_payers: overview.active_payers,
+            monthly_recurring_revenue: overview.monthly_recurring_revenue,
+            upcoming_renewals: overview
+                .upcoming_renewals
+                .iter()
+                .map(UpcomingRenewal::from)
+                .collect(),
+            lifetime_revenue: overview.lifetime_revenue,
+            lifetime_renewals: overview.lifetime_renewals,
+            churn_rate: overview.churn_rate(),
+            sources: overview.sources.into(),
+        }
+    }
+}
+
+/// Data source a dashboard metric was derived from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+pub enum MetricSource {
+    /// Derived from current on-chain account state
+    Snapshot,
+    /// Derived from program event history (RPC nodes may prune older history)
+    Events,
+}
+
+impl From<dashboard::MetricSource> for MetricSource {
+    fn from(source: dashboard::MetricSource) -> Self {
+        match source {
+            dashboard::MetricSource::Snapshot => Self::Snapshot,
+            dashboard::MetricSource::Events => Self::Events,
+        }
+    }
+}
+
+/// Data sources of the `Overview` metrics that prefer event history
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SimpleObject)]
+pub struct OverviewSources {
+    /// Source of `monthlyRevenue`
+    pub monthly_revenue: MetricSource,
+    /// Source of `monthlyNewAgreements` and `monthlyPausedAgreements`
+    pub monthly_activity: MetricSource,
+}
+
+impl From<dashboard::OverviewSources> for OverviewSources {
+    fn from(sources: dashboard::OverviewSources) -> Self {
+        Self {
+            monthly_revenue: sources.monthly_revenue.into(),
+            monthly_activity: sources.monthly_activity.into(),
+        }
+    }
+}
+
+/// Upcoming renewal of an active payment agreement
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub struct UpcomingRenewal {
+    /// Payment agreement PDA address
+    pub agreement_address: String,
+    /// Payment terms PDA address
+    pub payment_terms_address: String,
+    /// Payer who will be charged
+    pub payer: String,
+    /// Unix timestamp when the next payment becomes due
+    pub next_payment_ts: i64,
+    /// Expected payment amount (in USDC microlamports)
+    pub amount: u64,
+}
+
+impl From<&dashboard::UpcomingRenewal> for UpcomingRenewal {
+    fn from(renewal: &dashboard::UpcomingRenewal) -> Self {
+        Self {
+            agreement_address: renewal.agreement_address.to_string(),
+            payment_terms_address: renewal.payment_terms_address.to_string(),
+            payer: renewal.payer.to_string(),
+            next_payment_ts: renewal.next_payment_ts,
+            amount: renewal.amount,
+        }
+    }
+}
+
+/// Analytics data for specific payment terms
+#[allow(clippy::derive_partial_eq_without_eq)] // Contains f64 fields
+#[derive(Clone, Debug, PartialEq, SimpleObject)]
+pub struct PaymentTermsAnalytics {
+    /// Payment terms PDA address
+    pub payment_terms_address: String,
+    /// Price per billing period (in USDC microlamports)
+    pub amount_usdc: u64,
+    /// Billing period in seconds
+    pub period_secs: u64,
+    /// Maximum number of active agreements, if capped
+    pub max_subscribers: Option<u32>,
+    /// Unix timestamp from which the payment terms stop renewing, if deactivated
+    pub sunset_ts: Option<i64>,
+    /// Number of active payment agreements
+    pub active_count: u32,
+    /// Number of inactive payment agreements
+    pub inactive_count: u32,
+    /// Total revenue generated by these payment terms (in USDC microlamports)
+    pub total_revenue: u64,
+    /// Revenue this month (in USDC microlamports)
+    pub monthly_revenue: u64,
+    /// New payment agreements this month
+    pub monthly_new_agreements: u32,
+    /// Paused payment agreements this month
+    pub monthly_paused_agreements: u32,
+    /// Average payment agreement duration in days
+    pub average_duration_days: f64,
+    /// Conversion rate percentage (if applicable)
+    pub conversion_rate: Option<f64>,
+    /// Share of inactive payment agreements as a percentage
+    pub churn_rate: f64,
+    /// Net growth of payment agreements this month as a percentage
+    pub monthly_growth_rate: f64,
+}
+
+impl From<&dashboard::PaymentTermsAnalytics> for PaymentTermsAnalytics {
+    fn from(analytics: &dashboard::PaymentTermsAnalytics) -> Self {
+        Self {
+            payment_terms_address: analytics.payment_terms_address.to_string(),
+            amount_usdc: analytics.payment_terms.amount_usdc,
+            period_secs: analytics.payment_terms.period_secs,
+            max_subscribers: analytics.payment_terms.max_subscribers,
+            sunset_ts: analytics.payment_terms.sunset_ts,
+            active_count: analytics.active_count,
+            inactive_count: analytics.inactive_count,
+            total_revenue: analytics.total_revenue,
+            monthly_revenue: analytics.monthly_revenue,
+            monthly_new_agreements: analytics.monthly_new_agreements,
+            monthly_paused_agreements: analytics.monthly_paused_agreements,
+            average_duration_days: analytics.average_duration_days,
+            conversion_rate: analytics.conversion_rate,
+            churn_rate: analytics.churn_rate(),
+            monthly_growth_rate: analytics.monthly_growth_rate(),
+        }
+    }
+}
+
+/// Human-readable payment agreement status
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum)]
+pub enum AgreementStatus {
+    /// Payment agreement is active and current
+    Active,
+    /// Payment agreement is active but overdue (within grace period)
+    Overdue,
+    /// Payment agreement is inactive/paused
+    Inactive,
+    /// Payment agreement is expired (past grace period)
+    Expired,
+}
+
+impl From<&dashboard::AgreementStatus> for AgreementStatus {
+    fn from(status: &dashboard::AgreementStatus) -> Self {
+        match status {
+            dashboard::AgreementStatus::Active => Self::Active,
+            dashboard::AgreementStatus::Overdue => Self::Overdue,
+            dashboard::AgreementStatus::Inactive => Self::Inactive,
+            dashboard::AgreementStatus::Expired => Self::Expired,
+        }
+    }
+}
+
+/// Payment agreement details for dashboard display
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub struct DashboardAgreement {
+    /// Payment agreement PDA address
+    pub address: String,
+    /// Payment terms PDA address
+    pub payment_terms_address: String,
+    /// Payer's address
+    pub payer: String,
+    /// Human-readable status
+    pub status: AgreementStatus,
+    /// Unix timestamp of the next payment
+    pub next_payment_ts: i64,
+    /// Number of payments executed under this agreement
+    pub payment_count: u32,
+    /// Days until next renewal (if active)
+    pub days_until_renewal: Option<i64>,
+    /// Total amount paid over agreement lifetime (in USDC microlamports)
+    pub total_paid: u64,
+}
+
+impl From<&dashboard::DashboardAgreement> for DashboardAgreement {
+    fn from(agreement: &dashboard::DashboardAgreement) -> Self {
+        Self {
+            address: agreement.address.to_string(),
+            payment_terms_address: agreement.payment_terms_address.to_string(),
+            payer: agreement.payment_agreement.payer.to_string(),
+            status: (&agreement.status).into(),
+            next_payment_ts: agreement.payment_agreement.next_payment_ts,
+            payment_count: agreement.payment_agreement.payment_count,
+            days_until_renewal: agreement.days_until_renewal,
+            total_paid: agreement.total_paid,
+        }
+    }
+}
+
+/// Key/value pair of event metadata
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub struct MetadataEntry {
+    /// Metadata key
+    pub key: String,
+    /// Metadata value
+    pub value: String,
+}
+
+/// Program event for dashboard monitoring
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub struct DashboardEvent {
+    /// Event type, e.g. `PaymentExecuted`; other program events use their event name
+    pub event_type: String,
+    /// Payment terms address (if applicable)
+    pub payment_terms_address: Option<String>,
+    /// Payment agreement address (if applicable)
+    pub agreement_address: Option<String>,
+    /// Payer address (if applicable)
+    pub payer: Option<String>,
+    /// Amount involved (if applicable, in USDC microlamports)
+    pub amount: Option<u64>,
+    /// Transaction signature
+    pub transaction_signature: Option<String>,
+    /// Slot of the transaction, if the event was parsed with context
+    pub slot: Option<u64>,
+    /// Unix timestamp when the event occurred
+    pub timestamp: i64,
+    /// Additional event metadata, sorted by key
+    pub metadata: Vec<MetadataEntry>,
+}
+
+impl From<&dashboard::DashboardEvent> for DashboardEvent {
+    fn from(event: &dashboard::DashboardEvent) -> Self {
+        let event_type = match &event.event_type {
+            dashboard::DashboardEventType::AgreementStarted => "AgreementStarted",
+            dashboard::DashboardEventType::PaymentExecuted => "PaymentExecuted",
+            dashboard::DashboardEventType::AgreementPaused => "AgreementPaused",
+            dashboard::DashboardEventType::PaymentFailed => "PaymentFailed",
+            dashboard::DashboardEventType::PaymentTermsCreated => "PaymentTermsCreated",
+            dashboard::DashboardEventType::PaymentTermsUpdated => "PaymentTermsUpdated",
+            dashboard::DashboardEventType::FeesWithdrawn => "FeesWithdrawn",
+            dashboard::DashboardEventType::Other(name) => name,
+        };
+        let mut metadata: Vec<MetadataEntry> = event
+            .metadata
+            .iter()
+            .map(|(key, value)| MetadataEntry {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        metadata.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+
+        Self {
+            event_type: event_type.to_string(),
+            payment_terms_address: event.payment_terms_address.map(|key| key.to_string()),
+            agreement_address: event.agreement_address.map(|key| key.to_string()),
+            payer: event.payer.map(|key| key.to_string()),
+            amount: event.amount,
+            transaction_signature: event.transaction_signature.clone(),
+            slot: event.context.as_ref().map(|context| context.slot),
+            timestamp: event.timestamp,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_event_conversion() {
+        let payer = Pubkey::new_unique();
+        let event = dashboard::DashboardEvent {
+            event_type: dashboard::DashboardEventType::Other("DepositHeld".to_string()),
+            payment_terms_address: None,
+            agreement_address: None,
+            payer: Some(payer),
+            amount: Some(50_000_000),
+            transaction_signature: Some("sig".to_string()),
+            timestamp: 1_700_000_000,
+            metadata: HashMap::from([
+                ("total_held".to_string(), "50000000".to_string()),
+                ("payer".to_string(), payer.to_string()),
+            ]),
+            context: None,
+        };
+
+        let converted = DashboardEvent::from(&event);
+        assert_eq!(converted.event_type, "DepositHeld");
+        assert_eq!(converted.payer, Some(payer.to_string()));
+        assert_eq!(converted.slot, None);
+        let keys: Vec<_> = converted
+            .metadata
+            .iter()
+            .map(|entry| entry.key.as_str())
+            .collect();
+        assert_eq!(keys, ["payer", "total_held"]);
+    }
+
+    #[test]
+    fn test_schema_exposes_camel_case_fields() {
+        use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+        struct Query;
+
+        #[Object]
+        impl Query {
+            async fn renewal(&self) -> UpcomingRenewal {
+                UpcomingRenewal {
+                    agreement_address: Pubkey::default().to_string(),
+                    payment_terms_address: Pubkey::default().to_string(),
+                    payer: Pubkey::default().to_string(),
+                    next_payment_ts: 1_700_000_000,
+                    amount: 9_990_000,
+                }
+            }
+        }
+
+        let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+        assert!(sdl.contains("nextPaymentTs: Int!"));
+        assert!(sdl.contains("paymentTermsAddress: String!"));
+    }
+}
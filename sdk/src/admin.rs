@@ -11,7 +11,7 @@
 //! - **Configuration**: Initialize and update global protocol parameters
 //! - **Fee Management**: Withdraw accumulated platform fees
 //! - **Authority Transfer**: Securely transfer platform authority
-//! - **Emergency Controls**: Pause/unpause protocol operations
+//! - **Emergency Controls**: Pause/unpause protocol operations and freeze individual payees
 //!
 //! # Security
 //!
@@ -39,13 +39,16 @@
 
 // Re-export admin-related types from program_types
 pub use crate::program_types::{
-    AdminWithdrawFeesArgs, InitConfigArgs, UpdateConfigArgs,
+    AdminWithdrawFeesArgs, InitConfigArgs, SetFeeHolidayArgs, SetKeeperSolRateArgs,
+    UpdateConfigArgs,
 };
 
 // Re-export admin-related builders from transaction_builder
 pub use crate::transaction_builder::{
-    accept_authority, admin_withdraw_fees, cancel_authority_transfer, init_config, pause,
-    transfer_authority, unpause, update_config, AcceptAuthorityBuilder,
-    AdminWithdrawFeesBuilder, CancelAuthorityTransferBuilder, InitConfigBuilder, PauseBuilder,
-    TransferAuthorityBuilder, UnpauseBuilder, UpdateConfigBuilder,
+    accept_authority, admin_withdraw_fees, cancel_authority_transfer, freeze_payee, init_config,
+    pause, set_fee_holiday, set_keeper_sol_rate, transfer_authority, unfreeze_payee, unpause,
+    update_config, AcceptAuthorityBuilder, AdminWithdrawFeesBuilder,
+    CancelAuthorityTransferBuilder, FreezePayeeBuilder, InitConfigBuilder, PauseBuilder,
+    SetFeeHolidayBuilder, SetKeeperSolRateBuilder, TransferAuthorityBuilder,
+    UnfreezePayeeBuilder, UnpauseBuilder, UpdateConfigBuilder,
 };
@@ -0,0 +1,374 @@
+//! Address lookup tables (ALTs) for large Tally transactions
+//!
+//! Legacy transactions list every account key inline, so batched renewals and split
+//! payments quickly hit the transaction size limit. An address lookup table stores
+//! frequently referenced accounts on-chain (config, delegate and keeper fee vault PDAs,
+//! token program, USDC mint, treasury ATAs) and lets a v0 transaction reference each of
+//! them with a one-byte index instead of a 32-byte key.
+//!
+//! Typical flow:
+//! 1. [`create_lookup_table`] and [`extend_lookup_table`] with [`tally_lookup_addresses`]
+//! 2. Wait one slot for the table to activate, then fetch it with
+//!    [`SimpleTallyClient::get_address_lookup_table`](crate::SimpleTallyClient::get_address_lookup_table)
+//! 3. Assemble instructions from the transaction builders with [`VersionedTransactionBuilder`],
+//!    which compiles a v0 message whenever a lookup table is supplied
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use crate::program_types::Payee;
+use anchor_client::solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_address_lookup_table_interface::{instruction, state::AddressLookupTable};
+
+/// Maximum number of addresses added by a single `ExtendLookupTable` instruction
+///
+/// Keeps each extend instruction comfortably inside the transaction size limit.
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 30;
+
+/// Create a new lookup table owned by `authority`
+///
+/// `recent_slot` must be a recent finalized slot (e.g. from `SimpleTallyClient::get_slot`);
+/// it seeds the table address.
+///
+/// # Returns
+/// The create instruction and the new table's address
+#[must_use]
+pub fn create_lookup_table(authority: Pubkey, payer: Pubkey, recent_slot: u64) -> (Instruction, Pubkey) {
+    instruction::create_lookup_table(authority, payer, recent_slot)
+}
+
+/// Add addresses to a lookup table
+///
+/// Addresses are split across instructions of at most [`MAX_ADDRESSES_PER_EXTEND`]
+/// entries; submit them in separate transactions if they do not fit in one.
+#[must_use]
+pub fn extend_lookup_table(
+    lookup_table: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| {
+            instruction::extend_lookup_table(lookup_table, authority, Some(payer), chunk.to_vec())
+        })
+        .collect()
+}
+
+/// Accounts referenced by most Tally payment transactions
+///
+/// Includes the program, config, delegate and keeper fee vault PDAs, token program,
+/// platform treasury ATA, and each payee's PDA, USDC mint and treasury ATA, without
+/// duplicates.
+#[must_use]
+pub fn tally_lookup_addresses(
+    program_id: &Pubkey,
+    platform_treasury_ata: &Pubkey,
+    payees: &[Payee],
+) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        *program_id,
+        crate::pda::config_address_with_program_id(program_id),
+        crate::pda::delegate_address_with_program_id(program_id),
+        crate::pda::keeper_fee_vault_address_with_program_id(program_id),
+        spl_token::id(),
+        *platform_treasury_ata,
+    ];
+    for payee in payees {
+        addresses.extend([
+            crate::pda::payee_address_with_program_id(&payee.original_authority, program_id),
+            payee.usdc_mint,
+            payee.treasury_ata,
+        ]);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    addresses.retain(|address| seen.insert(*address));
+    addresses
+}
+
+/// Decode raw lookup table account data
+///
+/// # Errors
+/// Returns an error if the data is not an initialized lookup table
+pub fn decode_lookup_table(address: Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount> {
+    let table = AddressLookupTable::deserialize(data).map_err(|e| {
+        TallyError::ParseError(format!("Invalid address lookup table {address}: {e}"))
+    })?;
+
+    Ok(AddressLookupTableAccount {
+        key: address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Assembles builder instructions into an unsigned `VersionedTransaction`
+///
+/// Compiles a v0 message that resolves accounts through the supplied lookup tables, or
+/// a legacy message when no lookup table is supplied.
+#[derive(Clone, Debug, Default)]
+pub struct VersionedTransactionBuilder {
+    payer: Option<Pubkey>,
+    recent_blockhash: Option<Hash>,
+    instructions: Vec<Instruction>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl VersionedTransactionBuilder {
+    /// Create a new versioned transaction builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fee payer
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the recent blockhash
+    #[must_use]
+    pub const fn recent_blockhash(mut self, recent_blockhash: Hash) -> Self {
+        self.recent_blockhash = Some(recent_blockhash);
+        self
+    }
+
+    /// Append an instruction
+    #[must_use]
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Append several instructions
+    #[must_use]
+    pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Resolve accounts through a lookup table
+    #[must_use]
+    pub fn lookup_table(mut self, lookup_table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(lookup_table);
+        self
+    }
+
+    /// Build the unsigned transaction with placeholder signatures
+    ///
+    /// # Returns
+    /// * `Ok(VersionedTransaction)` - A v0 transaction if lookup tables were supplied,
+    ///   otherwise a legacy transaction
+    /// * `Err(TallyError)` - If required fields are missing or the message cannot be compiled
+    pub fn build(self) -> Result<VersionedTransaction> {
+        let payer = self.payer.ok_or("Payer not set")?;
+        let recent_blockhash = self.recent_blockhash.ok_or("Recent blockhash not set")?;
+        if self.instructions.is_empty() {
+            return Err("At least one instruction must be added".into());
+        }
+
+        let message = if self.lookup_tables.is_empty() {
+            VersionedMessage::Legacy(Message::new_with_blockhash(
+                &self.instructions,
+                Some(&payer),
+                &recent_blockhash,
+            ))
+        } else {
+            VersionedMessage::V0(
+                v0::Message::try_compile(
+                    &payer,
+                    &self.instructions,
+                    &self.lookup_tables,
+                    recent_blockhash,
+                )
+                .map_err(|e| TallyError::Generic(format!("Failed to compile v0 message: {e}")))?,
+            )
+        };
+
+        Ok(VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                usize::from(message.header().num_required_signatures)
+            ],
+            message,
+        })
+    }
+}
+
+/// Create a versioned transaction builder
+#[must_use]
+pub fn versioned_transaction() -> VersionedTransactionBuilder {
+    VersionedTransactionBuilder::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::instruction::AccountMeta;
+    use solana_address_lookup_table_interface::state::LookupTableMeta;
+    use std::borrow::Cow;
+
+    fn payee() -> Payee {
+        let authority = Pubkey::new_unique();
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    /// Instruction touching the shared accounts plus one unique writable account
+    fn payment_instruction(program_id: Pubkey, shared: &[Pubkey]) -> Instruction {
+        let mut accounts: Vec<AccountMeta> = shared
+            .iter()
+            .map(|address| AccountMeta::new_readonly(*address, false))
+            .collect();
+        accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+        Instruction {
+            program_id,
+            accounts,
+            data: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn test_tally_lookup_addresses_deduplicates() {
+        let program_id = crate::program_id();
+        let platform_treasury_ata = Pubkey::new_unique();
+        let first = payee();
+        let mut second = payee();
+        second.usdc_mint = first.usdc_mint;
+
+        let addresses = tally_lookup_addresses(&program_id, &platform_treasury_ata, &[first, second]);
+
+        // 6 shared accounts + (PDA, mint, treasury) + (PDA, treasury) with the mint shared
+        assert_eq!(addresses.len(), 11);
+        assert!(addresses.contains(&crate::pda::config_address_with_program_id(&program_id)));
+        assert!(addresses.contains(&crate::pda::delegate_address_with_program_id(&program_id)));
+        assert!(addresses.contains(&platform_treasury_ata));
+    }
+
+    #[test]
+    fn test_extend_lookup_table_chunks_addresses() {
+        let addresses: Vec<Pubkey> = (0..65).map(|_| Pubkey::new_unique()).collect();
+        let authority = Pubkey::new_unique();
+        let (create, table) = create_lookup_table(authority, authority, 1_000);
+
+        let extends = extend_lookup_table(table, authority, authority, &addresses);
+
+        assert_eq!(create.accounts[0].pubkey, table);
+        assert_eq!(extends.len(), 3);
+        assert!(extends.iter().all(|ix| ix.accounts[0].pubkey == table));
+    }
+
+    #[test]
+    fn test_decode_lookup_table() {
+        let address = Pubkey::new_unique();
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let data = AddressLookupTable {
+            meta: LookupTableMeta::new(Pubkey::new_unique()),
+            addresses: Cow::Owned(addresses.clone()),
+        }
+        .serialize_for_tests()
+        .unwrap();
+
+        let table = decode_lookup_table(address, &data).unwrap();
+        assert_eq!(table.key, address);
+        assert_eq!(table.addresses, addresses);
+
+        assert!(decode_lookup_table(address, &[0u8; 4]).is_err());
+    }
+
+    #[test]
+    fn test_build_without_lookup_table_is_legacy() {
+        let program_id = Pubkey::new_unique();
+        let transaction = versioned_transaction()
+            .payer(Pubkey::new_unique())
+            .recent_blockhash(Hash::new_unique())
+            .instruction(payment_instruction(program_id, &[]))
+            .build()
+            .unwrap();
+
+        assert!(matches!(transaction.message, VersionedMessage::Legacy(_)));
+        assert_eq!(transaction.signatures.len(), 1);
+    }
+
+    #[test]
+    fn test_build_with_lookup_table_shrinks_transaction() {
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let shared: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let instructions: Vec<Instruction> =
+            (0..4).map(|_| payment_instruction(program_id, &shared)).collect();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: shared.clone(),
+        };
+
+        let legacy = versioned_transaction()
+            .payer(payer)
+            .recent_blockhash(blockhash)
+            .instructions(instructions.clone())
+            .build()
+            .unwrap();
+        let v0 = versioned_transaction()
+            .payer(payer)
+            .recent_blockhash(blockhash)
+            .instructions(instructions)
+            .lookup_table(table.clone())
+            .build()
+            .unwrap();
+
+        let VersionedMessage::V0(message) = &v0.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(message.address_table_lookups[0].account_key, table.key);
+        assert_eq!(message.address_table_lookups[0].readonly_indexes.len(), shared.len());
+        assert!(message.account_keys.iter().all(|key| !shared.contains(key)));
+
+        let legacy_len = bincode::serialize(&legacy).unwrap().len();
+        let v0_len = bincode::serialize(&v0).unwrap().len();
+        assert!(v0_len + 250 < legacy_len, "v0: {v0_len}, legacy: {legacy_len}");
+    }
+
+    #[test]
+    fn test_build_missing_fields() {
+        let result = versioned_transaction().recent_blockhash(Hash::new_unique()).build();
+        assert!(result.unwrap_err().to_string().contains("Payer not set"));
+
+        let result = versioned_transaction()
+            .payer(Pubkey::new_unique())
+            .recent_blockhash(Hash::new_unique())
+            .build();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("At least one instruction must be added"));
+    }
+}
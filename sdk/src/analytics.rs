@@ -0,0 +1,631 @@
+//! Historical MRR and churn time series for a payee
+//!
+//! [`mrr_series`] and [`churn_series`] replay a payee's event history on top of its
+//! current agreement accounts and bucket the result by [`Resolution`], so dashboards
+//! chart the same numbers instead of each re-deriving them.
+//!
+//! Recurring revenue is the charged amount normalized to a 30-day month, like
+//! `Overview::monthly_recurring_revenue`. The replay follows these rules:
+//! - An agreement charged zero is trialing: it counts toward `trialing_agreements`
+//!   but not MRR, and cancelling it is not churn. Its first non-zero charge is new MRR.
+//! - Starting an agreement the payer had paused before is a reactivation, not new MRR.
+//! - A renewal charging a different amount (price or quantity change) is expansion or
+//!   contraction of the agreement's MRR.
+//! - Pausing or closing a paying agreement churns its MRR.
+//!
+//! RPC nodes prune transaction history, so agreements whose start predates the
+//! available events are seeded from their account: active since `created_ts` at the
+//! amount of their first known charge (or `last_amount`), and, if inactive with no
+//! pause event left, churned at `next_payment_ts`.
+
+#![forbid(unsafe_code)]
+
+use crate::{
+    dashboard::DashboardClient,
+    error::{Result, TallyError},
+    events::{ParsedEventWithContext, TallyEvent},
+    program_types::PaymentAgreement,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Width of the buckets of a time series
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resolution {
+    /// One bucket per day
+    Day,
+    /// One bucket per 7 days
+    Week,
+    /// One bucket per 30 days
+    Month,
+}
+
+impl Resolution {
+    /// Bucket width in seconds
+    #[must_use]
+    pub const fn secs(self) -> i64 {
+        match self {
+            Self::Day => 86_400,
+            Self::Week => 7 * 86_400,
+            Self::Month => 30 * 86_400,
+        }
+    }
+}
+
+/// Recurring revenue of one bucket
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MrrPoint {
+    /// Unix timestamp the bucket starts at (inclusive)
+    pub bucket_start: i64,
+    /// Monthly recurring revenue at the end of the bucket in USDC micro-units
+    pub mrr: u64,
+    /// Active agreements with a non-zero charge at the end of the bucket
+    pub paying_agreements: u32,
+    /// Active agreements charged zero at the end of the bucket
+    pub trialing_agreements: u32,
+    /// MRR from first paid charges, including converted trials
+    pub new_mrr: u64,
+    /// MRR from paused agreements that started again
+    pub reactivation_mrr: u64,
+    /// MRR added by renewals charging more than before
+    pub expansion_mrr: u64,
+    /// MRR removed by renewals charging less than before
+    pub contraction_mrr: u64,
+    /// MRR lost to paused or closed agreements
+    pub churned_mrr: u64,
+}
+
+/// Paying agreement churn of one bucket
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChurnPoint {
+    /// Unix timestamp the bucket starts at (inclusive)
+    pub bucket_start: i64,
+    /// Paying agreements at the start of the bucket
+    pub starting_agreements: u32,
+    /// Agreements that became paying for the first time, including converted trials
+    pub new_agreements: u32,
+    /// Paused agreements that started paying again
+    pub reactivated_agreements: u32,
+    /// Paying agreements paused or closed
+    pub churned_agreements: u32,
+    /// Trialing agreements paused or closed before their first paid charge
+    pub cancelled_trials: u32,
+    /// `churned_agreements` relative to `starting_agreements` in basis points
+    pub churn_rate_bps: u32,
+}
+
+/// Current agreement account together with the period of its payment terms
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AgreementSnapshot {
+    /// Payment agreement account data
+    pub agreement: PaymentAgreement,
+    /// `period_secs` of the agreement's payment terms
+    pub period_secs: u64,
+}
+
+/// Compute the MRR series of a payee between `from` and `to`
+///
+/// # Errors
+/// Returns an error if `from` is not before `to` or fetching accounts or events fails
+pub fn mrr_series(
+    dashboard: &DashboardClient,
+    payee: &Pubkey,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    resolution: Resolution,
+) -> Result<Vec<MrrPoint>> {
+    let (events, agreements) = fetch_history(dashboard, payee, from, to)?;
+    Ok(compute_mrr_series(&events, &agreements, from.timestamp(), to.timestamp(), resolution))
+}
+
+/// Compute the churn series of a payee between `from` and `to`
+///
+/// # Errors
+/// Returns an error if `from` is not before `to` or fetching accounts or events fails
+pub fn churn_series(
+    dashboard: &DashboardClient,
+    payee: &Pubkey,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    resolution: Resolution,
+) -> Result<Vec<ChurnPoint>> {
+    let (events, agreements) = fetch_history(dashboard, payee, from, to)?;
+    Ok(compute_churn_series(&events, &agreements, from.timestamp(), to.timestamp(), resolution))
+}
+
+/// Compute an MRR series from event history and current agreement accounts
+///
+/// Buckets start at `from` and the last one ends at or after `to`. Returns an empty
+/// series if `from` is not before `to`.
+#[must_use]
+pub fn compute_mrr_series(
+    events: &[ParsedEventWithContext],
+    agreements: &[AgreementSnapshot],
+    from: i64,
+    to: i64,
+    resolution: Resolution,
+) -> Vec<MrrPoint> {
+    replay(events, agreements, from, to, resolution)
+        .into_iter()
+        .map(|bucket| bucket.mrr)
+        .collect()
+}
+
+/// Compute a churn series from event history and current agreement accounts
+///
+/// Buckets start at `from` and the last one ends at or after `to`. Returns an empty
+/// series if `from` is not before `to`.
+#[must_use]
+pub fn compute_churn_series(
+    events: &[ParsedEventWithContext],
+    agreements: &[AgreementSnapshot],
+    from: i64,
+    to: i64,
+    resolution: Resolution,
+) -> Vec<ChurnPoint> {
+    replay(events, agreements, from, to, resolution)
+        .into_iter()
+        .map(|bucket| bucket.churn)
+        .collect()
+}
+
+fn fetch_history(
+    dashboard: &DashboardClient,
+    payee: &Pubkey,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<(Vec<ParsedEventWithContext>, Vec<AgreementSnapshot>)> {
+    if from >= to {
+        return Err(TallyError::Generic(format!(
+            "Invalid series range: from {from} is not before {to}"
+        )));
+    }
+    let agreements = dashboard
+        .scan_payee(payee)?
+        .into_iter()
+        .flat_map(|(_, payment_terms, agreements)| {
+            agreements.into_iter().map(move |(_, agreement)| AgreementSnapshot {
+                agreement,
+                period_secs: payment_terms.period_secs,
+            })
+        })
+        .collect();
+    // Events before `from` establish the state the series starts from
+    let events = dashboard.get_events_by_date_range_with_context(payee, DateTime::UNIX_EPOCH, to)?;
+    Ok((events, agreements))
+}
+
+/// Agreement state change derived from an event or an account
+#[derive(Clone, Copy, Debug)]
+enum Change {
+    /// Agreement started or resumed, charging `monthly`
+    Start { monthly: u64 },
+    /// Renewal charged, charging `monthly` from now on
+    Charge { monthly: u64 },
+    /// Agreement paused or closed
+    Stop,
+}
+
+#[derive(Clone, Copy)]
+struct Transition {
+    ts: i64,
+    key: (Pubkey, Pubkey),
+    change: Change,
+}
+
+/// State of one agreement during the replay
+#[derive(Clone, Copy, Default)]
+struct AgreementState {
+    /// Normalized monthly charge while active
+    active: Option<u64>,
+    /// Whether the agreement has ever paid a non-zero charge
+    has_paid: bool,
+}
+
+struct Bucket {
+    mrr: MrrPoint,
+    churn: ChurnPoint,
+}
+
+fn replay(
+    events: &[ParsedEventWithContext],
+    agreements: &[AgreementSnapshot],
+    from: i64,
+    to: i64,
+    resolution: Resolution,
+) -> Vec<Bucket> {
+    if from >= to {
+        return Vec::new();
+    }
+    let transitions = transitions(events, agreements);
+    let mut states: HashMap<(Pubkey, Pubkey), AgreementState> = HashMap::new();
+    let mut pending = transitions.iter().peekable();
+
+    // Everything before the first bucket only establishes the starting state
+    while let Some(transition) = pending.next_if(|transition| transition.ts < from) {
+        apply(&mut states, transition, None);
+    }
+
+    let mut buckets = Vec::new();
+    let mut bucket_start = from;
+    while bucket_start < to {
+        let bucket_end = bucket_start.saturating_add(resolution.secs());
+        let mut bucket = Bucket {
+            mrr: MrrPoint {
+                bucket_start,
+                ..MrrPoint::default()
+            },
+            churn: ChurnPoint {
+                bucket_start,
+                starting_agreements: count(&states, |monthly| monthly > 0),
+                ..ChurnPoint::default()
+            },
+        };
+        while let Some(transition) = pending.next_if(|transition| transition.ts < bucket_end) {
+            apply(&mut states, transition, Some(&mut bucket));
+        }
+        bucket.mrr.mrr = states
+            .values()
+            .filter_map(|state| state.active)
+            .fold(0u64, u64::saturating_add);
+        bucket.mrr.paying_agreements = count(&states, |monthly| monthly > 0);
+        bucket.mrr.trialing_agreements = count(&states, |monthly| monthly == 0);
+        bucket.churn.churn_rate_bps = churn_rate_bps(
+            bucket.churn.churned_agreements,
+            bucket.churn.starting_agreements,
+        );
+        buckets.push(bucket);
+        bucket_start = bucket_end;
+    }
+    buckets
+}
+
+/// Apply a transition, recording the movement in `bucket` if given
+fn apply(
+    states: &mut HashMap<(Pubkey, Pubkey), AgreementState>,
+    transition: &Transition,
+    bucket: Option<&mut Bucket>,
+) {
+    let state = states.entry(transition.key).or_default();
+    let mut discard = Bucket {
+        mrr: MrrPoint::default(),
+        churn: ChurnPoint::default(),
+    };
+    let bucket = bucket.unwrap_or(&mut discard);
+
+    match (transition.change, state.active) {
+        (Change::Start { monthly } | Change::Charge { monthly }, None) => {
+            if monthly > 0 {
+                if state.has_paid {
+                    bucket.mrr.reactivation_mrr = bucket.mrr.reactivation_mrr.saturating_add(monthly);
+                    bucket.churn.reactivated_agreements =
+                        bucket.churn.reactivated_agreements.saturating_add(1);
+                } else {
+                    bucket.mrr.new_mrr = bucket.mrr.new_mrr.saturating_add(monthly);
+                    bucket.churn.new_agreements = bucket.churn.new_agreements.saturating_add(1);
+                }
+                state.has_paid = true;
+            }
+            state.active = Some(monthly);
+        }
+        (Change::Start { monthly } | Change::Charge { monthly }, Some(previous)) => {
+            if previous == 0 && monthly > 0 && !state.has_paid {
+                // Trial converted to a paying agreement
+                bucket.mrr.new_mrr = bucket.mrr.new_mrr.saturating_add(monthly);
+                bucket.churn.new_agreements = bucket.churn.new_agreements.saturating_add(1);
+            } else if monthly > previous {
+                bucket.mrr.expansion_mrr =
+                    bucket.mrr.expansion_mrr.saturating_add(monthly.saturating_sub(previous));
+            } else {
+                bucket.mrr.contraction_mrr =
+                    bucket.mrr.contraction_mrr.saturating_add(previous.saturating_sub(monthly));
+            }
+            state.has_paid |= monthly > 0;
+            state.active = Some(monthly);
+        }
+        (Change::Stop, Some(previous)) => {
+            if previous > 0 {
+                bucket.mrr.churned_mrr = bucket.mrr.churned_mrr.saturating_add(previous);
+                bucket.churn.churned_agreements = bucket.churn.churned_agreements.saturating_add(1);
+            } else {
+                bucket.churn.cancelled_trials = bucket.churn.cancelled_trials.saturating_add(1);
+            }
+            state.active = None;
+        }
+        (Change::Stop, None) => {}
+    }
+}
+
+/// Time-ordered transitions of every agreement, seeded from accounts where history is missing
+fn transitions(
+    events: &[ParsedEventWithContext],
+    agreements: &[AgreementSnapshot],
+) -> Vec<Transition> {
+    let periods: HashMap<Pubkey, u64> = agreements
+        .iter()
+        .map(|snapshot| (snapshot.agreement.payment_terms, snapshot.period_secs))
+        .collect();
+    let monthly = |payment_terms: &Pubkey, amount: u64| {
+        periods
+            .get(payment_terms)
+            .map(|period_secs| DashboardClient::normalize_to_month(amount, *period_secs))
+    };
+
+    let mut transitions: Vec<Transition> = events
+        .iter()
+        .filter(|event| event.success)
+        .filter_map(|event| {
+            let ts = event.block_time?;
+            let (key, change) = match &event.event {
+                TallyEvent::PaymentAgreementStarted(e) => (
+                    (e.payment_terms, e.payer),
+                    Change::Start { monthly: monthly(&e.payment_terms, e.amount)? },
+                ),
+                TallyEvent::PaymentAgreementResumed(e) => (
+                    (e.payment_terms, e.payer),
+                    Change::Start { monthly: monthly(&e.payment_terms, e.amount)? },
+                ),
+                TallyEvent::PaymentExecuted(e) => (
+                    (e.payment_terms, e.payer),
+                    Change::Charge { monthly: monthly(&e.payment_terms, e.amount)? },
+                ),
+                TallyEvent::PaymentAgreementPaused(e) => ((e.payment_terms, e.payer), Change::Stop),
+                TallyEvent::PaymentAgreementClosed(e) => ((e.payment_terms, e.payer), Change::Stop),
+                _ => return None,
+            };
+            Some(Transition { ts, key, change })
+        })
+        .collect();
+    transitions.sort_by_key(|transition| transition.ts);
+
+    let mut seeded = Vec::new();
+    for snapshot in agreements {
+        let agreement = &snapshot.agreement;
+        let key = (agreement.payment_terms, agreement.payer);
+        let mut history = transitions.iter().filter(|transition| transition.key == key);
+        let first = history.next();
+        if matches!(first, Some(Transition { change: Change::Start { .. }, .. })) {
+            continue;
+        }
+
+        // History of this agreement starts after it was created
+        let monthly = match first {
+            Some(Transition { change: Change::Charge { monthly }, .. }) => *monthly,
+            _ => DashboardClient::normalize_to_month(agreement.last_amount, snapshot.period_secs),
+        };
+        seeded.push(Transition {
+            ts: agreement.created_ts,
+            key,
+            change: Change::Start { monthly },
+        });
+        let stopped = transitions
+            .iter()
+            .any(|transition| transition.key == key && matches!(transition.change, Change::Stop));
+        if !agreement.active && !stopped {
+            seeded.push(Transition {
+                ts: agreement.next_payment_ts,
+                key,
+                change: Change::Stop,
+            });
+        }
+    }
+    transitions.extend(seeded);
+    // Stable sort keeps event order within a timestamp
+    transitions.sort_by_key(|transition| transition.ts);
+    transitions
+}
+
+fn count(states: &HashMap<(Pubkey, Pubkey), AgreementState>, filter: impl Fn(u64) -> bool) -> u32 {
+    let count = states
+        .values()
+        .filter(|state| state.active.is_some_and(&filter))
+        .count();
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+fn churn_rate_bps(churned: u32, starting: u32) -> u32 {
+    (u64::from(churned) * 10_000)
+        .checked_div(u64::from(starting))
+        .map_or(0, |rate| u32::try_from(rate).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{PaymentAgreementPaused, PaymentAgreementStarted, PaymentExecuted};
+    use anchor_client::solana_sdk::signature::Signature;
+
+    const DAY: i64 = 86_400;
+    const START: i64 = 1_700_000_000;
+    const MONTH: u64 = 2_592_000;
+    const TEN_USDC: u64 = 10_000_000;
+
+    fn event(block_time: i64, event: TallyEvent) -> ParsedEventWithContext {
+        ParsedEventWithContext {
+            signature: Signature::default(),
+            slot: 0,
+            block_time: Some(block_time),
+            success: true,
+            event,
+            log_index: 0,
+            instruction_index: None,
+            cluster: None,
+        }
+    }
+
+    fn started(payment_terms: Pubkey, payer: Pubkey, amount: u64) -> TallyEvent {
+        TallyEvent::PaymentAgreementStarted(PaymentAgreementStarted {
+            payee: Pubkey::default(),
+            payment_terms,
+            payer,
+            amount,
+            external_ref_hash: None,
+            delegated_amount: 0,
+        })
+    }
+
+    fn executed(payment_terms: Pubkey, payer: Pubkey, amount: u64) -> TallyEvent {
+        TallyEvent::PaymentExecuted(PaymentExecuted {
+            payee: Pubkey::default(),
+            payment_terms,
+            payer,
+            amount,
+            keeper: Pubkey::default(),
+            keeper_fee: 0,
+            period_index: 1,
+        })
+    }
+
+    fn paused(payment_terms: Pubkey, payer: Pubkey) -> TallyEvent {
+        TallyEvent::PaymentAgreementPaused(PaymentAgreementPaused {
+            payee: Pubkey::default(),
+            payment_terms,
+            payer,
+        })
+    }
+
+    const fn snapshot(
+        payment_terms: Pubkey,
+        payer: Pubkey,
+        active: bool,
+        created_ts: i64,
+        last_amount: u64,
+    ) -> AgreementSnapshot {
+        AgreementSnapshot {
+            agreement: PaymentAgreement {
+                payment_terms,
+                payer,
+                next_payment_ts: created_ts.saturating_add(2 * 2_592_000),
+                active,
+                payment_count: 1,
+                created_ts,
+                last_amount,
+                last_payment_ts: created_ts.saturating_add(2_592_000),
+                last_pull_period_index: 1,
+                cancel_at_period_end: false,
+                pending_payer: None,
+                refunded_amount: 0,
+                max_periods: None,
+                periods_paid: 2,
+                external_ref_hash: None,
+                paused_at_ts: None,
+                credit_amount: 0,
+                escrow: None,
+                period_index: 0,
+                suspension: None,
+                bump: 255,
+                deposit_held: 0,
+                version: 1,
+                last_sweep_ts: 0,
+            },
+            period_secs: MONTH,
+        }
+    }
+
+    #[test]
+    fn test_mrr_series_tracks_new_expansion_and_churn() {
+        let terms = Pubkey::new_unique();
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let events = vec![
+            event(START + DAY, started(terms, alice, TEN_USDC)),
+            event(START + 2 * DAY, started(terms, bob, TEN_USDC)),
+            // Alice doubles her quantity at renewal
+            event(START + 8 * DAY, executed(terms, alice, 2 * TEN_USDC)),
+            event(START + 9 * DAY, paused(terms, bob)),
+        ];
+        let agreements = [
+            snapshot(terms, alice, true, START + DAY, 2 * TEN_USDC),
+            snapshot(terms, bob, false, START + 2 * DAY, TEN_USDC),
+        ];
+
+        let series = compute_mrr_series(&events, &agreements, START, START + 14 * DAY, Resolution::Week);
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].bucket_start, START);
+        assert_eq!(series[0].mrr, 2 * TEN_USDC);
+        assert_eq!(series[0].new_mrr, 2 * TEN_USDC);
+        assert_eq!(series[0].paying_agreements, 2);
+        assert_eq!(series[1].mrr, 2 * TEN_USDC);
+        assert_eq!(series[1].expansion_mrr, TEN_USDC);
+        assert_eq!(series[1].churned_mrr, TEN_USDC);
+        assert_eq!(series[1].paying_agreements, 1);
+    }
+
+    #[test]
+    fn test_churn_series_counts_reactivations_separately() {
+        let terms = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let events = vec![
+            event(START + DAY, started(terms, payer, TEN_USDC)),
+            event(START + 3 * DAY, paused(terms, payer)),
+            event(START + 9 * DAY, started(terms, payer, TEN_USDC)),
+        ];
+        let agreements = [snapshot(terms, payer, true, START + DAY, TEN_USDC)];
+
+        let churn = compute_churn_series(&events, &agreements, START, START + 14 * DAY, Resolution::Week);
+        let mrr = compute_mrr_series(&events, &agreements, START, START + 14 * DAY, Resolution::Week);
+
+        assert_eq!(churn[0].new_agreements, 1);
+        assert_eq!(churn[0].churned_agreements, 1);
+        assert_eq!(churn[1].starting_agreements, 0);
+        assert_eq!(churn[1].new_agreements, 0);
+        assert_eq!(churn[1].reactivated_agreements, 1);
+        assert_eq!(mrr[1].reactivation_mrr, TEN_USDC);
+        assert_eq!(mrr[1].new_mrr, 0);
+    }
+
+    #[test]
+    fn test_trials_are_not_revenue_or_churn() {
+        let terms = Pubkey::new_unique();
+        let (converted, cancelled) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let events = vec![
+            event(START + DAY, started(terms, converted, 0)),
+            event(START + DAY, started(terms, cancelled, 0)),
+            event(START + 3 * DAY, paused(terms, cancelled)),
+            event(START + 8 * DAY, executed(terms, converted, TEN_USDC)),
+        ];
+        let agreements = [
+            snapshot(terms, converted, true, START + DAY, TEN_USDC),
+            snapshot(terms, cancelled, false, START + DAY, 0),
+        ];
+
+        let mrr = compute_mrr_series(&events, &agreements, START, START + 14 * DAY, Resolution::Week);
+        let churn = compute_churn_series(&events, &agreements, START, START + 14 * DAY, Resolution::Week);
+
+        assert_eq!(mrr[0].mrr, 0);
+        assert_eq!(mrr[0].trialing_agreements, 1);
+        assert_eq!(churn[0].churned_agreements, 0);
+        assert_eq!(churn[0].cancelled_trials, 1);
+        assert_eq!(mrr[1].new_mrr, TEN_USDC, "Converted trial is new MRR, not expansion");
+        assert_eq!(mrr[1].expansion_mrr, 0);
+        assert_eq!(churn[1].new_agreements, 1);
+    }
+
+    #[test]
+    fn test_pruned_history_seeded_from_accounts() {
+        let terms = Pubkey::new_unique();
+        let (active, stopped) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // Both agreements predate the available history; no events remain
+        let agreements = [
+            snapshot(terms, active, true, START - 90 * DAY, TEN_USDC),
+            snapshot(terms, stopped, false, START - 90 * DAY, TEN_USDC),
+        ];
+
+        let mrr = compute_mrr_series(&[], &agreements, START, START + 7 * DAY, Resolution::Week);
+        let churn = compute_churn_series(&[], &agreements, START, START + 7 * DAY, Resolution::Week);
+
+        // The inactive agreement churned at its next payment, before the series starts
+        assert_eq!(mrr[0].mrr, TEN_USDC);
+        assert_eq!(churn[0].starting_agreements, 1);
+        assert_eq!(churn[0].churned_agreements, 0);
+    }
+
+    #[test]
+    fn test_churn_rate_and_invalid_range() {
+        assert_eq!(churn_rate_bps(1, 4), 2_500);
+        assert_eq!(churn_rate_bps(1, 0), 0);
+        assert!(compute_mrr_series(&[], &[], START, START, Resolution::Day).is_empty());
+    }
+}
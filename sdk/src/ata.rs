@@ -211,6 +211,37 @@ pub fn create_associated_token_account_instruction(
     )
 }
 
+/// Create an instruction that creates an associated token account if it does not exist
+///
+/// Unlike [`create_associated_token_account_instruction`], the instruction succeeds
+/// when the account already exists, so it can be prepended to a transaction without
+/// checking the account first.
+///
+/// # Arguments
+/// * `payer` - The account that will pay for account creation
+/// * `wallet` - The wallet that will own the token account
+/// * `mint` - The token mint
+/// * `token_program` - The token program to use
+///
+/// # Returns
+/// * `Ok(anchor_client::solana_sdk::instruction::Instruction)` - The idempotent create ATA instruction
+/// * `Err(TallyError)` - If instruction creation fails
+pub fn create_associated_token_account_idempotent_instruction(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: TokenProgram,
+) -> Result<anchor_client::solana_sdk::instruction::Instruction> {
+    Ok(
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            payer,
+            wallet,
+            mint,
+            &token_program.program_id(),
+        ),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,593 @@
+//! Snapshots and structured diffs of platform configuration for change review
+//!
+//! [`snapshot`] captures the global `Config` account and every `Payee` account as
+//! canonical JSON, and [`diff`] compares two snapshots into a list of changes (fee
+//! changes, authority changes, pause status, payee freezes). Platform operators can
+//! store snapshots before and after a change, or around an incident, and review the
+//! diff instead of raw account data.
+//!
+//! Volume counters (`monthly_volume_usdc`, `last_volume_update_ts`) change with every
+//! payment and are deliberately left out of snapshots.
+
+use crate::{
+    error::Result,
+    program_types::{Config, Payee, VolumeTier},
+    SimpleTallyClient,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Point-in-time copy of the platform configuration and all payees
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditSnapshot {
+    /// Program the accounts belong to
+    pub program_id: String,
+    /// Slot at which the snapshot was taken
+    pub slot: u64,
+    /// Global configuration (`None` if the program is not initialized)
+    pub config: Option<ConfigSnapshot>,
+    /// Payees keyed by payee PDA address
+    pub payees: BTreeMap<String, PayeeSnapshot>,
+}
+
+/// Audited fields of the global `Config` account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    /// Platform authority
+    pub platform_authority: String,
+    /// Pending authority of an in-progress authority transfer
+    pub pending_authority: Option<String>,
+    /// Maximum platform fee in basis points
+    pub max_platform_fee_bps: u16,
+    /// Minimum platform fee in basis points
+    pub min_platform_fee_bps: u16,
+    /// Keeper fee in basis points
+    pub keeper_fee_bps: u16,
+    /// Minimum payment period in seconds
+    pub min_period_seconds: u64,
+    /// Default allowance periods multiplier
+    pub default_allowance_periods: u8,
+    /// Allowed token mint
+    pub allowed_mint: String,
+    /// Maximum withdrawal amount per transaction
+    pub max_withdrawal_amount: u64,
+    /// Maximum grace period in seconds
+    pub max_grace_period_seconds: u64,
+    /// Emergency pause state
+    pub paused: bool,
+    /// Renewal clock-skew tolerance in seconds (absent from snapshots taken before it existed)
+    #[serde(default)]
+    pub renewal_tolerance_secs: u64,
+}
+
+/// Audited fields of a `Payee` account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayeeSnapshot {
+    /// Payee authority
+    pub authority: String,
+    /// Pinned USDC mint
+    pub usdc_mint: String,
+    /// Treasury ATA receiving payee revenue
+    pub treasury_ata: String,
+    /// Current volume tier
+    pub volume_tier: VolumeTier,
+    /// Whether the platform authority has frozen the payee
+    pub frozen: bool,
+    /// Whether any keeper may execute the payee's payments
+    #[serde(default)]
+    pub open_execution: bool,
+    /// Keepers allowed to execute payments when execution is permissioned
+    #[serde(default)]
+    pub authorized_keepers: Vec<String>,
+}
+
+/// Structured change between two snapshots
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditChange {
+    /// The program's `Config` account was created
+    ConfigCreated,
+    /// The program's `Config` account was removed
+    ConfigRemoved,
+    /// A config fee (`max_platform_fee_bps`, `min_platform_fee_bps`, `keeper_fee_bps`) changed
+    FeeChanged {
+        /// Config field name
+        field: String,
+        /// Previous value in basis points
+        old: u16,
+        /// New value in basis points
+        new: u16,
+    },
+    /// The platform or pending authority changed
+    AuthorityChanged {
+        /// Config field name
+        field: String,
+        /// Previous authority
+        old: Option<String>,
+        /// New authority
+        new: Option<String>,
+    },
+    /// The program was paused or unpaused
+    PauseStatusChanged {
+        /// Previous pause state
+        old: bool,
+        /// New pause state
+        new: bool,
+    },
+    /// Any other config setting changed
+    ConfigSettingChanged {
+        /// Config field name
+        field: String,
+        /// Previous value
+        old: String,
+        /// New value
+        new: String,
+    },
+    /// A payee was registered
+    PayeeAdded {
+        /// Payee PDA address
+        payee: String,
+    },
+    /// A payee account no longer exists
+    PayeeRemoved {
+        /// Payee PDA address
+        payee: String,
+    },
+    /// A payee was frozen or unfrozen
+    PayeeFrozenChanged {
+        /// Payee PDA address
+        payee: String,
+        /// Previous frozen state
+        old: bool,
+        /// New frozen state
+        new: bool,
+    },
+    /// Any other payee field changed
+    PayeeFieldChanged {
+        /// Payee PDA address
+        payee: String,
+        /// Payee field name
+        field: String,
+        /// Previous value
+        old: String,
+        /// New value
+        new: String,
+    },
+}
+
+/// Change set between two snapshots
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditDiff {
+    /// Slot of the earlier snapshot
+    pub from_slot: u64,
+    /// Slot of the later snapshot
+    pub to_slot: u64,
+    /// Changes in config-then-payee order, payees sorted by address
+    pub changes: Vec<AuditChange>,
+}
+
+impl AuditDiff {
+    /// Whether the snapshots are identical
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl From<&Config> for ConfigSnapshot {
+    fn from(config: &Config) -> Self {
+        Self {
+            platform_authority: config.platform_authority.to_string(),
+            pending_authority: config.pending_authority.map(|key| key.to_string()),
+            max_platform_fee_bps: config.max_platform_fee_bps,
+            min_platform_fee_bps: config.min_platform_fee_bps,
+            keeper_fee_bps: config.keeper_fee_bps,
+            min_period_seconds: config.min_period_seconds,
+            default_allowance_periods: config.default_allowance_periods,
+            allowed_mint: config.allowed_mint.to_string(),
+            max_withdrawal_amount: config.max_withdrawal_amount,
+            max_grace_period_seconds: config.max_grace_period_seconds,
+            paused: config.paused,
+            renewal_tolerance_secs: config.renewal_tolerance_secs,
+        }
+    }
+}
+
+impl From<&Payee> for PayeeSnapshot {
+    fn from(payee: &Payee) -> Self {
+        Self {
+            authority: payee.authority.to_string(),
+            usdc_mint: payee.usdc_mint.to_string(),
+            treasury_ata: payee.treasury_ata.to_string(),
+            volume_tier: payee.volume_tier,
+            frozen: payee.frozen,
+            open_execution: payee.open_execution,
+            authorized_keepers: payee
+                .authorized_keepers
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl AuditSnapshot {
+    /// Build a snapshot from already-fetched accounts
+    #[must_use]
+    pub fn from_accounts(
+        program_id: &Pubkey,
+        slot: u64,
+        config: Option<&Config>,
+        payees: &[(Pubkey, Payee)],
+    ) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+            slot,
+            config: config.map(ConfigSnapshot::from),
+            payees: payees
+                .iter()
+                .map(|(address, payee)| (address.to_string(), PayeeSnapshot::from(payee)))
+                .collect(),
+        }
+    }
+
+    /// Serialize to canonical JSON
+    ///
+    /// Fields are written in declaration order and payees sorted by address, so equal
+    /// snapshots always produce identical output.
+    pub fn to_canonical_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a snapshot previously written by [`AuditSnapshot::to_canonical_json`]
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// Capture the `Config` account and all `Payee` accounts of the client's program
+///
+/// # Errors
+/// Returns an error if any RPC query fails or the config account can't be deserialized
+pub fn snapshot(client: &SimpleTallyClient) -> Result<AuditSnapshot> {
+    let slot = client.get_slot()?;
+    let config = client.get_config()?;
+    let payees = client.list_payees()?;
+
+    Ok(AuditSnapshot::from_accounts(
+        &client.program_id,
+        slot,
+        config.as_ref(),
+        &payees,
+    ))
+}
+
+/// Compare two snapshots, returning the changes from `before` to `after`
+#[must_use]
+pub fn diff(before: &AuditSnapshot, after: &AuditSnapshot) -> AuditDiff {
+    let mut changes = Vec::new();
+
+    match (&before.config, &after.config) {
+        (None, Some(_)) => changes.push(AuditChange::ConfigCreated),
+        (Some(_), None) => changes.push(AuditChange::ConfigRemoved),
+        (Some(old), Some(new)) => diff_config(old, new, &mut changes),
+        (None, None) => {}
+    }
+
+    for (address, old) in &before.payees {
+        match after.payees.get(address) {
+            Some(new) => diff_payee(address, old, new, &mut changes),
+            None => changes.push(AuditChange::PayeeRemoved {
+                payee: address.clone(),
+            }),
+        }
+    }
+    for address in after.payees.keys() {
+        if !before.payees.contains_key(address) {
+            changes.push(AuditChange::PayeeAdded {
+                payee: address.clone(),
+            });
+        }
+    }
+
+    AuditDiff {
+        from_slot: before.slot,
+        to_slot: after.slot,
+        changes,
+    }
+}
+
+fn diff_config(old: &ConfigSnapshot, new: &ConfigSnapshot, changes: &mut Vec<AuditChange>) {
+    let fees = [
+        ("max_platform_fee_bps", old.max_platform_fee_bps, new.max_platform_fee_bps),
+        ("min_platform_fee_bps", old.min_platform_fee_bps, new.min_platform_fee_bps),
+        ("keeper_fee_bps", old.keeper_fee_bps, new.keeper_fee_bps),
+    ];
+    for (field, old, new) in fees {
+        if old != new {
+            changes.push(AuditChange::FeeChanged {
+                field: field.to_string(),
+                old,
+                new,
+            });
+        }
+    }
+
+    let authorities = [
+        (
+            "platform_authority",
+            Some(&old.platform_authority),
+            Some(&new.platform_authority),
+        ),
+        (
+            "pending_authority",
+            old.pending_authority.as_ref(),
+            new.pending_authority.as_ref(),
+        ),
+    ];
+    for (field, old, new) in authorities {
+        if old != new {
+            changes.push(AuditChange::AuthorityChanged {
+                field: field.to_string(),
+                old: old.cloned(),
+                new: new.cloned(),
+            });
+        }
+    }
+
+    if old.paused != new.paused {
+        changes.push(AuditChange::PauseStatusChanged {
+            old: old.paused,
+            new: new.paused,
+        });
+    }
+
+    let settings = [
+        (
+            "min_period_seconds",
+            old.min_period_seconds.to_string(),
+            new.min_period_seconds.to_string(),
+        ),
+        (
+            "default_allowance_periods",
+            old.default_allowance_periods.to_string(),
+            new.default_allowance_periods.to_string(),
+        ),
+        ("allowed_mint", old.allowed_mint.clone(), new.allowed_mint.clone()),
+        (
+            "max_withdrawal_amount",
+            old.max_withdrawal_amount.to_string(),
+            new.max_withdrawal_amount.to_string(),
+        ),
+        (
+            "max_grace_period_seconds",
+            old.max_grace_period_seconds.to_string(),
+            new.max_grace_period_seconds.to_string(),
+        ),
+        (
+            "renewal_tolerance_secs",
+            old.renewal_tolerance_secs.to_string(),
+            new.renewal_tolerance_secs.to_string(),
+        ),
+    ];
+    for (field, old, new) in settings {
+        if old != new {
+            changes.push(AuditChange::ConfigSettingChanged {
+                field: field.to_string(),
+                old,
+                new,
+            });
+        }
+    }
+}
+
+fn diff_payee(
+    address: &str,
+    old: &PayeeSnapshot,
+    new: &PayeeSnapshot,
+    changes: &mut Vec<AuditChange>,
+) {
+    if old.frozen != new.frozen {
+        changes.push(AuditChange::PayeeFrozenChanged {
+            payee: address.to_string(),
+            old: old.frozen,
+            new: new.frozen,
+        });
+    }
+
+    let fields = [
+        ("authority", old.authority.clone(), new.authority.clone()),
+        ("usdc_mint", old.usdc_mint.clone(), new.usdc_mint.clone()),
+        ("treasury_ata", old.treasury_ata.clone(), new.treasury_ata.clone()),
+        (
+            "volume_tier",
+            format!("{:?}", old.volume_tier),
+            format!("{:?}", new.volume_tier),
+        ),
+        (
+            "open_execution",
+            old.open_execution.to_string(),
+            new.open_execution.to_string(),
+        ),
+        (
+            "authorized_keepers",
+            old.authorized_keepers.join(","),
+            new.authorized_keepers.join(","),
+        ),
+    ];
+    for (field, old, new) in fields {
+        if old != new {
+            changes.push(AuditChange::PayeeFieldChanged {
+                payee: address.to_string(),
+                field: field.to_string(),
+                old,
+                new,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            platform_authority: Pubkey::new_unique(),
+            pending_authority: None,
+            max_platform_fee_bps: 50,
+            min_platform_fee_bps: 10,
+            min_period_seconds: 86_400,
+            default_allowance_periods: 3,
+            allowed_mint: Pubkey::new_unique(),
+            max_withdrawal_amount: 1_000_000_000,
+            max_grace_period_seconds: 604_800,
+            paused: false,
+            keeper_fee_bps: 25,
+            pause_reason: [0; 64],
+            auto_unpause_ts: None,
+            keeper_sol_rate: None,
+            renewal_tolerance_secs: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn payee() -> Payee {
+        let authority = Pubkey::new_unique();
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_no_changes() {
+        let program_id = Pubkey::new_unique();
+        let config = config();
+        let payees = vec![(Pubkey::new_unique(), payee())];
+
+        let before = AuditSnapshot::from_accounts(&program_id, 1, Some(&config), &payees);
+        let after = AuditSnapshot::from_accounts(&program_id, 2, Some(&config), &payees);
+
+        let diff = diff(&before, &after);
+        assert!(diff.is_empty());
+        assert_eq!((diff.from_slot, diff.to_slot), (1, 2));
+    }
+
+    #[test]
+    fn test_diff_config_changes() {
+        let program_id = Pubkey::new_unique();
+        let old_config = config();
+        let mut new_config = old_config.clone();
+        new_config.keeper_fee_bps = 30;
+        new_config.pending_authority = Some(Pubkey::new_unique());
+        new_config.paused = true;
+        new_config.max_withdrawal_amount = 5_000_000_000;
+
+        let before = AuditSnapshot::from_accounts(&program_id, 1, Some(&old_config), &[]);
+        let after = AuditSnapshot::from_accounts(&program_id, 2, Some(&new_config), &[]);
+
+        assert_eq!(
+            diff(&before, &after).changes,
+            vec![
+                AuditChange::FeeChanged {
+                    field: "keeper_fee_bps".to_string(),
+                    old: 25,
+                    new: 30,
+                },
+                AuditChange::AuthorityChanged {
+                    field: "pending_authority".to_string(),
+                    old: None,
+                    new: new_config.pending_authority.map(|key| key.to_string()),
+                },
+                AuditChange::PauseStatusChanged {
+                    old: false,
+                    new: true,
+                },
+                AuditChange::ConfigSettingChanged {
+                    field: "max_withdrawal_amount".to_string(),
+                    old: "1000000000".to_string(),
+                    new: "5000000000".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_payee_changes() {
+        let program_id = Pubkey::new_unique();
+        let (kept, removed, added) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut frozen = payee();
+        let before = AuditSnapshot::from_accounts(
+            &program_id,
+            1,
+            None,
+            &[(kept, frozen.clone()), (removed, payee())],
+        );
+        frozen.frozen = true;
+        frozen.volume_tier = VolumeTier::Growth;
+        let after =
+            AuditSnapshot::from_accounts(&program_id, 2, None, &[(kept, frozen), (added, payee())]);
+
+        let changes = diff(&before, &after).changes;
+        assert_eq!(changes.len(), 4);
+        assert!(changes.contains(&AuditChange::PayeeFrozenChanged {
+            payee: kept.to_string(),
+            old: false,
+            new: true,
+        }));
+        assert!(changes.contains(&AuditChange::PayeeFieldChanged {
+            payee: kept.to_string(),
+            field: "volume_tier".to_string(),
+            old: "Standard".to_string(),
+            new: "Growth".to_string(),
+        }));
+        assert!(changes.contains(&AuditChange::PayeeRemoved {
+            payee: removed.to_string(),
+        }));
+        assert!(changes.contains(&AuditChange::PayeeAdded {
+            payee: added.to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_canonical_json_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let config = config();
+        let payees = vec![
+            (Pubkey::new_unique(), payee()),
+            (Pubkey::new_unique(), payee()),
+        ];
+        let mut reversed = payees.clone();
+        reversed.reverse();
+
+        let snapshot = AuditSnapshot::from_accounts(&program_id, 7, Some(&config), &payees);
+        let reordered = AuditSnapshot::from_accounts(&program_id, 7, Some(&config), &reversed);
+
+        // Account order doesn't affect the output
+        let json = snapshot.to_canonical_json().unwrap();
+        assert_eq!(json, reordered.to_canonical_json().unwrap());
+        assert_eq!(AuditSnapshot::from_json(&json).unwrap(), snapshot);
+    }
+}
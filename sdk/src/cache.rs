@@ -0,0 +1,427 @@
+//! Read-through cache for the accounts every instruction build fetches
+//!
+//! Keepers and dashboards fetch the same `Config`, `Payee` and `PaymentTerms`
+//! accounts for every agreement they touch. [`CachedClient`] serves repeated reads
+//! from an [`AccountCache`] instead of the RPC node. Entries expire after a TTL and
+//! are dropped early when an event that changes the account is observed, unless
+//! the entry was read at a later slot than the event.
+//!
+//! ```no_run
+//! # use tally_sdk::{solana_sdk::pubkey::Pubkey, SimpleTallyClient};
+//! # fn example(client: &SimpleTallyClient, payee_address: &Pubkey) -> tally_sdk::Result<()> {
+//! let cached = client.cached();
+//! let config = cached.get_config()?;
+//! let payee = cached.get_payee(payee_address)?; // RPC call
+//! let payee_again = cached.get_payee(payee_address)?; // served from the cache
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    error::{Result, TallyError},
+    events::{ParsedEventWithContext, TallyEvent},
+    program_types::{decode_versioned_account, Config, Payee, PaymentTerms, VersionedAccount},
+    simple_client::SimpleTallyClient,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default time an entry is served before it is fetched again
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+/// Default number of accounts kept before the least recently used is evicted
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Account cache settings
+#[derive(Clone, Copy, Debug)]
+pub struct AccountCacheConfig {
+    /// Time an entry is served before it is fetched again
+    pub ttl: Duration,
+    /// Number of accounts kept before the least recently used is evicted
+    pub capacity: NonZeroUsize,
+}
+
+impl Default for AccountCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_CACHE_TTL,
+            capacity: NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+/// Account held by the cache
+#[derive(Clone, Debug)]
+pub enum CachedAccount {
+    /// Global program configuration
+    Config(Config),
+    /// Payee account
+    Payee(Payee),
+    /// Payment terms account
+    PaymentTerms(PaymentTerms),
+}
+
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    account: CachedAccount,
+    /// Slot the RPC node read the account at
+    slot: u64,
+    fetched_at: Instant,
+}
+
+/// Shared store of decoded accounts with TTL and slot-based invalidation
+///
+/// Clones share the same entries, so one cache can back several clients.
+#[derive(Clone, Debug)]
+pub struct AccountCache {
+    program_id: Pubkey,
+    ttl: Duration,
+    entries: Arc<Mutex<LruCache<Pubkey, CacheEntry>>>,
+}
+
+impl AccountCache {
+    /// Create an empty cache for accounts of `program_id`
+    #[must_use]
+    pub fn new(program_id: Pubkey, config: AccountCacheConfig) -> Self {
+        Self {
+            program_id,
+            ttl: config.ttl,
+            entries: Arc::new(Mutex::new(LruCache::new(config.capacity))),
+        }
+    }
+
+    /// Store an account read at `slot`
+    pub fn insert(&self, address: Pubkey, account: CachedAccount, slot: u64) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.put(
+                address,
+                CacheEntry {
+                    account,
+                    slot,
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Cached account at `address`, unless it is missing or expired
+    #[must_use]
+    pub fn get(&self, address: &Pubkey) -> Option<CachedAccount> {
+        let mut entries = self.entries.lock().ok()?;
+        let expired = entries.get(address)?.fetched_at.elapsed() >= self.ttl;
+        if expired {
+            entries.pop(address);
+            return None;
+        }
+        entries.get(address).map(|entry| entry.account.clone())
+    }
+
+    /// Drop the entry for `address`
+    pub fn invalidate(&self, address: &Pubkey) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.pop(address);
+        }
+    }
+
+    /// Drop the entries `event`, landed at `slot`, may have changed
+    ///
+    /// Entries read after `slot` already reflect the event and are kept.
+    pub fn invalidate_for_event(&self, event: &TallyEvent, slot: u64) {
+        let affected = affected_accounts(event, &self.program_id);
+        if affected.is_empty() {
+            return;
+        }
+        if let Ok(mut entries) = self.entries.lock() {
+            for address in &affected {
+                if entries
+                    .peek(address)
+                    .is_some_and(|entry| entry.slot <= slot)
+                {
+                    entries.pop(address);
+                }
+            }
+        }
+    }
+
+    /// Drop the entries a parsed event may have changed, using its slot
+    pub fn observe(&self, event: &ParsedEventWithContext) {
+        self.invalidate_for_event(&event.event, event.slot);
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    /// Number of entries, including expired ones not yet dropped
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |entries| entries.len())
+    }
+
+    /// Whether the cache holds no entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Accounts whose state `event` reports a change to
+///
+/// Warnings only report state, so they invalidate nothing.
+fn affected_accounts(event: &TallyEvent, program_id: &Pubkey) -> Vec<Pubkey> {
+    match event {
+        TallyEvent::ConfigInitialized(_)
+        | TallyEvent::ConfigUpdated(_)
+        | TallyEvent::ProgramPaused(_)
+        | TallyEvent::ProgramUnpaused(_) => {
+            vec![crate::pda::config_address_with_program_id(program_id)]
+        }
+        TallyEvent::PayeeInitialized(e) => vec![e.payee],
+        TallyEvent::VolumeTierUpgraded(e) => vec![e.payee],
+        TallyEvent::PayeeAuthorityTransferInitiated(e) => vec![e.payee],
+        TallyEvent::PayeeAuthorityTransferred(e) => vec![e.payee],
+        TallyEvent::PayeeAuthorityTransferCancelled(e) => vec![e.payee],
+        TallyEvent::PaymentAgreementClosed(e) => vec![e.payment_terms],
+        TallyEvent::PaymentAgreementStarted(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentAgreementResumed(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentExecuted(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentAgreementPaused(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentFailed(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentTermsStatusChanged(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentTermsCreated(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::PaymentTermsUpdated(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::CreditApplied(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::TermsSunset(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::EscrowReleased(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::EscrowRefunded(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::KeeperPaidInSol(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::Swept(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::AgreementSuspended(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::AgreementUnsuspended(e) => vec![e.payee, e.payment_terms],
+        TallyEvent::LowAllowanceWarning(_)
+        | TallyEvent::DelegateMismatchWarning(_)
+        | TallyEvent::FeesWithdrawn(_)
+        | TallyEvent::DepositHeld(_)
+        | TallyEvent::DepositClaimed(_)
+        | TallyEvent::DepositReleased(_)
+        | TallyEvent::AgreementSnapshot(_) => Vec::new(),
+    }
+}
+
+/// [`SimpleTallyClient`] reads served from an [`AccountCache`] when fresh
+pub struct CachedClient<'a> {
+    client: &'a SimpleTallyClient,
+    cache: AccountCache,
+}
+
+impl<'a> CachedClient<'a> {
+    /// Wrap `client` with an existing, possibly shared, cache
+    #[must_use]
+    pub const fn with_cache(client: &'a SimpleTallyClient, cache: AccountCache) -> Self {
+        Self { client, cache }
+    }
+
+    /// The cache backing this client
+    #[must_use]
+    pub const fn cache(&self) -> &AccountCache {
+        &self.cache
+    }
+
+    /// Get the config account, from the cache when fresh
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the account can't be deserialized
+    pub fn get_config(&self) -> Result<Option<Config>> {
+        let address = crate::pda::config_address_with_program_id(&self.client.program_id);
+        if let Some(CachedAccount::Config(config)) = self.cache.get(&address) {
+            return Ok(Some(config));
+        }
+        self.fetch(&address, "config", CachedAccount::Config)
+    }
+
+    /// Get a payee account, from the cache when fresh
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the account can't be deserialized
+    pub fn get_payee(&self, payee_address: &Pubkey) -> Result<Option<Payee>> {
+        if let Some(CachedAccount::Payee(payee)) = self.cache.get(payee_address) {
+            return Ok(Some(payee));
+        }
+        self.fetch(payee_address, "payee", CachedAccount::Payee)
+    }
+
+    /// Get a payment terms account, from the cache when fresh
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the account can't be deserialized
+    pub fn get_payment_terms(
+        &self,
+        payment_terms_address: &Pubkey,
+    ) -> Result<Option<PaymentTerms>> {
+        if let Some(CachedAccount::PaymentTerms(terms)) = self.cache.get(payment_terms_address) {
+            return Ok(Some(terms));
+        }
+        self.fetch(
+            payment_terms_address,
+            "payment terms",
+            CachedAccount::PaymentTerms,
+        )
+    }
+
+    /// Fetch and decode an account, caching it when it exists
+    ///
+    /// Missing accounts are not cached so that a later creation is seen immediately.
+    fn fetch<T: VersionedAccount + Clone>(
+        &self,
+        address: &Pubkey,
+        label: &str,
+        wrap: fn(T) -> CachedAccount,
+    ) -> Result<Option<T>> {
+        let (slot, data) = self.client.get_account_data_with_slot(address)?;
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        if data.len() < 8 {
+            return Err(TallyError::Generic(format!("Invalid {label} account data")));
+        }
+        let account = decode_versioned_account::<T>(&data[8..])?;
+        self.cache.insert(*address, wrap(account.clone()), slot);
+        Ok(Some(account))
+    }
+}
+
+impl SimpleTallyClient {
+    /// Wrap this client with a fresh [`AccountCache`] using the default TTL
+    ///
+    /// Use [`CachedClient::with_cache`] to share one cache between clients.
+    #[must_use]
+    pub fn cached(&self) -> CachedClient<'_> {
+        CachedClient::with_cache(
+            self,
+            AccountCache::new(self.program_id, AccountCacheConfig::default()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{LowAllowanceWarning, PaymentExecuted, ProgramUnpaused};
+
+    fn terms() -> CachedAccount {
+        CachedAccount::PaymentTerms(PaymentTerms {
+            payee: Pubkey::new_unique(),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        })
+    }
+
+    fn executed(payee: Pubkey, payment_terms: Pubkey) -> TallyEvent {
+        TallyEvent::PaymentExecuted(PaymentExecuted {
+            payee,
+            payment_terms,
+            payer: Pubkey::new_unique(),
+            amount: 10_000_000,
+            keeper: Pubkey::new_unique(),
+            keeper_fee: 25_000,
+            period_index: 1,
+        })
+    }
+
+    fn cache(ttl: Duration) -> AccountCache {
+        AccountCache::new(
+            Pubkey::new_unique(),
+            AccountCacheConfig {
+                ttl,
+                ..AccountCacheConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let fresh = cache(DEFAULT_CACHE_TTL);
+        let address = Pubkey::new_unique();
+        fresh.insert(address, terms(), 10);
+        assert!(matches!(
+            fresh.get(&address),
+            Some(CachedAccount::PaymentTerms(_))
+        ));
+
+        let expired = cache(Duration::ZERO);
+        expired.insert(address, terms(), 10);
+        assert!(expired.get(&address).is_none());
+        assert!(expired.is_empty(), "Expired entries are dropped on read");
+    }
+
+    #[test]
+    fn test_event_invalidation_respects_slot() {
+        let cache = cache(DEFAULT_CACHE_TTL);
+        let payee = Pubkey::new_unique();
+        let stale_terms = Pubkey::new_unique();
+        let fresh_terms = Pubkey::new_unique();
+        cache.insert(stale_terms, terms(), 100);
+        cache.insert(fresh_terms, terms(), 200);
+
+        cache.invalidate_for_event(&executed(payee, stale_terms), 150);
+        cache.invalidate_for_event(&executed(payee, fresh_terms), 150);
+
+        assert!(cache.get(&stale_terms).is_none());
+        assert!(
+            cache.get(&fresh_terms).is_some(),
+            "Entries read after the event already reflect it"
+        );
+    }
+
+    #[test]
+    fn test_event_targets() {
+        let program_id = Pubkey::new_unique();
+        let config_address = crate::pda::config_address_with_program_id(&program_id);
+
+        let unpaused = TallyEvent::ProgramUnpaused(ProgramUnpaused {
+            authority: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+        });
+        assert_eq!(
+            affected_accounts(&unpaused, &program_id),
+            vec![config_address]
+        );
+
+        let warning = TallyEvent::LowAllowanceWarning(LowAllowanceWarning {
+            payee: Pubkey::new_unique(),
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            current_allowance: 0,
+            recommended_allowance: 20_000_000,
+            payment_amount: 10_000_000,
+        });
+        assert!(affected_accounts(&warning, &program_id).is_empty());
+
+        let cache = AccountCache::new(program_id, AccountCacheConfig::default());
+        let other = Pubkey::new_unique();
+        cache.insert(other, terms(), 1);
+        cache.invalidate_for_event(&unpaused, 5);
+        assert_eq!(cache.len(), 1, "Unrelated entries are kept");
+        cache.invalidate(&other);
+        assert!(cache.is_empty());
+    }
+}
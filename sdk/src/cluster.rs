@@ -0,0 +1,440 @@
+//! Cluster presets and well-known addresses
+//!
+//! Integrators used to hardcode the USDC mint, RPC endpoints and program ID per
+//! environment, which made it easy to pair devnet USDC with a mainnet deployment. A
+//! [`ClusterConfig`] starts from the preset for one [`Cluster`] and keeps every
+//! address consistent with it:
+//!
+//! ```no_run
+//! use tally_sdk::cluster::{Cluster, ClusterConfig};
+//!
+//! # fn main() -> tally_sdk::Result<()> {
+//! let config = ClusterConfig::preset(Cluster::Devnet)
+//!     .with_rpc_url("https://devnet.helius-rpc.com/?api-key=...");
+//! config.validate()?;
+//!
+//! let usdc_mint = config.usdc_mint()?;
+//! let client = config.client()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Deployments can also be configured from the environment with
+//! [`ClusterConfig::from_env`].
+
+#![forbid(unsafe_code)]
+
+use crate::{error::Result, SimpleTallyClient, TallyError};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Circle's official USDC mint on mainnet-beta
+pub const MAINNET_USDC_MINT: Pubkey =
+    anchor_lang::solana_program::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+/// Circle's official USDC mint on devnet
+pub const DEVNET_USDC_MINT: Pubkey =
+    anchor_lang::solana_program::pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU");
+
+/// Tally program deployed on devnet (`[programs.devnet]` in `Anchor.toml`)
+pub const DEVNET_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("6jsdZp5TovWbPGuXcKvnNaBZr1EBYwVTWXW1RhGa2JM5");
+
+/// Tally program deployed by `anchor localnet` (`[programs.localnet]` in `Anchor.toml`)
+pub const LOCALNET_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("Em6skegRoagqF9BG4CRfewVN8JebsyrEKGwzDfcnAXku");
+
+/// Solana cluster a client talks to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Cluster {
+    #[serde(rename = "mainnet-beta")]
+    Mainnet,
+    #[serde(rename = "devnet")]
+    Devnet,
+    #[serde(rename = "localnet")]
+    Localnet,
+}
+
+impl Cluster {
+    /// Public RPC endpoint of the cluster
+    #[must_use]
+    pub const fn rpc_url(self) -> &'static str {
+        match self {
+            Self::Mainnet => "https://api.mainnet-beta.solana.com",
+            Self::Devnet => "https://api.devnet.solana.com",
+            Self::Localnet => "http://127.0.0.1:8899",
+        }
+    }
+
+    /// Public websocket endpoint of the cluster
+    #[must_use]
+    pub const fn ws_url(self) -> &'static str {
+        match self {
+            Self::Mainnet => "wss://api.mainnet-beta.solana.com",
+            Self::Devnet => "wss://api.devnet.solana.com",
+            Self::Localnet => "ws://127.0.0.1:8900",
+        }
+    }
+
+    /// Official USDC mint, or `None` on localnet where tests create their own mint
+    #[must_use]
+    pub const fn usdc_mint(self) -> Option<Pubkey> {
+        match self {
+            Self::Mainnet => Some(MAINNET_USDC_MINT),
+            Self::Devnet => Some(DEVNET_USDC_MINT),
+            Self::Localnet => None,
+        }
+    }
+
+    /// Deployed Tally program, or `None` where no deployment is published
+    #[must_use]
+    pub const fn program_id(self) -> Option<Pubkey> {
+        match self {
+            Self::Mainnet => None,
+            Self::Devnet => Some(DEVNET_PROGRAM_ID),
+            Self::Localnet => Some(LOCALNET_PROGRAM_ID),
+        }
+    }
+
+    /// SPL Memo program, which is deployed at the same address on every cluster
+    #[must_use]
+    pub const fn memo_program_id(self) -> Pubkey {
+        spl_memo::ID
+    }
+
+    /// Best guess at the cluster behind an RPC endpoint, or `None` if the URL doesn't say
+    ///
+    /// Recognizes the public endpoints and the usual provider URLs, which name the
+    /// cluster in their host (`devnet.helius-rpc.com`, `solana-mainnet.g.alchemy.com`).
+    #[must_use]
+    pub fn from_rpc_url(rpc_url: &str) -> Option<Self> {
+        let url = rpc_url.to_ascii_lowercase();
+        if url.contains("devnet") {
+            Some(Self::Devnet)
+        } else if url.contains("mainnet") {
+            Some(Self::Mainnet)
+        } else if url.contains("127.0.0.1") || url.contains("localhost") {
+            Some(Self::Localnet)
+        } else {
+            None
+        }
+    }
+
+    const fn all() -> [Self; 3] {
+        [Self::Mainnet, Self::Devnet, Self::Localnet]
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Mainnet => "mainnet-beta",
+            Self::Devnet => "devnet",
+            Self::Localnet => "localnet",
+        })
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = TallyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Self::Mainnet),
+            "devnet" => Ok(Self::Devnet),
+            "localnet" | "localhost" => Ok(Self::Localnet),
+            other => Err(TallyError::Generic(format!(
+                "Unknown cluster '{other}', expected mainnet-beta, devnet or localnet"
+            ))),
+        }
+    }
+}
+
+/// Endpoints and addresses for one cluster, starting from its preset
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClusterConfig {
+    cluster: Cluster,
+    rpc_url: String,
+    ws_url: String,
+    usdc_mint: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+impl ClusterConfig {
+    /// Preset endpoints and addresses for `cluster`
+    #[must_use]
+    pub fn preset(cluster: Cluster) -> Self {
+        Self {
+            cluster,
+            rpc_url: cluster.rpc_url().to_string(),
+            ws_url: cluster.ws_url().to_string(),
+            usdc_mint: cluster.usdc_mint(),
+            program_id: cluster.program_id(),
+        }
+    }
+
+    /// Build a validated configuration from the environment
+    ///
+    /// `TALLY_CLUSTER` selects the preset; `TALLY_RPC_URL`, `TALLY_WS_URL`,
+    /// `TALLY_USDC_MINT` and `TALLY_PROGRAM_ID` override it when set.
+    ///
+    /// # Errors
+    /// Returns an error if `TALLY_CLUSTER` is missing or unknown, an address does not
+    /// parse, or the result fails [`ClusterConfig::validate`]
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let cluster: Cluster = lookup("TALLY_CLUSTER")
+            .ok_or("TALLY_CLUSTER not set")?
+            .parse()?;
+        let mut config = Self::preset(cluster);
+
+        if let Some(rpc_url) = lookup("TALLY_RPC_URL") {
+            config = config.with_rpc_url(rpc_url);
+        }
+        if let Some(ws_url) = lookup("TALLY_WS_URL") {
+            config = config.with_ws_url(ws_url);
+        }
+        if let Some(usdc_mint) = lookup("TALLY_USDC_MINT") {
+            config = config.with_usdc_mint(parse_pubkey("TALLY_USDC_MINT", &usdc_mint)?);
+        }
+        if let Some(program_id) = lookup("TALLY_PROGRAM_ID") {
+            config = config.with_program_id(parse_pubkey("TALLY_PROGRAM_ID", &program_id)?);
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Use a private RPC endpoint instead of the public one
+    #[must_use]
+    pub fn with_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = rpc_url.into();
+        self
+    }
+
+    /// Use a private websocket endpoint instead of the public one
+    #[must_use]
+    pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = ws_url.into();
+        self
+    }
+
+    /// Use a different USDC mint, such as a test mint on localnet
+    #[must_use]
+    pub const fn with_usdc_mint(mut self, usdc_mint: Pubkey) -> Self {
+        self.usdc_mint = Some(usdc_mint);
+        self
+    }
+
+    /// Use a different Tally program deployment
+    #[must_use]
+    pub const fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Cluster this configuration was built from
+    #[must_use]
+    pub const fn cluster(&self) -> Cluster {
+        self.cluster
+    }
+
+    /// RPC endpoint
+    #[must_use]
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Websocket endpoint
+    #[must_use]
+    pub fn ws_url(&self) -> &str {
+        &self.ws_url
+    }
+
+    /// SPL Memo program
+    #[must_use]
+    pub const fn memo_program_id(&self) -> Pubkey {
+        self.cluster.memo_program_id()
+    }
+
+    /// USDC mint for this cluster
+    ///
+    /// # Errors
+    /// Returns an error on localnet when no mint has been configured
+    pub fn usdc_mint(&self) -> Result<Pubkey> {
+        self.usdc_mint.ok_or_else(|| {
+            TallyError::Generic(format!(
+                "No USDC mint for {}, set one with with_usdc_mint",
+                self.cluster
+            ))
+        })
+    }
+
+    /// Tally program for this cluster
+    ///
+    /// # Errors
+    /// Returns an error when the cluster has no published deployment and none has been
+    /// configured
+    pub fn program_id(&self) -> Result<Pubkey> {
+        self.program_id.ok_or_else(|| {
+            TallyError::Generic(format!(
+                "No Tally program deployment known for {}, set one with with_program_id",
+                self.cluster
+            ))
+        })
+    }
+
+    /// Check that no address belongs to a different public cluster
+    ///
+    /// Localnet is exempt, since test validators commonly clone mainnet or devnet
+    /// accounts.
+    ///
+    /// # Errors
+    /// Returns an error if the USDC mint or program ID is the preset of another cluster
+    pub fn validate(&self) -> Result<()> {
+        if self.cluster == Cluster::Localnet {
+            return Ok(());
+        }
+
+        for other in Cluster::all() {
+            if other == self.cluster {
+                continue;
+            }
+            if self.usdc_mint.is_some() && self.usdc_mint == other.usdc_mint() {
+                return Err(TallyError::Generic(format!(
+                    "USDC mint {} belongs to {other}, not {}",
+                    other.usdc_mint().unwrap_or_default(),
+                    self.cluster
+                )));
+            }
+            if self.program_id.is_some() && self.program_id == other.program_id() {
+                return Err(TallyError::Generic(format!(
+                    "Program ID {} belongs to {other}, not {}",
+                    other.program_id().unwrap_or_default(),
+                    self.cluster
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a [`SimpleTallyClient`] for this cluster's RPC endpoint and program
+    ///
+    /// # Errors
+    /// Returns an error if no program ID is known for the cluster
+    pub fn client(&self) -> Result<SimpleTallyClient> {
+        SimpleTallyClient::new_with_program_id(&self.rpc_url, &self.program_id()?.to_string())
+    }
+}
+
+fn parse_pubkey(name: &str, value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value.trim())
+        .map_err(|e| TallyError::Generic(format!("Invalid {name} '{value}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_cluster_round_trips_through_display() {
+        for cluster in Cluster::all() {
+            assert_eq!(cluster.to_string().parse::<Cluster>().unwrap(), cluster);
+        }
+        assert_eq!("mainnet".parse::<Cluster>().unwrap(), Cluster::Mainnet);
+        assert!("testnet".parse::<Cluster>().is_err());
+    }
+
+    #[test]
+    fn test_cluster_from_rpc_url() {
+        for cluster in Cluster::all() {
+            assert_eq!(Cluster::from_rpc_url(cluster.rpc_url()), Some(cluster));
+            let json = serde_json::to_string(&cluster).unwrap();
+            assert_eq!(json, format!("\"{cluster}\""));
+        }
+        assert_eq!(
+            Cluster::from_rpc_url("https://devnet.helius-rpc.com/?api-key=abc"),
+            Some(Cluster::Devnet)
+        );
+        assert_eq!(
+            Cluster::from_rpc_url("http://localhost:8899"),
+            Some(Cluster::Localnet)
+        );
+        assert_eq!(Cluster::from_rpc_url("https://rpc.example.com"), None);
+    }
+
+    #[test]
+    fn test_presets() {
+        let mainnet = ClusterConfig::preset(Cluster::Mainnet);
+        assert_eq!(mainnet.usdc_mint().unwrap(), MAINNET_USDC_MINT);
+        assert!(mainnet.program_id().is_err());
+        assert_eq!(mainnet.memo_program_id(), spl_memo::ID);
+
+        let devnet = ClusterConfig::preset(Cluster::Devnet);
+        assert_eq!(devnet.usdc_mint().unwrap(), DEVNET_USDC_MINT);
+        assert_eq!(devnet.program_id().unwrap(), DEVNET_PROGRAM_ID);
+        assert_eq!(devnet.rpc_url(), "https://api.devnet.solana.com");
+
+        let localnet = ClusterConfig::preset(Cluster::Localnet);
+        assert!(localnet.usdc_mint().is_err());
+        assert_eq!(localnet.ws_url(), "ws://127.0.0.1:8900");
+
+        for cluster in Cluster::all() {
+            ClusterConfig::preset(cluster).validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_mixed_clusters() {
+        let devnet_usdc_on_mainnet = ClusterConfig::preset(Cluster::Mainnet)
+            .with_usdc_mint(DEVNET_USDC_MINT)
+            .with_program_id(Pubkey::new_unique());
+        assert!(devnet_usdc_on_mainnet.validate().is_err());
+
+        let devnet_program_on_mainnet =
+            ClusterConfig::preset(Cluster::Mainnet).with_program_id(DEVNET_PROGRAM_ID);
+        assert!(devnet_program_on_mainnet.validate().is_err());
+
+        let mainnet_usdc_on_localnet =
+            ClusterConfig::preset(Cluster::Localnet).with_usdc_mint(MAINNET_USDC_MINT);
+        mainnet_usdc_on_localnet.validate().unwrap();
+    }
+
+    #[test]
+    fn test_from_lookup_applies_overrides() {
+        let program_id = Pubkey::new_unique();
+        let config = ClusterConfig::from_lookup(lookup(&[
+            ("TALLY_CLUSTER", "mainnet-beta"),
+            ("TALLY_RPC_URL", "https://rpc.example.com"),
+            ("TALLY_PROGRAM_ID", &program_id.to_string()),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.cluster(), Cluster::Mainnet);
+        assert_eq!(config.rpc_url(), "https://rpc.example.com");
+        assert_eq!(config.ws_url(), Cluster::Mainnet.ws_url());
+        assert_eq!(config.program_id().unwrap(), program_id);
+        assert_eq!(config.usdc_mint().unwrap(), MAINNET_USDC_MINT);
+
+        assert!(ClusterConfig::from_lookup(lookup(&[])).is_err());
+        assert!(ClusterConfig::from_lookup(lookup(&[
+            ("TALLY_CLUSTER", "mainnet"),
+            ("TALLY_USDC_MINT", &DEVNET_USDC_MINT.to_string()),
+        ]))
+        .is_err());
+    }
+}
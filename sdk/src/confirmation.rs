@@ -0,0 +1,384 @@
+//! Transaction confirmation tracking with websocket signature subscriptions
+//!
+//! `SimpleTallyClient` confirms transactions by polling `getSignatureStatuses`. This
+//! module subscribes to `signatureSubscribe` over the cluster's websocket endpoint
+//! instead, falling back to polling when the websocket is unavailable or drops.
+//! It also detects blockhash expiry so callers can re-sign and resend transactions
+//! that were never landed.
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use crate::metrics::observe_transaction;
+use anchor_client::solana_client::pubsub_client::PubsubClient;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcSignatureSubscribeConfig;
+use anchor_client::solana_client::rpc_response::{ProcessedSignatureResult, RpcSignatureResult};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_client::solana_sdk::signature::Signature;
+use anchor_client::solana_sdk::transaction::Transaction;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Default interval between status polls (and websocket receive timeouts)
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Final state of a transaction observed by the confirmation tracker
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfirmationStatus {
+    /// Transaction reached finalized commitment without error
+    Finalized,
+    /// Transaction landed but failed with the given error
+    Failed(String),
+    /// Transaction's recent blockhash expired before it landed
+    BlockhashExpired,
+    /// Timeout elapsed before the transaction was finalized
+    TimedOut,
+}
+
+/// Derive the websocket URL for an RPC endpoint
+///
+/// Follows the Solana convention: `http` becomes `ws`, `https` becomes `wss`, and an
+/// explicit port is incremented by one (e.g. `8899` → `8900` for a local validator).
+///
+/// # Errors
+/// Returns an error if the URL cannot be parsed or uses an unsupported scheme
+pub fn websocket_url(rpc_url: &str) -> Result<String> {
+    let mut url = url::Url::parse(rpc_url)
+        .map_err(|e| TallyError::Generic(format!("Invalid RPC URL '{rpc_url}': {e}")))?;
+
+    let scheme = match url.scheme() {
+        "http" | "ws" => "ws",
+        "https" | "wss" => "wss",
+        other => {
+            return Err(TallyError::Generic(format!(
+                "Unsupported RPC URL scheme '{other}'"
+            )))
+        }
+    };
+    url.set_scheme(scheme)
+        .map_err(|()| TallyError::Generic(format!("Cannot convert '{rpc_url}' to websocket URL")))?;
+
+    if let Some(port) = url.port() {
+        let ws_port = port
+            .checked_add(1)
+            .ok_or_else(|| TallyError::Generic(format!("Invalid RPC port {port}")))?;
+        url.set_port(Some(ws_port))
+            .map_err(|()| TallyError::Generic(format!("Cannot set websocket port {ws_port}")))?;
+    }
+
+    Ok(url.to_string())
+}
+
+/// Tracks transaction confirmations via websocket subscriptions with polling fallback
+pub struct ConfirmationTracker<'a> {
+    rpc_client: &'a RpcClient,
+    ws_url: String,
+    poll_interval: Duration,
+}
+
+impl<'a> ConfirmationTracker<'a> {
+    /// Create a tracker for the given RPC client, deriving the websocket URL from its endpoint
+    ///
+    /// # Errors
+    /// Returns an error if the websocket URL cannot be derived from the RPC URL
+    pub fn new(rpc_client: &'a RpcClient) -> Result<Self> {
+        let ws_url = websocket_url(&rpc_client.url())?;
+        Ok(Self::with_ws_url(rpc_client, ws_url))
+    }
+
+    /// Create a tracker with an explicit websocket URL
+    #[must_use]
+    pub const fn with_ws_url(rpc_client: &'a RpcClient, ws_url: String) -> Self {
+        Self {
+            rpc_client,
+            ws_url,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Set the interval between status polls
+    #[must_use]
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Websocket URL used for signature subscriptions
+    #[must_use]
+    pub fn ws_url(&self) -> &str {
+        &self.ws_url
+    }
+
+    /// Wait until a signature is finalized, fails, or the timeout elapses
+    ///
+    /// # Errors
+    /// Returns an error if polling the RPC endpoint fails
+    pub fn await_finalized(
+        &self,
+        signature: &Signature,
+        timeout: Duration,
+    ) -> Result<ConfirmationStatus> {
+        self.await_signature(signature, None, timeout)
+    }
+
+    /// Wait for a signature, reporting `BlockhashExpired` once `recent_blockhash` is no longer valid
+    ///
+    /// # Errors
+    /// Returns an error if polling the RPC endpoint fails
+    pub fn await_finalized_with_blockhash(
+        &self,
+        signature: &Signature,
+        recent_blockhash: &Hash,
+        timeout: Duration,
+    ) -> Result<ConfirmationStatus> {
+        self.await_signature(signature, Some(recent_blockhash), timeout)
+    }
+
+    /// Send a transaction and wait for finalization, re-signing it when its blockhash expires
+    ///
+    /// `resign` receives a fresh blockhash and must return the transaction signed against it.
+    /// At most `max_resends` re-signed copies are sent after the original.
+    ///
+    /// # Errors
+    /// Returns an error if sending fails, the transaction fails on-chain, the timeout
+    /// elapses, or the blockhash expires more than `max_resends` times
+    #[tracing::instrument(
+        name = "send_transaction",
+        skip_all,
+        fields(signature, resends),
+        err
+    )]
+    pub fn send_with_resend<F>(
+        &self,
+        transaction: Transaction,
+        timeout: Duration,
+        max_resends: u32,
+        resign: F,
+    ) -> Result<Signature>
+    where
+        F: FnMut(Hash) -> Result<Transaction>,
+    {
+        observe_transaction(|| self.resend_until_finalized(transaction, timeout, max_resends, resign))
+    }
+
+    fn resend_until_finalized<F>(
+        &self,
+        transaction: Transaction,
+        timeout: Duration,
+        max_resends: u32,
+        mut resign: F,
+    ) -> Result<Signature>
+    where
+        F: FnMut(Hash) -> Result<Transaction>,
+    {
+        let mut transaction = transaction;
+        let mut resends = 0u32;
+
+        loop {
+            let signature = self
+                .rpc_client
+                .send_transaction(&transaction)
+                .map_err(|e| TallyError::RpcError(format!("Failed to send transaction: {e}")))?;
+
+            match self.await_finalized_with_blockhash(
+                &signature,
+                &transaction.message.recent_blockhash,
+                timeout,
+            )? {
+                ConfirmationStatus::Finalized => {
+                    let span = tracing::Span::current();
+                    span.record("signature", tracing::field::display(&signature));
+                    span.record("resends", resends);
+                    return Ok(signature);
+                }
+                ConfirmationStatus::Failed(err) => {
+                    return Err(TallyError::Generic(format!(
+                        "Transaction {signature} failed: {err}"
+                    )))
+                }
+                ConfirmationStatus::TimedOut => {
+                    return Err(TallyError::Generic(format!(
+                        "Timed out waiting for transaction {signature} to finalize"
+                    )))
+                }
+                ConfirmationStatus::BlockhashExpired => {
+                    if resends >= max_resends {
+                        return Err(TallyError::Generic(format!(
+                            "Blockhash expired for transaction {signature} after {resends} resends"
+                        )));
+                    }
+                    resends = resends.saturating_add(1);
+                    debug!(
+                        service = "tally-sdk",
+                        component = "confirmation",
+                        event = "blockhash_expired",
+                        signature = %signature,
+                        resends,
+                        "Blockhash expired, re-signing transaction"
+                    );
+
+                    let blockhash = self
+                        .rpc_client
+                        .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+                        .map_err(|e| TallyError::RpcError(format!("Failed to get blockhash: {e}")))?
+                        .0;
+                    transaction = resign(blockhash)?;
+                }
+            }
+        }
+    }
+
+    fn await_signature(
+        &self,
+        signature: &Signature,
+        recent_blockhash: Option<&Hash>,
+        timeout: Duration,
+    ) -> Result<ConfirmationStatus> {
+        let deadline = Instant::now()
+            .checked_add(timeout)
+            .ok_or_else(|| TallyError::Generic("Confirmation timeout too large".to_string()))?;
+
+        let config = RpcSignatureSubscribeConfig {
+            commitment: Some(CommitmentConfig::finalized()),
+            enable_received_notification: Some(false),
+        };
+        let mut subscription = match PubsubClient::signature_subscribe(
+            &self.ws_url,
+            signature,
+            Some(config),
+        ) {
+            Ok(subscription) => Some(subscription),
+            Err(e) => {
+                warn!(
+                    service = "tally-sdk",
+                    component = "confirmation",
+                    event = "subscribe_failed",
+                    ws_url = %self.ws_url,
+                    error = %e,
+                    "Signature subscription unavailable, falling back to polling"
+                );
+                None
+            }
+        };
+
+        let status = loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break ConfirmationStatus::TimedOut;
+            }
+            let wait = remaining.min(self.poll_interval);
+
+            if let Some((_, receiver)) = &subscription {
+                match receiver.recv_timeout(wait) {
+                    Ok(response) => {
+                        if let RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+                            err,
+                        }) = response.value
+                        {
+                            break err.map_or(ConfirmationStatus::Finalized, |e| {
+                                ConfirmationStatus::Failed(e.to_string())
+                            });
+                        }
+                    }
+                    Err(e) if e.is_disconnected() => {
+                        warn!(
+                            service = "tally-sdk",
+                            component = "confirmation",
+                            event = "subscription_disconnected",
+                            "Signature subscription disconnected, falling back to polling"
+                        );
+                        subscription = None;
+                    }
+                    Err(_) => {}
+                }
+            } else {
+                std::thread::sleep(wait);
+            }
+
+            // Poll as well: covers missed notifications and the no-websocket fallback
+            if let Some(status) = self.poll_status(signature)? {
+                break status;
+            }
+
+            if let Some(blockhash) = recent_blockhash {
+                let valid = self
+                    .rpc_client
+                    .is_blockhash_valid(blockhash, CommitmentConfig::processed())
+                    .map_err(|e| TallyError::RpcError(format!("Failed to check blockhash: {e}")))?;
+                if !valid {
+                    // The transaction may have landed just before expiry
+                    break self
+                        .poll_status(signature)?
+                        .unwrap_or(ConfirmationStatus::BlockhashExpired);
+                }
+            }
+        };
+
+        if let Some((mut client_subscription, _)) = subscription {
+            // Best effort: the server drops the subscription after its single notification anyway
+            let _ = client_subscription.shutdown();
+        }
+
+        Ok(status)
+    }
+
+    fn poll_status(&self, signature: &Signature) -> Result<Option<ConfirmationStatus>> {
+        let statuses = self
+            .rpc_client
+            .get_signature_statuses(&[*signature])
+            .map_err(|e| TallyError::RpcError(format!("Failed to get signature status: {e}")))?;
+
+        let Some(Some(status)) = statuses.value.into_iter().next() else {
+            return Ok(None);
+        };
+
+        // A failed transaction never succeeds later, so report failure at any commitment
+        if let Some(err) = status.err {
+            return Ok(Some(ConfirmationStatus::Failed(err.to_string())));
+        }
+
+        Ok(status
+            .satisfies_commitment(CommitmentConfig::finalized())
+            .then_some(ConfirmationStatus::Finalized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_websocket_url_localhost() {
+        assert_eq!(
+            websocket_url("http://localhost:8899").unwrap(),
+            "ws://localhost:8900/"
+        );
+    }
+
+    #[test]
+    fn test_websocket_url_https_without_port() {
+        assert_eq!(
+            websocket_url("https://api.devnet.solana.com").unwrap(),
+            "wss://api.devnet.solana.com/"
+        );
+    }
+
+    #[test]
+    fn test_websocket_url_rejects_unknown_scheme() {
+        assert!(websocket_url("ftp://localhost:8899").is_err());
+        assert!(websocket_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_tracker_configuration() {
+        let rpc_client = RpcClient::new("http://localhost:8899".to_string());
+        let tracker = ConfirmationTracker::new(&rpc_client)
+            .unwrap()
+            .poll_interval(Duration::from_secs(2));
+
+        assert_eq!(tracker.ws_url(), "ws://localhost:8900/");
+        assert_eq!(tracker.poll_interval, Duration::from_secs(2));
+    }
+}
@@ -8,18 +8,32 @@
 
 use crate::{
     dashboard_types::{
-        DashboardAgreement, DashboardEvent, DashboardEventType, EventStream, Overview,
-        PaymentTermsAnalytics,
+        DashboardAgreement, DashboardEvent, DashboardEventType, EventStream, MetricSource,
+        Overview, OverviewSources, PaymentTermsAnalytics, UpcomingRenewal,
     },
     error::{Result, TallyError},
     events::{ParsedEventWithContext, TallyEvent},
-    program_types::{CreatePaymentTermsArgs, InitPayeeArgs, Payee, PaymentTerms},
+    program_types::{
+        decode_versioned_account, CreatePaymentTermsArgs, InitPayeeArgs, Payee, PaymentAgreement,
+        PaymentTerms,
+    },
+    rpc_exec::BoundedExecutor,
     simple_client::SimpleTallyClient,
 };
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signature::Signer;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use tracing::warn;
+
+/// Length of a month used to normalize recurring revenue (30 days)
+const MONTH_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// How far ahead `Overview::upcoming_renewals` looks (7 days)
+pub const UPCOMING_RENEWAL_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Payment terms address and data together with all of their agreements
+type PaymentTermsWithAgreements = (Pubkey, PaymentTerms, Vec<(Pubkey, PaymentAgreement)>);
 
 /// Time period for statistics calculation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -61,10 +75,14 @@ pub struct EventStats {
 /// Dashboard client for payee management and analytics
 ///
 /// Provides high-level methods for dashboard operations including payee provisioning,
-/// live data fetching, and real-time event monitoring.
+/// live data fetching, and real-time event monitoring. Account scans run through a
+/// `BoundedExecutor`, which fetches agreements concurrently in `getMultipleAccounts`
+/// batches while respecting the RPC provider's rate limits.
 pub struct DashboardClient {
     /// Underlying simple client for blockchain operations
     client: SimpleTallyClient,
+    /// Executor for bulk account scans
+    executor: BoundedExecutor,
 }
 
 impl DashboardClient {
@@ -80,7 +98,20 @@ impl DashboardClient {
     /// Returns an error if the underlying client cannot be created
     pub fn new(cluster_url: &str) -> Result<Self> {
         let client = SimpleTallyClient::new(cluster_url)?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            executor: BoundedExecutor::default(),
+        })
+    }
+
+    /// Use a custom executor for bulk account scans
+    ///
+    /// Lower the concurrency and request rate for public RPC endpoints, or raise them
+    /// for dedicated nodes.
+    #[must_use]
+    pub const fn with_executor(mut self, executor: BoundedExecutor) -> Self {
+        self.executor = executor;
+        self
     }
 
     /// Get the underlying simple client
@@ -179,6 +210,12 @@ impl DashboardClient {
 
     /// Get comprehensive overview statistics for a payee
     ///
+    /// Monthly revenue and activity are taken from event history when it contains
+    /// events for the last 30 days. RPC nodes may prune that history, so when it is
+    /// empty or unavailable the snapshot-derived values from
+    /// [`get_payee_snapshot_overview`](Self::get_payee_snapshot_overview) are kept.
+    /// `Overview::sources` records which path each metric came from.
+    ///
     /// # Arguments
     /// * `payee` - The payee PDA address
     ///
@@ -186,78 +223,66 @@ impl DashboardClient {
     /// * `Ok(Overview)` - Overview statistics
     ///
     /// # Errors
-    /// Returns an error if the payee doesn't exist or data fetching fails
+    /// Returns an error if the payee doesn't exist or account fetching fails
     pub fn get_payee_overview(&self, payee: &Pubkey) -> Result<Overview> {
+        let mut overview = self.get_payee_snapshot_overview(payee)?;
+
+        match self.get_event_statistics(payee, Period::Month) {
+            Ok(stats) if stats.total_events > 0 => {
+                let count =
+                    |event_type: &str| stats.event_counts.get(event_type).copied().unwrap_or(0);
+                overview.monthly_revenue = stats.revenue;
+                overview.monthly_new_agreements = count("AgreementStarted");
+                overview.monthly_paused_agreements = count("AgreementPaused");
+                overview.sources = OverviewSources {
+                    monthly_revenue: MetricSource::Events,
+                    monthly_activity: MetricSource::Events,
+                };
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(
+                    service = "tally-sdk",
+                    component = "dashboard",
+                    event = "event_history_unavailable",
+                    payee = %payee,
+                    error = %e,
+                    "Event history unavailable, using snapshot-derived overview"
+                );
+            }
+        }
+
+        Ok(overview)
+    }
+
+    /// Get overview statistics derived purely from current on-chain accounts
+    ///
+    /// Does not depend on event history, so it works against RPC nodes that prune
+    /// transaction history. Revenue and monthly activity are approximations based on
+    /// each agreement's `payment_count` and `last_amount`.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee PDA address
+    ///
+    /// # Returns
+    /// * `Ok(Overview)` - Overview statistics with all sources set to `Snapshot`
+    ///
+    /// # Errors
+    /// Returns an error if the payee doesn't exist or account fetching fails
+    pub fn get_payee_snapshot_overview(&self, payee: &Pubkey) -> Result<Overview> {
         // Get payee data
         let payee_data = self.client.get_payee(payee)?.ok_or_else(|| {
             TallyError::AccountNotFound(format!("Payee not found: {payee}"))
         })?;
 
-        // Get all payment terms for this payee
-        let payment_terms_list = self.client.list_payment_terms(payee)?;
-        let total_payment_terms = u32::try_from(payment_terms_list.len())
-            .map_err(|_| TallyError::Generic("Too many payment terms for payee".to_string()))?;
-
         // Collect all payment agreement data across all payment terms
-        let mut all_agreements = Vec::new();
-        for (payment_terms_address, _payment_terms) in &payment_terms_list {
-            let agreements = self.client.list_payment_agreements(payment_terms_address)?;
-            all_agreements.extend(agreements);
-        }
-
-        // Calculate statistics
-        let current_time = Utc::now().timestamp();
-        let month_start = current_time.saturating_sub(30 * 24 * 60 * 60); // 30 days ago
-
-        let mut active_count = 0u32;
-        let mut inactive_count = 0u32;
-        let mut total_revenue = 0u64;
-        let mut monthly_revenue = 0u64;
-        let mut monthly_new_agreements = 0u32;
-        let mut monthly_paused_agreements = 0u32;
-
-        for (_agreement_address, payment_agreement) in &all_agreements {
-            if payment_agreement.active {
-                active_count = active_count.saturating_add(1);
-            } else {
-                inactive_count = inactive_count.saturating_add(1);
-            }
-
-            // Calculate revenue (payment_count * last_amount)
-            let agreement_revenue =
-                u64::from(payment_agreement.payment_count).saturating_mul(payment_agreement.last_amount);
-            total_revenue = total_revenue.saturating_add(agreement_revenue);
-
-            // Monthly statistics (approximate)
-            if payment_agreement.created_ts >= month_start {
-                monthly_new_agreements = monthly_new_agreements.saturating_add(1);
-                monthly_revenue = monthly_revenue.saturating_add(payment_agreement.last_amount);
-            }
-
-            // Count paused agreements (inactive agreements created this month)
-            if !payment_agreement.active && payment_agreement.created_ts >= month_start {
-                monthly_paused_agreements = monthly_paused_agreements.saturating_add(1);
-            }
-        }
-
-        let average_revenue_per_payer = if all_agreements.is_empty() {
-            0
-        } else {
-            total_revenue / u64::try_from(all_agreements.len()).unwrap_or(1)
-        };
+        let payment_terms_with_agreements = self.scan_payee(payee)?;
 
-        Ok(Overview {
-            total_revenue,
-            active_agreements: active_count,
-            inactive_agreements: inactive_count,
-            total_payment_terms,
-            monthly_revenue,
-            monthly_new_agreements,
-            monthly_paused_agreements,
-            average_revenue_per_payer,
-            payee_authority: payee_data.authority,
-            usdc_mint: payee_data.usdc_mint,
-        })
+        Self::compute_snapshot_overview(
+            &payee_data,
+            &payment_terms_with_agreements,
+            Utc::now().timestamp(),
+        )
     }
 
     /// Get all active payment agreements for a payee with enhanced information
@@ -271,13 +296,11 @@ impl DashboardClient {
     /// # Errors
     /// Returns an error if data fetching fails
     pub fn get_live_agreements(&self, payee: &Pubkey) -> Result<Vec<DashboardAgreement>> {
-        let payment_terms_list = self.client.list_payment_terms(payee)?;
+        let payment_terms_with_agreements = self.scan_payee(payee)?;
         let mut dashboard_agreements = Vec::new();
         let current_time = Utc::now().timestamp();
 
-        for (payment_terms_address, payment_terms) in payment_terms_list {
-            let agreements = self.client.list_payment_agreements(&payment_terms_address)?;
-
+        for (payment_terms_address, payment_terms, agreements) in payment_terms_with_agreements {
             for (agreement_address, payment_agreement) in agreements {
                 let status = DashboardAgreement::calculate_status(&payment_agreement, current_time);
                 let days_until_renewal = DashboardAgreement::calculate_days_until_renewal(
@@ -323,10 +346,27 @@ impl DashboardClient {
             .ok_or_else(|| TallyError::AccountNotFound(format!("Payment terms not found: {payment_terms}")))?;
 
         // Get all payment agreements for these payment terms
-        let agreements = self.client.list_payment_agreements(payment_terms)?;
+        let addresses = self
+            .executor
+            .execute(|| self.client.list_payment_agreement_addresses(payment_terms))?;
+        let agreements = self.fetch_payment_agreements(addresses)?;
+
+        Self::compute_payment_terms_analytics(
+            *payment_terms,
+            payment_terms_data,
+            &agreements,
+            Utc::now().timestamp(),
+        )
+    }
 
+    /// Compute analytics for payment terms from their agreements
+    fn compute_payment_terms_analytics(
+        payment_terms_address: Pubkey,
+        payment_terms_data: PaymentTerms,
+        agreements: &[(Pubkey, PaymentAgreement)],
+        current_time: i64,
+    ) -> Result<PaymentTermsAnalytics> {
         // Calculate statistics
-        let current_time = Utc::now().timestamp();
         let month_start = current_time - (30 * 24 * 60 * 60); // 30 days ago
 
         let mut active_count: u32 = 0;
@@ -338,7 +378,7 @@ impl DashboardClient {
         let mut total_duration_secs: i64 = 0;
         let mut completed_agreements: u32 = 0;
 
-        for (_agreement_address, payment_agreement) in &agreements {
+        for (_agreement_address, payment_agreement) in agreements {
             if payment_agreement.active {
                 active_count = active_count.saturating_add(1);
             } else {
@@ -384,7 +424,7 @@ impl DashboardClient {
 
         Ok(PaymentTermsAnalytics {
             payment_terms: payment_terms_data,
-            payment_terms_address: *payment_terms,
+            payment_terms_address,
             active_count,
             inactive_count,
             total_revenue,
@@ -421,15 +461,74 @@ impl DashboardClient {
     /// # Errors
     /// Returns an error if data fetching fails
     pub fn get_all_payment_terms_analytics(&self, payee: &Pubkey) -> Result<Vec<PaymentTermsAnalytics>> {
-        let payment_terms_list = self.client.list_payment_terms(payee)?;
-        let mut analytics = Vec::new();
+        let current_time = Utc::now().timestamp();
+        self.scan_payee(payee)?
+            .into_iter()
+            .map(|(payment_terms_address, payment_terms, agreements)| {
+                Self::compute_payment_terms_analytics(
+                    payment_terms_address,
+                    payment_terms,
+                    &agreements,
+                    current_time,
+                )
+            })
+            .collect()
+    }
 
-        for (payment_terms_address, _payment_terms) in payment_terms_list {
-            let payment_terms_analytics = self.get_payment_terms_analytics(&payment_terms_address)?;
-            analytics.push(payment_terms_analytics);
-        }
+    // ========================================
+    // Bulk Scan Helpers
+    // ========================================
+
+    /// Fetch every payment terms account of a payee together with its agreements
+    ///
+    /// Agreement addresses are listed per payment terms concurrently, then all agreement
+    /// data is fetched in `getMultipleAccounts` batches.
+    pub(crate) fn scan_payee(&self, payee: &Pubkey) -> Result<Vec<PaymentTermsWithAgreements>> {
+        let payment_terms = self
+            .executor
+            .execute(|| self.client.list_payment_terms(payee))?;
+        let addresses = self.executor.map(&payment_terms, |(payment_terms_address, _)| {
+            self.client
+                .list_payment_agreement_addresses(payment_terms_address)
+        })?;
+
+        let all_addresses: Vec<Pubkey> = addresses.iter().flatten().copied().collect();
+        let mut agreements = self.fetch_payment_agreements(all_addresses)?.into_iter().peekable();
+
+        Ok(payment_terms
+            .into_iter()
+            .map(|(payment_terms_address, payment_terms)| {
+                // Agreements come back grouped in the order their payment terms were listed
+                let mut terms_agreements = Vec::new();
+                while let Some(entry) = agreements
+                    .next_if(|(_, agreement)| agreement.payment_terms == payment_terms_address)
+                {
+                    terms_agreements.push(entry);
+                }
+                (payment_terms_address, payment_terms, terms_agreements)
+            })
+            .collect())
+    }
 
-        Ok(analytics)
+    /// Fetch and decode payment agreements, skipping closed or invalid accounts
+    fn fetch_payment_agreements(
+        &self,
+        addresses: Vec<Pubkey>,
+    ) -> Result<Vec<(Pubkey, PaymentAgreement)>> {
+        let accounts = self
+            .executor
+            .get_multiple_accounts(self.client.rpc(), &addresses)?;
+        let program_id = self.client.program_id();
+
+        Ok(addresses
+            .into_iter()
+            .zip(accounts)
+            .filter_map(|(address, account)| {
+                let account = account.filter(|account| account.owner == program_id)?;
+                let agreement = decode_versioned_account::<PaymentAgreement>(account.data.get(8..)?).ok()?;
+                Some((address, agreement))
+            })
+            .collect())
     }
 
     // ========================================
@@ -664,7 +763,7 @@ impl DashboardClient {
                 // Filter by timestamp
                 if let Some(block_time) = parsed_event.block_time {
                     if block_time >= since_timestamp {
-                        return Some(Self::convert_parsed_event_to_dashboard_event(&parsed_event));
+                        return Some(DashboardEvent::from(&parsed_event));
                     }
                 }
                 None
@@ -824,6 +923,119 @@ impl DashboardClient {
         self.client.account_exists(payment_terms)
     }
 
+    /// Compute the snapshot overview from payment terms and their agreements
+    fn compute_snapshot_overview(
+        payee: &Payee,
+        payment_terms_with_agreements: &[PaymentTermsWithAgreements],
+        current_time: i64,
+    ) -> Result<Overview> {
+        let total_payment_terms = u32::try_from(payment_terms_with_agreements.len())
+            .map_err(|_| TallyError::Generic("Too many payment terms for payee".to_string()))?;
+
+        let month_start = current_time.saturating_sub(30 * 24 * 60 * 60); // 30 days ago
+        let renewal_window_end = current_time.saturating_add(UPCOMING_RENEWAL_WINDOW_SECS);
+
+        let mut agreement_count = 0u64;
+        let mut active_count = 0u32;
+        let mut inactive_count = 0u32;
+        let mut total_revenue = 0u64;
+        let mut monthly_revenue = 0u64;
+        let mut monthly_new_agreements = 0u32;
+        let mut monthly_paused_agreements = 0u32;
+        let mut monthly_recurring_revenue = 0u64;
+        let mut active_payers = HashSet::new();
+        let mut upcoming_renewals = Vec::new();
+
+        for (payment_terms_address, payment_terms, agreements) in payment_terms_with_agreements {
+            for (agreement_address, payment_agreement) in agreements {
+                agreement_count = agreement_count.saturating_add(1);
+
+                if payment_agreement.active {
+                    active_count = active_count.saturating_add(1);
+                } else {
+                    inactive_count = inactive_count.saturating_add(1);
+                }
+
+                // Calculate revenue (payment_count * last_amount)
+                let agreement_revenue = u64::from(payment_agreement.payment_count)
+                    .saturating_mul(payment_agreement.last_amount);
+                total_revenue = total_revenue.saturating_add(agreement_revenue);
+
+                // Monthly statistics (approximate)
+                if payment_agreement.created_ts >= month_start {
+                    monthly_new_agreements = monthly_new_agreements.saturating_add(1);
+                    monthly_revenue = monthly_revenue.saturating_add(payment_agreement.last_amount);
+                }
+
+                // Count paused agreements (inactive agreements created this month)
+                if !payment_agreement.active && payment_agreement.created_ts >= month_start {
+                    monthly_paused_agreements = monthly_paused_agreements.saturating_add(1);
+                }
+
+                if !payment_agreement.active {
+                    continue;
+                }
+
+                // The last charged amount reflects gate discounts; fall back to the
+                // terms price for agreements that have not been charged yet
+                let amount = if payment_agreement.last_amount > 0 {
+                    payment_agreement.last_amount
+                } else {
+                    payment_terms.amount_usdc
+                };
+
+                active_payers.insert(payment_agreement.payer);
+                monthly_recurring_revenue = monthly_recurring_revenue
+                    .saturating_add(Self::normalize_to_month(amount, payment_terms.period_secs));
+
+                // Streaming agreements are swept as they accrue and never renew
+                if !payment_terms.is_streaming()
+                    && payment_agreement.next_payment_ts <= renewal_window_end
+                {
+                    upcoming_renewals.push(UpcomingRenewal {
+                        agreement_address: *agreement_address,
+                        payment_terms_address: *payment_terms_address,
+                        payer: payment_agreement.payer,
+                        next_payment_ts: payment_agreement.next_payment_ts,
+                        amount,
+                    });
+                }
+            }
+        }
+
+        upcoming_renewals.sort_by_key(|renewal| renewal.next_payment_ts);
+
+        let average_revenue_per_payer = total_revenue.checked_div(agreement_count).unwrap_or(0);
+
+        Ok(Overview {
+            total_revenue,
+            active_agreements: active_count,
+            inactive_agreements: inactive_count,
+            total_payment_terms,
+            monthly_revenue,
+            monthly_new_agreements,
+            monthly_paused_agreements,
+            average_revenue_per_payer,
+            payee_authority: payee.authority,
+            usdc_mint: payee.usdc_mint,
+            active_payers: u32::try_from(active_payers.len()).unwrap_or(u32::MAX),
+            monthly_recurring_revenue,
+            upcoming_renewals,
+            lifetime_revenue: payee.lifetime_revenue_usdc,
+            lifetime_renewals: payee.lifetime_renewals,
+            sources: OverviewSources::default(),
+        })
+    }
+
+    /// Normalize a per-period amount to a 30-day month
+    pub(crate) fn normalize_to_month(amount: u64, period_secs: u64) -> u64 {
+        if period_secs == 0 {
+            return 0;
+        }
+        let monthly = u128::from(amount) * u128::from(MONTH_SECS) / u128::from(period_secs);
+        u64::try_from(monthly).unwrap_or(u64::MAX)
+    }
+
     /// Convert Period to Unix timestamp
     fn period_to_timestamp(period: Period) -> i64 {
         let now = Utc::now();
@@ -858,13 +1070,38 @@ impl DashboardClient {
             TallyEvent::ConfigUpdated(_) => "ConfigUpdated".to_string(),
             TallyEvent::VolumeTierUpgraded(_) => "VolumeTierUpgraded".to_string(),
             TallyEvent::PaymentTermsUpdated(_) => "PaymentTermsTermsUpdated".to_string(),
+            TallyEvent::PayeeAuthorityTransferInitiated(_) => {
+                "PayeeAuthorityTransferInitiated".to_string()
+            }
+            TallyEvent::PayeeAuthorityTransferred(_) => "PayeeAuthorityTransferred".to_string(),
+            TallyEvent::PayeeAuthorityTransferCancelled(_) => {
+                "PayeeAuthorityTransferCancelled".to_string()
+            }
+            TallyEvent::CreditApplied(_) => "CreditApplied".to_string(),
+            TallyEvent::TermsSunset(_) => "TermsSunset".to_string(),
+            TallyEvent::EscrowReleased(_) => "EscrowReleased".to_string(),
+            TallyEvent::EscrowRefunded(_) => "EscrowRefunded".to_string(),
+            TallyEvent::KeeperPaidInSol(_) => "KeeperPaidInSol".to_string(),
+            TallyEvent::AgreementSuspended(_) => "AgreementSuspended".to_string(),
+            TallyEvent::AgreementUnsuspended(_) => "AgreementUnsuspended".to_string(),
+            TallyEvent::DepositHeld(_) => "DepositHeld".to_string(),
+            TallyEvent::DepositClaimed(_) => "DepositClaimed".to_string(),
+            TallyEvent::DepositReleased(_) => "DepositReleased".to_string(),
+            TallyEvent::Swept(_) => "Swept".to_string(),
+            TallyEvent::AgreementSnapshot(_) => "AgreementSnapshot".to_string(),
         }
     }
 
-    /// Convert `ParsedEventWithContext` to `DashboardEvent`
-    fn convert_parsed_event_to_dashboard_event(
-        parsed_event: &ParsedEventWithContext,
-    ) -> DashboardEvent {
+    /// Get the current timestamp (useful for event filtering)
+    #[must_use]
+    pub fn current_timestamp() -> i64 {
+        Utc::now().timestamp()
+    }
+}
+
+impl From<&ParsedEventWithContext> for DashboardEvent {
+    /// Build a dashboard event from the decoded event, keeping its transaction context
+    fn from(parsed_event: &ParsedEventWithContext) -> Self {
         let (event_type, payment_terms_address, agreement_address, payer, amount) =
             match &parsed_event.event {
                 TallyEvent::PaymentAgreementStarted(event) => (
@@ -895,9 +1132,29 @@ impl DashboardClient {
                     Some(event.payer),
                     None,
                 ),
-                // Handle all other event types with default values
+                TallyEvent::PaymentTermsCreated(event) => (
+                    DashboardEventType::PaymentTermsCreated,
+                    Some(event.payment_terms),
+                    None,
+                    None,
+                    None,
+                ),
+                TallyEvent::PaymentTermsUpdated(event) => (
+                    DashboardEventType::PaymentTermsUpdated,
+                    Some(event.payment_terms),
+                    None,
+                    None,
+                    None,
+                ),
+                TallyEvent::FeesWithdrawn(event) => (
+                    DashboardEventType::FeesWithdrawn,
+                    None,
+                    None,
+                    None,
+                    Some(event.amount),
+                ),
                 _ => (
-                    DashboardEventType::AgreementStarted, // Default type
+                    DashboardEventType::Other(parsed_event.get_event_type_string()),
                     None,
                     None,
                     None,
@@ -910,13 +1167,19 @@ impl DashboardClient {
         metadata.insert("slot".to_string(), parsed_event.slot.to_string());
         metadata.insert("success".to_string(), parsed_event.success.to_string());
         metadata.insert("log_index".to_string(), parsed_event.log_index.to_string());
+        if let Some(instruction_index) = parsed_event.instruction_index {
+            metadata.insert("instruction_index".to_string(), instruction_index.to_string());
+        }
+        if let Some(cluster) = parsed_event.cluster {
+            metadata.insert("cluster".to_string(), cluster.to_string());
+        }
 
         // Add event-specific metadata
         if let TallyEvent::PaymentFailed(event) = &parsed_event.event {
             metadata.insert("failure_reason".to_string(), event.reason.clone());
         }
 
-        DashboardEvent {
+        Self {
             event_type,
             payment_terms_address,
             agreement_address,
@@ -927,19 +1190,11 @@ impl DashboardClient {
                 .block_time
                 .unwrap_or_else(|| Utc::now().timestamp()),
             metadata,
+            context: Some(parsed_event.context()),
         }
     }
-
-    /// Get the current timestamp (useful for event filtering)
-    #[must_use]
-    pub fn current_timestamp() -> i64 {
-        Utc::now().timestamp()
-    }
 }
 
-// ParsedEvent is now available as ParsedEventWithContext from crate::events
-// The duplicate definition has been removed and replaced with the SDK version
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -993,6 +1248,12 @@ mod tests {
             average_revenue_per_payer: 10_000_000, // 10 USDC
             payee_authority: Pubkey::from(Keypair::new().pubkey().to_bytes()),
             usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            active_payers: 80,
+            monthly_recurring_revenue: 800_000_000, // 800 USDC
+            upcoming_renewals: Vec::new(),
+            lifetime_revenue: 2_500_000_000, // 2,500 USDC
+            lifetime_renewals: 150,
+            sources: OverviewSources::default(),
         };
 
         // Use epsilon comparison for float values
@@ -1002,6 +1263,136 @@ mod tests {
         assert!((overview.churn_rate() - 20.0).abs() < f64::EPSILON); // 20 out of 100 = 20%
     }
 
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn test_compute_snapshot_overview() {
+        const NOW: i64 = 1_700_000_000;
+        const DAY: i64 = 24 * 60 * 60;
+
+        let authority = Pubkey::new_unique();
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 3,
+            lifetime_revenue_usdc: 250_000_000,
+            lifetime_renewals: 20,
+            bump: 255,
+            version: 1,
+        };
+        let terms = |amount_usdc: u64, period_secs: u64| PaymentTerms {
+            payee: Pubkey::new_unique(),
+            terms_id: [0u8; 32],
+            amount_usdc,
+            period_secs,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let agreement = |payer: Pubkey, active: bool, next_payment_ts: i64, last_amount: u64| {
+            (
+                Pubkey::new_unique(),
+                PaymentAgreement {
+                    payment_terms: Pubkey::new_unique(),
+                    payer,
+                    next_payment_ts,
+                    active,
+                    payment_count: 1,
+                    created_ts: NOW - 60 * DAY,
+                    last_amount,
+                    last_payment_ts: NOW - DAY,
+                    last_pull_period_index: 1,
+                    cancel_at_period_end: false,
+                    pending_payer: None,
+                    refunded_amount: 0,
+                    max_periods: None,
+                    periods_paid: 1,
+                    external_ref_hash: None,
+                    paused_at_ts: None,
+                    credit_amount: 0,
+                    escrow: None,
+                    period_index: 0,
+                    suspension: None,
+                    bump: 255,
+                    deposit_held: 0,
+                    version: 1,
+                    last_sweep_ts: 0,
+                },
+            )
+        };
+
+        let repeat_payer = Pubkey::new_unique();
+        let monthly_terms = Pubkey::new_unique();
+        let weekly_terms = Pubkey::new_unique();
+        let data = vec![
+            (
+                monthly_terms,
+                terms(10_000_000, 30 * 24 * 60 * 60),
+                vec![
+                    agreement(repeat_payer, true, NOW + 20 * DAY, 10_000_000),
+                    agreement(Pubkey::new_unique(), false, NOW + DAY, 10_000_000),
+                ],
+            ),
+            (
+                weekly_terms,
+                terms(1_000_000, 7 * 24 * 60 * 60),
+                vec![
+                    agreement(repeat_payer, true, NOW + 2 * DAY, 0),
+                    agreement(Pubkey::new_unique(), true, NOW + DAY, 900_000),
+                ],
+            ),
+        ];
+
+        let overview = DashboardClient::compute_snapshot_overview(&payee, &data, NOW).unwrap();
+
+        assert_eq!(overview.total_payment_terms, 2);
+        assert_eq!(overview.active_agreements, 3);
+        assert_eq!(overview.inactive_agreements, 1);
+        assert_eq!(overview.active_payers, 2);
+        // Lifetime totals come straight from the payee's counters
+        assert_eq!(overview.lifetime_revenue, 250_000_000);
+        assert_eq!(overview.lifetime_renewals, 20);
+        // 10 USDC monthly + 1 USDC weekly (uncharged, terms price) + 0.9 USDC weekly
+        assert_eq!(
+            overview.monthly_recurring_revenue,
+            10_000_000 + 1_000_000 * 30 / 7 + 900_000 * 30 / 7
+        );
+        // Only active agreements due within the window, soonest first
+        let renewals: Vec<_> = overview
+            .upcoming_renewals
+            .iter()
+            .map(|renewal| (renewal.payment_terms_address, renewal.next_payment_ts, renewal.amount))
+            .collect();
+        assert_eq!(
+            renewals,
+            vec![
+                (weekly_terms, NOW + DAY, 900_000),
+                (weekly_terms, NOW + 2 * DAY, 1_000_000),
+            ]
+        );
+        assert_eq!(overview.sources.monthly_revenue, MetricSource::Snapshot);
+        assert_eq!(overview.sources.monthly_activity, MetricSource::Snapshot);
+    }
+
     #[test]
     fn test_dashboard_event_functionality() {
         use crate::dashboard_types::{DashboardEvent, DashboardEventType};
@@ -1019,6 +1410,7 @@ mod tests {
             transaction_signature: Some("test_sig_123".to_string()),
             timestamp: chrono::Utc::now().timestamp(),
             metadata,
+            context: None,
         };
 
         assert_eq!(event.amount_formatted(), Some(5.0));
@@ -1035,6 +1427,7 @@ mod tests {
             transaction_signature: None,
             timestamp: chrono::Utc::now().timestamp(),
             metadata: HashMap::new(),
+            context: None,
         };
 
         assert!(!payment_failed_event.affects_revenue());
@@ -1062,6 +1455,7 @@ mod tests {
             transaction_signature: None,
             timestamp: chrono::Utc::now().timestamp() - 3600,
             metadata: HashMap::new(),
+            context: None,
         };
 
         let event2 = DashboardEvent {
@@ -1073,6 +1467,7 @@ mod tests {
             transaction_signature: None,
             timestamp: chrono::Utc::now().timestamp() - 1800,
             metadata: HashMap::new(),
+            context: None,
         };
 
         let event3 = DashboardEvent {
@@ -1084,6 +1479,7 @@ mod tests {
             transaction_signature: None,
             timestamp: chrono::Utc::now().timestamp(),
             metadata: HashMap::new(),
+            context: None,
         };
 
         stream.add_event(event1);
@@ -1212,6 +1608,8 @@ mod tests {
             payment_terms: Pubkey::from(Keypair::new().pubkey().to_bytes()),
             payer: Pubkey::from(Keypair::new().pubkey().to_bytes()),
             amount: 5_000_000,
+            external_ref_hash: None,
+            delegated_amount: 0,
         });
 
         let payment_executed_event = TallyEvent::PaymentExecuted(PaymentExecuted {
@@ -1221,6 +1619,7 @@ mod tests {
             amount: 5_000_000,
             keeper: Pubkey::from(Keypair::new().pubkey().to_bytes()),
             keeper_fee: 25_000,
+            period_index: 1,
         });
 
         let payment_agreement_paused_event = TallyEvent::PaymentAgreementPaused(PaymentAgreementPaused {
@@ -1255,7 +1654,7 @@ mod tests {
     }
 
     #[test]
-    fn test_convert_parsed_event_to_dashboard_event() {
+    fn test_dashboard_event_from_parsed_event() {
         use crate::events::{PaymentFailed, PaymentAgreementStarted, TallyEvent};
         use anchor_client::solana_sdk::signature::Signature;
         use std::str::FromStr;
@@ -1268,9 +1667,11 @@ mod tests {
 
         let payment_agreement_started_event = TallyEvent::PaymentAgreementStarted(PaymentAgreementStarted {
             payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
-            payment_terms: payment_terms,
+            payment_terms,
             payer,
             amount: 10_000_000, // 10 USDC
+            external_ref_hash: None,
+            delegated_amount: 0,
         });
 
         let parsed_event = ParsedEventWithContext {
@@ -1280,10 +1681,12 @@ mod tests {
             block_time: Some(timestamp),
             success: true,
             log_index: 0,
+            instruction_index: Some(2),
+            cluster: Some(crate::cluster::Cluster::Devnet),
         };
 
         let dashboard_event =
-            DashboardClient::convert_parsed_event_to_dashboard_event(&parsed_event);
+            DashboardEvent::from(&parsed_event);
 
         assert_eq!(
             dashboard_event.event_type,
@@ -1305,11 +1708,16 @@ mod tests {
             dashboard_event.metadata.get("log_index"),
             Some(&"0".to_string())
         );
+        assert_eq!(
+            dashboard_event.metadata.get("instruction_index"),
+            Some(&"2".to_string())
+        );
+        assert_eq!(dashboard_event.context, Some(parsed_event.context()));
 
         // Test PaymentFailed event with failure reason metadata
         let payment_failed_event = TallyEvent::PaymentFailed(PaymentFailed {
             payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
-            payment_terms: payment_terms,
+            payment_terms,
             payer,
             reason: "Insufficient allowance".to_string(),
         });
@@ -1321,10 +1729,12 @@ mod tests {
             block_time: Some(timestamp),
             success: false,
             log_index: 1,
+            instruction_index: None,
+            cluster: None,
         };
 
         let dashboard_payment_failed =
-            DashboardClient::convert_parsed_event_to_dashboard_event(&parsed_payment_failed);
+            DashboardEvent::from(&parsed_payment_failed);
 
         assert_eq!(
             dashboard_payment_failed.event_type,
@@ -1334,5 +1744,27 @@ mod tests {
             dashboard_payment_failed.metadata.get("failure_reason"),
             Some(&"Insufficient allowance".to_string())
         );
+        let context = dashboard_payment_failed.context.unwrap();
+        assert!(!context.success);
+        assert_eq!(context.instruction_index, None);
+
+        // Events without a dashboard type keep their name instead of posing as another type
+        let parsed_resumed = ParsedEventWithContext {
+            event: TallyEvent::PaymentAgreementResumed(crate::events::PaymentAgreementResumed {
+                payee: Pubkey::new_unique(),
+                payment_terms,
+                payer,
+                amount: 10_000_000,
+                total_payments: 3,
+                original_created_ts: timestamp,
+            }),
+            ..parsed_event
+        };
+        let dashboard_resumed = DashboardEvent::from(&parsed_resumed);
+        assert_eq!(
+            dashboard_resumed.event_type,
+            DashboardEventType::Other("PaymentAgreementResumed".to_string())
+        );
+        assert!(!dashboard_resumed.affects_revenue());
     }
 }
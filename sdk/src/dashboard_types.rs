@@ -5,6 +5,7 @@
 #![allow(clippy::cast_possible_truncation)] // Controlled truncation for display formatting
 #![allow(clippy::cast_lossless)] // Safe casting for USDC formatting
 
+use crate::events::EventContext;
 use crate::program_types::{PaymentTerms, PaymentAgreement};
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use serde::{Deserialize, Serialize};
@@ -34,6 +35,24 @@ pub struct Overview {
     pub payee_authority: Pubkey,
     /// USDC mint being used
     pub usdc_mint: Pubkey,
+    /// Number of unique payers with at least one active payment agreement
+    pub active_payers: u32,
+    /// Recurring revenue of active agreements normalized to a 30-day month (in USDC microlamports)
+    pub monthly_recurring_revenue: u64,
+    /// Active payment agreements renewing within the next 7 days, soonest first
+    pub upcoming_renewals: Vec<UpcomingRenewal>,
+    /// Revenue charged to payers since the payee was created, read from the payee account
+    /// (in USDC microlamports)
+    ///
+    /// Unlike `total_revenue`, this counts every payment, including those of closed
+    /// agreements and payments charged before a price change.
+    pub lifetime_revenue: u64,
+    /// Renewal payments collected since the payee was created, read from the payee account
+    pub lifetime_renewals: u64,
+    /// Data source of the metrics that can be derived from event history
+    ///
+    /// All other metrics are always derived from current account state.
+    pub sources: OverviewSources,
 }
 
 impl Overview {
@@ -70,6 +89,40 @@ impl Overview {
     }
 }
 
+/// Data source a dashboard metric was derived from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetricSource {
+    /// Derived from current on-chain account state
+    #[default]
+    Snapshot,
+    /// Derived from program event history (RPC nodes may prune older history)
+    Events,
+}
+
+/// Data sources of the `Overview` metrics that prefer event history
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverviewSources {
+    /// Source of `monthly_revenue`
+    pub monthly_revenue: MetricSource,
+    /// Source of `monthly_new_agreements` and `monthly_paused_agreements`
+    pub monthly_activity: MetricSource,
+}
+
+/// Upcoming renewal of an active payment agreement
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpcomingRenewal {
+    /// Payment agreement PDA address
+    pub agreement_address: Pubkey,
+    /// Payment terms PDA address
+    pub payment_terms_address: Pubkey,
+    /// Payer who will be charged
+    pub payer: Pubkey,
+    /// Unix timestamp when the next payment becomes due
+    pub next_payment_ts: i64,
+    /// Expected payment amount (in USDC microlamports)
+    pub amount: u64,
+}
+
 /// Analytics data for specific payment terms
 #[allow(clippy::derive_partial_eq_without_eq)] // Contains f64 fields
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -169,6 +222,9 @@ pub struct DashboardEvent {
     pub timestamp: i64,
     /// Additional event metadata
     pub metadata: HashMap<String, String>,
+    /// Transaction context of the on-chain event this was built from
+    #[serde(default)]
+    pub context: Option<EventContext>,
 }
 
 /// Types of events that can occur in the payment agreement system
@@ -188,6 +244,8 @@ pub enum DashboardEventType {
     PaymentTermsUpdated,
     /// Payee fees withdrawn
     FeesWithdrawn,
+    /// Any other program event, by its event name
+    Other(String),
 }
 
 impl DashboardEvent {
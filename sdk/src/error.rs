@@ -7,14 +7,16 @@
 //!
 //! The SDK automatically maps specific program error codes to detailed error variants:
 //!
-//! - **6012**: `InvalidPayerTokenAccount` - Invalid payer USDC token account
-//! - **6013**: `InvalidPayeeTreasuryAccount` - Invalid payee treasury account
-//! - **6014**: `InvalidPlatformTreasuryAccount` - Invalid platform treasury account
-//! - **6015**: `InvalidUsdcMint` - Invalid USDC mint account
-//! - **6016**: `PayeeNotFound` - Payee account not found or invalid
-//! - **6017**: `PaymentTermsNotFound` - `PaymentAgreement` `payment_terms` not found or invalid
-//! - **6018**: `PaymentAgreementNotFound` - `PaymentAgreement` not found or invalid
-//! - **6019**: `ConfigNotFound` - Global configuration account not found
+//! - **6003**: `WrongMint` - Token account or mint is not the payee's USDC mint
+//! - **6011**: `InvalidPayerTokenAccount` - Invalid payer USDC token account
+//! - **6012**: `InvalidPayeeTreasuryAccount` - Invalid payee treasury account
+//! - **6013**: `InvalidPlatformTreasuryAccount` - Invalid platform treasury account
+//! - **6014**: `InvalidUsdcMint` - Invalid USDC mint account
+//! - **6015**: `PayeeNotFound` - Payee account not found or invalid
+//! - **6016**: `PaymentTermsNotFound` - `PaymentAgreement` `payment_terms` not found or invalid
+//! - **6017**: `PaymentAgreementNotFound` - `PaymentAgreement` not found or invalid
+//! - **6018**: `ConfigNotFound` - Global configuration account not found
+//! - **6048**: `WrongOwner` - Payer token account not owned by the agreement's payer
 //!
 //! # Example
 //!
@@ -110,40 +112,88 @@ pub enum TallyError {
     #[error("RPC error: {0}")]
     RpcError(String),
 
-    // Specific program error variants (maps to Anchor error codes 6012-6019)
-    /// Invalid payer token account (program error 6012)
+    /// RPC call still failing with a transient error (timeout, rate limit, unavailable
+    /// endpoint) after the client's retry policy was exhausted
+    #[error("RPC {method} failed after {attempts} attempt(s): {last_error}")]
+    RpcRetriesExhausted {
+        method: String,
+        attempts: u32,
+        last_error: String,
+    },
+
+    /// SDK discriminators don't match the IDL of the deployed program
+    #[error("SDK discriminators do not match the deployed program: {0}")]
+    DiscriminatorMismatch(crate::verify::DiscriminatorReport),
+
+    /// Sign-In-With-Solana message rejected during verification
+    #[error("SIWS verification failed: {0}")]
+    SiwsVerification(String),
+
+    /// Dashboard capability token rejected during issuance or verification
+    #[error("Capability token rejected: {0}")]
+    CapabilityVerification(String),
+
+    /// State export failed validation on import (version, checksum or account mismatch)
+    #[error("Invalid state export: {0}")]
+    InvalidStateExport(String),
+
+    /// Account written in a newer layout version than this SDK can decode
+    #[error("Unsupported {account} layout version {version}; this SDK reads up to version {supported}")]
+    UnsupportedAccountVersion {
+        account: &'static str,
+        version: u8,
+        supported: u8,
+    },
+
+    /// Signature or address supplied by a user could not be parsed
+    #[error("Malformed {kind}: {problem}")]
+    MalformedInput {
+        kind: crate::input::InputKind,
+        problem: crate::input::Malformed,
+    },
+
+    // Specific program error variants (maps to Anchor error codes 6003, 6011-6018 and 6048)
+    /// Token account or mint is not the payee's USDC mint (program error 6003)
+    #[error("Invalid token mint provided. Only USDC is supported for payments.")]
+    WrongMint,
+
+    /// Invalid payer token account (program error 6011)
     #[error("Invalid payer token account. Ensure the account is a valid USDC token account owned by the payer.")]
     InvalidPayerTokenAccount,
 
-    /// Invalid payee treasury token account (program error 6013)
+    /// Invalid payee treasury token account (program error 6012)
     #[error("Invalid payee treasury token account. Ensure the account is a valid USDC token account.")]
     InvalidPayeeTreasuryAccount,
 
-    /// Invalid platform treasury token account (program error 6014)
+    /// Invalid platform treasury token account (program error 6013)
     #[error("Invalid platform treasury token account. Ensure the account is a valid USDC token account.")]
     InvalidPlatformTreasuryAccount,
 
-    /// Invalid USDC mint account (program error 6015)
+    /// Invalid USDC mint account (program error 6014)
     #[error("Invalid USDC mint account. Ensure the account is a valid token mint account.")]
     InvalidUsdcMint,
 
-    /// Payee account not found or invalid (program error 6016)
+    /// Payee account not found or invalid (program error 6015)
     #[error(
         "Payee account not found or invalid. Ensure the payee has been properly initialized."
     )]
     PayeeNotFound,
 
-    /// `PaymentTerms` not found or invalid (program error 6017)
+    /// `PaymentTerms` not found or invalid (program error 6016)
     #[error("PaymentTerms not found or invalid. Ensure the payment terms exist and belong to the specified payee.")]
     PaymentTermsNotFound,
 
-    /// `PaymentAgreement` not found or invalid (program error 6018)
+    /// `PaymentAgreement` not found or invalid (program error 6017)
     #[error("PaymentAgreement not found or invalid. Ensure the payment agreement exists for these payment terms and payer.")]
     PaymentAgreementNotFound,
 
-    /// Global configuration account not found or invalid (program error 6019)
+    /// Global configuration account not found or invalid (program error 6018)
     #[error("Global configuration account not found or invalid. Ensure the program has been properly initialized.")]
     ConfigNotFound,
+
+    /// Payer token account not owned by the agreement's payer (program error 6048)
+    #[error("Wrong token account owner. The payer token account must be owned by the agreement's payer.")]
+    WrongOwner,
 }
 
 // Update the From implementation for anchor_client::ClientError to use our mapping
@@ -185,6 +235,24 @@ impl From<anyhow::Error> for TallyError {
 }
 
 impl TallyError {
+    /// Map a program error code to its specific `TallyError` variant, if it has one
+    #[must_use]
+    pub const fn from_program_error_code(error_code: u32) -> Option<Self> {
+        match error_code {
+            6003 => Some(Self::WrongMint),
+            6011 => Some(Self::InvalidPayerTokenAccount),
+            6012 => Some(Self::InvalidPayeeTreasuryAccount),
+            6013 => Some(Self::InvalidPlatformTreasuryAccount),
+            6014 => Some(Self::InvalidUsdcMint),
+            6015 => Some(Self::PayeeNotFound),
+            6016 => Some(Self::PaymentTermsNotFound),
+            6017 => Some(Self::PaymentAgreementNotFound),
+            6018 => Some(Self::ConfigNotFound),
+            6048 => Some(Self::WrongOwner),
+            _ => None,
+        }
+    }
+
     /// Map program error codes to specific `TallyError` variants
     ///
     /// This function takes an Anchor error and attempts to map it to a more specific
@@ -204,18 +272,9 @@ impl TallyError {
             Error::AnchorError(anchor_err) => {
                 // Map specific error codes to our custom variants
                 // Anchor assigns error codes starting from 6000 for custom errors
-                match anchor_err.error_code_number {
-                    6012 => Self::InvalidPayerTokenAccount,
-                    6013 => Self::InvalidPayeeTreasuryAccount,
-                    6014 => Self::InvalidPlatformTreasuryAccount,
-                    6015 => Self::InvalidUsdcMint,
-                    6016 => Self::PayeeNotFound,
-                    6017 => Self::PaymentTermsNotFound,
-                    6018 => Self::PaymentAgreementNotFound,
-                    6019 => Self::ConfigNotFound,
-                    // For any other error codes, fall back to the generic Anchor error
-                    _ => Self::Anchor(anchor_error),
-                }
+                // For any other error codes, fall back to the generic Anchor error
+                Self::from_program_error_code(anchor_err.error_code_number)
+                    .unwrap_or(Self::Anchor(anchor_error))
             }
             // For non-AnchorError variants, use the generic Anchor wrapper
             Error::ProgramError(_) => Self::Anchor(anchor_error),
@@ -241,16 +300,8 @@ impl TallyError {
             ) = solana_err.get_transaction_error()
             {
                 // Map specific program error codes
-                match error_code {
-                    6012 => return Self::InvalidPayerTokenAccount,
-                    6013 => return Self::InvalidPayeeTreasuryAccount,
-                    6014 => return Self::InvalidPlatformTreasuryAccount,
-                    6015 => return Self::InvalidUsdcMint,
-                    6016 => return Self::PayeeNotFound,
-                    6017 => return Self::PaymentTermsNotFound,
-                    6018 => return Self::PaymentAgreementNotFound,
-                    6019 => return Self::ConfigNotFound,
-                    _ => {} // Fall through to generic handling
+                if let Some(error) = Self::from_program_error_code(error_code) {
+                    return error;
                 }
             }
         }
@@ -259,3 +310,48 @@ impl TallyError {
         Self::AnchorClient(Box::new(client_error))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_program_error_code() {
+        assert!(matches!(
+            TallyError::from_program_error_code(6003),
+            Some(TallyError::WrongMint)
+        ));
+        assert!(matches!(
+            TallyError::from_program_error_code(6011),
+            Some(TallyError::InvalidPayerTokenAccount)
+        ));
+        assert!(matches!(
+            TallyError::from_program_error_code(6018),
+            Some(TallyError::ConfigNotFound)
+        ));
+        assert!(matches!(
+            TallyError::from_program_error_code(6048),
+            Some(TallyError::WrongOwner)
+        ));
+        assert!(TallyError::from_program_error_code(6009).is_none());
+    }
+
+    #[test]
+    fn test_anchor_error_mapping_matches_program() {
+        let error = anchor_lang::error::Error::from(
+            tally_protocol::errors::RecurringPaymentError::WrongOwner,
+        );
+        assert!(matches!(
+            TallyError::from_anchor_error(error),
+            TallyError::WrongOwner
+        ));
+
+        let error = anchor_lang::error::Error::from(
+            tally_protocol::errors::RecurringPaymentError::InvalidPayerTokenAccount,
+        );
+        assert!(matches!(
+            TallyError::from_anchor_error(error),
+            TallyError::InvalidPayerTokenAccount
+        ));
+    }
+}
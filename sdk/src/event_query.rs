@@ -6,8 +6,10 @@
 
 #![forbid(unsafe_code)]
 
+use crate::cluster::Cluster;
+use crate::events::{parse_events_with_context, ParsedEventWithContext};
 use crate::solana_sdk::pubkey::Pubkey;
-use crate::{error::Result, events::TallyEvent, SimpleTallyClient, TallyError};
+use crate::{error::Result, SimpleTallyClient, TallyError};
 use anchor_client::solana_account_decoder::UiAccountEncoding;
 use anchor_client::solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
 use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
@@ -16,7 +18,6 @@ use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, signature::
 use anyhow::Context;
 use chrono::{DateTime, Utc};
 use lru::LruCache;
-use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::str::FromStr;
@@ -66,21 +67,10 @@ pub struct EventQueryClientConfig {
 }
 
 /// A parsed event with transaction context
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ParsedEvent {
-    /// Transaction signature that contains this event
-    pub signature: Signature,
-    /// Slot number where transaction was processed
-    pub slot: u64,
-    /// Block time (Unix timestamp)
-    pub block_time: Option<i64>,
-    /// Transaction success status
-    pub success: bool,
-    /// The parsed Tally event
-    pub event: TallyEvent,
-    /// Log index within the transaction
-    pub log_index: usize,
-}
+///
+/// Same type the log parser and dashboard use, so query results convert without
+/// re-parsing.
+pub type ParsedEvent = ParsedEventWithContext;
 
 /// Cache entry for query results
 #[derive(Debug, Clone)]
@@ -141,6 +131,10 @@ pub struct EventQueryClient {
     config: EventQueryConfig,
     /// LRU cache for query results
     cache: Arc<Mutex<LruCache<QueryKey, CacheEntry>>>,
+    /// Block times by slot, for transactions returned without one
+    block_times: Arc<Mutex<LruCache<u64, i64>>>,
+    /// Cluster recorded on every parsed event
+    cluster: Option<Cluster>,
 }
 
 impl EventQueryClient {
@@ -162,6 +156,7 @@ impl EventQueryClient {
         let cache_size = NonZeroUsize::new(config.query_config.max_cache_size)
             .context("Cache size must be greater than 0")?;
         let cache = Arc::new(Mutex::new(LruCache::new(cache_size)));
+        let block_times = Arc::new(Mutex::new(LruCache::new(cache_size)));
 
         info!(
             service = "tally-sdk",
@@ -179,9 +174,20 @@ impl EventQueryClient {
             program_id: config.program_id,
             config: config.query_config,
             cache,
+            block_times,
+            cluster: Cluster::from_rpc_url(&config.rpc_url),
         })
     }
 
+    /// Record `cluster` on parsed events instead of the one guessed from the RPC URL
+    ///
+    /// Needed for private RPC endpoints whose URL doesn't name the cluster.
+    #[must_use]
+    pub const fn with_cluster(mut self, cluster: Cluster) -> Self {
+        self.cluster = Some(cluster);
+        self
+    }
+
     /// Create a new `EventQueryClient` with program ID from environment
     ///
     /// # Arguments
@@ -306,7 +312,7 @@ impl EventQueryClient {
 
     /// Sort events by slot (most recent first) and apply limit
     fn sort_and_limit_events(mut events: Vec<ParsedEvent>, limit: usize) -> Vec<ParsedEvent> {
-        events.sort_by(|a, b| b.slot.cmp(&a.slot));
+        events.sort_by_key(|e| std::cmp::Reverse(e.slot));
         events.truncate(limit);
         events
     }
@@ -488,7 +494,7 @@ impl EventQueryClient {
 
     /// Sort events by block time (most recent first)
     fn sort_events_by_block_time(mut events: Vec<ParsedEvent>) -> Vec<ParsedEvent> {
-        events.sort_by(|a, b| b.block_time.unwrap_or(0).cmp(&a.block_time.unwrap_or(0)));
+        events.sort_by_key(|e| std::cmp::Reverse(e.block_time.unwrap_or(0)));
         events
     }
 
@@ -768,25 +774,89 @@ impl EventQueryClient {
 
     /// Process a single chunk of signatures
     fn process_signature_chunk(&self, chunk: &[Signature]) -> Vec<ParsedEvent> {
-        let batch_events = Vec::new();
+        let mut batch_events = Vec::new();
 
         for signature in chunk {
-            self.try_fetch_and_log_transaction(signature);
+            match self.sdk_client.get_transaction(signature) {
+                Ok(transaction) => {
+                    Self::log_transaction_received(signature);
+                    batch_events.extend(Self::parse_transaction(
+                        &self.program_id,
+                        self.cluster,
+                        *signature,
+                        &transaction,
+                        |slot| self.block_time(slot),
+                    ));
+                }
+                Err(e) => {
+                    Self::log_transaction_fetch_error(signature, &e);
+                }
+            }
         }
 
         batch_events
     }
 
-    /// Try to fetch transaction and log result
-    fn try_fetch_and_log_transaction(&self, signature: &Signature) {
-        match self.sdk_client.get_transaction(signature) {
-            Ok(_transaction) => {
-                Self::log_transaction_received(signature);
-            }
-            Err(e) => {
-                Self::log_transaction_fetch_error(signature, &e);
-            }
+    /// Parse the Tally events of a `getTransaction` response
+    ///
+    /// Every event carries the transaction's signature, slot, block time, success and
+    /// cluster, plus its log and instruction index. `lookup_block_time` fills in the block
+    /// time when the RPC node didn't return one.
+    fn parse_transaction(
+        program_id: &Pubkey,
+        cluster: Option<Cluster>,
+        signature: Signature,
+        transaction: &serde_json::Value,
+        lookup_block_time: impl FnOnce(u64) -> Option<i64>,
+    ) -> Vec<ParsedEvent> {
+        let (Some(slot), Some(meta)) = (
+            transaction.get("slot").and_then(serde_json::Value::as_u64),
+            transaction.get("meta"),
+        ) else {
+            return Vec::new();
+        };
+        let logs: Vec<String> = meta
+            .get("logMessages")
+            .and_then(serde_json::Value::as_array)
+            .map(|logs| {
+                logs.iter()
+                    .filter_map(|log| log.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if logs.is_empty() {
+            return Vec::new();
         }
+
+        let success = meta.get("err").is_none_or(serde_json::Value::is_null);
+        let block_time = transaction
+            .get("blockTime")
+            .and_then(serde_json::Value::as_i64)
+            .or_else(|| lookup_block_time(slot));
+
+        parse_events_with_context(&logs, program_id, signature, slot, block_time, success)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|event| event.with_cluster(cluster))
+            .collect()
+    }
+
+    /// Block time of `slot`, from the cache or the RPC node
+    fn block_time(&self, slot: u64) -> Option<i64> {
+        if let Some(block_time) = self
+            .block_times
+            .lock()
+            .ok()
+            .and_then(|mut block_times| block_times.get(&slot).copied())
+        {
+            return Some(block_time);
+        }
+
+        let block_time = self.sdk_client.get_block_time(slot).ok()?;
+        if let Ok(mut block_times) = self.block_times.lock() {
+            block_times.put(slot, block_time);
+        }
+        Some(block_time)
     }
 
     /// Log successful transaction fetch
@@ -796,7 +866,7 @@ impl EventQueryClient {
             component = "event_query_client",
             event = "transaction_received",
             signature = %signature,
-            "Transaction data received - event parsing temporarily disabled"
+            "Transaction data received"
         );
     }
 
@@ -1142,6 +1212,78 @@ mod tests {
         assert!(old_slot <= current_slot);
     }
 
+    #[test]
+    fn test_parse_transaction_carries_context() {
+        use crate::events::{compute_event_discriminator, PaymentAgreementPaused, TallyEvent};
+        use anchor_lang::AnchorSerialize;
+        use base64::prelude::*;
+
+        let program_id = crate::program_id();
+        let mut data = compute_event_discriminator("PaymentAgreementPaused").to_vec();
+        PaymentAgreementPaused {
+            payee: Pubkey::new_unique(),
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+        }
+        .serialize(&mut data)
+        .unwrap();
+        let event_log = format!("Program data: {program_id} {}", BASE64_STANDARD.encode(data));
+        let signature = Signature::new_unique();
+
+        let transaction = serde_json::json!({
+            "slot": 250_000_000u64,
+            "blockTime": null,
+            "meta": {
+                "err": null,
+                "logMessages": [
+                    "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+                    format!("Program {program_id} invoke [1]"),
+                    event_log,
+                ],
+            },
+        });
+
+        let events = EventQueryClient::parse_transaction(
+            &program_id,
+            Some(Cluster::Devnet),
+            signature,
+            &transaction,
+            |slot| (slot == 250_000_000).then_some(1_700_000_000),
+        );
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert!(matches!(event.event, TallyEvent::PaymentAgreementPaused(_)));
+        assert_eq!(event.signature, signature);
+        assert_eq!(event.slot, 250_000_000);
+        assert_eq!(event.block_time, Some(1_700_000_000), "Missing block time is looked up");
+        assert!(event.success);
+        assert_eq!(event.log_index, 0);
+        assert_eq!(event.instruction_index, Some(1));
+        assert_eq!(event.cluster, Some(Cluster::Devnet));
+
+        // Failed transactions keep their events but are marked unsuccessful, and a
+        // block time returned by the node wins over the lookup
+        let mut failed = transaction;
+        failed["blockTime"] = serde_json::json!(1_600_000_000);
+        failed["meta"]["err"] = serde_json::json!({ "InstructionError": [1, "Custom"] });
+        let events = EventQueryClient::parse_transaction(&program_id, None, signature, &failed, |_| {
+            panic!("Block time should not be looked up")
+        });
+        assert!(!events[0].success);
+        assert_eq!(events[0].block_time, Some(1_600_000_000));
+        assert_eq!(events[0].cluster, None);
+    }
+
+    #[test]
+    fn test_cluster_detected_from_rpc_url() {
+        let client = EventQueryClient::new(create_test_config()).unwrap();
+        assert_eq!(client.cluster, Some(Cluster::Localnet));
+
+        let client = client.with_cluster(Cluster::Mainnet);
+        assert_eq!(client.cluster, Some(Cluster::Mainnet));
+    }
+
     #[test]
     fn test_cache_operations() {
         let config = create_test_config();
@@ -1,6 +1,6 @@
 //! Event parsing utilities for Tally program events and structured receipts
 
-use crate::{error::Result, TallyError};
+use crate::{cluster::Cluster, error::Result, TallyError};
 use anchor_client::solana_sdk::{signature::Signature, transaction::TransactionError};
 use anchor_lang::prelude::*;
 use base64::prelude::*;
@@ -21,6 +21,10 @@ pub struct PaymentAgreementStarted {
     pub payer: Pubkey,
     /// The amount paid for the first payment (in USDC micro-units)
     pub amount: u64,
+    /// Salted hash of the payee's internal customer reference, if supplied
+    pub external_ref_hash: Option<[u8; 32]>,
+    /// Delegate allowance the payer approved (in USDC micro-units)
+    pub delegated_amount: u64,
 }
 
 /// Event emitted when a payment is successfully executed
@@ -40,6 +44,8 @@ pub struct PaymentExecuted {
     pub keeper: Pubkey,
     /// The fee paid to the keeper (in USDC micro-units)
     pub keeper_fee: u64,
+    /// The agreement's renewal counter after this payment; unique per agreement
+    pub period_index: u64,
 }
 
 /// Event emitted when a payment agreement is paused
@@ -191,6 +197,19 @@ pub struct ProgramPaused {
     pub authority: Pubkey,
     /// Unix timestamp when program was paused
     pub timestamp: i64,
+    /// Reason for the pause as zero-padded UTF-8 (see [`ProgramPaused::reason_text`])
+    #[serde(with = "crate::program_types::pause_reason_bytes")]
+    pub reason: [u8; 64],
+    /// Unix timestamp at which the pause lapses automatically, if scheduled
+    pub auto_unpause_ts: Option<i64>,
+}
+
+impl ProgramPaused {
+    /// Pause reason as text, with zero padding removed
+    #[must_use]
+    pub fn reason_text(&self) -> String {
+        crate::program_types::decode_pause_reason(&self.reason)
+    }
 }
 
 /// Event emitted when the program is unpaused
@@ -270,6 +289,8 @@ pub struct ConfigUpdated {
     pub min_platform_fee_bps: u16,
     /// Maximum platform fee in basis points
     pub max_platform_fee_bps: u16,
+    /// Seconds before `next_payment_ts` from which renewals are accepted
+    pub renewal_tolerance_secs: u64,
     /// Platform authority who made the update
     pub updated_by: Pubkey,
 }
@@ -372,6 +393,292 @@ pub struct PaymentTermsUpdated {
     pub updated_by: Pubkey,
 }
 
+/// Event emitted when a payee authority proposes handing the payee to a new key
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct PayeeAuthorityTransferInitiated {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The current payee authority
+    pub authority: Pubkey,
+    /// The authority that must accept the transfer
+    pub new_authority: Pubkey,
+    /// Unix timestamp when the transfer was initiated
+    pub timestamp: i64,
+}
+
+/// Event emitted when a new authority accepts a payee authority transfer
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct PayeeAuthorityTransferred {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The previous payee authority
+    pub old_authority: Pubkey,
+    /// The payee authority from now on
+    pub new_authority: Pubkey,
+    /// The new treasury ATA receiving payments
+    pub treasury_ata: Pubkey,
+    /// Unix timestamp when the transfer completed
+    pub timestamp: i64,
+}
+
+/// Event emitted when a payee authority cancels a pending authority transfer
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct PayeeAuthorityTransferCancelled {
+    /// The payee account
+    pub payee: Pubkey,
+    /// The payee authority who cancelled the transfer
+    pub authority: Pubkey,
+    /// The authority whose pending transfer was revoked
+    pub cancelled_authority: Pubkey,
+    /// Unix timestamp when the transfer was cancelled
+    pub timestamp: i64,
+}
+
+/// Event emitted when `resume_agreement` applies pause credit to the first charge after a pause
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct CreditApplied {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms being resumed
+    pub payment_terms: Pubkey,
+    /// The payer's public key
+    pub payer: Pubkey,
+    /// Price of the billing period before credit (in USDC micro-units)
+    pub amount: u64,
+    /// Credit applied against the charge (in USDC micro-units)
+    pub credit_applied: u64,
+    /// Amount actually charged to the payer (in USDC micro-units)
+    pub amount_charged: u64,
+    /// Credit left on the agreement for a later resume (in USDC micro-units)
+    pub remaining_credit: u64,
+    /// Unix timestamp when the agreement was paused
+    pub paused_at_ts: i64,
+}
+
+/// Event emitted when `execute_payment` pauses an agreement because its payment terms
+/// reached their sunset
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct TermsSunset {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The deactivated payment terms
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was paused
+    pub payer: Pubkey,
+    /// Sunset timestamp set by the payee
+    pub sunset_ts: i64,
+    /// Unix timestamp when the agreement was paused
+    pub timestamp: i64,
+}
+
+/// Event emitted when the payee confirms activation and an escrowed first payment is
+/// released to the treasuries
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct EscrowReleased {
+    /// The payee who confirmed activation
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose first payment was released
+    pub payer: Pubkey,
+    /// Escrowed amount released (in USDC micro-units)
+    pub amount: u64,
+    /// Platform fee taken from the escrowed amount (in USDC micro-units)
+    pub platform_fee: u64,
+    /// Amount sent to the payee treasury (in USDC micro-units)
+    pub payee_amount: u64,
+    /// Unix timestamp when the escrow was released
+    pub timestamp: i64,
+}
+
+/// Event emitted when an escrowed first payment is returned to the payer after the
+/// activation window lapsed
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct EscrowRefunded {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer who was refunded
+    pub payer: Pubkey,
+    /// Escrowed amount returned to the payer (in USDC micro-units)
+    pub amount: u64,
+    /// End of the activation window
+    pub expires_ts: i64,
+    /// Unix timestamp when the escrow was refunded
+    pub timestamp: i64,
+}
+
+/// Event emitted when a keeper takes its fee in SOL from the keeper fee vault
+///
+/// The USDC keeper fee goes to the platform treasury instead of the keeper.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct KeeperPaidInSol {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms being executed
+    pub payment_terms: Pubkey,
+    /// The payer who was charged
+    pub payer: Pubkey,
+    /// The keeper who executed the payment
+    pub keeper: Pubkey,
+    /// Keeper fee the payer paid, sent to the platform treasury (in USDC micro-units)
+    pub keeper_fee: u64,
+    /// Platform rate the fee was converted at, in lamports per whole USDC
+    pub lamports_per_usdc: u64,
+    /// Lamports paid to the keeper from the vault
+    pub lamports: u64,
+}
+
+/// Event emitted when `execute_payment` suspends an agreement instead of charging it
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct AgreementSuspended {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was suspended
+    pub payer: Pubkey,
+    /// Why the agreement was suspended
+    pub reason: crate::program_types::SuspensionReason,
+    /// The payer token account that caused the suspension
+    pub payer_token_account: Pubkey,
+    /// Unix timestamp when the agreement was suspended
+    pub timestamp: i64,
+}
+
+/// Event emitted when `resume_after_unfreeze` reinstates a suspended agreement
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct AgreementUnsuspended {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose agreement was reinstated
+    pub payer: Pubkey,
+    /// Unix timestamp of the agreement's next payment
+    pub next_payment_ts: i64,
+    /// Unix timestamp when the agreement was reinstated
+    pub timestamp: i64,
+}
+
+/// Event emitted when `start_agreement` pulls a security deposit into escrow
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct DepositHeld {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms requiring the deposit
+    pub payment_terms: Pubkey,
+    /// The payer who paid the deposit
+    pub payer: Pubkey,
+    /// Amount pulled into escrow by this start (in USDC micro-units)
+    pub amount: u64,
+    /// Total deposit now held for the agreement (in USDC micro-units)
+    pub total_held: u64,
+    /// Unix timestamp when the deposit was pulled
+    pub timestamp: i64,
+}
+
+/// Event emitted when the payee claims damages from a held security deposit
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct DepositClaimed {
+    /// The payee who claimed the damages
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer whose deposit was claimed from
+    pub payer: Pubkey,
+    /// Amount sent to the payee treasury (in USDC micro-units)
+    pub amount: u64,
+    /// Deposit still held after the claim (in USDC micro-units)
+    pub remaining: u64,
+    /// Unix timestamp of the claim
+    pub timestamp: i64,
+}
+
+/// Event emitted when `close_agreement` returns the unclaimed deposit to the payer
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct DepositReleased {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer who was refunded
+    pub payer: Pubkey,
+    /// Amount returned to the payer (in USDC micro-units)
+    pub amount: u64,
+    /// Unix timestamp when the deposit was released
+    pub timestamp: i64,
+}
+
+/// Event emitted when a keeper sweeps the accrual of a streaming agreement, or a resume
+/// collects accrual left unswept at the pause
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct Swept {
+    /// The payee who owns the payment terms
+    pub payee: Pubkey,
+    /// The streaming payment terms of the agreement
+    pub payment_terms: Pubkey,
+    /// The payer who was charged
+    pub payer: Pubkey,
+    /// The keeper who swept the accrual (the payer for accrual collected on resume)
+    pub keeper: Pubkey,
+    /// Amount charged to the payer (in USDC micro-units)
+    pub amount: u64,
+    /// The fee paid to the keeper out of `amount` (in USDC micro-units)
+    pub keeper_fee: u64,
+    /// Start of the accrual swept (the agreement's previous `last_sweep_ts`)
+    pub from_ts: i64,
+    /// End of the accrual swept (the agreement's new `last_sweep_ts`)
+    pub to_ts: i64,
+}
+
+/// Compact view of an agreement's state, emitted after every lifecycle mutation
+///
+/// Emitted alongside the start, payment, pause, resume and close events, so indexers
+/// can keep agreement views current without fetching the account.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct AgreementSnapshot {
+    /// The payment agreement account
+    pub payment_agreement: Pubkey,
+    /// Lifecycle status after the mutation
+    pub status: crate::program_types::AgreementStatus,
+    /// Unix timestamp of the next payment
+    pub next_payment_ts: i64,
+    /// Number of renewals charged by `execute_payment`
+    pub period_index: u64,
+}
+
 /// All possible Tally program events
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TallyEvent {
@@ -411,6 +718,36 @@ pub enum TallyEvent {
     VolumeTierUpgraded(VolumeTierUpgraded),
     /// Payment terms updated
     PaymentTermsUpdated(PaymentTermsUpdated),
+    /// Payee authority transfer proposed
+    PayeeAuthorityTransferInitiated(PayeeAuthorityTransferInitiated),
+    /// Payee authority transfer accepted
+    PayeeAuthorityTransferred(PayeeAuthorityTransferred),
+    /// Payee authority transfer cancelled
+    PayeeAuthorityTransferCancelled(PayeeAuthorityTransferCancelled),
+    /// Pause credit applied on resume
+    CreditApplied(CreditApplied),
+    /// Agreement paused at the sunset of deactivated payment terms
+    TermsSunset(TermsSunset),
+    /// Escrowed first payment released on activation
+    EscrowReleased(EscrowReleased),
+    /// Escrowed first payment refunded after the activation window
+    EscrowRefunded(EscrowRefunded),
+    /// Keeper fee paid in SOL from the keeper fee vault
+    KeeperPaidInSol(KeeperPaidInSol),
+    /// Agreement suspended because the payer token account is frozen
+    AgreementSuspended(AgreementSuspended),
+    /// Suspended agreement reinstated after the token account was thawed
+    AgreementUnsuspended(AgreementUnsuspended),
+    /// Security deposit pulled into escrow when an agreement started
+    DepositHeld(DepositHeld),
+    /// Payee claimed damages from a security deposit
+    DepositClaimed(DepositClaimed),
+    /// Unclaimed security deposit returned to the payer on close
+    DepositReleased(DepositReleased),
+    /// Streaming agreement accrual swept by a keeper
+    Swept(Swept),
+    /// Agreement state after a lifecycle mutation
+    AgreementSnapshot(AgreementSnapshot),
 }
 
 /// Enhanced parsed event with transaction context for RPC queries and WebSocket streaming
@@ -428,6 +765,31 @@ pub struct ParsedEventWithContext {
     pub event: TallyEvent,
     /// Log index within the transaction
     pub log_index: usize,
+    /// Index of the top-level instruction that emitted the event, if the logs show it
+    #[serde(default)]
+    pub instruction_index: Option<usize>,
+    /// Cluster the transaction was read from, if known
+    #[serde(default)]
+    pub cluster: Option<Cluster>,
+}
+
+/// Transaction context of a parsed event, without the event itself
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventContext {
+    /// Transaction signature that contains the event
+    pub signature: Signature,
+    /// Slot number where transaction was processed
+    pub slot: u64,
+    /// Block time (Unix timestamp)
+    pub block_time: Option<i64>,
+    /// Transaction success status
+    pub success: bool,
+    /// Log index within the transaction
+    pub log_index: usize,
+    /// Index of the top-level instruction that emitted the event, if known
+    pub instruction_index: Option<usize>,
+    /// Cluster the transaction was read from, if known
+    pub cluster: Option<Cluster>,
 }
 
 /// WebSocket-friendly event data for dashboard streaming
@@ -469,6 +831,36 @@ impl ParsedEventWithContext {
             success,
             event,
             log_index,
+            instruction_index: None,
+            cluster: None,
+        }
+    }
+
+    /// Set the index of the top-level instruction that emitted the event
+    #[must_use]
+    pub const fn with_instruction_index(mut self, instruction_index: Option<usize>) -> Self {
+        self.instruction_index = instruction_index;
+        self
+    }
+
+    /// Set the cluster the transaction was read from
+    #[must_use]
+    pub const fn with_cluster(mut self, cluster: Option<Cluster>) -> Self {
+        self.cluster = cluster;
+        self
+    }
+
+    /// Transaction context of the event
+    #[must_use]
+    pub const fn context(&self) -> EventContext {
+        EventContext {
+            signature: self.signature,
+            slot: self.slot,
+            block_time: self.block_time,
+            success: self.success,
+            log_index: self.log_index,
+            instruction_index: self.instruction_index,
+            cluster: self.cluster,
         }
     }
 
@@ -481,6 +873,7 @@ impl ParsedEventWithContext {
         let (event_type, payee_pda, payment_terms_address, amount) = match &self.event {
             TallyEvent::PaymentAgreementStarted(e) => {
                 metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("delegated_amount".to_string(), e.delegated_amount.to_string());
                 ("payment_agreement_started".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
             }
             TallyEvent::PaymentAgreementResumed(e) => {
@@ -534,6 +927,10 @@ impl ParsedEventWithContext {
             }
             TallyEvent::ProgramPaused(e) => {
                 metadata.insert("authority".to_string(), e.authority.to_string());
+                metadata.insert("reason".to_string(), e.reason_text());
+                if let Some(auto_unpause_ts) = e.auto_unpause_ts {
+                    metadata.insert("auto_unpause_ts".to_string(), auto_unpause_ts.to_string());
+                }
                 ("program_paused".to_string(), String::new(), None, None)
             }
             TallyEvent::ProgramUnpaused(e) => {
@@ -563,6 +960,7 @@ impl ParsedEventWithContext {
             TallyEvent::ConfigUpdated(e) => {
                 metadata.insert("updated_by".to_string(), e.updated_by.to_string());
                 metadata.insert("keeper_fee_bps".to_string(), e.keeper_fee_bps.to_string());
+                metadata.insert("renewal_tolerance_secs".to_string(), e.renewal_tolerance_secs.to_string());
                 ("config_updated".to_string(), String::new(), None, None)
             }
             TallyEvent::VolumeTierUpgraded(e) => {
@@ -582,6 +980,101 @@ impl ParsedEventWithContext {
                 }
                 ("payment_terms_updated".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), None)
             }
+            TallyEvent::PayeeAuthorityTransferInitiated(e) => {
+                metadata.insert("authority".to_string(), e.authority.to_string());
+                metadata.insert("new_authority".to_string(), e.new_authority.to_string());
+                ("payee_authority_transfer_initiated".to_string(), e.payee.to_string(), None, None)
+            }
+            TallyEvent::PayeeAuthorityTransferred(e) => {
+                metadata.insert("old_authority".to_string(), e.old_authority.to_string());
+                metadata.insert("new_authority".to_string(), e.new_authority.to_string());
+                metadata.insert("treasury_ata".to_string(), e.treasury_ata.to_string());
+                ("payee_authority_transferred".to_string(), e.payee.to_string(), None, None)
+            }
+            TallyEvent::PayeeAuthorityTransferCancelled(e) => {
+                metadata.insert("authority".to_string(), e.authority.to_string());
+                metadata.insert("cancelled_authority".to_string(), e.cancelled_authority.to_string());
+                ("payee_authority_transfer_cancelled".to_string(), e.payee.to_string(), None, None)
+            }
+            TallyEvent::CreditApplied(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("credit_applied".to_string(), e.credit_applied.to_string());
+                metadata.insert("amount_charged".to_string(), e.amount_charged.to_string());
+                metadata.insert("remaining_credit".to_string(), e.remaining_credit.to_string());
+                metadata.insert("paused_at_ts".to_string(), e.paused_at_ts.to_string());
+                ("credit_applied".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::TermsSunset(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("sunset_ts".to_string(), e.sunset_ts.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("terms_sunset".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), None)
+            }
+            TallyEvent::EscrowReleased(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("platform_fee".to_string(), e.platform_fee.to_string());
+                metadata.insert("payee_amount".to_string(), e.payee_amount.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("escrow_released".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::EscrowRefunded(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("expires_ts".to_string(), e.expires_ts.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("escrow_refunded".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::KeeperPaidInSol(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("keeper".to_string(), e.keeper.to_string());
+                metadata.insert("lamports_per_usdc".to_string(), e.lamports_per_usdc.to_string());
+                metadata.insert("lamports".to_string(), e.lamports.to_string());
+                ("keeper_paid_in_sol".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.keeper_fee))
+            }
+            TallyEvent::AgreementSuspended(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("reason".to_string(), format!("{:?}", e.reason));
+                metadata.insert("payer_token_account".to_string(), e.payer_token_account.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("agreement_suspended".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), None)
+            }
+            TallyEvent::AgreementUnsuspended(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("next_payment_ts".to_string(), e.next_payment_ts.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("agreement_unsuspended".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), None)
+            }
+            TallyEvent::DepositHeld(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("total_held".to_string(), e.total_held.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("deposit_held".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::DepositClaimed(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("remaining".to_string(), e.remaining.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("deposit_claimed".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::DepositReleased(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("timestamp".to_string(), e.timestamp.to_string());
+                ("deposit_released".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::Swept(e) => {
+                metadata.insert("payer".to_string(), e.payer.to_string());
+                metadata.insert("keeper".to_string(), e.keeper.to_string());
+                metadata.insert("keeper_fee".to_string(), e.keeper_fee.to_string());
+                metadata.insert("from_ts".to_string(), e.from_ts.to_string());
+                metadata.insert("to_ts".to_string(), e.to_ts.to_string());
+                ("swept".to_string(), e.payee.to_string(), Some(e.payment_terms.to_string()), Some(e.amount))
+            }
+            TallyEvent::AgreementSnapshot(e) => {
+                metadata.insert("payment_agreement".to_string(), e.payment_agreement.to_string());
+                metadata.insert("status".to_string(), format!("{:?}", e.status));
+                metadata.insert("next_payment_ts".to_string(), e.next_payment_ts.to_string());
+                metadata.insert("period_index".to_string(), e.period_index.to_string());
+                ("agreement_snapshot".to_string(), String::new(), None, None)
+            }
         };
         metadata.insert("slot".to_string(), self.slot.to_string());
         metadata.insert("success".to_string(), self.success.to_string());
@@ -633,6 +1126,20 @@ impl ParsedEventWithContext {
             TallyEvent::DelegateMismatchWarning(e) => Some(e.payee),
             TallyEvent::VolumeTierUpgraded(e) => Some(e.payee),
             TallyEvent::PaymentTermsUpdated(e) => Some(e.payee),
+            TallyEvent::PayeeAuthorityTransferInitiated(e) => Some(e.payee),
+            TallyEvent::PayeeAuthorityTransferred(e) => Some(e.payee),
+            TallyEvent::PayeeAuthorityTransferCancelled(e) => Some(e.payee),
+            TallyEvent::CreditApplied(e) => Some(e.payee),
+            TallyEvent::TermsSunset(e) => Some(e.payee),
+            TallyEvent::EscrowReleased(e) => Some(e.payee),
+            TallyEvent::EscrowRefunded(e) => Some(e.payee),
+            TallyEvent::KeeperPaidInSol(e) => Some(e.payee),
+            TallyEvent::AgreementSuspended(e) => Some(e.payee),
+            TallyEvent::AgreementUnsuspended(e) => Some(e.payee),
+            TallyEvent::DepositHeld(e) => Some(e.payee),
+            TallyEvent::DepositClaimed(e) => Some(e.payee),
+            TallyEvent::DepositReleased(e) => Some(e.payee),
+            TallyEvent::Swept(e) => Some(e.payee),
             _ => None,
         }
     }
@@ -706,6 +1213,25 @@ impl ParsedEventWithContext {
             TallyEvent::ConfigUpdated(_) => "ConfigUpdated".to_string(),
             TallyEvent::VolumeTierUpgraded(_) => "VolumeTierUpgraded".to_string(),
             TallyEvent::PaymentTermsUpdated(_) => "PaymentTermsUpdated".to_string(),
+            TallyEvent::PayeeAuthorityTransferInitiated(_) => {
+                "PayeeAuthorityTransferInitiated".to_string()
+            }
+            TallyEvent::PayeeAuthorityTransferred(_) => "PayeeAuthorityTransferred".to_string(),
+            TallyEvent::PayeeAuthorityTransferCancelled(_) => {
+                "PayeeAuthorityTransferCancelled".to_string()
+            }
+            TallyEvent::CreditApplied(_) => "CreditApplied".to_string(),
+            TallyEvent::TermsSunset(_) => "TermsSunset".to_string(),
+            TallyEvent::EscrowReleased(_) => "EscrowReleased".to_string(),
+            TallyEvent::EscrowRefunded(_) => "EscrowRefunded".to_string(),
+            TallyEvent::KeeperPaidInSol(_) => "KeeperPaidInSol".to_string(),
+            TallyEvent::AgreementSuspended(_) => "AgreementSuspended".to_string(),
+            TallyEvent::AgreementUnsuspended(_) => "AgreementUnsuspended".to_string(),
+            TallyEvent::DepositHeld(_) => "DepositHeld".to_string(),
+            TallyEvent::DepositClaimed(_) => "DepositClaimed".to_string(),
+            TallyEvent::DepositReleased(_) => "DepositReleased".to_string(),
+            TallyEvent::Swept(_) => "Swept".to_string(),
+            TallyEvent::AgreementSnapshot(_) => "AgreementSnapshot".to_string(),
         }
     }
 
@@ -758,7 +1284,7 @@ impl ParsedEventWithContext {
 
 /// Compute the 8-byte discriminator for an Anchor event
 /// Formula: first 8 bytes of SHA256("event:<EventName>")
-fn compute_event_discriminator(event_name: &str) -> [u8; 8] {
+pub(crate) fn compute_event_discriminator(event_name: &str) -> [u8; 8] {
     use anchor_lang::solana_program::hash;
     let preimage = format!("event:{event_name}");
     let hash_result = hash::hash(preimage.as_bytes());
@@ -768,7 +1294,7 @@ fn compute_event_discriminator(event_name: &str) -> [u8; 8] {
 }
 
 /// Get all event discriminators for fast lookup
-fn get_event_discriminators() -> HashMap<[u8; 8], &'static str> {
+pub(crate) fn get_event_discriminators() -> HashMap<[u8; 8], &'static str> {
     let mut discriminators = HashMap::new();
     discriminators.insert(compute_event_discriminator("PaymentAgreementStarted"), "PaymentAgreementStarted");
     discriminators.insert(compute_event_discriminator("PaymentExecuted"), "PaymentExecuted");
@@ -777,6 +1303,25 @@ fn get_event_discriminators() -> HashMap<[u8; 8], &'static str> {
         compute_event_discriminator("PaymentFailed"),
         "PaymentFailed",
     );
+    for name in [
+        "PayeeAuthorityTransferInitiated",
+        "PayeeAuthorityTransferred",
+        "PayeeAuthorityTransferCancelled",
+        "CreditApplied",
+        "TermsSunset",
+        "EscrowReleased",
+        "EscrowRefunded",
+        "KeeperPaidInSol",
+        "AgreementSuspended",
+        "AgreementUnsuspended",
+        "DepositHeld",
+        "DepositClaimed",
+        "DepositReleased",
+        "Swept",
+        "AgreementSnapshot",
+    ] {
+        discriminators.insert(compute_event_discriminator(name), name);
+    }
     discriminators
 }
 
@@ -801,6 +1346,9 @@ pub struct TallyReceipt {
     pub compute_units_consumed: Option<u64>,
     /// Transaction fee in lamports
     pub fee: u64,
+    /// Memo attached to the transaction, such as a customer note on a renewal
+    #[serde(default)]
+    pub memo: Option<String>,
 }
 
 /// Parse Tally events from transaction logs with transaction context
@@ -816,6 +1364,7 @@ pub struct TallyReceipt {
 /// # Returns
 /// * `Ok(Vec<ParsedEventWithContext>)` - Parsed events with context
 /// * `Err(TallyError)` - If parsing fails
+#[tracing::instrument(level = "debug", skip(logs, block_time), fields(signature = %signature), err)]
 pub fn parse_events_with_context(
     logs: &[String],
     program_id: &Pubkey,
@@ -824,14 +1373,14 @@ pub fn parse_events_with_context(
     block_time: Option<i64>,
     success: bool,
 ) -> Result<Vec<ParsedEventWithContext>> {
-    let events = parse_events_from_logs(logs, program_id)?;
-    let mut parsed_events = Vec::new();
-
-    for (log_index, event) in events.into_iter().enumerate() {
-        parsed_events.push(ParsedEventWithContext::new(
-            signature, slot, block_time, success, event, log_index,
-        ));
-    }
+    let parsed_events = parse_logged_events(logs, program_id)
+        .into_iter()
+        .enumerate()
+        .map(|(log_index, (instruction_index, event))| {
+            ParsedEventWithContext::new(signature, slot, block_time, success, event, log_index)
+                .with_instruction_index(instruction_index)
+        })
+        .collect();
 
     Ok(parsed_events)
 }
@@ -845,26 +1394,56 @@ pub fn parse_events_with_context(
 /// # Returns
 /// * `Ok(Vec<TallyEvent>)` - Parsed events
 /// * `Err(TallyError)` - If parsing fails
+#[tracing::instrument(
+    name = "parse_events",
+    level = "debug",
+    skip(logs),
+    fields(program_id = %program_id, log_count = logs.len(), event_count),
+    err
+)]
 pub fn parse_events_from_logs(logs: &[String], program_id: &Pubkey) -> Result<Vec<TallyEvent>> {
+    let events: Vec<TallyEvent> = parse_logged_events(logs, program_id)
+        .into_iter()
+        .map(|(_, event)| event)
+        .collect();
+
+    tracing::Span::current().record("event_count", events.len());
+    Ok(events)
+}
+
+/// Parse Tally events from transaction logs along with the index of the top-level
+/// instruction that emitted each one
+///
+/// The runtime logs `Program <id> invoke [1]` at the start of every top-level
+/// instruction, so events are attributed to the most recent one. Events logged before
+/// any such line (e.g. from truncated logs) have no instruction index.
+fn parse_logged_events(logs: &[String], program_id: &Pubkey) -> Vec<(Option<usize>, TallyEvent)> {
     let mut events = Vec::new();
     let program_data_prefix = format!("Program data: {program_id} ");
+    let mut instruction_index: Option<usize> = None;
 
     for log in logs {
+        if log.starts_with("Program ") && log.ends_with(" invoke [1]") {
+            instruction_index = Some(instruction_index.map_or(0, |index| index.saturating_add(1)));
+        }
         if let Some(data_start) = log.find(&program_data_prefix) {
             let event_data = &log[data_start.saturating_add(program_data_prefix.len())..];
-            if let Ok(event) = parse_single_event(event_data) {
-                events.push(event);
+            let parsed = parse_single_event(event_data);
+            crate::metrics::record_event_parse(parsed.is_ok());
+            if let Ok(event) = parsed {
+                events.push((instruction_index, event));
             }
         }
     }
 
-    Ok(events)
+    events
 }
 
 /// Parse a single event from base64-encoded data
 ///
 /// Anchor events are encoded as: discriminator (8 bytes) + borsh-serialized event data
 /// The discriminator is computed as the first 8 bytes of SHA256("event:<EventName>")
+#[allow(clippy::too_many_lines)]
 pub fn parse_single_event(data: &str) -> Result<TallyEvent> {
     // Decode base64 data
     let decoded_data = base64::prelude::BASE64_STANDARD
@@ -917,6 +1496,100 @@ pub fn parse_single_event(data: &str) -> Result<TallyEvent> {
             })?;
             Ok(TallyEvent::PaymentFailed(event))
         }
+        "PayeeAuthorityTransferInitiated" => {
+            let event = PayeeAuthorityTransferInitiated::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!(
+                    "Failed to deserialize PayeeAuthorityTransferInitiated event: {e}"
+                ))
+            })?;
+            Ok(TallyEvent::PayeeAuthorityTransferInitiated(event))
+        }
+        "PayeeAuthorityTransferred" => {
+            let event = PayeeAuthorityTransferred::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize PayeeAuthorityTransferred event: {e}"))
+            })?;
+            Ok(TallyEvent::PayeeAuthorityTransferred(event))
+        }
+        "PayeeAuthorityTransferCancelled" => {
+            let event = PayeeAuthorityTransferCancelled::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!(
+                    "Failed to deserialize PayeeAuthorityTransferCancelled event: {e}"
+                ))
+            })?;
+            Ok(TallyEvent::PayeeAuthorityTransferCancelled(event))
+        }
+        "CreditApplied" => {
+            let event = CreditApplied::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize CreditApplied event: {e}"))
+            })?;
+            Ok(TallyEvent::CreditApplied(event))
+        }
+        "TermsSunset" => {
+            let event = TermsSunset::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize TermsSunset event: {e}"))
+            })?;
+            Ok(TallyEvent::TermsSunset(event))
+        }
+        "EscrowReleased" => {
+            let event = EscrowReleased::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize EscrowReleased event: {e}"))
+            })?;
+            Ok(TallyEvent::EscrowReleased(event))
+        }
+        "EscrowRefunded" => {
+            let event = EscrowRefunded::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize EscrowRefunded event: {e}"))
+            })?;
+            Ok(TallyEvent::EscrowRefunded(event))
+        }
+        "KeeperPaidInSol" => {
+            let event = KeeperPaidInSol::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize KeeperPaidInSol event: {e}"))
+            })?;
+            Ok(TallyEvent::KeeperPaidInSol(event))
+        }
+        "AgreementSuspended" => {
+            let event = AgreementSuspended::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize AgreementSuspended event: {e}"))
+            })?;
+            Ok(TallyEvent::AgreementSuspended(event))
+        }
+        "AgreementUnsuspended" => {
+            let event = AgreementUnsuspended::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize AgreementUnsuspended event: {e}"))
+            })?;
+            Ok(TallyEvent::AgreementUnsuspended(event))
+        }
+        "DepositHeld" => {
+            let event = DepositHeld::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize DepositHeld event: {e}"))
+            })?;
+            Ok(TallyEvent::DepositHeld(event))
+        }
+        "DepositClaimed" => {
+            let event = DepositClaimed::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize DepositClaimed event: {e}"))
+            })?;
+            Ok(TallyEvent::DepositClaimed(event))
+        }
+        "DepositReleased" => {
+            let event = DepositReleased::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize DepositReleased event: {e}"))
+            })?;
+            Ok(TallyEvent::DepositReleased(event))
+        }
+        "Swept" => {
+            let event = Swept::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize Swept event: {e}"))
+            })?;
+            Ok(TallyEvent::Swept(event))
+        }
+        "AgreementSnapshot" => {
+            let event = AgreementSnapshot::try_from_slice(event_data).map_err(|e| {
+                TallyError::ParseError(format!("Failed to deserialize AgreementSnapshot event: {e}"))
+            })?;
+            Ok(TallyEvent::AgreementSnapshot(event))
+        }
         _ => Err(TallyError::ParseError(format!(
             "Unhandled event type: {event_type}"
         ))),
@@ -955,6 +1628,7 @@ pub struct ReceiptParams {
 /// * `Err(TallyError)` - If parsing fails
 pub fn create_receipt(params: ReceiptParams) -> Result<TallyReceipt> {
     let events = parse_events_from_logs(&params.logs, &params.program_id)?;
+    let memo = extract_memo_from_logs(&params.logs);
 
     Ok(TallyReceipt {
         signature: params.signature,
@@ -966,6 +1640,7 @@ pub fn create_receipt(params: ReceiptParams) -> Result<TallyReceipt> {
         logs: params.logs,
         compute_units_consumed: params.compute_units_consumed,
         fee: params.fee,
+        memo,
     })
 }
 
@@ -1096,6 +1771,7 @@ impl TallyReceipt {
 }
 
 #[cfg(test)]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
 mod tests {
     use super::*;
     use anchor_client::solana_sdk::signature::{Keypair, Signer};
@@ -1145,6 +1821,8 @@ mod tests {
             payment_terms,
             payer,
             amount: 1_000_000, // 1 USDC
+            external_ref_hash: None,
+            delegated_amount: 0,
         };
 
         let receipt = TallyReceipt {
@@ -1157,6 +1835,7 @@ mod tests {
             logs: vec![],
             compute_units_consumed: Some(5000),
             fee: 5000,
+            memo: None,
         };
 
         assert_eq!(receipt.get_agreement_started_event(), Some(&agreement_started_event));
@@ -1180,6 +1859,7 @@ mod tests {
             logs: vec![],
             compute_units_consumed: Some(1000),
             fee: 5000,
+            memo: None,
         };
 
         assert!(!receipt.is_agreement_success());
@@ -1208,6 +1888,30 @@ mod tests {
         assert!(receipt.success);
         assert_eq!(receipt.error, None);
         assert_eq!(receipt.fee, 5000);
+        assert_eq!(receipt.memo, None);
+    }
+
+    #[test]
+    fn test_create_receipt_extracts_memo() {
+        let memo = "Invoice INV-2024-0042";
+        let receipt = create_receipt(ReceiptParams {
+            signature: Signature::default(),
+            block_time: None,
+            slot: 100,
+            success: true,
+            error: None,
+            logs: vec![
+                "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr invoke [1]".to_string(),
+                format!("Program log: Memo (len {}): {memo:?}", memo.len()),
+            ],
+            compute_units_consumed: None,
+            fee: 5000,
+            program_id: crate::program_id(),
+        })
+        .unwrap();
+
+        assert!(crate::transaction_utils::validate_customer_memo(memo).is_ok());
+        assert_eq!(receipt.memo.as_deref(), Some(memo));
     }
 
     // Helper function to create base64-encoded event data for testing
@@ -1243,11 +1947,24 @@ mod tests {
     fn test_get_event_discriminators() {
         let discriminators = get_event_discriminators();
 
-        assert_eq!(discriminators.len(), 4);
+        assert_eq!(discriminators.len(), 19);
         assert!(discriminators.contains_key(&compute_event_discriminator("PaymentAgreementStarted")));
         assert!(discriminators.contains_key(&compute_event_discriminator("PaymentExecuted")));
         assert!(discriminators.contains_key(&compute_event_discriminator("PaymentAgreementPaused")));
         assert!(discriminators.contains_key(&compute_event_discriminator("PaymentFailed")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("PayeeAuthorityTransferred")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("CreditApplied")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("TermsSunset")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("EscrowReleased")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("EscrowRefunded")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("KeeperPaidInSol")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("AgreementSuspended")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("AgreementUnsuspended")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("DepositHeld")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("DepositClaimed")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("DepositReleased")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("Swept")));
+        assert!(discriminators.contains_key(&compute_event_discriminator("AgreementSnapshot")));
     }
 
     #[test]
@@ -1261,6 +1978,8 @@ mod tests {
             payment_terms,
             payer,
             amount: 5_000_000, // 5 USDC
+            external_ref_hash: None,
+            delegated_amount: 15_000_000, // 3 periods
         };
 
         let encoded_data = create_test_event_data("PaymentAgreementStarted", &event);
@@ -1291,6 +2010,7 @@ mod tests {
             amount: 10_000_000, // 10 USDC
             keeper,
             keeper_fee: 50_000, // 0.05 USDC keeper fee
+            period_index: 3,
         };
 
         let encoded_data = create_test_event_data("PaymentExecuted", &event);
@@ -1302,6 +2022,7 @@ mod tests {
                 assert_eq!(parsed.payment_terms, payment_terms);
                 assert_eq!(parsed.payer, payer);
                 assert_eq!(parsed.amount, 10_000_000);
+                assert_eq!(parsed.period_index, 3);
             }
             _ => panic!("Expected PaymentExecuted event"),
         }
@@ -1359,6 +2080,209 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_payee_authority_transferred_event() {
+        let event = PayeeAuthorityTransferred {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            old_authority: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            new_authority: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            timestamp: 1_700_000_000,
+        };
+
+        let encoded_data = create_test_event_data("PayeeAuthorityTransferred", &event);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+
+        assert_eq!(parsed_event, TallyEvent::PayeeAuthorityTransferred(event));
+    }
+
+    #[test]
+    fn test_parse_credit_applied_event() {
+        let event = CreditApplied {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payment_terms: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payer: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            amount: 10_000_000,
+            credit_applied: 5_000_000,
+            amount_charged: 5_000_000,
+            remaining_credit: 0,
+            paused_at_ts: 1_700_000_000,
+        };
+
+        let encoded_data = create_test_event_data("CreditApplied", &event);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+
+        assert_eq!(parsed_event, TallyEvent::CreditApplied(event));
+    }
+
+    #[test]
+    fn test_parse_terms_sunset_event() {
+        let event = TermsSunset {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payment_terms: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payer: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            sunset_ts: 1_702_592_000,
+            timestamp: 1_702_600_000,
+        };
+
+        let encoded_data = create_test_event_data("TermsSunset", &event);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+
+        assert_eq!(parsed_event, TallyEvent::TermsSunset(event));
+    }
+
+    #[test]
+    fn test_parse_escrow_events() {
+        let payee = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let released = EscrowReleased {
+            payee,
+            payment_terms,
+            payer,
+            amount: 5_000_000_000,
+            platform_fee: 12_500_000,
+            payee_amount: 4_987_500_000,
+            timestamp: 1_700_100_000,
+        };
+        let encoded_data = create_test_event_data("EscrowReleased", &released);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::EscrowReleased(released));
+
+        let refunded = EscrowRefunded {
+            payee,
+            payment_terms,
+            payer,
+            amount: 5_000_000_000,
+            expires_ts: 1_700_259_200,
+            timestamp: 1_700_260_000,
+        };
+        let encoded_data = create_test_event_data("EscrowRefunded", &refunded);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::EscrowRefunded(refunded));
+    }
+
+    #[test]
+    fn test_parse_keeper_paid_in_sol_event() {
+        let event = KeeperPaidInSol {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payment_terms: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payer: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            keeper: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            keeper_fee: 15_000,
+            lamports_per_usdc: 5_000_000,
+            lamports: 75_000,
+        };
+
+        let encoded_data = create_test_event_data("KeeperPaidInSol", &event);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::KeeperPaidInSol(event));
+    }
+
+    #[test]
+    fn test_parse_suspension_events() {
+        let payee = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let suspended = AgreementSuspended {
+            payee,
+            payment_terms,
+            payer,
+            reason: crate::program_types::SuspensionReason::FrozenAccount,
+            payer_token_account: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            timestamp: 1_700_000_000,
+        };
+        let encoded_data = create_test_event_data("AgreementSuspended", &suspended);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::AgreementSuspended(suspended));
+
+        let unsuspended = AgreementUnsuspended {
+            payee,
+            payment_terms,
+            payer,
+            next_payment_ts: 1_700_100_000,
+            timestamp: 1_700_100_000,
+        };
+        let encoded_data = create_test_event_data("AgreementUnsuspended", &unsuspended);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::AgreementUnsuspended(unsuspended));
+    }
+
+    #[test]
+    fn test_parse_deposit_events() {
+        let payee = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let held = DepositHeld {
+            payee,
+            payment_terms,
+            payer,
+            amount: 100_000_000,
+            total_held: 100_000_000,
+            timestamp: 1_700_000_000,
+        };
+        let encoded_data = create_test_event_data("DepositHeld", &held);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::DepositHeld(held));
+
+        let claimed = DepositClaimed {
+            payee,
+            payment_terms,
+            payer,
+            amount: 30_000_000,
+            remaining: 70_000_000,
+            timestamp: 1_700_100_000,
+        };
+        let encoded_data = create_test_event_data("DepositClaimed", &claimed);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::DepositClaimed(claimed));
+
+        let released = DepositReleased {
+            payee,
+            payment_terms,
+            payer,
+            amount: 70_000_000,
+            timestamp: 1_700_200_000,
+        };
+        let encoded_data = create_test_event_data("DepositReleased", &released);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::DepositReleased(released));
+    }
+
+    #[test]
+    fn test_parse_swept_event() {
+        let event = Swept {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payment_terms: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payer: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            keeper: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            amount: 3_600_000,
+            keeper_fee: 9_000,
+            from_ts: 1_700_000_000,
+            to_ts: 1_700_003_600,
+        };
+        let encoded_data = create_test_event_data("Swept", &event);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::Swept(event));
+    }
+
+    #[test]
+    fn test_parse_agreement_snapshot_event() {
+        let snapshot = AgreementSnapshot {
+            payment_agreement: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            status: crate::program_types::AgreementStatus::Suspended,
+            next_payment_ts: 1_700_000_000,
+            period_index: 7,
+        };
+
+        let encoded_data = create_test_event_data("AgreementSnapshot", &snapshot);
+        let parsed_event = parse_single_event(&encoded_data).unwrap();
+        assert_eq!(parsed_event, TallyEvent::AgreementSnapshot(snapshot));
+    }
+
     #[test]
     fn test_parse_single_event_invalid_base64() {
         let result = parse_single_event("invalid_base64_!@#$%");
@@ -1422,6 +2346,8 @@ mod tests {
             payment_terms,
             payer,
             amount: 1_000_000,
+            external_ref_hash: None,
+            delegated_amount: 0,
         };
 
         let agreement_paused_event = PaymentAgreementPaused {
@@ -1466,6 +2392,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_events_with_context_tracks_instruction_index() {
+        let program_id = crate::program_id();
+        let paused = PaymentAgreementPaused {
+            payee: Pubkey::new_unique(),
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+        };
+        let paused_data = create_test_event_data("PaymentAgreementPaused", &paused);
+
+        let logs = vec![
+            format!("Program data: {program_id} {paused_data}"),
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_string(),
+            format!("Program {program_id} invoke [1]"),
+            format!("Program {program_id} invoke [2]"),
+            format!("Program data: {program_id} {paused_data}"),
+            format!("Program {program_id} invoke [1]"),
+            format!("Program data: {program_id} {paused_data}"),
+        ];
+        let signature = Signature::new_unique();
+
+        let events =
+            parse_events_with_context(&logs, &program_id, signature, 42, Some(1_700_000_000), true)
+                .unwrap();
+
+        let indexes: Vec<_> = events
+            .iter()
+            .map(|event| (event.log_index, event.instruction_index))
+            .collect();
+        // The first event predates any invocation; inner invocations don't advance the index
+        assert_eq!(indexes, vec![(0, None), (1, Some(1)), (2, Some(2))]);
+
+        let context = events[2].clone().with_cluster(Some(Cluster::Devnet)).context();
+        assert_eq!(context.signature, signature);
+        assert_eq!(context.slot, 42);
+        assert_eq!(context.block_time, Some(1_700_000_000));
+        assert_eq!(context.cluster, Some(Cluster::Devnet));
+    }
+
     #[test]
     fn test_parse_events_from_logs_with_malformed_data() {
         let program_id = crate::program_id();
@@ -1478,6 +2443,8 @@ mod tests {
             payment_terms,
             payer,
             amount: 1_000_000,
+            external_ref_hash: None,
+            delegated_amount: 0,
         };
 
         let valid_data = create_test_event_data("PaymentAgreementStarted", &valid_event);
@@ -1529,6 +2496,8 @@ mod tests {
             payment_terms,
             payer,
             amount: 1_000_000,
+            external_ref_hash: None,
+            delegated_amount: 0,
         };
 
         let event_data = create_test_event_data("PaymentAgreementStarted", &event);
@@ -0,0 +1,426 @@
+//! Versioned export of a payee's on-chain state for backups, audits and migrations
+//!
+//! [`export_state`] captures a `Payee` account with all of its `PaymentTerms` and
+//! `PaymentAgreement` accounts as a [`TallyStateExport`], and [`validate_import`] reads
+//! an export back from disk after checking it. Backups, audit snapshots and the legacy
+//! migration tooling all share this one schema.
+//!
+//! Every account carries the SHA-256 checksum of its Borsh encoding (the on-chain
+//! account data without the discriminator), and the export carries a checksum over its
+//! header and all account checksums, so edited or truncated files are rejected on
+//! import. Accounts are sorted by address, so exports of the same state at the same
+//! slot are byte-for-byte identical.
+
+use crate::{
+    error::{Result, TallyError},
+    program_types::{Payee, PaymentAgreement, PaymentTerms},
+    SimpleTallyClient,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_lang::{solana_program::hash, AnchorSerialize};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Current version of the [`TallyStateExport`] format
+///
+/// Bump whenever an exported account layout changes; imports of newer versions are
+/// rejected.
+pub const STATE_EXPORT_VERSION: u16 = 1;
+
+/// Point-in-time copy of a payee, its payment terms and their agreements
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TallyStateExport {
+    /// Format version the export was written with
+    pub version: u16,
+    /// Program the accounts belong to
+    pub program_id: String,
+    /// Slot at which the export was taken
+    pub slot: u64,
+    /// The exported payee
+    pub payee: ExportedAccount<Payee>,
+    /// All payment terms of the payee, sorted by address
+    pub payment_terms: Vec<ExportedAccount<PaymentTerms>>,
+    /// All agreements under the payee's payment terms, sorted by address
+    pub agreements: Vec<ExportedAccount<PaymentAgreement>>,
+    /// Hex SHA-256 over the header fields and every account checksum
+    pub checksum: String,
+}
+
+/// An exported account with its address and checksum
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedAccount<T> {
+    /// Account address
+    pub address: String,
+    /// Decoded account data
+    pub account: T,
+    /// Hex SHA-256 of the account's Borsh encoding
+    pub checksum: String,
+}
+
+impl<T: AnchorSerialize> ExportedAccount<T> {
+    fn new(address: &Pubkey, account: T) -> Result<Self> {
+        let checksum = account_checksum(&account)?;
+        Ok(Self {
+            address: address.to_string(),
+            account,
+            checksum,
+        })
+    }
+
+    fn verify(&self, kind: &str) -> Result<()> {
+        if account_checksum(&self.account)? != self.checksum {
+            return Err(TallyError::InvalidStateExport(format!(
+                "checksum mismatch for {kind} {}",
+                self.address
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl TallyStateExport {
+    /// Build an export from already-fetched accounts
+    ///
+    /// # Errors
+    /// Returns an error if an account can't be Borsh-encoded for its checksum
+    pub fn from_accounts(
+        program_id: &Pubkey,
+        slot: u64,
+        payee: (&Pubkey, &Payee),
+        payment_terms: &[(Pubkey, PaymentTerms)],
+        agreements: &[(Pubkey, PaymentAgreement)],
+    ) -> Result<Self> {
+        let mut payment_terms = payment_terms
+            .iter()
+            .map(|(address, terms)| ExportedAccount::new(address, terms.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        payment_terms.sort_by(|a, b| a.address.cmp(&b.address));
+        let mut agreements = agreements
+            .iter()
+            .map(|(address, agreement)| ExportedAccount::new(address, agreement.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        agreements.sort_by(|a, b| a.address.cmp(&b.address));
+
+        let mut export = Self {
+            version: STATE_EXPORT_VERSION,
+            program_id: program_id.to_string(),
+            slot,
+            payee: ExportedAccount::new(payee.0, payee.1.clone())?,
+            payment_terms,
+            agreements,
+            checksum: String::new(),
+        };
+        export.checksum = export.compute_checksum();
+        Ok(export)
+    }
+
+    /// Check the version, every checksum and that all accounts belong to the payee
+    ///
+    /// # Errors
+    /// Returns [`TallyError::InvalidStateExport`] describing the first problem found
+    pub fn validate(&self) -> Result<()> {
+        if self.version == 0 || self.version > STATE_EXPORT_VERSION {
+            return Err(TallyError::InvalidStateExport(format!(
+                "unsupported version {} (supported up to {STATE_EXPORT_VERSION})",
+                self.version
+            )));
+        }
+        if self.compute_checksum() != self.checksum {
+            return Err(TallyError::InvalidStateExport(
+                "export checksum mismatch".to_string(),
+            ));
+        }
+
+        self.payee.verify("payee")?;
+        let payee = parse_address(&self.payee.address)?;
+        let mut terms_addresses = BTreeSet::new();
+        for terms in &self.payment_terms {
+            terms.verify("payment terms")?;
+            if terms.account.payee != payee {
+                return Err(TallyError::InvalidStateExport(format!(
+                    "payment terms {} belong to another payee",
+                    terms.address
+                )));
+            }
+            terms_addresses.insert(parse_address(&terms.address)?);
+        }
+        for agreement in &self.agreements {
+            agreement.verify("agreement")?;
+            if !terms_addresses.contains(&agreement.account.payment_terms) {
+                return Err(TallyError::InvalidStateExport(format!(
+                    "agreement {} references payment terms missing from the export",
+                    agreement.address
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize to pretty-printed JSON
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse and [validate](TallyStateExport::validate) an export written by
+    /// [`TallyStateExport::to_json`]
+    ///
+    /// # Errors
+    /// Returns an error if the JSON is malformed or the export fails validation
+    pub fn from_json(json: &str) -> Result<Self> {
+        let export: Self = serde_json::from_str(json)?;
+        export.validate()?;
+        Ok(export)
+    }
+
+    fn compute_checksum(&self) -> String {
+        let version = self.version.to_le_bytes();
+        let slot = self.slot.to_le_bytes();
+        let mut parts: Vec<&[u8]> = vec![
+            &version,
+            self.program_id.as_bytes(),
+            &slot,
+            self.payee.checksum.as_bytes(),
+        ];
+        parts.extend(self.payment_terms.iter().map(|t| t.checksum.as_bytes()));
+        parts.extend(self.agreements.iter().map(|a| a.checksum.as_bytes()));
+        hex::encode(hash::hashv(&parts).to_bytes())
+    }
+}
+
+/// Export a payee with all of its payment terms and agreements
+///
+/// # Arguments
+/// * `client` - Client of the program to export from
+/// * `payee_address` - The payee PDA address
+///
+/// # Errors
+/// Returns an error if any RPC query fails or the payee doesn't exist
+pub fn export_state(
+    client: &SimpleTallyClient,
+    payee_address: &Pubkey,
+) -> Result<TallyStateExport> {
+    let slot = client.get_slot()?;
+    let payee = client
+        .get_payee(payee_address)?
+        .ok_or(TallyError::PayeeNotFound)?;
+    let payment_terms = client.list_payment_terms(payee_address)?;
+    let mut agreements = Vec::new();
+    for (terms_address, _) in &payment_terms {
+        agreements.extend(client.list_payment_agreements(terms_address)?);
+    }
+
+    TallyStateExport::from_accounts(
+        &client.program_id,
+        slot,
+        (payee_address, &payee),
+        &payment_terms,
+        &agreements,
+    )
+}
+
+/// Read and validate a state export from a file
+///
+/// # Errors
+/// Returns an error if the file can't be read, isn't a state export, or fails
+/// [validation](TallyStateExport::validate)
+pub fn validate_import(path: &str) -> Result<TallyStateExport> {
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        TallyError::Generic(format!("Failed to read state export from {path}: {e}"))
+    })?;
+    TallyStateExport::from_json(&json)
+}
+
+fn account_checksum<T: AnchorSerialize>(account: &T) -> Result<String> {
+    let data = account
+        .try_to_vec()
+        .map_err(|e| TallyError::Generic(format!("Failed to serialize account: {e}")))?;
+    Ok(hex::encode(hash::hash(&data).to_bytes()))
+}
+
+fn parse_address(address: &str) -> Result<Pubkey> {
+    address
+        .parse()
+        .map_err(|e| TallyError::InvalidStateExport(format!("invalid address {address}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_types::VolumeTier;
+
+    fn payee() -> Payee {
+        let authority = Pubkey::new_unique();
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 1,
+            lifetime_revenue_usdc: 10_000_000,
+            lifetime_renewals: 1,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn payment_terms(payee: Pubkey) -> PaymentTerms {
+        PaymentTerms {
+            payee,
+            terms_id: [1; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 1,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 10_000_000,
+            lifetime_renewals: 1,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        }
+    }
+
+    fn agreement(payment_terms: Pubkey) -> PaymentAgreement {
+        PaymentAgreement {
+            payment_terms,
+            payer: Pubkey::new_unique(),
+            next_payment_ts: 2_592_000,
+            active: true,
+            payment_count: 1,
+            created_ts: 0,
+            last_amount: 10_000_000,
+            last_payment_ts: 0,
+            last_pull_period_index: 0,
+            cancel_at_period_end: false,
+            pending_payer: None,
+            refunded_amount: 0,
+            max_periods: None,
+            periods_paid: 1,
+            external_ref_hash: None,
+            paused_at_ts: None,
+            credit_amount: 0,
+            escrow: None,
+            period_index: 0,
+            suspension: None,
+            bump: 255,
+            deposit_held: 0,
+            version: 1,
+            last_sweep_ts: 0,
+        }
+    }
+
+    fn export() -> TallyStateExport {
+        let payee_address = Pubkey::new_unique();
+        let terms_address = Pubkey::new_unique();
+        let agreements = vec![
+            (Pubkey::new_unique(), agreement(terms_address)),
+            (Pubkey::new_unique(), agreement(terms_address)),
+        ];
+        TallyStateExport::from_accounts(
+            &Pubkey::new_unique(),
+            42,
+            (&payee_address, &payee()),
+            &[(terms_address, payment_terms(payee_address))],
+            &agreements,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let export = export();
+        assert_eq!(export.version, STATE_EXPORT_VERSION);
+        assert!(export.validate().is_ok());
+
+        let json = export.to_json().unwrap();
+        assert_eq!(TallyStateExport::from_json(&json).unwrap(), export);
+    }
+
+    #[test]
+    fn test_accounts_sorted_by_address() {
+        let export = export();
+        assert!(export
+            .agreements
+            .windows(2)
+            .all(|pair| pair[0].address <= pair[1].address));
+    }
+
+    #[test]
+    fn test_rejects_edited_account() {
+        let mut export = export();
+        export.agreements[0].account.credit_amount = 1_000_000;
+
+        assert!(matches!(
+            export.validate(),
+            Err(TallyError::InvalidStateExport(msg)) if msg.contains("checksum mismatch for agreement")
+        ));
+    }
+
+    #[test]
+    fn test_rejects_removed_account() {
+        let mut export = export();
+        export.agreements.pop();
+
+        assert!(matches!(
+            export.validate(),
+            Err(TallyError::InvalidStateExport(msg)) if msg == "export checksum mismatch"
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unsupported_version() {
+        let mut export = export();
+        export.version = STATE_EXPORT_VERSION + 1;
+
+        assert!(matches!(
+            export.validate(),
+            Err(TallyError::InvalidStateExport(msg)) if msg.starts_with("unsupported version")
+        ));
+    }
+
+    #[test]
+    fn test_rejects_foreign_payment_terms() {
+        let payee_address = Pubkey::new_unique();
+        let export = TallyStateExport::from_accounts(
+            &Pubkey::new_unique(),
+            42,
+            (&payee_address, &payee()),
+            &[(Pubkey::new_unique(), payment_terms(Pubkey::new_unique()))],
+            &[],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            export.validate(),
+            Err(TallyError::InvalidStateExport(msg)) if msg.contains("belong to another payee")
+        ));
+    }
+
+    #[test]
+    fn test_validate_import_reads_file() {
+        let export = export();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.json");
+        std::fs::write(&path, export.to_json().unwrap()).unwrap();
+
+        let imported = validate_import(path.to_str().unwrap()).unwrap();
+        assert_eq!(imported, export);
+        assert!(validate_import(dir.path().join("missing.json").to_str().unwrap()).is_err());
+    }
+}
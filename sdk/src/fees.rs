@@ -0,0 +1,424 @@
+//! Fee calculation utilities matching the on-chain integer math
+//!
+//! Frontends and keepers should use these helpers instead of floating-point
+//! approximations, which can disagree with the program by a micro-unit.
+//! The math mirrors the program exactly:
+//!
+//! 1. The keeper fee is taken from the full amount (`execute_payment` only)
+//! 2. The platform fee is taken from the remainder at the payee's volume tier rate,
+//!    less the rebate of any active fee holiday
+//! 3. The keeper fee and the payee's share round down; the platform receives the
+//!    rounding remainder, so the parts always sum to the amount charged
+//! 4. A keeper taking its fee in SOL gets no USDC: its fee goes to the platform
+//!    treasury and the keeper fee vault pays the keeper lamports at the config's rate
+//!
+//! See [`split_payment`] for the exact algorithm.
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use crate::program_types::{Config, Payee, PaymentTerms, VolumeTier};
+
+/// Divisor for basis point calculations (matches the program's `FEE_BASIS_POINTS_DIVISOR`)
+pub const FEE_BASIS_POINTS_DIVISOR: u128 = 10_000;
+
+/// Breakdown of a single payment into its recipients, in USDC micro-units
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PaymentBreakdown {
+    /// Amount credited to the payee treasury
+    pub payee_amount: u64,
+    /// Fee credited to the platform treasury
+    pub platform_fee: u64,
+    /// Fee credited to the keeper executing the payment
+    pub keeper_fee: u64,
+    /// Fee credited to a referrer (always zero; the program has no referral fee yet)
+    pub referral_fee: u64,
+    /// Lamports the keeper fee vault pays the keeper when it takes its fee in SOL
+    ///
+    /// Not part of [`Self::total`]; the USDC keeper fee is then in `platform_fee`.
+    pub keeper_fee_lamports: u64,
+}
+
+impl PaymentBreakdown {
+    /// Total amount pulled from the payer
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.payee_amount
+            .saturating_add(self.platform_fee)
+            .saturating_add(self.keeper_fee)
+            .saturating_add(self.referral_fee)
+    }
+}
+
+/// Volume window used for automatic tier evaluation (matches the program's `VOLUME_WINDOW_SECONDS`)
+pub const VOLUME_WINDOW_SECONDS: i64 = 2_592_000;
+
+/// Compute the breakdown of a recurring payment as charged by `execute_payment`
+///
+/// `amount` is the amount actually charged; for token-gated terms pass the result of
+/// [`apply_gate_discount`] when the payer holds the gate token.
+///
+/// `now` is the payment time. Like `execute_payment`, the platform fee is charged at
+/// the volume tier the payee reaches with this payment (see
+/// [`volume_tier_after_payment`]), less the rebate of a fee holiday active at `now`.
+///
+/// `keeper_fee_in_sol` matches the instruction's argument of the same name: the keeper
+/// fee is then added to the platform fee and paid to the keeper in
+/// `keeper_fee_lamports` instead.
+///
+/// # Errors
+/// Returns an error if the calculation overflows, or if `keeper_fee_in_sol` is set and
+/// the config has no keeper SOL rate or it is stale at `now`
+pub fn compute_payment_breakdown(
+    amount: u64,
+    payee: &Payee,
+    config: &Config,
+    now: i64,
+    keeper_fee_in_sol: bool,
+) -> Result<PaymentBreakdown> {
+    let breakdown = split_payment(
+        amount,
+        config.keeper_fee_bps,
+        platform_fee_bps_after_payment(payee, amount, now),
+    )?;
+    if !keeper_fee_in_sol {
+        return Ok(breakdown);
+    }
+
+    let keeper_fee_lamports = config
+        .keeper_sol_rate
+        .and_then(|rate| rate.lamports_for(breakdown.keeper_fee, now))
+        .ok_or_else(|| TallyError::Generic("Keeper SOL rate unavailable".to_string()))?;
+    let platform_fee = breakdown
+        .platform_fee
+        .checked_add(breakdown.keeper_fee)
+        .ok_or_else(|| TallyError::Generic("Arithmetic overflow".to_string()))?;
+    Ok(PaymentBreakdown {
+        platform_fee,
+        keeper_fee: 0,
+        keeper_fee_lamports,
+        ..breakdown
+    })
+}
+
+/// Compute the breakdown of the initial payment charged by `start_agreement`
+///
+/// The initial payment has no keeper, so only the platform fee is deducted, at the
+/// volume tier the payee reaches with this payment.
+///
+/// # Errors
+/// Returns an error if the calculation overflows
+pub fn compute_initial_payment_breakdown(
+    amount: u64,
+    payee: &Payee,
+    now: i64,
+) -> Result<PaymentBreakdown> {
+    split_payment(
+        amount,
+        0,
+        platform_fee_bps_after_payment(payee, amount, now),
+    )
+}
+
+/// Platform fee the program charges on a payment of `amount` at `now`
+///
+/// The program records the payment's volume before splitting it, so the fee is the
+/// rate of the tier after the payment, less any active fee holiday rebate.
+fn platform_fee_bps_after_payment(payee: &Payee, amount: u64, now: i64) -> u16 {
+    let tier_fee_bps = volume_tier_after_payment(payee, amount, now).platform_fee_bps();
+    payee
+        .active_fee_holiday(now)
+        .map_or(tier_fee_bps, |holiday| {
+            tier_fee_bps.saturating_sub(holiday.rebate_bps)
+        })
+}
+
+/// Volume tier `execute_payment` applies to a payment of `amount` at `now`
+///
+/// Mirrors the program's automatic tier evaluation: a payment after the 30-day volume
+/// window has elapsed starts a new window and resets the tier to the one earned by the
+/// completed window (Standard if a whole window passed without payments); within a
+/// window the tier is promoted once the running volume crosses a threshold.
+#[must_use]
+pub fn volume_tier_after_payment(payee: &Payee, amount: u64, now: i64) -> VolumeTier {
+    let elapsed = now.saturating_sub(payee.last_volume_update_ts);
+    let (base_tier, window_volume) = if elapsed >= VOLUME_WINDOW_SECONDS {
+        let completed_volume = if elapsed >= VOLUME_WINDOW_SECONDS.saturating_mul(2) {
+            0
+        } else {
+            payee.monthly_volume_usdc
+        };
+        (VolumeTier::from_monthly_volume(completed_volume), 0)
+    } else {
+        (payee.volume_tier, payee.monthly_volume_usdc)
+    };
+
+    base_tier.max(VolumeTier::from_monthly_volume(
+        window_volume.saturating_add(amount),
+    ))
+}
+
+/// Apply a token-gate discount exactly as the program does (discount rounds down)
+///
+/// # Errors
+/// Returns an error if the calculation overflows
+pub fn apply_gate_discount(amount: u64, discount_bps: u16) -> Result<u64> {
+    let discount = bps_of(amount, discount_bps)?;
+    amount
+        .checked_sub(discount)
+        .ok_or_else(|| TallyError::Generic("Gate discount exceeds amount".to_string()))
+}
+
+/// Amount charged for payment terms, applying the gate discount when the payer holds the gate token
+///
+/// # Errors
+/// Returns an error if the calculation overflows
+pub fn charged_amount(payment_terms: &PaymentTerms, holds_gate_token: bool) -> Result<u64> {
+    if holds_gate_token && payment_terms.gate_mint.is_some() {
+        apply_gate_discount(payment_terms.amount_usdc, payment_terms.gate_discount_bps)
+    } else {
+        Ok(payment_terms.amount_usdc)
+    }
+}
+
+/// Split `amount` exactly as the program's `calculate_fee_split` does
+///
+/// 1. `keeper_fee = floor(amount * keeper_fee_bps / 10_000)`
+/// 2. `payee_amount = floor((amount - keeper_fee) * (10_000 - platform_fee_bps) / 10_000)`
+/// 3. `platform_fee = amount - keeper_fee - payee_amount`
+///
+/// The referral fee is always zero.
+///
+/// # Errors
+/// Returns an error if the calculation overflows or `platform_fee_bps` exceeds 10,000
+pub fn split_payment(
+    amount: u64,
+    keeper_fee_bps: u16,
+    platform_fee_bps: u16,
+) -> Result<PaymentBreakdown> {
+    let keeper_fee = bps_of(amount, keeper_fee_bps)?;
+    let remaining_after_keeper = amount
+        .checked_sub(keeper_fee)
+        .ok_or_else(|| TallyError::Generic("Keeper fee exceeds amount".to_string()))?;
+    let payee_share_bps = 10_000_u16
+        .checked_sub(platform_fee_bps)
+        .ok_or_else(|| TallyError::Generic("Platform fee exceeds 100%".to_string()))?;
+    let payee_amount = bps_of(remaining_after_keeper, payee_share_bps)?;
+    let platform_fee = remaining_after_keeper
+        .checked_sub(payee_amount)
+        .ok_or_else(|| TallyError::Generic("Payee share exceeds amount".to_string()))?;
+
+    Ok(PaymentBreakdown {
+        payee_amount,
+        platform_fee,
+        keeper_fee,
+        referral_fee: 0,
+        keeper_fee_lamports: 0,
+    })
+}
+
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let value = u128::from(amount)
+        .checked_mul(u128::from(bps))
+        .ok_or_else(|| TallyError::Generic("Arithmetic overflow".to_string()))?
+        / FEE_BASIS_POINTS_DIVISOR;
+    u64::try_from(value).map_err(|_| TallyError::Generic("Arithmetic overflow".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_types::{FeeHoliday, KeeperSolRate};
+    use anchor_lang::prelude::Pubkey;
+
+    fn payee(volume_tier: VolumeTier) -> Payee {
+        let authority = Pubkey::new_unique();
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn config(keeper_fee_bps: u16) -> Config {
+        Config {
+            platform_authority: Pubkey::new_unique(),
+            pending_authority: None,
+            max_platform_fee_bps: 50,
+            min_platform_fee_bps: 10,
+            min_period_seconds: 86_400,
+            default_allowance_periods: 3,
+            allowed_mint: Pubkey::new_unique(),
+            max_withdrawal_amount: 1_000_000_000,
+            max_grace_period_seconds: 604_800,
+            paused: false,
+            keeper_fee_bps,
+            pause_reason: [0; 64],
+            auto_unpause_ts: None,
+            keeper_sol_rate: None,
+            renewal_tolerance_secs: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_breakdown_standard_tier() {
+        let payee = payee(VolumeTier::Standard);
+        let breakdown =
+            compute_payment_breakdown(100_000_000, &payee, &config(15), 0, false).unwrap();
+
+        assert_eq!(breakdown.keeper_fee, 150_000);
+        assert_eq!(breakdown.platform_fee, 249_625);
+        assert_eq!(breakdown.payee_amount, 99_600_375);
+        assert_eq!(breakdown.referral_fee, 0);
+        assert_eq!(breakdown.keeper_fee_lamports, 0);
+        assert_eq!(breakdown.total(), 100_000_000);
+    }
+
+    #[test]
+    fn test_breakdown_keeper_fee_in_sol() {
+        let payee = payee(VolumeTier::Standard);
+        let mut config = config(15);
+        assert!(compute_payment_breakdown(100_000_000, &payee, &config, 0, true).is_err());
+
+        config.keeper_sol_rate = Some(KeeperSolRate {
+            lamports_per_usdc: 7_000_000,
+            updated_ts: 0,
+        });
+        let breakdown = compute_payment_breakdown(100_000_000, &payee, &config, 0, true).unwrap();
+
+        // The USDC keeper fee goes to the platform and the keeper is paid in lamports
+        assert_eq!(breakdown.keeper_fee, 0);
+        assert_eq!(breakdown.platform_fee, 249_625 + 150_000);
+        assert_eq!(breakdown.payee_amount, 99_600_375);
+        assert_eq!(breakdown.keeper_fee_lamports, 1_050_000);
+        assert_eq!(breakdown.total(), 100_000_000);
+
+        // A stale rate is refused like the program refuses it
+        let stale = crate::MAX_KEEPER_SOL_RATE_AGE_SECS + 1;
+        assert!(compute_payment_breakdown(100_000_000, &payee, &config, stale, true).is_err());
+    }
+
+    #[test]
+    fn test_breakdown_uses_volume_tier() {
+        let standard =
+            compute_initial_payment_breakdown(10_000_000, &payee(VolumeTier::Standard), 0).unwrap();
+        let scale =
+            compute_initial_payment_breakdown(10_000_000, &payee(VolumeTier::Scale), 0).unwrap();
+
+        assert_eq!(standard.platform_fee, 25_000);
+        assert_eq!(scale.platform_fee, 15_000);
+        assert_eq!(scale.keeper_fee, 0);
+    }
+
+    #[test]
+    fn test_breakdown_applies_fee_holiday_until_it_ends() {
+        let mut payee = payee(VolumeTier::Standard);
+        payee.fee_holiday = Some(FeeHoliday {
+            until_ts: 1_000,
+            rebate_bps: 10,
+        });
+
+        let during = compute_initial_payment_breakdown(10_000_000, &payee, 999).unwrap();
+        let after = compute_initial_payment_breakdown(10_000_000, &payee, 1_000).unwrap();
+
+        assert_eq!(during.platform_fee, 15_000);
+        assert_eq!(after.platform_fee, 25_000);
+    }
+
+    #[test]
+    fn test_rounding_remainder_goes_to_platform() {
+        // 0.25% of 399 is 0.9975 micro-units; the payee share rounds down to 398
+        let breakdown =
+            compute_initial_payment_breakdown(399, &payee(VolumeTier::Standard), 0).unwrap();
+
+        assert_eq!(breakdown.platform_fee, 1);
+        assert_eq!(breakdown.payee_amount, 398);
+    }
+
+    #[test]
+    fn test_split_payment_sums_to_amount() {
+        for amount in [0, 1, 399, 9_999, 10_001, 123_456_789, u64::MAX] {
+            for (keeper_bps, platform_bps) in [(0, 0), (15, 25), (100, 50), (0, 10_000)] {
+                let breakdown = split_payment(amount, keeper_bps, platform_bps).unwrap();
+                assert_eq!(breakdown.total(), amount);
+                assert!(breakdown.payee_amount <= amount);
+            }
+        }
+        assert!(split_payment(1_000, 0, 10_001).is_err());
+    }
+
+    #[test]
+    fn test_apply_gate_discount() {
+        assert_eq!(apply_gate_discount(10_000_000, 1_000).unwrap(), 9_000_000);
+        assert_eq!(apply_gate_discount(999, 2_500).unwrap(), 750);
+    }
+
+    #[test]
+    fn test_volume_tier_after_payment() {
+        let mut growth = payee(VolumeTier::Growth);
+        growth.monthly_volume_usdc = 95_000_000_000;
+
+        // Crossing the Scale threshold within the window promotes immediately
+        assert_eq!(
+            volume_tier_after_payment(&growth, 5_000_000_000, 1_000),
+            VolumeTier::Scale
+        );
+        // A new window is evaluated against the completed window's volume
+        assert_eq!(
+            volume_tier_after_payment(&growth, 1_000_000, VOLUME_WINDOW_SECONDS),
+            VolumeTier::Growth
+        );
+        // A whole idle window resets to Standard
+        assert_eq!(
+            volume_tier_after_payment(&growth, 1_000_000, VOLUME_WINDOW_SECONDS * 2),
+            VolumeTier::Standard
+        );
+    }
+
+    #[test]
+    fn test_breakdown_at_tier_boundary() {
+        let mut growth = payee(VolumeTier::Growth);
+        growth.monthly_volume_usdc = 95_000_000_000;
+        let config = config(0);
+
+        // One micro-unit short of the Scale threshold stays at the Growth rate
+        let below =
+            compute_payment_breakdown(4_999_999_999, &growth, &config, 1_000, false).unwrap();
+        assert_eq!(
+            below,
+            split_payment(4_999_999_999, 0, VolumeTier::Growth.platform_fee_bps()).unwrap()
+        );
+
+        // The payment crossing the threshold is charged at the Scale rate
+        let crossing =
+            compute_payment_breakdown(5_000_000_000, &growth, &config, 1_000, false).unwrap();
+        assert_eq!(crossing.platform_fee, 7_500_000);
+        let initial = compute_initial_payment_breakdown(5_000_000_000, &growth, 1_000).unwrap();
+        assert_eq!(initial, crossing);
+
+        // A fee holiday rebates the new tier's rate
+        growth.fee_holiday = Some(FeeHoliday {
+            until_ts: 2_000,
+            rebate_bps: 10,
+        });
+        let rebated =
+            compute_payment_breakdown(5_000_000_000, &growth, &config, 1_000, false).unwrap();
+        assert_eq!(rebated.platform_fee, 2_500_000);
+    }
+}
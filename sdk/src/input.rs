@@ -0,0 +1,452 @@
+//! Parsing transaction signatures and addresses supplied by users
+//!
+//! Signatures and addresses reach support tooling and payee dashboards pasted from
+//! wallets, explorers, emails and chat, so they often carry surrounding whitespace,
+//! quotes, URL-encoding or the whole explorer link. [`parse_signature`] and
+//! [`parse_pubkey`] accept them in two modes:
+//!
+//! - [`ParseMode::Strict`] takes the input as-is, for values produced by software;
+//! - [`ParseMode::Lenient`] first cleans up common copy-paste artifacts, for values
+//!   typed or pasted by people.
+//!
+//! Either way, a rejected input is reported as [`TallyError::MalformedInput`] with a
+//! [`Malformed`] reason naming exactly what is wrong with it:
+//!
+//! ```
+//! use tally_sdk::input::{parse_signature, ParseMode};
+//!
+//! let sig = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+//! let pasted = format!(" https://solscan.io/tx/{sig}?cluster=devnet\n");
+//!
+//! assert_eq!(parse_signature(&pasted, ParseMode::Lenient)?.to_string(), sig);
+//! assert!(parse_signature(&pasted, ParseMode::Strict).is_err());
+//! # Ok::<(), tally_sdk::TallyError>(())
+//! ```
+
+#![forbid(unsafe_code)]
+
+use crate::{error::Result, TallyError};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::fmt;
+
+/// Length in bytes of a decoded transaction signature
+const SIGNATURE_LEN: usize = 64;
+
+/// Length in bytes of a decoded address
+const PUBKEY_LEN: usize = 32;
+
+/// Characters of the Bitcoin base58 alphabet used by Solana
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Explorer path segments that precede the signature or address in a link
+const EXPLORER_PATH_PREFIXES: [&str; 5] = ["tx", "account", "address", "token", "transaction"];
+
+/// How much cleanup is applied before parsing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// The input must be exactly the base58 encoding
+    Strict,
+    /// Whitespace, wrapping quotes and punctuation, URL-encoding and explorer or
+    /// Solana Pay links are removed first; signatures may also be hex-encoded
+    #[default]
+    Lenient,
+}
+
+/// What a user-supplied value was expected to be
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// A transaction signature
+    Signature,
+    /// An account address (public key)
+    Address,
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signature => f.write_str("signature"),
+            Self::Address => f.write_str("address"),
+        }
+    }
+}
+
+/// Why a user-supplied signature or address was rejected
+///
+/// Positions are character offsets into the input after any lenient cleanup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Malformed {
+    /// Nothing was left to parse
+    Empty,
+    /// Whitespace inside the value (strict mode only)
+    Whitespace { position: usize },
+    /// A character outside the base58 alphabet
+    InvalidCharacter { character: char, position: usize },
+    /// A `%` not followed by two hex digits
+    InvalidPercentEncoding { position: usize },
+    /// A link without a path segment to take the value from
+    UnrecognizedUrl(String),
+    /// Valid base58 that decodes to the wrong number of bytes, usually a truncated
+    /// or doubled paste
+    WrongLength { expected: usize, found: usize },
+}
+
+impl fmt::Display for Malformed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("input is empty"),
+            Self::Whitespace { position } => write!(f, "whitespace at position {position}"),
+            Self::InvalidCharacter {
+                character,
+                position,
+            } => {
+                write!(f, "{character:?} at position {position} is not base58")?;
+                if matches!(character, '0' | 'O' | 'I' | 'l') {
+                    f.write_str(" (base58 never uses 0, O, I or l)")?;
+                }
+                Ok(())
+            }
+            Self::InvalidPercentEncoding { position } => {
+                write!(f, "invalid percent-encoding at position {position}")
+            }
+            Self::UnrecognizedUrl(url) => write!(f, "no signature or address found in link {url}"),
+            Self::WrongLength { expected, found } => {
+                write!(f, "decodes to {found} bytes, expected {expected}")
+            }
+        }
+    }
+}
+
+/// Parse a transaction signature
+///
+/// In lenient mode, 128-character hex signatures from wallets that return raw bytes
+/// are accepted as well.
+///
+/// # Errors
+/// Returns [`TallyError::MalformedInput`] describing why the input is not a signature
+pub fn parse_signature(input: &str, mode: ParseMode) -> Result<Signature> {
+    let malformed = |problem| TallyError::MalformedInput {
+        kind: InputKind::Signature,
+        problem,
+    };
+    let value = prepare(input, mode).map_err(malformed)?;
+
+    if mode == ParseMode::Lenient && value.len() == SIGNATURE_LEN * 2 {
+        if let Ok(bytes) = hex::decode(&value) {
+            if let Ok(array) = <[u8; SIGNATURE_LEN]>::try_from(bytes) {
+                return Ok(Signature::from(array));
+            }
+        }
+    }
+
+    let bytes = decode_base58(&value, SIGNATURE_LEN).map_err(malformed)?;
+    let array = <[u8; SIGNATURE_LEN]>::try_from(bytes).map_err(|bytes| {
+        malformed(Malformed::WrongLength {
+            expected: SIGNATURE_LEN,
+            found: bytes.len(),
+        })
+    })?;
+    Ok(Signature::from(array))
+}
+
+/// Parse an account address
+///
+/// # Errors
+/// Returns [`TallyError::MalformedInput`] describing why the input is not an address
+pub fn parse_pubkey(input: &str, mode: ParseMode) -> Result<Pubkey> {
+    let malformed = |problem| TallyError::MalformedInput {
+        kind: InputKind::Address,
+        problem,
+    };
+    let value = prepare(input, mode).map_err(malformed)?;
+
+    let bytes = decode_base58(&value, PUBKEY_LEN).map_err(malformed)?;
+    let array = <[u8; PUBKEY_LEN]>::try_from(bytes).map_err(|bytes| {
+        malformed(Malformed::WrongLength {
+            expected: PUBKEY_LEN,
+            found: bytes.len(),
+        })
+    })?;
+    Ok(Pubkey::from(array))
+}
+
+/// Returns the value to decode, cleaned up in lenient mode
+fn prepare(input: &str, mode: ParseMode) -> std::result::Result<String, Malformed> {
+    let value = match mode {
+        ParseMode::Strict => {
+            if let Some(position) = input.chars().position(char::is_whitespace) {
+                return Err(Malformed::Whitespace { position });
+            }
+            input.to_string()
+        }
+        ParseMode::Lenient => clean(input)?,
+    };
+    if value.is_empty() {
+        return Err(Malformed::Empty);
+    }
+    Ok(value)
+}
+
+/// Removes copy-paste artifacts around and inside a pasted value
+fn clean(input: &str) -> std::result::Result<String, Malformed> {
+    // Zero-width characters survive `trim` and are invisible in most UIs
+    let visible: String = input
+        .chars()
+        .filter(|c| !matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}'))
+        .collect();
+    let unwrapped = unwrap(visible.trim());
+    let decoded = percent_decode(unwrapped)?;
+    let value = if is_link(&decoded) {
+        value_from_link(&decoded)?
+    } else {
+        decoded
+    };
+
+    // Values wrapped across lines in emails and chat
+    Ok(unwrap(&value)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect())
+}
+
+/// Strips wrapping quotes and brackets and trailing sentence punctuation
+fn unwrap(mut value: &str) -> &str {
+    loop {
+        let trimmed = value
+            .trim()
+            .trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        let mut chars = trimmed.chars();
+        let stripped = match (chars.next(), chars.next_back()) {
+            (Some(open), Some(close))
+                if matches!(
+                    (open, close),
+                    ('"', '"')
+                        | ('\'', '\'')
+                        | ('`', '`')
+                        | ('<', '>')
+                        | ('(', ')')
+                        | ('[', ']')
+                        | ('“', '”')
+                        | ('‘', '’')
+                ) =>
+            {
+                chars.as_str()
+            }
+            _ => trimmed,
+        };
+        if stripped == value {
+            return value;
+        }
+        value = stripped;
+    }
+}
+
+/// Decodes `%XX` escapes, as left by links copied from a browser address bar
+fn percent_decode(value: &str) -> std::result::Result<String, Malformed> {
+    if !value.contains('%') {
+        return Ok(value.to_string());
+    }
+
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.char_indices().enumerate();
+    while let Some((position, (offset, c))) = chars.next() {
+        if c != '%' {
+            let mut buf = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let escape = value
+            .get(offset.saturating_add(1)..offset.saturating_add(3))
+            .and_then(|hex_digits| u8::from_str_radix(hex_digits, 16).ok())
+            .ok_or(Malformed::InvalidPercentEncoding { position })?;
+        bytes.push(escape);
+        chars.nth(1);
+    }
+    String::from_utf8(bytes).map_err(|_| Malformed::InvalidPercentEncoding { position: 0 })
+}
+
+/// Whether the value is an explorer, Solana Pay or other link rather than the value
+fn is_link(value: &str) -> bool {
+    value.contains("://") || value.starts_with("solana:") || value.contains('/')
+}
+
+/// Takes the signature or address out of an explorer or Solana Pay link
+///
+/// Explorer links put the value after a `tx`, `account`, `address`, `token` or
+/// `transaction` segment; otherwise the last path segment is used. Query strings
+/// (such as `?cluster=devnet`) and fragments are ignored.
+fn value_from_link(link: &str) -> std::result::Result<String, Malformed> {
+    let unrecognized = || Malformed::UnrecognizedUrl(link.to_string());
+    let with_scheme = if link.contains(':') {
+        link.to_string()
+    } else {
+        format!("https://{link}")
+    };
+    let url = url::Url::parse(&with_scheme).map_err(|_| unrecognized())?;
+
+    // `solana:<address>?amount=...` has an opaque path rather than segments
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map_or_else(|| vec![url.path()], Iterator::collect);
+    let segments: Vec<&str> = segments
+        .into_iter()
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    let value = segments
+        .windows(2)
+        .find(|pair| EXPLORER_PATH_PREFIXES.contains(&pair[0]))
+        .map(|pair| pair[1])
+        .or_else(|| segments.last().copied())
+        .ok_or_else(unrecognized)?;
+    percent_decode(value)
+}
+
+/// Decodes base58, reporting the first character outside the alphabet
+fn decode_base58(value: &str, expected: usize) -> std::result::Result<Vec<u8>, Malformed> {
+    if let Some((position, character)) = value
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !BASE58_ALPHABET.contains(*c))
+    {
+        return Err(Malformed::InvalidCharacter {
+            character,
+            position,
+        });
+    }
+    bs58::decode(value)
+        .into_vec()
+        .map_err(|_| Malformed::WrongLength { expected, found: 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+    const ADDRESS: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn problem(error: TallyError) -> Malformed {
+        match error {
+            TallyError::MalformedInput { problem, .. } => problem,
+            other => panic!("expected MalformedInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_strict_accepts_exact_values() {
+        assert_eq!(
+            parse_signature(SIGNATURE, ParseMode::Strict)
+                .unwrap()
+                .to_string(),
+            SIGNATURE
+        );
+        assert_eq!(
+            parse_pubkey(ADDRESS, ParseMode::Strict)
+                .unwrap()
+                .to_string(),
+            ADDRESS
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_artifacts() {
+        let padded = format!(" {ADDRESS}");
+        assert_eq!(
+            problem(parse_pubkey(&padded, ParseMode::Strict).unwrap_err()),
+            Malformed::Whitespace { position: 0 }
+        );
+
+        let link = format!("https://solscan.io/tx/{SIGNATURE}");
+        assert_eq!(
+            problem(parse_signature(&link, ParseMode::Strict).unwrap_err()),
+            Malformed::InvalidCharacter {
+                character: ':',
+                position: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_lenient_cleans_copy_paste_artifacts() {
+        let inputs = [
+            format!("  {SIGNATURE}\n"),
+            format!("\"{SIGNATURE}\"."),
+            format!("`{SIGNATURE}`"),
+            format!("\u{200B}{SIGNATURE}\u{FEFF}"),
+            format!("{}\n{}", &SIGNATURE[..40], &SIGNATURE[40..]),
+            format!("https://solscan.io/tx/{SIGNATURE}?cluster=devnet"),
+            format!("https://explorer.solana.com/tx/{SIGNATURE}/inspect"),
+            format!("<solscan.io/tx/{SIGNATURE}>"),
+            format!("https%3A%2F%2Fsolscan.io%2Ftx%2F{SIGNATURE}"),
+        ];
+        for input in inputs {
+            let signature = parse_signature(&input, ParseMode::Lenient)
+                .unwrap_or_else(|e| panic!("{input:?}: {e}"));
+            assert_eq!(signature.to_string(), SIGNATURE, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_lenient_addresses_from_links() {
+        let inputs = [
+            format!("https://solscan.io/account/{ADDRESS}#portfolio"),
+            format!("https://explorer.solana.com/address/{ADDRESS}/tokens?cluster=devnet"),
+            format!("solana:{ADDRESS}?amount=10&label=Tally"),
+            format!("({ADDRESS})"),
+        ];
+        for input in inputs {
+            let pubkey = parse_pubkey(&input, ParseMode::Lenient)
+                .unwrap_or_else(|e| panic!("{input:?}: {e}"));
+            assert_eq!(pubkey.to_string(), ADDRESS, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_lenient_accepts_hex_signatures() {
+        let signature = parse_signature(SIGNATURE, ParseMode::Strict).unwrap();
+        let hex_signature = hex::encode(signature.as_ref());
+        assert_eq!(
+            parse_signature(&hex_signature, ParseMode::Lenient).unwrap(),
+            signature
+        );
+    }
+
+    #[test]
+    fn test_errors_identify_the_problem() {
+        assert_eq!(
+            problem(parse_pubkey("  \"\" ", ParseMode::Lenient).unwrap_err()),
+            Malformed::Empty
+        );
+
+        let typo = ADDRESS.replacen('j', "0", 1);
+        let error = parse_pubkey(&typo, ParseMode::Lenient).unwrap_err();
+        assert!(error.to_string().contains("never uses 0, O, I or l"));
+        assert_eq!(
+            problem(error),
+            Malformed::InvalidCharacter {
+                character: '0',
+                position: 2
+            }
+        );
+
+        assert_eq!(
+            problem(parse_signature(&SIGNATURE[..80], ParseMode::Lenient).unwrap_err()),
+            Malformed::WrongLength {
+                expected: 64,
+                found: 59
+            }
+        );
+        assert_eq!(
+            problem(parse_pubkey(&format!("{ADDRESS}%2"), ParseMode::Lenient).unwrap_err()),
+            Malformed::InvalidPercentEncoding { position: 44 }
+        );
+        assert!(matches!(
+            problem(parse_pubkey("https://solscan.io/", ParseMode::Lenient).unwrap_err()),
+            Malformed::UnrecognizedUrl(_)
+        ));
+
+        let error = parse_signature("", ParseMode::Strict).unwrap_err();
+        assert_eq!(error.to_string(), "Malformed signature: input is empty");
+    }
+}
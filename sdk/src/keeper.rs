@@ -0,0 +1,414 @@
+//! Due-agreement discovery for keepers
+//!
+//! `start_agreement` and `execute_payment` index every agreement in a `RenewalQueue`
+//! shard of the day its next payment falls due. [`due_agreements`] pages through those
+//! queues one bucket at a time, reading every shard of a bucket in one request, and
+//! yields each due agreement joined with its payment terms and payee, ready for
+//! [`execute_builder`]. Terms and payees shared by many agreements are fetched once
+//! and cached for the lifetime of the iterator.
+//!
+//! Queues are a hint: entries for agreements paused or canceled since they were
+//! indexed are re-checked and skipped. Buckets with an overflowed shard fall back to a
+//! full scan of agreement accounts. Agreements on streaming terms have no renewals and
+//! are swept with `sweep_accrued` instead, so the scan skips them.
+//!
+//! Discovery is not exhaustive. The iterator does not return:
+//! - agreements overdue by more than the `window` passed to [`due_agreements`]
+//! - agreements started before renewal queues existed and not renewed since, which
+//!   are listed in no queue
+//!
+//! Keepers that must not miss a payment should also scan the agreements of each
+//! payment terms account (`SimpleTallyClient::list_payment_agreements`) from time to
+//! time.
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use crate::metrics::observe_rpc;
+use crate::pda;
+use crate::rpc_exec::MAX_MULTIPLE_ACCOUNTS;
+use crate::program_types::{decode_versioned_account, Payee, PaymentAgreement, PaymentTerms};
+use crate::simple_client::account_filter;
+use crate::transaction_builder::{execute_payment, ExecutePaymentBuilder};
+use crate::SimpleTallyClient;
+use anchor_client::solana_account_decoder::UiAccountEncoding;
+use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
+
+/// A due agreement joined with the accounts needed to execute it
+pub type DueAgreement = (Pubkey, PaymentAgreement, PaymentTerms, Payee);
+
+/// Page through agreements due at `now`
+///
+/// Reads the renewal queues for every bucket from `now - window` to `now`, so
+/// agreements overdue by up to `window` seconds are included; older ones are not.
+/// Each page covers one bucket; the iterator yields agreements page by page.
+///
+/// # Errors
+/// Returns an error if `window` is negative or `now - window` is before the Unix epoch
+pub fn due_agreements(
+    client: &SimpleTallyClient,
+    now: i64,
+    window: i64,
+) -> Result<DueAgreements<'_>> {
+    let buckets = bucket_range(now, window)
+        .ok_or_else(|| TallyError::Generic(format!("Invalid due window: now {now}, window {window}")))?;
+    Ok(DueAgreements {
+        client,
+        now,
+        next_bucket: *buckets.start(),
+        last_bucket: *buckets.end(),
+        buffered: VecDeque::new(),
+        seen: HashSet::new(),
+        payment_terms: HashMap::new(),
+        payees: HashMap::new(),
+        scanned: None,
+    })
+}
+
+/// Create an `execute_payment` builder for a due agreement
+///
+/// Payment terms, payer and the payment timestamp are filled in; set the keeper and
+/// keeper ATA, then build with the joined payee and payment terms.
+#[must_use]
+pub fn execute_builder(due: &DueAgreement, program_id: Pubkey) -> ExecutePaymentBuilder {
+    let (_, agreement, _, _) = due;
+    execute_payment()
+        .payment_terms(agreement.payment_terms)
+        .payer(agreement.payer)
+        .next_payment_ts(agreement.next_payment_ts)
+        .program_id(program_id)
+}
+
+/// Paginated iterator over due agreements, created by [`due_agreements`]
+pub struct DueAgreements<'a> {
+    client: &'a SimpleTallyClient,
+    now: i64,
+    next_bucket: u64,
+    last_bucket: u64,
+    buffered: VecDeque<DueAgreement>,
+    seen: HashSet<Pubkey>,
+    payment_terms: HashMap<Pubkey, PaymentTerms>,
+    payees: HashMap<Pubkey, Payee>,
+    scanned: Option<Vec<(Pubkey, PaymentAgreement)>>,
+}
+
+impl DueAgreements<'_> {
+    /// Fetch the due agreements of the next bucket
+    ///
+    /// Returns `None` once every bucket in the window has been read. Pages may be
+    /// empty when a bucket has no due agreements.
+    pub fn next_page(&mut self) -> Option<Result<Vec<DueAgreement>>> {
+        if self.next_bucket > self.last_bucket {
+            return None;
+        }
+        let bucket = self.next_bucket;
+        self.next_bucket = self.next_bucket.saturating_add(1);
+        Some(self.fetch_bucket(bucket))
+    }
+
+    /// Bucket the next page will read, or `None` once the window is exhausted
+    #[must_use]
+    pub const fn next_bucket(&self) -> Option<u64> {
+        if self.next_bucket > self.last_bucket {
+            None
+        } else {
+            Some(self.next_bucket)
+        }
+    }
+
+    fn fetch_bucket(&mut self, bucket: u64) -> Result<Vec<DueAgreement>> {
+        let queues = self.client.get_renewal_queues(bucket)?;
+        let agreements = if queues.iter().any(|queue| queue.overflow_count > 0) {
+            self.scan_bucket(bucket)?
+        } else {
+            let addresses: Vec<Pubkey> = queues
+                .into_iter()
+                .flat_map(|queue| queue.agreements)
+                .collect();
+            self.fetch_agreements(&addresses)?
+        };
+
+        let mut page = Vec::new();
+        for (address, agreement) in agreements {
+            if !is_due(&agreement, self.now) || !self.seen.insert(address) {
+                continue;
+            }
+            let payment_terms = self.payment_terms(&agreement.payment_terms)?;
+            if payment_terms.is_streaming() {
+                continue;
+            }
+            let payee = self.payee(&payment_terms.payee)?;
+            page.push((address, agreement, payment_terms, payee));
+        }
+        Ok(page)
+    }
+
+    fn fetch_agreements(&self, addresses: &[Pubkey]) -> Result<Vec<(Pubkey, PaymentAgreement)>> {
+        // A bucket's shards can list more agreements than one request may fetch
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(observe_rpc("getMultipleAccounts", || {
+                self.client
+                    .rpc()
+                    .get_multiple_accounts(batch)
+                    .map_err(|e| TallyError::Generic(format!("Failed to fetch payment agreements: {e}")))
+            })?);
+        }
+        let program_id = self.client.program_id();
+
+        Ok(addresses
+            .iter()
+            .zip(accounts)
+            .filter_map(|(address, account)| {
+                let account = account.filter(|account| account.owner == program_id)?;
+                let agreement = decode_versioned_account::<PaymentAgreement>(account.data.get(8..)?).ok()?;
+                Some((*address, agreement))
+            })
+            .collect())
+    }
+
+    /// Agreements due within `bucket` found by scanning every agreement account
+    ///
+    /// The scan runs at most once per iterator and is reused for later overflowed buckets.
+    fn scan_bucket(&mut self, bucket: u64) -> Result<Vec<(Pubkey, PaymentAgreement)>> {
+        if self.scanned.is_none() {
+            self.scanned = Some(self.scan_all_agreements()?);
+        }
+        Ok(self
+            .scanned
+            .iter()
+            .flatten()
+            .filter(|(_, agreement)| pda::renewal_bucket(agreement.next_payment_ts) == Some(bucket))
+            .cloned()
+            .collect())
+    }
+
+    fn scan_all_agreements(&self) -> Result<Vec<(Pubkey, PaymentAgreement)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![account_filter::<PaymentAgreement>()]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: None,
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: Some(false),
+            sort_results: None,
+        };
+        let accounts = observe_rpc("getProgramAccounts", || {
+            self.client
+                .rpc()
+                .get_program_accounts_with_config(&self.client.program_id(), config)
+                .map_err(|e| {
+                    TallyError::Generic(format!("Failed to query payment agreement accounts: {e}"))
+                })
+        })?;
+
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(address, account)| {
+                let agreement = decode_versioned_account::<PaymentAgreement>(account.data.get(8..)?).ok()?;
+                Some((address, agreement))
+            })
+            .collect())
+    }
+
+    fn payment_terms(&mut self, address: &Pubkey) -> Result<PaymentTerms> {
+        if let Some(payment_terms) = self.payment_terms.get(address) {
+            return Ok(payment_terms.clone());
+        }
+        let payment_terms = self
+            .client
+            .get_payment_terms(address)?
+            .ok_or_else(|| TallyError::Generic(format!("Payment terms {address} not found")))?;
+        self.payment_terms.insert(*address, payment_terms.clone());
+        Ok(payment_terms)
+    }
+
+    fn payee(&mut self, address: &Pubkey) -> Result<Payee> {
+        if let Some(payee) = self.payees.get(address) {
+            return Ok(payee.clone());
+        }
+        let payee = self
+            .client
+            .get_payee(address)?
+            .ok_or_else(|| TallyError::Generic(format!("Payee {address} not found")))?;
+        self.payees.insert(*address, payee.clone());
+        Ok(payee)
+    }
+}
+
+impl Iterator for DueAgreements<'_> {
+    type Item = Result<DueAgreement>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(due) = self.buffered.pop_front() {
+                return Some(Ok(due));
+            }
+            match self.next_page()? {
+                Ok(page) => self.buffered.extend(page),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Buckets covering timestamps from `now - window` to `now`
+fn bucket_range(now: i64, window: i64) -> Option<RangeInclusive<u64>> {
+    if window < 0 {
+        return None;
+    }
+    let first = pda::renewal_bucket(now.checked_sub(window)?)?;
+    let last = pda::renewal_bucket(now)?;
+    Some(first..=last)
+}
+
+/// Whether a queued agreement is still active and due at `now`
+///
+/// Agreements whose first payment is still in escrow are skipped; the program rejects
+/// their next payment until the escrow is released or refunded.
+const fn is_due(agreement: &PaymentAgreement, now: i64) -> bool {
+    agreement.active && !agreement.escrow_pending() && agreement.next_payment_ts <= now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pda::RENEWAL_BUCKET_SECS;
+
+    const NOW: i64 = 1_700_000_000;
+
+    const fn agreement(active: bool, next_payment_ts: i64) -> PaymentAgreement {
+        PaymentAgreement {
+            payment_terms: Pubkey::new_from_array([1; 32]),
+            payer: Pubkey::new_from_array([2; 32]),
+            next_payment_ts,
+            active,
+            payment_count: 3,
+            created_ts: NOW - 3 * 2_592_000,
+            last_amount: 10_000_000,
+            last_payment_ts: NOW - 2_592_000,
+            last_pull_period_index: 3,
+            cancel_at_period_end: false,
+            pending_payer: None,
+            refunded_amount: 0,
+            max_periods: None,
+            periods_paid: 1,
+            external_ref_hash: None,
+            paused_at_ts: None,
+            credit_amount: 0,
+            escrow: None,
+            period_index: 0,
+            suspension: None,
+            bump: 255,
+            deposit_held: 0,
+            version: 1,
+            last_sweep_ts: 0,
+        }
+    }
+
+    #[test]
+    fn test_bucket_range_covers_window() {
+        let range = bucket_range(NOW, 3 * RENEWAL_BUCKET_SECS).unwrap();
+        assert_eq!(*range.start(), 19_672);
+        assert_eq!(*range.end(), 19_675);
+        assert_eq!(bucket_range(NOW, 0).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_bucket_range_rejects_invalid_window() {
+        assert!(bucket_range(NOW, -1).is_none());
+        assert!(bucket_range(NOW, NOW + RENEWAL_BUCKET_SECS).is_none());
+    }
+
+    #[test]
+    fn test_is_due_skips_stale_entries() {
+        assert!(is_due(&agreement(true, NOW), NOW));
+        assert!(is_due(&agreement(true, NOW - RENEWAL_BUCKET_SECS), NOW));
+        assert!(!is_due(&agreement(true, NOW + 1), NOW), "Not yet due");
+        assert!(!is_due(&agreement(false, NOW), NOW), "Paused since it was queued");
+
+        let mut escrowed = agreement(true, NOW);
+        escrowed.escrow = Some(crate::program_types::AgreementEscrow {
+            amount: 10_000_000,
+            expires_ts: NOW,
+        });
+        assert!(!is_due(&escrowed, NOW), "First payment still in escrow");
+    }
+
+    #[test]
+    fn test_execute_builder_prefills_agreement() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let payment_terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&authority, &program_id),
+            terms_id: [0; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 1,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let agreement = agreement(true, NOW);
+        let due: DueAgreement = (Pubkey::new_unique(), agreement.clone(), payment_terms, payee);
+
+        let instruction = execute_builder(&due, program_id)
+            .keeper(Pubkey::new_unique())
+            .keeper_ata(Pubkey::new_unique())
+            .build_instruction(&due.3, &due.2, &Pubkey::new_unique())
+            .unwrap();
+
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts[2].pubkey, agreement.payment_terms);
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            pda::payment_agreement_address_with_program_id(
+                &agreement.payment_terms,
+                &agreement.payer,
+                &program_id
+            )
+        );
+        assert_eq!(
+            instruction.accounts[13].pubkey,
+            pda::agreement_renewal_queue_address_with_program_id(
+                19_675,
+                &instruction.accounts[1].pubkey,
+                &program_id
+            )
+        );
+    }
+}
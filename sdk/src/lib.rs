@@ -4,15 +4,48 @@
 //! recurring payment program on Solana. It includes utilities for:
 //!
 //! - Computing Program Derived Addresses (PDAs) and Associated Token Accounts (ATAs)
-//! - Building payment agreement transactions (approve→start, revoke→pause flows)
+//! - Building payment agreement transactions (approve→start, pause flows)
 //! - Token program detection (SPL Token vs Token-2022)
+//! - Snapshotting and diffing platform configuration for change review (`audit`)
+//! - Rate-limited concurrent RPC execution for bulk account scans (`rpc_exec`)
+//! - Address lookup tables and v0 transactions for large batched payments (`alt`)
+//! - Balance and account preflight checks before prompting for a signature (`preflight`)
+//! - Paginated discovery of due agreements from renewal queues for keepers (`keeper`)
+//! - Sign-In-With-Solana messages for authenticating payers in payee backends (`siws`)
+//! - Scoped, read-only dashboard access tokens signed by payee authorities (`tenancy`)
+//! - Per-cluster presets for endpoints, the USDC mint and program IDs (`cluster`)
+//! - Running one query against several clusters to compare environments (`multi_cluster`)
+//! - Caching `Config`, `Payee` and `PaymentTerms` reads with event-driven invalidation (`cache`)
+//! - Versioned, checksummed exports of a payee's state for backups and migrations (`export`)
+//! - Strict and lenient parsing of pasted signatures, addresses and explorer links (`input`)
+//! - Renewal and low-allowance reminder payloads with i18n keys and merge fields (`notifications`)
 //!
 //! # Feature Flags
 //!
 //! - **`platform-admin`** - Enables platform-level administration functions (`init_config`,
-//!   `update_config`, `admin_withdraw_fees`, pause, unpause, authority transfer, etc.).
+//!   `update_config`, `admin_withdraw_fees`, pause, unpause, payee freezes, authority
+//!   transfer, etc.).
 //!   Required for Tally platform operators only. Not needed by payees or application
 //!   builders integrating recurring payments.
+//! - **`metrics`** - Records RPC latency, transaction confirmation time and event parsing
+//!   counters, rendered in the Prometheus text format by `metrics::registry().render()`.
+//! - **`swap`** - Enables the `swap` module and `StartAgreementBuilder::with_funding_swap`,
+//!   which funds the payer's USDC account with a Jupiter swap in the agreement's transaction.
+//! - **`receipt-render`** - Enables the `receipt_render` module, which renders a `TallyReceipt`
+//!   into a branded HTML or PDF receipt for customers.
+//! - **`testkit`** - Enables the `testkit` module, whose `EventFactory` generates deterministic,
+//!   seedable fixtures of every event type for tests and simulations.
+//! - **`test-clock`** - Enables `advance_test_clock`, `ExecutePaymentBuilder::test_clock` and
+//!   `SimpleTallyClient::advance_test_clock` for localnet and devnet programs built with the
+//!   program's `test-clock` feature, so integration tests can skip ahead billing periods.
+//!
+//! # Tracing
+//!
+//! Client operations emit [`tracing`](https://docs.rs/tracing) spans that downstream
+//! services can correlate with their own traces: `build_instructions` (instruction,
+//! `program_id`, `payment_terms`, payer), `send_transaction` (`program_id`, signature) and
+//! `parse_events` (`program_id`, event count, plus signature and slot when parsed with
+//! context). Failed operations record the error on the span.
 //!
 //! # Example Usage
 //!
@@ -64,62 +97,119 @@
 
 pub mod simple_client;
 // pub mod client;  // Disabled for now due to missing discriminator implementations
+pub mod alt;
+pub mod analytics;
 pub mod ata;
+pub mod audit;
+pub mod cache;
+pub mod cluster;
+pub mod confirmation;
 pub mod dashboard;
 pub mod dashboard_types;
 pub mod error;
 pub mod event_query;
 pub mod events;
+pub mod export;
+pub mod fees;
+pub mod input;
+pub mod keeper;
 pub mod keypair;
+pub mod metrics;
+pub mod multi_cluster;
+pub mod notifications;
 pub mod pda;
+pub mod preflight;
 pub mod program_types;
+#[cfg(feature = "receipt-render")]
+pub mod receipt_render;
+pub mod rpc_exec;
 pub mod signature;
+pub mod siws;
+#[cfg(feature = "swap")]
+pub mod swap;
+pub mod tenancy;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 pub mod transaction_builder;
 pub mod transaction_utils;
 pub mod utils;
 pub mod validation;
+pub mod verify;
 
 // Platform administration module (requires 'platform-admin' feature flag)
 #[cfg(feature = "platform-admin")]
 pub mod admin;
 
 // Re-export commonly used items
-pub use simple_client::SimpleTallyClient;
+pub use simple_client::{ClientOptions, SimpleTallyClient};
 // pub use client::TallyClient;  // Disabled for now
+pub use alt::{versioned_transaction, VersionedTransactionBuilder};
+pub use cache::{AccountCache, AccountCacheConfig, CachedClient};
+pub use cluster::{Cluster, ClusterConfig};
+pub use confirmation::{ConfirmationStatus, ConfirmationTracker};
 pub use dashboard::DashboardClient;
 pub use dashboard_types::{
     AgreementStatus, DashboardAgreement, DashboardEvent, DashboardEventType, EventStream,
-    Overview, PaymentTermsAnalytics,
+    MetricSource, Overview, OverviewSources, PaymentTermsAnalytics, UpcomingRenewal,
 };
 pub use error::{Result, TallyError};
 pub use event_query::{EventQueryClient, EventQueryClientConfig, EventQueryConfig, ParsedEvent};
 pub use events::{
     create_receipt, create_receipt_legacy, extract_memo_from_logs, parse_events_from_logs,
-    parse_events_with_context, ConfigInitialized, ConfigUpdated, DelegateMismatchWarning,
-    FeesWithdrawn, LowAllowanceWarning, ParsedEventWithContext, PayeeInitialized,
+    parse_events_with_context, AgreementSnapshot, AgreementSuspended, AgreementUnsuspended, ConfigInitialized, ConfigUpdated, CreditApplied,
+    DelegateMismatchWarning, DepositClaimed, DepositHeld, DepositReleased, EscrowRefunded, EscrowReleased, EventContext, FeesWithdrawn,
+    KeeperPaidInSol, LowAllowanceWarning, ParsedEventWithContext, PayeeAuthorityTransferCancelled,
+    PayeeAuthorityTransferInitiated, PayeeAuthorityTransferred, PayeeInitialized,
     PaymentAgreementClosed, PaymentAgreementPaused, PaymentAgreementResumed,
     PaymentAgreementStarted, PaymentExecuted, PaymentFailed, PaymentTermsCreated,
-    PaymentTermsStatusChanged, PaymentTermsUpdated, ProgramPaused, ProgramUnpaused,
-    ReceiptParams, StreamableEventData, TallyEvent, TallyReceipt, VolumeTier, VolumeTierUpgraded,
+    PaymentTermsStatusChanged, PaymentTermsUpdated, ProgramPaused, ProgramUnpaused, ReceiptParams,
+    StreamableEventData, Swept, TallyEvent, TallyReceipt, TermsSunset, VolumeTier, VolumeTierUpgraded,
+};
+pub use fees::{
+    compute_initial_payment_breakdown, compute_payment_breakdown, split_payment, PaymentBreakdown,
 };
+pub use input::{parse_pubkey, parse_signature, InputKind, Malformed, ParseMode};
+pub use keeper::{due_agreements, DueAgreement, DueAgreements};
 pub use keypair::load_keypair;
+pub use notifications::{
+    low_allowance_notice, low_allowance_reminder, renewal_reminder, Notification, NotificationKind,
+};
+pub use preflight::{check_start_agreement, PreflightIssue, StartAgreementPreflight};
+pub use rpc_exec::{BoundedExecutor, BoundedExecutorConfig};
 pub use program_types::*;
 // Re-export transaction builders for common operations
 pub use transaction_builder::{
-    close_agreement, create_payment_terms, execute_payment, init_payee, pause_agreement,
-    start_agreement, CloseAgreementBuilder, CreatePaymentTermsBuilder, ExecutePaymentBuilder,
-    InitPayeeBuilder, PauseAgreementBuilder, StartAgreementBuilder,
+    accept_agreement_transfer, accept_payee_authority, cancel_payee_authority_transfer,
+    claim_deposit, close_agreement, close_agreements_batch, confirm_activation, create_payment_terms, deactivate_payment_terms,
+    execute_payment, init_payee, initiate_agreement_transfer, migrate_account, pause_agreement, refund_escrow,
+    refund_payment, repair_delegate, reserve_slot, resume_after_unfreeze, resume_agreement,
+    schedule_cancellation,
+    schedule_terms_update, set_keeper_policy, start_agreement, sweep_accrued, transfer_payee_authority,
+    AcceptAgreementTransferBuilder, AcceptPayeeAuthorityBuilder,
+    CancelPayeeAuthorityTransferBuilder, ClaimDepositBuilder, CloseAgreementBuilder, CloseAgreementsBatchBuilder, ConfirmActivationBuilder,
+    CreatePaymentTermsBuilder, DeactivatePaymentTermsBuilder, ExecutePaymentBuilder,
+    InitPayeeBuilder, InitiateAgreementTransferBuilder, MigrateAccountBuilder, PauseAgreementBuilder,
+    RefundEscrowBuilder, RefundPaymentBuilder, RepairDelegateBuilder, ReserveSlotBuilder,
+    ResumeAfterUnfreezeBuilder, ResumeAgreementBuilder, ScheduleCancellationBuilder, ScheduleTermsUpdateBuilder,
+    SetKeeperPolicyBuilder, StartAgreementBuilder, SweepAccruedBuilder, TransferPayeeAuthorityBuilder,
 };
 
 // Re-export admin transaction builders (only with 'platform-admin' feature)
 #[cfg(feature = "platform-admin")]
 pub use transaction_builder::{
-    accept_authority, admin_withdraw_fees, cancel_authority_transfer, init_config, pause,
-    transfer_authority, unpause, update_config, AcceptAuthorityBuilder, AdminWithdrawFeesBuilder,
-    CancelAuthorityTransferBuilder, InitConfigBuilder, PauseBuilder, TransferAuthorityBuilder,
-    UnpauseBuilder, UpdateConfigBuilder,
+    accept_authority, admin_withdraw_fees, cancel_authority_transfer, freeze_payee, init_config,
+    pause, set_fee_holiday, set_keeper_sol_rate, transfer_authority, unfreeze_payee, unpause,
+    update_config, AcceptAuthorityBuilder, AdminWithdrawFeesBuilder,
+    CancelAuthorityTransferBuilder, FreezePayeeBuilder, InitConfigBuilder, PauseBuilder,
+    SetFeeHolidayBuilder, SetKeeperSolRateBuilder, TransferAuthorityBuilder,
+    UnfreezePayeeBuilder, UnpauseBuilder, UpdateConfigBuilder,
 };
+
+// Re-export test clock builders (only with 'test-clock' feature)
+#[cfg(feature = "test-clock")]
+pub use transaction_builder::{advance_test_clock, AdvanceTestClockBuilder};
 pub use validation::*;
+pub use verify::{check_discriminators, DiscriminatorMismatch, DiscriminatorReport};
 
 // Re-export signature verification and transaction signing utilities
 pub use signature::{
@@ -131,13 +221,16 @@ pub use signature::{
 // Re-export transaction utilities
 pub use transaction_utils::{
     build_transaction, convert_anchor_pubkey, create_memo_instruction, get_user_usdc_ata,
-    map_tally_error_to_string, StartAgreementTransactionParams,
+    map_tally_error_to_string, missing_signers, partially_sign, payment_memo, serialize_transaction,
+    tx_assembler, validate_customer_memo, StartAgreementTransactionParams, TxAssembler,
+    MAX_CUSTOMER_MEMO_LEN,
 };
 
 // Re-export general utilities
 pub use utils::{
-    calculate_next_payment, format_duration, is_agreement_overdue, is_payment_due,
-    is_valid_pubkey, micro_lamports_to_usdc, system_programs, usdc_to_micro_lamports,
+    calculate_next_payment, format_duration, format_period, format_usdc, format_usdc_grouped,
+    is_agreement_overdue, is_payment_due, is_valid_pubkey, micro_lamports_to_usdc, parse_duration,
+    parse_usdc, system_programs, usdc_to_micro_lamports,
 };
 
 // Re-export commonly used external types
@@ -170,6 +263,23 @@ pub const MAX_PAYMENT_AMOUNT_USDC: u64 = 1_000_000_000_000;
 /// Any configuration attempting to set `keeper_fee_bps` above this value will be rejected.
 pub const MAX_KEEPER_FEE_BPS: u16 = 100;
 
+/// Maximum renewal clock-skew tolerance in seconds (1 hour)
+///
+/// Any configuration attempting to set `renewal_tolerance_secs` above this value will be
+/// rejected by the program.
+pub const MAX_RENEWAL_TOLERANCE_SECS: u64 = 3_600;
+
+/// Maximum number of agreements closed by one `close_agreements_batch` instruction
+///
+/// Larger batches are rejected by the program; split them across transactions.
+pub const MAX_CLOSE_BATCH_AGREEMENTS: usize = 20;
+
+/// Maximum age of the keeper SOL rate in seconds (1 day)
+///
+/// Keeper fees taken in SOL are rejected by the program once the platform's rate is
+/// older than this.
+pub const MAX_KEEPER_SOL_RATE_AGE_SECS: i64 = 86_400;
+
 /// Program ID loaded from `TALLY_PROGRAM_ID` environment variable at runtime.
 ///
 /// # Panics
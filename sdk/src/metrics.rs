@@ -0,0 +1,403 @@
+//! Prometheus-compatible metrics for SDK instrumentation
+//!
+//! With the `metrics` feature enabled, `SimpleTallyClient` RPC calls, transaction
+//! submission and event parsing record counters and histograms in a process-wide
+//! registry. Services expose them by serving [`registry`]'s
+//! [`render`](MetricsRegistry::render) output on their scrape endpoint:
+//!
+//! ```no_run
+//! # #[cfg(feature = "metrics")]
+//! # fn scrape() -> String {
+//! tally_sdk::metrics::registry().render()
+//! # }
+//! ```
+//!
+//! Without the feature the instrumentation hooks compile to plain calls and no
+//! registry is exposed.
+//!
+//! # Metrics
+//!
+//! - `tally_sdk_rpc_requests_total{method}` - RPC requests issued
+//! - `tally_sdk_rpc_errors_total{method}` - RPC requests that returned an error
+//! - `tally_sdk_rpc_latency_seconds{method}` - RPC request latency
+//! - `tally_sdk_transactions_submitted_total` - Transactions submitted
+//! - `tally_sdk_transaction_failures_total` - Transactions that failed to confirm
+//! - `tally_sdk_transaction_confirmation_seconds` - Time from submission to confirmation
+//! - `tally_sdk_events_parsed_total` - Program events parsed from logs
+//! - `tally_sdk_event_parse_failures_total` - Program data logs that failed to parse
+
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "metrics")]
+pub use registry::{registry, Counter, Histogram, MetricsRegistry, LATENCY_BUCKETS};
+
+/// Run an RPC call, recording its latency and outcome under `method`
+pub(crate) fn observe_rpc<T, E>(
+    method: &'static str,
+    call: impl FnOnce() -> std::result::Result<T, E>,
+) -> std::result::Result<T, E> {
+    #[cfg(feature = "metrics")]
+    {
+        let start = std::time::Instant::now();
+        let result = call();
+        registry().record_rpc(method, start.elapsed(), result.is_ok());
+        result
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = method;
+        call()
+    }
+}
+
+/// Submit and confirm a transaction, recording submission, confirmation time and failures
+pub(crate) fn observe_transaction<T, E>(
+    call: impl FnOnce() -> std::result::Result<T, E>,
+) -> std::result::Result<T, E> {
+    #[cfg(feature = "metrics")]
+    {
+        let start = std::time::Instant::now();
+        let result = call();
+        registry().record_transaction(start.elapsed(), result.is_ok());
+        result
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        call()
+    }
+}
+
+/// Record the outcome of parsing one program data log
+#[cfg_attr(not(feature = "metrics"), allow(clippy::missing_const_for_fn))]
+pub(crate) fn record_event_parse(success: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        if success {
+            registry().events_parsed.inc();
+        } else {
+            registry().event_parse_failures.inc();
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = success;
+    }
+}
+
+#[cfg(feature = "metrics")]
+mod registry {
+    use std::collections::BTreeMap;
+    use std::fmt::Write;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{LazyLock, Mutex};
+    use std::time::Duration;
+
+    /// Histogram bucket upper bounds in seconds, covering fast RPC calls through slow confirmations
+    pub const LATENCY_BUCKETS: &[f64] = &[
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+    ];
+
+    static REGISTRY: LazyLock<MetricsRegistry> = LazyLock::new(MetricsRegistry::default);
+
+    /// Process-wide registry used by the SDK's instrumentation
+    #[must_use]
+    pub fn registry() -> &'static MetricsRegistry {
+        &REGISTRY
+    }
+
+    /// Monotonically increasing counter
+    #[derive(Debug, Default)]
+    pub struct Counter(AtomicU64);
+
+    impl Counter {
+        /// Increment the counter by one
+        pub fn inc(&self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+
+        /// Current value
+        #[must_use]
+        pub fn get(&self) -> u64 {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    /// Histogram of durations using [`LATENCY_BUCKETS`]
+    #[derive(Debug)]
+    pub struct Histogram {
+        buckets: Vec<AtomicU64>,
+        count: AtomicU64,
+        sum_micros: AtomicU64,
+    }
+
+    impl Default for Histogram {
+        fn default() -> Self {
+            Self {
+                buckets: LATENCY_BUCKETS.iter().map(|_| AtomicU64::new(0)).collect(),
+                count: AtomicU64::new(0),
+                sum_micros: AtomicU64::new(0),
+            }
+        }
+    }
+
+    impl Histogram {
+        /// Record one observation
+        pub fn observe(&self, duration: Duration) {
+            let seconds = duration.as_secs_f64();
+            if let Some(index) = LATENCY_BUCKETS.iter().position(|&bound| seconds <= bound) {
+                self.buckets[index].fetch_add(1, Ordering::Relaxed);
+            }
+            self.count.fetch_add(1, Ordering::Relaxed);
+            let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+            self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+        }
+
+        /// Number of observations
+        #[must_use]
+        pub fn count(&self) -> u64 {
+            self.count.load(Ordering::Relaxed)
+        }
+
+        /// Sum of all observations
+        #[must_use]
+        pub fn sum(&self) -> Duration {
+            Duration::from_micros(self.sum_micros.load(Ordering::Relaxed))
+        }
+
+        fn render(&self, out: &mut String, name: &str, labels: &str) {
+            let separator = if labels.is_empty() { "" } else { "," };
+            let mut cumulative = 0u64;
+            for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+                cumulative = cumulative.saturating_add(bucket.load(Ordering::Relaxed));
+                let _ = writeln!(
+                    out,
+                    "{name}_bucket{{{labels}{separator}le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{labels}{separator}le=\"+Inf\"}} {}",
+                self.count()
+            );
+            let braced = if labels.is_empty() {
+                String::new()
+            } else {
+                format!("{{{labels}}}")
+            };
+            let _ = writeln!(out, "{name}_sum{braced} {}", self.sum().as_secs_f64());
+            let _ = writeln!(out, "{name}_count{braced} {}", self.count());
+        }
+    }
+
+    /// Metrics recorded for a single RPC method
+    #[derive(Debug, Default)]
+    struct RpcMetrics {
+        requests: Counter,
+        errors: Counter,
+        latency: Histogram,
+    }
+
+    /// Registry of all SDK metrics
+    #[derive(Debug, Default)]
+    pub struct MetricsRegistry {
+        rpc: Mutex<BTreeMap<&'static str, RpcMetrics>>,
+        /// Transactions submitted
+        pub transactions_submitted: Counter,
+        /// Transactions that failed to submit or confirm
+        pub transaction_failures: Counter,
+        /// Time from submission to confirmation of successful transactions
+        pub transaction_confirmation: Histogram,
+        /// Program events parsed from logs
+        pub events_parsed: Counter,
+        /// Program data logs that failed to parse
+        pub event_parse_failures: Counter,
+    }
+
+    impl MetricsRegistry {
+        /// Number of RPC requests issued for `method`
+        #[must_use]
+        pub fn rpc_requests(&self, method: &str) -> u64 {
+            self.with_rpc(method, |metrics| metrics.requests.get())
+        }
+
+        /// Number of failed RPC requests for `method`
+        #[must_use]
+        pub fn rpc_errors(&self, method: &str) -> u64 {
+            self.with_rpc(method, |metrics| metrics.errors.get())
+        }
+
+        /// Render all metrics in the Prometheus text exposition format
+        #[must_use]
+        pub fn render(&self) -> String {
+            let mut out = String::new();
+            let rpc = self
+                .rpc
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+            Self::header(&mut out, "tally_sdk_rpc_requests_total", "counter", "RPC requests issued");
+            for (method, metrics) in rpc.iter() {
+                let _ = writeln!(
+                    out,
+                    "tally_sdk_rpc_requests_total{{method=\"{method}\"}} {}",
+                    metrics.requests.get()
+                );
+            }
+
+            Self::header(
+                &mut out,
+                "tally_sdk_rpc_errors_total",
+                "counter",
+                "RPC requests that returned an error",
+            );
+            for (method, metrics) in rpc.iter() {
+                let _ = writeln!(
+                    out,
+                    "tally_sdk_rpc_errors_total{{method=\"{method}\"}} {}",
+                    metrics.errors.get()
+                );
+            }
+
+            Self::header(
+                &mut out,
+                "tally_sdk_rpc_latency_seconds",
+                "histogram",
+                "RPC request latency",
+            );
+            for (method, metrics) in rpc.iter() {
+                metrics.latency.render(
+                    &mut out,
+                    "tally_sdk_rpc_latency_seconds",
+                    &format!("method=\"{method}\""),
+                );
+            }
+            drop(rpc);
+
+            Self::counter(
+                &mut out,
+                "tally_sdk_transactions_submitted_total",
+                "Transactions submitted",
+                &self.transactions_submitted,
+            );
+            Self::counter(
+                &mut out,
+                "tally_sdk_transaction_failures_total",
+                "Transactions that failed to submit or confirm",
+                &self.transaction_failures,
+            );
+            Self::header(
+                &mut out,
+                "tally_sdk_transaction_confirmation_seconds",
+                "histogram",
+                "Time from submission to confirmation",
+            );
+            self.transaction_confirmation
+                .render(&mut out, "tally_sdk_transaction_confirmation_seconds", "");
+            Self::counter(
+                &mut out,
+                "tally_sdk_events_parsed_total",
+                "Program events parsed from logs",
+                &self.events_parsed,
+            );
+            Self::counter(
+                &mut out,
+                "tally_sdk_event_parse_failures_total",
+                "Program data logs that failed to parse",
+                &self.event_parse_failures,
+            );
+
+            out
+        }
+
+        pub(crate) fn record_rpc(&self, method: &'static str, latency: Duration, success: bool) {
+            let mut rpc = self
+                .rpc
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let metrics = rpc.entry(method).or_default();
+            metrics.requests.inc();
+            if !success {
+                metrics.errors.inc();
+            }
+            metrics.latency.observe(latency);
+            drop(rpc);
+        }
+
+        pub(crate) fn record_transaction(&self, elapsed: Duration, success: bool) {
+            self.transactions_submitted.inc();
+            if success {
+                self.transaction_confirmation.observe(elapsed);
+            } else {
+                self.transaction_failures.inc();
+            }
+        }
+
+        fn with_rpc(&self, method: &str, read: impl FnOnce(&RpcMetrics) -> u64) -> u64 {
+            self.rpc
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .get(method)
+                .map_or(0, read)
+        }
+
+        fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+        }
+
+        fn counter(out: &mut String, name: &str, help: &str, counter: &Counter) {
+            Self::header(out, name, "counter", help);
+            let _ = writeln!(out, "{name} {}", counter.get());
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_histogram_buckets_are_cumulative() {
+            let histogram = Histogram::default();
+            histogram.observe(Duration::from_millis(3));
+            histogram.observe(Duration::from_millis(40));
+            histogram.observe(Duration::from_secs(90));
+
+            let mut out = String::new();
+            histogram.render(&mut out, "latency", "");
+
+            assert!(out.contains("latency_bucket{le=\"0.005\"} 1\n"));
+            assert!(out.contains("latency_bucket{le=\"0.05\"} 2\n"));
+            assert!(out.contains("latency_bucket{le=\"60\"} 2\n"));
+            assert!(out.contains("latency_bucket{le=\"+Inf\"} 3\n"));
+            assert!(out.contains("latency_count 3\n"));
+            assert_eq!(histogram.sum(), Duration::from_millis(90_043));
+        }
+
+        #[test]
+        fn test_registry_render() {
+            let registry = MetricsRegistry::default();
+            registry.record_rpc("getSlot", Duration::from_millis(20), true);
+            registry.record_rpc("getSlot", Duration::from_millis(20), false);
+            registry.record_transaction(Duration::from_secs(2), true);
+            registry.record_transaction(Duration::from_secs(2), false);
+            registry.events_parsed.inc();
+
+            assert_eq!(registry.rpc_requests("getSlot"), 2);
+            assert_eq!(registry.rpc_errors("getSlot"), 1);
+            assert_eq!(registry.rpc_requests("getHealth"), 0);
+
+            let out = registry.render();
+            assert!(out.contains("# TYPE tally_sdk_rpc_latency_seconds histogram\n"));
+            assert!(out.contains("tally_sdk_rpc_requests_total{method=\"getSlot\"} 2\n"));
+            assert!(out.contains("tally_sdk_rpc_errors_total{method=\"getSlot\"} 1\n"));
+            assert!(out.contains(
+                "tally_sdk_rpc_latency_seconds_bucket{method=\"getSlot\",le=\"0.025\"} 2\n"
+            ));
+            assert!(out.contains("tally_sdk_transactions_submitted_total 2\n"));
+            assert!(out.contains("tally_sdk_transaction_failures_total 1\n"));
+            assert!(out.contains("tally_sdk_transaction_confirmation_seconds_count 1\n"));
+            assert!(out.contains("tally_sdk_events_parsed_total 1\n"));
+            assert!(out.contains("tally_sdk_event_parse_failures_total 0\n"));
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! Running the same query against several clusters
+//!
+//! Release tooling checks that a config or payment terms exist identically on every
+//! environment before cutting over. [`MultiClusterClient`] wraps one labeled
+//! [`SimpleTallyClient`] per environment, runs a query against all of them
+//! concurrently and returns the results under their labels:
+//!
+//! ```no_run
+//! use tally_sdk::cluster::{Cluster, ClusterConfig};
+//! use tally_sdk::multi_cluster::MultiClusterClient;
+//! use anchor_client::solana_sdk::pubkey::Pubkey;
+//!
+//! # fn main() -> tally_sdk::Result<()> {
+//! # let mainnet_program_id = Pubkey::new_unique();
+//! # let payee_authority = Pubkey::new_unique();
+//! let clients = MultiClusterClient::from_configs(&[
+//!     ClusterConfig::preset(Cluster::Devnet),
+//!     ClusterConfig::preset(Cluster::Mainnet).with_program_id(mainnet_program_id),
+//! ])?;
+//!
+//! // Addresses and bumps differ per deployment, so compare the terms themselves
+//! let terms = clients.get_payment_terms(&payee_authority, "premium");
+//! terms.ensure_consistent_by(|terms| terms.as_ref().map(|t| (t.amount_usdc, t.period_secs)))?;
+//! # Ok(())
+//! # }
+//! ```
+
+#![forbid(unsafe_code)]
+
+use crate::{
+    cluster::ClusterConfig,
+    error::{Result, TallyError},
+    pda,
+    program_types::{Config, Payee, PaymentTerms},
+    SimpleTallyClient,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use std::fmt::Debug;
+
+/// Result of a query against one labeled cluster
+#[derive(Debug)]
+pub struct ClusterResult<T> {
+    /// Label of the cluster the query ran against
+    pub label: String,
+    /// What the query returned on that cluster
+    pub result: Result<T>,
+}
+
+/// Results of one query against every cluster of a [`MultiClusterClient`], in the
+/// order the clusters were added
+#[derive(Debug)]
+pub struct ClusterResults<T> {
+    results: Vec<ClusterResult<T>>,
+}
+
+impl<T> ClusterResults<T> {
+    /// Iterate over the labeled results
+    pub fn iter(&self) -> std::slice::Iter<'_, ClusterResult<T>> {
+        self.results.iter()
+    }
+
+    /// Result for the cluster with `label`, if there is one
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<&Result<T>> {
+        self.results
+            .iter()
+            .find(|cluster| cluster.label == label)
+            .map(|cluster| &cluster.result)
+    }
+
+    /// Number of clusters queried
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether no clusters were queried
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Check that the query succeeded on every cluster with the same `key`
+    ///
+    /// Comparing a key rather than the whole value leaves out what legitimately
+    /// differs between deployments, such as PDAs, bumps and authorities.
+    ///
+    /// # Errors
+    /// Returns an error naming every cluster whose query failed, or every cluster's
+    /// key when they are not all equal
+    pub fn ensure_consistent_by<K, F>(&self, key: F) -> Result<()>
+    where
+        K: PartialEq + Debug,
+        F: Fn(&T) -> K,
+    {
+        let failures: Vec<String> = self
+            .results
+            .iter()
+            .filter_map(|cluster| match &cluster.result {
+                Ok(_) => None,
+                Err(e) => Some(format!("{}: {e}", cluster.label)),
+            })
+            .collect();
+        if !failures.is_empty() {
+            return Err(TallyError::Generic(format!(
+                "Query failed on {}",
+                failures.join("; ")
+            )));
+        }
+
+        let keys: Vec<(&str, K)> = self
+            .results
+            .iter()
+            .filter_map(|cluster| {
+                let value = cluster.result.as_ref().ok()?;
+                Some((cluster.label.as_str(), key(value)))
+            })
+            .collect();
+        if keys.windows(2).all(|pair| pair[0].1 == pair[1].1) {
+            return Ok(());
+        }
+        let found: Vec<String> = keys
+            .iter()
+            .map(|(label, key)| format!("{label}: {key:?}"))
+            .collect();
+        Err(TallyError::Generic(format!(
+            "Clusters disagree: {}",
+            found.join("; ")
+        )))
+    }
+}
+
+impl<T> IntoIterator for ClusterResults<T> {
+    type Item = ClusterResult<T>;
+    type IntoIter = std::vec::IntoIter<ClusterResult<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ClusterResults<T> {
+    type Item = &'a ClusterResult<T>;
+    type IntoIter = std::slice::Iter<'a, ClusterResult<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Labeled clients for several clusters, queried together
+#[derive(Default)]
+pub struct MultiClusterClient {
+    clients: Vec<(String, SimpleTallyClient)>,
+}
+
+impl MultiClusterClient {
+    /// Create a client without any clusters
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a client for each configuration, labeled with its cluster name
+    ///
+    /// # Errors
+    /// Returns an error if a configuration has no program ID, or if two
+    /// configurations are for the same cluster
+    pub fn from_configs(configs: &[ClusterConfig]) -> Result<Self> {
+        configs.iter().try_fold(Self::new(), |clients, config| {
+            clients.with_client(config.cluster().to_string(), config.client()?)
+        })
+    }
+
+    /// Add a client under `label`
+    ///
+    /// # Errors
+    /// Returns an error if a client already uses `label`
+    pub fn with_client(
+        mut self,
+        label: impl Into<String>,
+        client: SimpleTallyClient,
+    ) -> Result<Self> {
+        let label = label.into();
+        if self.clients.iter().any(|(existing, _)| *existing == label) {
+            return Err(TallyError::Generic(format!(
+                "Cluster label '{label}' is already in use"
+            )));
+        }
+        self.clients.push((label, client));
+        Ok(self)
+    }
+
+    /// Labels of the clusters, in the order they were added
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.clients.iter().map(|(label, _)| label.as_str())
+    }
+
+    /// Client for the cluster with `label`, if there is one
+    #[must_use]
+    pub fn client(&self, label: &str) -> Option<&SimpleTallyClient> {
+        self.clients
+            .iter()
+            .find(|(existing, _)| existing == label)
+            .map(|(_, client)| client)
+    }
+
+    /// Run `query` against every cluster concurrently, one thread per cluster
+    ///
+    /// A failure on one cluster doesn't stop the others; it is returned as that
+    /// cluster's result.
+    #[must_use]
+    pub fn query<T, F>(&self, query: F) -> ClusterResults<T>
+    where
+        T: Send,
+        F: Fn(&SimpleTallyClient) -> Result<T> + Sync,
+    {
+        let query = &query;
+        let results = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .clients
+                .iter()
+                .map(|(label, client)| (label, scope.spawn(move || query(client))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|(label, handle)| ClusterResult {
+                    label: label.clone(),
+                    result: handle.join().unwrap_or_else(|_| {
+                        Err(TallyError::Generic(format!("Query panicked on {label}")))
+                    }),
+                })
+                .collect()
+        });
+        ClusterResults { results }
+    }
+
+    /// Fetch the global config from every cluster
+    #[must_use]
+    pub fn get_config(&self) -> ClusterResults<Option<Config>> {
+        self.query(SimpleTallyClient::get_config)
+    }
+
+    /// Fetch the payee of `authority` from every cluster
+    ///
+    /// The payee PDA is derived per cluster from that client's program ID.
+    #[must_use]
+    pub fn get_payee(&self, authority: &Pubkey) -> ClusterResults<Option<Payee>> {
+        self.query(|client| client.get_payee(&client.payee_address(authority)))
+    }
+
+    /// Fetch the payment terms `terms_id` of the payee of `payee_authority` from every
+    /// cluster
+    ///
+    /// The payee and payment terms PDAs are derived per cluster from that client's
+    /// program ID.
+    #[must_use]
+    pub fn get_payment_terms(
+        &self,
+        payee_authority: &Pubkey,
+        terms_id: &str,
+    ) -> ClusterResults<Option<PaymentTerms>> {
+        self.query(|client| {
+            let payment_terms = pda::payment_terms_address_from_string_with_program_id(
+                &client.payee_address(payee_authority),
+                terms_id,
+                &client.program_id(),
+            );
+            client.get_payment_terms(&payment_terms)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cluster::Cluster;
+
+    fn clients() -> MultiClusterClient {
+        MultiClusterClient::from_configs(&[
+            ClusterConfig::preset(Cluster::Devnet),
+            ClusterConfig::preset(Cluster::Localnet),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_configs_labels_by_cluster() {
+        let clients = clients();
+        assert_eq!(clients.labels().collect::<Vec<_>>(), ["devnet", "localnet"]);
+        assert!(clients.client("devnet").is_some());
+        assert!(clients.client("mainnet-beta").is_none());
+
+        // Mainnet has no published deployment to default to
+        assert!(
+            MultiClusterClient::from_configs(&[ClusterConfig::preset(Cluster::Mainnet)]).is_err()
+        );
+        // Two configurations for one cluster would share a label
+        assert!(MultiClusterClient::from_configs(&[
+            ClusterConfig::preset(Cluster::Devnet),
+            ClusterConfig::preset(Cluster::Devnet),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_query_returns_labeled_results_in_order() {
+        let clients = clients();
+        let results = clients.query(|client| Ok(client.program_id()));
+
+        assert_eq!(results.len(), 2);
+        let labels: Vec<&str> = results
+            .iter()
+            .map(|cluster| cluster.label.as_str())
+            .collect();
+        assert_eq!(labels, ["devnet", "localnet"]);
+        assert_eq!(
+            *results.get("devnet").unwrap().as_ref().unwrap(),
+            crate::cluster::DEVNET_PROGRAM_ID
+        );
+        assert_eq!(
+            *results.get("localnet").unwrap().as_ref().unwrap(),
+            crate::cluster::LOCALNET_PROGRAM_ID
+        );
+    }
+
+    #[test]
+    fn test_ensure_consistent_by() {
+        let clients = clients();
+
+        let same = clients.query(|_| Ok(42_u64));
+        assert!(same.ensure_consistent_by(|value| *value).is_ok());
+
+        let different = clients.query(|client| Ok(client.program_id()));
+        let error = different
+            .ensure_consistent_by(|program_id| *program_id)
+            .unwrap_err();
+        assert!(error.to_string().contains("devnet"));
+        assert!(error.to_string().contains("localnet"));
+        // Keys can leave out what differs between deployments
+        assert!(different.ensure_consistent_by(|_| ()).is_ok());
+
+        let failed = clients.query(|client| {
+            if client.program_id() == crate::cluster::LOCALNET_PROGRAM_ID {
+                Err(TallyError::Generic("validator not running".to_string()))
+            } else {
+                Ok(())
+            }
+        });
+        let error = failed.ensure_consistent_by(|()| ()).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("localnet: Tally SDK error: validator not running"));
+        assert!(!error.to_string().contains("devnet"));
+    }
+}
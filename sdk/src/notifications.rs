@@ -0,0 +1,451 @@
+//! Reminder notifications for payers
+//!
+//! Builds structured reminder payloads from on-chain state, ready to hand to the
+//! webhook or email system a payee plugs in. The SDK doesn't send anything: each
+//! [`Notification`] carries a stable i18n key and a set of merge fields, so payees
+//! translate and template the message however they like. English defaults are
+//! available through [`Notification::render_default`].
+//!
+//! - [`renewal_reminder`]: the agreement renews within the reminder lead time
+//! - [`low_allowance_reminder`]: the payer's token delegation covers fewer than
+//!   [`LOW_ALLOWANCE_RENEWALS`] renewals (the allowance is the `delegated_amount` of
+//!   the payer's USDC account)
+//! - [`low_allowance_notice`]: a `LowAllowanceWarning` emitted by `execute_payment`
+//!
+//! Merge field values are plain strings; amounts are formatted from micro-USDC with
+//! [`format_usdc`] and dates are UTC `YYYY-MM-DD`.
+//!
+//! ```
+//! use tally_sdk::notifications::{renewal_reminder, DEFAULT_RENEWAL_LEAD_SECS};
+//! # use tally_sdk::program_types::{PaymentAgreement, PaymentTerms};
+//! # fn remind(agreement: &PaymentAgreement, terms: &PaymentTerms, now: i64) {
+//! if let Some(notification) = renewal_reminder(agreement, terms, now, DEFAULT_RENEWAL_LEAD_SECS) {
+//!     println!("{}: {}", notification.i18n_key, notification.render_default());
+//! }
+//! # }
+//! ```
+
+#![forbid(unsafe_code)]
+
+use crate::events::LowAllowanceWarning;
+use crate::program_types::{PaymentAgreement, PaymentTerms};
+use crate::utils::{format_period, format_usdc};
+use anchor_lang::prelude::Pubkey;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default lead time for renewal reminders (3 days)
+pub const DEFAULT_RENEWAL_LEAD_SECS: i64 = 259_200;
+
+/// Renewals an allowance must cover to not be considered low
+///
+/// Matches the program's `LowAllowanceWarning`, which recommends an allowance of
+/// twice the payment amount.
+pub const LOW_ALLOWANCE_RENEWALS: u64 = 2;
+
+/// Currency shown in merge fields
+const CURRENCY: &str = "USDC";
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Kind of reminder a [`Notification`] carries
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// The agreement renews soon
+    UpcomingRenewal,
+    /// The payer's allowance covers too few renewals
+    LowAllowance,
+}
+
+impl NotificationKind {
+    /// Stable i18n key for translation catalogs
+    #[must_use]
+    pub const fn i18n_key(self) -> &'static str {
+        match self {
+            Self::UpcomingRenewal => "tally.notification.upcoming_renewal",
+            Self::LowAllowance => "tally.notification.low_allowance",
+        }
+    }
+
+    /// English template using `{{merge_field}}` placeholders
+    #[must_use]
+    pub const fn default_template(self) -> &'static str {
+        match self {
+            Self::UpcomingRenewal => {
+                "Your subscription renews in {{days_until}} day(s) on {{renewal_date}} for {{amount}} {{currency}}."
+            }
+            Self::LowAllowance => {
+                "Your allowance covers only {{renewals_covered}} more renewal(s) of {{amount}} {{currency}}. Approve at least {{recommended_allowance}} {{currency}} to keep your subscription active."
+            }
+        }
+    }
+}
+
+/// Structured reminder payload
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    /// Kind of reminder
+    pub kind: NotificationKind,
+    /// Stable i18n key, see [`NotificationKind::i18n_key`]
+    pub i18n_key: String,
+    /// Payer to notify
+    pub payer: Pubkey,
+    /// Payment terms the reminder is about
+    pub payment_terms: Pubkey,
+    /// Values for the template placeholders, keyed by field name
+    pub merge_fields: BTreeMap<String, String>,
+}
+
+impl Notification {
+    fn new(kind: NotificationKind, payer: Pubkey, payment_terms: Pubkey) -> Self {
+        let mut merge_fields = BTreeMap::new();
+        merge_fields.insert("currency".to_string(), CURRENCY.to_string());
+        merge_fields.insert("payment_terms".to_string(), payment_terms.to_string());
+        merge_fields.insert("payer".to_string(), payer.to_string());
+        Self {
+            kind,
+            i18n_key: kind.i18n_key().to_string(),
+            payer,
+            payment_terms,
+            merge_fields,
+        }
+    }
+
+    fn field(mut self, name: &str, value: String) -> Self {
+        self.merge_fields.insert(name.to_string(), value);
+        self
+    }
+
+    /// Fill `{{merge_field}}` placeholders in `template`
+    ///
+    /// Placeholders without a matching merge field are left in place.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        self.merge_fields
+            .iter()
+            .fold(template.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{{{name}}}}}"), value)
+            })
+    }
+
+    /// Render the English default template of this notification's kind
+    #[must_use]
+    pub fn render_default(&self) -> String {
+        self.render(self.kind.default_template())
+    }
+}
+
+/// Reminder for an agreement renewing within `lead_secs` of `now`
+///
+/// Returns `None` when the agreement won't renew: it is inactive or suspended, is
+/// canceling at period end, has charged all of its `max_periods`, its terms stream
+/// rather than renew, its terms sunset before the renewal, or the renewal is more
+/// than `lead_secs` away. Overdue
+/// agreements get a reminder with `days_until` of zero.
+///
+/// The amount applies any scheduled terms update due by the renewal, without the
+/// token-gate discount.
+///
+/// Merge fields: `amount`, `amount_micro`, `currency`, `days_until`, `renewal_date`,
+/// `renewal_ts`, `period`, `terms_id`, `payer`, `payment_terms`.
+#[must_use]
+pub fn renewal_reminder(
+    agreement: &PaymentAgreement,
+    payment_terms: &PaymentTerms,
+    now: i64,
+    lead_secs: i64,
+) -> Option<Notification> {
+    if !agreement.active
+        || agreement.is_suspended()
+        || agreement.cancel_at_period_end
+        || agreement.remaining_periods() == Some(0)
+        || payment_terms.is_streaming()
+    {
+        return None;
+    }
+    let renewal_ts = agreement.next_payment_ts;
+    if payment_terms
+        .sunset_ts
+        .is_some_and(|sunset_ts| renewal_ts >= sunset_ts)
+    {
+        return None;
+    }
+    let secs_until = renewal_ts.saturating_sub(now);
+    if secs_until > lead_secs {
+        return None;
+    }
+
+    let amount = payment_terms.amount_usdc_at(renewal_ts);
+    let days_until = u64::try_from(secs_until)
+        .unwrap_or(0)
+        .div_ceil(SECONDS_PER_DAY);
+    let renewal_date = DateTime::from_timestamp(renewal_ts, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    Some(
+        Notification::new(
+            NotificationKind::UpcomingRenewal,
+            agreement.payer,
+            agreement.payment_terms,
+        )
+        .field("amount", format_usdc(amount))
+        .field("amount_micro", amount.to_string())
+        .field("days_until", days_until.to_string())
+        .field("renewal_date", renewal_date)
+        .field("renewal_ts", renewal_ts.to_string())
+        .field(
+            "period",
+            format_period(payment_terms.period_secs_at(renewal_ts)),
+        )
+        .field("terms_id", payment_terms.terms_id_str()),
+    )
+}
+
+/// Reminder for an allowance covering fewer than [`LOW_ALLOWANCE_RENEWALS`] renewals
+///
+/// `allowance` is the `delegated_amount` of the payer's USDC account. Returns `None`
+/// for inactive agreements, free terms, or an allowance that covers enough renewals.
+///
+/// Merge fields: `amount`, `amount_micro`, `currency`, `renewals_covered`,
+/// `current_allowance`, `recommended_allowance`, `terms_id`, `payer`, `payment_terms`.
+#[must_use]
+pub fn low_allowance_reminder(
+    agreement: &PaymentAgreement,
+    payment_terms: &PaymentTerms,
+    allowance: u64,
+) -> Option<Notification> {
+    if !agreement.active {
+        return None;
+    }
+    let amount = payment_terms.amount_usdc_at(agreement.next_payment_ts);
+    let renewals_covered = allowance.checked_div(amount)?;
+    if renewals_covered >= LOW_ALLOWANCE_RENEWALS {
+        return None;
+    }
+
+    Some(
+        low_allowance(
+            agreement.payer,
+            agreement.payment_terms,
+            allowance,
+            amount.saturating_mul(LOW_ALLOWANCE_RENEWALS),
+            amount,
+        )
+        .field("terms_id", payment_terms.terms_id_str()),
+    )
+}
+
+/// Reminder for a `LowAllowanceWarning` emitted by `execute_payment`
+///
+/// Merge fields: `amount`, `amount_micro`, `currency`, `renewals_covered`,
+/// `current_allowance`, `recommended_allowance`, `payer`, `payment_terms`.
+#[must_use]
+pub fn low_allowance_notice(event: &LowAllowanceWarning) -> Notification {
+    low_allowance(
+        event.payer,
+        event.payment_terms,
+        event.current_allowance,
+        event.recommended_allowance,
+        event.payment_amount,
+    )
+}
+
+fn low_allowance(
+    payer: Pubkey,
+    payment_terms: Pubkey,
+    allowance: u64,
+    recommended_allowance: u64,
+    amount: u64,
+) -> Notification {
+    Notification::new(NotificationKind::LowAllowance, payer, payment_terms)
+        .field("amount", format_usdc(amount))
+        .field("amount_micro", amount.to_string())
+        .field(
+            "renewals_covered",
+            allowance.checked_div(amount).unwrap_or(0).to_string(),
+        )
+        .field("current_allowance", format_usdc(allowance))
+        .field("recommended_allowance", format_usdc(recommended_allowance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_types::PendingTermsUpdate;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn terms(amount_usdc: u64) -> PaymentTerms {
+        let mut terms_id = [0u8; 32];
+        terms_id[..3].copy_from_slice(b"pro");
+        PaymentTerms {
+            payee: Pubkey::new_unique(),
+            terms_id,
+            amount_usdc,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 1,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        }
+    }
+
+    fn agreement(next_payment_ts: i64) -> PaymentAgreement {
+        PaymentAgreement {
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            next_payment_ts,
+            active: true,
+            payment_count: 1,
+            created_ts: NOW,
+            last_amount: 9_990_000,
+            last_payment_ts: NOW,
+            last_pull_period_index: 0,
+            cancel_at_period_end: false,
+            pending_payer: None,
+            refunded_amount: 0,
+            max_periods: None,
+            periods_paid: 1,
+            external_ref_hash: None,
+            paused_at_ts: None,
+            credit_amount: 0,
+            escrow: None,
+            period_index: 0,
+            suspension: None,
+            deposit_held: 0,
+            bump: 255,
+            version: 1,
+            last_sweep_ts: 0,
+        }
+    }
+
+    #[test]
+    fn test_renewal_reminder_within_lead_time() {
+        let agreement = agreement(NOW + 3 * 86_400);
+        let notification = renewal_reminder(
+            &agreement,
+            &terms(9_990_000),
+            NOW,
+            DEFAULT_RENEWAL_LEAD_SECS,
+        )
+        .unwrap();
+
+        assert_eq!(notification.kind, NotificationKind::UpcomingRenewal);
+        assert_eq!(notification.i18n_key, "tally.notification.upcoming_renewal");
+        assert_eq!(notification.merge_fields["terms_id"], "pro");
+        assert_eq!(notification.merge_fields["period"], "1mo");
+        assert_eq!(
+            notification.render_default(),
+            "Your subscription renews in 3 day(s) on 2023-11-17 for 9.99 USDC."
+        );
+    }
+
+    #[test]
+    fn test_renewal_reminder_skips_agreements_that_wont_renew() {
+        let terms = terms(9_990_000);
+        let lead = DEFAULT_RENEWAL_LEAD_SECS;
+
+        let far = agreement(NOW + lead + 1);
+        assert!(renewal_reminder(&far, &terms, NOW, lead).is_none());
+
+        let mut canceling = agreement(NOW + 60);
+        canceling.cancel_at_period_end = true;
+        assert!(renewal_reminder(&canceling, &terms, NOW, lead).is_none());
+
+        let mut finished = agreement(NOW + 60);
+        finished.max_periods = Some(1);
+        assert!(renewal_reminder(&finished, &terms, NOW, lead).is_none());
+
+        let mut streaming = terms.clone();
+        streaming.accrual_rate_per_sec = Some(9_990_000 / 2_592_000);
+        assert!(renewal_reminder(&agreement(NOW + 60), &streaming, NOW, lead).is_none());
+
+        let mut sunset = terms;
+        sunset.sunset_ts = Some(NOW + 60);
+        assert!(renewal_reminder(&agreement(NOW + 60), &sunset, NOW, lead).is_none());
+    }
+
+    #[test]
+    fn test_renewal_reminder_uses_scheduled_amount() {
+        let mut terms = terms(9_990_000);
+        terms.pending_update = Some(PendingTermsUpdate {
+            amount_usdc: 12_500_000,
+            period_secs: 2_592_000,
+            effective_ts: NOW + 3_600,
+        });
+
+        let notification = renewal_reminder(
+            &agreement(NOW + 7_200),
+            &terms,
+            NOW,
+            DEFAULT_RENEWAL_LEAD_SECS,
+        )
+        .unwrap();
+
+        assert_eq!(notification.merge_fields["amount"], "12.50");
+        assert_eq!(notification.merge_fields["days_until"], "1");
+    }
+
+    #[test]
+    fn test_low_allowance_reminder() {
+        let terms = terms(9_990_000);
+        let agreement = agreement(NOW + 86_400);
+
+        let notification = low_allowance_reminder(&agreement, &terms, 15_000_000).unwrap();
+        assert_eq!(notification.merge_fields["renewals_covered"], "1");
+        assert_eq!(
+            notification.render_default(),
+            "Your allowance covers only 1 more renewal(s) of 9.99 USDC. Approve at least 19.98 USDC to keep your subscription active."
+        );
+
+        assert!(low_allowance_reminder(&agreement, &terms, 19_980_000).is_none());
+        assert!(low_allowance_reminder(&agreement, &self::terms(0), 0).is_none());
+    }
+
+    #[test]
+    fn test_low_allowance_notice_from_event() {
+        let event = LowAllowanceWarning {
+            payee: Pubkey::new_unique(),
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            current_allowance: 0,
+            recommended_allowance: 20_000_000,
+            payment_amount: 10_000_000,
+        };
+
+        let notification = low_allowance_notice(&event);
+        assert_eq!(notification.i18n_key, "tally.notification.low_allowance");
+        assert_eq!(notification.payer, event.payer);
+        assert_eq!(notification.merge_fields["renewals_covered"], "0");
+        assert_eq!(notification.merge_fields["recommended_allowance"], "20.00");
+    }
+
+    #[test]
+    fn test_render_leaves_unknown_placeholders() {
+        let notification = low_allowance_notice(&LowAllowanceWarning {
+            payee: Pubkey::new_unique(),
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            current_allowance: 0,
+            recommended_allowance: 0,
+            payment_amount: 1,
+        });
+
+        assert_eq!(
+            notification.render("{{amount}} {{unknown}}"),
+            "0.000001 {{unknown}}"
+        );
+    }
+}
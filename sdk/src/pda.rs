@@ -264,6 +264,167 @@ pub fn payment_agreement_address_with_program_id(
     payment_agreement_with_program_id(payment_terms, payer, program_id).0
 }
 
+/// Compute the `SlotReservation` (waitlist) PDA address only (without bump)
+///
+/// # Arguments
+/// * `payment_terms` - The payment terms PDA pubkey
+/// * `payer` - The payer's pubkey
+///
+/// # Returns
+/// * `Ok(Pubkey)` - The PDA address
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn slot_reservation_address(payment_terms: &Pubkey, payer: &Pubkey) -> Result<Pubkey> {
+    let program_id = program_id_string().parse()?;
+    Ok(slot_reservation_address_with_program_id(
+        payment_terms,
+        payer,
+        &program_id,
+    ))
+}
+
+/// Compute the `SlotReservation` (waitlist) PDA with custom program ID
+///
+/// # Arguments
+/// * `payment_terms` - The payment terms PDA pubkey
+/// * `payer` - The payer's pubkey
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `(Pubkey, u8)` - The PDA address and bump seed
+#[must_use]
+pub fn slot_reservation_with_program_id(
+    payment_terms: &Pubkey,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    let seeds = &[b"slot_reservation", payment_terms.as_ref(), payer.as_ref()];
+    Pubkey::find_program_address(seeds, program_id)
+}
+
+/// Compute the `SlotReservation` (waitlist) PDA address only (without bump) with custom program ID
+///
+/// # Arguments
+/// * `payment_terms` - The payment terms PDA pubkey
+/// * `payer` - The payer's pubkey
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `Pubkey` - The PDA address
+#[must_use]
+pub fn slot_reservation_address_with_program_id(
+    payment_terms: &Pubkey,
+    payer: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    slot_reservation_with_program_id(payment_terms, payer, program_id).0
+}
+
+/// Width of a renewal queue bucket in seconds (matches the program's `RENEWAL_BUCKET_SECS`)
+pub const RENEWAL_BUCKET_SECS: i64 = 86_400;
+
+/// Compute the renewal queue bucket containing a timestamp
+///
+/// # Arguments
+/// * `ts` - Unix timestamp, typically an agreement's next payment timestamp
+///
+/// # Returns
+/// * `Some(u64)` - The bucket index
+/// * `None` - If the timestamp is before the Unix epoch
+#[must_use]
+pub fn renewal_bucket(ts: i64) -> Option<u64> {
+    u64::try_from(ts.checked_div(RENEWAL_BUCKET_SECS)?).ok()
+}
+
+/// Number of renewal queue shards per bucket (matches the program's `RENEWAL_QUEUE_SHARDS`)
+pub const RENEWAL_QUEUE_SHARDS: u8 = 64;
+
+/// Compute the renewal queue shard that indexes a payment agreement
+///
+/// # Arguments
+/// * `agreement` - The payment agreement PDA
+///
+/// # Returns
+/// * `u8` - The shard index, below [`RENEWAL_QUEUE_SHARDS`]
+#[must_use]
+pub const fn renewal_queue_shard(agreement: &Pubkey) -> u8 {
+    agreement.to_bytes()[0] % RENEWAL_QUEUE_SHARDS
+}
+
+/// Compute the `RenewalQueue` (crank index) PDA
+///
+/// # Arguments
+/// * `bucket` - The renewal bucket index (see [`renewal_bucket`])
+/// * `shard` - The shard index within the bucket (see [`renewal_queue_shard`])
+///
+/// # Returns
+/// * `Ok((Pubkey, u8))` - The PDA address and bump seed
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn renewal_queue(bucket: u64, shard: u8) -> Result<(Pubkey, u8)> {
+    let program_id = program_id_string().parse()?;
+    Ok(renewal_queue_with_program_id(bucket, shard, &program_id))
+}
+
+/// Compute the `RenewalQueue` (crank index) PDA address only (without bump)
+///
+/// # Arguments
+/// * `bucket` - The renewal bucket index (see [`renewal_bucket`])
+/// * `shard` - The shard index within the bucket (see [`renewal_queue_shard`])
+///
+/// # Returns
+/// * `Ok(Pubkey)` - The PDA address
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn renewal_queue_address(bucket: u64, shard: u8) -> Result<Pubkey> {
+    let program_id = program_id_string().parse()?;
+    Ok(renewal_queue_address_with_program_id(bucket, shard, &program_id))
+}
+
+/// Compute the `RenewalQueue` (crank index) PDA with custom program ID
+///
+/// # Arguments
+/// * `bucket` - The renewal bucket index (see [`renewal_bucket`])
+/// * `shard` - The shard index within the bucket (see [`renewal_queue_shard`])
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `(Pubkey, u8)` - The PDA address and bump seed
+#[must_use]
+pub fn renewal_queue_with_program_id(bucket: u64, shard: u8, program_id: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"renewal_queue".as_ref(), &bucket.to_le_bytes(), &[shard]];
+    Pubkey::find_program_address(seeds, program_id)
+}
+
+/// Compute the `RenewalQueue` (crank index) PDA address only (without bump) with custom program ID
+///
+/// # Arguments
+/// * `bucket` - The renewal bucket index (see [`renewal_bucket`])
+/// * `shard` - The shard index within the bucket (see [`renewal_queue_shard`])
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `Pubkey` - The PDA address
+#[must_use]
+pub fn renewal_queue_address_with_program_id(bucket: u64, shard: u8, program_id: &Pubkey) -> Pubkey {
+    renewal_queue_with_program_id(bucket, shard, program_id).0
+}
+
+/// Compute the `RenewalQueue` PDA address of the shard indexing a payment agreement
+///
+/// # Arguments
+/// * `bucket` - The renewal bucket index (see [`renewal_bucket`])
+/// * `agreement` - The payment agreement PDA
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `Pubkey` - The PDA address
+#[must_use]
+pub fn agreement_renewal_queue_address_with_program_id(
+    bucket: u64,
+    agreement: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    renewal_queue_address_with_program_id(bucket, renewal_queue_shard(agreement), program_id)
+}
+
 /// Compute the Config PDA
 ///
 /// # Returns
@@ -365,6 +526,101 @@ pub fn delegate_address_with_program_id(program_id: &Pubkey) -> Pubkey {
     delegate_with_program_id(program_id).0
 }
 
+/// Compute the Keeper Fee Vault PDA
+///
+/// System-owned account funded by the platform with the SOL paid to keepers that
+/// take their fee in SOL.
+///
+/// # Returns
+/// * `Ok((Pubkey, u8))` - The PDA address and bump seed
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn keeper_fee_vault() -> Result<(Pubkey, u8)> {
+    let program_id = program_id_string().parse()?;
+    Ok(keeper_fee_vault_with_program_id(&program_id))
+}
+
+/// Compute the Keeper Fee Vault PDA address only (without bump)
+///
+/// # Returns
+/// * `Ok(Pubkey)` - The keeper fee vault PDA address
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn keeper_fee_vault_address() -> Result<Pubkey> {
+    let program_id = program_id_string().parse()?;
+    Ok(keeper_fee_vault_address_with_program_id(&program_id))
+}
+
+/// Compute the Keeper Fee Vault PDA with custom program ID
+///
+/// # Arguments
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `(Pubkey, u8)` - The PDA address and bump seed
+#[must_use]
+pub fn keeper_fee_vault_with_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"keeper_fee_vault" as &[u8]];
+    Pubkey::find_program_address(seeds, program_id)
+}
+
+/// Compute the Keeper Fee Vault PDA address only (without bump) with custom program ID
+///
+/// # Arguments
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `Pubkey` - The keeper fee vault PDA address
+#[must_use]
+pub fn keeper_fee_vault_address_with_program_id(program_id: &Pubkey) -> Pubkey {
+    keeper_fee_vault_with_program_id(program_id).0
+}
+
+/// Compute the Test Clock PDA
+///
+/// The account only exists on programs built with the `test-clock` feature.
+///
+/// # Returns
+/// * `Ok((Pubkey, u8))` - The PDA address and bump seed
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn test_clock() -> Result<(Pubkey, u8)> {
+    let program_id = program_id_string().parse()?;
+    Ok(test_clock_with_program_id(&program_id))
+}
+
+/// Compute the Test Clock PDA address only (without bump)
+///
+/// # Returns
+/// * `Ok(Pubkey)` - The test clock PDA address
+/// * `Err(TallyError)` - If PDA computation fails
+pub fn test_clock_address() -> Result<Pubkey> {
+    let program_id = program_id_string().parse()?;
+    Ok(test_clock_address_with_program_id(&program_id))
+}
+
+/// Compute the Test Clock PDA with custom program ID
+///
+/// # Arguments
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `(Pubkey, u8)` - The PDA address and bump seed
+#[must_use]
+pub fn test_clock_with_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"test_clock" as &[u8]];
+    Pubkey::find_program_address(seeds, program_id)
+}
+
+/// Compute the Test Clock PDA address only (without bump) with custom program ID
+///
+/// # Arguments
+/// * `program_id` - The program ID to use for PDA computation
+///
+/// # Returns
+/// * `Pubkey` - The test clock PDA address
+#[must_use]
+pub fn test_clock_address_with_program_id(program_id: &Pubkey) -> Pubkey {
+    test_clock_with_program_id(program_id).0
+}
+
 
 
 
@@ -403,6 +659,30 @@ mod tests {
         assert_ne!(terms_pda, terms_pda3);
     }
 
+    #[test]
+    fn test_renewal_queue_pda() {
+        let bucket = renewal_bucket(1_700_000_000).unwrap();
+        assert_eq!(bucket, 19_675);
+        assert_eq!(renewal_bucket(1_700_000_000 + RENEWAL_BUCKET_SECS), Some(bucket + 1));
+        assert_eq!(renewal_bucket(-1), Some(0));
+        assert_eq!(renewal_bucket(-RENEWAL_BUCKET_SECS), None);
+
+        let (queue_pda, _bump) = renewal_queue(bucket, 0).unwrap();
+        assert_eq!(renewal_queue_address(bucket, 0).unwrap(), queue_pda);
+        assert_ne!(renewal_queue_address(bucket + 1, 0).unwrap(), queue_pda);
+        assert_ne!(renewal_queue_address(bucket, 1).unwrap(), queue_pda);
+
+        let mut bytes = [0u8; 32];
+        bytes[0] = RENEWAL_QUEUE_SHARDS + 5;
+        let agreement = Pubkey::from(bytes);
+        assert_eq!(renewal_queue_shard(&agreement), 5);
+        let program_id = crate::program_id();
+        assert_eq!(
+            agreement_renewal_queue_address_with_program_id(bucket, &agreement, &program_id),
+            renewal_queue_address(bucket, 5).unwrap()
+        );
+    }
+
     #[test]
     fn test_payment_agreement_pda() {
         let payment_terms_pda = Pubkey::from(Keypair::new().pubkey().to_bytes());
@@ -420,6 +700,25 @@ mod tests {
         assert_ne!(agreement_pda, agreement_pda3);
     }
 
+    #[test]
+    fn test_slot_reservation_pda() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms_pda = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let reservation_pda =
+            slot_reservation_address_with_program_id(&payment_terms_pda, &payer, &program_id);
+
+        // Must not collide with the payer's agreement PDA for the same terms
+        let agreement_pda =
+            payment_agreement_address_with_program_id(&payment_terms_pda, &payer, &program_id);
+        assert_ne!(reservation_pda, agreement_pda);
+        assert_eq!(
+            reservation_pda,
+            slot_reservation_with_program_id(&payment_terms_pda, &payer, &program_id).0
+        );
+    }
+
     #[test]
     fn test_payment_terms_string_functions() {
         let payee = Pubkey::from(Keypair::new().pubkey().to_bytes());
@@ -518,6 +817,23 @@ mod tests {
         assert_eq!(delegate_pda, delegate_pda3);
     }
 
+    #[test]
+    fn test_keeper_fee_vault_pda() {
+        let (vault_pda, _bump) = keeper_fee_vault().unwrap();
+        assert_eq!(vault_pda, keeper_fee_vault_address().unwrap());
+
+        // Distinct from the other global PDAs
+        assert_ne!(vault_pda, delegate_address().unwrap());
+        assert_ne!(vault_pda, config_address().unwrap());
+    }
+
+    #[test]
+    fn test_test_clock_pda() {
+        let (test_clock_pda, _bump) = test_clock().unwrap();
+        assert_eq!(test_clock_pda, test_clock_address().unwrap());
+        assert_ne!(test_clock_pda, config_address().unwrap());
+    }
+
     #[test]
     fn test_program_id_from_env() {
         // Test requires TALLY_PROGRAM_ID to be set
@@ -0,0 +1,506 @@
+//! Preflight checks run before asking a payer to sign
+//!
+//! A `start_agreement` transaction that fails on-chain still costs the payer a wallet
+//! prompt and a network fee, and the resulting program error is hard to act on.
+//! [`check_start_agreement`] reads the accounts involved up front and reports every
+//! problem it finds as a [`PreflightIssue`], so a UI can tell the payer to top up USDC
+//! or SOL, create their token account, or manage an existing agreement instead.
+//!
+//! [`StartAgreementBuilder::validated`](crate::transaction_builder::StartAgreementBuilder::validated)
+//! runs the same check and fails early when any issue is found.
+
+#![forbid(unsafe_code)]
+
+use crate::ata::{get_associated_token_address_with_program, TokenProgram};
+use crate::error::{Result, TallyError};
+use crate::metrics::observe_rpc;
+use crate::program_types::{Config, Payee, PaymentAgreement, PaymentTerms};
+use crate::SimpleTallyClient;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use std::fmt;
+
+/// Network fee assumed per required signature, in lamports
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Account size of a new payment agreement, including the discriminator
+pub const PAYMENT_AGREEMENT_SPACE: usize = 259;
+
+/// A problem that would make `start_agreement` fail
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreflightIssue {
+    /// The payer has no USDC token account for the payee's mint
+    PayerTokenAccountMissing {
+        /// Expected associated token account address
+        address: Pubkey,
+    },
+    /// The payer's USDC balance does not cover the first charge
+    InsufficientUsdc {
+        /// Current balance in USDC micro-units
+        balance: u64,
+        /// First charge in USDC micro-units
+        required: u64,
+    },
+    /// The payer cannot pay rent for the agreement account plus the network fee
+    InsufficientSol {
+        /// Current balance in lamports
+        balance: u64,
+        /// Rent plus fees in lamports
+        required: u64,
+    },
+    /// The payer already has an active agreement for these terms
+    AgreementAlreadyActive {
+        /// Existing payment agreement address
+        address: Pubkey,
+    },
+    /// The payee has deactivated the terms
+    TermsDeactivated,
+    /// The terms have reached their subscriber cap
+    TermsFull,
+    /// The payee has been frozen by the platform authority
+    PayeeFrozen,
+    /// The program is paused
+    ProgramPaused,
+}
+
+impl fmt::Display for PreflightIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PayerTokenAccountMissing { address } => {
+                write!(f, "payer USDC token account {address} does not exist")
+            }
+            Self::InsufficientUsdc { balance, required } => write!(
+                f,
+                "insufficient USDC: balance {balance}, first charge {required} (micro-units)"
+            ),
+            Self::InsufficientSol { balance, required } => write!(
+                f,
+                "insufficient SOL: balance {balance}, rent and fees {required} (lamports)"
+            ),
+            Self::AgreementAlreadyActive { address } => {
+                write!(f, "payment agreement {address} is already active")
+            }
+            Self::TermsDeactivated => write!(f, "payment terms have been deactivated"),
+            Self::TermsFull => write!(f, "payment terms have reached their subscriber cap"),
+            Self::PayeeFrozen => write!(f, "payee is frozen"),
+            Self::ProgramPaused => write!(f, "program is paused"),
+        }
+    }
+}
+
+/// Result of [`check_start_agreement`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StartAgreementPreflight {
+    /// Payment agreement PDA the transaction would create or reactivate
+    pub payment_agreement: Pubkey,
+    /// Payer's USDC associated token account
+    pub payer_usdc_ata: Pubkey,
+    /// Payer's USDC balance (`None` if the token account does not exist)
+    pub usdc_balance: Option<u64>,
+    /// First charge in USDC micro-units (the undiscounted terms amount)
+    pub first_charge: u64,
+    /// Payer's SOL balance in lamports
+    pub sol_balance: u64,
+    /// Lamports needed for agreement rent (zero on reactivation) and network fees
+    pub sol_required: u64,
+    /// Whether an agreement account already exists (the transaction reactivates it)
+    pub reactivation: bool,
+    /// Problems found; empty if the transaction is expected to succeed
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl StartAgreementPreflight {
+    /// Returns true if no issues were found
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Convert the report into an error if any issue was found
+    ///
+    /// # Errors
+    /// Returns a `TallyError::Generic` listing every issue
+    pub fn into_result(self) -> Result<Self> {
+        if self.is_ok() {
+            return Ok(self);
+        }
+        let issues: Vec<String> = self.issues.iter().map(ToString::to_string).collect();
+        Err(TallyError::Generic(format!(
+            "start_agreement preflight failed: {}",
+            issues.join("; ")
+        )))
+    }
+}
+
+/// Account state read by [`check_start_agreement`]
+struct StartAgreementState<'a> {
+    config: &'a Config,
+    payee: &'a Payee,
+    terms: &'a PaymentTerms,
+    agreement: Option<&'a PaymentAgreement>,
+    usdc_balance: Option<u64>,
+    sol_balance: u64,
+    agreement_rent: u64,
+    now: i64,
+}
+
+/// Check whether `payer` can start an agreement on `payment_terms`
+///
+/// Reads the config, payee, terms, any existing agreement and the payer's balances,
+/// and reports the first charge, SOL requirement and every issue found. The first
+/// charge is the undiscounted terms amount, so token-gated discounts only make the
+/// check more conservative.
+///
+/// # Errors
+/// Returns an error if an RPC call fails or the config, payee or terms account is missing
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+pub fn check_start_agreement(
+    client: &SimpleTallyClient,
+    payer: &Pubkey,
+    payment_terms: &Pubkey,
+    token_program: TokenProgram,
+) -> Result<StartAgreementPreflight> {
+    let config = client
+        .get_config()?
+        .ok_or_else(|| TallyError::AccountNotFound("config".to_string()))?;
+    let terms = client
+        .get_payment_terms(payment_terms)?
+        .ok_or_else(|| TallyError::AccountNotFound(format!("payment terms {payment_terms}")))?;
+    let payee = client
+        .get_payee(&terms.payee)?
+        .ok_or_else(|| TallyError::AccountNotFound(format!("payee {}", terms.payee)))?;
+
+    let payment_agreement = crate::pda::payment_agreement_address_with_program_id(
+        payment_terms,
+        payer,
+        &client.program_id,
+    );
+    let agreement = client.get_payment_agreement(&payment_agreement)?;
+
+    let payer_usdc_ata =
+        get_associated_token_address_with_program(payer, &payee.usdc_mint, token_program)?;
+    let usdc_balance = if client.account_exists(&payer_usdc_ata)? {
+        let balance = observe_rpc("getTokenAccountBalance", || {
+            client
+                .rpc_client
+                .get_token_account_balance(&payer_usdc_ata)
+                .map_err(|e| TallyError::RpcError(format!("Failed to fetch USDC balance: {e}")))
+        })?;
+        Some(balance.amount.parse::<u64>().map_err(|e| {
+            TallyError::ParseError(format!("Invalid token amount {}: {e}", balance.amount))
+        })?)
+    } else {
+        None
+    };
+
+    let sol_balance = observe_rpc("getBalance", || {
+        client
+            .rpc_client
+            .get_balance(payer)
+            .map_err(|e| TallyError::RpcError(format!("Failed to fetch SOL balance: {e}")))
+    })?;
+    let agreement_rent = if agreement.is_some() {
+        0
+    } else {
+        observe_rpc("getMinimumBalanceForRentExemption", || {
+            client
+                .rpc_client
+                .get_minimum_balance_for_rent_exemption(PAYMENT_AGREEMENT_SPACE)
+                .map_err(|e| TallyError::RpcError(format!("Failed to fetch rent: {e}")))
+        })?
+    };
+
+    Ok(assess_start_agreement(
+        payment_agreement,
+        payer_usdc_ata,
+        &StartAgreementState {
+            config: &config,
+            payee: &payee,
+            terms: &terms,
+            agreement: agreement.as_ref(),
+            usdc_balance,
+            sol_balance,
+            agreement_rent,
+            now: chrono::Utc::now().timestamp(),
+        },
+    ))
+}
+
+fn assess_start_agreement(
+    payment_agreement: Pubkey,
+    payer_usdc_ata: Pubkey,
+    state: &StartAgreementState<'_>,
+) -> StartAgreementPreflight {
+    let mut issues = Vec::new();
+
+    if state.config.is_paused(state.now) {
+        issues.push(PreflightIssue::ProgramPaused);
+    }
+    if state.payee.frozen {
+        issues.push(PreflightIssue::PayeeFrozen);
+    }
+
+    let reactivation = state.agreement.is_some();
+    match state.agreement {
+        Some(agreement) if agreement.active => {
+            issues.push(PreflightIssue::AgreementAlreadyActive {
+                address: payment_agreement,
+            });
+        }
+        _ => {
+            if state.terms.sunset_ts.is_some() {
+                issues.push(PreflightIssue::TermsDeactivated);
+            }
+            if state
+                .terms
+                .max_subscribers
+                .is_some_and(|cap| state.terms.active_agreements >= cap)
+            {
+                issues.push(PreflightIssue::TermsFull);
+            }
+        }
+    }
+
+    let first_charge = state.terms.amount_usdc;
+    match state.usdc_balance {
+        None => issues.push(PreflightIssue::PayerTokenAccountMissing {
+            address: payer_usdc_ata,
+        }),
+        Some(balance) if balance < first_charge => issues.push(PreflightIssue::InsufficientUsdc {
+            balance,
+            required: first_charge,
+        }),
+        Some(_) => {}
+    }
+
+    let sol_required = state.agreement_rent.saturating_add(LAMPORTS_PER_SIGNATURE);
+    if state.sol_balance < sol_required {
+        issues.push(PreflightIssue::InsufficientSol {
+            balance: state.sol_balance,
+            required: sol_required,
+        });
+    }
+
+    StartAgreementPreflight {
+        payment_agreement,
+        payer_usdc_ata,
+        usdc_balance: state.usdc_balance,
+        first_charge,
+        sol_balance: state.sol_balance,
+        sol_required,
+        reactivation,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_types::VolumeTier;
+
+    const RENT: u64 = 2_004_480;
+
+    fn config() -> Config {
+        Config {
+            platform_authority: Pubkey::new_unique(),
+            pending_authority: None,
+            max_platform_fee_bps: 50,
+            min_platform_fee_bps: 10,
+            min_period_seconds: 86_400,
+            default_allowance_periods: 3,
+            allowed_mint: Pubkey::new_unique(),
+            max_withdrawal_amount: 1_000_000_000,
+            max_grace_period_seconds: 604_800,
+            paused: false,
+            keeper_fee_bps: 25,
+            pause_reason: [0; 64],
+            auto_unpause_ts: None,
+            keeper_sol_rate: None,
+            renewal_tolerance_secs: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn payee() -> Payee {
+        let authority = Pubkey::new_unique();
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn terms() -> PaymentTerms {
+        PaymentTerms {
+            payee: Pubkey::new_unique(),
+            terms_id: [0; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        }
+    }
+
+    fn agreement(active: bool) -> PaymentAgreement {
+        PaymentAgreement {
+            payment_terms: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            next_payment_ts: 0,
+            active,
+            payment_count: 1,
+            created_ts: 0,
+            last_amount: 10_000_000,
+            last_payment_ts: 0,
+            last_pull_period_index: 0,
+            cancel_at_period_end: false,
+            pending_payer: None,
+            refunded_amount: 0,
+            max_periods: None,
+            periods_paid: 1,
+            external_ref_hash: None,
+            paused_at_ts: None,
+            credit_amount: 0,
+            escrow: None,
+            period_index: 0,
+            suspension: None,
+            bump: 255,
+            deposit_held: 0,
+            version: 1,
+            last_sweep_ts: 0,
+        }
+    }
+
+    fn assess(
+        config: &Config,
+        payee: &Payee,
+        terms: &PaymentTerms,
+        agreement: Option<&PaymentAgreement>,
+        usdc_balance: Option<u64>,
+        sol_balance: u64,
+    ) -> StartAgreementPreflight {
+        assess_start_agreement(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &StartAgreementState {
+                config,
+                payee,
+                terms,
+                agreement,
+                usdc_balance,
+                sol_balance,
+                agreement_rent: if agreement.is_some() { 0 } else { RENT },
+                now: 1_700_000_000,
+            },
+        )
+    }
+
+    #[test]
+    fn test_funded_payer_passes() {
+        let report = assess(&config(), &payee(), &terms(), None, Some(10_000_000), 1_000_000_000);
+
+        assert!(report.is_ok());
+        assert!(!report.reactivation);
+        assert_eq!(report.first_charge, 10_000_000);
+        assert_eq!(report.sol_required, RENT + LAMPORTS_PER_SIGNATURE);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_funding_issue() {
+        let report = assess(&config(), &payee(), &terms(), None, Some(9_999_999), RENT);
+
+        assert_eq!(
+            report.issues,
+            vec![
+                PreflightIssue::InsufficientUsdc {
+                    balance: 9_999_999,
+                    required: 10_000_000,
+                },
+                PreflightIssue::InsufficientSol {
+                    balance: RENT,
+                    required: RENT + LAMPORTS_PER_SIGNATURE,
+                },
+            ]
+        );
+
+        let message = report.into_result().unwrap_err().to_string();
+        assert!(message.contains("insufficient USDC"));
+        assert!(message.contains("insufficient SOL"));
+    }
+
+    #[test]
+    fn test_missing_token_account() {
+        let report = assess(&config(), &payee(), &terms(), None, None, 1_000_000_000);
+
+        assert!(matches!(
+            report.issues.as_slice(),
+            [PreflightIssue::PayerTokenAccountMissing { address }] if *address == report.payer_usdc_ata
+        ));
+    }
+
+    #[test]
+    fn test_existing_agreement() {
+        let active = agreement(true);
+        let report = assess(&config(), &payee(), &terms(), Some(&active), Some(10_000_000), 10_000);
+        assert!(matches!(
+            report.issues.as_slice(),
+            [PreflightIssue::AgreementAlreadyActive { address }] if *address == report.payment_agreement
+        ));
+
+        // Reactivating an inactive agreement needs no rent
+        let inactive = agreement(false);
+        let report = assess(&config(), &payee(), &terms(), Some(&inactive), Some(10_000_000), 10_000);
+        assert!(report.is_ok());
+        assert!(report.reactivation);
+        assert_eq!(report.sol_required, LAMPORTS_PER_SIGNATURE);
+    }
+
+    #[test]
+    fn test_platform_and_payee_state() {
+        let mut config = config();
+        config.paused = true;
+        let mut payee = payee();
+        payee.frozen = true;
+        let mut terms = terms();
+        terms.max_subscribers = Some(5);
+        terms.active_agreements = 5;
+        terms.sunset_ts = Some(1_702_592_000);
+
+        let report = assess(&config, &payee, &terms, None, Some(10_000_000), 1_000_000_000);
+
+        assert_eq!(
+            report.issues,
+            vec![
+                PreflightIssue::ProgramPaused,
+                PreflightIssue::PayeeFrozen,
+                PreflightIssue::TermsDeactivated,
+                PreflightIssue::TermsFull,
+            ]
+        );
+    }
+}
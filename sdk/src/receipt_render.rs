@@ -0,0 +1,427 @@
+//! Customer-facing receipt documents rendered from [`TallyReceipt`]s
+//!
+//! [`create_receipt`](crate::create_receipt) produces structured data; this module turns
+//! it into a document a payee can send to its customer. [`ReceiptRenderer`] fills an
+//! HTML template with the payee's branding, the receipt's payment line items (amounts
+//! formatted from micro-USDC) and an explorer link for the transaction signature, and
+//! can also produce a single-page PDF with the same content.
+//!
+//! The default HTML template can be replaced with [`ReceiptRenderer::with_template`].
+//! Templates use `{{placeholder}}` markers; see [`DEFAULT_HTML_TEMPLATE`] for the full
+//! set. Every substituted value is HTML-escaped except `{{line_items}}` and `{{logo}}`,
+//! which the renderer builds from escaped parts.
+
+#![forbid(unsafe_code)]
+
+use crate::events::{TallyEvent, TallyReceipt};
+use crate::utils::format_usdc_grouped;
+use chrono::{DateTime, Utc};
+use std::fmt::Write as _;
+
+/// Explorer used for signature links
+pub const DEFAULT_EXPLORER_URL: &str = "https://explorer.solana.com";
+
+/// Default receipt HTML template
+pub const DEFAULT_HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Receipt from {{merchant_name}}</title>
+<style>
+body { font-family: -apple-system, Helvetica, Arial, sans-serif; color: #111827; max-width: 640px; margin: 40px auto; }
+header { border-bottom: 3px solid {{accent_color}}; padding-bottom: 16px; margin-bottom: 24px; }
+h1 { margin: 8px 0 0; font-size: 22px; }
+table { width: 100%; border-collapse: collapse; }
+td { padding: 8px 0; border-bottom: 1px solid #E5E7EB; }
+td.amount { text-align: right; font-variant-numeric: tabular-nums; }
+tr.total td { font-weight: bold; border-bottom: none; }
+.meta { color: #6B7280; font-size: 13px; }
+a { color: {{accent_color}}; }
+</style>
+</head>
+<body>
+<header>
+{{logo}}
+<h1>{{merchant_name}}</h1>
+<div class="meta">Receipt · {{date}} · {{status}}</div>
+</header>
+<table>
+{{line_items}}
+<tr class="total"><td>Total</td><td class="amount">{{total}} USDC</td></tr>
+</table>
+<p class="meta">Transaction <a href="{{explorer_url}}">{{signature}}</a></p>
+<p class="meta">{{footer}}</p>
+</body>
+</html>
+"#;
+
+/// Payee branding shown on rendered receipts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptBranding {
+    /// Name displayed as the receipt heading
+    pub merchant_name: String,
+    /// Optional logo image URL
+    pub logo_url: Option<String>,
+    /// CSS color used for the header rule and links
+    pub accent_color: String,
+    /// Optional footer text (support contact, legal notice, ...)
+    pub footer: Option<String>,
+}
+
+impl Default for ReceiptBranding {
+    fn default() -> Self {
+        Self {
+            merchant_name: "Tally".to_string(),
+            logo_url: None,
+            accent_color: "#4F46E5".to_string(),
+            footer: None,
+        }
+    }
+}
+
+/// A payment line on a rendered receipt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptLineItem {
+    /// Line description
+    pub description: String,
+    /// Amount in USDC micro-units
+    pub amount: u64,
+}
+
+/// Renders [`TallyReceipt`]s into HTML and PDF documents
+#[derive(Clone, Debug)]
+pub struct ReceiptRenderer {
+    branding: ReceiptBranding,
+    template: String,
+    explorer_url: String,
+    cluster: Option<String>,
+}
+
+impl ReceiptRenderer {
+    /// Create a renderer with the default template and Solana Explorer links
+    #[must_use]
+    pub fn new(branding: ReceiptBranding) -> Self {
+        Self {
+            branding,
+            template: DEFAULT_HTML_TEMPLATE.to_string(),
+            explorer_url: DEFAULT_EXPLORER_URL.to_string(),
+            cluster: None,
+        }
+    }
+
+    /// Use a custom HTML template
+    #[must_use]
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Use a different explorer for signature links (e.g. `https://solscan.io`)
+    #[must_use]
+    pub fn with_explorer_url(mut self, explorer_url: impl Into<String>) -> Self {
+        self.explorer_url = explorer_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Link signatures on a non-mainnet cluster (e.g. `devnet`)
+    #[must_use]
+    pub fn with_cluster(mut self, cluster: impl Into<String>) -> Self {
+        self.cluster = Some(cluster.into());
+        self
+    }
+
+    /// Explorer link for the receipt's transaction signature
+    #[must_use]
+    pub fn explorer_link(&self, receipt: &TallyReceipt) -> String {
+        let mut link = format!("{}/tx/{}", self.explorer_url, receipt.signature);
+        if let Some(cluster) = self.cluster.as_deref().filter(|c| *c != "mainnet-beta") {
+            let _ = write!(link, "?cluster={cluster}");
+        }
+        link
+    }
+
+    /// Render the receipt as an HTML document
+    #[must_use]
+    pub fn render_html(&self, receipt: &TallyReceipt) -> String {
+        let line_items = line_items(receipt);
+        let rows = line_items.iter().fold(String::new(), |mut rows, item| {
+            let _ = writeln!(
+                rows,
+                "<tr><td>{}</td><td class=\"amount\">{} USDC</td></tr>",
+                escape_html(&item.description),
+                format_usdc_grouped(item.amount)
+            );
+            rows
+        });
+        let logo = self.branding.logo_url.as_deref().map_or_else(String::new, |url| {
+            format!(
+                "<img src=\"{}\" alt=\"{}\" height=\"40\">",
+                escape_html(url),
+                escape_html(&self.branding.merchant_name)
+            )
+        });
+
+        self.template
+            .replace("{{line_items}}", rows.trim_end())
+            .replace("{{logo}}", &logo)
+            .replace("{{merchant_name}}", &escape_html(&self.branding.merchant_name))
+            .replace("{{accent_color}}", &escape_html(&self.branding.accent_color))
+            .replace("{{date}}", &escape_html(&format_block_time(receipt.block_time)))
+            .replace("{{status}}", status(receipt))
+            .replace("{{total}}", &format_usdc_grouped(total(&line_items)))
+            .replace("{{signature}}", &receipt.signature.to_string())
+            .replace("{{explorer_url}}", &escape_html(&self.explorer_link(receipt)))
+            .replace(
+                "{{footer}}",
+                &escape_html(self.branding.footer.as_deref().unwrap_or_default()),
+            )
+    }
+
+    /// Render the receipt as a single-page PDF document
+    ///
+    /// The PDF uses the built-in Helvetica font, so characters outside printable ASCII
+    /// are replaced with `?`.
+    #[must_use]
+    pub fn render_pdf(&self, receipt: &TallyReceipt) -> Vec<u8> {
+        let line_items = line_items(receipt);
+        let mut lines = vec![
+            (18, self.branding.merchant_name.clone()),
+            (
+                10,
+                format!("Receipt - {} - {}", format_block_time(receipt.block_time), status(receipt)),
+            ),
+            (10, String::new()),
+        ];
+        lines.extend(line_items.iter().map(|item| {
+            (12, format!("{}: {} USDC", item.description, format_usdc_grouped(item.amount)))
+        }));
+        lines.push((12, format!("Total: {} USDC", format_usdc_grouped(total(&line_items)))));
+        lines.push((10, String::new()));
+        lines.push((9, format!("Transaction: {}", receipt.signature)));
+        lines.push((9, self.explorer_link(receipt)));
+        if let Some(footer) = &self.branding.footer {
+            lines.push((9, footer.clone()));
+        }
+
+        write_pdf(&lines)
+    }
+}
+
+/// Payment line items contained in a receipt's events
+#[must_use]
+pub fn line_items(receipt: &TallyReceipt) -> Vec<ReceiptLineItem> {
+    receipt
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TallyEvent::PaymentAgreementStarted(e) => Some(ReceiptLineItem {
+                description: "Subscription started".to_string(),
+                amount: e.amount,
+            }),
+            TallyEvent::PaymentExecuted(e) => Some(ReceiptLineItem {
+                description: "Recurring payment".to_string(),
+                amount: e.amount,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn total(line_items: &[ReceiptLineItem]) -> u64 {
+    line_items
+        .iter()
+        .fold(0u64, |total, item| total.saturating_add(item.amount))
+}
+
+const fn status(receipt: &TallyReceipt) -> &'static str {
+    if receipt.success {
+        "Paid"
+    } else {
+        "Failed"
+    }
+}
+
+fn format_block_time(block_time: Option<i64>) -> String {
+    block_time
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+        .map_or_else(
+            || "Pending confirmation".to_string(),
+            |time| time.format("%Y-%m-%d %H:%M UTC").to_string(),
+        )
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_pdf_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+/// Write a one-page US Letter PDF with one text line per entry of `(font size, text)`
+fn write_pdf(lines: &[(u16, String)]) -> Vec<u8> {
+    let mut content = String::from("BT\n50 740 Td\n");
+    for (size, text) in lines {
+        let leading = u32::from(*size).saturating_mul(3) / 2;
+        let _ = writeln!(
+            content,
+            "/F1 {size} Tf\n({}) Tj\n0 -{leading} Td",
+            escape_pdf_text(text)
+        );
+    }
+    content.push_str("ET\n");
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+         /Resources << /Font << /F1 4 0 R >> >> /Contents 5 0 R >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        format!("<< /Length {} >>\nstream\n{content}endstream", content.len()),
+    ];
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = writeln!(pdf, "{} 0 obj\n{object}\nendobj", index.saturating_add(1));
+    }
+
+    let xref_offset = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len().saturating_add(1));
+    for offset in offsets {
+        let _ = writeln!(pdf, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+        objects.len().saturating_add(1)
+    );
+
+    pdf.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{PaymentAgreementStarted, PaymentExecuted};
+    use anchor_client::solana_sdk::signature::Signature;
+    use anchor_lang::prelude::Pubkey;
+
+    fn receipt() -> TallyReceipt {
+        TallyReceipt {
+            signature: Signature::from([7u8; 64]),
+            block_time: Some(1_700_000_000),
+            slot: 42,
+            success: true,
+            error: None,
+            events: vec![
+                TallyEvent::PaymentAgreementStarted(PaymentAgreementStarted {
+                    payee: Pubkey::new_unique(),
+                    payment_terms: Pubkey::new_unique(),
+                    payer: Pubkey::new_unique(),
+                    amount: 10_000_000,
+                    external_ref_hash: None,
+                    delegated_amount: 0,
+                }),
+                TallyEvent::PaymentExecuted(PaymentExecuted {
+                    payee: Pubkey::new_unique(),
+                    payment_terms: Pubkey::new_unique(),
+                    payer: Pubkey::new_unique(),
+                    amount: 2_500_000,
+                    keeper: Pubkey::new_unique(),
+                    keeper_fee: 6_250,
+                    period_index: 1,
+                }),
+            ],
+            logs: Vec::new(),
+            compute_units_consumed: None,
+            fee: 5_000,
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn test_render_html_with_branding() {
+        let renderer = ReceiptRenderer::new(ReceiptBranding {
+            merchant_name: "Acme <Pro> & Co".to_string(),
+            logo_url: Some("https://acme.example/logo.png".to_string()),
+            footer: Some("Questions? support@acme.example".to_string()),
+            ..ReceiptBranding::default()
+        })
+        .with_cluster("devnet");
+        let receipt = receipt();
+
+        let html = renderer.render_html(&receipt);
+
+        assert!(html.contains("<h1>Acme &lt;Pro&gt; &amp; Co</h1>"));
+        assert!(html.contains("<img src=\"https://acme.example/logo.png\""));
+        assert!(html.contains("<td>Subscription started</td><td class=\"amount\">10.00 USDC</td>"));
+        assert!(html.contains("<td>Recurring payment</td><td class=\"amount\">2.50 USDC</td>"));
+        assert!(html.contains("<td class=\"amount\">12.50 USDC</td>"));
+        assert!(html.contains("2023-11-14 22:13 UTC"));
+        assert!(html.contains(&format!(
+            "https://explorer.solana.com/tx/{}?cluster=devnet",
+            receipt.signature
+        )));
+        assert!(html.contains("Questions? support@acme.example"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn test_custom_template_and_explorer() {
+        let renderer = ReceiptRenderer::new(ReceiptBranding::default())
+            .with_template("{{merchant_name}}|{{total}}|{{status}}|{{explorer_url}}")
+            .with_explorer_url("https://solscan.io/")
+            .with_cluster("mainnet-beta");
+        let mut receipt = receipt();
+        receipt.success = false;
+
+        assert_eq!(
+            renderer.render_html(&receipt),
+            format!("Tally|12.50|Failed|https://solscan.io/tx/{}", receipt.signature)
+        );
+    }
+
+    #[test]
+    fn test_render_pdf_structure() {
+        let renderer = ReceiptRenderer::new(ReceiptBranding {
+            merchant_name: "Café (Paris)".to_string(),
+            ..ReceiptBranding::default()
+        });
+        let pdf = String::from_utf8(renderer.render_pdf(&receipt())).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(Caf? \\(Paris\\)) Tj"));
+        assert!(pdf.contains("(Total: 12.50 USDC) Tj"));
+
+        // startxref points at the cross-reference table
+        let startxref = pdf.rsplit("startxref\n").next().unwrap();
+        let offset: usize = startxref.lines().next().unwrap().parse().unwrap();
+        assert!(pdf[offset..].starts_with("xref\n0 6\n"));
+    }
+}
@@ -0,0 +1,395 @@
+//! Rate-limited concurrent RPC execution for bulk account scans
+//!
+//! Scanning thousands of agreements one request at a time is slow, while firing them
+//! all at once trips RPC provider rate limits. `BoundedExecutor` runs RPC calls on a
+//! bounded number of worker threads, paces them with a token bucket, and backs off
+//! exponentially when the provider answers with HTTP 429.
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use crate::metrics::observe_rpc;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Maximum number of accounts the RPC accepts in a single `getMultipleAccounts` call
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Configuration for a [`BoundedExecutor`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundedExecutorConfig {
+    /// Maximum number of RPC calls in flight at once
+    pub max_concurrency: usize,
+    /// Sustained request rate (requests per second)
+    pub requests_per_second: u32,
+    /// Number of requests that may be sent back-to-back before pacing applies
+    pub burst: u32,
+    /// Retries after a rate-limited (429) response before giving up
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled on each subsequent retry
+    pub initial_backoff: Duration,
+    /// Upper bound on a single backoff
+    pub max_backoff: Duration,
+}
+
+impl Default for BoundedExecutorConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 8,
+            requests_per_second: 40,
+            burst: 10,
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+/// Token bucket implemented as a generic cell rate algorithm
+///
+/// Tracks the theoretical arrival time of the next request; callers reserve a slot
+/// under the lock and sleep outside of it, so waiting workers don't serialize.
+#[derive(Debug)]
+struct TokenBucket {
+    interval: Duration,
+    tolerance: Duration,
+    next_arrival: Mutex<Option<Instant>>,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: u32, burst: u32) -> Self {
+        let interval = Duration::from_secs(1)
+            .checked_div(requests_per_second.max(1))
+            .unwrap_or_default();
+        let tolerance = interval.saturating_mul(burst.max(1).saturating_sub(1));
+        Self {
+            interval,
+            tolerance,
+            next_arrival: Mutex::new(None),
+        }
+    }
+
+    /// Reserve the next request slot, returning how long the caller must wait for it
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut next_arrival = self
+            .next_arrival
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let arrival = next_arrival.map_or(now, |next| next.max(now));
+        let allowed_at = arrival.checked_sub(self.tolerance).unwrap_or(now);
+        *next_arrival = arrival.checked_add(self.interval);
+        drop(next_arrival);
+        allowed_at.saturating_duration_since(now)
+    }
+
+    fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+    }
+}
+
+/// Whether an error is an RPC provider rate limit response (HTTP 429)
+#[must_use]
+pub fn is_rate_limited(error: &TallyError) -> bool {
+    let message = error.to_string();
+    message.contains("429") || message.contains("Too Many Requests")
+}
+
+/// Whether an error is worth retrying: a rate limit, a timeout, a failed connection
+/// or an unavailable (502, 503, 504) endpoint
+#[must_use]
+pub fn is_transient(error: &TallyError) -> bool {
+    let message = error.to_string().to_ascii_lowercase();
+    is_rate_limited(error)
+        || [
+            "timed out",
+            "timeout",
+            "error sending request",
+            "connection refused",
+            "connection reset",
+            "502 bad gateway",
+            "503 service unavailable",
+            "504 gateway timeout",
+        ]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Runs RPC calls with bounded concurrency, rate limiting and 429 backoff
+#[derive(Debug)]
+pub struct BoundedExecutor {
+    config: BoundedExecutorConfig,
+    bucket: TokenBucket,
+}
+
+impl Default for BoundedExecutor {
+    fn default() -> Self {
+        Self::new(BoundedExecutorConfig::default())
+    }
+}
+
+impl BoundedExecutor {
+    /// Create an executor with the given configuration
+    #[must_use]
+    pub fn new(config: BoundedExecutorConfig) -> Self {
+        let bucket = TokenBucket::new(config.requests_per_second, config.burst);
+        Self { config, bucket }
+    }
+
+    /// Get the executor configuration
+    #[must_use]
+    pub const fn config(&self) -> &BoundedExecutorConfig {
+        &self.config
+    }
+
+    /// Backoff before retry number `attempt` (zero-based)
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.config
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.config.max_backoff)
+    }
+
+    /// Run a single RPC call, waiting for a rate limit token and retrying on 429
+    ///
+    /// # Errors
+    /// Returns the call's error if it is not a rate limit, or the last rate limit
+    /// error once `max_retries` is exhausted
+    pub fn execute<T>(&self, mut call: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            self.bucket.acquire();
+            match call() {
+                Err(e) if is_rate_limited(&e) && attempt < self.config.max_retries => {
+                    let backoff = self.backoff(attempt);
+                    debug!(
+                        service = "tally-sdk",
+                        component = "rpc_exec",
+                        event = "rate_limited",
+                        attempt = attempt,
+                        backoff_ms = u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX),
+                        "RPC rate limited, backing off"
+                    );
+                    std::thread::sleep(backoff);
+                    attempt = attempt.saturating_add(1);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Apply `call` to every item concurrently, returning results in input order
+    ///
+    /// At most `max_concurrency` calls run at once and each goes through
+    /// [`execute`](Self::execute). Remaining items are skipped after the first error.
+    ///
+    /// # Errors
+    /// Returns the first error produced by any call
+    pub fn map<I, T, F>(&self, items: &[I], call: F) -> Result<Vec<T>>
+    where
+        I: Sync,
+        T: Send,
+        F: Fn(&I) -> Result<T> + Sync,
+    {
+        let workers = self.config.max_concurrency.clamp(1, items.len().max(1));
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+
+        let batches: Vec<Vec<(usize, Result<T>)>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        while !failed.load(Ordering::Relaxed) {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = items.get(index) else { break };
+                            let result = self.execute(|| call(item));
+                            if result.is_err() {
+                                failed.store(true, Ordering::Relaxed);
+                            }
+                            results.push((index, result));
+                        }
+                        results
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+
+        let mut results: Vec<(usize, Result<T>)> = batches.into_iter().flatten().collect();
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Fetch accounts with `getMultipleAccounts`, batched 100 addresses per call
+    ///
+    /// Batches run concurrently through the executor. The returned vector lines up with
+    /// `addresses`; missing accounts are `None`.
+    ///
+    /// # Errors
+    /// Returns an error if any batch fails after retries
+    pub fn get_multiple_accounts(
+        &self,
+        rpc_client: &RpcClient,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<Account>>> {
+        let batches: Vec<&[Pubkey]> = addresses.chunks(MAX_MULTIPLE_ACCOUNTS).collect();
+        let accounts = self.map(&batches, |batch| {
+            observe_rpc("getMultipleAccounts", || {
+                rpc_client.get_multiple_accounts(batch).map_err(|e| {
+                    TallyError::Generic(format!("Failed to fetch multiple accounts: {e}"))
+                })
+            })
+        })?;
+        Ok(accounts.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn fast_config() -> BoundedExecutorConfig {
+        BoundedExecutorConfig {
+            max_concurrency: 4,
+            requests_per_second: 10_000,
+            burst: 100,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        }
+    }
+
+    #[test]
+    fn test_token_bucket_allows_burst_then_paces() {
+        let bucket = TokenBucket::new(10, 3);
+        let now = Instant::now();
+
+        // First three requests fit in the burst
+        for _ in 0..3 {
+            assert_eq!(bucket.reserve(now), Duration::ZERO);
+        }
+        // The fourth waits one interval, the fifth two
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let executor = BoundedExecutor::new(BoundedExecutorConfig {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            ..BoundedExecutorConfig::default()
+        });
+        assert_eq!(executor.backoff(0), Duration::from_millis(100));
+        assert_eq!(executor.backoff(1), Duration::from_millis(200));
+        assert_eq!(executor.backoff(2), Duration::from_millis(400));
+        assert_eq!(executor.backoff(3), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_execute_retries_rate_limited_calls() {
+        let executor = BoundedExecutor::new(fast_config());
+        let calls = AtomicU32::new(0);
+
+        let result = executor.execute(|| {
+            if calls.fetch_add(1, Ordering::Relaxed) < 2 {
+                Err(TallyError::Generic("HTTP status client error (429 Too Many Requests)".to_string()))
+            } else {
+                Ok(7)
+            }
+        });
+
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_execute_gives_up_after_max_retries() {
+        let executor = BoundedExecutor::new(fast_config());
+        let calls = AtomicU32::new(0);
+
+        let result: Result<()> = executor.execute(|| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(TallyError::Generic("429 Too Many Requests".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::Relaxed), 4); // initial call + 3 retries
+    }
+
+    #[test]
+    fn test_execute_does_not_retry_other_errors() {
+        let executor = BoundedExecutor::new(fast_config());
+        let calls = AtomicU32::new(0);
+
+        let result: Result<()> = executor.execute(|| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(TallyError::Generic("account not found".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_is_transient() {
+        for message in [
+            "HTTP status client error (429 Too Many Requests)",
+            "error sending request for url (http://localhost:8899/): operation timed out",
+            "HTTP status server error (503 Service Unavailable)",
+        ] {
+            assert!(is_transient(&TallyError::RpcError(message.to_string())), "{message}");
+        }
+        assert!(!is_transient(&TallyError::Generic("account not found".to_string())));
+    }
+
+    #[test]
+    fn test_map_preserves_order_and_bounds_concurrency() {
+        let executor = BoundedExecutor::new(fast_config());
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<u32> = (0..50).collect();
+
+        let results = executor
+            .map(&items, |item| {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst).saturating_add(1);
+                peak.fetch_max(current, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(1));
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(item.saturating_mul(2))
+            })
+            .unwrap();
+
+        assert_eq!(results, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[test]
+    fn test_map_returns_first_error() {
+        let executor = BoundedExecutor::new(fast_config());
+        let items: Vec<u32> = (0..20).collect();
+
+        let result = executor.map(&items, |item| {
+            if *item == 5 {
+                Err(TallyError::Generic("boom".to_string()))
+            } else {
+                Ok(*item)
+            }
+        });
+
+        assert!(result.is_err());
+        assert!(executor.map(&Vec::<u32>::new(), |item| Ok(*item)).unwrap().is_empty());
+    }
+}
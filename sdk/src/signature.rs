@@ -122,6 +122,9 @@ pub fn verify_wallet_signature(wallet_address: &str, signature: &str, message: &
 /// - Base58-encoded signatures (88 characters)
 /// - Signature objects with toString method
 ///
+/// For signatures pasted by people, such as explorer links or values wrapped in
+/// quotes, use [`crate::input::parse_signature`] in lenient mode instead.
+///
 /// # Arguments
 ///
 /// * `signature_input` - Signature in various formats from frontend wallets
@@ -2,10 +2,14 @@
 
 use crate::{
     error::{Result, TallyError},
+    metrics::{observe_rpc, observe_transaction},
     program_id_string,
-    program_types::{Payee, PaymentTerms, PaymentAgreement},
+    program_types::{
+        decode_versioned_account, Payee, PaymentTerms, PaymentAgreement, RenewalQueue, VersionedAccount,
+    },
+    rpc_exec::is_transient,
 };
-use anchor_client::solana_account_decoder::UiAccountEncoding;
+use anchor_client::solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use anchor_client::solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
 use anchor_client::solana_client::rpc_client::RpcClient;
 use anchor_client::solana_client::rpc_config::{
@@ -18,6 +22,76 @@ use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::{signature::Signer, transaction::Transaction};
 use anchor_lang::AnchorDeserialize;
 use std::str::FromStr;
+use std::time::Duration;
+
+/// Upper bound on a single backoff between RPC retries
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Timeout and retry policy for the RPC calls of a [`SimpleTallyClient`]
+///
+/// Only reads are retried, and only on transient errors (timeouts, rate limits,
+/// unavailable endpoints); transaction submission is never retried. Once the retries
+/// are used up the call fails with [`TallyError::RpcRetriesExhausted`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientOptions {
+    /// Timeout of a single RPC request
+    pub timeout: Duration,
+    /// Retries after a transient failure
+    pub retries: u32,
+    /// Backoff before the first retry, doubled on each subsequent retry
+    pub retry_backoff: Duration,
+    /// Compare the SDK's discriminators with the deployed program's IDL when the
+    /// client is created, failing with [`TallyError::DiscriminatorMismatch`]
+    pub verify_discriminators: bool,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            retries: 2,
+            retry_backoff: Duration::from_millis(500),
+            verify_discriminators: false,
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Set the timeout of a single RPC request
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the number of retries after a transient failure
+    #[must_use]
+    pub const fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set the backoff before the first retry
+    #[must_use]
+    pub const fn retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Verify the SDK's discriminators against the deployed program on startup
+    #[must_use]
+    pub const fn verify_discriminators(mut self, verify_discriminators: bool) -> Self {
+        self.verify_discriminators = verify_discriminators;
+        self
+    }
+
+    /// Backoff before retry number `attempt` (zero-based)
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RETRY_BACKOFF)
+    }
+}
 
 /// Simple Tally client for basic operations
 pub struct SimpleTallyClient {
@@ -25,6 +99,8 @@ pub struct SimpleTallyClient {
     pub rpc_client: RpcClient,
     /// Program ID
     pub program_id: Pubkey,
+    /// Timeout and retry policy
+    pub options: ClientOptions,
 }
 
 impl SimpleTallyClient {
@@ -39,14 +115,10 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if the program ID cannot be parsed or client creation fails
     pub fn new(cluster_url: &str) -> Result<Self> {
-        let rpc_client = RpcClient::new_with_commitment(cluster_url, CommitmentConfig::confirmed());
         let program_id = Pubkey::from_str(&program_id_string())
             .map_err(|e| TallyError::Generic(format!("Invalid program ID: {e}")))?;
 
-        Ok(Self {
-            rpc_client,
-            program_id,
-        })
+        Ok(Self::with_options(cluster_url, program_id, ClientOptions::default()))
     }
 
     /// Create a new simple Tally client with custom program ID
@@ -61,14 +133,103 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if the program ID cannot be parsed or client creation fails
     pub fn new_with_program_id(cluster_url: &str, program_id: &str) -> Result<Self> {
-        let rpc_client = RpcClient::new_with_commitment(cluster_url, CommitmentConfig::confirmed());
+        Self::new_with_options(cluster_url, program_id, ClientOptions::default())
+    }
+
+    /// Create a new simple Tally client with a custom timeout and retry policy
+    ///
+    /// # Arguments
+    /// * `cluster_url` - RPC endpoint URL
+    /// * `program_id` - Program ID to use
+    /// * `options` - Timeout and retry policy for RPC calls
+    ///
+    /// # Errors
+    /// Returns an error if the program ID cannot be parsed, or if
+    /// [`ClientOptions::verify_discriminators`] is set and the check fails or finds
+    /// mismatches
+    pub fn new_with_options(
+        cluster_url: &str,
+        program_id: &str,
+        options: ClientOptions,
+    ) -> Result<Self> {
         let program_id = Pubkey::from_str(program_id)
             .map_err(|e| TallyError::Generic(format!("Invalid program ID '{program_id}': {e}")))?;
 
-        Ok(Self {
+        let client = Self::with_options(cluster_url, program_id, options);
+        if options.verify_discriminators {
+            let report = client.check_discriminators()?;
+            if !report.is_ok() {
+                return Err(TallyError::DiscriminatorMismatch(report));
+            }
+        }
+        Ok(client)
+    }
+
+    fn with_options(cluster_url: &str, program_id: Pubkey, options: ClientOptions) -> Self {
+        let rpc_client = RpcClient::new_with_timeout_and_commitment(
+            cluster_url,
+            options.timeout,
+            CommitmentConfig::confirmed(),
+        );
+
+        Self {
             rpc_client,
             program_id,
-        })
+            options,
+        }
+    }
+
+    /// Run a read-only RPC call under the client's retry policy
+    ///
+    /// Transient failures are retried with exponential backoff; other errors are
+    /// returned immediately.
+    fn rpc_call<T>(&self, method: &'static str, mut call: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut attempt: u32 = 0;
+        loop {
+            match observe_rpc(method, &mut call) {
+                Err(e) if is_transient(&e) => {
+                    if attempt >= self.options.retries {
+                        return Err(TallyError::RpcRetriesExhausted {
+                            method: method.to_string(),
+                            attempts: attempt.saturating_add(1),
+                            last_error: e.to_string(),
+                        });
+                    }
+                    let backoff = self.options.backoff(attempt);
+                    tracing::debug!(
+                        service = "tally-sdk",
+                        component = "simple_client",
+                        event = "rpc_retry",
+                        method = method,
+                        attempt = attempt,
+                        backoff_ms = u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX),
+                        "Transient RPC failure, retrying"
+                    );
+                    std::thread::sleep(backoff);
+                    attempt = attempt.saturating_add(1);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Compare the SDK's instruction and event discriminators with the IDL the
+    /// deployed program published on chain
+    ///
+    /// # Errors
+    /// Returns an error if the IDL account can't be fetched or decoded
+    pub fn check_discriminators(&self) -> Result<crate::verify::DiscriminatorReport> {
+        let address = crate::verify::idl_address(&self.program_id);
+        let account = self
+            .rpc_call("getAccountInfo", || {
+                self.rpc_client
+                    .get_account_with_commitment(&address, CommitmentConfig::confirmed())
+                    .map_err(|e| TallyError::RpcError(format!("Failed to fetch IDL account: {e}")))
+            })?
+            .value
+            .ok_or_else(|| TallyError::AccountNotFound(format!("IDL account {address}")))?;
+
+        crate::verify::check_idl_account(&account.data)
     }
 
     /// Get the program ID
@@ -93,9 +254,11 @@ impl SimpleTallyClient {
     /// Returns an error if the RPC call to check account existence fails
     pub fn account_exists(&self, address: &Pubkey) -> Result<bool> {
         // First try with confirmed commitment
-        match self
-            .rpc_client
-            .get_account_with_commitment(address, CommitmentConfig::confirmed())
+        match observe_rpc("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(address, CommitmentConfig::confirmed())
+                .map_err(Box::new)
+        })
         {
             Ok(response) => match response.value {
                 Some(_) => Ok(true),
@@ -103,9 +266,11 @@ impl SimpleTallyClient {
             },
             Err(e) => {
                 // If confirmed fails, try with processed commitment (more recent but less reliable)
-                match self
-                    .rpc_client
-                    .get_account_with_commitment(address, CommitmentConfig::processed())
+                match observe_rpc("getAccountInfo", || {
+                    self.rpc_client
+                        .get_account_with_commitment(address, CommitmentConfig::processed())
+                        .map_err(Box::new)
+                })
                 {
                     Ok(response) => match response.value {
                         Some(_) => Ok(true),
@@ -124,10 +289,11 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if the account doesn't exist or can't be deserialized
     pub fn get_payee(&self, payee_address: &Pubkey) -> Result<Option<Payee>> {
-        let account_data = match self
-            .rpc_client
-            .get_account_with_commitment(payee_address, CommitmentConfig::confirmed())
-            .map_err(|e| TallyError::Generic(format!("Failed to fetch payee account: {e}")))?
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(payee_address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch payee account: {e}")))
+        })?
             .value
         {
             Some(account) => account.data,
@@ -140,8 +306,7 @@ impl SimpleTallyClient {
             ));
         }
 
-        let payee = Payee::try_from_slice(&account_data[8..])
-            .map_err(|e| TallyError::Generic(format!("Failed to deserialize payee: {e}")))?;
+        let payee = decode_versioned_account::<Payee>(&account_data[8..])?;
 
         Ok(Some(payee))
     }
@@ -151,10 +316,11 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if the account doesn't exist or can't be deserialized
     pub fn get_payment_terms(&self, payment_terms_address: &Pubkey) -> Result<Option<PaymentTerms>> {
-        let account_data = match self
-            .rpc_client
-            .get_account_with_commitment(payment_terms_address, CommitmentConfig::confirmed())
-            .map_err(|e| TallyError::Generic(format!("Failed to fetch payment terms account: {e}")))?
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(payment_terms_address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch payment terms account: {e}")))
+        })?
             .value
         {
             Some(account) => account.data,
@@ -165,8 +331,7 @@ impl SimpleTallyClient {
             return Err(TallyError::Generic("Invalid payment terms account data".to_string()));
         }
 
-        let payment_terms = PaymentTerms::try_from_slice(&account_data[8..])
-            .map_err(|e| TallyError::Generic(format!("Failed to deserialize payment terms: {e}")))?;
+        let payment_terms = decode_versioned_account::<PaymentTerms>(&account_data[8..])?;
 
         Ok(Some(payment_terms))
     }
@@ -178,10 +343,11 @@ impl SimpleTallyClient {
     pub fn get_config(&self) -> Result<Option<crate::program_types::Config>> {
         let config_address = crate::pda::config_address_with_program_id(&self.program_id);
 
-        let account_data = match self
-            .rpc_client
-            .get_account_with_commitment(&config_address, CommitmentConfig::confirmed())
-            .map_err(|e| TallyError::Generic(format!("Failed to fetch config account: {e}")))?
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(&config_address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch config account: {e}")))
+        })?
             .value
         {
             Some(account) => account.data,
@@ -192,21 +358,62 @@ impl SimpleTallyClient {
             return Err(TallyError::Generic("Invalid config account data".to_string()));
         }
 
-        let config = crate::program_types::Config::try_from_slice(&account_data[8..])
-            .map_err(|e| TallyError::Generic(format!("Failed to deserialize config: {e}")))?;
+        let config = decode_versioned_account::<crate::program_types::Config>(&account_data[8..])?;
 
         Ok(Some(config))
     }
 
+    /// Get raw account data together with the slot the RPC node read it at
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails
+    pub(crate) fn get_account_data_with_slot(
+        &self,
+        address: &Pubkey,
+    ) -> Result<(u64, Option<Vec<u8>>)> {
+        let response = self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch account {address}: {e}")))
+        })?;
+        Ok((
+            response.context.slot,
+            response.value.map(|account| account.data),
+        ))
+    }
+
+    /// Get an address lookup table for use with `alt::VersionedTransactionBuilder`
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the account is not a lookup table
+    pub fn get_address_lookup_table(
+        &self,
+        address: &Pubkey,
+    ) -> Result<Option<anchor_client::solana_sdk::message::AddressLookupTableAccount>> {
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch lookup table account: {e}")))
+        })?
+            .value
+        {
+            Some(account) => account.data,
+            None => return Ok(None),
+        };
+
+        crate::alt::decode_lookup_table(*address, &account_data).map(Some)
+    }
+
     /// Get payment agreement account data
     ///
     /// # Errors
     /// Returns an error if the account doesn't exist or can't be deserialized
     pub fn get_payment_agreement(&self, payment_agreement_address: &Pubkey) -> Result<Option<PaymentAgreement>> {
-        let account_data = match self
-            .rpc_client
-            .get_account_with_commitment(payment_agreement_address, CommitmentConfig::confirmed())
-            .map_err(|e| TallyError::Generic(format!("Failed to fetch payment agreement account: {e}")))?
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(payment_agreement_address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch payment agreement account: {e}")))
+        })?
             .value
         {
             Some(account) => account.data,
@@ -219,12 +426,103 @@ impl SimpleTallyClient {
             ));
         }
 
-        let payment_agreement = PaymentAgreement::try_from_slice(&account_data[8..])
-            .map_err(|e| TallyError::Generic(format!("Failed to deserialize payment agreement: {e}")))?;
+        let payment_agreement = decode_versioned_account::<PaymentAgreement>(&account_data[8..])?;
 
         Ok(Some(payment_agreement))
     }
 
+    /// Get one shard of the renewal queue (crank index) for a time bucket
+    ///
+    /// Returns `None` if no agreement has been indexed under the shard yet. Listed
+    /// agreements may have been paused or canceled since, so callers should re-check
+    /// each one before executing it.
+    ///
+    /// # Errors
+    /// Returns an error if the account can't be fetched or deserialized
+    pub fn get_renewal_queue(&self, bucket: u64, shard: u8) -> Result<Option<RenewalQueue>> {
+        let queue_address =
+            crate::pda::renewal_queue_address_with_program_id(bucket, shard, &self.program_id);
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(&queue_address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch renewal queue account: {e}")))
+        })?
+            .value
+        {
+            Some(account) => account.data,
+            None => return Ok(None),
+        };
+
+        decode_renewal_queue(&account_data).map(Some)
+    }
+
+    /// Get every existing shard of the renewal queue (crank index) for a time bucket
+    ///
+    /// Reads all `RENEWAL_QUEUE_SHARDS` shards in one `getMultipleAccounts` call and
+    /// skips shards no agreement has been indexed under yet.
+    ///
+    /// # Errors
+    /// Returns an error if the accounts can't be fetched or deserialized
+    pub fn get_renewal_queues(&self, bucket: u64) -> Result<Vec<RenewalQueue>> {
+        let queue_addresses: Vec<Pubkey> = (0..crate::pda::RENEWAL_QUEUE_SHARDS)
+            .map(|shard| {
+                crate::pda::renewal_queue_address_with_program_id(bucket, shard, &self.program_id)
+            })
+            .collect();
+        let accounts = self.rpc_call("getMultipleAccounts", || {
+            self.rpc_client
+                .get_multiple_accounts(&queue_addresses)
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch renewal queue accounts: {e}")))
+        })?;
+
+        accounts
+            .into_iter()
+            .flatten()
+            .filter(|account| account.owner == self.program_id)
+            .map(|account| decode_renewal_queue(&account.data))
+            .collect()
+    }
+
+    /// List all payee accounts of the program
+    ///
+    /// # Errors
+    /// Returns an error if the RPC query fails
+    pub fn list_payees(&self) -> Result<Vec<(Pubkey, Payee)>> {
+        let filters = vec![account_filter::<Payee>()];
+
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: None,
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: Some(false),
+            sort_results: None,
+        };
+
+        let payee_accounts = self.rpc_call("getProgramAccounts", || {
+            self.rpc_client
+                .get_program_accounts_with_config(&self.program_id, config.clone())
+                .map_err(|e| TallyError::Generic(format!("Failed to query payee accounts: {e}")))
+        })?;
+
+        let mut payees = Vec::new();
+        for (pubkey, account) in payee_accounts {
+            if account.data.len() < 8 {
+                continue;
+            }
+
+            if let Ok(payee) = decode_versioned_account::<Payee>(&account.data[8..]) {
+                payees.push((pubkey, payee));
+            }
+            // Skip invalid accounts
+        }
+
+        Ok(payees)
+    }
+
     /// List all payment terms for a payee
     ///
     /// # Errors
@@ -234,7 +532,7 @@ impl SimpleTallyClient {
         // PaymentTerms account layout: 8 bytes discriminator + PaymentTerms struct
         // PaymentTerms struct: payee (32 bytes) at offset 8
         let filters = vec![
-            RpcFilterType::DataSize(129), // Filter by PaymentTerms account size
+            account_filter::<PaymentTerms>(),
             RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
                 8,
                 payee_address.to_bytes().to_vec(),
@@ -253,10 +551,11 @@ impl SimpleTallyClient {
             sort_results: None,
         };
 
-        let payment_terms_accounts = self
-            .rpc_client
-            .get_program_accounts_with_config(&self.program_id, config)
-            .map_err(|e| TallyError::Generic(format!("Failed to query payment terms accounts: {e}")))?;
+        let payment_terms_accounts = self.rpc_call("getProgramAccounts", || {
+            self.rpc_client
+                .get_program_accounts_with_config(&self.program_id, config.clone())
+                .map_err(|e| TallyError::Generic(format!("Failed to query payment terms accounts: {e}")))
+        })?;
 
         let mut payment_terms_list = Vec::new();
         for (pubkey, account) in payment_terms_accounts {
@@ -264,7 +563,7 @@ impl SimpleTallyClient {
                 continue;
             }
 
-            if let Ok(payment_terms) = PaymentTerms::try_from_slice(&account.data[8..]) {
+            if let Ok(payment_terms) = decode_versioned_account::<PaymentTerms>(&account.data[8..]) {
                 payment_terms_list.push((pubkey, payment_terms));
             }
             // Skip invalid accounts
@@ -282,7 +581,7 @@ impl SimpleTallyClient {
         // PaymentAgreement account layout: 8 bytes discriminator + PaymentAgreement struct
         // PaymentAgreement struct: payment_terms (32 bytes) at offset 8
         let filters = vec![
-            RpcFilterType::DataSize(105), // Filter by PaymentAgreement account size (8 + 32 + 32 + 8 + 8 + 8 + 8 + 1)
+            account_filter::<PaymentAgreement>(),
             RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, payment_terms_address.to_bytes().to_vec())),
         ];
 
@@ -298,12 +597,13 @@ impl SimpleTallyClient {
             sort_results: None,
         };
 
-        let payment_agreement_accounts = self
-            .rpc_client
-            .get_program_accounts_with_config(&self.program_id, config)
-            .map_err(|e| {
-                TallyError::Generic(format!("Failed to query payment agreement accounts: {e}"))
-            })?;
+        let payment_agreement_accounts = self.rpc_call("getProgramAccounts", || {
+            self.rpc_client
+                .get_program_accounts_with_config(&self.program_id, config.clone())
+                .map_err(|e| {
+                    TallyError::Generic(format!("Failed to query payment agreement accounts: {e}"))
+                })
+        })?;
 
         let mut payment_agreements = Vec::new();
         for (pubkey, account) in payment_agreement_accounts {
@@ -311,7 +611,7 @@ impl SimpleTallyClient {
                 continue;
             }
 
-            if let Ok(payment_agreement) = PaymentAgreement::try_from_slice(&account.data[8..]) {
+            if let Ok(payment_agreement) = decode_versioned_account::<PaymentAgreement>(&account.data[8..]) {
                 payment_agreements.push((pubkey, payment_agreement));
             }
             // Skip invalid accounts
@@ -320,34 +620,93 @@ impl SimpleTallyClient {
         Ok(payment_agreements)
     }
 
+    /// List the addresses of all payment agreements for payment terms without their data
+    ///
+    /// Uses a zero-length data slice so large scans transfer only account keys; fetch the
+    /// data afterwards with batched `getMultipleAccounts` calls (see `rpc_exec`).
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails
+    pub fn list_payment_agreement_addresses(&self, payment_terms_address: &Pubkey) -> Result<Vec<Pubkey>> {
+        let filters = vec![
+            account_filter::<PaymentAgreement>(),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, payment_terms_address.to_bytes().to_vec())),
+        ];
+
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                }),
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: Some(false),
+            sort_results: None,
+        };
+
+        let accounts = self.rpc_call("getProgramAccounts", || {
+            self.rpc_client
+                .get_program_accounts_with_config(&self.program_id, config.clone())
+                .map_err(|e| {
+                    TallyError::Generic(format!("Failed to query payment agreement addresses: {e}"))
+                })
+        })?;
+
+        Ok(accounts.into_iter().map(|(pubkey, _)| pubkey).collect())
+    }
+
     /// Submit and confirm a transaction
     ///
     /// # Errors
     /// Returns an error if transaction submission or confirmation fails
+    #[tracing::instrument(
+        name = "send_transaction",
+        skip_all,
+        fields(program_id = %self.program_id, signature),
+        err
+    )]
     pub fn submit_transaction<T: Signer>(
         &self,
         transaction: &mut Transaction,
         signers: &[&T],
     ) -> Result<String> {
         // Get recent blockhash
-        let recent_blockhash = self
-            .rpc_client
-            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
-            .map_err(|e| TallyError::Generic(format!("Failed to get recent blockhash: {e}")))?
+        let recent_blockhash = self.rpc_call("getLatestBlockhash", || {
+            self.rpc_client
+                .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to get recent blockhash: {e}")))
+        })?
             .0;
 
         // Sign transaction
         transaction.sign(signers, recent_blockhash);
 
         // Submit and confirm transaction
-        let signature = self
-            .rpc_client
-            .send_and_confirm_transaction_with_spinner(transaction)
-            .map_err(|e| TallyError::Generic(format!("Transaction failed: {e}")))?;
+        let signature = observe_transaction(|| {
+            self.rpc_client
+                .send_and_confirm_transaction_with_spinner(transaction)
+                .map_err(|e| TallyError::Generic(format!("Transaction failed: {e}")))
+        })?;
+        tracing::Span::current().record("signature", tracing::field::display(&signature));
 
         Ok(signature.to_string())
     }
 
+    /// Create a confirmation tracker using websocket signature subscriptions
+    ///
+    /// The websocket URL is derived from the RPC endpoint; polling is used as a
+    /// fallback when the websocket is unavailable.
+    ///
+    /// # Errors
+    /// Returns an error if the websocket URL cannot be derived from the RPC URL
+    pub fn confirmation_tracker(&self) -> Result<crate::confirmation::ConfirmationTracker<'_>> {
+        crate::confirmation::ConfirmationTracker::new(&self.rpc_client)
+    }
+
     /// Submit instruction with automatic transaction handling
     ///
     /// # Errors
@@ -367,10 +726,12 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if RPC call fails
     pub fn get_latest_blockhash(&self) -> Result<anchor_client::solana_sdk::hash::Hash> {
-        self.rpc_client
-            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
-            .map(|(hash, _slot)| hash)
-            .map_err(|e| TallyError::Generic(format!("Failed to get latest blockhash: {e}")))
+        self.rpc_call("getLatestBlockhash", || {
+            self.rpc_client
+                .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+                .map(|(hash, _slot)| hash)
+                .map_err(|e| TallyError::Generic(format!("Failed to get latest blockhash: {e}")))
+        })
     }
 
     /// Get latest blockhash with commitment
@@ -381,9 +742,11 @@ impl SimpleTallyClient {
         &self,
         commitment: CommitmentConfig,
     ) -> Result<(anchor_client::solana_sdk::hash::Hash, u64)> {
-        self.rpc_client
-            .get_latest_blockhash_with_commitment(commitment)
-            .map_err(|e| TallyError::Generic(format!("Failed to get latest blockhash: {e}")))
+        self.rpc_call("getLatestBlockhash", || {
+            self.rpc_client
+                .get_latest_blockhash_with_commitment(commitment)
+                .map_err(|e| TallyError::Generic(format!("Failed to get latest blockhash: {e}")))
+        })
     }
 
     /// High-level method to create a payee account
@@ -603,6 +966,58 @@ impl SimpleTallyClient {
         self.submit_instruction(instruction, &[platform_authority])
     }
 
+    /// Get the test clock of a program built with the `test-clock` feature
+    ///
+    /// Returns `None` until the clock has first been advanced, and always on programs
+    /// built without the feature.
+    ///
+    /// # Errors
+    /// Returns an error if the account can't be fetched or deserialized
+    #[cfg(feature = "test-clock")]
+    pub fn get_test_clock(&self) -> Result<Option<crate::program_types::TestClock>> {
+        let test_clock_address = crate::pda::test_clock_address_with_program_id(&self.program_id);
+
+        let account_data = match self.rpc_call("getAccountInfo", || {
+            self.rpc_client
+                .get_account_with_commitment(&test_clock_address, CommitmentConfig::confirmed())
+                .map_err(|e| TallyError::Generic(format!("Failed to fetch test clock account: {e}")))
+        })?
+            .value
+        {
+            Some(account) => account.data,
+            None => return Ok(None),
+        };
+
+        if account_data.len() < 8 {
+            return Err(TallyError::Generic("Invalid test clock account data".to_string()));
+        }
+
+        let test_clock = crate::program_types::TestClock::try_from_slice(&account_data[8..])
+            .map_err(|e| TallyError::Generic(format!("Failed to deserialize test clock: {e}")))?;
+
+        Ok(Some(test_clock))
+    }
+
+    /// High-level method to move the test clock forward by `secs`
+    ///
+    /// Renewals executed with [`ExecutePaymentBuilder::test_clock`] then see the cluster
+    /// time shifted by the accumulated offset.
+    ///
+    /// [`ExecutePaymentBuilder::test_clock`]: crate::transaction_builder::ExecutePaymentBuilder::test_clock
+    ///
+    /// # Errors
+    /// Returns an error if `secs` is zero or the transaction fails
+    #[cfg(feature = "test-clock")]
+    pub fn advance_test_clock<T: Signer>(&self, platform_authority: &T, secs: u64) -> Result<String> {
+        let instruction = crate::transaction_builder::advance_test_clock()
+            .platform_authority(platform_authority.pubkey())
+            .secs(secs)
+            .program_id(self.program_id)
+            .build_instruction()?;
+
+        self.submit_instruction(instruction, &[platform_authority])
+    }
+
     /// Get confirmed signatures for a program address
     ///
     /// # Errors
@@ -612,13 +1027,28 @@ impl SimpleTallyClient {
         address: &Pubkey,
         config: Option<GetConfirmedSignaturesForAddress2Config>,
     ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
-        self.rpc_client
-            .get_signatures_for_address_with_config(address, config.unwrap_or_default())
-            .map_err(|e| {
-                TallyError::Generic(format!(
-                    "Failed to get signatures for address {address}: {e}"
-                ))
-            })
+        // The config is consumed by each attempt and is not `Clone`
+        let GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit,
+            commitment,
+        } = config.unwrap_or_default();
+        self.rpc_call("getSignaturesForAddress", || {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit,
+                commitment,
+            };
+            self.rpc_client
+                .get_signatures_for_address_with_config(address, config)
+                .map_err(|e| {
+                    TallyError::Generic(format!(
+                        "Failed to get signatures for address {address}: {e}"
+                    ))
+                })
+        })
     }
 
     /// Get transaction details
@@ -629,10 +1059,12 @@ impl SimpleTallyClient {
         &self,
         signature: &anchor_client::solana_sdk::signature::Signature,
     ) -> Result<serde_json::Value> {
-        self.rpc_client
-            .get_transaction_with_config(signature, RpcTransactionConfig::default())
-            .map(|tx| serde_json::to_value(tx).unwrap_or_default())
-            .map_err(|e| TallyError::Generic(format!("Failed to get transaction {signature}: {e}")))
+        self.rpc_call("getTransaction", || {
+            self.rpc_client
+                .get_transaction_with_config(signature, RpcTransactionConfig::default())
+                .map(|tx| serde_json::to_value(tx).unwrap_or_default())
+                .map_err(|e| TallyError::Generic(format!("Failed to get transaction {signature}: {e}")))
+        })
     }
 
     /// Get multiple transactions in batch
@@ -649,9 +1081,11 @@ impl SimpleTallyClient {
 
         for chunk in signatures.chunks(CHUNK_SIZE) {
             for signature in chunk {
-                let transaction_result = self
-                    .rpc_client
-                    .get_transaction_with_config(signature, RpcTransactionConfig::default());
+                let transaction_result = observe_rpc("getTransaction", || {
+                    self.rpc_client
+                        .get_transaction_with_config(signature, RpcTransactionConfig::default())
+                        .map_err(Box::new)
+                });
                 match transaction_result {
                     Ok(tx) => results.push(Some(serde_json::to_value(tx).unwrap_or_default())),
                     Err(_) => results.push(None), // Transaction not found or other error
@@ -666,13 +1100,21 @@ impl SimpleTallyClient {
     ///
     /// # Errors
     /// Returns an error if transaction submission or confirmation fails
+    #[tracing::instrument(
+        name = "send_transaction",
+        skip_all,
+        fields(program_id = %self.program_id, signature = ?transaction.signatures.first()),
+        err
+    )]
     pub fn send_and_confirm_transaction(
         &self,
         transaction: &anchor_client::solana_sdk::transaction::VersionedTransaction,
     ) -> Result<anchor_client::solana_sdk::signature::Signature> {
-        self.rpc_client
-            .send_and_confirm_transaction(transaction)
-            .map_err(|e| TallyError::Generic(format!("Transaction submission failed: {e}")))
+        observe_transaction(|| {
+            self.rpc_client
+                .send_and_confirm_transaction(transaction)
+                .map_err(|e| TallyError::Generic(format!("Transaction submission failed: {e}")))
+        })
     }
 
     /// Get current slot
@@ -680,9 +1122,23 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if RPC call fails
     pub fn get_slot(&self) -> Result<u64> {
-        self.rpc_client
-            .get_slot()
-            .map_err(|e| TallyError::Generic(format!("Failed to get slot: {e}")))
+        self.rpc_call("getSlot", || {
+            self.rpc_client
+                .get_slot()
+                .map_err(|e| TallyError::Generic(format!("Failed to get slot: {e}")))
+        })
+    }
+
+    /// Get the estimated production time of a block (Unix timestamp)
+    ///
+    /// # Errors
+    /// Returns an error if RPC call fails or the node has no time for the slot
+    pub fn get_block_time(&self, slot: u64) -> Result<i64> {
+        self.rpc_call("getBlockTime", || {
+            self.rpc_client
+                .get_block_time(slot)
+                .map_err(|e| TallyError::Generic(format!("Failed to get block time for slot {slot}: {e}")))
+        })
     }
 
     /// Get health status
@@ -690,12 +1146,33 @@ impl SimpleTallyClient {
     /// # Errors
     /// Returns an error if RPC call fails
     pub fn get_health(&self) -> Result<()> {
-        self.rpc_client
-            .get_health()
-            .map_err(|e| TallyError::Generic(format!("Health check failed: {e}")))
+        self.rpc_call("getHealth", || {
+            self.rpc_client
+                .get_health()
+                .map_err(|e| TallyError::Generic(format!("Health check failed: {e}")))
+        })
     }
 }
 
+/// `getProgramAccounts` filter matching accounts of type `T`
+///
+/// Matches the account discriminator rather than the data size, so accounts of every
+/// layout version are found, whether or not they have been migrated.
+pub(crate) fn account_filter<T: VersionedAccount>() -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, T::DISCRIMINATOR.to_vec()))
+}
+
+/// Decode a `RenewalQueue` account, skipping its discriminator
+///
+/// The account is sized for a full queue, so unused capacity trails the data.
+fn decode_renewal_queue(account_data: &[u8]) -> Result<RenewalQueue> {
+    let mut data = account_data
+        .get(8..)
+        .ok_or_else(|| TallyError::Generic("Invalid renewal queue account data".to_string()))?;
+    RenewalQueue::deserialize(&mut data)
+        .map_err(|e| TallyError::Generic(format!("Failed to deserialize renewal queue: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -705,4 +1182,83 @@ mod tests {
         let client = SimpleTallyClient::new("http://localhost:8899").unwrap();
         assert_eq!(client.program_id().to_string(), program_id_string());
     }
+
+    fn client(retries: u32) -> SimpleTallyClient {
+        let options = ClientOptions::default()
+            .retries(retries)
+            .retry_backoff(Duration::from_millis(1));
+        SimpleTallyClient::new_with_options(
+            "http://localhost:8899",
+            "11111111111111111111111111111111",
+            options,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let options = ClientOptions::default().retry_backoff(Duration::from_secs(4));
+        assert_eq!(options.backoff(0), Duration::from_secs(4));
+        assert_eq!(options.backoff(2), Duration::from_secs(16));
+        assert_eq!(options.backoff(5), MAX_RETRY_BACKOFF);
+    }
+
+    #[test]
+    fn test_rpc_call_retries_transient_errors() {
+        let mut attempts = 0u32;
+        let result = client(2).rpc_call("getSlot", || {
+            attempts = attempts.saturating_add(1);
+            if attempts < 3 {
+                Err(TallyError::RpcError("operation timed out".to_string()))
+            } else {
+                Ok(42)
+            }
+        });
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_rpc_call_reports_exhausted_retries() {
+        let result: Result<u64> = client(1).rpc_call("getSlot", || {
+            Err(TallyError::RpcError("503 Service Unavailable".to_string()))
+        });
+
+        assert!(matches!(
+            result,
+            Err(TallyError::RpcRetriesExhausted { ref method, attempts: 2, .. }) if method == "getSlot"
+        ));
+    }
+
+    #[test]
+    fn test_rpc_call_does_not_retry_permanent_errors() {
+        let mut attempts = 0u32;
+        let result: Result<u64> = client(3).rpc_call("getAccountInfo", || {
+            attempts = attempts.saturating_add(1);
+            Err(TallyError::Generic("Failed to deserialize payee".to_string()))
+        });
+
+        assert!(matches!(result, Err(TallyError::Generic(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_startup_discriminator_check_is_optional() {
+        let options = ClientOptions::default()
+            .timeout(Duration::from_millis(200))
+            .retries(0);
+        assert!(!options.verify_discriminators);
+
+        // Without the check no RPC call is made, so an unreachable endpoint is fine
+        let url = "http://127.0.0.1:1";
+        let program_id = "11111111111111111111111111111111";
+        assert!(SimpleTallyClient::new_with_options(url, program_id, options).is_ok());
+        assert!(SimpleTallyClient::new_with_options(
+            url,
+            program_id,
+            options.verify_discriminators(true)
+        )
+        .is_err());
+    }
 }
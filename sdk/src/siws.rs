@@ -0,0 +1,757 @@
+//! Sign-In-With-Solana (SIWS) messages for wallet-authenticated sessions
+//!
+//! Payee backends use this module to authenticate payers before showing billing pages:
+//! issue a nonce, build a [`SiwsMessage`] for the wallet to sign, then check the signed
+//! text with a [`SiwsVerifier`]. The message text follows the wallet-standard
+//! `signIn` format, so messages produced by browser wallets parse and verify as-is:
+//!
+//! ```text
+//! billing.example.com wants you to sign in with your Solana account:
+//! 7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU
+//!
+//! Sign in to manage your subscription
+//!
+//! URI: https://billing.example.com/login
+//! Version: 1
+//! Chain ID: mainnet
+//! Nonce: 32891756
+//! Issued At: 2024-01-01T00:00:00.000Z
+//! Expiration Time: 2024-01-01T00:10:00.000Z
+//! ```
+//!
+//! Replay protection is delegated to a [`NonceStore`], which the verifier asks to
+//! consume the message nonce once everything else checks out. [`InMemoryNonceStore`]
+//! covers single-process backends; multi-instance deployments should implement the
+//! trait over their shared store.
+
+#![forbid(unsafe_code)]
+
+use crate::{error::Result, signature::verify_wallet_signature, TallyError};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Message format version emitted in the `Version` field
+pub const SIWS_VERSION: &str = "1";
+
+/// Length of nonces produced by [`generate_nonce`]
+pub const NONCE_LENGTH: usize = 16;
+
+/// Minimum nonce length accepted in messages
+const MIN_NONCE_LENGTH: usize = 8;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+
+/// Generate a random alphanumeric nonce for a sign-in message
+#[must_use]
+pub fn generate_nonce() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(NONCE_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// A Sign-In-With-Solana message
+///
+/// [`fmt::Display`] produces the canonical text the wallet signs and [`FromStr`] parses
+/// it back. Parsing rejects text that does not re-serialize byte for byte, so a
+/// verified message has exactly one interpretation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiwsMessage {
+    /// Domain requesting the sign-in (host, optionally with port)
+    pub domain: String,
+    /// Wallet signing in
+    pub address: Pubkey,
+    /// Human-readable statement shown to the user
+    pub statement: Option<String>,
+    /// URI of the resource the sign-in is for
+    pub uri: Option<String>,
+    /// Message format version, always [`SIWS_VERSION`] for built messages
+    pub version: String,
+    /// Cluster the session is for (e.g. `mainnet`, `devnet`)
+    pub chain_id: Option<String>,
+    /// Single-use value issued by the backend
+    pub nonce: String,
+    /// When the message was created
+    pub issued_at: DateTime<Utc>,
+    /// When the message stops being valid
+    pub expiration_time: Option<DateTime<Utc>>,
+    /// When the message becomes valid
+    pub not_before: Option<DateTime<Utc>>,
+    /// Backend request identifier
+    pub request_id: Option<String>,
+    /// Resources the user is granting access to
+    pub resources: Vec<String>,
+}
+
+impl SiwsMessage {
+    /// Create a new SIWS message builder
+    #[must_use]
+    pub fn builder() -> SiwsMessageBuilder {
+        SiwsMessageBuilder::default()
+    }
+
+    /// Check that the message is valid at `now`, allowing `max_clock_skew` of drift
+    ///
+    /// # Errors
+    /// Returns an error if the message was issued in the future, has expired, or is
+    /// not valid yet
+    pub fn check_time(&self, now: DateTime<Utc>, max_clock_skew: Duration) -> Result<()> {
+        let earliest = now.checked_add_signed(max_clock_skew).unwrap_or(now);
+        let latest = now.checked_sub_signed(max_clock_skew).unwrap_or(now);
+
+        if self.issued_at > earliest {
+            return Err(TallyError::SiwsVerification(
+                "Message issued in the future".to_string(),
+            ));
+        }
+        if self
+            .expiration_time
+            .is_some_and(|expiration| expiration <= latest)
+        {
+            return Err(TallyError::SiwsVerification(
+                "Message has expired".to_string(),
+            ));
+        }
+        if self
+            .not_before
+            .is_some_and(|not_before| not_before > earliest)
+        {
+            return Err(TallyError::SiwsVerification(
+                "Message is not valid yet".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+impl fmt::Display for SiwsMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{HEADER_SUFFIX}\n{}", self.domain, self.address)?;
+        if let Some(statement) = &self.statement {
+            write!(f, "\n\n{statement}")?;
+        }
+
+        write!(f, "\n\n")?;
+        if let Some(uri) = &self.uri {
+            writeln!(f, "URI: {uri}")?;
+        }
+        writeln!(f, "Version: {}", self.version)?;
+        if let Some(chain_id) = &self.chain_id {
+            writeln!(f, "Chain ID: {chain_id}")?;
+        }
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", format_timestamp(&self.issued_at))?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(
+                f,
+                "\nExpiration Time: {}",
+                format_timestamp(expiration_time)
+            )?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", format_timestamp(not_before))?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| TallyError::SiwsVerification(format!("Invalid {field}: {e}")))
+}
+
+impl FromStr for SiwsMessage {
+    type Err = TallyError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let malformed =
+            |reason: &str| TallyError::SiwsVerification(format!("Malformed message: {reason}"));
+
+        let mut sections = text.split("\n\n");
+        let preamble = sections.next().ok_or_else(|| malformed("empty message"))?;
+        let (header, address) = preamble
+            .split_once('\n')
+            .ok_or_else(|| malformed("missing address"))?;
+        let domain = header
+            .strip_suffix(HEADER_SUFFIX)
+            .ok_or_else(|| malformed("missing header"))?;
+        let address = Pubkey::from_str(address).map_err(|_| malformed("invalid address"))?;
+
+        let (statement, fields) = match (sections.next(), sections.next(), sections.next()) {
+            (Some(fields), None, None) => (None, fields),
+            (Some(statement), Some(fields), None) => (Some(statement.to_string()), fields),
+            _ => return Err(malformed("unexpected sections")),
+        };
+
+        let mut values: HashMap<&str, &str> = HashMap::new();
+        let mut resources = Vec::new();
+        let mut lines = fields.lines();
+        while let Some(line) = lines.next() {
+            if line == "Resources:" {
+                for resource in lines.by_ref() {
+                    let resource = resource
+                        .strip_prefix("- ")
+                        .ok_or_else(|| malformed("invalid resource"))?;
+                    resources.push(resource.to_string());
+                }
+                break;
+            }
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| malformed("invalid field"))?;
+            if values.insert(key, value).is_some() {
+                return Err(malformed("duplicate field"));
+            }
+        }
+
+        let mut take = |key: &str| values.remove(key).map(str::to_string);
+        let message = Self {
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri: take("URI"),
+            version: take("Version").ok_or_else(|| malformed("missing version"))?,
+            chain_id: take("Chain ID"),
+            nonce: take("Nonce").ok_or_else(|| malformed("missing nonce"))?,
+            issued_at: parse_timestamp(
+                "issued at",
+                &take("Issued At").ok_or_else(|| malformed("missing issued at"))?,
+            )?,
+            expiration_time: take("Expiration Time")
+                .map(|value| parse_timestamp("expiration time", &value))
+                .transpose()?,
+            not_before: take("Not Before")
+                .map(|value| parse_timestamp("not before", &value))
+                .transpose()?,
+            request_id: take("Request ID"),
+            resources,
+        };
+
+        if let Some(key) = values.keys().next() {
+            return Err(malformed(&format!("unknown field {key}")));
+        }
+        if message.to_string() != text {
+            return Err(malformed("not in canonical form"));
+        }
+        Ok(message)
+    }
+}
+
+/// Builder for [`SiwsMessage`]
+#[derive(Clone, Debug, Default)]
+pub struct SiwsMessageBuilder {
+    domain: Option<String>,
+    address: Option<Pubkey>,
+    statement: Option<String>,
+    uri: Option<String>,
+    chain_id: Option<String>,
+    nonce: Option<String>,
+    issued_at: Option<DateTime<Utc>>,
+    expiration_time: Option<DateTime<Utc>>,
+    not_before: Option<DateTime<Utc>>,
+    request_id: Option<String>,
+    resources: Vec<String>,
+}
+
+impl SiwsMessageBuilder {
+    /// Set the domain requesting the sign-in
+    #[must_use]
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Set the wallet signing in
+    #[must_use]
+    pub const fn address(mut self, address: Pubkey) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Set the statement shown to the user
+    #[must_use]
+    pub fn statement(mut self, statement: impl Into<String>) -> Self {
+        self.statement = Some(statement.into());
+        self
+    }
+
+    /// Set the URI of the resource the sign-in is for
+    #[must_use]
+    pub fn uri(mut self, uri: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Set the cluster the session is for
+    #[must_use]
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Set the nonce issued by the backend (see [`NonceStore`])
+    #[must_use]
+    pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Set when the message was created (defaults to now)
+    #[must_use]
+    pub const fn issued_at(mut self, issued_at: DateTime<Utc>) -> Self {
+        self.issued_at = Some(issued_at);
+        self
+    }
+
+    /// Set when the message stops being valid
+    #[must_use]
+    pub const fn expiration_time(mut self, expiration_time: DateTime<Utc>) -> Self {
+        self.expiration_time = Some(expiration_time);
+        self
+    }
+
+    /// Set when the message becomes valid
+    #[must_use]
+    pub const fn not_before(mut self, not_before: DateTime<Utc>) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    /// Set the backend request identifier
+    #[must_use]
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Add a resource the user is granting access to
+    #[must_use]
+    pub fn resource(mut self, resource: impl Into<String>) -> Self {
+        self.resources.push(resource.into());
+        self
+    }
+
+    /// Build the message
+    ///
+    /// # Errors
+    /// Returns an error if the domain, address or nonce is not set, the domain contains
+    /// whitespace, the nonce is shorter than 8 alphanumeric characters, a field spans
+    /// multiple lines, or the expiration time is not after the issue time
+    pub fn build(self) -> Result<SiwsMessage> {
+        let domain = self.domain.ok_or("Domain not set")?;
+        let address = self.address.ok_or("Address not set")?;
+        let nonce = self.nonce.ok_or("Nonce not set")?;
+        let issued_at = self.issued_at.unwrap_or_else(Utc::now);
+
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err("Domain must be non-empty and contain no whitespace".into());
+        }
+        if nonce.len() < MIN_NONCE_LENGTH || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err("Nonce must be at least 8 alphanumeric characters".into());
+        }
+        let single_line = [&self.statement, &self.uri, &self.chain_id, &self.request_id]
+            .into_iter()
+            .flatten()
+            .chain(&self.resources)
+            .all(|value| !value.is_empty() && !value.contains('\n'));
+        if !single_line {
+            return Err("Message fields must be non-empty single lines".into());
+        }
+        if self
+            .expiration_time
+            .is_some_and(|expiration| expiration <= issued_at)
+        {
+            return Err("Expiration time must be after issued at".into());
+        }
+
+        Ok(SiwsMessage {
+            domain,
+            address,
+            statement: self.statement,
+            uri: self.uri,
+            version: SIWS_VERSION.to_string(),
+            chain_id: self.chain_id,
+            nonce,
+            issued_at,
+            expiration_time: self.expiration_time,
+            not_before: self.not_before,
+            request_id: self.request_id,
+            resources: self.resources,
+        })
+    }
+}
+
+/// Replay protection hook for [`SiwsVerifier`]
+///
+/// The verifier calls [`NonceStore::consume`] only after the signature and every
+/// other check passed, so rejected attempts don't burn the nonce.
+pub trait NonceStore {
+    /// Mark `nonce` as used by `address`, returning whether it could be used
+    ///
+    /// Return `false` for nonces that were never issued, have expired, or were already
+    /// consumed. Shared stores should make the check-and-mark atomic.
+    ///
+    /// # Errors
+    /// Returns an error if the backing store cannot be reached
+    fn consume(&self, nonce: &str, address: &Pubkey) -> Result<bool>;
+}
+
+/// Process-local [`NonceStore`] with per-nonce expiry
+#[derive(Debug)]
+pub struct InMemoryNonceStore {
+    ttl: Duration,
+    issued: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl InMemoryNonceStore {
+    /// Create a store whose nonces can be consumed for `ttl` after being issued
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            issued: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issue a fresh nonce, dropping expired ones
+    ///
+    /// # Errors
+    /// Returns an error if the nonce map lock is poisoned
+    pub fn issue(&self) -> Result<String> {
+        let now = Utc::now();
+        let expires_at = now.checked_add_signed(self.ttl).unwrap_or(now);
+        let nonce = generate_nonce();
+
+        {
+            let mut issued = self
+                .issued
+                .lock()
+                .map_err(|_| TallyError::Generic("Nonce store lock poisoned".to_string()))?;
+            issued.retain(|_, expiry| *expiry > now);
+            issued.insert(nonce.clone(), expires_at);
+        }
+        Ok(nonce)
+    }
+}
+
+impl NonceStore for InMemoryNonceStore {
+    fn consume(&self, nonce: &str, _address: &Pubkey) -> Result<bool> {
+        let mut issued = self
+            .issued
+            .lock()
+            .map_err(|_| TallyError::Generic("Nonce store lock poisoned".to_string()))?;
+        Ok(issued
+            .remove(nonce)
+            .is_some_and(|expires_at| expires_at > Utc::now()))
+    }
+}
+
+/// Verifies signed SIWS messages for one domain
+#[derive(Debug)]
+pub struct SiwsVerifier<S> {
+    domain: String,
+    chain_id: Option<String>,
+    max_clock_skew: Duration,
+    nonce_store: S,
+}
+
+impl<S: NonceStore> SiwsVerifier<S> {
+    /// Create a verifier accepting messages for `domain`
+    ///
+    /// Allows 60 seconds of clock skew between the wallet and the backend.
+    pub fn new(domain: impl Into<String>, nonce_store: S) -> Self {
+        Self {
+            domain: domain.into(),
+            chain_id: None,
+            max_clock_skew: Duration::seconds(60),
+            nonce_store,
+        }
+    }
+
+    /// Require messages to name this cluster
+    #[must_use]
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Set the clock skew tolerated for time checks
+    #[must_use]
+    pub const fn max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = max_clock_skew;
+        self
+    }
+
+    /// The nonce store used for replay protection
+    pub const fn nonce_store(&self) -> &S {
+        &self.nonce_store
+    }
+
+    /// Verify a signed message now
+    ///
+    /// # Errors
+    /// See [`SiwsVerifier::verify_at`]
+    pub fn verify(&self, message: &str, signature: &str) -> Result<SiwsMessage> {
+        self.verify_at(message, signature, Utc::now())
+    }
+
+    /// Verify a signed message as of `now`, returning the parsed message
+    ///
+    /// `signature` is the wallet's signature over the exact message text, base58 or
+    /// hex encoded as accepted by [`verify_wallet_signature`].
+    ///
+    /// # Errors
+    /// Returns [`TallyError::SiwsVerification`] if the message is malformed or not in
+    /// canonical form, names another domain or cluster, is outside its validity window,
+    /// carries an invalid signature, or its nonce was unknown or already used
+    pub fn verify_at(
+        &self,
+        message: &str,
+        signature: &str,
+        now: DateTime<Utc>,
+    ) -> Result<SiwsMessage> {
+        let parsed = SiwsMessage::from_str(message)?;
+
+        if parsed.domain != self.domain {
+            return Err(TallyError::SiwsVerification(format!(
+                "Domain mismatch: expected {}, got {}",
+                self.domain, parsed.domain
+            )));
+        }
+        if parsed.version != SIWS_VERSION {
+            return Err(TallyError::SiwsVerification(format!(
+                "Unsupported version {}",
+                parsed.version
+            )));
+        }
+        if self.chain_id.is_some() && parsed.chain_id != self.chain_id {
+            return Err(TallyError::SiwsVerification(
+                "Chain ID mismatch".to_string(),
+            ));
+        }
+        parsed.check_time(now, self.max_clock_skew)?;
+
+        verify_wallet_signature(&parsed.address.to_string(), signature, message)
+            .map_err(|e| TallyError::SiwsVerification(e.to_string()))?;
+
+        if !self.nonce_store.consume(&parsed.nonce, &parsed.address)? {
+            return Err(TallyError::SiwsVerification(
+                "Nonce is unknown, expired or already used".to_string(),
+            ));
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::signature::{Keypair, Signer};
+
+    fn issued_at() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-01-01T00:00:00.000Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn minutes_after_issue(minutes: i64) -> DateTime<Utc> {
+        issued_at()
+            .checked_add_signed(Duration::minutes(minutes))
+            .unwrap()
+    }
+
+    fn message(address: Pubkey, nonce: &str) -> SiwsMessage {
+        SiwsMessage::builder()
+            .domain("billing.example.com")
+            .address(address)
+            .statement("Sign in to manage your subscription")
+            .uri("https://billing.example.com/login")
+            .chain_id("mainnet")
+            .nonce(nonce)
+            .issued_at(issued_at())
+            .expiration_time(minutes_after_issue(10))
+            .build()
+            .unwrap()
+    }
+
+    fn sign(keypair: &Keypair, text: &str) -> String {
+        keypair.sign_message(text.as_bytes()).to_string()
+    }
+
+    #[test]
+    fn test_canonical_serialization() {
+        let address = Pubkey::new_unique();
+        let text = message(address, "32891756").to_string();
+
+        assert_eq!(
+            text,
+            format!(
+                "billing.example.com wants you to sign in with your Solana account:\n{address}\n\n\
+                 Sign in to manage your subscription\n\n\
+                 URI: https://billing.example.com/login\nVersion: 1\nChain ID: mainnet\n\
+                 Nonce: 32891756\nIssued At: 2024-01-01T00:00:00.000Z\n\
+                 Expiration Time: 2024-01-01T00:10:00.000Z"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let mut original = message(Pubkey::new_unique(), "32891756");
+        assert_eq!(
+            original.to_string().parse::<SiwsMessage>().unwrap(),
+            original
+        );
+
+        // Minimal message without statement, plus resources
+        original.statement = None;
+        original.uri = None;
+        original.resources = vec!["https://billing.example.com/invoices".to_string()];
+        assert_eq!(
+            original.to_string().parse::<SiwsMessage>().unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_non_canonical_text() {
+        let text = message(Pubkey::new_unique(), "32891756").to_string();
+
+        let reordered = text.replace(
+            "Version: 1\nChain ID: mainnet",
+            "Chain ID: mainnet\nVersion: 1",
+        );
+        assert!(reordered.parse::<SiwsMessage>().is_err());
+
+        let reformatted = text.replace("00:00:00.000Z", "00:00:00Z");
+        assert!(reformatted.parse::<SiwsMessage>().is_err());
+
+        assert!(format!("{text}\nX-Extra: 1")
+            .parse::<SiwsMessage>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let address = Pubkey::new_unique();
+        let base = || {
+            SiwsMessage::builder()
+                .domain("example.com")
+                .address(address)
+        };
+
+        assert!(base()
+            .build()
+            .unwrap_err()
+            .to_string()
+            .contains("Nonce not set"));
+        assert!(base().nonce("short").build().is_err());
+        assert!(base().nonce("not-alphanumeric").build().is_err());
+        assert!(base()
+            .nonce(generate_nonce())
+            .statement("two\nlines")
+            .build()
+            .is_err());
+        assert!(base()
+            .nonce(generate_nonce())
+            .issued_at(issued_at())
+            .expiration_time(issued_at())
+            .build()
+            .is_err());
+        assert!(SiwsMessage::builder()
+            .domain("example .com")
+            .address(address)
+            .nonce(generate_nonce())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_signed_message() {
+        let keypair = Keypair::new();
+        let store = InMemoryNonceStore::new(Duration::minutes(5));
+        let nonce = store.issue().unwrap();
+        let text = message(keypair.pubkey(), &nonce).to_string();
+        let signature = sign(&keypair, &text);
+
+        let verifier = SiwsVerifier::new("billing.example.com", store).chain_id("mainnet");
+        let now = minutes_after_issue(1);
+        let signed_in = verifier.verify_at(&text, &signature, now).unwrap();
+        assert_eq!(signed_in.address, keypair.pubkey());
+
+        // Replaying the same signed message fails once the nonce is consumed
+        let replay = verifier.verify_at(&text, &signature, now).unwrap_err();
+        assert!(replay.to_string().contains("already used"));
+    }
+
+    #[test]
+    fn test_verify_rejects_invalid_messages() {
+        let keypair = Keypair::new();
+        let store = InMemoryNonceStore::new(Duration::minutes(5));
+        let nonce = store.issue().unwrap();
+        let text = message(keypair.pubkey(), &nonce).to_string();
+        let signature = sign(&keypair, &text);
+        let now = minutes_after_issue(1);
+
+        let other_domain = SiwsVerifier::new(
+            "evil.example.com",
+            InMemoryNonceStore::new(Duration::minutes(5)),
+        );
+        assert!(other_domain.verify_at(&text, &signature, now).is_err());
+
+        let verifier = SiwsVerifier::new("billing.example.com", store);
+        let other_signer = sign(&Keypair::new(), &text);
+        assert!(verifier.verify_at(&text, &other_signer, now).is_err());
+
+        let expired = minutes_after_issue(30);
+        assert!(verifier
+            .verify_at(&text, &signature, expired)
+            .unwrap_err()
+            .to_string()
+            .contains("expired"));
+
+        let before_issue = minutes_after_issue(-5);
+        assert!(verifier.verify_at(&text, &signature, before_issue).is_err());
+
+        // Rejected attempts leave the nonce usable
+        assert!(verifier.verify_at(&text, &signature, now).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_and_expired_nonces() {
+        let keypair = Keypair::new();
+        let now = minutes_after_issue(1);
+
+        let store = InMemoryNonceStore::new(Duration::minutes(5));
+        let text = message(keypair.pubkey(), &generate_nonce()).to_string();
+        let verifier = SiwsVerifier::new("billing.example.com", store);
+        assert!(verifier
+            .verify_at(&text, &sign(&keypair, &text), now)
+            .is_err());
+
+        let lapsed = InMemoryNonceStore::new(Duration::seconds(-1));
+        let nonce = lapsed.issue().unwrap();
+        assert!(!lapsed.consume(&nonce, &keypair.pubkey()).unwrap());
+    }
+}
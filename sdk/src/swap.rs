@@ -0,0 +1,449 @@
+//! Jupiter swap funding for payers without USDC
+//!
+//! Many payers hold SOL rather than USDC. This module turns a Jupiter quote and its
+//! `/swap-instructions` response into a [`FundingSwap`] that
+//! [`StartAgreementBuilder::with_funding_swap`](crate::StartAgreementBuilder::with_funding_swap)
+//! places ahead of the approve → start instructions, so funding and starting the
+//! agreement land in a single transaction.
+//!
+//! The SDK does not call the Jupiter API itself. Fetch the quote from the URL built by
+//! [`exact_out_quote_url`], post it to Jupiter's `/swap-instructions` endpoint, and pass
+//! both JSON responses to [`FundingSwap::from_jupiter`]. Jupiter routes usually rely on
+//! address lookup tables, so compile the transaction as a v0 message using
+//! [`FundingSwap::address_lookup_tables`].
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use anchor_client::solana_sdk::instruction::{AccountMeta, Instruction};
+use anchor_lang::prelude::Pubkey;
+use base64::Engine;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Jupiter v6 aggregator program ID
+pub const JUPITER_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// Wrapped SOL mint, the input mint for SOL → USDC swaps
+pub const WRAPPED_SOL_MINT: Pubkey =
+    anchor_lang::solana_program::pubkey!("So11111111111111111111111111111111111111112");
+
+/// Default maximum slippage accepted for funding swaps (1%)
+pub const DEFAULT_MAX_SLIPPAGE_BPS: u16 = 100;
+
+/// Build a Jupiter quote URL that swaps `input_mint` for exactly `amount` of `output_mint`
+///
+/// `api_base` is the quote API root, e.g. `https://quote-api.jup.ag/v6`.
+///
+/// # Errors
+/// Returns an error if `api_base` is not a valid URL
+pub fn exact_out_quote_url(
+    api_base: &str,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+    amount: u64,
+    slippage_bps: u16,
+) -> Result<String> {
+    let mut url = url::Url::parse(&format!("{}/quote", api_base.trim_end_matches('/')))
+        .map_err(|e| TallyError::Generic(format!("Invalid Jupiter API URL: {e}")))?;
+    url.query_pairs_mut()
+        .append_pair("inputMint", &input_mint.to_string())
+        .append_pair("outputMint", &output_mint.to_string())
+        .append_pair("amount", &amount.to_string())
+        .append_pair("swapMode", "ExactOut")
+        .append_pair("slippageBps", &slippage_bps.to_string());
+    Ok(url.into())
+}
+
+/// Swap instructions funding the payer's USDC account before an agreement starts
+#[derive(Clone, Debug)]
+pub struct FundingSwap {
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    /// Most the payer can spend of the input mint
+    maximum_in: u64,
+    /// Least the swap can deliver of the output mint
+    minimum_out: u64,
+    slippage_bps: u16,
+    max_slippage_bps: u16,
+    instructions: Vec<Instruction>,
+    address_lookup_tables: Vec<Pubkey>,
+}
+
+impl FundingSwap {
+    /// Build a funding swap from Jupiter's quote and `/swap-instructions` JSON responses
+    ///
+    /// # Errors
+    /// Returns an error if either response is malformed or the swap instruction does not
+    /// target the Jupiter program
+    pub fn from_jupiter(
+        quote: &serde_json::Value,
+        swap_instructions: &serde_json::Value,
+    ) -> Result<Self> {
+        let quote: QuoteResponse = serde_json::from_value(quote.clone())
+            .map_err(|e| TallyError::ParseError(format!("Invalid Jupiter quote: {e}")))?;
+        let response: SwapInstructionsResponse = serde_json::from_value(swap_instructions.clone())
+            .map_err(|e| {
+                TallyError::ParseError(format!("Invalid Jupiter swap instructions: {e}"))
+            })?;
+
+        let in_amount = parse_amount(&quote.in_amount)?;
+        let out_amount = parse_amount(&quote.out_amount)?;
+        let threshold = parse_amount(&quote.other_amount_threshold)?;
+        // The threshold bounds the side of the swap that is not fixed by the quote
+        let (maximum_in, minimum_out) = match quote.swap_mode.as_str() {
+            "ExactIn" => (in_amount, threshold),
+            "ExactOut" => (threshold, out_amount),
+            other => {
+                return Err(TallyError::ParseError(format!(
+                    "Unknown Jupiter swap mode: {other}"
+                )))
+            }
+        };
+
+        let swap_instruction = Instruction::try_from(response.swap_instruction)?;
+        if swap_instruction.program_id != JUPITER_PROGRAM_ID {
+            return Err(TallyError::Generic(format!(
+                "Swap instruction targets {} instead of the Jupiter program",
+                swap_instruction.program_id
+            )));
+        }
+
+        let mut instructions = response
+            .compute_budget_instructions
+            .into_iter()
+            .chain(response.setup_instructions)
+            .map(Instruction::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        instructions.push(swap_instruction);
+        if let Some(cleanup) = response.cleanup_instruction {
+            instructions.push(Instruction::try_from(cleanup)?);
+        }
+
+        let address_lookup_tables = response
+            .address_lookup_table_addresses
+            .iter()
+            .map(|address| parse_pubkey(address))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            input_mint: parse_pubkey(&quote.input_mint)?,
+            output_mint: parse_pubkey(&quote.output_mint)?,
+            maximum_in,
+            minimum_out,
+            slippage_bps: quote.slippage_bps,
+            max_slippage_bps: DEFAULT_MAX_SLIPPAGE_BPS,
+            instructions,
+            address_lookup_tables,
+        })
+    }
+
+    /// Set the maximum slippage accepted when the agreement is built (default 1%)
+    #[must_use]
+    pub const fn max_slippage_bps(mut self, max_slippage_bps: u16) -> Self {
+        self.max_slippage_bps = max_slippage_bps;
+        self
+    }
+
+    /// Input mint spent by the swap
+    #[must_use]
+    pub const fn input_mint(&self) -> Pubkey {
+        self.input_mint
+    }
+
+    /// Most the payer can spend of the input mint
+    #[must_use]
+    pub const fn maximum_in(&self) -> u64 {
+        self.maximum_in
+    }
+
+    /// Least the swap can deliver of the output mint
+    #[must_use]
+    pub const fn minimum_out(&self) -> u64 {
+        self.minimum_out
+    }
+
+    /// Address lookup tables required to compile the transaction
+    #[must_use]
+    pub fn address_lookup_tables(&self) -> &[Pubkey] {
+        &self.address_lookup_tables
+    }
+
+    /// Swap instructions in execution order (compute budget, setup, swap, cleanup)
+    #[must_use]
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Check that the swap delivers at least `required_amount` of `usdc_mint` within the slippage limit
+    ///
+    /// # Errors
+    /// Returns an error if the output mint is wrong, the slippage exceeds the limit, or
+    /// the minimum output is below `required_amount`
+    pub fn validate(&self, usdc_mint: &Pubkey, required_amount: u64) -> Result<()> {
+        if self.output_mint != *usdc_mint {
+            return Err(TallyError::Generic(format!(
+                "Swap outputs {} instead of the payee's USDC mint {usdc_mint}",
+                self.output_mint
+            )));
+        }
+        if self.slippage_bps > self.max_slippage_bps {
+            return Err(TallyError::Generic(format!(
+                "Swap slippage of {} bps exceeds the maximum of {} bps",
+                self.slippage_bps, self.max_slippage_bps
+            )));
+        }
+        if self.minimum_out < required_amount {
+            return Err(TallyError::Generic(format!(
+                "Swap minimum output {} is below the required {required_amount}",
+                self.minimum_out
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Subset of Jupiter's quote response used to validate the swap
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuoteResponse {
+    input_mint: String,
+    output_mint: String,
+    in_amount: String,
+    out_amount: String,
+    other_amount_threshold: String,
+    swap_mode: String,
+    slippage_bps: u16,
+}
+
+/// Jupiter's `/swap-instructions` response
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructionsResponse {
+    #[serde(default)]
+    compute_budget_instructions: Vec<JupiterInstruction>,
+    #[serde(default)]
+    setup_instructions: Vec<JupiterInstruction>,
+    swap_instruction: JupiterInstruction,
+    cleanup_instruction: Option<JupiterInstruction>,
+    #[serde(default)]
+    address_lookup_table_addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JupiterInstruction {
+    program_id: String,
+    accounts: Vec<JupiterAccountMeta>,
+    data: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JupiterAccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl TryFrom<JupiterInstruction> for Instruction {
+    type Error = TallyError;
+
+    fn try_from(instruction: JupiterInstruction) -> Result<Self> {
+        let accounts = instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                Ok(AccountMeta {
+                    pubkey: parse_pubkey(&meta.pubkey)?,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let data = base64::prelude::BASE64_STANDARD
+            .decode(&instruction.data)
+            .map_err(|e| TallyError::ParseError(format!("Invalid instruction data: {e}")))?;
+
+        Ok(Self {
+            program_id: parse_pubkey(&instruction.program_id)?,
+            accounts,
+            data,
+        })
+    }
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value)
+        .map_err(|e| TallyError::ParseError(format!("Invalid pubkey {value}: {e}")))
+}
+
+fn parse_amount(value: &str) -> Result<u64> {
+    value
+        .parse()
+        .map_err(|e| TallyError::ParseError(format!("Invalid amount {value}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_types::{Payee, PaymentTerms, VolumeTier};
+    use crate::transaction_builder::start_agreement;
+    use serde_json::json;
+
+    fn usdc_mint() -> Pubkey {
+        Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap()
+    }
+
+    fn instruction_json(program_id: &Pubkey) -> serde_json::Value {
+        json!({
+            "programId": program_id.to_string(),
+            "accounts": [
+                { "pubkey": Pubkey::new_unique().to_string(), "isSigner": true, "isWritable": true }
+            ],
+            "data": "AQID"
+        })
+    }
+
+    fn quote(slippage_bps: u16, out_amount: u64) -> serde_json::Value {
+        json!({
+            "inputMint": WRAPPED_SOL_MINT.to_string(),
+            "outputMint": usdc_mint().to_string(),
+            "inAmount": "200000000",
+            "outAmount": out_amount.to_string(),
+            "otherAmountThreshold": "202000000",
+            "swapMode": "ExactOut",
+            "slippageBps": slippage_bps
+        })
+    }
+
+    fn swap_instructions() -> serde_json::Value {
+        json!({
+            "computeBudgetInstructions": [instruction_json(&Pubkey::new_unique())],
+            "setupInstructions": [instruction_json(&Pubkey::new_unique())],
+            "swapInstruction": instruction_json(&JUPITER_PROGRAM_ID),
+            "cleanupInstruction": instruction_json(&Pubkey::new_unique()),
+            "addressLookupTableAddresses": [Pubkey::new_unique().to_string()]
+        })
+    }
+
+    #[test]
+    fn test_from_jupiter_exact_out() {
+        let swap = FundingSwap::from_jupiter(&quote(50, 30_000_000), &swap_instructions()).unwrap();
+
+        assert_eq!(swap.input_mint(), WRAPPED_SOL_MINT);
+        assert_eq!(swap.maximum_in(), 202_000_000);
+        assert_eq!(swap.minimum_out(), 30_000_000);
+        assert_eq!(swap.instructions().len(), 4);
+        assert_eq!(swap.instructions()[2].program_id, JUPITER_PROGRAM_ID);
+        assert_eq!(swap.instructions()[2].data, vec![1, 2, 3]);
+        assert_eq!(swap.address_lookup_tables().len(), 1);
+    }
+
+    #[test]
+    fn test_from_jupiter_rejects_foreign_swap_program() {
+        let mut instructions = swap_instructions();
+        instructions["swapInstruction"] = instruction_json(&Pubkey::new_unique());
+
+        assert!(FundingSwap::from_jupiter(&quote(50, 30_000_000), &instructions).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let swap = FundingSwap::from_jupiter(&quote(50, 30_000_000), &swap_instructions()).unwrap();
+        assert!(swap.validate(&usdc_mint(), 30_000_000).is_ok());
+        assert!(swap.validate(&usdc_mint(), 30_000_001).is_err());
+        assert!(swap.validate(&Pubkey::new_unique(), 30_000_000).is_err());
+
+        let loose =
+            FundingSwap::from_jupiter(&quote(300, 30_000_000), &swap_instructions()).unwrap();
+        assert!(loose.validate(&usdc_mint(), 30_000_000).is_err());
+        assert!(loose
+            .max_slippage_bps(300)
+            .validate(&usdc_mint(), 30_000_000)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_exact_out_quote_url() {
+        let url = exact_out_quote_url(
+            "https://quote-api.jup.ag/v6/",
+            &WRAPPED_SOL_MINT,
+            &usdc_mint(),
+            30_000_000,
+            50,
+        )
+        .unwrap();
+
+        assert!(url.starts_with("https://quote-api.jup.ag/v6/quote?"));
+        assert!(url.contains("amount=30000000"));
+        assert!(url.contains("swapMode=ExactOut"));
+        assert!(url.contains("slippageBps=50"));
+    }
+
+    #[test]
+    fn test_start_agreement_with_funding_swap() {
+        let authority = Pubkey::new_unique();
+        let payee = Payee {
+            authority,
+            usdc_mint: usdc_mint(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let payment_terms = PaymentTerms {
+            payee: Pubkey::new_unique(),
+            terms_id: [0; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let builder = || {
+            start_agreement()
+                .payment_terms(Pubkey::new_unique())
+                .payer(Pubkey::new_unique())
+                .program_id(Pubkey::new_unique())
+        };
+
+        // Three periods of allowance require 30 USDC from the swap
+        let swap = FundingSwap::from_jupiter(&quote(50, 30_000_000), &swap_instructions()).unwrap();
+        let instructions = builder()
+            .with_funding_swap(swap)
+            .build_instructions(&payee, &payment_terms, &Pubkey::new_unique())
+            .unwrap();
+        assert_eq!(instructions.len(), 6);
+        assert_eq!(instructions[2].program_id, JUPITER_PROGRAM_ID);
+
+        let short =
+            FundingSwap::from_jupiter(&quote(50, 20_000_000), &swap_instructions()).unwrap();
+        assert!(builder()
+            .with_funding_swap(short)
+            .build_instructions(&payee, &payment_terms, &Pubkey::new_unique())
+            .is_err());
+    }
+}
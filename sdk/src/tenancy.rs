@@ -0,0 +1,796 @@
+//! Scoped, read-only dashboard access tokens for hosted analytics
+//!
+//! Hosted dashboards need to read a payee's billing data without holding the payee's
+//! keys. The payee authority signs a [`DashboardGrant`] naming the dashboard host, the
+//! plans (payment terms) and the date range it may read, and hands the resulting
+//! [`CapabilityToken`] to the host as an API key. The host checks presented tokens with
+//! a [`CapabilityVerifier`].
+//!
+//! The authority signs the grant's canonical text, so browser wallets can issue tokens
+//! with `signMessage` and every token verifies with [`verify_wallet_signature`]:
+//!
+//! ```text
+//! Grant analytics.example.com read-only access to Tally dashboard data
+//! Authority: 7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU
+//! Payee: 9aE476sH92Vz7DMPyq5WLPkrKWivxeuTKEFKd2sZZcde
+//! Data From: 2024-01-01T00:00:00.000Z
+//! Data Until: 2024-04-01T00:00:00.000Z
+//! Token ID: Xk2f9LqP0aZr7TbM
+//! Issued At: 2024-01-01T00:00:00.000Z
+//! Expires At: 2024-02-01T00:00:00.000Z
+//! Plans:
+//! - 4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T
+//! ```
+//!
+//! Grants name the payee account, and both issuing and verifying check the signer
+//! against that account's current on-chain authority, so rotating the payee authority
+//! revokes every token the old key signed.
+//!
+//! Tokens are bearer credentials until they expire. Hosts that need to revoke one
+//! earlier should keep a deny-list of [`DashboardGrant::token_id`]s.
+
+#![forbid(unsafe_code)]
+
+use crate::{
+    error::Result,
+    pda,
+    program_types::Payee,
+    signature::{normalize_signature_format, verify_wallet_signature},
+    TallyError,
+};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Length of token IDs generated by [`DashboardGrantBuilder::build`]
+pub const TOKEN_ID_LENGTH: usize = 16;
+
+/// Maximum number of plans a single grant can cover
+pub const MAX_GRANT_PLANS: usize = 64;
+
+const HEADER_PREFIX: &str = "Grant ";
+const HEADER_SUFFIX: &str = " read-only access to Tally dashboard data";
+
+/// A payee authority's grant of read-only dashboard access to one host
+///
+/// [`fmt::Display`] produces the canonical text the authority signs and [`FromStr`]
+/// parses it back. Parsing rejects text that does not re-serialize byte for byte, so a
+/// verified grant has exactly one interpretation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DashboardGrant {
+    /// Dashboard host the grant is for (host, optionally with port)
+    pub audience: String,
+    /// Payee authority issuing the grant
+    pub authority: Pubkey,
+    /// Payee account whose data may be read
+    pub payee: Pubkey,
+    /// Payment terms whose data may be read
+    pub plans: Vec<Pubkey>,
+    /// Start of the readable data range (inclusive)
+    pub data_from: DateTime<Utc>,
+    /// End of the readable data range (exclusive)
+    pub data_until: DateTime<Utc>,
+    /// Random identifier hosts can use to revoke the token
+    pub token_id: String,
+    /// When the grant was issued
+    pub issued_at: DateTime<Utc>,
+    /// When the grant stops being valid
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DashboardGrant {
+    /// Create a new dashboard grant builder
+    #[must_use]
+    pub fn builder() -> DashboardGrantBuilder {
+        DashboardGrantBuilder::default()
+    }
+
+    /// Whether the grant covers data of `payment_terms`
+    #[must_use]
+    pub fn allows_plan(&self, payment_terms: &Pubkey) -> bool {
+        self.plans.contains(payment_terms)
+    }
+
+    /// Whether the grant covers data recorded at the Unix timestamp `ts`
+    #[must_use]
+    pub const fn allows_timestamp(&self, ts: i64) -> bool {
+        self.data_from.timestamp() <= ts && ts < self.data_until.timestamp()
+    }
+
+    /// Narrow a requested `[from, until)` range to the granted one
+    ///
+    /// Returns `None` if the ranges don't overlap.
+    #[must_use]
+    pub fn clamp_range(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let from = from.max(self.data_from);
+        let until = until.min(self.data_until);
+        (from < until).then_some((from, until))
+    }
+
+    /// Sign the grant with the payee authority's key
+    ///
+    /// # Errors
+    /// Returns an error if `signer` is not the grant's authority
+    pub fn sign(self, signer: &impl Signer) -> Result<CapabilityToken> {
+        if signer.pubkey() != self.authority {
+            return Err(TallyError::CapabilityVerification(
+                "Signer is not the grant authority".to_string(),
+            ));
+        }
+        let signature = signer.sign_message(self.to_string().as_bytes());
+        Ok(CapabilityToken {
+            grant: self,
+            signature,
+        })
+    }
+}
+
+fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+impl fmt::Display for DashboardGrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER_PREFIX}{}{HEADER_SUFFIX}", self.audience)?;
+        writeln!(f, "Authority: {}", self.authority)?;
+        writeln!(f, "Payee: {}", self.payee)?;
+        writeln!(f, "Data From: {}", format_timestamp(&self.data_from))?;
+        writeln!(f, "Data Until: {}", format_timestamp(&self.data_until))?;
+        writeln!(f, "Token ID: {}", self.token_id)?;
+        writeln!(f, "Issued At: {}", format_timestamp(&self.issued_at))?;
+        writeln!(f, "Expires At: {}", format_timestamp(&self.expires_at))?;
+        write!(f, "Plans:")?;
+        for plan in &self.plans {
+            write!(f, "\n- {plan}")?;
+        }
+        Ok(())
+    }
+}
+
+fn malformed(reason: &str) -> TallyError {
+    TallyError::CapabilityVerification(format!("Malformed grant: {reason}"))
+}
+
+fn parse_pubkey(field: &str, value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| malformed(&format!("invalid {field}")))
+}
+
+fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|_| malformed(&format!("invalid {field}")))
+}
+
+impl FromStr for DashboardGrant {
+    type Err = TallyError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let mut lines = text.lines();
+        let audience = lines
+            .next()
+            .and_then(|header| header.strip_prefix(HEADER_PREFIX))
+            .and_then(|header| header.strip_suffix(HEADER_SUFFIX))
+            .ok_or_else(|| malformed("missing header"))?;
+
+        let mut values: HashMap<&str, &str> = HashMap::new();
+        let mut plans = Vec::new();
+        while let Some(line) = lines.next() {
+            if line == "Plans:" {
+                for plan in lines.by_ref() {
+                    let plan = plan
+                        .strip_prefix("- ")
+                        .ok_or_else(|| malformed("invalid plan"))?;
+                    plans.push(parse_pubkey("plan", plan)?);
+                }
+                break;
+            }
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| malformed("invalid field"))?;
+            if values.insert(key, value).is_some() {
+                return Err(malformed("duplicate field"));
+            }
+        }
+
+        let mut take = |key: &str| {
+            values
+                .remove(key)
+                .ok_or_else(|| malformed(&format!("missing {key}")))
+        };
+        let grant = Self {
+            audience: audience.to_string(),
+            authority: parse_pubkey("authority", take("Authority")?)?,
+            payee: parse_pubkey("payee", take("Payee")?)?,
+            data_from: parse_timestamp("data from", take("Data From")?)?,
+            data_until: parse_timestamp("data until", take("Data Until")?)?,
+            token_id: take("Token ID")?.to_string(),
+            issued_at: parse_timestamp("issued at", take("Issued At")?)?,
+            expires_at: parse_timestamp("expires at", take("Expires At")?)?,
+            plans,
+        };
+
+        if let Some(key) = values.keys().next() {
+            return Err(malformed(&format!("unknown field {key}")));
+        }
+        if grant.to_string() != text {
+            return Err(malformed("not in canonical form"));
+        }
+        Ok(grant)
+    }
+}
+
+/// Builder for [`DashboardGrant`]
+#[derive(Clone, Debug, Default)]
+pub struct DashboardGrantBuilder {
+    audience: Option<String>,
+    authority: Option<Pubkey>,
+    plans: Vec<Pubkey>,
+    data_from: Option<DateTime<Utc>>,
+    data_until: Option<DateTime<Utc>>,
+    issued_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    program_id: Option<Pubkey>,
+}
+
+impl DashboardGrantBuilder {
+    /// Set the dashboard host the grant is for
+    #[must_use]
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Set the payee authority issuing the grant (defaults to the payee's current authority)
+    #[must_use]
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Add payment terms whose data may be read
+    #[must_use]
+    pub fn plan(mut self, payment_terms: Pubkey) -> Self {
+        self.plans.push(payment_terms);
+        self
+    }
+
+    /// Set the readable data range as `[from, until)`
+    #[must_use]
+    pub const fn data_range(mut self, from: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        self.data_from = Some(from);
+        self.data_until = Some(until);
+        self
+    }
+
+    /// Set when the grant was issued (defaults to now)
+    #[must_use]
+    pub const fn issued_at(mut self, issued_at: DateTime<Utc>) -> Self {
+        self.issued_at = Some(issued_at);
+        self
+    }
+
+    /// Set when the grant stops being valid
+    #[must_use]
+    pub const fn expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Set the program ID used to derive the payee (optional, defaults to the SDK program ID)
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the grant for `payee` with a fresh random token ID
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data, whose current authority must issue the grant
+    ///
+    /// # Errors
+    /// Returns an error if the audience, data range or expiry is not set, the authority
+    /// is not the payee's current authority, the audience is empty or contains
+    /// whitespace, no plans or more than [`MAX_GRANT_PLANS`] are listed, a plan is
+    /// listed twice, or a range is empty
+    pub fn build(self, payee: &Payee) -> Result<DashboardGrant> {
+        let audience = self.audience.ok_or("Audience not set")?;
+        let authority = self.authority.unwrap_or(payee.authority);
+        let data_from = self.data_from.ok_or("Data range not set")?;
+        let data_until = self.data_until.ok_or("Data range not set")?;
+        let expires_at = self.expires_at.ok_or("Expiry not set")?;
+        let issued_at = self.issued_at.unwrap_or_else(Utc::now);
+
+        if authority != payee.authority {
+            return Err("Authority is not the payee's current authority".into());
+        }
+        if audience.is_empty() || audience.contains(char::is_whitespace) {
+            return Err("Audience must be non-empty and contain no whitespace".into());
+        }
+        if self.plans.is_empty() || self.plans.len() > MAX_GRANT_PLANS {
+            return Err(format!("Grant must list between 1 and {MAX_GRANT_PLANS} plans").into());
+        }
+        if self
+            .plans
+            .iter()
+            .enumerate()
+            .any(|(i, plan)| self.plans[..i].contains(plan))
+        {
+            return Err("Grant lists a plan more than once".into());
+        }
+        if data_until <= data_from {
+            return Err("Data range end must be after its start".into());
+        }
+        if expires_at <= issued_at {
+            return Err("Expiry must be after issued at".into());
+        }
+
+        let program_id = self.program_id.unwrap_or_else(crate::program_id);
+        Ok(DashboardGrant {
+            audience,
+            authority,
+            payee: pda::payee_address_with_program_id(&payee.original_authority, &program_id),
+            plans: self.plans,
+            data_from,
+            data_until,
+            token_id: generate_token_id(),
+            issued_at,
+            expires_at,
+        })
+    }
+}
+
+fn generate_token_id() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_ID_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// A signed [`DashboardGrant`], handed to dashboard hosts as an API key
+///
+/// The string form is the URL-safe base64 of the grant text, a `.`, and the base58
+/// signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapabilityToken {
+    /// The signed grant
+    pub grant: DashboardGrant,
+    /// The authority's signature over the grant text
+    pub signature: Signature,
+}
+
+impl CapabilityToken {
+    /// Combine a grant with a signature produced by the authority's wallet
+    ///
+    /// `signature` is the wallet's `signMessage` output over the grant text, base58 or
+    /// hex encoded.
+    ///
+    /// # Errors
+    /// Returns an error if the signature cannot be decoded or does not verify
+    pub fn from_wallet_signature(grant: DashboardGrant, signature: &str) -> Result<Self> {
+        let signature = normalize_signature_format(signature)?;
+        verify_wallet_signature(&grant.authority.to_string(), &signature, &grant.to_string())
+            .map_err(|e| TallyError::CapabilityVerification(e.to_string()))?;
+        let signature = Signature::from_str(&signature)
+            .map_err(|e| TallyError::CapabilityVerification(e.to_string()))?;
+        Ok(Self { grant, signature })
+    }
+}
+
+impl fmt::Display for CapabilityToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(self.grant.to_string()),
+            self.signature
+        )
+    }
+}
+
+impl FromStr for CapabilityToken {
+    type Err = TallyError;
+
+    fn from_str(token: &str) -> Result<Self> {
+        let malformed_token = || TallyError::CapabilityVerification("Malformed token".to_string());
+
+        let (grant, signature) = token.split_once('.').ok_or_else(malformed_token)?;
+        let grant = URL_SAFE_NO_PAD
+            .decode(grant)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(malformed_token)?;
+        Ok(Self {
+            grant: grant.parse()?,
+            signature: Signature::from_str(signature).map_err(|_| malformed_token())?,
+        })
+    }
+}
+
+/// Verifies capability tokens presented to one dashboard host
+#[derive(Clone, Debug)]
+pub struct CapabilityVerifier {
+    audience: String,
+    program_id: Option<Pubkey>,
+    max_clock_skew: Duration,
+}
+
+impl CapabilityVerifier {
+    /// Create a verifier accepting tokens for `audience`
+    ///
+    /// Allows 60 seconds of clock skew between the issuer and the host.
+    pub fn new(audience: impl Into<String>) -> Self {
+        Self {
+            audience: audience.into(),
+            program_id: None,
+            max_clock_skew: Duration::seconds(60),
+        }
+    }
+
+    /// Set the program ID used to derive payees (optional, defaults to the SDK program ID)
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Set the clock skew tolerated for time checks
+    #[must_use]
+    pub const fn max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = max_clock_skew;
+        self
+    }
+
+    /// Verify a token now
+    ///
+    /// # Errors
+    /// See [`CapabilityVerifier::verify_at`]
+    pub fn verify(&self, token: &str, payee: &Payee) -> Result<DashboardGrant> {
+        self.verify_at(token, payee, Utc::now())
+    }
+
+    /// Verify a token as of `now`, returning the granted scope
+    ///
+    /// `payee` is the on-chain account the token's grant names, fetched by the host;
+    /// parse the token with [`CapabilityToken::from_str`] to find its address. The token
+    /// must be signed by the payee's current authority.
+    ///
+    /// The host must still restrict every query to [`DashboardGrant::payee`] and
+    /// [`DashboardGrant::plans`], and clamp its date range with
+    /// [`DashboardGrant::clamp_range`].
+    ///
+    /// # Errors
+    /// Returns [`TallyError::CapabilityVerification`] if the token is malformed, names
+    /// another host or a payee other than `payee`, its authority is not the payee's
+    /// current authority, it is outside its validity window, or its signature is not
+    /// the authority's
+    pub fn verify_at(
+        &self,
+        token: &str,
+        payee: &Payee,
+        now: DateTime<Utc>,
+    ) -> Result<DashboardGrant> {
+        let token = CapabilityToken::from_str(token)?;
+        let grant = token.grant;
+
+        if grant.audience != self.audience {
+            return Err(TallyError::CapabilityVerification(format!(
+                "Audience mismatch: expected {}, got {}",
+                self.audience, grant.audience
+            )));
+        }
+        let program_id = self.program_id.unwrap_or_else(crate::program_id);
+        if grant.payee != pda::payee_address_with_program_id(&payee.original_authority, &program_id)
+        {
+            return Err(TallyError::CapabilityVerification(
+                "Grant names a different payee".to_string(),
+            ));
+        }
+        if grant.authority != payee.authority {
+            return Err(TallyError::CapabilityVerification(
+                "Authority is not the payee's current authority".to_string(),
+            ));
+        }
+
+        let earliest = now.checked_add_signed(self.max_clock_skew).unwrap_or(now);
+        let latest = now.checked_sub_signed(self.max_clock_skew).unwrap_or(now);
+        if grant.issued_at > earliest {
+            return Err(TallyError::CapabilityVerification(
+                "Token issued in the future".to_string(),
+            ));
+        }
+        if grant.expires_at <= latest {
+            return Err(TallyError::CapabilityVerification(
+                "Token has expired".to_string(),
+            ));
+        }
+
+        verify_wallet_signature(
+            &grant.authority.to_string(),
+            &token.signature.to_string(),
+            &grant.to_string(),
+        )
+        .map_err(|e| TallyError::CapabilityVerification(e.to_string()))?;
+        Ok(grant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_types::VolumeTier;
+    use anchor_client::solana_sdk::signature::Keypair;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn payee(authority: Pubkey) -> Payee {
+        Payee {
+            authority,
+            usdc_mint: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            volume_tier: VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    fn build_grant(payee: &Payee, program_id: Pubkey, plan: Pubkey) -> DashboardGrant {
+        DashboardGrant::builder()
+            .audience("analytics.example.com")
+            .plan(plan)
+            .data_range(at("2024-01-01T00:00:00Z"), at("2024-04-01T00:00:00Z"))
+            .issued_at(at("2024-01-01T00:00:00Z"))
+            .expires_at(at("2024-02-01T00:00:00Z"))
+            .program_id(program_id)
+            .build(payee)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_canonical_text_round_trip() {
+        let plan = Pubkey::new_unique();
+        let grant = build_grant(&payee(Pubkey::new_unique()), Pubkey::new_unique(), plan);
+        let text = grant.to_string();
+
+        assert!(text
+            .starts_with("Grant analytics.example.com read-only access to Tally dashboard data\n"));
+        assert!(text.contains("\nData From: 2024-01-01T00:00:00.000Z\n"));
+        assert!(text.ends_with(&format!("Plans:\n- {plan}")));
+        assert_eq!(text.parse::<DashboardGrant>().unwrap(), grant);
+
+        assert!(text
+            .replace("00:00:00.000Z", "00:00:00Z")
+            .parse::<DashboardGrant>()
+            .is_err());
+        assert!(format!("Scope: admin\n{text}")
+            .parse::<DashboardGrant>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let plan = Pubkey::new_unique();
+        let payee = payee(Pubkey::new_unique());
+        let base = || {
+            DashboardGrant::builder()
+                .audience("analytics.example.com")
+                .data_range(at("2024-01-01T00:00:00Z"), at("2024-04-01T00:00:00Z"))
+                .issued_at(at("2024-01-01T00:00:00Z"))
+                .expires_at(at("2024-02-01T00:00:00Z"))
+                .program_id(Pubkey::new_unique())
+        };
+
+        assert!(base().plan(plan).build(&payee).is_ok());
+        assert!(base().build(&payee).is_err());
+        assert!(base().plan(plan).plan(plan).build(&payee).is_err());
+        assert!(base()
+            .plan(plan)
+            .authority(Pubkey::new_unique())
+            .build(&payee)
+            .is_err());
+        assert!(base()
+            .plan(plan)
+            .audience("analytics example.com")
+            .build(&payee)
+            .is_err());
+        assert!(base()
+            .plan(plan)
+            .data_range(at("2024-04-01T00:00:00Z"), at("2024-04-01T00:00:00Z"))
+            .build(&payee)
+            .is_err());
+        assert!(base()
+            .plan(plan)
+            .expires_at(at("2023-12-31T00:00:00Z"))
+            .build(&payee)
+            .is_err());
+    }
+
+    #[test]
+    fn test_scope_checks() {
+        let plan = Pubkey::new_unique();
+        let grant = build_grant(&payee(Pubkey::new_unique()), Pubkey::new_unique(), plan);
+
+        assert!(grant.allows_plan(&plan));
+        assert!(!grant.allows_plan(&Pubkey::new_unique()));
+        assert!(grant.allows_timestamp(at("2024-01-01T00:00:00Z").timestamp()));
+        assert!(!grant.allows_timestamp(at("2024-04-01T00:00:00Z").timestamp()));
+
+        assert_eq!(
+            grant.clamp_range(at("2023-06-01T00:00:00Z"), at("2024-02-01T00:00:00Z")),
+            Some((at("2024-01-01T00:00:00Z"), at("2024-02-01T00:00:00Z")))
+        );
+        assert_eq!(
+            grant.clamp_range(at("2024-05-01T00:00:00Z"), at("2024-06-01T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_issue_and_verify_token() {
+        let authority = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let payee = payee(authority.pubkey());
+        let grant = build_grant(&payee, program_id, Pubkey::new_unique());
+        let token = grant.clone().sign(&authority).unwrap().to_string();
+
+        // The signature is a plain wallet signature over the grant text
+        let (_, signature) = token.split_once('.').unwrap();
+        assert!(verify_wallet_signature(
+            &authority.pubkey().to_string(),
+            signature,
+            &grant.to_string()
+        )
+        .is_ok());
+
+        let verifier = CapabilityVerifier::new("analytics.example.com").program_id(program_id);
+        let scope = verifier
+            .verify_at(&token, &payee, at("2024-01-15T00:00:00Z"))
+            .unwrap();
+        assert_eq!(scope, grant);
+    }
+
+    #[test]
+    fn test_wallet_signed_token() {
+        let authority = Keypair::new();
+        let grant = build_grant(
+            &payee(authority.pubkey()),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let signature = authority.sign_message(grant.to_string().as_bytes());
+
+        let hex_signature = hex::encode(signature.as_ref());
+        let token = CapabilityToken::from_wallet_signature(grant.clone(), &hex_signature).unwrap();
+        assert_eq!(token.signature, signature);
+
+        let other = Keypair::new().sign_message(grant.to_string().as_bytes());
+        assert!(CapabilityToken::from_wallet_signature(grant, &other.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_invalid_tokens() {
+        let authority = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let payee = payee(authority.pubkey());
+        let grant = build_grant(&payee, program_id, Pubkey::new_unique());
+        let token = grant.clone().sign(&authority).unwrap().to_string();
+        let now = at("2024-01-15T00:00:00Z");
+        let verifier = CapabilityVerifier::new("analytics.example.com").program_id(program_id);
+
+        let other_host = CapabilityVerifier::new("evil.example.com").program_id(program_id);
+        assert!(other_host.verify_at(&token, &payee, now).is_err());
+
+        let other_program =
+            CapabilityVerifier::new("analytics.example.com").program_id(Pubkey::new_unique());
+        assert!(other_program.verify_at(&token, &payee, now).is_err());
+
+        assert!(verifier
+            .verify_at(&token, &payee, at("2024-03-01T00:00:00Z"))
+            .unwrap_err()
+            .to_string()
+            .contains("expired"));
+        assert!(verifier
+            .verify_at(&token, &payee, at("2023-12-31T00:00:00Z"))
+            .is_err());
+
+        // Widening the scope invalidates the authority's signature
+        let mut widened = grant.clone();
+        widened.plans.push(Pubkey::new_unique());
+        let forged = CapabilityToken {
+            grant: widened,
+            signature: grant.sign(&authority).unwrap().signature,
+        };
+        assert!(verifier
+            .verify_at(&forged.to_string(), &payee, now)
+            .is_err());
+
+        // A token checked against another payee's account is rejected
+        let other_payee = self::payee(Pubkey::new_unique());
+        assert!(verifier.verify_at(&token, &other_payee, now).is_err());
+
+        // Only the authority can sign its grants
+        let stranger = build_grant(&other_payee, program_id, Pubkey::new_unique());
+        assert!(stranger.sign(&Keypair::new()).is_err());
+
+        assert!(verifier.verify_at("not-a-token", &payee, now).is_err());
+    }
+
+    #[test]
+    fn test_transferred_authority() {
+        let old_authority = Keypair::new();
+        let new_authority = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let now = at("2024-01-15T00:00:00Z");
+        let verifier = CapabilityVerifier::new("analytics.example.com").program_id(program_id);
+        let before = payee(old_authority.pubkey());
+        let old_token = build_grant(&before, program_id, Pubkey::new_unique())
+            .sign(&old_authority)
+            .unwrap()
+            .to_string();
+
+        // The payee account keeps its address when the authority is transferred
+        let after = Payee {
+            authority: new_authority.pubkey(),
+            ..before.clone()
+        };
+        assert!(verifier.verify_at(&old_token, &before, now).is_ok());
+        assert!(verifier
+            .verify_at(&old_token, &after, now)
+            .unwrap_err()
+            .to_string()
+            .contains("current authority"));
+
+        // Only the new authority can issue grants for the payee from now on
+        let plan = Pubkey::new_unique();
+        let builder = || {
+            DashboardGrant::builder()
+                .audience("analytics.example.com")
+                .plan(plan)
+                .data_range(at("2024-01-01T00:00:00Z"), at("2024-04-01T00:00:00Z"))
+                .issued_at(at("2024-01-01T00:00:00Z"))
+                .expires_at(at("2024-02-01T00:00:00Z"))
+                .program_id(program_id)
+        };
+        assert!(builder()
+            .authority(old_authority.pubkey())
+            .build(&after)
+            .is_err());
+        let grant = builder().build(&after).unwrap();
+        assert_eq!(grant.authority, new_authority.pubkey());
+        assert_eq!(
+            grant.payee,
+            pda::payee_address_with_program_id(&old_authority.pubkey(), &program_id)
+        );
+        let new_token = grant.sign(&new_authority).unwrap().to_string();
+        assert!(verifier.verify_at(&new_token, &after, now).is_ok());
+        assert!(verifier.verify_at(&new_token, &before, now).is_err());
+    }
+}
@@ -0,0 +1,664 @@
+//! Deterministic Tally event fixtures for tests, demos and load simulations
+//!
+//! [`EventFactory`] generates every [`TallyEvent`] type from a seed, so the same seed
+//! always yields the same sequence for a given SDK version. Events are drawn from a
+//! small fixed population of payees, payment terms and payers, so generated streams
+//! look like real traffic: payments repeat against the same agreements and payee
+//! events refer to payees that also receive payments.
+//!
+//! ```
+//! use tally_sdk::testkit::EventFactory;
+//!
+//! let mut factory = EventFactory::new(42);
+//! let events: Vec<_> = (0..10).map(|_| factory.next_event_with_context()).collect();
+//!
+//! let mut replay = EventFactory::new(42);
+//! assert_eq!(
+//!     events[0].get_event_type_string(),
+//!     replay.next_event_with_context().get_event_type_string()
+//! );
+//! ```
+
+#![forbid(unsafe_code)]
+
+use crate::events::{
+    AgreementSnapshot, AgreementSuspended, AgreementUnsuspended, ConfigInitialized, ConfigUpdated, CreditApplied, DelegateMismatchWarning,
+    DepositClaimed, DepositHeld, DepositReleased, EscrowRefunded,
+    EscrowReleased, FeesWithdrawn, KeeperPaidInSol,
+    LowAllowanceWarning, ParsedEventWithContext, PayeeAuthorityTransferCancelled,
+    PayeeAuthorityTransferInitiated, PayeeAuthorityTransferred, PayeeInitialized,
+    PaymentAgreementClosed, PaymentAgreementPaused, PaymentAgreementResumed,
+    PaymentAgreementStarted, PaymentExecuted, PaymentFailed, PaymentTermsCreated,
+    PaymentTermsStatusChanged, PaymentTermsUpdated, ProgramPaused, ProgramUnpaused, Swept,
+    TallyEvent, TermsSunset, VolumeTier, VolumeTierUpgraded,
+};
+use crate::fees::split_payment;
+use anchor_client::solana_sdk::signature::Signature;
+use anchor_lang::prelude::Pubkey;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Names of all event types the factory generates, as returned by
+/// [`ParsedEventWithContext::get_event_type_string`]
+pub const EVENT_TYPES: [&str; 33] = [
+    "PaymentAgreementStarted",
+    "PaymentAgreementResumed",
+    "PaymentExecuted",
+    "PaymentAgreementPaused",
+    "PaymentAgreementClosed",
+    "PaymentFailed",
+    "PaymentTermsStatusChanged",
+    "ConfigInitialized",
+    "PayeeInitialized",
+    "PaymentTermsCreated",
+    "ProgramPaused",
+    "ProgramUnpaused",
+    "LowAllowanceWarning",
+    "FeesWithdrawn",
+    "DelegateMismatchWarning",
+    "ConfigUpdated",
+    "VolumeTierUpgraded",
+    "PaymentTermsUpdated",
+    "PayeeAuthorityTransferInitiated",
+    "PayeeAuthorityTransferred",
+    "PayeeAuthorityTransferCancelled",
+    "CreditApplied",
+    "TermsSunset",
+    "EscrowReleased",
+    "EscrowRefunded",
+    "KeeperPaidInSol",
+    "AgreementSuspended",
+    "AgreementUnsuspended",
+    "DepositHeld",
+    "DepositClaimed",
+    "DepositReleased",
+    "Swept",
+    "AgreementSnapshot",
+];
+
+const PAYEES: usize = 3;
+const TERMS_PER_PAYEE: usize = 2;
+const PAYERS: usize = 8;
+const TERMS_AMOUNTS: [u64; 4] = [5_000_000, 10_000_000, 29_990_000, 99_000_000];
+const TERMS_PERIODS: [u64; 3] = [604_800, 2_592_000, 31_536_000];
+
+/// Seedable generator of realistic Tally events
+#[derive(Clone, Debug)]
+pub struct EventFactory {
+    rng: StdRng,
+    platform_authority: Pubkey,
+    payees: Vec<Pubkey>,
+    terms: Vec<(Pubkey, Pubkey, u64)>,
+    payers: Vec<Pubkey>,
+    now: i64,
+    slot: u64,
+}
+
+impl EventFactory {
+    /// Default timestamp of the first generated event (2024-01-01T00:00:00Z)
+    pub const DEFAULT_START_TS: i64 = 1_704_067_200;
+
+    /// Create a factory whose output is fully determined by `seed`
+    #[must_use]
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn new(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let platform_authority = Pubkey::new_from_array(rng.gen());
+        let payees: Vec<Pubkey> = (0..PAYEES)
+            .map(|_| Pubkey::new_from_array(rng.gen()))
+            .collect();
+        let terms = payees
+            .iter()
+            .flat_map(|payee| std::iter::repeat_n(*payee, TERMS_PER_PAYEE))
+            .map(|payee| {
+                let terms = Pubkey::new_from_array(rng.gen());
+                let amount = TERMS_AMOUNTS[rng.gen_range(0..TERMS_AMOUNTS.len())];
+                (payee, terms, amount)
+            })
+            .collect();
+        let payers = (0..PAYERS)
+            .map(|_| Pubkey::new_from_array(rng.gen()))
+            .collect();
+
+        Self {
+            rng,
+            platform_authority,
+            payees,
+            terms,
+            payers,
+            now: Self::DEFAULT_START_TS,
+            slot: 250_000_000,
+        }
+    }
+
+    /// Start the generated timeline at `start_ts` instead of [`Self::DEFAULT_START_TS`]
+    #[must_use]
+    pub const fn with_start_ts(mut self, start_ts: i64) -> Self {
+        self.now = start_ts;
+        self
+    }
+
+    /// Payee PDAs events are drawn from
+    #[must_use]
+    pub fn payees(&self) -> &[Pubkey] {
+        &self.payees
+    }
+
+    /// Payer wallets events are drawn from
+    #[must_use]
+    pub fn payers(&self) -> &[Pubkey] {
+        &self.payers
+    }
+
+    /// Generate an event of a random type
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn next_event(&mut self) -> TallyEvent {
+        let event_type = EVENT_TYPES[self.rng.gen_range(0..EVENT_TYPES.len())];
+        self.event(event_type).unwrap_or_else(|| {
+            let (payee, payment_terms, amount) = self.pick_terms();
+            let payer = self.pick_payer();
+            self.payment_executed(payee, payment_terms, payer, amount)
+        })
+    }
+
+    /// Generate an event of a random type with transaction context
+    ///
+    /// Block time advances by up to an hour and the slot by the matching number of
+    /// 400ms slots between calls.
+    pub fn next_event_with_context(&mut self) -> ParsedEventWithContext {
+        let elapsed_secs: u16 = self.rng.gen_range(1..=3_600);
+        self.now = self.now.saturating_add(i64::from(elapsed_secs));
+        self.slot = self
+            .slot
+            .saturating_add(u64::from(elapsed_secs).saturating_mul(5) / 2);
+
+        let event = self.next_event();
+        let mut signature = [0u8; 64];
+        self.rng.fill(&mut signature[..]);
+
+        ParsedEventWithContext::new(
+            Signature::from(signature),
+            self.slot,
+            Some(self.now),
+            true,
+            event,
+            self.rng.gen_range(0..4),
+        )
+    }
+
+    /// Generate one event of each type, in the order of [`EVENT_TYPES`]
+    pub fn one_of_each(&mut self) -> Vec<TallyEvent> {
+        EVENT_TYPES
+            .into_iter()
+            .filter_map(|event_type| self.event(event_type))
+            .collect()
+    }
+
+    /// Generate an event of the named type, or `None` for an unknown name
+    pub fn event(&mut self, event_type: &str) -> Option<TallyEvent> {
+        self.agreement_event(event_type)
+            .or_else(|| self.payee_event(event_type))
+            .or_else(|| self.platform_event(event_type))
+            .or_else(|| self.escrow_event(event_type))
+    }
+
+    #[allow(clippy::similar_names, clippy::too_many_lines)] // payer and payee are distinct payment domain terms
+    fn agreement_event(&mut self, event_type: &str) -> Option<TallyEvent> {
+        let (payee, payment_terms, amount) = self.pick_terms();
+        let payer = self.pick_payer();
+        let event = match event_type {
+            "PaymentAgreementStarted" => {
+                let periods: u64 = self.rng.gen_range(1..=12);
+                TallyEvent::PaymentAgreementStarted(PaymentAgreementStarted {
+                    payee,
+                    payment_terms,
+                    payer,
+                    amount,
+                    external_ref_hash: self.rng.gen_bool(0.25).then(|| self.rng.gen()),
+                    delegated_amount: amount.saturating_mul(periods),
+                })
+            }
+            "PaymentAgreementResumed" => {
+                TallyEvent::PaymentAgreementResumed(PaymentAgreementResumed {
+                    payee,
+                    payment_terms,
+                    payer,
+                    amount,
+                    total_payments: self.rng.gen_range(1..48),
+                    original_created_ts: self.past_ts(),
+                })
+            }
+            "PaymentExecuted" => self.payment_executed(payee, payment_terms, payer, amount),
+            "KeeperPaidInSol" => self.keeper_paid_in_sol(payee, payment_terms, payer, amount),
+            "Swept" => self.swept(payee, payment_terms, payer, amount),
+            "PaymentAgreementPaused" => {
+                TallyEvent::PaymentAgreementPaused(PaymentAgreementPaused {
+                    payee,
+                    payment_terms,
+                    payer,
+                })
+            }
+            "PaymentAgreementClosed" => {
+                TallyEvent::PaymentAgreementClosed(PaymentAgreementClosed {
+                    payment_terms,
+                    payer,
+                })
+            }
+            "PaymentFailed" => TallyEvent::PaymentFailed(PaymentFailed {
+                payee,
+                payment_terms,
+                payer,
+                reason: ["InsufficientFunds", "InsufficientAllowance", "Inactive"]
+                    [self.rng.gen_range(0..3)]
+                .to_string(),
+            }),
+            "LowAllowanceWarning" => TallyEvent::LowAllowanceWarning(LowAllowanceWarning {
+                payee,
+                payment_terms,
+                payer,
+                current_allowance: amount.saturating_mul(self.rng.gen_range(1..3)),
+                recommended_allowance: amount.saturating_mul(3),
+                payment_amount: amount,
+            }),
+            "DelegateMismatchWarning" => {
+                TallyEvent::DelegateMismatchWarning(DelegateMismatchWarning {
+                    payee,
+                    payment_terms,
+                    payer,
+                    expected_delegate: Pubkey::new_from_array(self.rng.gen()),
+                    actual_delegate: self
+                        .rng
+                        .gen_bool(0.5)
+                        .then(|| Pubkey::new_from_array(self.rng.gen())),
+                })
+            }
+            "CreditApplied" => {
+                let credit_applied = self.rng.gen_range(0..=amount);
+                TallyEvent::CreditApplied(CreditApplied {
+                    payee,
+                    payment_terms,
+                    payer,
+                    amount,
+                    credit_applied,
+                    amount_charged: amount.saturating_sub(credit_applied),
+                    remaining_credit: 0,
+                    paused_at_ts: self.past_ts(),
+                })
+            }
+            "TermsSunset" => TallyEvent::TermsSunset(TermsSunset {
+                payee,
+                payment_terms,
+                payer,
+                sunset_ts: self.now,
+                timestamp: self.now,
+            }),
+            "AgreementSuspended" => TallyEvent::AgreementSuspended(AgreementSuspended {
+                payee,
+                payment_terms,
+                payer,
+                reason: crate::program_types::SuspensionReason::FrozenAccount,
+                payer_token_account: Pubkey::new_from_array(self.rng.gen()),
+                timestamp: self.now,
+            }),
+            "AgreementUnsuspended" => TallyEvent::AgreementUnsuspended(AgreementUnsuspended {
+                payee,
+                payment_terms,
+                payer,
+                next_payment_ts: self.now,
+                timestamp: self.now,
+            }),
+            "AgreementSnapshot" => TallyEvent::AgreementSnapshot(AgreementSnapshot {
+                payment_agreement: crate::pda::payment_agreement_address_with_program_id(
+                    &payment_terms,
+                    &payer,
+                    &crate::program_id(),
+                ),
+                status: crate::program_types::AgreementStatus::Active,
+                next_payment_ts: self.now,
+                period_index: self.rng.gen_range(0..48),
+            }),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn escrow_event(&mut self, event_type: &str) -> Option<TallyEvent> {
+        let (payee, payment_terms, amount) = self.pick_terms();
+        let payer = self.pick_payer();
+        let event = match event_type {
+            "EscrowReleased" => {
+                // 0.25% platform fee, matching the Standard tier
+                let split = split_payment(amount, 0, 25).ok()?;
+                TallyEvent::EscrowReleased(EscrowReleased {
+                    payee,
+                    payment_terms,
+                    payer,
+                    amount,
+                    platform_fee: split.platform_fee,
+                    payee_amount: split.payee_amount,
+                    timestamp: self.now,
+                })
+            }
+            "EscrowRefunded" => TallyEvent::EscrowRefunded(EscrowRefunded {
+                payee,
+                payment_terms,
+                payer,
+                amount,
+                expires_ts: self.past_ts(),
+                timestamp: self.now,
+            }),
+            "DepositHeld" => TallyEvent::DepositHeld(DepositHeld {
+                payee,
+                payment_terms,
+                payer,
+                amount,
+                total_held: amount,
+                timestamp: self.now,
+            }),
+            "DepositClaimed" => {
+                let claimed = self.rng.gen_range(1..=amount);
+                TallyEvent::DepositClaimed(DepositClaimed {
+                    payee,
+                    payment_terms,
+                    payer,
+                    amount: claimed,
+                    remaining: amount.saturating_sub(claimed),
+                    timestamp: self.now,
+                })
+            }
+            "DepositReleased" => TallyEvent::DepositReleased(DepositReleased {
+                payee,
+                payment_terms,
+                payer,
+                amount,
+                timestamp: self.now,
+            }),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    fn payee_event(&mut self, event_type: &str) -> Option<TallyEvent> {
+        let (payee, payment_terms, amount) = self.pick_terms();
+        let authority = Pubkey::new_from_array(self.rng.gen());
+        let new_authority = Pubkey::new_from_array(self.rng.gen());
+        let event = match event_type {
+            "PaymentTermsStatusChanged" => {
+                TallyEvent::PaymentTermsStatusChanged(PaymentTermsStatusChanged {
+                    payee,
+                    payment_terms,
+                    active: self.rng.gen_bool(0.5),
+                    changed_by: "payee".to_string(),
+                })
+            }
+            "PayeeInitialized" => TallyEvent::PayeeInitialized(PayeeInitialized {
+                payee,
+                authority,
+                usdc_mint: crate::cluster::MAINNET_USDC_MINT,
+                treasury_ata: Pubkey::new_from_array(self.rng.gen()),
+                platform_fee_bps: 25,
+                timestamp: self.now,
+            }),
+            "PaymentTermsCreated" => {
+                let terms_number: u16 = self.rng.gen();
+                TallyEvent::PaymentTermsCreated(PaymentTermsCreated {
+                    payment_terms,
+                    payee,
+                    terms_id: format!("plan_{terms_number}"),
+                    amount_usdc: amount,
+                    period_secs: TERMS_PERIODS[self.rng.gen_range(0..TERMS_PERIODS.len())],
+                    grace_secs: 0,
+                    name: format!("Plan {terms_number}"),
+                    timestamp: self.now,
+                })
+            }
+            "VolumeTierUpgraded" => {
+                let (old_tier, new_tier, monthly_volume_usdc, new_platform_fee_bps) =
+                    if self.rng.gen_bool(0.5) {
+                        (VolumeTier::Standard, VolumeTier::Growth, 10_000_000_000, 20)
+                    } else {
+                        (VolumeTier::Growth, VolumeTier::Scale, 100_000_000_000, 15)
+                    };
+                TallyEvent::VolumeTierUpgraded(VolumeTierUpgraded {
+                    payee,
+                    old_tier,
+                    new_tier,
+                    monthly_volume_usdc,
+                    new_platform_fee_bps,
+                })
+            }
+            "PaymentTermsUpdated" => {
+                let new_price = amount.saturating_add(1_000_000);
+                TallyEvent::PaymentTermsUpdated(PaymentTermsUpdated {
+                    payment_terms,
+                    payee,
+                    old_price: Some(amount),
+                    new_price: Some(new_price),
+                    old_period: None,
+                    new_period: None,
+                    old_grace: None,
+                    new_grace: None,
+                    updated_by: authority,
+                })
+            }
+            "PayeeAuthorityTransferInitiated" => {
+                TallyEvent::PayeeAuthorityTransferInitiated(PayeeAuthorityTransferInitiated {
+                    payee,
+                    authority,
+                    new_authority,
+                    timestamp: self.now,
+                })
+            }
+            "PayeeAuthorityTransferred" => {
+                TallyEvent::PayeeAuthorityTransferred(PayeeAuthorityTransferred {
+                    payee,
+                    old_authority: authority,
+                    new_authority,
+                    treasury_ata: Pubkey::new_from_array(self.rng.gen()),
+                    timestamp: self.now,
+                })
+            }
+            "PayeeAuthorityTransferCancelled" => {
+                TallyEvent::PayeeAuthorityTransferCancelled(PayeeAuthorityTransferCancelled {
+                    payee,
+                    authority,
+                    cancelled_authority: new_authority,
+                    timestamp: self.now,
+                })
+            }
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    fn platform_event(&mut self, event_type: &str) -> Option<TallyEvent> {
+        let platform_authority = self.platform_authority;
+        let event = match event_type {
+            "ConfigInitialized" => TallyEvent::ConfigInitialized(ConfigInitialized {
+                platform_authority,
+                max_platform_fee_bps: 50,
+                min_platform_fee_bps: 10,
+                min_period_seconds: 86_400,
+                default_allowance_periods: 3,
+                allowed_mint: crate::cluster::MAINNET_USDC_MINT,
+                max_withdrawal_amount: 1_000_000_000_000,
+                max_grace_period_seconds: 604_800,
+                timestamp: self.now,
+            }),
+            "ConfigUpdated" => TallyEvent::ConfigUpdated(ConfigUpdated {
+                keeper_fee_bps: self.rng.gen_range(0..=100),
+                max_withdrawal_amount: 1_000_000_000_000,
+                max_grace_period_seconds: 604_800,
+                min_platform_fee_bps: 10,
+                max_platform_fee_bps: 50,
+                renewal_tolerance_secs: 60,
+                updated_by: platform_authority,
+            }),
+            "ProgramPaused" => {
+                let mut reason = [0u8; 64];
+                let text = b"Scheduled maintenance";
+                reason[..text.len()].copy_from_slice(text);
+                TallyEvent::ProgramPaused(ProgramPaused {
+                    authority: platform_authority,
+                    timestamp: self.now,
+                    reason,
+                    auto_unpause_ts: Some(self.now.saturating_add(3_600)),
+                })
+            }
+            "ProgramUnpaused" => TallyEvent::ProgramUnpaused(ProgramUnpaused {
+                authority: platform_authority,
+                timestamp: self.now,
+            }),
+            "FeesWithdrawn" => TallyEvent::FeesWithdrawn(FeesWithdrawn {
+                platform_authority,
+                destination: Pubkey::new_from_array(self.rng.gen()),
+                amount: self.rng.gen_range(1_000_000..1_000_000_000),
+                timestamp: self.now,
+            }),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn payment_executed(
+        &mut self,
+        payee: Pubkey,
+        payment_terms: Pubkey,
+        payer: Pubkey,
+        amount: u64,
+    ) -> TallyEvent {
+        TallyEvent::PaymentExecuted(PaymentExecuted {
+            payee,
+            payment_terms,
+            payer,
+            amount,
+            keeper: Pubkey::new_from_array(self.rng.gen()),
+            // 0.15% keeper fee, matching the default `keeper_fee_bps`
+            keeper_fee: amount.saturating_mul(15) / 10_000,
+            period_index: self.rng.gen_range(1..24),
+        })
+    }
+
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn keeper_paid_in_sol(
+        &mut self,
+        payee: Pubkey,
+        payment_terms: Pubkey,
+        payer: Pubkey,
+        amount: u64,
+    ) -> TallyEvent {
+        // 0.15% keeper fee at roughly 200 USDC per SOL
+        let keeper_fee = amount.saturating_mul(15) / 10_000;
+        let lamports_per_usdc = self.rng.gen_range(4_000_000..6_000_000);
+        TallyEvent::KeeperPaidInSol(KeeperPaidInSol {
+            payee,
+            payment_terms,
+            payer,
+            keeper: Pubkey::new_from_array(self.rng.gen()),
+            keeper_fee,
+            lamports_per_usdc,
+            lamports: keeper_fee.saturating_mul(lamports_per_usdc) / 1_000_000,
+        })
+    }
+
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn swept(
+        &mut self,
+        payee: Pubkey,
+        payment_terms: Pubkey,
+        payer: Pubkey,
+        amount: u64,
+    ) -> TallyEvent {
+        // Sweeps cover anywhere from a minute to a full day of accrual
+        let to_ts = self.now;
+        let from_ts = to_ts.saturating_sub(self.rng.gen_range(60..=86_400));
+        TallyEvent::Swept(Swept {
+            payee,
+            payment_terms,
+            payer,
+            keeper: Pubkey::new_from_array(self.rng.gen()),
+            amount,
+            // 0.15% keeper fee, matching the default `keeper_fee_bps`
+            keeper_fee: amount.saturating_mul(15) / 10_000,
+            from_ts,
+            to_ts,
+        })
+    }
+
+    fn pick_terms(&mut self) -> (Pubkey, Pubkey, u64) {
+        self.terms[self.rng.gen_range(0..self.terms.len())]
+    }
+
+    fn pick_payer(&mut self) -> Pubkey {
+        self.payers[self.rng.gen_range(0..self.payers.len())]
+    }
+
+    fn past_ts(&mut self) -> i64 {
+        self.now
+            .saturating_sub(self.rng.gen_range(86_400..31_536_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_events() {
+        let mut a = EventFactory::new(7);
+        let mut b = EventFactory::new(7);
+
+        for _ in 0..50 {
+            let (x, y) = (a.next_event_with_context(), b.next_event_with_context());
+            assert_eq!(x.signature, y.signature);
+            assert_eq!(x.block_time, y.block_time);
+            assert_eq!(
+                serde_json::to_string(&x.event).unwrap(),
+                serde_json::to_string(&y.event).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_different_seeds_differ() {
+        assert_ne!(EventFactory::new(1).payees(), EventFactory::new(2).payees());
+    }
+
+    #[test]
+    fn test_one_of_each_covers_every_event_type() {
+        let events = EventFactory::new(3).one_of_each();
+        let names: Vec<String> = events
+            .into_iter()
+            .map(|event| {
+                ParsedEventWithContext::new(Signature::default(), 0, None, true, event, 0)
+                    .get_event_type_string()
+            })
+            .collect();
+
+        assert_eq!(names, EVENT_TYPES);
+        assert!(EventFactory::new(3).event("TrialStarted").is_none());
+    }
+
+    #[test]
+    fn test_events_use_factory_population() {
+        let mut factory = EventFactory::new(11);
+        for _ in 0..20 {
+            if let Some(TallyEvent::PaymentExecuted(e)) = factory.event("PaymentExecuted") {
+                assert!(factory.payees().contains(&e.payee));
+                assert!(factory.payers().contains(&e.payer));
+            }
+        }
+    }
+
+    #[test]
+    fn test_timeline_advances() {
+        let mut factory = EventFactory::new(5).with_start_ts(1_000);
+        let first = factory.next_event_with_context();
+        let second = factory.next_event_with_context();
+
+        assert!(first.block_time.unwrap() > 1_000);
+        assert!(second.block_time > first.block_time);
+        assert!(second.slot > first.slot);
+    }
+}
@@ -1,13 +1,23 @@
 //! Transaction building utilities for Tally payment agreement flows
 
 use crate::{
-    ata::{get_associated_token_address_with_program, TokenProgram},
+    ata::{
+        create_associated_token_account_idempotent_instruction,
+        get_associated_token_address_with_program, TokenProgram,
+    },
     error::{Result, TallyError},
+    events::DelegateMismatchWarning,
     pda, program_id,
     program_types::{
         PauseAgreementArgs, CreatePaymentTermsArgs,
-        StartAgreementArgs, Payee, PaymentTerms, InitPayeeArgs,
+        StartAgreementArgs, Payee, PaymentTerms, InitPayeeArgs, ReserveSlotArgs,
+        ScheduleCancellationArgs, ScheduleTermsUpdateArgs, InitiateAgreementTransferArgs,
+        AcceptAgreementTransferArgs, RefundPaymentArgs, ClaimDepositArgs, SetKeeperPolicyArgs,
+        ConfirmActivationArgs, RefundEscrowArgs, PaymentAgreement, ResumeAfterUnfreezeArgs,
+        TransferPayeeAuthorityArgs, AcceptPayeeAuthorityArgs, CancelPayeeAuthorityTransferArgs,
+        ResumeAgreementArgs, DeactivatePaymentTermsArgs,
     },
+    transaction_utils::{create_memo_instruction, validate_customer_memo},
 };
 
 #[cfg(feature = "platform-admin")]
@@ -20,14 +30,52 @@ use spl_token_2022::instruction::{
     approve_checked as approve_checked_token2022, revoke as revoke_token2022,
 };
 
+/// Record the resolved accounts on the current `build_instructions` span
+fn record_build_fields(program_id: &Pubkey, payment_terms: &Pubkey, payer: &Pubkey) {
+    let span = tracing::Span::current();
+    span.record("program_id", tracing::field::display(program_id));
+    span.record("payment_terms", tracing::field::display(payment_terms));
+    span.record("payer", tracing::field::display(payer));
+}
+
+/// Idempotent create instructions for the given `(token account, owner)` pairs
+///
+/// Token accounts that are not the owner's associated token account for `mint` are
+/// skipped, since they cannot be created on the owner's behalf.
+fn ensure_ata_instructions(
+    funder: &Pubkey,
+    accounts: &[(Pubkey, Pubkey)],
+    mint: &Pubkey,
+    token_program: TokenProgram,
+) -> Result<Vec<Instruction>> {
+    let mut instructions = Vec::new();
+    for (token_account, owner) in accounts {
+        if get_associated_token_address_with_program(owner, mint, token_program)? == *token_account {
+            instructions.push(create_associated_token_account_idempotent_instruction(
+                funder,
+                owner,
+                mint,
+                token_program,
+            )?);
+        }
+    }
+    Ok(instructions)
+}
+
 /// Builder for start agreement transactions (approve → start flow)
 #[derive(Clone, Debug, Default)]
 pub struct StartAgreementBuilder {
     payment_terms: Option<Pubkey>,
     payer: Option<Pubkey>,
     allowance_periods: Option<u8>,
+    renewal_bucket: Option<u64>,
+    max_periods: Option<u16>,
+    external_ref_hash: Option<[u8; 32]>,
+    ensure_atas: bool,
     token_program: Option<TokenProgram>,
     program_id: Option<Pubkey>,
+    #[cfg(feature = "swap")]
+    funding_swap: Option<crate::swap::FundingSwap>,
 }
 
 /// Builder for pause agreement transactions (revoke → cancel flow)
@@ -39,6 +87,28 @@ pub struct PauseAgreementBuilder {
     program_id: Option<Pubkey>,
 }
 
+/// Builder for resume agreement transactions (approve → resume flow)
+#[derive(Clone, Debug, Default)]
+pub struct ResumeAgreementBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    allowance_periods: Option<u8>,
+    renewal_bucket: Option<u64>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for delegate repair transactions (revoke → `approve_checked` flow)
+#[derive(Clone, Debug, Default)]
+pub struct RepairDelegateBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    current_delegate: Option<Pubkey>,
+    allowance_periods: Option<u8>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
 /// Builder for init payee transactions
 #[derive(Clone, Debug, Default)]
 pub struct InitPayeeBuilder {
@@ -53,12 +123,33 @@ pub struct InitPayeeBuilder {
 #[derive(Clone, Debug, Default)]
 pub struct CreatePaymentTermsBuilder {
     authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
     payer: Option<Pubkey>,
     payment_terms_args: Option<CreatePaymentTermsArgs>,
     program_id: Option<Pubkey>,
 }
 
 
+/// Builder for schedule terms update transactions
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleTermsUpdateBuilder {
+    authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
+    payment_terms: Option<Pubkey>,
+    schedule_args: Option<ScheduleTermsUpdateArgs>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for deactivate payment terms transactions
+#[derive(Clone, Debug, Default)]
+pub struct DeactivatePaymentTermsBuilder {
+    authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
+    payment_terms: Option<Pubkey>,
+    sunset_ts: Option<i64>,
+    program_id: Option<Pubkey>,
+}
+
 /// Builder for admin fee withdrawal transactions
 #[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
 #[derive(Clone, Debug, Default)]
@@ -91,6 +182,23 @@ pub struct ExecutePaymentBuilder {
     payer: Option<Pubkey>,
     keeper: Option<Pubkey>,
     keeper_ata: Option<Pubkey>,
+    next_payment_ts: Option<i64>,
+    keeper_fee_in_sol: bool,
+    ensure_atas: bool,
+    memo: Option<String>,
+    test_clock: bool,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for transactions sweeping the accrual of a streaming agreement
+#[derive(Clone, Debug, Default)]
+pub struct SweepAccruedBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    keeper: Option<Pubkey>,
+    keeper_ata: Option<Pubkey>,
+    test_clock: bool,
     token_program: Option<TokenProgram>,
     program_id: Option<Pubkey>,
 }
@@ -100,6 +208,150 @@ pub struct ExecutePaymentBuilder {
 pub struct CloseAgreementBuilder {
     payment_terms: Option<Pubkey>,
     payer: Option<Pubkey>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for transactions closing several payment agreements of one payer
+#[derive(Clone, Debug, Default)]
+pub struct CloseAgreementsBatchBuilder {
+    payer: Option<Pubkey>,
+    payment_terms: Vec<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for transactions upgrading an account to the current layout version
+#[derive(Clone, Debug, Default)]
+pub struct MigrateAccountBuilder {
+    account: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for schedule cancellation transactions
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleCancellationBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    payee_authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for reserve slot (waitlist) transactions
+#[derive(Clone, Debug, Default)]
+pub struct ReserveSlotBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    payee_authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for initiate agreement transfer transactions (step one of a payer wallet change)
+#[derive(Clone, Debug, Default)]
+pub struct InitiateAgreementTransferBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    new_payer: Option<Pubkey>,
+    payee_authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for accept agreement transfer transactions (approve → accept flow)
+#[derive(Clone, Debug, Default)]
+pub struct AcceptAgreementTransferBuilder {
+    payment_terms: Option<Pubkey>,
+    old_payer: Option<Pubkey>,
+    new_payer: Option<Pubkey>,
+    next_payment_ts: Option<i64>,
+    allowance_periods: Option<u8>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for refund payment transactions (payee authority refunds the payer)
+#[derive(Clone, Debug, Default)]
+pub struct RefundPaymentBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    amount: Option<u64>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for claim deposit transactions (payee claims from a held security deposit)
+#[derive(Clone, Debug, Default)]
+pub struct ClaimDepositBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    amount: Option<u64>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for confirm activation transactions (payee releases an escrowed first payment)
+#[derive(Clone, Debug, Default)]
+pub struct ConfirmActivationBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for refund escrow transactions (lapsed escrowed first payment back to the payer)
+#[derive(Clone, Debug, Default)]
+pub struct RefundEscrowBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    caller: Option<Pubkey>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for resume after unfreeze transactions (reinstating an agreement suspended
+/// for a frozen token account)
+#[derive(Clone, Debug, Default)]
+pub struct ResumeAfterUnfreezeBuilder {
+    payment_terms: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    caller: Option<Pubkey>,
+    renewal_bucket: Option<u64>,
+    token_program: Option<TokenProgram>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for set keeper policy transactions (payee keeper allow-list)
+#[derive(Clone, Debug, Default)]
+pub struct SetKeeperPolicyBuilder {
+    authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
+    open_execution: Option<bool>,
+    authorized_keepers: Vec<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for transfer payee authority transactions
+#[derive(Clone, Debug, Default)]
+pub struct TransferPayeeAuthorityBuilder {
+    authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
+    new_authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for accept payee authority transactions
+#[derive(Clone, Debug, Default)]
+pub struct AcceptPayeeAuthorityBuilder {
+    new_authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
+    usdc_mint: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for cancel payee authority transfer transactions
+#[derive(Clone, Debug, Default)]
+pub struct CancelPayeeAuthorityTransferBuilder {
+    authority: Option<Pubkey>,
+    original_authority: Option<Pubkey>,
     program_id: Option<Pubkey>,
 }
 
@@ -133,6 +385,8 @@ pub struct CancelAuthorityTransferBuilder {
 #[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
 pub struct PauseBuilder {
     platform_authority: Option<Pubkey>,
+    reason: Option<String>,
+    auto_unpause_ts: Option<i64>,
     program_id: Option<Pubkey>,
 }
 
@@ -144,6 +398,53 @@ pub struct UnpauseBuilder {
     program_id: Option<Pubkey>,
 }
 
+/// Builder for freeze payee transactions
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
+pub struct FreezePayeeBuilder {
+    platform_authority: Option<Pubkey>,
+    payee_authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for unfreeze payee transactions
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
+pub struct UnfreezePayeeBuilder {
+    platform_authority: Option<Pubkey>,
+    payee_authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for set fee holiday transactions
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
+pub struct SetFeeHolidayBuilder {
+    platform_authority: Option<Pubkey>,
+    payee_authority: Option<Pubkey>,
+    until_ts: Option<i64>,
+    rebate_bps: Option<u16>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for set keeper SOL rate transactions
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
+pub struct SetKeeperSolRateBuilder {
+    platform_authority: Option<Pubkey>,
+    lamports_per_usdc: Option<u64>,
+    program_id: Option<Pubkey>,
+}
+
+/// Builder for advance test clock transactions
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "test-clock"), allow(dead_code))]
+pub struct AdvanceTestClockBuilder {
+    platform_authority: Option<Pubkey>,
+    secs: Option<u64>,
+    program_id: Option<Pubkey>,
+}
+
 /// Builder for update config transactions
 #[cfg_attr(not(feature = "platform-admin"), allow(dead_code))]
 #[derive(Clone, Debug, Default)]
@@ -156,6 +457,7 @@ pub struct UpdateConfigBuilder {
     max_platform_fee_bps: Option<u16>,
     min_period_seconds: Option<u64>,
     default_allowance_periods: Option<u8>,
+    renewal_tolerance_secs: Option<u64>,
     program_id: Option<Pubkey>,
 }
 
@@ -188,6 +490,50 @@ impl StartAgreementBuilder {
         self
     }
 
+    /// Limit the agreement to a fixed number of billing periods (installment plans)
+    ///
+    /// The initial payment counts as the first period; the agreement completes after
+    /// charging the last one. Must be at least 2.
+    #[must_use]
+    pub const fn max_periods(mut self, max_periods: u16) -> Self {
+        self.max_periods = Some(max_periods);
+        self
+    }
+
+    /// Link the agreement to the payee's internal customer reference
+    ///
+    /// Stores the hash on the agreement and emits it in `PaymentAgreementStarted`.
+    /// Compute it with [`crate::utils::hash_external_ref`] so the customer id itself
+    /// never appears on-chain.
+    #[must_use]
+    pub const fn external_ref_hash(mut self, external_ref_hash: [u8; 32]) -> Self {
+        self.external_ref_hash = Some(external_ref_hash);
+        self
+    }
+
+    /// Prepend idempotent creation of the payer's USDC ATA and the payee treasury ATA
+    ///
+    /// The payer funds any account that does not exist yet; existing accounts are left
+    /// untouched. For payment terms with an escrow window, the program delegate's escrow
+    /// ATA is created as well. The platform treasury ATA is maintained by the platform
+    /// and is not created here.
+    #[must_use]
+    pub const fn ensure_atas(mut self, ensure_atas: bool) -> Self {
+        self.ensure_atas = ensure_atas;
+        self
+    }
+
+    /// Set the renewal queue bucket of the agreement's next payment
+    ///
+    /// Defaults to the bucket of the current time plus the payment period. The program
+    /// rejects the transaction if it lands in a different bucket, so override this only
+    /// when building against a known landing time.
+    #[must_use]
+    pub const fn renewal_bucket(mut self, bucket: u64) -> Self {
+        self.renewal_bucket = Some(bucket);
+        self
+    }
+
     /// Set the token program to use
     #[must_use]
     pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
@@ -202,6 +548,38 @@ impl StartAgreementBuilder {
         self
     }
 
+    /// Fund the payer's USDC account with a Jupiter swap in the same transaction
+    ///
+    /// The swap instructions are placed before `approve_checked`. Building fails unless
+    /// the swap outputs the payee's USDC mint, stays within its slippage limit, and its
+    /// minimum output covers the full allowance amount.
+    #[cfg(feature = "swap")]
+    #[must_use]
+    pub fn with_funding_swap(mut self, swap: crate::swap::FundingSwap) -> Self {
+        self.funding_swap = Some(swap);
+        self
+    }
+
+    /// Run the `start_agreement` preflight check before building
+    ///
+    /// Checks the payer's USDC and SOL balances, token account, any existing agreement
+    /// and the program, payee and terms state via [`crate::preflight::check_start_agreement`]
+    /// (using the client's program ID), and returns the builder unchanged if the
+    /// transaction is expected to succeed.
+    ///
+    /// # Errors
+    /// Returns an error if payment terms or payer are not set, an RPC call fails, or the
+    /// preflight check finds any issue (all issues are listed in the message)
+    pub fn validated(self, client: &crate::SimpleTallyClient) -> Result<Self> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        crate::preflight::check_start_agreement(client, &payer, &payment_terms, token_program)?
+            .into_result()?;
+        Ok(self)
+    }
+
     /// Build the transaction instructions
     ///
     /// # Arguments
@@ -210,9 +588,18 @@ impl StartAgreementBuilder {
     /// * `platform_treasury_ata` - Platform treasury ATA address
     ///
     /// # Returns
-    /// * `Ok(Vec<Instruction>)` - The transaction instructions (`approve_checked` + `start_payment_agreement`)
+    /// * `Ok(Vec<Instruction>)` - The transaction instructions (`approve_checked` + `start_payment_agreement`,
+    ///   preceded by the funding swap when one is set and by ATA creation with `ensure_atas`)
     /// * `Err(TallyError)` - If building fails
     #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    #[allow(clippy::too_many_lines)]
+    #[tracing::instrument(
+        name = "build_instructions",
+        level = "debug",
+        skip_all,
+        fields(instruction = "start_agreement", program_id, payment_terms, payer),
+        err
+    )]
     pub fn build_instructions(
         self,
         payee: &Payee,
@@ -225,10 +612,15 @@ impl StartAgreementBuilder {
         let token_program = self.token_program.unwrap_or(TokenProgram::Token);
 
         let program_id = self.program_id.unwrap_or_else(program_id);
+        record_build_fields(&program_id, &payment_terms, &payer);
+
+        if matches!(self.max_periods, Some(max_periods) if max_periods < 2) {
+            return Err("Max periods must be at least 2".into());
+        }
 
         // Compute required PDAs
         let config_pda = pda::config_address_with_program_id(&program_id);
-        let payee_pda = pda::payee_address_with_program_id(&payee.authority, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
         let payment_agreement_pda =
             pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
         let delegate_pda = pda::delegate_address_with_program_id(&program_id);
@@ -237,6 +629,29 @@ impl StartAgreementBuilder {
             &payee.usdc_mint,
             token_program,
         )?;
+        let escrow_ata = get_associated_token_address_with_program(
+            &delegate_pda,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        // The program indexes the agreement under the bucket of its next payment
+        let renewal_bucket = if let Some(bucket) = self.renewal_bucket {
+            bucket
+        } else {
+            let now = chrono::Utc::now().timestamp();
+            let period = i64::try_from(payment_terms_data.period_secs_at(now))
+                .map_err(|_| TallyError::Generic("Arithmetic overflow".to_string()))?;
+            now.checked_add(period)
+                .and_then(pda::renewal_bucket)
+                .ok_or("Invalid renewal bucket")?
+        };
+        let renewal_queue_pda =
+            pda::agreement_renewal_queue_address_with_program_id(
+                renewal_bucket,
+                &payment_agreement_pda,
+                &program_id,
+            );
 
         // Calculate allowance amount based on payment_terms price and periods
         let allowance_amount = payment_terms_data
@@ -272,20 +687,25 @@ impl StartAgreementBuilder {
         let start_sub_accounts = vec![
             AccountMeta::new_readonly(config_pda, false),   // config
             AccountMeta::new(payment_agreement_pda, false),      // payment agreement (PDA)
-            AccountMeta::new_readonly(payment_terms, false),         // payment_terms
-            AccountMeta::new_readonly(payee_pda, false), // payee
+            AccountMeta::new(payment_terms, false),         // payment_terms (mutable)
+            AccountMeta::new(payee_pda, false), // payee (mutable for agreement counters)
             AccountMeta::new(payer, true),                  // payer (signer)
             AccountMeta::new(payer_ata, false),        // payer_usdc_ata
             AccountMeta::new(payee.treasury_ata, false), // payee_treasury_ata
             AccountMeta::new(*platform_treasury_ata, false), // platform_treasury_ata
+            AccountMeta::new(escrow_ata, false),            // escrow_ata (used with an escrow window or deposit)
             AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
             AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new(renewal_queue_pda, false),      // renewal_queue (PDA, created if needed)
             AccountMeta::new_readonly(token_program.program_id(), false), // token_program
             AccountMeta::new_readonly(system_program::ID, false), // system_program
         ];
 
         let start_sub_args = StartAgreementArgs {
             allowance_periods,
+            renewal_bucket,
+            max_periods: self.max_periods,
+            external_ref_hash: self.external_ref_hash,
         };
         let start_sub_data = {
             let mut data = Vec::new();
@@ -302,7 +722,26 @@ impl StartAgreementBuilder {
             data: start_sub_data,
         };
 
-        Ok(vec![approve_ix, start_sub_ix])
+        let mut instructions = if self.ensure_atas {
+            let mut atas = vec![(payer_ata, payer), (payee.treasury_ata, payee.authority)];
+            if payment_terms_data.escrow_window_secs.is_some()
+                || payment_terms_data.deposit_usdc.is_some()
+            {
+                atas.push((escrow_ata, delegate_pda));
+            }
+            ensure_ata_instructions(&payer, &atas, &payee.usdc_mint, token_program)?
+        } else {
+            Vec::new()
+        };
+
+        #[cfg(feature = "swap")]
+        if let Some(swap) = self.funding_swap {
+            swap.validate(&payee.usdc_mint, allowance_amount)?;
+            instructions.extend_from_slice(swap.instructions());
+        }
+
+        instructions.extend([approve_ix, start_sub_ix]);
+        Ok(instructions)
     }
 }
 
@@ -343,19 +782,30 @@ impl PauseAgreementBuilder {
 
     /// Build the transaction instructions
     ///
+    /// The program revokes the delegate itself, except on streaming terms where keepers
+    /// may still sweep the accrual up to the pause.
+    ///
     /// # Arguments
     /// * `payee` - The payee account data
     ///
     /// # Returns
-    /// * `Ok(Vec<Instruction>)` - The transaction instructions (revoke + `cancel_payment_agreement`)
+    /// * `Ok(Vec<Instruction>)` - The transaction instructions (`pause_agreement`)
     /// * `Err(TallyError)` - If building fails
     #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    #[tracing::instrument(
+        name = "build_instructions",
+        level = "debug",
+        skip_all,
+        fields(instruction = "pause_agreement", program_id, payment_terms, payer),
+        err
+    )]
     pub fn build_instructions(self, payee: &Payee) -> Result<Vec<Instruction>> {
         let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
         let payer = self.payer.ok_or("Payer not set")?;
         let token_program = self.token_program.unwrap_or(TokenProgram::Token);
 
         let program_id = self.program_id.unwrap_or_else(program_id);
+        record_build_fields(&program_id, &payment_terms, &payer);
 
         // Compute required PDAs
         let payment_agreement_pda =
@@ -366,29 +816,17 @@ impl PauseAgreementBuilder {
             token_program,
         )?;
 
-        // Create revoke instruction using the correct token program
-        let revoke_ix = match token_program {
-            TokenProgram::Token => revoke_token(
-                &token_program.program_id(),
-                &payer_ata,
-                &payer,      // Payer as owner
-                &[],         // No additional signers
-            )?,
-            TokenProgram::Token2022 => revoke_token2022(
-                &token_program.program_id(),
-                &payer_ata,
-                &payer,      // Payer as owner
-                &[],         // No additional signers
-            )?,
-        };
-
         // Create cancel_payment_agreement instruction
-        let payee_pda = pda::payee_address_with_program_id(&payee.authority, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
         let cancel_sub_accounts = vec![
             AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA)
-            AccountMeta::new_readonly(payment_terms, false),    // payment_terms
-            AccountMeta::new_readonly(payee_pda, false), // payee
+            AccountMeta::new(payment_terms, false),             // payment_terms (mutable, releases subscriber slot)
+            AccountMeta::new(payee_pda, false), // payee (mutable for agreement counters)
             AccountMeta::new_readonly(payer, true),  // payer (signer)
+            AccountMeta::new(payer_ata, false),      // payer_usdc_ata (mutable)
+            AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
         ];
 
         let cancel_sub_args = PauseAgreementArgs {};
@@ -407,42 +845,52 @@ impl PauseAgreementBuilder {
             data: cancel_sub_data,
         };
 
-        Ok(vec![revoke_ix, cancel_sub_ix])
+        Ok(vec![cancel_sub_ix])
     }
 }
 
-impl InitPayeeBuilder {
-    /// Create a new init payee builder
+impl ResumeAgreementBuilder {
+    /// Create a new resume agreement builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the payee authority
+    /// Set the `payment_terms` PDA
     #[must_use]
-    pub const fn authority(mut self, authority: Pubkey) -> Self {
-        self.authority = Some(authority);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
         self
     }
 
-    /// Set the transaction payer
+    /// Set the payer pubkey (also sets as transaction payer)
     #[must_use]
     pub const fn payer(mut self, payer: Pubkey) -> Self {
         self.payer = Some(payer);
         self
     }
 
-    /// Set the USDC mint
+    /// Set the allowance periods multiplier (default 3)
     #[must_use]
-    pub const fn usdc_mint(mut self, usdc_mint: Pubkey) -> Self {
-        self.usdc_mint = Some(usdc_mint);
+    pub const fn allowance_periods(mut self, periods: u8) -> Self {
+        self.allowance_periods = Some(periods);
         self
     }
 
-    /// Set the treasury ATA
+    /// Set the renewal queue bucket of the agreement's next payment
+    ///
+    /// Defaults to the bucket of the current time plus the payment period, as in
+    /// [`StartAgreementBuilder::renewal_bucket`].
     #[must_use]
-    pub const fn treasury_ata(mut self, treasury_ata: Pubkey) -> Self {
-        self.treasury_ata = Some(treasury_ata);
+    pub const fn renewal_bucket(mut self, bucket: u64) -> Self {
+        self.renewal_bucket = Some(bucket);
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
         self
     }
 
@@ -453,7 +901,327 @@ impl InitPayeeBuilder {
         self
     }
 
-    /// Build the transaction instruction
+    /// Build the transaction instructions
+    ///
+    /// Pausing revokes the delegate of non-streaming agreements, so the delegate is
+    /// re-approved for the allowance before resuming. The agreement's pause credit is
+    /// applied on-chain.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `payment_terms_data` - The `payment_terms` account data
+    /// * `platform_treasury_ata` - Platform treasury ATA address
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Instruction>)` - The transaction instructions (`approve_checked` + `resume_agreement`)
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    #[tracing::instrument(
+        name = "build_instructions",
+        level = "debug",
+        skip_all,
+        fields(instruction = "resume_agreement", program_id, payment_terms, payer),
+        err
+    )]
+    pub fn build_instructions(
+        self,
+        payee: &Payee,
+        payment_terms_data: &PaymentTerms,
+        platform_treasury_ata: &Pubkey,
+    ) -> Result<Vec<Instruction>> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let allowance_periods = self.allowance_periods.unwrap_or(3);
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        record_build_fields(&program_id, &payment_terms, &payer);
+
+        // Compute required PDAs
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let payer_ata = get_associated_token_address_with_program(
+            &payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        let renewal_bucket = if let Some(bucket) = self.renewal_bucket {
+            bucket
+        } else {
+            let now = chrono::Utc::now().timestamp();
+            let period = i64::try_from(payment_terms_data.period_secs_at(now))
+                .map_err(|_| TallyError::Generic("Arithmetic overflow".to_string()))?;
+            now.checked_add(period)
+                .and_then(pda::renewal_bucket)
+                .ok_or("Invalid renewal bucket")?
+        };
+        let renewal_queue_pda =
+            pda::agreement_renewal_queue_address_with_program_id(
+                renewal_bucket,
+                &payment_agreement_pda,
+                &program_id,
+            );
+
+        let allowance_amount = payment_terms_data
+            .amount_usdc
+            .checked_mul(u64::from(allowance_periods))
+            .ok_or_else(|| TallyError::Generic("Arithmetic overflow".to_string()))?;
+
+        let approve_ix = match token_program {
+            TokenProgram::Token => approve_checked_token(
+                &token_program.program_id(),
+                &payer_ata,
+                &payee.usdc_mint,
+                &delegate_pda,
+                &payer,
+                &[],
+                allowance_amount,
+                6, // USDC decimals
+            )?,
+            TokenProgram::Token2022 => approve_checked_token2022(
+                &token_program.program_id(),
+                &payer_ata,
+                &payee.usdc_mint,
+                &delegate_pda,
+                &payer,
+                &[],
+                allowance_amount,
+                6, // USDC decimals
+            )?,
+        };
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false),                 // config
+            AccountMeta::new(payment_agreement_pda, false),               // payment_agreement (PDA)
+            AccountMeta::new(payment_terms, false),                       // payment_terms (mutable)
+            AccountMeta::new(payee_pda, false), // payee (mutable for agreement counters)
+            AccountMeta::new(payer, true),                                // payer (signer)
+            AccountMeta::new(payer_ata, false),                           // payer_usdc_ata
+            AccountMeta::new(payee.treasury_ata, false),                  // payee_treasury_ata
+            AccountMeta::new(*platform_treasury_ata, false),              // platform_treasury_ata
+            AccountMeta::new_readonly(payee.usdc_mint, false),            // usdc_mint
+            AccountMeta::new_readonly(delegate_pda, false),               // program_delegate
+            AccountMeta::new(renewal_queue_pda, false),                   // renewal_queue (PDA, created if needed)
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+            AccountMeta::new_readonly(system_program::ID, false),         // system_program
+        ];
+
+        let args = ResumeAgreementArgs {
+            allowance_periods,
+            renewal_bucket,
+        };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:resume_agreement")
+            data.extend_from_slice(&[158, 1, 240, 85, 78, 170, 184, 23]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        let resume_ix = Instruction {
+            program_id,
+            accounts,
+            data,
+        };
+
+        Ok(vec![approve_ix, resume_ix])
+    }
+}
+
+impl RepairDelegateBuilder {
+    /// Create a new repair delegate builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the payment terms, payer and current delegate from a mismatch warning
+    #[must_use]
+    pub const fn from_warning(mut self, warning: &DelegateMismatchWarning) -> Self {
+        self.payment_terms = Some(warning.payment_terms);
+        self.payer = Some(warning.payer);
+        self.current_delegate = warning.actual_delegate;
+        self
+    }
+
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey (owner of the token account being repaired)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the delegate currently approved on the payer's token account
+    ///
+    /// A delegate other than the program delegate PDA is revoked before the approval.
+    #[must_use]
+    pub const fn current_delegate(mut self, current_delegate: Pubkey) -> Self {
+        self.current_delegate = Some(current_delegate);
+        self
+    }
+
+    /// Set the allowance periods multiplier (default 3)
+    #[must_use]
+    pub const fn allowance_periods(mut self, periods: u8) -> Self {
+        self.allowance_periods = Some(periods);
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instructions
+    ///
+    /// Restores the allowance `execute_payment` needs after a `DelegateMismatchWarning`:
+    /// the program delegate PDA is approved for the payment terms price times the
+    /// allowance periods. All instructions are signed by the payer alone.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `payment_terms_data` - The `payment_terms` account data
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Instruction>)` - The transaction instructions (revoke, if another
+    ///   delegate is set, + `approve_checked`)
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    #[tracing::instrument(
+        name = "build_instructions",
+        level = "debug",
+        skip_all,
+        fields(instruction = "repair_delegate", program_id, payment_terms, payer),
+        err
+    )]
+    pub fn build_instructions(
+        self,
+        payee: &Payee,
+        payment_terms_data: &PaymentTerms,
+    ) -> Result<Vec<Instruction>> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let allowance_periods = self.allowance_periods.unwrap_or(3);
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        record_build_fields(&program_id, &payment_terms, &payer);
+
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let payer_ata = get_associated_token_address_with_program(
+            &payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        let allowance_amount = payment_terms_data
+            .amount_usdc
+            .checked_mul(u64::from(allowance_periods))
+            .ok_or_else(|| TallyError::Generic("Arithmetic overflow".to_string()))?;
+
+        let mut instructions = Vec::with_capacity(2);
+        if matches!(self.current_delegate, Some(delegate) if delegate != delegate_pda) {
+            instructions.push(match token_program {
+                TokenProgram::Token => {
+                    revoke_token(&token_program.program_id(), &payer_ata, &payer, &[])?
+                }
+                TokenProgram::Token2022 => {
+                    revoke_token2022(&token_program.program_id(), &payer_ata, &payer, &[])?
+                }
+            });
+        }
+
+        instructions.push(match token_program {
+            TokenProgram::Token => approve_checked_token(
+                &token_program.program_id(),
+                &payer_ata,
+                &payee.usdc_mint,
+                &delegate_pda,
+                &payer,
+                &[],
+                allowance_amount,
+                6, // USDC decimals
+            )?,
+            TokenProgram::Token2022 => approve_checked_token2022(
+                &token_program.program_id(),
+                &payer_ata,
+                &payee.usdc_mint,
+                &delegate_pda,
+                &payer,
+                &[],
+                allowance_amount,
+                6, // USDC decimals
+            )?,
+        });
+
+        Ok(instructions)
+    }
+}
+
+impl InitPayeeBuilder {
+    /// Create a new init payee builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the payee authority
+    #[must_use]
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Set the transaction payer
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the USDC mint
+    #[must_use]
+    pub const fn usdc_mint(mut self, usdc_mint: Pubkey) -> Self {
+        self.usdc_mint = Some(usdc_mint);
+        self
+    }
+
+    /// Set the treasury ATA
+    #[must_use]
+    pub const fn treasury_ata(mut self, treasury_ata: Pubkey) -> Self {
+        self.treasury_ata = Some(treasury_ata);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
     ///
     /// # Returns
     /// * `Ok(Instruction)` - The `init_payee` instruction
@@ -516,6 +1284,14 @@ impl CreatePaymentTermsBuilder {
         self
     }
 
+    /// Set the authority the payee was created with, if its authority has since
+    /// been transferred (used to derive the payee PDA, defaults to `authority`)
+    #[must_use]
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
+        self
+    }
+
     /// Set the transaction payer
     #[must_use]
     pub const fn payer(mut self, payer: Pubkey) -> Self {
@@ -551,7 +1327,8 @@ impl CreatePaymentTermsBuilder {
 
         // Compute PDAs
         let config_pda = pda::config_address_with_program_id(&program_id);
-        let payee_pda = pda::payee_address_with_program_id(&authority, &program_id);
+        let original_authority = self.original_authority.unwrap_or(authority);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
         let payment_terms_pda =
             pda::payment_terms_address_with_program_id(&payee_pda, &payment_terms_args.terms_id_bytes, &program_id);
 
@@ -580,46 +1357,214 @@ impl CreatePaymentTermsBuilder {
     }
 }
 
-
-#[cfg(feature = "platform-admin")]
-impl AdminWithdrawFeesBuilder {
-    /// Create a new admin withdraw fees builder
+impl ScheduleTermsUpdateBuilder {
+    /// Create a new schedule terms update builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the platform authority
+    /// Set the payee authority
     #[must_use]
-    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
-        self.platform_authority = Some(platform_authority);
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
         self
     }
 
-    /// Set the platform treasury ATA (source of funds)
+    /// Set the authority the payee was created with, if its authority has since
+    /// been transferred (used to derive the payee PDA, defaults to `authority`)
     #[must_use]
-    pub const fn platform_treasury_ata(mut self, platform_treasury_ata: Pubkey) -> Self {
-        self.platform_treasury_ata = Some(platform_treasury_ata);
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
         self
     }
 
-    /// Set the destination ATA (where funds will be sent)
+    /// Set the `payment_terms` PDA
     #[must_use]
-    pub const fn destination_ata(mut self, destination_ata: Pubkey) -> Self {
-        self.destination_ata = Some(destination_ata);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
         self
     }
 
-    /// Set the USDC mint
+    /// Set the schedule arguments
     #[must_use]
-    pub const fn usdc_mint(mut self, usdc_mint: Pubkey) -> Self {
-        self.usdc_mint = Some(usdc_mint);
+    pub const fn schedule_args(mut self, args: ScheduleTermsUpdateArgs) -> Self {
+        self.schedule_args = Some(args);
         self
     }
 
-    /// Set the amount to withdraw
+    /// Set the program ID to use
     #[must_use]
-    pub const fn amount(mut self, amount: u64) -> Self {
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `schedule_terms_update` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let authority = self.authority.ok_or("Authority not set")?;
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let schedule_args = self.schedule_args.ok_or("Schedule args not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute PDAs
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let original_authority = self.original_authority.unwrap_or(authority);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false), // config
+            AccountMeta::new(payment_terms, false),       // payment_terms (mutable)
+            AccountMeta::new_readonly(payee_pda, false),  // payee
+            AccountMeta::new_readonly(authority, true),   // authority (signer)
+        ];
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "schedule_terms_update")
+            data.extend_from_slice(&[32, 116, 115, 11, 103, 95, 151, 193]);
+            borsh::to_writer(&mut data, &schedule_args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl DeactivatePaymentTermsBuilder {
+    /// Create a new deactivate payment terms builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the payee authority
+    #[must_use]
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Set the authority the payee was created with, if its authority has since
+    /// been transferred (used to derive the payee PDA, defaults to `authority`)
+    #[must_use]
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
+        self
+    }
+
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the timestamp from which agreements stop renewing
+    #[must_use]
+    pub const fn sunset_ts(mut self, sunset_ts: i64) -> Self {
+        self.sunset_ts = Some(sunset_ts);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `deactivate_payment_terms` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let authority = self.authority.ok_or("Authority not set")?;
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let sunset_ts = self.sunset_ts.ok_or("Sunset timestamp not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute PDAs
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let original_authority = self.original_authority.unwrap_or(authority);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false), // config
+            AccountMeta::new(payment_terms, false),       // payment_terms (mutable)
+            AccountMeta::new_readonly(payee_pda, false),  // payee
+            AccountMeta::new_readonly(authority, true),   // authority (signer)
+        ];
+
+        let args = DeactivatePaymentTermsArgs { sunset_ts };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "deactivate_payment_terms")
+            data.extend_from_slice(&[33, 182, 63, 230, 115, 204, 125, 143]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl AdminWithdrawFeesBuilder {
+    /// Create a new admin withdraw fees builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the platform treasury ATA (source of funds)
+    #[must_use]
+    pub const fn platform_treasury_ata(mut self, platform_treasury_ata: Pubkey) -> Self {
+        self.platform_treasury_ata = Some(platform_treasury_ata);
+        self
+    }
+
+    /// Set the destination ATA (where funds will be sent)
+    #[must_use]
+    pub const fn destination_ata(mut self, destination_ata: Pubkey) -> Self {
+        self.destination_ata = Some(destination_ata);
+        self
+    }
+
+    /// Set the USDC mint
+    #[must_use]
+    pub const fn usdc_mint(mut self, usdc_mint: Pubkey) -> Self {
+        self.usdc_mint = Some(usdc_mint);
+        self
+    }
+
+    /// Set the amount to withdraw
+    #[must_use]
+    pub const fn amount(mut self, amount: u64) -> Self {
         self.amount = Some(amount);
         self
     }
@@ -803,6 +1748,59 @@ impl ExecutePaymentBuilder {
         self
     }
 
+    /// Set the agreement's current `next_payment_ts` (the payment being executed)
+    ///
+    /// Used to derive the renewal queues the agreement moves between.
+    #[must_use]
+    pub const fn next_payment_ts(mut self, next_payment_ts: i64) -> Self {
+        self.next_payment_ts = Some(next_payment_ts);
+        self
+    }
+
+    /// Take the keeper fee in SOL from the keeper fee vault
+    ///
+    /// The fee is converted at the config's `keeper_sol_rate`, which must be set and
+    /// current. The keeper's USDC ATA is then neither required nor created by
+    /// [`Self::ensure_atas`].
+    #[must_use]
+    pub const fn keeper_fee_in_sol(mut self, keeper_fee_in_sol: bool) -> Self {
+        self.keeper_fee_in_sol = keeper_fee_in_sol;
+        self
+    }
+
+    /// Prepend idempotent creation of the keeper's USDC ATA and the payee treasury ATA
+    ///
+    /// Applied by [`Self::build_instructions`]; the keeper funds any account that does
+    /// not exist yet. A `keeper_ata` that is not the keeper's associated token account
+    /// is assumed to exist.
+    #[must_use]
+    pub const fn ensure_atas(mut self, ensure_atas: bool) -> Self {
+        self.ensure_atas = ensure_atas;
+        self
+    }
+
+    /// Attach a customer note, such as an order or invoice reference, to this renewal
+    ///
+    /// Appended as an SPL memo instruction by [`Self::build_instructions`] and read back
+    /// into [`TallyReceipt::memo`](crate::events::TallyReceipt::memo). The note must
+    /// pass [`validate_customer_memo`](crate::transaction_utils::validate_customer_memo).
+    #[must_use]
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Pass the test clock so due-date checks use its shifted time
+    ///
+    /// Only programs built with the `test-clock` feature read the account; see
+    /// [`AdvanceTestClockBuilder`].
+    #[must_use]
+    #[cfg(feature = "test-clock")]
+    pub const fn test_clock(mut self, test_clock: bool) -> Self {
+        self.test_clock = test_clock;
+        self
+    }
+
     /// Set the token program to use
     #[must_use]
     pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
@@ -828,49 +1826,122 @@ impl ExecutePaymentBuilder {
     /// * `Ok(Instruction)` - The `renew_payment_agreement` instruction
     /// * `Err(TallyError)` - If building fails
     #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    #[tracing::instrument(
+        name = "build_instructions",
+        level = "debug",
+        skip_all,
+        fields(instruction = "execute_payment", program_id, payment_terms, payer),
+        err
+    )]
     pub fn build_instruction(
         self,
         payee: &Payee,
-        _payment_terms_data: &PaymentTerms,
+        payment_terms_data: &PaymentTerms,
         platform_treasury_ata: &Pubkey,
     ) -> Result<Instruction> {
         let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
         let payer = self.payer.ok_or("Payer not set")?;
         let keeper = self.keeper.ok_or("Keeper not set")?;
-        let keeper_ata = self.keeper_ata.ok_or("Keeper ATA not set")?;
+        let next_payment_ts = self.next_payment_ts.ok_or("Next payment timestamp not set")?;
         let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+        // A keeper paid in SOL needs no USDC account; its ATA address is passed unread
+        let keeper_ata = match (self.keeper_ata, self.keeper_fee_in_sol) {
+            (Some(keeper_ata), _) => keeper_ata,
+            (None, true) => {
+                get_associated_token_address_with_program(&keeper, &payee.usdc_mint, token_program)?
+            }
+            (None, false) => return Err("Keeper ATA not set".into()),
+        };
 
         let program_id = self.program_id.unwrap_or_else(program_id);
+        record_build_fields(&program_id, &payment_terms, &payer);
+
+        // Fail fast instead of sending a transaction the program rejects
+        if !payee.is_keeper_authorized(&keeper) {
+            return Err(TallyError::Generic(format!(
+                "Keeper {keeper} is not authorized to execute payments for this payee"
+            )));
+        }
 
         // Compute required PDAs
         let config_pda = pda::config_address_with_program_id(&program_id);
-        let payee_pda = pda::payee_address_with_program_id(&payee.authority, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
         let payment_agreement_pda =
             pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
         let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        // The vault is only passed for SOL keeper fees; the program ID marks it absent
+        let keeper_fee_vault = if self.keeper_fee_in_sol {
+            AccountMeta::new(pda::keeper_fee_vault_address_with_program_id(&program_id), false)
+        } else {
+            AccountMeta::new_readonly(program_id, false)
+        };
         let payer_ata = get_associated_token_address_with_program(
             &payer,
             &payee.usdc_mint,
             token_program,
         )?;
 
+        // The agreement moves from the queue of this payment to the queue of the next
+        let period = i64::try_from(payment_terms_data.period_secs_at(chrono::Utc::now().timestamp()))
+            .map_err(|_| TallyError::Generic("Arithmetic overflow".to_string()))?;
+        let current_bucket = pda::renewal_bucket(next_payment_ts).ok_or("Invalid renewal bucket")?;
+        let next_renewal_bucket = next_payment_ts
+            .checked_add(period)
+            .and_then(pda::renewal_bucket)
+            .ok_or("Invalid renewal bucket")?;
+        let current_queue_pda =
+            pda::agreement_renewal_queue_address_with_program_id(
+                current_bucket,
+                &payment_agreement_pda,
+                &program_id,
+            );
+        let next_queue_pda =
+            pda::agreement_renewal_queue_address_with_program_id(
+                next_renewal_bucket,
+                &payment_agreement_pda,
+                &program_id,
+            );
+
+        // Token-gated terms require the payer's gate ATA, whether or not it exists
+        let payer_gate_ata = payment_terms_data
+            .gate_mint
+            .map(|gate_mint| {
+                get_associated_token_address_with_program(&payer, &gate_mint, TokenProgram::Token)
+            })
+            .transpose()?;
+
         // Create renew_payment_agreement instruction
-        let renew_sub_accounts = vec![
+        let mut renew_sub_accounts = vec![
             AccountMeta::new_readonly(config_pda, false),   // config
             AccountMeta::new(payment_agreement_pda, false),      // payment agreement (PDA, mutable)
-            AccountMeta::new_readonly(payment_terms, false),         // payment_terms
-            AccountMeta::new_readonly(payee_pda, false), // payee
+            AccountMeta::new(payment_terms, false),         // payment_terms (mutable)
+            AccountMeta::new(payee_pda, false),          // payee (mutable for volume tracking)
             AccountMeta::new(payer_ata, false),        // payer_usdc_ata (mutable)
             AccountMeta::new(payee.treasury_ata, false), // payee_treasury_ata (mutable)
             AccountMeta::new(*platform_treasury_ata, false), // platform_treasury_ata (mutable)
             AccountMeta::new(keeper, true),                 // keeper (signer, mutable for fees)
             AccountMeta::new(keeper_ata, false),            // keeper_usdc_ata (mutable)
+            keeper_fee_vault,                               // keeper_fee_vault (optional)
             AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
             AccountMeta::new_readonly(delegate_pda, false), // program_delegate
             AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+            AccountMeta::new(current_queue_pda, false),     // current_renewal_queue (mutable)
+            AccountMeta::new(next_queue_pda, false),        // next_renewal_queue (PDA, created if needed)
+            AccountMeta::new_readonly(system_program::ID, false), // system_program
+            // payer_gate_ata (optional, the program ID marks it absent)
+            AccountMeta::new_readonly(payer_gate_ata.unwrap_or(program_id), false),
         ];
+        if self.test_clock {
+            renew_sub_accounts.push(AccountMeta::new_readonly(
+                pda::test_clock_address_with_program_id(&program_id),
+                false,
+            )); // test_clock (remaining account)
+        }
 
-        let renew_sub_args = crate::program_types::ExecutePaymentArgs {};
+        let renew_sub_args = crate::program_types::ExecutePaymentArgs {
+            next_renewal_bucket,
+            keeper_fee_in_sol: self.keeper_fee_in_sol,
+        };
         let renew_sub_data = {
             let mut data = Vec::new();
             // Instruction discriminator (computed from "execute_payment")
@@ -886,6 +1957,56 @@ impl ExecutePaymentBuilder {
             data: renew_sub_data,
         })
     }
+
+    /// Build the `execute_payment` instruction, preceded by ATA creation if enabled
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `payment_terms_data` - The `payment_terms` account data
+    /// * `platform_treasury_ata` - Platform treasury ATA address
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Instruction>)` - Idempotent create ATA instructions (with [`Self::ensure_atas`])
+    ///   followed by the `execute_payment` instruction and the memo (with [`Self::memo`])
+    /// * `Err(TallyError)` - If building fails or the memo is invalid
+    pub fn build_instructions(
+        self,
+        payee: &Payee,
+        payment_terms_data: &PaymentTerms,
+        platform_treasury_ata: &Pubkey,
+    ) -> Result<Vec<Instruction>> {
+        let ensure_atas = self.ensure_atas;
+        let keeper = self.keeper;
+        let keeper_ata = self.keeper_ata;
+        let paid_in_sol = self.keeper_fee_in_sol;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+        let memo = self.memo.clone();
+        if let Some(memo) = &memo {
+            validate_customer_memo(memo)?;
+        }
+        let execute_ix = self.build_instruction(payee, payment_terms_data, platform_treasury_ata)?;
+
+        let mut instructions = match (ensure_atas, keeper, keeper_ata) {
+            (true, Some(keeper), _) if paid_in_sol => ensure_ata_instructions(
+                &keeper,
+                &[(payee.treasury_ata, payee.authority)],
+                &payee.usdc_mint,
+                token_program,
+            )?,
+            (true, Some(keeper), Some(keeper_ata)) => ensure_ata_instructions(
+                &keeper,
+                &[(keeper_ata, keeper), (payee.treasury_ata, payee.authority)],
+                &payee.usdc_mint,
+                token_program,
+            )?,
+            _ => Vec::new(),
+        };
+        instructions.push(execute_ix);
+        if let Some(memo) = memo {
+            instructions.push(create_memo_instruction(&memo));
+        }
+        Ok(instructions)
+    }
 }
 
 impl CloseAgreementBuilder {
@@ -909,6 +2030,13 @@ impl CloseAgreementBuilder {
         self
     }
 
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
+
     /// Set the program ID to use
     #[must_use]
     pub const fn program_id(mut self, program_id: Pubkey) -> Self {
@@ -918,23 +2046,50 @@ impl CloseAgreementBuilder {
 
     /// Build the transaction instruction
     ///
+    /// The escrow and payer token accounts are always passed so the program can return
+    /// any security deposit still held for the agreement.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    ///
     /// # Returns
     /// * `Ok(Instruction)` - The `close_payment_agreement` instruction
     /// * `Err(TallyError)` - If building fails
-    pub fn build_instruction(self) -> Result<Instruction> {
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(self, payee: &Payee) -> Result<Instruction> {
         let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
         let payer = self.payer.ok_or("Payer not set")?;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
 
         let program_id = self.program_id.unwrap_or_else(program_id);
 
         // Compute payment agreement PDA
         let payment_agreement_pda =
             pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let escrow_ata = get_associated_token_address_with_program(
+            &delegate_pda,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+        let payer_ata = get_associated_token_address_with_program(
+            &payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
 
         // Create close_payment_agreement instruction
         let close_sub_accounts = vec![
             AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable, will be closed)
             AccountMeta::new(payer, true), // payer (signer, mutable, receives rent)
+            AccountMeta::new_readonly(payment_terms, false), // payment_terms
+            AccountMeta::new_readonly(payee_pda, false),    // payee
+            AccountMeta::new(escrow_ata, false),            // escrow_ata (mutable)
+            AccountMeta::new(payer_ata, false),             // payer_usdc_ata (mutable)
+            AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
+            AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
         ];
 
         let close_sub_args = crate::program_types::CloseAgreementArgs {};
@@ -955,25 +2110,24 @@ impl CloseAgreementBuilder {
     }
 }
 
-#[cfg(feature = "platform-admin")]
-impl TransferAuthorityBuilder {
-    /// Create a new transfer authority builder
+impl CloseAgreementsBatchBuilder {
+    /// Create a new close agreements batch builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the current platform authority (must be signer)
+    /// Set the payer pubkey (signer, receives the reclaimed rent)
     #[must_use]
-    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
-        self.platform_authority = Some(platform_authority);
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
         self
     }
 
-    /// Set the new authority to transfer to
+    /// Set the payment terms of the agreements to close (replaces the current list)
     #[must_use]
-    pub const fn new_authority(mut self, new_authority: Pubkey) -> Self {
-        self.new_authority = Some(new_authority);
+    pub fn payment_terms(mut self, payment_terms: Vec<Pubkey>) -> Self {
+        self.payment_terms = payment_terms;
         self
     }
 
@@ -986,31 +2140,43 @@ impl TransferAuthorityBuilder {
 
     /// Build the transaction instruction
     ///
+    /// Every agreement must be inactive and hold no security deposit; close agreements
+    /// holding a deposit with [`CloseAgreementBuilder`] so the deposit is refunded.
+    ///
     /// # Returns
-    /// * `Ok(Instruction)` - The `transfer_authority` instruction
-    /// * `Err(TallyError)` - If building fails
+    /// * `Ok(Instruction)` - The `close_agreements_batch` instruction
+    /// * `Err(TallyError)` - If the payer is not set, or no payment terms or more than
+    ///   [`MAX_CLOSE_BATCH_AGREEMENTS`](crate::MAX_CLOSE_BATCH_AGREEMENTS) are set
     pub fn build_instruction(self) -> Result<Instruction> {
-        let platform_authority = self
-            .platform_authority
-            .ok_or("Platform authority not set")?;
-        let new_authority = self.new_authority.ok_or("New authority not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        if self.payment_terms.is_empty()
+            || self.payment_terms.len() > crate::MAX_CLOSE_BATCH_AGREEMENTS
+        {
+            return Err(TallyError::Generic(format!(
+                "Batch must close between 1 and {} agreements, got {}",
+                crate::MAX_CLOSE_BATCH_AGREEMENTS,
+                self.payment_terms.len()
+            )));
+        }
 
         let program_id = self.program_id.unwrap_or_else(program_id);
 
-        // Compute config PDA
-        let config_pda = pda::config_address_with_program_id(&program_id);
-
-        let accounts = vec![
-            AccountMeta::new(config_pda, false), // config (PDA, mutable)
-            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        let mut accounts = vec![
+            AccountMeta::new(payer, true), // payer (signer, mutable, receives rent)
         ];
-
-        let args = crate::program_types::TransferAuthorityArgs { new_authority };
-
+        // Payment agreements (PDAs, mutable, will be closed) as remaining accounts
+        accounts.extend(self.payment_terms.iter().map(|payment_terms| {
+            AccountMeta::new(
+                pda::payment_agreement_address_with_program_id(payment_terms, &payer, &program_id),
+                false,
+            )
+        }));
+
+        let args = crate::program_types::CloseAgreementsBatchArgs {};
         let data = {
             let mut data = Vec::new();
-            // Instruction discriminator (computed from "global:transfer_authority")
-            data.extend_from_slice(&[48, 169, 76, 72, 229, 180, 55, 161]);
+            // Instruction discriminator (computed from "close_agreements_batch")
+            data.extend_from_slice(&[202, 174, 50, 59, 60, 11, 6, 16]);
             borsh::to_writer(&mut data, &args)
                 .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
             data
@@ -1024,18 +2190,58 @@ impl TransferAuthorityBuilder {
     }
 }
 
-#[cfg(feature = "platform-admin")]
-impl AcceptAuthorityBuilder {
-    /// Create a new accept authority builder
+impl SweepAccruedBuilder {
+    /// Create a new sweep accrued builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the new authority (must be signer and pending authority)
+    /// Set the streaming `payment_terms` PDA
     #[must_use]
-    pub const fn new_authority(mut self, new_authority: Pubkey) -> Self {
-        self.new_authority = Some(new_authority);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the keeper (transaction caller) who sweeps the accrual
+    #[must_use]
+    pub const fn keeper(mut self, keeper: Pubkey) -> Self {
+        self.keeper = Some(keeper);
+        self
+    }
+
+    /// Set the keeper's USDC account where the keeper fee will be sent
+    ///
+    /// Defaults to the keeper's associated token account for the payee's mint.
+    #[must_use]
+    pub const fn keeper_ata(mut self, keeper_ata: Pubkey) -> Self {
+        self.keeper_ata = Some(keeper_ata);
+        self
+    }
+
+    /// Pass the test clock so the accrual is measured at its shifted time
+    ///
+    /// Only programs built with the `test-clock` feature read the account; see
+    /// [`AdvanceTestClockBuilder`].
+    #[must_use]
+    #[cfg(feature = "test-clock")]
+    pub const fn test_clock(mut self, test_clock: bool) -> Self {
+        self.test_clock = test_clock;
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
         self
     }
 
@@ -1048,28 +2254,83 @@ impl AcceptAuthorityBuilder {
 
     /// Build the transaction instruction
     ///
+    /// Use [`PaymentAgreement::accrued_at`](crate::program_types::PaymentAgreement::accrued_at)
+    /// to check there is accrual to sweep before sending it.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `payment_terms_data` - The `payment_terms` account data
+    /// * `platform_treasury_ata` - Platform treasury ATA address
+    ///
     /// # Returns
-    /// * `Ok(Instruction)` - The `accept_authority` instruction
-    /// * `Err(TallyError)` - If building fails
-    pub fn build_instruction(self) -> Result<Instruction> {
-        let new_authority = self.new_authority.ok_or("New authority not set")?;
+    /// * `Ok(Instruction)` - The `sweep_accrued` instruction
+    /// * `Err(TallyError)` - If the terms are not streaming terms, the keeper is not
+    ///   authorized, or a required field is not set
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(
+        self,
+        payee: &Payee,
+        payment_terms_data: &PaymentTerms,
+        platform_treasury_ata: &Pubkey,
+    ) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let keeper = self.keeper.ok_or("Keeper not set")?;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+        let keeper_ata = match self.keeper_ata {
+            Some(keeper_ata) => keeper_ata,
+            None => {
+                get_associated_token_address_with_program(&keeper, &payee.usdc_mint, token_program)?
+            }
+        };
 
-        let program_id = self.program_id.unwrap_or_else(program_id);
+        // Fail fast instead of sending a transaction the program rejects
+        if !payment_terms_data.is_streaming() {
+            return Err(TallyError::Generic(
+                "Payment terms are not streaming terms; use execute_payment".to_string(),
+            ));
+        }
+        if !payee.is_keeper_authorized(&keeper) {
+            return Err(TallyError::Generic(format!(
+                "Keeper {keeper} is not authorized to execute payments for this payee"
+            )));
+        }
 
-        // Compute config PDA
+        let program_id = self.program_id.unwrap_or_else(program_id);
         let config_pda = pda::config_address_with_program_id(&program_id);
-
-        let accounts = vec![
-            AccountMeta::new(config_pda, false), // config (PDA, mutable)
-            AccountMeta::new_readonly(new_authority, true), // new_authority (signer)
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let payer_ata =
+            get_associated_token_address_with_program(&payer, &payee.usdc_mint, token_program)?;
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(config_pda, false),           // config
+            AccountMeta::new(payment_agreement_pda, false),         // payment_agreement (mutable)
+            AccountMeta::new(payment_terms, false),                 // payment_terms (mutable)
+            AccountMeta::new(payee_pda, false),                     // payee (mutable)
+            AccountMeta::new(payer_ata, false),                     // payer_usdc_ata (mutable)
+            AccountMeta::new(payee.treasury_ata, false),            // payee_treasury_ata (mutable)
+            AccountMeta::new(*platform_treasury_ata, false),        // platform_treasury_ata (mutable)
+            AccountMeta::new_readonly(keeper, true),                // keeper (signer)
+            AccountMeta::new(keeper_ata, false),                    // keeper_usdc_ata (mutable)
+            AccountMeta::new_readonly(payee.usdc_mint, false),      // usdc_mint
+            AccountMeta::new_readonly(delegate_pda, false),         // program_delegate
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
         ];
+        if self.test_clock {
+            accounts.push(AccountMeta::new_readonly(
+                pda::test_clock_address_with_program_id(&program_id),
+                false,
+            )); // test_clock (remaining account)
+        }
 
-        let args = crate::program_types::AcceptAuthorityArgs::default();
-
+        let args = crate::program_types::SweepAccruedArgs {};
         let data = {
             let mut data = Vec::new();
-            // Instruction discriminator (computed from "global:accept_authority")
-            data.extend_from_slice(&[107, 86, 198, 91, 33, 12, 107, 160]);
+            // Instruction discriminator (computed from "sweep_accrued")
+            data.extend_from_slice(&[57, 220, 131, 162, 187, 194, 8, 196]);
             borsh::to_writer(&mut data, &args)
                 .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
             data
@@ -1083,18 +2344,24 @@ impl AcceptAuthorityBuilder {
     }
 }
 
-#[cfg(feature = "platform-admin")]
-impl CancelAuthorityTransferBuilder {
-    /// Create a new cancel authority transfer builder
+impl MigrateAccountBuilder {
+    /// Create a new migrate account builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the current platform authority (must be signer)
+    /// Set the Config, Payee, `PaymentTerms` or `PaymentAgreement` account to upgrade
     #[must_use]
-    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
-        self.platform_authority = Some(platform_authority);
+    pub const fn account(mut self, account: Pubkey) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// Set the payer pubkey (signer, pays the rent for the added bytes)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
         self
     }
 
@@ -1107,30 +2374,30 @@ impl CancelAuthorityTransferBuilder {
 
     /// Build the transaction instruction
     ///
+    /// The instruction is permissionless: any payer can upgrade any account of the
+    /// program. Use [`decode_versioned_account`](crate::decode_versioned_account) to
+    /// find accounts still at an older layout version.
+    ///
     /// # Returns
-    /// * `Ok(Instruction)` - The `cancel_authority_transfer` instruction
-    /// * `Err(TallyError)` - If building fails
+    /// * `Ok(Instruction)` - The `migrate_account` instruction
+    /// * `Err(TallyError)` - If the account or payer is not set
     pub fn build_instruction(self) -> Result<Instruction> {
-        let platform_authority = self
-            .platform_authority
-            .ok_or("Platform authority not set")?;
+        let account = self.account.ok_or("Account not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
 
         let program_id = self.program_id.unwrap_or_else(program_id);
 
-        // Compute config PDA
-        let config_pda = pda::config_address_with_program_id(&program_id);
-
         let accounts = vec![
-            AccountMeta::new(config_pda, false), // config (PDA, mutable)
-            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+            AccountMeta::new(account, false),                     // account (mutable, resized)
+            AccountMeta::new(payer, true),                        // payer (signer, mutable)
+            AccountMeta::new_readonly(system_program::ID, false), // system_program
         ];
 
-        let args = crate::program_types::CancelAuthorityTransferArgs::default();
-
+        let args = crate::program_types::MigrateAccountArgs {};
         let data = {
             let mut data = Vec::new();
-            // Instruction discriminator (computed from "global:cancel_authority_transfer")
-            data.extend_from_slice(&[94, 131, 125, 184, 183, 24, 125, 229]);
+            // Instruction discriminator (computed from "migrate_account")
+            data.extend_from_slice(&[177, 228, 60, 125, 13, 116, 44, 84]);
             borsh::to_writer(&mut data, &args)
                 .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
             data
@@ -1144,18 +2411,32 @@ impl CancelAuthorityTransferBuilder {
     }
 }
 
-#[cfg(feature = "platform-admin")]
-impl PauseBuilder {
-    /// Create a new pause builder
+impl ScheduleCancellationBuilder {
+    /// Create a new schedule cancellation builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the platform authority (must be signer)
+    /// Set the `payment_terms` PDA
     #[must_use]
-    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
-        self.platform_authority = Some(platform_authority);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey (must be signer)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the authority the payee that owns the payment terms was created with
+    /// (its `original_authority`, used to derive the payee PDA)
+    #[must_use]
+    pub const fn payee_authority(mut self, payee_authority: Pubkey) -> Self {
+        self.payee_authority = Some(payee_authority);
         self
     }
 
@@ -1169,29 +2450,31 @@ impl PauseBuilder {
     /// Build the transaction instruction
     ///
     /// # Returns
-    /// * `Ok(Instruction)` - The `pause` instruction
+    /// * `Ok(Instruction)` - The `schedule_cancellation` instruction
     /// * `Err(TallyError)` - If building fails
     pub fn build_instruction(self) -> Result<Instruction> {
-        let platform_authority = self
-            .platform_authority
-            .ok_or("Platform authority not set")?;
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let payee_authority = self.payee_authority.ok_or("Payee authority not set")?;
 
         let program_id = self.program_id.unwrap_or_else(program_id);
 
-        // Compute config PDA
-        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee_authority, &program_id);
 
         let accounts = vec![
-            AccountMeta::new(config_pda, false), // config (PDA, mutable)
-            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+            AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable)
+            AccountMeta::new_readonly(payment_terms, false), // payment_terms
+            AccountMeta::new_readonly(payee_pda, false),    // payee
+            AccountMeta::new_readonly(payer, true),         // payer (signer)
         ];
 
-        let args = crate::program_types::PauseArgs {};
-
+        let args = ScheduleCancellationArgs {};
         let data = {
             let mut data = Vec::new();
-            // Instruction discriminator (computed from "global:pause")
-            data.extend_from_slice(&[211, 22, 221, 251, 74, 121, 193, 47]);
+            // Instruction discriminator (computed from "schedule_cancellation")
+            data.extend_from_slice(&[141, 114, 46, 221, 173, 128, 100, 145]);
             borsh::to_writer(&mut data, &args)
                 .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
             data
@@ -1205,18 +2488,32 @@ impl PauseBuilder {
     }
 }
 
-#[cfg(feature = "platform-admin")]
-impl UnpauseBuilder {
-    /// Create a new unpause builder
+impl ReserveSlotBuilder {
+    /// Create a new reserve slot builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the platform authority (must be signer)
+    /// Set the `payment_terms` PDA (must have a subscriber cap)
     #[must_use]
-    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
-        self.platform_authority = Some(platform_authority);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey (must be signer, pays rent for the reservation)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the authority the payee that owns the payment terms was created with
+    /// (its `original_authority`, used to derive the payee PDA)
+    #[must_use]
+    pub const fn payee_authority(mut self, payee_authority: Pubkey) -> Self {
+        self.payee_authority = Some(payee_authority);
         self
     }
 
@@ -1230,29 +2527,34 @@ impl UnpauseBuilder {
     /// Build the transaction instruction
     ///
     /// # Returns
-    /// * `Ok(Instruction)` - The `unpause` instruction
+    /// * `Ok(Instruction)` - The `reserve_slot` instruction
     /// * `Err(TallyError)` - If building fails
     pub fn build_instruction(self) -> Result<Instruction> {
-        let platform_authority = self
-            .platform_authority
-            .ok_or("Platform authority not set")?;
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let payee_authority = self.payee_authority.ok_or("Payee authority not set")?;
 
         let program_id = self.program_id.unwrap_or_else(program_id);
 
-        // Compute config PDA
         let config_pda = pda::config_address_with_program_id(&program_id);
+        let slot_reservation_pda =
+            pda::slot_reservation_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee_authority, &program_id);
 
         let accounts = vec![
-            AccountMeta::new(config_pda, false), // config (PDA, mutable)
-            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+            AccountMeta::new_readonly(config_pda, false),        // config
+            AccountMeta::new(slot_reservation_pda, false),       // slot reservation (PDA, created)
+            AccountMeta::new(payment_terms, false),              // payment_terms (mutable)
+            AccountMeta::new_readonly(payee_pda, false),         // payee
+            AccountMeta::new(payer, true),                       // payer (signer, pays rent)
+            AccountMeta::new_readonly(system_program::ID, false), // system_program
         ];
 
-        let args = crate::program_types::UnpauseArgs {};
-
+        let args = ReserveSlotArgs {};
         let data = {
             let mut data = Vec::new();
-            // Instruction discriminator (computed from "global:unpause")
-            data.extend_from_slice(&[169, 144, 4, 38, 10, 141, 188, 255]);
+            // Instruction discriminator (computed from "reserve_slot")
+            data.extend_from_slice(&[109, 148, 20, 186, 66, 121, 242, 72]);
             borsh::to_writer(&mut data, &args)
                 .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
             data
@@ -1266,494 +2568,3814 @@ impl UnpauseBuilder {
     }
 }
 
-#[cfg(feature = "platform-admin")]
-impl UpdateConfigBuilder {
-    /// Create a new update config builder
+impl InitiateAgreementTransferBuilder {
+    /// Create a new initiate agreement transfer builder
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set the platform authority (must be signer)
+    /// Set the `payment_terms` PDA
     #[must_use]
-    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
-        self.platform_authority = Some(platform_authority);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
         self
     }
 
-    /// Set the keeper fee in basis points (0-100)
+    /// Set the current payer pubkey (must be signer)
     #[must_use]
-    pub const fn keeper_fee_bps(mut self, keeper_fee_bps: u16) -> Self {
-        self.keeper_fee_bps = Some(keeper_fee_bps);
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
         self
     }
 
-    /// Set the maximum withdrawal amount
+    /// Set the wallet that will take over the agreement
     #[must_use]
-    pub const fn max_withdrawal_amount(mut self, max_withdrawal_amount: u64) -> Self {
-        self.max_withdrawal_amount = Some(max_withdrawal_amount);
+    pub const fn new_payer(mut self, new_payer: Pubkey) -> Self {
+        self.new_payer = Some(new_payer);
         self
     }
 
-    /// Set the maximum grace period in seconds
+    /// Set the authority the payee that owns the payment terms was created with
+    /// (its `original_authority`, used to derive the payee PDA)
     #[must_use]
-    pub const fn max_grace_period_seconds(mut self, max_grace_period_seconds: u64) -> Self {
-        self.max_grace_period_seconds = Some(max_grace_period_seconds);
+    pub const fn payee_authority(mut self, payee_authority: Pubkey) -> Self {
+        self.payee_authority = Some(payee_authority);
         self
     }
 
-    /// Set the minimum platform fee in basis points
+    /// Set the program ID to use
     #[must_use]
-    pub const fn min_platform_fee_bps(mut self, min_platform_fee_bps: u16) -> Self {
-        self.min_platform_fee_bps = Some(min_platform_fee_bps);
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
         self
     }
 
-    /// Set the maximum platform fee in basis points
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `initiate_agreement_transfer` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let new_payer = self.new_payer.ok_or("New payer not set")?;
+        let payee_authority = self.payee_authority.ok_or("Payee authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable)
+            AccountMeta::new_readonly(payment_terms, false), // payment_terms
+            AccountMeta::new_readonly(payee_pda, false),    // payee
+            AccountMeta::new_readonly(payer, true),         // payer (signer)
+        ];
+
+        let args = InitiateAgreementTransferArgs { new_payer };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "initiate_agreement_transfer")
+            data.extend_from_slice(&[208, 126, 130, 181, 213, 186, 250, 200]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl AcceptAgreementTransferBuilder {
+    /// Create a new accept agreement transfer builder
     #[must_use]
-    pub const fn max_platform_fee_bps(mut self, max_platform_fee_bps: u16) -> Self {
-        self.max_platform_fee_bps = Some(max_platform_fee_bps);
-        self
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    /// Set the minimum period in seconds
+    /// Set the `payment_terms` PDA
     #[must_use]
-    pub const fn min_period_seconds(mut self, min_period_seconds: u64) -> Self {
-        self.min_period_seconds = Some(min_period_seconds);
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
         self
     }
 
-    /// Set the default allowance periods
+    /// Set the previous payer pubkey (receives the closed agreement's rent)
     #[must_use]
-    pub const fn default_allowance_periods(mut self, default_allowance_periods: u8) -> Self {
-        self.default_allowance_periods = Some(default_allowance_periods);
+    pub const fn old_payer(mut self, old_payer: Pubkey) -> Self {
+        self.old_payer = Some(old_payer);
         self
     }
 
-    /// Set the program ID to use
+    /// Set the new payer pubkey (must be signer, also sets as transaction payer)
     #[must_use]
-    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
-        self.program_id = Some(program_id);
+    pub const fn new_payer(mut self, new_payer: Pubkey) -> Self {
+        self.new_payer = Some(new_payer);
         self
     }
 
-    /// Build the transaction instruction
+    /// Set the agreement's current `next_payment_ts`
     ///
-    /// # Returns
-    /// * `Ok(Instruction)` - The `update_config` instruction
-    /// * `Err(TallyError)` - If building fails
-    ///
-    /// # Validation
-    /// * Platform authority must be set
-    /// * At least one field must be set for update
-    /// * `keeper_fee_bps` <= 100 if provided
-    /// * `min_platform_fee_bps` <= `max_platform_fee_bps` if both provided
-    /// * All numeric values > 0 where required
-    pub fn build_instruction(self) -> Result<Instruction> {
-        let platform_authority = self
-            .platform_authority
-            .ok_or("Platform authority not set")?;
-
-        let program_id = self.program_id.unwrap_or_else(program_id);
+    /// Used to derive the renewal queues the agreement entry moves between.
+    #[must_use]
+    pub const fn next_payment_ts(mut self, next_payment_ts: i64) -> Self {
+        self.next_payment_ts = Some(next_payment_ts);
+        self
+    }
 
-        // Validate at least one field is set for update
-        let has_update = self.keeper_fee_bps.is_some()
-            || self.max_withdrawal_amount.is_some()
-            || self.max_grace_period_seconds.is_some()
-            || self.min_platform_fee_bps.is_some()
-            || self.max_platform_fee_bps.is_some()
-            || self.min_period_seconds.is_some()
-            || self.default_allowance_periods.is_some();
+    /// Set the allowance periods multiplier for the new wallet's approval (default 3)
+    #[must_use]
+    pub const fn allowance_periods(mut self, periods: u8) -> Self {
+        self.allowance_periods = Some(periods);
+        self
+    }
 
-        if !has_update {
-            return Err("At least one configuration field must be set for update".into());
-        }
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
 
-        // Validate keeper_fee_bps <= 100 if provided
-        if let Some(keeper_fee) = self.keeper_fee_bps {
-            if keeper_fee > 100 {
-                return Err("Keeper fee must be <= 100 basis points (1%)".into());
-            }
-        }
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
 
-        // Validate min_platform_fee_bps <= max_platform_fee_bps if both provided
-        if let Some(min_fee) = self.min_platform_fee_bps {
-            if let Some(max_fee) = self.max_platform_fee_bps {
-                if min_fee > max_fee {
-                    return Err("Minimum platform fee must be <= maximum platform fee".into());
-                }
-            }
-        }
+    /// Build the transaction instructions
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `payment_terms_data` - The `payment_terms` account data
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Instruction>)` - The transaction instructions (`approve_checked` from the
+    ///   new wallet + `accept_agreement_transfer`)
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instructions(
+        self,
+        payee: &Payee,
+        payment_terms_data: &PaymentTerms,
+    ) -> Result<Vec<Instruction>> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let old_payer = self.old_payer.ok_or("Old payer not set")?;
+        let new_payer = self.new_payer.ok_or("New payer not set")?;
+        let next_payment_ts = self.next_payment_ts.ok_or("Next payment timestamp not set")?;
+        let allowance_periods = self.allowance_periods.unwrap_or(3);
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
 
-        // Validate numeric values > 0 where required
-        if let Some(max_withdrawal) = self.max_withdrawal_amount {
-            if max_withdrawal == 0 {
-                return Err("Maximum withdrawal amount must be > 0".into());
-            }
-        }
+        let program_id = self.program_id.unwrap_or_else(program_id);
 
-        if let Some(max_grace) = self.max_grace_period_seconds {
-            if max_grace == 0 {
-                return Err("Maximum grace period must be > 0".into());
-            }
-        }
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let old_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &old_payer, &program_id);
+        let new_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &new_payer, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
 
-        if let Some(min_period) = self.min_period_seconds {
-            if min_period == 0 {
-                return Err("Minimum period must be > 0".into());
-            }
-        }
+        // The queue entry moves from the old agreement address to the new one
+        let renewal_bucket = pda::renewal_bucket(next_payment_ts).ok_or("Invalid renewal bucket")?;
+        let old_queue_pda = pda::agreement_renewal_queue_address_with_program_id(
+            renewal_bucket,
+            &old_agreement_pda,
+            &program_id,
+        );
+        let new_queue_pda = pda::agreement_renewal_queue_address_with_program_id(
+            renewal_bucket,
+            &new_agreement_pda,
+            &program_id,
+        );
+        let new_payer_ata = get_associated_token_address_with_program(
+            &new_payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
 
-        if let Some(allowance_periods) = self.default_allowance_periods {
-            if allowance_periods == 0 {
-                return Err("Default allowance periods must be > 0".into());
-            }
-        }
+        let allowance_amount = payment_terms_data
+            .amount_usdc
+            .checked_mul(u64::from(allowance_periods))
+            .ok_or_else(|| TallyError::Generic("Arithmetic overflow".to_string()))?;
 
-        // Compute config PDA
-        let config_pda = pda::config_address_with_program_id(&program_id);
+        // The new wallet approves the program delegate before accepting
+        let approve_ix = match token_program {
+            TokenProgram::Token => approve_checked_token(
+                &token_program.program_id(),
+                &new_payer_ata,
+                &payee.usdc_mint,
+                &delegate_pda, // Program delegate PDA
+                &new_payer,    // New payer as owner
+                &[],           // No additional signers
+                allowance_amount,
+                6, // USDC decimals
+            )?,
+            TokenProgram::Token2022 => approve_checked_token2022(
+                &token_program.program_id(),
+                &new_payer_ata,
+                &payee.usdc_mint,
+                &delegate_pda, // Program delegate PDA
+                &new_payer,    // New payer as owner
+                &[],           // No additional signers
+                allowance_amount,
+                6, // USDC decimals
+            )?,
+        };
 
         let accounts = vec![
-            AccountMeta::new(config_pda, false), // config (PDA, mutable)
-            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+            AccountMeta::new(old_agreement_pda, false),     // payment agreement (PDA, closed)
+            AccountMeta::new(new_agreement_pda, false),     // new payment agreement (PDA, created)
+            AccountMeta::new_readonly(payment_terms, false), // payment_terms
+            AccountMeta::new_readonly(payee_pda, false),    // payee
+            AccountMeta::new(old_payer, false),             // old_payer (receives rent)
+            AccountMeta::new(new_payer, true),              // new_payer (signer, pays rent)
+            AccountMeta::new_readonly(new_payer_ata, false), // new_payer_usdc_ata
+            AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new(old_queue_pda, false),         // renewal_queue (mutable)
+            AccountMeta::new(new_queue_pda, false),         // new_renewal_queue (PDA, created if needed)
+            AccountMeta::new_readonly(system_program::ID, false), // system_program
         ];
 
-        let args = UpdateConfigArgs {
-            keeper_fee_bps: self.keeper_fee_bps,
-            max_withdrawal_amount: self.max_withdrawal_amount,
-            max_grace_period_seconds: self.max_grace_period_seconds,
-            min_platform_fee_bps: self.min_platform_fee_bps,
-            max_platform_fee_bps: self.max_platform_fee_bps,
-            min_period_seconds: self.min_period_seconds,
-            default_allowance_periods: self.default_allowance_periods,
-        };
-
+        let args = AcceptAgreementTransferArgs { renewal_bucket };
         let data = {
             let mut data = Vec::new();
-            // Instruction discriminator (computed from "global:update_config")
-            data.extend_from_slice(&[29, 158, 252, 191, 10, 83, 219, 99]);
+            // Instruction discriminator (computed from "accept_agreement_transfer")
+            data.extend_from_slice(&[200, 45, 22, 115, 91, 45, 150, 37]);
             borsh::to_writer(&mut data, &args)
                 .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
             data
         };
 
-        Ok(Instruction {
+        let accept_ix = Instruction {
             program_id,
             accounts,
             data,
-        })
+        };
+
+        Ok(vec![approve_ix, accept_ix])
     }
 }
 
+impl RefundPaymentBuilder {
+    /// Create a new refund payment builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-// Convenience functions for common transaction building patterns
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
 
-/// Create a start agreement transaction builder
-#[must_use]
-pub fn start_agreement() -> StartAgreementBuilder {
-    StartAgreementBuilder::new()
-}
+    /// Set the payer pubkey (receives the refund)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
 
-/// Create a pause agreement transaction builder
-#[must_use]
-pub fn pause_agreement() -> PauseAgreementBuilder {
-    PauseAgreementBuilder::new()
-}
+    /// Set the amount to refund in USDC microlamports
+    #[must_use]
+    pub const fn amount(mut self, amount: u64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
 
-/// Create a payee initialization transaction builder
-#[must_use]
-pub fn init_payee() -> InitPayeeBuilder {
-    InitPayeeBuilder::new()
-}
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
 
-/// Create a `payment_terms` creation transaction builder
-#[must_use]
-pub fn create_payment_terms() -> CreatePaymentTermsBuilder {
-    CreatePaymentTermsBuilder::new()
-}
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
 
-/// Create an admin withdraw fees transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn admin_withdraw_fees() -> AdminWithdrawFeesBuilder {
-    AdminWithdrawFeesBuilder::new()
-}
+    /// Build the transaction instruction
+    ///
+    /// The payee authority signs the instruction and owns the treasury the refund is
+    /// paid from.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `refund_payment` instruction
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(self, payee: &Payee) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let amount = self.amount.ok_or("Amount not set")?;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
 
-/// Create a config initialization transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn init_config() -> InitConfigBuilder {
-    InitConfigBuilder::new()
-}
+        let program_id = self.program_id.unwrap_or_else(program_id);
 
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let payer_ata = get_associated_token_address_with_program(
+            &payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
 
-/// Create a execute payment transaction builder
-#[must_use]
-pub fn execute_payment() -> ExecutePaymentBuilder {
-    ExecutePaymentBuilder::new()
-}
+        let accounts = vec![
+            AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable)
+            AccountMeta::new_readonly(payment_terms, false), // payment_terms
+            AccountMeta::new_readonly(payee_pda, false),    // payee
+            AccountMeta::new_readonly(payee.authority, true), // authority (signer)
+            AccountMeta::new(payee.treasury_ata, false),    // payee_treasury_ata (mutable)
+            AccountMeta::new(payer_ata, false),             // payer_usdc_ata (mutable)
+            AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+        ];
 
-/// Create a close agreement transaction builder
-#[must_use]
-pub fn close_agreement() -> CloseAgreementBuilder {
-    CloseAgreementBuilder::new()
-}
+        let args = RefundPaymentArgs { amount };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "refund_payment")
+            data.extend_from_slice(&[121, 205, 211, 181, 202, 147, 45, 248]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
 
-/// Create a transfer authority transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn transfer_authority() -> TransferAuthorityBuilder {
-    TransferAuthorityBuilder::new()
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
 }
 
-/// Create an accept authority transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn accept_authority() -> AcceptAuthorityBuilder {
-    AcceptAuthorityBuilder::new()
+impl ClaimDepositBuilder {
+    /// Create a new claim deposit builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey (whose deposit is claimed)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the amount to claim in USDC microlamports
+    #[must_use]
+    pub const fn amount(mut self, amount: u64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// The payee authority signs the instruction; the claimed amount moves from the
+    /// program delegate's escrow ATA to the payee treasury. The program only accepts
+    /// claims on agreements that are no longer active.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `claim_deposit` instruction
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(self, payee: &Payee) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let amount = self.amount.ok_or("Amount not set")?;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let escrow_ata = get_associated_token_address_with_program(
+            &delegate_pda,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        let accounts = vec![
+            AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable)
+            AccountMeta::new_readonly(payment_terms, false), // payment_terms
+            AccountMeta::new_readonly(payee_pda, false),    // payee
+            AccountMeta::new_readonly(payee.authority, true), // authority (signer)
+            AccountMeta::new(escrow_ata, false),            // escrow_ata (mutable)
+            AccountMeta::new(payee.treasury_ata, false),    // payee_treasury_ata (mutable)
+            AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
+            AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+        ];
+
+        let args = ClaimDepositArgs { amount };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "claim_deposit")
+            data.extend_from_slice(&[201, 106, 1, 224, 122, 144, 210, 155]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
 }
 
-/// Create a cancel authority transfer transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn cancel_authority_transfer() -> CancelAuthorityTransferBuilder {
-    CancelAuthorityTransferBuilder::new()
+impl ConfirmActivationBuilder {
+    /// Create a new confirm activation builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey of the agreement being activated
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// The payee authority signs the instruction; the escrowed payment is split between
+    /// the payee treasury and the platform treasury.
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `platform_treasury_ata` - Platform treasury ATA address
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `confirm_activation` instruction
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(
+        self,
+        payee: &Payee,
+        platform_treasury_ata: &Pubkey,
+    ) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let escrow_ata = get_associated_token_address_with_program(
+            &delegate_pda,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false),    // config
+            AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable)
+            AccountMeta::new(payment_terms, false), // payment_terms (mutable for revenue)
+            AccountMeta::new(payee_pda, false), // payee (mutable for revenue)
+            AccountMeta::new_readonly(payee.authority, true), // authority (signer)
+            AccountMeta::new(escrow_ata, false),            // escrow_ata (mutable)
+            AccountMeta::new(payee.treasury_ata, false),    // payee_treasury_ata (mutable)
+            AccountMeta::new(*platform_treasury_ata, false), // platform_treasury_ata (mutable)
+            AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
+            AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+        ];
+
+        let args = ConfirmActivationArgs {};
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "confirm_activation")
+            data.extend_from_slice(&[226, 158, 162, 48, 18, 108, 131, 224]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
 }
 
-/// Create a pause program transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn pause() -> PauseBuilder {
-    PauseBuilder::new()
-}
+impl RefundEscrowBuilder {
+    /// Create a new refund escrow builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey (receives the refund)
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the signer submitting the refund (defaults to the payer)
+    ///
+    /// Refunding a lapsed escrow is permissionless, so keepers can submit it on the
+    /// payer's behalf.
+    #[must_use]
+    pub const fn caller(mut self, caller: Pubkey) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `refund_escrow` instruction
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(self, payee: &Payee) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let caller = self.caller.unwrap_or(payer);
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let escrow_ata = get_associated_token_address_with_program(
+            &delegate_pda,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+        let payer_ata = get_associated_token_address_with_program(
+            &payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        let accounts = vec![
+            AccountMeta::new(payment_agreement_pda, false), // payment agreement (PDA, mutable)
+            AccountMeta::new(payment_terms, false),         // payment_terms (mutable)
+            AccountMeta::new(payee_pda, false), // payee (mutable for agreement counters)
+            AccountMeta::new_readonly(caller, true),        // caller (signer)
+            AccountMeta::new(escrow_ata, false),            // escrow_ata (mutable)
+            AccountMeta::new(payer_ata, false),             // payer_usdc_ata (mutable)
+            AccountMeta::new_readonly(payee.usdc_mint, false), // usdc_mint
+            AccountMeta::new_readonly(delegate_pda, false), // program_delegate
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+        ];
+
+        let args = RefundEscrowArgs {};
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "refund_escrow")
+            data.extend_from_slice(&[107, 186, 89, 99, 26, 194, 23, 204]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl ResumeAfterUnfreezeBuilder {
+    /// Create a new resume after unfreeze builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `payment_terms` PDA
+    #[must_use]
+    pub const fn payment_terms(mut self, payment_terms: Pubkey) -> Self {
+        self.payment_terms = Some(payment_terms);
+        self
+    }
+
+    /// Set the payer pubkey whose token account was thawed
+    #[must_use]
+    pub const fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the signer submitting the transaction (defaults to the payer)
+    ///
+    /// Reinstating a thawed agreement is permissionless, so keepers can submit it on
+    /// the payer's behalf. The caller pays for the renewal queue if it doesn't exist.
+    #[must_use]
+    pub const fn caller(mut self, caller: Pubkey) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Set the renewal queue bucket of the agreement's next payment
+    ///
+    /// Defaults to the bucket of the agreement's `next_payment_ts`, or of the current
+    /// time if that has passed.
+    #[must_use]
+    pub const fn renewal_bucket(mut self, bucket: u64) -> Self {
+        self.renewal_bucket = Some(bucket);
+        self
+    }
+
+    /// Set the token program to use
+    #[must_use]
+    pub const fn token_program(mut self, token_program: TokenProgram) -> Self {
+        self.token_program = Some(token_program);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Arguments
+    /// * `payee` - The payee account data
+    /// * `agreement` - The suspended `payment_agreement` account data
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `resume_after_unfreeze` instruction
+    /// * `Err(TallyError)` - If building fails
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    pub fn build_instruction(self, payee: &Payee, agreement: &PaymentAgreement) -> Result<Instruction> {
+        let payment_terms = self.payment_terms.ok_or("PaymentTerms not set")?;
+        let payer = self.payer.ok_or("Payer not set")?;
+        let caller = self.caller.unwrap_or(payer);
+        let token_program = self.token_program.unwrap_or(TokenProgram::Token);
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payment_agreement_pda =
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee.original_authority, &program_id);
+        let payer_ata = get_associated_token_address_with_program(
+            &payer,
+            &payee.usdc_mint,
+            token_program,
+        )?;
+
+        // The program reschedules a payment missed while frozen to the current time
+        let renewal_bucket = if let Some(bucket) = self.renewal_bucket {
+            bucket
+        } else {
+            let now = chrono::Utc::now().timestamp();
+            pda::renewal_bucket(agreement.next_payment_ts.max(now))
+                .ok_or("Invalid renewal bucket")?
+        };
+        let renewal_queue_pda =
+            pda::agreement_renewal_queue_address_with_program_id(
+                renewal_bucket,
+                &payment_agreement_pda,
+                &program_id,
+            );
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false),       // config
+            AccountMeta::new(payment_agreement_pda, false),     // payment_agreement (PDA)
+            AccountMeta::new(payment_terms, false),             // payment_terms (mutable)
+            AccountMeta::new(payee_pda, false), // payee (mutable for agreement counters)
+            AccountMeta::new(caller, true),                     // caller (signer, pays for queue)
+            AccountMeta::new_readonly(payer_ata, false),        // payer_usdc_ata
+            AccountMeta::new(renewal_queue_pda, false),         // renewal_queue (PDA, created if needed)
+            AccountMeta::new_readonly(token_program.program_id(), false), // token_program
+            AccountMeta::new_readonly(system_program::ID, false), // system_program
+        ];
+
+        let args = ResumeAfterUnfreezeArgs { renewal_bucket };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:resume_after_unfreeze")
+            data.extend_from_slice(&[47, 253, 126, 127, 182, 81, 189, 59]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl SetKeeperPolicyBuilder {
+    /// Create a new set keeper policy builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the payee authority (must be signer)
+    #[must_use]
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Set the authority the payee was created with, if its authority has since
+    /// been transferred (used to derive the payee PDA, defaults to `authority`)
+    #[must_use]
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
+        self
+    }
+
+    /// Set whether any keeper may execute payments
+    #[must_use]
+    pub const fn open_execution(mut self, open_execution: bool) -> Self {
+        self.open_execution = Some(open_execution);
+        self
+    }
+
+    /// Set the keepers allowed to execute payments (replaces the current list)
+    #[must_use]
+    pub fn authorized_keepers(mut self, authorized_keepers: Vec<Pubkey>) -> Self {
+        self.authorized_keepers = authorized_keepers;
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `set_keeper_policy` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let authority = self.authority.ok_or("Authority not set")?;
+        let open_execution = self.open_execution.ok_or("Open execution not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        let original_authority = self.original_authority.unwrap_or(authority);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new(payee_pda, false),          // payee (PDA, mutable)
+            AccountMeta::new_readonly(authority, true), // authority (signer)
+        ];
+
+        let args = SetKeeperPolicyArgs {
+            open_execution,
+            authorized_keepers: self.authorized_keepers,
+        };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "set_keeper_policy")
+            data.extend_from_slice(&[113, 131, 98, 244, 25, 146, 140, 188]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl TransferPayeeAuthorityBuilder {
+    /// Create a new transfer payee authority builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current payee authority (must be signer)
+    #[must_use]
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Set the authority the payee was created with, if its authority has since
+    /// been transferred (used to derive the payee PDA, defaults to `authority`)
+    #[must_use]
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
+        self
+    }
+
+    /// Set the new authority to transfer the payee to
+    #[must_use]
+    pub const fn new_authority(mut self, new_authority: Pubkey) -> Self {
+        self.new_authority = Some(new_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `transfer_payee_authority` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let authority = self.authority.ok_or("Authority not set")?;
+        let new_authority = self.new_authority.ok_or("New authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        let original_authority = self.original_authority.unwrap_or(authority);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new(payee_pda, false),          // payee (PDA, mutable)
+            AccountMeta::new_readonly(authority, true), // authority (signer)
+        ];
+
+        let args = TransferPayeeAuthorityArgs { new_authority };
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:transfer_payee_authority")
+            data.extend_from_slice(&[158, 105, 60, 223, 133, 232, 77, 152]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl AcceptPayeeAuthorityBuilder {
+    /// Create a new accept payee authority builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the new authority (must be signer and pending authority)
+    #[must_use]
+    pub const fn new_authority(mut self, new_authority: Pubkey) -> Self {
+        self.new_authority = Some(new_authority);
+        self
+    }
+
+    /// Set the authority the payee was created with (used to derive the payee PDA)
+    #[must_use]
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
+        self
+    }
+
+    /// Set the payee's USDC mint (used to derive the new treasury ATA)
+    #[must_use]
+    pub const fn usdc_mint(mut self, usdc_mint: Pubkey) -> Self {
+        self.usdc_mint = Some(usdc_mint);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// The new authority's USDC ATA becomes the payee treasury and must exist
+    /// before the instruction runs.
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `accept_payee_authority` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let new_authority = self.new_authority.ok_or("New authority not set")?;
+        let original_authority = self.original_authority.ok_or("Original authority not set")?;
+        let usdc_mint = self.usdc_mint.ok_or("USDC mint not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+        let new_treasury_ata =
+            get_associated_token_address_with_program(&new_authority, &usdc_mint, TokenProgram::Token)?;
+
+        let accounts = vec![
+            AccountMeta::new(payee_pda, false),                  // payee (PDA, mutable)
+            AccountMeta::new_readonly(new_authority, true),     // new_authority (signer)
+            AccountMeta::new_readonly(new_treasury_ata, false), // new_treasury_ata
+            AccountMeta::new_readonly(TokenProgram::Token.program_id(), false), // token_program
+        ];
+
+        let args = AcceptPayeeAuthorityArgs::default();
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:accept_payee_authority")
+            data.extend_from_slice(&[226, 96, 208, 121, 93, 75, 227, 251]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+impl CancelPayeeAuthorityTransferBuilder {
+    /// Create a new cancel payee authority transfer builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current payee authority (must be signer)
+    #[must_use]
+    pub const fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Set the authority the payee was created with, if its authority has since
+    /// been transferred (used to derive the payee PDA, defaults to `authority`)
+    #[must_use]
+    pub const fn original_authority(mut self, original_authority: Pubkey) -> Self {
+        self.original_authority = Some(original_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `cancel_payee_authority_transfer` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let authority = self.authority.ok_or("Authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        let original_authority = self.original_authority.unwrap_or(authority);
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new(payee_pda, false),          // payee (PDA, mutable)
+            AccountMeta::new_readonly(authority, true), // authority (signer)
+        ];
+
+        let args = CancelPayeeAuthorityTransferArgs::default();
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:cancel_payee_authority_transfer")
+            data.extend_from_slice(&[4, 104, 244, 236, 138, 214, 187, 45]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl TransferAuthorityBuilder {
+    /// Create a new transfer authority builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the new authority to transfer to
+    #[must_use]
+    pub const fn new_authority(mut self, new_authority: Pubkey) -> Self {
+        self.new_authority = Some(new_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `transfer_authority` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+        let new_authority = self.new_authority.ok_or("New authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute config PDA
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::TransferAuthorityArgs { new_authority };
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:transfer_authority")
+            data.extend_from_slice(&[48, 169, 76, 72, 229, 180, 55, 161]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl AcceptAuthorityBuilder {
+    /// Create a new accept authority builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the new authority (must be signer and pending authority)
+    #[must_use]
+    pub const fn new_authority(mut self, new_authority: Pubkey) -> Self {
+        self.new_authority = Some(new_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `accept_authority` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let new_authority = self.new_authority.ok_or("New authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute config PDA
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(new_authority, true), // new_authority (signer)
+        ];
+
+        let args = crate::program_types::AcceptAuthorityArgs::default();
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:accept_authority")
+            data.extend_from_slice(&[107, 86, 198, 91, 33, 12, 107, 160]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl CancelAuthorityTransferBuilder {
+    /// Create a new cancel authority transfer builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `cancel_authority_transfer` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute config PDA
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::CancelAuthorityTransferArgs::default();
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:cancel_authority_transfer")
+            data.extend_from_slice(&[94, 131, 125, 184, 183, 24, 125, 229]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl PauseBuilder {
+    /// Create a new pause builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the reason shown to integrators while the program is paused (at most 64 bytes)
+    #[must_use]
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Schedule the pause to lapse automatically at a Unix timestamp
+    #[must_use]
+    pub const fn auto_unpause_ts(mut self, auto_unpause_ts: i64) -> Self {
+        self.auto_unpause_ts = Some(auto_unpause_ts);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `pause` instruction
+    /// * `Err(TallyError)` - If building fails or the reason exceeds 64 bytes
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+
+        let reason_text = self.reason.unwrap_or_default();
+        if reason_text.len() > 64 {
+            return Err("Pause reason must be at most 64 bytes".into());
+        }
+        let mut reason = [0u8; 64];
+        reason[..reason_text.len()].copy_from_slice(reason_text.as_bytes());
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute config PDA
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::PauseArgs {
+            reason,
+            auto_unpause_ts: self.auto_unpause_ts,
+        };
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:pause")
+            data.extend_from_slice(&[211, 22, 221, 251, 74, 121, 193, 47]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl UnpauseBuilder {
+    /// Create a new unpause builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `unpause` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute config PDA
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::UnpauseArgs {};
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:unpause")
+            data.extend_from_slice(&[169, 144, 4, 38, 10, 141, 188, 255]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl FreezePayeeBuilder {
+    /// Create a new freeze payee builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the authority of the payee to freeze (used to derive the payee PDA)
+    #[must_use]
+    pub const fn payee_authority(mut self, payee_authority: Pubkey) -> Self {
+        self.payee_authority = Some(payee_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `freeze_payee` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+        let payee_authority = self.payee_authority.ok_or("Payee authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute required PDAs
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false), // config (PDA)
+            AccountMeta::new(payee_pda, false),           // payee (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::FreezePayeeArgs {};
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:freeze_payee")
+            data.extend_from_slice(&[156, 126, 104, 149, 244, 68, 176, 95]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl UnfreezePayeeBuilder {
+    /// Create a new unfreeze payee builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the authority of the payee to unfreeze (used to derive the payee PDA)
+    #[must_use]
+    pub const fn payee_authority(mut self, payee_authority: Pubkey) -> Self {
+        self.payee_authority = Some(payee_authority);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `unfreeze_payee` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+        let payee_authority = self.payee_authority.ok_or("Payee authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute required PDAs
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false), // config (PDA)
+            AccountMeta::new(payee_pda, false),           // payee (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::UnfreezePayeeArgs {};
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:unfreeze_payee")
+            data.extend_from_slice(&[83, 40, 102, 144, 194, 101, 157, 195]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl SetFeeHolidayBuilder {
+    /// Create a new set fee holiday builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the authority of the payee receiving the fee holiday (used to derive the payee PDA)
+    #[must_use]
+    pub const fn payee_authority(mut self, payee_authority: Pubkey) -> Self {
+        self.payee_authority = Some(payee_authority);
+        self
+    }
+
+    /// Set the Unix timestamp at which the discount ends
+    #[must_use]
+    pub const fn until_ts(mut self, until_ts: i64) -> Self {
+        self.until_ts = Some(until_ts);
+        self
+    }
+
+    /// Set the basis points subtracted from the payee's tier fee (zero ends the holiday)
+    #[must_use]
+    pub const fn rebate_bps(mut self, rebate_bps: u16) -> Self {
+        self.rebate_bps = Some(rebate_bps);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `set_fee_holiday` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+        let payee_authority = self.payee_authority.ok_or("Payee authority not set")?;
+        let until_ts = self.until_ts.ok_or("Until timestamp not set")?;
+        let rebate_bps = self.rebate_bps.ok_or("Rebate basis points not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Compute required PDAs
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let payee_pda = pda::payee_address_with_program_id(&payee_authority, &program_id);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false), // config (PDA)
+            AccountMeta::new(payee_pda, false),           // payee (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::SetFeeHolidayArgs {
+            until_ts,
+            rebate_bps,
+        };
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:set_fee_holiday")
+            data.extend_from_slice(&[12, 30, 9, 225, 238, 1, 36, 239]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl SetKeeperSolRateBuilder {
+    /// Create a new set keeper SOL rate builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the rate in lamports per whole USDC
+    ///
+    /// The program stamps the rate with the current time; it must be set again within
+    /// [`crate::MAX_KEEPER_SOL_RATE_AGE_SECS`] to keep SOL keeper fees available. Leave
+    /// unset to pay every keeper fee in USDC again.
+    #[must_use]
+    pub const fn rate(mut self, lamports_per_usdc: u64) -> Self {
+        self.lamports_per_usdc = Some(lamports_per_usdc);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `set_keeper_sol_rate` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+
+        if self.lamports_per_usdc == Some(0) {
+            return Err(TallyError::Generic(
+                "Keeper SOL rate must be positive".to_string(),
+            ));
+        }
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = crate::program_types::SetKeeperSolRateArgs {
+            lamports_per_usdc: self.lamports_per_usdc,
+        };
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:set_keeper_sol_rate")
+            data.extend_from_slice(&[39, 1, 201, 33, 122, 210, 202, 103]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "test-clock")]
+impl AdvanceTestClockBuilder {
+    /// Create a new advance test clock builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer, pays for the test clock account)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the number of seconds to move the test clock forward by
+    #[must_use]
+    pub const fn secs(mut self, secs: u64) -> Self {
+        self.secs = Some(secs);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// The program must be built with the `test-clock` feature; other builds reject
+    /// the instruction as unknown.
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `advance_test_clock` instruction
+    /// * `Err(TallyError)` - If building fails
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+        let secs = self.secs.ok_or("Seconds not set")?;
+
+        if secs == 0 || i64::try_from(secs).is_err() {
+            return Err(TallyError::Generic(
+                "Test clock must advance by a positive number of seconds".to_string(),
+            ));
+        }
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let test_clock_pda = pda::test_clock_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(config_pda, false), // config (PDA)
+            AccountMeta::new(test_clock_pda, false),      // test_clock (PDA, created if needed)
+            AccountMeta::new(platform_authority, true),   // platform_authority (signer, payer)
+            AccountMeta::new_readonly(system_program::ID, false), // system_program
+        ];
+
+        let args = crate::program_types::AdvanceTestClockArgs { secs };
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:advance_test_clock")
+            data.extend_from_slice(&[219, 83, 45, 165, 9, 127, 222, 135]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "platform-admin")]
+impl UpdateConfigBuilder {
+    /// Create a new update config builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the platform authority (must be signer)
+    #[must_use]
+    pub const fn platform_authority(mut self, platform_authority: Pubkey) -> Self {
+        self.platform_authority = Some(platform_authority);
+        self
+    }
+
+    /// Set the keeper fee in basis points (0-100)
+    #[must_use]
+    pub const fn keeper_fee_bps(mut self, keeper_fee_bps: u16) -> Self {
+        self.keeper_fee_bps = Some(keeper_fee_bps);
+        self
+    }
+
+    /// Set the maximum withdrawal amount
+    #[must_use]
+    pub const fn max_withdrawal_amount(mut self, max_withdrawal_amount: u64) -> Self {
+        self.max_withdrawal_amount = Some(max_withdrawal_amount);
+        self
+    }
+
+    /// Set the maximum grace period in seconds
+    #[must_use]
+    pub const fn max_grace_period_seconds(mut self, max_grace_period_seconds: u64) -> Self {
+        self.max_grace_period_seconds = Some(max_grace_period_seconds);
+        self
+    }
+
+    /// Set the minimum platform fee in basis points
+    #[must_use]
+    pub const fn min_platform_fee_bps(mut self, min_platform_fee_bps: u16) -> Self {
+        self.min_platform_fee_bps = Some(min_platform_fee_bps);
+        self
+    }
+
+    /// Set the maximum platform fee in basis points
+    #[must_use]
+    pub const fn max_platform_fee_bps(mut self, max_platform_fee_bps: u16) -> Self {
+        self.max_platform_fee_bps = Some(max_platform_fee_bps);
+        self
+    }
+
+    /// Set the minimum period in seconds
+    #[must_use]
+    pub const fn min_period_seconds(mut self, min_period_seconds: u64) -> Self {
+        self.min_period_seconds = Some(min_period_seconds);
+        self
+    }
+
+    /// Set the default allowance periods
+    #[must_use]
+    pub const fn default_allowance_periods(mut self, default_allowance_periods: u8) -> Self {
+        self.default_allowance_periods = Some(default_allowance_periods);
+        self
+    }
+
+    /// Set the renewal clock-skew tolerance in seconds (zero disables it)
+    #[must_use]
+    pub const fn renewal_tolerance_secs(mut self, renewal_tolerance_secs: u64) -> Self {
+        self.renewal_tolerance_secs = Some(renewal_tolerance_secs);
+        self
+    }
+
+    /// Set the program ID to use
+    #[must_use]
+    pub const fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Build the transaction instruction
+    ///
+    /// # Returns
+    /// * `Ok(Instruction)` - The `update_config` instruction
+    /// * `Err(TallyError)` - If building fails
+    ///
+    /// # Validation
+    /// * Platform authority must be set
+    /// * At least one field must be set for update
+    /// * `keeper_fee_bps` <= 100 if provided
+    /// * `min_platform_fee_bps` <= `max_platform_fee_bps` if both provided
+    /// * `renewal_tolerance_secs` <= `MAX_RENEWAL_TOLERANCE_SECS` if provided
+    /// * All numeric values > 0 where required
+    pub fn build_instruction(self) -> Result<Instruction> {
+        let platform_authority = self
+            .platform_authority
+            .ok_or("Platform authority not set")?;
+
+        let program_id = self.program_id.unwrap_or_else(program_id);
+
+        // Validate at least one field is set for update
+        let has_update = self.keeper_fee_bps.is_some()
+            || self.max_withdrawal_amount.is_some()
+            || self.max_grace_period_seconds.is_some()
+            || self.min_platform_fee_bps.is_some()
+            || self.max_platform_fee_bps.is_some()
+            || self.min_period_seconds.is_some()
+            || self.default_allowance_periods.is_some()
+            || self.renewal_tolerance_secs.is_some();
+
+        if !has_update {
+            return Err("At least one configuration field must be set for update".into());
+        }
+
+        // Validate keeper_fee_bps <= 100 if provided
+        if let Some(keeper_fee) = self.keeper_fee_bps {
+            if keeper_fee > 100 {
+                return Err("Keeper fee must be <= 100 basis points (1%)".into());
+            }
+        }
+
+        // Validate min_platform_fee_bps <= max_platform_fee_bps if both provided
+        if let Some(min_fee) = self.min_platform_fee_bps {
+            if let Some(max_fee) = self.max_platform_fee_bps {
+                if min_fee > max_fee {
+                    return Err("Minimum platform fee must be <= maximum platform fee".into());
+                }
+            }
+        }
+
+        // Validate numeric values > 0 where required
+        if let Some(max_withdrawal) = self.max_withdrawal_amount {
+            if max_withdrawal == 0 {
+                return Err("Maximum withdrawal amount must be > 0".into());
+            }
+        }
+
+        if let Some(max_grace) = self.max_grace_period_seconds {
+            if max_grace == 0 {
+                return Err("Maximum grace period must be > 0".into());
+            }
+        }
+
+        if let Some(min_period) = self.min_period_seconds {
+            if min_period == 0 {
+                return Err("Minimum period must be > 0".into());
+            }
+        }
+
+        if let Some(allowance_periods) = self.default_allowance_periods {
+            if allowance_periods == 0 {
+                return Err("Default allowance periods must be > 0".into());
+            }
+        }
+
+        if let Some(tolerance) = self.renewal_tolerance_secs {
+            if tolerance > crate::MAX_RENEWAL_TOLERANCE_SECS {
+                return Err(format!(
+                    "Renewal tolerance must be <= {} seconds",
+                    crate::MAX_RENEWAL_TOLERANCE_SECS
+                )
+                .into());
+            }
+        }
+
+        // Compute config PDA
+        let config_pda = pda::config_address_with_program_id(&program_id);
+
+        let accounts = vec![
+            AccountMeta::new(config_pda, false), // config (PDA, mutable)
+            AccountMeta::new_readonly(platform_authority, true), // platform_authority (signer)
+        ];
+
+        let args = UpdateConfigArgs {
+            keeper_fee_bps: self.keeper_fee_bps,
+            max_withdrawal_amount: self.max_withdrawal_amount,
+            max_grace_period_seconds: self.max_grace_period_seconds,
+            min_platform_fee_bps: self.min_platform_fee_bps,
+            max_platform_fee_bps: self.max_platform_fee_bps,
+            min_period_seconds: self.min_period_seconds,
+            default_allowance_periods: self.default_allowance_periods,
+            renewal_tolerance_secs: self.renewal_tolerance_secs,
+        };
+
+        let data = {
+            let mut data = Vec::new();
+            // Instruction discriminator (computed from "global:update_config")
+            data.extend_from_slice(&[29, 158, 252, 191, 10, 83, 219, 99]);
+            borsh::to_writer(&mut data, &args)
+                .map_err(|e| TallyError::Generic(format!("Failed to serialize args: {e}")))?;
+            data
+        };
+
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+}
+
+
+// Convenience functions for common transaction building patterns
+
+/// Create a start agreement transaction builder
+#[must_use]
+pub fn start_agreement() -> StartAgreementBuilder {
+    StartAgreementBuilder::new()
+}
+
+/// Create a pause agreement transaction builder
+#[must_use]
+pub fn pause_agreement() -> PauseAgreementBuilder {
+    PauseAgreementBuilder::new()
+}
+
+/// Create a resume agreement transaction builder
+#[must_use]
+pub fn resume_agreement() -> ResumeAgreementBuilder {
+    ResumeAgreementBuilder::new()
+}
+
+/// Create a repair delegate transaction builder
+#[must_use]
+pub fn repair_delegate() -> RepairDelegateBuilder {
+    RepairDelegateBuilder::new()
+}
+
+/// Create a payee initialization transaction builder
+#[must_use]
+pub fn init_payee() -> InitPayeeBuilder {
+    InitPayeeBuilder::new()
+}
+
+/// Create a `payment_terms` creation transaction builder
+#[must_use]
+pub fn create_payment_terms() -> CreatePaymentTermsBuilder {
+    CreatePaymentTermsBuilder::new()
+}
+
+/// Create a schedule terms update transaction builder
+#[must_use]
+pub fn schedule_terms_update() -> ScheduleTermsUpdateBuilder {
+    ScheduleTermsUpdateBuilder::new()
+}
+
+/// Create a deactivate payment terms transaction builder
+#[must_use]
+pub fn deactivate_payment_terms() -> DeactivatePaymentTermsBuilder {
+    DeactivatePaymentTermsBuilder::new()
+}
+
+/// Create an admin withdraw fees transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn admin_withdraw_fees() -> AdminWithdrawFeesBuilder {
+    AdminWithdrawFeesBuilder::new()
+}
+
+/// Create a config initialization transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn init_config() -> InitConfigBuilder {
+    InitConfigBuilder::new()
+}
+
+
+/// Create a execute payment transaction builder
+#[must_use]
+pub fn execute_payment() -> ExecutePaymentBuilder {
+    ExecutePaymentBuilder::new()
+}
+
+/// Create a close agreement transaction builder
+#[must_use]
+pub fn close_agreement() -> CloseAgreementBuilder {
+    CloseAgreementBuilder::new()
+}
+
+/// Create a close agreements batch transaction builder
+#[must_use]
+pub fn close_agreements_batch() -> CloseAgreementsBatchBuilder {
+    CloseAgreementsBatchBuilder::new()
+}
+
+/// Create a migrate account transaction builder
+#[must_use]
+pub fn migrate_account() -> MigrateAccountBuilder {
+    MigrateAccountBuilder::new()
+}
+
+/// Create a sweep accrued transaction builder
+#[must_use]
+pub fn sweep_accrued() -> SweepAccruedBuilder {
+    SweepAccruedBuilder::new()
+}
+
+/// Create a schedule cancellation transaction builder
+#[must_use]
+pub fn schedule_cancellation() -> ScheduleCancellationBuilder {
+    ScheduleCancellationBuilder::new()
+}
+
+/// Create a reserve slot (waitlist) transaction builder
+#[must_use]
+pub fn reserve_slot() -> ReserveSlotBuilder {
+    ReserveSlotBuilder::new()
+}
+
+/// Create an initiate agreement transfer transaction builder
+#[must_use]
+pub fn initiate_agreement_transfer() -> InitiateAgreementTransferBuilder {
+    InitiateAgreementTransferBuilder::new()
+}
+
+/// Create an accept agreement transfer transaction builder
+#[must_use]
+pub fn accept_agreement_transfer() -> AcceptAgreementTransferBuilder {
+    AcceptAgreementTransferBuilder::new()
+}
+
+/// Create a refund payment transaction builder
+#[must_use]
+pub fn refund_payment() -> RefundPaymentBuilder {
+    RefundPaymentBuilder::new()
+}
+
+/// Create a claim deposit transaction builder
+#[must_use]
+pub fn claim_deposit() -> ClaimDepositBuilder {
+    ClaimDepositBuilder::new()
+}
+
+/// Create a confirm activation transaction builder
+#[must_use]
+pub fn confirm_activation() -> ConfirmActivationBuilder {
+    ConfirmActivationBuilder::new()
+}
+
+/// Create a refund escrow transaction builder
+#[must_use]
+pub fn refund_escrow() -> RefundEscrowBuilder {
+    RefundEscrowBuilder::new()
+}
+
+/// Create a resume after unfreeze transaction builder
+#[must_use]
+pub fn resume_after_unfreeze() -> ResumeAfterUnfreezeBuilder {
+    ResumeAfterUnfreezeBuilder::new()
+}
+
+/// Create a set keeper policy transaction builder
+#[must_use]
+pub fn set_keeper_policy() -> SetKeeperPolicyBuilder {
+    SetKeeperPolicyBuilder::new()
+}
+
+/// Create a transfer payee authority transaction builder
+#[must_use]
+pub fn transfer_payee_authority() -> TransferPayeeAuthorityBuilder {
+    TransferPayeeAuthorityBuilder::new()
+}
+
+/// Create an accept payee authority transaction builder
+#[must_use]
+pub fn accept_payee_authority() -> AcceptPayeeAuthorityBuilder {
+    AcceptPayeeAuthorityBuilder::new()
+}
+
+/// Create a cancel payee authority transfer transaction builder
+#[must_use]
+pub fn cancel_payee_authority_transfer() -> CancelPayeeAuthorityTransferBuilder {
+    CancelPayeeAuthorityTransferBuilder::new()
+}
+
+/// Create a transfer authority transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn transfer_authority() -> TransferAuthorityBuilder {
+    TransferAuthorityBuilder::new()
+}
+
+/// Create an accept authority transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn accept_authority() -> AcceptAuthorityBuilder {
+    AcceptAuthorityBuilder::new()
+}
+
+/// Create a cancel authority transfer transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn cancel_authority_transfer() -> CancelAuthorityTransferBuilder {
+    CancelAuthorityTransferBuilder::new()
+}
+
+/// Create a pause program transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn pause() -> PauseBuilder {
+    PauseBuilder::new()
+}
+
+/// Create an unpause program transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn unpause() -> UnpauseBuilder {
+    UnpauseBuilder::new()
+}
+
+/// Create a freeze payee transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn freeze_payee() -> FreezePayeeBuilder {
+    FreezePayeeBuilder::new()
+}
+
+/// Create an unfreeze payee transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn unfreeze_payee() -> UnfreezePayeeBuilder {
+    UnfreezePayeeBuilder::new()
+}
+
+/// Create a set fee holiday transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn set_fee_holiday() -> SetFeeHolidayBuilder {
+    SetFeeHolidayBuilder::new()
+}
+
+/// Create a set keeper SOL rate transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn set_keeper_sol_rate() -> SetKeeperSolRateBuilder {
+    SetKeeperSolRateBuilder::new()
+}
+
+/// Create an advance test clock transaction builder
+#[must_use]
+#[cfg(feature = "test-clock")]
+pub fn advance_test_clock() -> AdvanceTestClockBuilder {
+    AdvanceTestClockBuilder::new()
+}
+
+/// Create an update config transaction builder
+#[must_use]
+#[cfg(feature = "platform-admin")]
+pub fn update_config() -> UpdateConfigBuilder {
+    UpdateConfigBuilder::new()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::signature::{Keypair, Signer};
+    #[cfg(feature = "platform-admin")]
+    use std::str::FromStr;
+
+    #[cfg(feature = "platform-admin")]
+    fn create_test_payee() -> Payee {
+        Payee {
+            authority: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            usdc_mint: Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap(),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            platform_fee_bps: 50,
+            volume_tier: 0, // Standard tier
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            bump: 255,
+            version: 1,
+        }
+    }
+
+    #[cfg(feature = "platform-admin")]
+    fn create_test_payment_terms() -> PaymentTerms {
+        let payee = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let mut terms_id = [0u8; 32];
+        terms_id[..12].copy_from_slice(b"premium_payment_terms");
+        let mut name = [0u8; 32];
+        name[..12].copy_from_slice(b"Premium PaymentTerms");
+
+        PaymentTerms {
+            payee,
+            terms_id,
+            amount_usdc: 5_000_000,  // 5 USDC
+            period_secs: 2_592_000, // 30 days
+            grace_secs: 432_000,    // 5 days
+            name,
+            active: true,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "platform-admin")]
+    fn test_start_payment_agreement_builder() {
+        let payee = create_test_payee();
+        let payment_terms_data = create_test_payment_terms();
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instructions = start_agreement()
+            .payment_terms(payment_terms_key)
+            .payer(payer)
+            .allowance_periods(3) // 3x payment_terms price
+            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .unwrap();
+
+        assert_eq!(instructions.len(), 2, "Should have 2 instructions: approve_checked and start_payment_agreement");
+
+        // ========== Validate approve_checked instruction ==========
+        let approve_ix = &instructions[0];
+        assert_eq!(approve_ix.program_id, spl_token::id(), "First instruction must be SPL Token program");
+
+        // Validate allowance calculation (3 periods × payment_terms price)
+        let expected_allowance = payment_terms_data.amount_usdc.checked_mul(3).expect("Allowance overflow");
+
+        // Decode approve_checked data to validate amount
+        // approve_checked format: [discriminator(1 byte), amount(8 bytes), decimals(1 byte)]
+        assert!(approve_ix.data.len() >= 10, "approve_checked data should be at least 10 bytes");
+
+        // The first byte should be the ApproveChecked discriminator (13)
+        assert_eq!(approve_ix.data[0], 13, "approve_checked instruction discriminator should be 13");
+
+        // Extract amount (bytes 1-8, little-endian u64)
+        let amount_bytes: [u8; 8] = approve_ix.data[1..9].try_into().unwrap();
+        let actual_amount = u64::from_le_bytes(amount_bytes);
+        assert_eq!(actual_amount, expected_allowance, "Allowance amount should be 3x payment_terms price");
+
+        // Decimals byte (byte 9) should be 6 for USDC
+        assert_eq!(approve_ix.data[9], 6, "USDC decimals should be 6");
+
+        // Validate approve_checked accounts structure
+        assert_eq!(approve_ix.accounts.len(), 4, "approve_checked requires 4 accounts");
+
+        // Account 0: Source account (payer's token account, writable)
+        assert!(approve_ix.accounts[0].is_writable, "Source account must be writable");
+
+        // Account 1: Mint (USDC mint, readonly)
+        assert!(!approve_ix.accounts[1].is_writable, "Mint must be readonly");
+        assert_eq!(approve_ix.accounts[1].pubkey, payee.usdc_mint, "Second account must be USDC mint");
+
+        // Account 2: Delegate (program delegate PDA, readonly)
+        assert!(!approve_ix.accounts[2].is_writable, "Delegate must be readonly");
+
+        // Account 3: Owner (payer, signer)
+        assert!(approve_ix.accounts[3].is_signer, "Owner must be signer");
+
+        // ========== Validate start_payment_agreement instruction ==========
+        let start_sub_ix = &instructions[1];
+        let expected_program_id = program_id();
+        assert_eq!(start_sub_ix.program_id, expected_program_id, "Second instruction must be Tally program");
+
+        // Validate start_payment_agreement discriminator
+        // start_payment_agreement discriminator: [167, 59, 160, 222, 194, 175, 3, 13]
+        assert!(start_sub_ix.data.len() >= 8, "Instruction data should include discriminator");
+        assert_eq!(&start_sub_ix.data[0..8], &[167, 59, 160, 222, 194, 175, 3, 13],
+            "start_payment_agreement discriminator mismatch");
+
+        // Validate account count for start_payment_agreement
+        assert_eq!(start_sub_ix.accounts.len(), 14, "start_payment_agreement requires 14 accounts");
+
+        // Validate key accounts are present (specific indices)
+        // Account indices based on actual start_payment_agreement builder:
+        // 0: config, 1: payment agreement, 2: payment_terms, 3: payee, 4: payer,
+        // 5: payer_ata, 6: payee_treasury_ata, 7: platform_treasury_ata, 8: escrow_ata,
+        // 9: usdc_mint, 10: delegate, 11: renewal_queue, 12: token_program, 13: system_program
+
+        assert_eq!(start_sub_ix.accounts[2].pubkey, payment_terms_key, "PaymentTerms account mismatch");
+        assert_eq!(start_sub_ix.accounts[9].pubkey, payee.usdc_mint, "USDC mint account mismatch");
+        assert_eq!(start_sub_ix.accounts[7].pubkey, platform_treasury_ata, "Platform treasury ATA mismatch");
+
+        // Payer must be signer and writable (pays for payment agreement account creation)
+        assert!(start_sub_ix.accounts[4].is_signer, "Payer must be signer");
+        assert!(start_sub_ix.accounts[4].is_writable, "Payer must be writable");
+
+        // PaymentAgreement PDA must be writable (created in instruction)
+        assert!(start_sub_ix.accounts[1].is_writable, "PaymentAgreement PDA must be writable");
+
+        // Payee must be writable (agreement and revenue counters)
+        assert!(start_sub_ix.accounts[3].is_writable, "Payee must be writable");
+    }
+
+    #[test]
+    #[cfg(feature = "platform-admin")]
+    fn test_start_payment_agreement_allowance_edge_cases() {
+        let payee = create_test_payee();
+        let payment_terms_data = create_test_payment_terms();
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        // Test with 1 period (minimum)
+        let instructions_min = start_agreement()
+            .payment_terms(payment_terms_key)
+            .payer(payer)
+            .allowance_periods(1)
+            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .unwrap();
+
+        let approve_ix_min = &instructions_min[0];
+        let amount_bytes_min: [u8; 8] = approve_ix_min.data[1..9].try_into().unwrap();
+        let amount_min = u64::from_le_bytes(amount_bytes_min);
+        assert_eq!(amount_min, payment_terms_data.amount_usdc, "1 period should equal payment_terms price");
+
+        // Test with 10 periods
+        let instructions_max = start_agreement()
+            .payment_terms(payment_terms_key)
+            .payer(payer)
+            .allowance_periods(10)
+            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .unwrap();
+
+        let approve_ix_max = &instructions_max[0];
+        let amount_bytes_max: [u8; 8] = approve_ix_max.data[1..9].try_into().unwrap();
+        let amount_max = u64::from_le_bytes(amount_bytes_max);
+        assert_eq!(amount_max, payment_terms_data.amount_usdc * 10, "10 periods should be 10x payment_terms price");
+    }
+
+    #[test]
+    #[cfg(feature = "platform-admin")]
+    fn test_start_payment_agreement_with_token2022() {
+        let mut payee = create_test_payee();
+        payee.usdc_mint = Pubkey::from(Keypair::new().pubkey().to_bytes()); // Custom Token2022 mint
+
+        let payment_terms_data = create_test_payment_terms();
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instructions = start_agreement()
+            .payment_terms(payment_terms_key)
+            .payer(payer)
+            .allowance_periods(3)
+            .token_program(TokenProgram::Token2022)
+            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .unwrap();
+
+        // Verify Token2022 program is used
+        assert_eq!(instructions[0].program_id, spl_token_2022::id(), "Should use Token2022 program");
+        assert_eq!(instructions[1].accounts[11].pubkey, spl_token_2022::id(),
+            "start_payment_agreement should reference Token2022");
+    }
+
+    #[test]
+    #[cfg(feature = "platform-admin")]
+    fn test_cancel_payment_agreement_builder() {
+        let payee = create_test_payee();
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instructions = pause_agreement()
+            .payment_terms(payment_terms_key)
+            .payer(payer)
+            .build_instructions(&payee)
+            .unwrap();
+
+        // The program revokes the delegate, so only pause_agreement is sent
+        assert_eq!(instructions.len(), 1);
+        let program_id = program_id();
+        assert_eq!(instructions[0].program_id, program_id);
+        assert_eq!(instructions[0].accounts.len(), 7);
+        assert_eq!(
+            instructions[0].accounts[5].pubkey,
+            pda::delegate_address_with_program_id(&program_id)
+        );
+    }
+
+    #[test]
+    fn test_reserve_slot_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instruction = reserve_slot()
+            .payment_terms(payment_terms_key)
+            .payer(payer)
+            .payee_authority(payee_authority)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 6);
+        assert_eq!(&instruction.data[..8], &[109, 148, 20, 186, 66, 121, 242, 72]);
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            pda::slot_reservation_address_with_program_id(&payment_terms_key, &payer, &program_id)
+        );
+        assert!(instruction.accounts[2].is_writable); // payment_terms (waitlist counter)
+        assert!(instruction.accounts[4].is_signer); // payer
+
+        let result = reserve_slot().payer(payer).build_instruction();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("PaymentTerms not set"));
+    }
+
+    #[test]
+    fn test_accept_agreement_transfer_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let old_payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let new_payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&payee.original_authority, &program_id),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+
+        let instructions = accept_agreement_transfer()
+            .payment_terms(payment_terms_key)
+            .old_payer(old_payer)
+            .new_payer(new_payer)
+            .next_payment_ts(1_700_000_000)
+            .program_id(program_id)
+            .build_instructions(&payee, &terms)
+            .unwrap();
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].program_id, spl_token::id());
+
+        let accept_ix = &instructions[1];
+        assert_eq!(&accept_ix.data[..8], &[200, 45, 22, 115, 91, 45, 150, 37]);
+        assert_eq!(
+            accept_ix.accounts[0].pubkey,
+            pda::payment_agreement_address_with_program_id(&payment_terms_key, &old_payer, &program_id)
+        );
+        assert_eq!(
+            accept_ix.accounts[1].pubkey,
+            pda::payment_agreement_address_with_program_id(&payment_terms_key, &new_payer, &program_id)
+        );
+        assert!(accept_ix.accounts[5].is_signer); // new payer
+        assert!(!accept_ix.accounts[4].is_signer); // old payer doesn't sign
+        assert_eq!(
+            accept_ix.accounts[8].pubkey,
+            pda::agreement_renewal_queue_address_with_program_id(
+                19_675,
+                &accept_ix.accounts[0].pubkey,
+                &program_id
+            )
+        );
+        assert_eq!(
+            accept_ix.accounts[9].pubkey,
+            pda::agreement_renewal_queue_address_with_program_id(
+                19_675,
+                &accept_ix.accounts[1].pubkey,
+                &program_id
+            )
+        );
+        assert!(accept_ix.accounts[9].is_writable);
+        assert_eq!(&accept_ix.data[8..], 19_675u64.to_le_bytes().as_slice());
+
+        let initiate_ix = initiate_agreement_transfer()
+            .payment_terms(payment_terms_key)
+            .payer(old_payer)
+            .new_payer(new_payer)
+            .payee_authority(payee.authority)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        assert_eq!(&initiate_ix.data[..8], &[208, 126, 130, 181, 213, 186, 250, 200]);
+        assert_eq!(&initiate_ix.data[8..], new_payer.as_ref());
+    }
+
+    #[test]
+    fn test_set_keeper_policy_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let keeper = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instruction = set_keeper_policy()
+            .authority(authority)
+            .open_execution(false)
+            .authorized_keepers(vec![keeper])
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+
+        assert_eq!(&instruction.data[..8], &[113, 131, 98, 244, 25, 146, 140, 188]);
+        let args = SetKeeperPolicyArgs::try_from_slice(&instruction.data[8..]).unwrap();
+        assert!(!args.open_execution);
+        assert_eq!(args.authorized_keepers, vec![keeper]);
+        assert_eq!(
+            instruction.accounts[0].pubkey,
+            pda::payee_address_with_program_id(&authority, &program_id)
+        );
+        assert!(instruction.accounts[1].is_signer);
+
+        // A permissioned payee rejects other keepers before anything is sent
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: false,
+            authorized_keepers: vec![keeper],
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&authority, &program_id),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let execute = |executor: Pubkey| {
+            execute_payment()
+                .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+                .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+                .keeper(executor)
+                .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+                .program_id(program_id)
+                .next_payment_ts(1_700_000_000)
+                .build_instruction(&payee, &terms, &Pubkey::default())
+        };
+        assert!(execute(keeper).is_ok());
+        assert!(execute(Pubkey::from(Keypair::new().pubkey().to_bytes())).is_err());
+    }
+
+    #[test]
+    fn test_payee_authority_transfer_builders() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let original_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let current_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let new_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let usdc_mint = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee_pda = pda::payee_address_with_program_id(&original_authority, &program_id);
+
+        // A payee that was transferred once is still addressed by its original authority
+        let transfer_ix = transfer_payee_authority()
+            .authority(current_authority)
+            .original_authority(original_authority)
+            .new_authority(new_authority)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        assert_eq!(&transfer_ix.data[..8], &[158, 105, 60, 223, 133, 232, 77, 152]);
+        let args = TransferPayeeAuthorityArgs::try_from_slice(&transfer_ix.data[8..]).unwrap();
+        assert_eq!(args.new_authority, new_authority);
+        assert_eq!(transfer_ix.accounts[0].pubkey, payee_pda);
+        assert!(transfer_ix.accounts[1].is_signer);
+
+        let accept_ix = accept_payee_authority()
+            .new_authority(new_authority)
+            .original_authority(original_authority)
+            .usdc_mint(usdc_mint)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        assert_eq!(&accept_ix.data[..8], &[226, 96, 208, 121, 93, 75, 227, 251]);
+        assert_eq!(accept_ix.accounts[0].pubkey, payee_pda);
+        assert!(accept_ix.accounts[1].is_signer);
+        assert_eq!(
+            accept_ix.accounts[2].pubkey,
+            get_associated_token_address_with_program(&new_authority, &usdc_mint, TokenProgram::Token)
+                .unwrap()
+        );
+        assert!(accept_payee_authority().new_authority(new_authority).build_instruction().is_err());
+
+        let cancel_ix = cancel_payee_authority_transfer()
+            .authority(original_authority)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        assert_eq!(&cancel_ix.data[..8], &[4, 104, 244, 236, 138, 214, 187, 45]);
+        assert_eq!(cancel_ix.accounts[0].pubkey, payee_pda, "Defaults to the signing authority");
+
+        let policy_ix = set_keeper_policy()
+            .authority(current_authority)
+            .original_authority(original_authority)
+            .open_execution(true)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        assert_eq!(policy_ix.accounts[0].pubkey, payee_pda);
+    }
+
+    #[test]
+    fn test_start_agreement_builder_max_periods() {
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let payment_terms_data = PaymentTerms {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let build = |max_periods: u16| {
+            start_agreement()
+                .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+                .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+                .max_periods(max_periods)
+                .build_instructions(&payee, &payment_terms_data, &Pubkey::default())
+        };
+
+        let instructions = build(12).unwrap();
+        let args = StartAgreementArgs::try_from_slice(&instructions[1].data[8..]).unwrap();
+        assert_eq!(args.max_periods, Some(12));
+
+        assert!(build(1)
+            .unwrap_err()
+            .to_string()
+            .contains("Max periods must be at least 2"));
+        assert_eq!(args.external_ref_hash, None);
+
+        let external_ref_hash = crate::utils::hash_external_ref("cus_1234", b"salt");
+        let instructions = start_agreement()
+            .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .external_ref_hash(external_ref_hash)
+            .build_instructions(&payee, &payment_terms_data, &Pubkey::default())
+            .unwrap();
+        let args = StartAgreementArgs::try_from_slice(&instructions[1].data[8..]).unwrap();
+        assert_eq!(args.external_ref_hash, Some(external_ref_hash));
+    }
 
-/// Create an unpause program transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn unpause() -> UnpauseBuilder {
-    UnpauseBuilder::new()
-}
+    #[test]
+    fn test_deactivate_payment_terms_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
 
-/// Create an update config transaction builder
-#[must_use]
-#[cfg(feature = "platform-admin")]
-pub fn update_config() -> UpdateConfigBuilder {
-    UpdateConfigBuilder::new()
-}
+        let instruction = deactivate_payment_terms()
+            .authority(authority)
+            .payment_terms(payment_terms)
+            .sunset_ts(1_702_592_000)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
 
+        assert_eq!(&instruction.data[..8], &[33, 182, 63, 230, 115, 204, 125, 143]);
+        let args = DeactivatePaymentTermsArgs::try_from_slice(&instruction.data[8..]).unwrap();
+        assert_eq!(args.sunset_ts, 1_702_592_000);
+        assert_eq!(instruction.accounts[1].pubkey, payment_terms);
+        assert!(instruction.accounts[1].is_writable);
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            pda::payee_address_with_program_id(&authority, &program_id)
+        );
+        assert!(instruction.accounts[3].is_signer);
 
-#[cfg(test)]
-mod tests {
-    #[cfg(feature = "platform-admin")]
-    use super::*;
-    #[cfg(feature = "platform-admin")]
-    use anchor_client::solana_sdk::signature::{Keypair, Signer};
-    #[cfg(feature = "platform-admin")]
-    use std::str::FromStr;
+        assert!(deactivate_payment_terms()
+            .authority(authority)
+            .payment_terms(payment_terms)
+            .build_instruction()
+            .is_err());
+    }
 
-    #[cfg(feature = "platform-admin")]
-    fn create_test_payee() -> Payee {
-        Payee {
-            authority: Pubkey::from(Keypair::new().pubkey().to_bytes()),
-            usdc_mint: Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap(),
+    #[test]
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn test_resume_agreement_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
             treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
-            platform_fee_bps: 50,
-            volume_tier: 0, // Standard tier
+            volume_tier: crate::program_types::VolumeTier::Standard,
             monthly_volume_usdc: 0,
             last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
             bump: 255,
-        }
+            version: 1,
+        };
+        let payment_terms_data = PaymentTerms {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instructions = resume_agreement()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .renewal_bucket(19_675)
+            .program_id(program_id)
+            .build_instructions(&payee, &payment_terms_data, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 2, "Re-approves the delegate before resuming");
+
+        let resume_ix = &instructions[1];
+        assert_eq!(&resume_ix.data[..8], &[158, 1, 240, 85, 78, 170, 184, 23]);
+        let args = ResumeAgreementArgs::try_from_slice(&resume_ix.data[8..]).unwrap();
+        assert_eq!(args.allowance_periods, 3);
+        assert_eq!(args.renewal_bucket, 19_675);
+        assert_eq!(
+            resume_ix.accounts[1].pubkey,
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id)
+        );
+        assert!(resume_ix.accounts[4].is_signer);
+
+        assert!(resume_agreement()
+            .payer(payer)
+            .build_instructions(&payee, &payment_terms_data, &Pubkey::default())
+            .is_err());
     }
 
-    #[cfg(feature = "platform-admin")]
-    fn create_test_payment_terms() -> PaymentTerms {
-        let payee = Pubkey::from(Keypair::new().pubkey().to_bytes());
-        let mut terms_id = [0u8; 32];
-        terms_id[..12].copy_from_slice(b"premium_payment_terms");
-        let mut name = [0u8; 32];
-        name[..12].copy_from_slice(b"Premium PaymentTerms");
+    #[test]
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn test_repair_delegate_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let payment_terms_data = PaymentTerms {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let warning = DelegateMismatchWarning {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payment_terms: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            payer,
+            expected_delegate: delegate_pda,
+            actual_delegate: Some(Pubkey::from(Keypair::new().pubkey().to_bytes())),
+        };
 
-        PaymentTerms {
-            payee,
-            terms_id,
-            amount_usdc: 5_000_000,  // 5 USDC
-            period_secs: 2_592_000, // 30 days
-            grace_secs: 432_000,    // 5 days
-            name,
-            active: true,
-        }
+        let instructions = repair_delegate()
+            .from_warning(&warning)
+            .program_id(program_id)
+            .build_instructions(&payee, &payment_terms_data)
+            .unwrap();
+        assert_eq!(instructions.len(), 2, "Revokes the foreign delegate first");
+        assert_eq!(
+            instructions[0].data,
+            spl_token::instruction::TokenInstruction::Revoke.pack()
+        );
+        let approve = spl_token::instruction::TokenInstruction::unpack(&instructions[1].data);
+        assert!(matches!(
+            approve,
+            Ok(spl_token::instruction::TokenInstruction::ApproveChecked {
+                amount: 30_000_000,
+                decimals: 6
+            })
+        ));
+        assert_eq!(instructions[1].accounts[2].pubkey, delegate_pda);
+        assert!(instructions
+            .iter()
+            .all(|ix| ix.accounts.iter().filter(|meta| meta.is_signer).all(|meta| meta.pubkey == payer)));
+
+        // The program delegate with a depleted allowance only needs the approval
+        let instructions = repair_delegate()
+            .payment_terms(warning.payment_terms)
+            .payer(payer)
+            .current_delegate(delegate_pda)
+            .allowance_periods(1)
+            .program_id(program_id)
+            .build_instructions(&payee, &payment_terms_data)
+            .unwrap();
+        assert_eq!(instructions.len(), 1);
+
+        assert!(repair_delegate()
+            .payer(payer)
+            .build_instructions(&payee, &payment_terms_data)
+            .is_err());
     }
 
     #[test]
-    #[cfg(feature = "platform-admin")]
-    fn test_start_payment_agreement_builder() {
-        let payee = create_test_payee();
-        let payment_terms_data = create_test_payment_terms();
-        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn test_builders_index_renewal_queues() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&authority, &program_id),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
         let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
-        let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
 
+        // start_agreement indexes the agreement under the requested bucket
         let instructions = start_agreement()
-            .payment_terms(payment_terms_key)
+            .payment_terms(payment_terms)
             .payer(payer)
-            .allowance_periods(3) // 3x payment_terms price
-            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .renewal_bucket(19_705)
+            .program_id(program_id)
+            .build_instructions(&payee, &terms, &Pubkey::default())
             .unwrap();
+        let start_ix = &instructions[1];
+        let args = StartAgreementArgs::try_from_slice(&start_ix.data[8..]).unwrap();
+        assert_eq!(args.renewal_bucket, 19_705);
+        assert_eq!(args.max_periods, None);
+        // Each agreement is indexed in the shard selected by its address
+        let agreement = pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id);
+        let shard = pda::renewal_queue_shard(&agreement);
+        assert_eq!(
+            start_ix.accounts[11].pubkey,
+            pda::renewal_queue_address_with_program_id(19_705, shard, &program_id)
+        );
+        assert!(start_ix.accounts[11].is_writable);
 
-        assert_eq!(instructions.len(), 2, "Should have 2 instructions: approve_checked and start_payment_agreement");
+        // execute_payment moves it from this payment's bucket to the next one
+        let instruction = execute_payment()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_702_500_000)
+            .program_id(program_id)
+            .build_instruction(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        let args = crate::program_types::ExecutePaymentArgs::try_from_slice(&instruction.data[8..])
+            .unwrap();
+        assert_eq!(args.next_renewal_bucket, 19_734);
+        assert_eq!(
+            instruction.accounts[13].pubkey,
+            pda::renewal_queue_address_with_program_id(19_704, shard, &program_id)
+        );
+        assert_eq!(
+            instruction.accounts[14].pubkey,
+            pda::renewal_queue_address_with_program_id(19_734, shard, &program_id)
+        );
+        assert_eq!(instruction.accounts[15].pubkey, system_program::ID);
 
-        // ========== Validate approve_checked instruction ==========
-        let approve_ix = &instructions[0];
-        assert_eq!(approve_ix.program_id, spl_token::id(), "First instruction must be SPL Token program");
+        // The timestamp of the payment being executed is required
+        let result = execute_payment()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .program_id(program_id)
+            .build_instruction(&payee, &terms, &Pubkey::default());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Next payment timestamp not set"));
+    }
 
-        // Validate allowance calculation (3 periods × payment_terms price)
-        let expected_allowance = payment_terms_data.amount_usdc.checked_mul(3).expect("Allowance overflow");
+    #[test]
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn test_execute_payment_keeper_fee_in_sol() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let usdc_mint = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint,
+            treasury_ata: get_associated_token_address_with_program(
+                &authority,
+                &usdc_mint,
+                TokenProgram::Token,
+            )
+            .unwrap(),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&authority, &program_id),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let keeper = Pubkey::from(Keypair::new().pubkey().to_bytes());
 
-        // Decode approve_checked data to validate amount
-        // approve_checked format: [discriminator(1 byte), amount(8 bytes), decimals(1 byte)]
-        assert!(approve_ix.data.len() >= 10, "approve_checked data should be at least 10 bytes");
+        // A keeper paid in SOL needs no USDC account of its own
+        let builder = execute_payment()
+            .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .keeper(keeper)
+            .next_payment_ts(1_702_500_000)
+            .ensure_atas(true)
+            .program_id(program_id);
+        let instructions = builder
+            .clone()
+            .keeper_fee_in_sol(true)
+            .build_instructions(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 2, "Only the payee treasury ATA is ensured");
 
-        // The first byte should be the ApproveChecked discriminator (13)
-        assert_eq!(approve_ix.data[0], 13, "approve_checked instruction discriminator should be 13");
+        let instruction = &instructions[1];
+        let args = crate::program_types::ExecutePaymentArgs::try_from_slice(&instruction.data[8..])
+            .unwrap();
+        assert!(args.keeper_fee_in_sol);
+        assert_eq!(
+            instruction.accounts[8].pubkey,
+            get_associated_token_address_with_program(&keeper, &usdc_mint, TokenProgram::Token)
+                .unwrap()
+        );
+        assert_eq!(
+            instruction.accounts[9].pubkey,
+            pda::keeper_fee_vault_address_with_program_id(&program_id)
+        );
+        assert!(instruction.accounts[9].is_writable);
+        assert!(!instruction.accounts[9].is_signer);
 
-        // Extract amount (bytes 1-8, little-endian u64)
-        let amount_bytes: [u8; 8] = approve_ix.data[1..9].try_into().unwrap();
-        let actual_amount = u64::from_le_bytes(amount_bytes);
-        assert_eq!(actual_amount, expected_allowance, "Allowance amount should be 3x payment_terms price");
+        // Keepers paid in USDC leave the vault out
+        let instruction = builder
+            .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .build_instruction(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        let args = crate::program_types::ExecutePaymentArgs::try_from_slice(&instruction.data[8..])
+            .unwrap();
+        assert!(!args.keeper_fee_in_sol);
+        assert_eq!(instruction.accounts[9].pubkey, program_id);
+        assert!(!instruction.accounts[9].is_writable);
+    }
 
-        // Decimals byte (byte 9) should be 6 for USDC
-        assert_eq!(approve_ix.data[9], 6, "USDC decimals should be 6");
+    #[test]
+    fn test_execute_payment_gate_ata() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let gate_mint = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&authority, &program_id),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let builder = execute_payment()
+            .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .payer(payer_key)
+            .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_702_500_000)
+            .program_id(program_id);
 
-        // Validate approve_checked accounts structure
-        assert_eq!(approve_ix.accounts.len(), 4, "approve_checked requires 4 accounts");
+        // Without a gate the optional account is marked absent
+        let instruction = builder
+            .clone()
+            .build_instruction(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instruction.accounts.len(), 17);
+        assert_eq!(instruction.accounts[16].pubkey, program_id);
+
+        // Gated terms pass the payer's gate ATA, read-only
+        let gated = PaymentTerms {
+            gate_mint: Some(gate_mint),
+            gate_discount_bps: 1_000,
+            ..terms
+        };
+        let instruction = builder
+            .build_instruction(&payee, &gated, &Pubkey::default())
+            .unwrap();
+        assert_eq!(
+            instruction.accounts[16].pubkey,
+            get_associated_token_address_with_program(&payer_key, &gate_mint, TokenProgram::Token)
+                .unwrap()
+        );
+        assert!(!instruction.accounts[16].is_writable);
+    }
 
-        // Account 0: Source account (payer's token account, writable)
-        assert!(approve_ix.accounts[0].is_writable, "Source account must be writable");
+    #[test]
+    fn test_execute_payment_memo() {
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let builder = execute_payment()
+            .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_702_500_000);
 
-        // Account 1: Mint (USDC mint, readonly)
-        assert!(!approve_ix.accounts[1].is_writable, "Mint must be readonly");
-        assert_eq!(approve_ix.accounts[1].pubkey, payee.usdc_mint, "Second account must be USDC mint");
+        let instructions = builder
+            .clone()
+            .memo("Invoice INV-2024-0042")
+            .build_instructions(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[1].program_id, spl_memo::ID);
+        assert_eq!(instructions[1].data, b"Invoice INV-2024-0042");
 
-        // Account 2: Delegate (program delegate PDA, readonly)
-        assert!(!approve_ix.accounts[2].is_writable, "Delegate must be readonly");
+        let result = builder
+            .memo("say \"hi\"")
+            .build_instructions(&payee, &terms, &Pubkey::default());
+        assert!(result.unwrap_err().to_string().contains("unsupported character"));
+    }
 
-        // Account 3: Owner (payer, signer)
-        assert!(approve_ix.accounts[3].is_signer, "Owner must be signer");
+    #[test]
+    #[cfg(feature = "test-clock")]
+    fn test_advance_test_clock() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let platform_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
 
-        // ========== Validate start_payment_agreement instruction ==========
-        let start_sub_ix = &instructions[1];
-        let expected_program_id = program_id();
-        assert_eq!(start_sub_ix.program_id, expected_program_id, "Second instruction must be Tally program");
+        let instruction = advance_test_clock()
+            .platform_authority(platform_authority)
+            .secs(2_592_000)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
 
-        // Validate start_payment_agreement discriminator
-        // start_payment_agreement discriminator: [167, 59, 160, 222, 194, 175, 3, 13]
-        assert!(start_sub_ix.data.len() >= 8, "Instruction data should include discriminator");
-        assert_eq!(&start_sub_ix.data[0..8], &[167, 59, 160, 222, 194, 175, 3, 13],
-            "start_payment_agreement discriminator mismatch");
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            pda::test_clock_address_with_program_id(&program_id)
+        );
+        assert!(instruction.accounts[2].is_signer);
+        assert_eq!(
+            instruction.data[..8],
+            anchor_lang::solana_program::hash::hash(b"global:advance_test_clock").to_bytes()[..8]
+        );
+        let args =
+            crate::program_types::AdvanceTestClockArgs::try_from_slice(&instruction.data[8..])
+                .unwrap();
+        assert_eq!(args.secs, 2_592_000);
 
-        // Validate account count for start_payment_agreement
-        assert_eq!(start_sub_ix.accounts.len(), 12, "start_payment_agreement requires 12 accounts");
+        // The clock only moves forward
+        assert!(advance_test_clock()
+            .platform_authority(platform_authority)
+            .secs(0)
+            .build_instruction()
+            .is_err());
+    }
 
-        // Validate key accounts are present (specific indices)
-        // Account indices based on actual start_payment_agreement builder:
-        // 0: config, 1: payment agreement, 2: payment_terms, 3: payee, 4: payer,
-        // 5: payer_ata, 6: payee_treasury_ata, 7: platform_treasury_ata,
-        // 8: usdc_mint, 9: delegate, 10: token_program, 11: system_program
+    #[test]
+    #[allow(clippy::similar_names, clippy::too_many_lines)] // payer and payee are distinct payment domain terms
+    fn test_builders_ensure_atas() {
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let usdc_mint = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint,
+            treasury_ata: get_associated_token_address_with_program(
+                &authority,
+                &usdc_mint,
+                TokenProgram::Token,
+            )
+            .unwrap(),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let terms = PaymentTerms {
+            payee: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            terms_id: [0u8; 32],
+            amount_usdc: 10_000_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 1,
+            accrual_rate_per_sec: None,
+        };
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let ata_program = spl_associated_token_account::id();
 
-        assert_eq!(start_sub_ix.accounts[2].pubkey, payment_terms_key, "PaymentTerms account mismatch");
-        assert_eq!(start_sub_ix.accounts[8].pubkey, payee.usdc_mint, "USDC mint account mismatch");
-        assert_eq!(start_sub_ix.accounts[7].pubkey, platform_treasury_ata, "Platform treasury ATA mismatch");
+        // start_agreement creates the payer and payee treasury ATAs before approving
+        let instructions = start_agreement()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .ensure_atas(true)
+            .build_instructions(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 4);
+        assert!(instructions[..2].iter().all(|ix| ix.program_id == ata_program));
+        // Idempotent variant of the associated token account instruction
+        assert!(instructions[..2].iter().all(|ix| ix.data == vec![1]));
+        assert_eq!(instructions[0].accounts[0].pubkey, payer);
+        assert_eq!(instructions[0].accounts[2].pubkey, payer);
+        assert_eq!(instructions[1].accounts[1].pubkey, payee.treasury_ata);
+
+        // Escrowed terms also need the program delegate's escrow ATA
+        let escrow_terms = PaymentTerms {
+            escrow_window_secs: Some(259_200),
+            ..terms
+        };
+        let delegate = pda::delegate_address_with_program_id(&program_id());
+        let instructions = start_agreement()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .ensure_atas(true)
+            .build_instructions(&payee, &escrow_terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 5);
+        assert_eq!(instructions[2].accounts[2].pubkey, delegate);
+        assert_eq!(instructions[4].accounts[8].pubkey, instructions[2].accounts[1].pubkey);
 
-        // Payer must be signer and writable (pays for payment agreement account creation)
-        assert!(start_sub_ix.accounts[4].is_signer, "Payer must be signer");
-        assert!(start_sub_ix.accounts[4].is_writable, "Payer must be writable");
+        // Disabled by default
+        let instructions = start_agreement()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .build_instructions(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 2);
 
-        // PaymentAgreement PDA must be writable (created in instruction)
-        assert!(start_sub_ix.accounts[1].is_writable, "PaymentAgreement PDA must be writable");
+        // execute_payment creates the keeper's ATA, funded by the keeper
+        let keeper = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let keeper_ata =
+            get_associated_token_address_with_program(&keeper, &usdc_mint, TokenProgram::Token)
+                .unwrap();
+        let instructions = execute_payment()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .keeper(keeper)
+            .keeper_ata(keeper_ata)
+            .next_payment_ts(1_700_000_000)
+            .ensure_atas(true)
+            .build_instructions(&payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0].accounts[0].pubkey, keeper);
+        assert_eq!(instructions[0].accounts[1].pubkey, keeper_ata);
+        assert_eq!(instructions[2].accounts[8].pubkey, keeper_ata);
+
+        // Token accounts that are not ATAs are assumed to exist
+        let mut custom_payee = payee;
+        custom_payee.treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let instructions = execute_payment()
+            .payment_terms(payment_terms)
+            .payer(payer)
+            .keeper(keeper)
+            .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_700_000_000)
+            .ensure_atas(true)
+            .build_instructions(&custom_payee, &terms, &Pubkey::default())
+            .unwrap();
+        assert_eq!(instructions.len(), 1);
     }
 
     #[test]
-    #[cfg(feature = "platform-admin")]
-    fn test_start_payment_agreement_allowance_edge_cases() {
-        let payee = create_test_payee();
-        let payment_terms_data = create_test_payment_terms();
+    fn test_refund_payment_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
         let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
-        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
-        let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
 
-        // Test with 1 period (minimum)
-        let instructions_min = start_agreement()
+        let instruction = refund_payment()
             .payment_terms(payment_terms_key)
-            .payer(payer)
-            .allowance_periods(1)
-            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .payer(payer_key)
+            .amount(2_500_000)
+            .program_id(program_id)
+            .build_instruction(&payee)
             .unwrap();
 
-        let approve_ix_min = &instructions_min[0];
-        let amount_bytes_min: [u8; 8] = approve_ix_min.data[1..9].try_into().unwrap();
-        let amount_min = u64::from_le_bytes(amount_bytes_min);
-        assert_eq!(amount_min, payment_terms_data.amount_usdc, "1 period should equal payment_terms price");
+        assert_eq!(&instruction.data[..8], &[121, 205, 211, 181, 202, 147, 45, 248]);
+        assert_eq!(&instruction.data[8..], &2_500_000u64.to_le_bytes());
+        assert_eq!(instruction.accounts.len(), 8);
+        assert_eq!(instruction.accounts[3].pubkey, payee.authority);
+        assert!(instruction.accounts[3].is_signer);
+        assert_eq!(instruction.accounts[4].pubkey, payee.treasury_ata);
+        assert!(instruction.accounts[4].is_writable);
 
-        // Test with 10 periods
-        let instructions_max = start_agreement()
+        // Amount is required
+        assert!(refund_payment()
             .payment_terms(payment_terms_key)
-            .payer(payer)
-            .allowance_periods(10)
-            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .payer(payer_key)
+            .build_instruction(&payee)
+            .is_err());
+    }
+
+    #[test]
+    fn test_claim_deposit_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+
+        let instruction = claim_deposit()
+            .payment_terms(payment_terms_key)
+            .payer(payer_key)
+            .amount(10_000_000)
+            .program_id(program_id)
+            .build_instruction(&payee)
             .unwrap();
 
-        let approve_ix_max = &instructions_max[0];
-        let amount_bytes_max: [u8; 8] = approve_ix_max.data[1..9].try_into().unwrap();
-        let amount_max = u64::from_le_bytes(amount_bytes_max);
-        assert_eq!(amount_max, payment_terms_data.amount_usdc * 10, "10 periods should be 10x payment_terms price");
+        let delegate_pda = pda::delegate_address_with_program_id(&program_id);
+        let escrow_ata = get_associated_token_address_with_program(
+            &delegate_pda,
+            &payee.usdc_mint,
+            TokenProgram::Token,
+        )
+        .unwrap();
+
+        assert_eq!(&instruction.data[..8], &[201, 106, 1, 224, 122, 144, 210, 155]);
+        assert_eq!(&instruction.data[8..], &10_000_000u64.to_le_bytes());
+        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(instruction.accounts[3].pubkey, payee.authority);
+        assert!(instruction.accounts[3].is_signer);
+        assert_eq!(instruction.accounts[4].pubkey, escrow_ata);
+        assert!(instruction.accounts[4].is_writable);
+        assert_eq!(instruction.accounts[5].pubkey, payee.treasury_ata);
+        assert_eq!(instruction.accounts[7].pubkey, delegate_pda);
+
+        // Amount is required
+        assert!(claim_deposit()
+            .payment_terms(payment_terms_key)
+            .payer(payer_key)
+            .build_instruction(&payee)
+            .is_err());
     }
 
     #[test]
-    #[cfg(feature = "platform-admin")]
-    fn test_start_payment_agreement_with_token2022() {
-        let mut payee = create_test_payee();
-        payee.usdc_mint = Pubkey::from(Keypair::new().pubkey().to_bytes()); // Custom Token2022 mint
-
-        let payment_terms_data = create_test_payment_terms();
+    fn test_escrow_builders() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
         let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
-        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let keeper = Pubkey::from(Keypair::new().pubkey().to_bytes());
         let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let escrow_ata = get_associated_token_address_with_program(
+            &pda::delegate_address_with_program_id(&program_id),
+            &payee.usdc_mint,
+            TokenProgram::Token,
+        )
+        .unwrap();
 
-        let instructions = start_agreement()
+        // The payee authority releases the escrow to both treasuries
+        let instruction = confirm_activation()
             .payment_terms(payment_terms_key)
-            .payer(payer)
-            .allowance_periods(3)
-            .token_program(TokenProgram::Token2022)
-            .build_instructions(&payee, &payment_terms_data, &platform_treasury_ata)
+            .payer(payer_key)
+            .program_id(program_id)
+            .build_instruction(&payee, &platform_treasury_ata)
+            .unwrap();
+        assert_eq!(&instruction.data, &[226, 158, 162, 48, 18, 108, 131, 224]);
+        assert_eq!(instruction.accounts.len(), 11);
+        assert_eq!(instruction.accounts[4].pubkey, payee.authority);
+        assert!(instruction.accounts[4].is_signer);
+        assert_eq!(instruction.accounts[5].pubkey, escrow_ata);
+        assert_eq!(instruction.accounts[7].pubkey, platform_treasury_ata);
+
+        // Anyone may refund a lapsed escrow; the payer signs by default
+        let instruction = refund_escrow()
+            .payment_terms(payment_terms_key)
+            .payer(payer_key)
+            .program_id(program_id)
+            .build_instruction(&payee)
             .unwrap();
+        assert_eq!(&instruction.data, &[107, 186, 89, 99, 26, 194, 23, 204]);
+        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(instruction.accounts[3].pubkey, payer_key);
+        assert!(instruction.accounts[3].is_signer);
+        assert_eq!(instruction.accounts[4].pubkey, escrow_ata);
 
-        // Verify Token2022 program is used
-        assert_eq!(instructions[0].program_id, spl_token_2022::id(), "Should use Token2022 program");
-        assert_eq!(instructions[1].accounts[10].pubkey, spl_token_2022::id(),
-            "start_payment_agreement should reference Token2022");
+        let instruction = refund_escrow()
+            .payment_terms(payment_terms_key)
+            .payer(payer_key)
+            .caller(keeper)
+            .program_id(program_id)
+            .build_instruction(&payee)
+            .unwrap();
+        assert_eq!(instruction.accounts[3].pubkey, keeper);
+
+        // Payer is required
+        assert!(confirm_activation()
+            .payment_terms(payment_terms_key)
+            .build_instruction(&payee, &platform_treasury_ata)
+            .is_err());
     }
 
     #[test]
-    #[cfg(feature = "platform-admin")]
-    fn test_cancel_payment_agreement_builder() {
-        let payee = create_test_payee();
+    fn test_resume_after_unfreeze_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
         let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
-        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let keeper = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payee = Payee {
+            authority,
+            usdc_mint: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            treasury_ata: Pubkey::from(Keypair::new().pubkey().to_bytes()),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        // Suspended far in the future so the default bucket is deterministic
+        let next_payment_ts = 4_000_000_000;
+        let agreement = PaymentAgreement {
+            payment_terms: payment_terms_key,
+            payer: payer_key,
+            next_payment_ts,
+            active: false,
+            payment_count: 1,
+            created_ts: 0,
+            last_amount: 10_000_000,
+            last_payment_ts: 0,
+            last_pull_period_index: 0,
+            cancel_at_period_end: false,
+            pending_payer: None,
+            refunded_amount: 0,
+            max_periods: None,
+            periods_paid: 1,
+            external_ref_hash: None,
+            paused_at_ts: None,
+            credit_amount: 0,
+            escrow: None,
+            period_index: 0,
+            suspension: Some(crate::program_types::SuspensionReason::FrozenAccount),
+            bump: 255,
+            deposit_held: 0,
+            version: 1,
+            last_sweep_ts: 0,
+        };
+        let payer_ata = get_associated_token_address_with_program(
+            &payer_key,
+            &payee.usdc_mint,
+            TokenProgram::Token,
+        )
+        .unwrap();
 
-        let instructions = pause_agreement()
+        // The payer signs by default and the queue follows the next payment
+        let instruction = resume_after_unfreeze()
             .payment_terms(payment_terms_key)
-            .payer(payer)
-            .build_instructions(&payee)
+            .payer(payer_key)
+            .program_id(program_id)
+            .build_instruction(&payee, &agreement)
             .unwrap();
+        assert_eq!(&instruction.data[..8], &[47, 253, 126, 127, 182, 81, 189, 59]);
+        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(instruction.accounts[4].pubkey, payer_key);
+        assert!(instruction.accounts[4].is_signer);
+        assert_eq!(instruction.accounts[5].pubkey, payer_ata);
+        assert!(!instruction.accounts[5].is_writable);
+        let bucket = pda::renewal_bucket(next_payment_ts).unwrap();
+        assert_eq!(
+            instruction.accounts[6].pubkey,
+            pda::agreement_renewal_queue_address_with_program_id(
+                bucket,
+                &instruction.accounts[1].pubkey,
+                &program_id
+            )
+        );
+        assert_eq!(&instruction.data[8..], &bucket.to_le_bytes());
 
-        assert_eq!(instructions.len(), 2);
-
-        // First instruction should be revoke
-        assert_eq!(instructions[0].program_id, spl_token::id());
+        // Keepers may submit it on the payer's behalf
+        let instruction = resume_after_unfreeze()
+            .payment_terms(payment_terms_key)
+            .payer(payer_key)
+            .caller(keeper)
+            .program_id(program_id)
+            .build_instruction(&payee, &agreement)
+            .unwrap();
+        assert_eq!(instruction.accounts[4].pubkey, keeper);
 
-        // Second instruction should be cancel_payment_agreement
-        let program_id = program_id();
-        assert_eq!(instructions[1].program_id, program_id);
+        // Payer is required
+        assert!(resume_after_unfreeze()
+            .payment_terms(payment_terms_key)
+            .build_instruction(&payee, &agreement)
+            .is_err());
     }
 
     #[test]
@@ -1794,6 +6416,7 @@ mod tests {
             period_secs: 2_592_000,
             grace_secs: 432_000,
             name: "Premium PaymentTerms".to_string(),
+            deposit_usdc: None,
         };
 
         let instruction = create_payment_terms()
@@ -1847,6 +6470,7 @@ mod tests {
             monthly_volume_usdc: 0,
             last_volume_update_ts: 0,
             bump: 255,
+            version: 1,
         };
 
         let payee_token2022 = Payee {
@@ -1858,6 +6482,7 @@ mod tests {
             monthly_volume_usdc: 0,
             last_volume_update_ts: 0,
             bump: 255,
+            version: 1,
         };
 
         let payment_terms_data = create_test_payment_terms();
@@ -2108,12 +6733,13 @@ mod tests {
             .payer(payer)
             .keeper(keeper)
             .keeper_ata(keeper_ata)
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee, &payment_terms_data, &platform_treasury_ata)
             .unwrap();
 
         let program_id = program_id();
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 12);
+        assert_eq!(instruction.accounts.len(), 16);
 
         // Verify instruction discriminator matches program
         assert_eq!(
@@ -2134,9 +6760,11 @@ mod tests {
         assert!(!instruction.accounts[0].is_writable); // config
         assert!(!instruction.accounts[2].is_writable); // payment_terms
         assert!(!instruction.accounts[3].is_writable); // payee
-        assert!(!instruction.accounts[9].is_writable); // usdc_mint
-        assert!(!instruction.accounts[10].is_writable); // program_delegate
-        assert!(!instruction.accounts[11].is_writable); // token_program
+        assert!(!instruction.accounts[9].is_writable); // keeper_fee_vault (absent)
+        assert!(!instruction.accounts[10].is_writable); // usdc_mint
+        assert!(!instruction.accounts[11].is_writable); // program_delegate
+        assert!(!instruction.accounts[12].is_writable); // token_program
+        assert!(!instruction.accounts[15].is_writable); // system_program
     }
 
     #[cfg(feature = "platform-admin")]
@@ -2147,6 +6775,8 @@ mod tests {
         assert!(instruction.accounts[6].is_writable); // platform_treasury_ata
         assert!(instruction.accounts[7].is_writable); // keeper
         assert!(instruction.accounts[8].is_writable); // keeper_usdc_ata
+        assert!(instruction.accounts[13].is_writable); // current_renewal_queue
+        assert!(instruction.accounts[14].is_writable); // next_renewal_queue
     }
 
     #[cfg(feature = "platform-admin")]
@@ -2160,9 +6790,13 @@ mod tests {
         assert!(!instruction.accounts[6].is_signer); // platform_treasury_ata
         assert!(instruction.accounts[7].is_signer); // keeper (only signer)
         assert!(!instruction.accounts[8].is_signer); // keeper_usdc_ata
-        assert!(!instruction.accounts[9].is_signer); // usdc_mint
-        assert!(!instruction.accounts[10].is_signer); // program_delegate
-        assert!(!instruction.accounts[11].is_signer); // token_program
+        assert!(!instruction.accounts[9].is_signer); // keeper_fee_vault
+        assert!(!instruction.accounts[10].is_signer); // usdc_mint
+        assert!(!instruction.accounts[11].is_signer); // program_delegate
+        assert!(!instruction.accounts[12].is_signer); // token_program
+        assert!(!instruction.accounts[13].is_signer); // current_renewal_queue
+        assert!(!instruction.accounts[14].is_signer); // next_renewal_queue
+        assert!(!instruction.accounts[15].is_signer); // system_program
     }
 
     #[test]
@@ -2177,6 +6811,7 @@ mod tests {
             .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee, &payment_terms_data, &platform_treasury_ata);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("PaymentTerms not set"));
@@ -2186,6 +6821,7 @@ mod tests {
             .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee, &payment_terms_data, &platform_treasury_ata);
         assert!(result.is_err());
         assert!(result
@@ -2198,6 +6834,7 @@ mod tests {
             .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .keeper_ata(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee, &payment_terms_data, &platform_treasury_ata);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Keeper not set"));
@@ -2207,6 +6844,7 @@ mod tests {
             .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
             .keeper(Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee, &payment_terms_data, &platform_treasury_ata);
         assert!(result.is_err());
         assert!(result
@@ -2228,6 +6866,7 @@ mod tests {
             monthly_volume_usdc: 0,
             last_volume_update_ts: 0,
             bump: 255,
+            version: 1,
         };
 
         let payee_token2022 = Payee {
@@ -2239,6 +6878,7 @@ mod tests {
             monthly_volume_usdc: 0,
             last_volume_update_ts: 0,
             bump: 255,
+            version: 1,
         };
 
         let payment_terms_data = create_test_payment_terms();
@@ -2255,6 +6895,7 @@ mod tests {
             .keeper(keeper)
             .keeper_ata(keeper_ata)
             .token_program(TokenProgram::Token2022)
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee_token2022, &payment_terms_data, &platform_treasury_ata)
             .unwrap();
 
@@ -2265,6 +6906,7 @@ mod tests {
             .keeper(keeper)
             .keeper_ata(keeper_ata)
             .token_program(TokenProgram::Token)
+            .next_payment_ts(1_700_000_000)
             .build_instruction(&payee_token, &payment_terms_data, &platform_treasury_ata)
             .unwrap();
 
@@ -2282,15 +6924,17 @@ mod tests {
         let payment_terms_key = Pubkey::from(Keypair::new().pubkey().to_bytes());
         let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
 
+        let payee = create_test_payee();
+
         let instruction = close_agreement()
             .payment_terms(payment_terms_key)
             .payer(payer)
-            .build_instruction()
+            .build_instruction(&payee)
             .unwrap();
 
         let program_id = program_id();
         assert_eq!(instruction.program_id, program_id);
-        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(instruction.accounts.len(), 9);
 
         // Verify instruction discriminator matches program
         assert_eq!(&instruction.data[..8], &[33, 214, 169, 135, 35, 127, 78, 7]);
@@ -2308,14 +6952,14 @@ mod tests {
         // Test missing payment_terms
         let result = close_agreement()
             .payer(Pubkey::from(Keypair::new().pubkey().to_bytes()))
-            .build_instruction();
+            .build_instruction(&create_test_payee());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("PaymentTerms not set"));
 
         // Test missing payer
         let result = close_agreement()
             .payment_terms(Pubkey::from(Keypair::new().pubkey().to_bytes()))
-            .build_instruction();
+            .build_instruction(&create_test_payee());
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -2334,7 +6978,7 @@ mod tests {
             .payment_terms(payment_terms_key)
             .payer(payer)
             .program_id(custom_program_id)
-            .build_instruction()
+            .build_instruction(&create_test_payee())
             .unwrap();
 
         assert_eq!(instruction.program_id, custom_program_id);
@@ -2349,7 +6993,7 @@ mod tests {
         let instruction = close_agreement()
             .payment_terms(payment_terms_key)
             .payer(payer)
-            .build_instruction()
+            .build_instruction(&create_test_payee())
             .unwrap();
 
         // Verify the computed payment agreement PDA is correct
@@ -2361,6 +7005,180 @@ mod tests {
         assert_eq!(instruction.accounts[1].pubkey, payer);
     }
 
+    #[test]
+    fn test_close_agreements_batch_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms: Vec<Pubkey> = (0..3)
+            .map(|_| Pubkey::from(Keypair::new().pubkey().to_bytes()))
+            .collect();
+
+        let instruction = close_agreements_batch()
+            .payer(payer)
+            .payment_terms(payment_terms.clone())
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+
+        assert_eq!(&instruction.data[..8], &[202, 174, 50, 59, 60, 11, 6, 16]);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(instruction.accounts[0].pubkey, payer);
+        assert!(instruction.accounts[0].is_signer);
+        for (account, terms) in instruction.accounts[1..].iter().zip(&payment_terms) {
+            assert_eq!(
+                account.pubkey,
+                pda::payment_agreement_address_with_program_id(terms, &payer, &program_id)
+            );
+            assert!(account.is_writable);
+            assert!(!account.is_signer);
+        }
+
+        // The program rejects empty and oversized batches
+        assert!(close_agreements_batch()
+            .payer(payer)
+            .build_instruction()
+            .is_err());
+        let oversized = vec![payment_terms[0]; crate::MAX_CLOSE_BATCH_AGREEMENTS + 1];
+        assert!(close_agreements_batch()
+            .payer(payer)
+            .payment_terms(oversized)
+            .build_instruction()
+            .is_err());
+    }
+
+    #[test]
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    fn test_sweep_accrued_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let usdc_mint = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let keeper = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payment_terms = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let mut payee = Payee {
+            authority,
+            usdc_mint,
+            treasury_ata: get_associated_token_address_with_program(
+                &authority,
+                &usdc_mint,
+                TokenProgram::Token,
+            )
+            .unwrap(),
+            volume_tier: crate::program_types::VolumeTier::Standard,
+            monthly_volume_usdc: 0,
+            last_volume_update_ts: 0,
+            frozen: false,
+            open_execution: true,
+            authorized_keepers: Vec::new(),
+            original_authority: authority,
+            pending_authority: None,
+            fee_holiday: None,
+            active_agreements: 0,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            bump: 255,
+            version: 1,
+        };
+        let mut terms = PaymentTerms {
+            payee: pda::payee_address_with_program_id(&authority, &program_id),
+            terms_id: [0u8; 32],
+            amount_usdc: 2_592_000,
+            period_secs: 2_592_000,
+            gate_mint: None,
+            gate_discount_bps: 0,
+            pending_update: None,
+            max_subscribers: None,
+            active_agreements: 0,
+            waitlist_len: 0,
+            sunset_ts: None,
+            escrow_window_secs: None,
+            lifetime_revenue_usdc: 0,
+            lifetime_renewals: 0,
+            deposit_usdc: None,
+            version: 2,
+            accrual_rate_per_sec: Some(1),
+        };
+        let platform_treasury_ata = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let builder = || {
+            sweep_accrued()
+                .payment_terms(payment_terms)
+                .payer(payer)
+                .keeper(keeper)
+                .program_id(program_id)
+        };
+
+        let instruction = builder()
+            .build_instruction(&payee, &terms, &platform_treasury_ata)
+            .unwrap();
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(&instruction.data[..8], &[57, 220, 131, 162, 187, 194, 8, 196]);
+        assert_eq!(instruction.accounts.len(), 12);
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            pda::payment_agreement_address_with_program_id(&payment_terms, &payer, &program_id)
+        );
+        assert_eq!(instruction.accounts[2].pubkey, payment_terms);
+        assert!(instruction.accounts[2].is_writable);
+        assert_eq!(instruction.accounts[6].pubkey, platform_treasury_ata);
+        assert_eq!(instruction.accounts[7].pubkey, keeper);
+        assert!(instruction.accounts[7].is_signer);
+        assert_eq!(
+            instruction.accounts[8].pubkey,
+            get_associated_token_address_with_program(&keeper, &usdc_mint, TokenProgram::Token)
+                .unwrap()
+        );
+
+        // Only authorized keepers may sweep for a payee with a keeper list
+        payee.open_execution = false;
+        payee.authorized_keepers = vec![Pubkey::from(Keypair::new().pubkey().to_bytes())];
+        assert!(builder()
+            .build_instruction(&payee, &terms, &platform_treasury_ata)
+            .is_err());
+        payee.authorized_keepers.push(keeper);
+        assert!(builder()
+            .build_instruction(&payee, &terms, &platform_treasury_ata)
+            .is_ok());
+
+        assert!(sweep_accrued()
+            .payment_terms(payment_terms)
+            .keeper(keeper)
+            .build_instruction(&payee, &terms, &platform_treasury_ata)
+            .is_err());
+
+        // Renewing terms go through execute_payment
+        terms.accrual_rate_per_sec = None;
+        assert!(builder()
+            .build_instruction(&payee, &terms, &platform_treasury_ata)
+            .is_err());
+    }
+
+    #[test]
+    fn test_migrate_account_builder() {
+        let program_id = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let account = Pubkey::from(Keypair::new().pubkey().to_bytes());
+        let payer = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instruction = migrate_account()
+            .account(account)
+            .payer(payer)
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(&instruction.data[..8], &[177, 228, 60, 125, 13, 116, 44, 84]);
+        assert_eq!(instruction.accounts.len(), 3);
+        assert_eq!(instruction.accounts[0].pubkey, account);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(!instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[1].pubkey, payer);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(instruction.accounts[2].pubkey, system_program::ID);
+
+        assert!(migrate_account().payer(payer).build_instruction().is_err());
+        assert!(migrate_account().account(account).build_instruction().is_err());
+    }
+
     #[test]
     #[cfg(feature = "platform-admin")]
     fn test_transfer_authority_builder() {
@@ -2892,8 +7710,9 @@ mod tests {
             .unwrap();
 
         // Verify the data contains the discriminator (8 bytes) followed by serialized args
-        // PauseArgs is empty, so data should be exactly 8 bytes (discriminator only)
-        assert_eq!(instruction.data.len(), 8);
+        // Without a reason or auto-unpause time: 64 zero bytes + 1 byte `None`
+        assert_eq!(instruction.data.len(), 8 + 64 + 1);
+        assert!(instruction.data[8..].iter().all(|&b| b == 0));
 
         // Verify the discriminator matches
         assert_eq!(
@@ -2902,6 +7721,33 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "platform-admin")]
+    fn test_pause_builder_reason_and_auto_unpause() {
+        let platform_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        let instruction = pause()
+            .platform_authority(platform_authority)
+            .reason("Scheduled maintenance")
+            .auto_unpause_ts(1_700_003_600)
+            .build_instruction()
+            .unwrap();
+
+        let args = crate::program_types::PauseArgs::try_from_slice(&instruction.data[8..]).unwrap();
+        assert_eq!(&args.reason[..21], b"Scheduled maintenance");
+        assert!(args.reason[21..].iter().all(|&b| b == 0));
+        assert_eq!(args.auto_unpause_ts, Some(1_700_003_600));
+
+        let result = pause()
+            .platform_authority(platform_authority)
+            .reason("x".repeat(65))
+            .build_instruction();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Pause reason must be at most 64 bytes"));
+    }
+
     #[test]
     #[cfg(feature = "platform-admin")]
     fn test_pause_builder_clone_debug() {
@@ -3284,6 +8130,29 @@ mod tests {
             .contains("Default allowance periods must be > 0"));
     }
 
+    #[test]
+    #[cfg(feature = "platform-admin")]
+    fn test_update_config_builder_renewal_tolerance_bounds() {
+        let platform_authority = Pubkey::from(Keypair::new().pubkey().to_bytes());
+
+        assert!(update_config()
+            .platform_authority(platform_authority)
+            .renewal_tolerance_secs(crate::MAX_RENEWAL_TOLERANCE_SECS)
+            .build_instruction()
+            .is_ok());
+
+        let result = update_config()
+            .platform_authority(platform_authority)
+            .renewal_tolerance_secs(crate::MAX_RENEWAL_TOLERANCE_SECS + 1)
+            .build_instruction();
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Renewal tolerance must be <= 3600 seconds"));
+    }
+
     #[test]
     #[cfg(feature = "platform-admin")]
     fn test_update_config_builder_custom_program_id() {
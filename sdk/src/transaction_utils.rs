@@ -2,6 +2,9 @@
 //!
 //! This module provides core transaction building and serialization utilities
 //! that are commonly needed across different applications in the Tally ecosystem.
+//! [`TxAssembler`] covers flows `build_transaction` does not: v0 messages, a fee payer
+//! separate from the other signers, compute-budget and memo instructions, and
+//! partially-signed transactions that a wallet co-signs.
 
 #![forbid(unsafe_code)]
 
@@ -9,13 +12,17 @@ use crate::error::{Result, TallyError};
 use anchor_client::solana_sdk::{
     hash::Hash,
     instruction::Instruction,
-    message::{Message, VersionedMessage},
+    message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
     pubkey::Pubkey,
-    signature::Signature,
+    signature::{Signature, Signer},
     transaction::VersionedTransaction,
 };
 use base64::{engine::general_purpose::STANDARD, Engine};
 
+/// Compute Budget program ID
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("ComputeBudget111111111111111111111111111111");
+
 /// Parameters for building a start agreement transaction
 #[derive(Debug, Clone)]
 pub struct StartAgreementTransactionParams<'a> {
@@ -60,6 +67,56 @@ pub fn create_memo_instruction(memo: &str) -> Instruction {
     }
 }
 
+/// Maximum length in bytes of a customer note attached to a payment
+pub const MAX_CUSTOMER_MEMO_LEN: usize = 128;
+
+/// Validates a customer note (order or invoice reference) attached to a payment
+///
+/// The memo program logs memos in quoted, escaped form, so notes are limited to
+/// printable ASCII without `"` or `\` to read back unchanged through
+/// [`extract_memo_from_logs`](crate::events::extract_memo_from_logs).
+///
+/// # Errors
+/// Returns an error if the note is empty, longer than [`MAX_CUSTOMER_MEMO_LEN`] bytes,
+/// or contains characters outside that set
+pub fn validate_customer_memo(memo: &str) -> Result<()> {
+    if memo.is_empty() {
+        return Err("Memo must not be empty".into());
+    }
+    if memo.len() > MAX_CUSTOMER_MEMO_LEN {
+        return Err(TallyError::Generic(format!(
+            "Memo must be at most {MAX_CUSTOMER_MEMO_LEN} bytes, got {}",
+            memo.len()
+        )));
+    }
+    if let Some(invalid) = memo
+        .chars()
+        .find(|c| !matches!(c, ' '..='~') || matches!(c, '"' | '\\'))
+    {
+        return Err(TallyError::Generic(format!(
+            "Memo contains unsupported character {invalid:?}; use printable ASCII without quotes or backslashes"
+        )));
+    }
+    Ok(())
+}
+
+/// Formats the receipt memo for a renewal charged by `execute_payment`
+///
+/// Keyed on the agreement and the `period_index` the payment advances it to, which
+/// is unique per agreement even when retries land in the same second. Pass the
+/// agreement's current `period_index` plus one.
+///
+/// # Arguments
+/// * `payment_agreement` - The payment agreement PDA
+/// * `period_index` - The agreement's `period_index` after the payment
+///
+/// # Returns
+/// Memo string of the form `tally:<agreement>:<period_index>`
+#[must_use]
+pub fn payment_memo(payment_agreement: &Pubkey, period_index: u64) -> String {
+    format!("tally:{payment_agreement}:{period_index}")
+}
+
 /// Builds a complete transaction with recent blockhash and serializes it to base64
 ///
 /// This is the core transaction building utility used across the Tally ecosystem
@@ -99,6 +156,259 @@ pub fn build_transaction(
     Ok(STANDARD.encode(serialized))
 }
 
+/// Creates a `SetComputeUnitLimit` instruction for the Compute Budget program
+#[must_use]
+pub fn set_compute_unit_limit_instruction(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Creates a `SetComputeUnitPrice` instruction (priority fee in micro-lamports per unit)
+#[must_use]
+pub fn set_compute_unit_price_instruction(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Assembles instructions into a versioned transaction for multi-party signing
+///
+/// The fee payer is the first signer of the message and may differ from the signers
+/// required by the instructions, e.g. a backend sponsoring fees for a payer's
+/// `start_agreement`. Compute-budget instructions are prepended and the memo is
+/// appended. A v0 message is compiled when lookup tables are supplied or [`Self::v0`]
+/// is set, otherwise a legacy message.
+#[derive(Clone, Debug, Default)]
+pub struct TxAssembler {
+    fee_payer: Option<Pubkey>,
+    recent_blockhash: Option<Hash>,
+    instructions: Vec<Instruction>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    v0: bool,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    memo: Option<String>,
+}
+
+impl TxAssembler {
+    /// Create a new transaction assembler
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fee payer
+    #[must_use]
+    pub const fn fee_payer(mut self, fee_payer: Pubkey) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    /// Set the recent blockhash
+    #[must_use]
+    pub const fn recent_blockhash(mut self, recent_blockhash: Hash) -> Self {
+        self.recent_blockhash = Some(recent_blockhash);
+        self
+    }
+
+    /// Append an instruction
+    #[must_use]
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Append several instructions
+    #[must_use]
+    pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Resolve accounts through a lookup table (implies a v0 message)
+    #[must_use]
+    pub fn lookup_table(mut self, lookup_table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(lookup_table);
+        self
+    }
+
+    /// Compile a v0 message even without lookup tables
+    #[must_use]
+    pub const fn v0(mut self) -> Self {
+        self.v0 = true;
+        self
+    }
+
+    /// Set the compute unit limit
+    #[must_use]
+    pub const fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Set the compute unit price (priority fee) in micro-lamports
+    #[must_use]
+    pub const fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Attach a memo for transaction traceability
+    #[must_use]
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Compile the message
+    ///
+    /// # Errors
+    /// Returns an error if required fields are missing or the message cannot be compiled
+    pub fn compile(&self) -> Result<VersionedMessage> {
+        let fee_payer = self.fee_payer.ok_or("Fee payer not set")?;
+        let recent_blockhash = self.recent_blockhash.ok_or("Recent blockhash not set")?;
+        if self.instructions.is_empty() {
+            return Err("At least one instruction must be added".into());
+        }
+
+        let mut instructions = Vec::with_capacity(self.instructions.len().saturating_add(3));
+        if let Some(units) = self.compute_unit_limit {
+            instructions.push(set_compute_unit_limit_instruction(units));
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            instructions.push(set_compute_unit_price_instruction(micro_lamports));
+        }
+        instructions.extend(self.instructions.iter().cloned());
+        if let Some(memo) = &self.memo {
+            instructions.push(create_memo_instruction(memo));
+        }
+
+        if self.v0 || !self.lookup_tables.is_empty() {
+            let message = v0::Message::try_compile(
+                &fee_payer,
+                &instructions,
+                &self.lookup_tables,
+                recent_blockhash,
+            )
+            .map_err(|e| TallyError::Generic(format!("Failed to compile v0 message: {e}")))?;
+            Ok(VersionedMessage::V0(message))
+        } else {
+            Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+                &instructions,
+                Some(&fee_payer),
+                &recent_blockhash,
+            )))
+        }
+    }
+
+    /// Build the unsigned transaction with placeholder signatures
+    ///
+    /// # Errors
+    /// Returns an error if required fields are missing or the message cannot be compiled
+    pub fn build(&self) -> Result<VersionedTransaction> {
+        let message = self.compile()?;
+        Ok(VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                usize::from(message.header().num_required_signatures)
+            ],
+            message,
+        })
+    }
+
+    /// Build the transaction signed by the available `signers`
+    ///
+    /// Signatures of the remaining required signers are left as placeholders for
+    /// co-signing; see [`missing_signers`].
+    ///
+    /// # Errors
+    /// Returns an error if the transaction cannot be built or a signer is not required
+    /// by the message
+    pub fn build_partially_signed(&self, signers: &[&dyn Signer]) -> Result<VersionedTransaction> {
+        let mut transaction = self.build()?;
+        partially_sign(&mut transaction, signers)?;
+        Ok(transaction)
+    }
+}
+
+/// Create a transaction assembler
+#[must_use]
+pub fn tx_assembler() -> TxAssembler {
+    TxAssembler::new()
+}
+
+/// Add signatures to a partially-signed transaction
+///
+/// Each signer fills its own slot, so co-signers can sign in any order without
+/// invalidating earlier signatures.
+///
+/// # Errors
+/// Returns an error if a signer is not a required signer of the message or signing fails
+pub fn partially_sign(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+) -> Result<()> {
+    let message_data = transaction.message.serialize();
+    let num_signers = usize::from(transaction.message.header().num_required_signatures);
+    let required = transaction
+        .message
+        .static_account_keys()
+        .get(..num_signers)
+        .ok_or("Message header lists more signers than account keys")?
+        .to_vec();
+
+    for signer in signers {
+        let pubkey = signer.pubkey();
+        let index = required
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or_else(|| TallyError::Generic(format!("{pubkey} is not a required signer")))?;
+        let signature = signer
+            .try_sign_message(&message_data)
+            .map_err(|e| TallyError::Generic(format!("Signing failed for {pubkey}: {e}")))?;
+        let slot = transaction
+            .signatures
+            .get_mut(index)
+            .ok_or("Transaction is missing signature slots")?;
+        *slot = signature;
+    }
+    Ok(())
+}
+
+/// Required signers whose signature is still a placeholder
+#[must_use]
+pub fn missing_signers(transaction: &VersionedTransaction) -> Vec<Pubkey> {
+    let num_signers = usize::from(transaction.message.header().num_required_signatures);
+    transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .take(num_signers)
+        .zip(transaction.signatures.iter())
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+/// Serialize a (partially-signed) transaction to base64 for a wallet to co-sign
+///
+/// # Errors
+/// Returns error if serialization fails
+pub fn serialize_transaction(transaction: &VersionedTransaction) -> Result<String> {
+    let serialized = bincode::serialize(transaction)
+        .map_err(|e| TallyError::Generic(format!("Transaction serialization failed: {e}")))?;
+    Ok(STANDARD.encode(serialized))
+}
+
 /// Gets or creates the associated token address for a user's USDC account
 /// using tally-sdk ATA utilities
 ///
@@ -155,6 +465,7 @@ pub fn map_tally_error_to_string(err: &TallyError) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_client::solana_sdk::{instruction::AccountMeta, signature::Keypair};
 
     #[test]
     fn test_convert_anchor_pubkey() {
@@ -173,6 +484,27 @@ mod tests {
         assert!(instruction.accounts.is_empty());
     }
 
+    #[test]
+    fn test_validate_customer_memo() {
+        assert!(validate_customer_memo("Order #1234 / PO-99").is_ok());
+        assert!(validate_customer_memo(&"x".repeat(MAX_CUSTOMER_MEMO_LEN)).is_ok());
+
+        assert!(validate_customer_memo("").is_err());
+        assert!(validate_customer_memo(&"x".repeat(MAX_CUSTOMER_MEMO_LEN + 1)).is_err());
+        assert!(validate_customer_memo("say \"hi\"").is_err());
+        assert!(validate_customer_memo("C:\\orders").is_err());
+        assert!(validate_customer_memo("line\nbreak").is_err());
+        assert!(validate_customer_memo("café").is_err());
+    }
+
+    #[test]
+    fn test_payment_memo() {
+        let agreement = Pubkey::new_unique();
+
+        assert_eq!(payment_memo(&agreement, 3), format!("tally:{agreement}:3"));
+        assert_ne!(payment_memo(&agreement, 3), payment_memo(&agreement, 4));
+    }
+
     #[test]
     fn test_build_transaction() {
         let payer = Pubkey::new_unique();
@@ -203,6 +535,111 @@ mod tests {
         assert!(transaction.is_ok());
     }
 
+    fn co_signed_instruction(payer: Pubkey) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![AccountMeta::new(payer, true)],
+            data: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn test_tx_assembler_separates_fee_payer() {
+        let fee_payer = Keypair::new();
+        let payer = Keypair::new();
+
+        let transaction = tx_assembler()
+            .fee_payer(fee_payer.pubkey())
+            .recent_blockhash(Hash::new_unique())
+            .instruction(co_signed_instruction(payer.pubkey()))
+            .build()
+            .unwrap();
+
+        let keys = transaction.message.static_account_keys();
+        assert_eq!(transaction.message.header().num_required_signatures, 2);
+        assert_eq!(keys[0], fee_payer.pubkey());
+        assert_eq!(keys[1], payer.pubkey());
+        assert!(matches!(transaction.message, VersionedMessage::Legacy(_)));
+        assert_eq!(
+            missing_signers(&transaction),
+            vec![fee_payer.pubkey(), payer.pubkey()]
+        );
+    }
+
+    #[test]
+    fn test_tx_assembler_compute_budget_and_memo() {
+        let fee_payer = Pubkey::new_unique();
+        let message = tx_assembler()
+            .fee_payer(fee_payer)
+            .recent_blockhash(Hash::new_unique())
+            .instruction(co_signed_instruction(fee_payer))
+            .compute_unit_limit(200_000)
+            .compute_unit_price(5_000)
+            .memo("order-42")
+            .v0()
+            .compile()
+            .unwrap();
+
+        assert!(matches!(message, VersionedMessage::V0(_)));
+        let keys = message.static_account_keys();
+        let instructions = message.instructions();
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(*instructions[0].program_id(keys), COMPUTE_BUDGET_PROGRAM_ID);
+        assert_eq!(instructions[0].data, [2, 0x40, 0x0d, 0x03, 0x00]);
+        assert_eq!(instructions[1].data[0], 3);
+        assert_eq!(instructions[1].data[1..], 5_000u64.to_le_bytes());
+        assert_eq!(*instructions[3].program_id(keys), spl_memo::ID);
+        assert_eq!(instructions[3].data, b"order-42");
+    }
+
+    #[test]
+    fn test_tx_assembler_partial_signing_and_co_signing() {
+        let fee_payer = Keypair::new();
+        let payer = Keypair::new();
+
+        let mut transaction = tx_assembler()
+            .fee_payer(fee_payer.pubkey())
+            .recent_blockhash(Hash::new_unique())
+            .instruction(co_signed_instruction(payer.pubkey()))
+            .build_partially_signed(&[&fee_payer])
+            .unwrap();
+        assert_eq!(missing_signers(&transaction), vec![payer.pubkey()]);
+
+        // The payer's wallet co-signs the serialized transaction
+        let encoded = serialize_transaction(&transaction).unwrap();
+        let decoded: VersionedTransaction =
+            bincode::deserialize(&STANDARD.decode(encoded).unwrap()).unwrap();
+        assert_eq!(decoded, transaction);
+
+        partially_sign(&mut transaction, &[&payer]).unwrap();
+        assert!(missing_signers(&transaction).is_empty());
+        assert!(transaction.verify_with_results().iter().all(|valid| *valid));
+    }
+
+    #[test]
+    fn test_tx_assembler_rejects_invalid_input() {
+        let fee_payer = Keypair::new();
+        assert!(tx_assembler()
+            .recent_blockhash(Hash::new_unique())
+            .build()
+            .is_err());
+        assert!(tx_assembler()
+            .fee_payer(fee_payer.pubkey())
+            .build()
+            .is_err());
+
+        let assembler = tx_assembler()
+            .fee_payer(fee_payer.pubkey())
+            .recent_blockhash(Hash::new_unique());
+        assert!(assembler.build().is_err(), "No instructions");
+
+        let stranger = Keypair::new();
+        let result = assembler
+            .instruction(co_signed_instruction(fee_payer.pubkey()))
+            .build_partially_signed(&[&stranger]);
+        assert!(result.is_err(), "Signer not required by the message");
+    }
+
     #[test]
     fn test_get_user_usdc_ata() {
         let user = Pubkey::new_unique();
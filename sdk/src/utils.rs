@@ -6,6 +6,7 @@
 
 #![forbid(unsafe_code)]
 
+use crate::error::{Result, TallyError};
 use anchor_client::solana_sdk::{pubkey::Pubkey, sysvar};
 use std::str::FromStr;
 // Note: system_program is deprecated but still used for compatibility
@@ -100,6 +101,9 @@ pub fn basis_points_to_percentage(basis_points: u16) -> f64 {
 
 /// Check if a pubkey is a valid Solana address
 ///
+/// Only exact base58 addresses are valid. Use [`crate::input::parse_pubkey`] to accept
+/// pasted addresses and explorer links and learn why an input was rejected.
+///
 /// # Arguments
 /// * `address` - Base58 encoded address string
 ///
@@ -171,6 +175,232 @@ pub fn format_duration(seconds: u64) -> String {
     }
 }
 
+/// Duration units accepted by [`parse_duration`], largest first
+///
+/// A month is 30 days and a year 365 days, matching how billing periods are priced.
+const DURATION_UNITS: [(&str, u64); 7] = [
+    ("y", 31_536_000),
+    ("mo", 2_592_000),
+    ("w", 604_800),
+    ("d", 86_400),
+    ("h", 3_600),
+    ("m", 60),
+    ("s", 1),
+];
+
+/// Parse a USDC amount written in whole units into micro-lamports
+///
+/// Accepts an optional leading `$` and up to 6 decimal places. The conversion is exact,
+/// unlike [`usdc_to_micro_lamports`], so prices such as `9.99` never round to a
+/// neighbouring amount.
+///
+/// # Arguments
+/// * `input` - Amount such as `"9.99"`, `"$10"` or `"0.000001"`
+///
+/// # Returns
+/// Amount in micro-lamports
+///
+/// # Errors
+/// Returns an error if the input is not a plain non-negative decimal, has more than
+/// 6 decimal places, or overflows `u64`
+///
+/// # Examples
+/// ```
+/// use tally_sdk::utils::parse_usdc;
+///
+/// assert_eq!(parse_usdc("9.99").unwrap(), 9_990_000);
+/// assert_eq!(parse_usdc("$10").unwrap(), 10_000_000);
+/// assert!(parse_usdc("9.9999999").is_err());
+/// ```
+pub fn parse_usdc(input: &str) -> Result<u64> {
+    let invalid = || TallyError::Generic(format!("Invalid USDC amount: {input:?}"));
+    let trimmed = input.trim();
+    let amount = trimmed.strip_prefix('$').unwrap_or(trimmed);
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole.bytes().all(|b| b.is_ascii_digit()) || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if fraction.len() > 6 {
+        return Err(TallyError::Generic(format!(
+            "USDC amount {input:?} has more than 6 decimal places"
+        )));
+    }
+
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| invalid())?
+    };
+    let fraction: u64 = format!("{fraction:0<6}").parse().map_err(|_| invalid())?;
+
+    whole
+        .checked_mul(1_000_000)
+        .and_then(|micro| micro.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+/// Format micro-USDC as a USDC amount in whole units
+///
+/// Always shows at least 2 decimal places and never more than needed, so the output
+/// parses back to the same amount with [`parse_usdc`].
+///
+/// # Arguments
+/// * `micro_usdc` - Amount in micro-USDC (6 decimal places)
+///
+/// # Returns
+/// Amount string without a currency symbol
+///
+/// # Examples
+/// ```
+/// use tally_sdk::utils::format_usdc;
+///
+/// assert_eq!(format_usdc(9_990_000), "9.99");
+/// assert_eq!(format_usdc(10_000_000), "10.00");
+/// assert_eq!(format_usdc(1), "0.000001");
+/// ```
+#[must_use]
+pub fn format_usdc(micro_usdc: u64) -> String {
+    let whole = micro_usdc / 1_000_000;
+    let fraction = format!("{:06}", micro_usdc % 1_000_000);
+    let trimmed = fraction.trim_end_matches('0');
+    let decimals = if trimmed.len() < 2 {
+        &fraction[..2]
+    } else {
+        trimmed
+    };
+    format!("{whole}.{decimals}")
+}
+
+/// Format micro-USDC like [`format_usdc`], with thousands separators for display
+///
+/// # Examples
+/// ```
+/// use tally_sdk::utils::format_usdc_grouped;
+///
+/// assert_eq!(format_usdc_grouped(1_234_500_000), "1,234.50");
+/// ```
+#[must_use]
+pub fn format_usdc_grouped(micro_usdc: u64) -> String {
+    let formatted = format_usdc(micro_usdc);
+    let (digits, decimals) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let mut grouped = String::with_capacity(formatted.len().saturating_add(digits.len() / 3));
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && digits.len().saturating_sub(i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{grouped}.{decimals}")
+}
+
+/// Parse a human readable duration into seconds
+///
+/// A duration is one or more `<number><unit>` segments, optionally separated by
+/// whitespace. Units are `s`, `m`, `h`, `d`, `w`, `mo` (30 days) and `y` (365 days).
+/// A bare number is rejected rather than read as seconds, so `"30"` can't silently
+/// become a 30 second billing period. Accepts the output of [`format_duration`] and
+/// [`format_period`].
+///
+/// # Arguments
+/// * `input` - Duration such as `"30d"`, `"1mo"` or `"1d 12h"`
+///
+/// # Returns
+/// Duration in seconds
+///
+/// # Errors
+/// Returns an error if a segment is missing its number or unit, uses an unknown unit,
+/// or the total overflows `u64`
+///
+/// # Examples
+/// ```
+/// use tally_sdk::utils::parse_duration;
+///
+/// assert_eq!(parse_duration("30d").unwrap(), 2_592_000);
+/// assert_eq!(parse_duration("1mo").unwrap(), 2_592_000);
+/// assert_eq!(parse_duration("1h 30m").unwrap(), 5_400);
+/// assert!(parse_duration("30").is_err());
+/// ```
+pub fn parse_duration(input: &str) -> Result<u64> {
+    let invalid =
+        |reason: &str| TallyError::Generic(format!("Invalid duration {input:?}: {reason}"));
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(invalid("empty"));
+    }
+
+    let mut total: u64 = 0;
+    while !rest.is_empty() {
+        let digits_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits_end == 0 {
+            return Err(invalid("expected a number"));
+        }
+        let (number, after_number) = rest.split_at(digits_end);
+        let unit_end = after_number
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after_number.len());
+        let (unit, after_unit) = after_number.split_at(unit_end);
+        if unit.is_empty() {
+            return Err(invalid("missing unit (s, m, h, d, w, mo, y)"));
+        }
+
+        let multiplier = DURATION_UNITS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+            .map(|(_, secs)| *secs)
+            .ok_or_else(|| invalid(&format!("unknown unit {unit:?}")))?;
+        let value: u64 = number.parse().map_err(|_| invalid("number too large"))?;
+
+        total = value
+            .checked_mul(multiplier)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| invalid("too large"))?;
+        rest = after_unit.trim_start();
+    }
+
+    Ok(total)
+}
+
+/// Format a billing period in seconds using its largest whole unit
+///
+/// Unlike [`format_duration`], which breaks a duration into days, hours, minutes and
+/// seconds, this picks the single largest unit that divides the period exactly, so a
+/// 30 day period reads `"1mo"` and a week `"1w"`. The output parses back with
+/// [`parse_duration`].
+///
+/// # Arguments
+/// * `seconds` - Period in seconds
+///
+/// # Returns
+/// Compact period string
+///
+/// # Examples
+/// ```
+/// use tally_sdk::utils::format_period;
+///
+/// assert_eq!(format_period(2_592_000), "1mo");
+/// assert_eq!(format_period(604_800), "1w");
+/// assert_eq!(format_period(90_000), "25h");
+/// assert_eq!(format_period(0), "0s");
+/// ```
+#[must_use]
+pub fn format_period(seconds: u64) -> String {
+    DURATION_UNITS
+        .iter()
+        .filter(|(_, secs)| seconds != 0 && seconds.is_multiple_of(*secs))
+        .find_map(|(name, secs)| {
+            seconds
+                .checked_div(*secs)
+                .map(|count| format!("{count}{name}"))
+        })
+        .unwrap_or_else(|| format!("{seconds}s"))
+}
+
 /// Calculate payment agreement next payment timestamp
 ///
 /// # Arguments
@@ -242,10 +472,46 @@ pub fn is_agreement_overdue(next_payment_timestamp: i64, grace_period_seconds: u
     current_timestamp > grace_end
 }
 
+/// Hash a payee's internal customer reference for `StartAgreementBuilder::external_ref_hash`
+///
+/// Computes `sha256(salt || customer_id)`. The salt should be a secret kept by the
+/// payee so the hash cannot be reversed by enumerating likely customer ids; the same
+/// salt must be reused to look agreements up by customer later.
+///
+/// # Arguments
+/// * `customer_id` - The payee's internal customer identifier
+/// * `salt` - Payee-held secret salt
+///
+/// # Returns
+/// The 32-byte hash stored on the agreement
+///
+/// # Examples
+/// ```
+/// use tally_sdk::utils::hash_external_ref;
+///
+/// let hash = hash_external_ref("cus_1234", b"merchant-secret");
+/// assert_eq!(hash, hash_external_ref("cus_1234", b"merchant-secret"));
+/// assert_ne!(hash, hash_external_ref("cus_1234", b"other-secret"));
+/// ```
+#[must_use]
+pub fn hash_external_ref(customer_id: &str, salt: &[u8]) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[salt, customer_id.as_bytes()]).to_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hash_external_ref() {
+        let hash = hash_external_ref("cus_1234", b"salt");
+        assert_eq!(
+            hash,
+            anchor_lang::solana_program::hash::hash(b"saltcus_1234").to_bytes()
+        );
+        assert_ne!(hash, hash_external_ref("cus_1235", b"salt"));
+    }
+
     #[test]
     fn test_micro_lamports_to_usdc() {
         const EPSILON: f64 = 1e-10;
@@ -299,6 +565,69 @@ mod tests {
         assert_eq!(format_duration(86400), "1d 0h 0m 0s");
     }
 
+    #[test]
+    fn test_parse_and_format_usdc() {
+        assert_eq!(parse_usdc("9.99").unwrap(), 9_990_000);
+        assert_eq!(parse_usdc(" $10 ").unwrap(), 10_000_000);
+        assert_eq!(parse_usdc(".5").unwrap(), 500_000);
+        assert_eq!(parse_usdc("5.").unwrap(), 5_000_000);
+        assert_eq!(parse_usdc("0.000001").unwrap(), 1);
+
+        // Malformed or lossy amounts are rejected instead of rounded
+        assert!(parse_usdc("").is_err());
+        assert!(parse_usdc(".").is_err());
+        assert!(parse_usdc("-1").is_err());
+        assert!(parse_usdc("1,000").is_err());
+        assert!(parse_usdc("9.9999999").is_err());
+        assert!(parse_usdc("18446744073709.551616").is_err());
+
+        assert_eq!(format_usdc(9_990_000), "9.99");
+        assert_eq!(format_usdc(10_000_000), "10.00");
+        assert_eq!(format_usdc(1_234_567), "1.234567");
+        assert_eq!(format_usdc(0), "0.00");
+        assert_eq!(format_usdc_grouped(0), "0.00");
+        assert_eq!(format_usdc_grouped(12_500_000), "12.50");
+        assert_eq!(format_usdc_grouped(1_234_567), "1.234567");
+        assert_eq!(format_usdc_grouped(1_234_500_000), "1,234.50");
+        assert_eq!(format_usdc_grouped(1_000_000_000_000), "1,000,000.00");
+
+        for amount in [0, 1, 9_990_000, 1_234_567, u64::MAX] {
+            assert_eq!(parse_usdc(&format_usdc(amount)).unwrap(), amount);
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30d").unwrap(), 2_592_000);
+        assert_eq!(parse_duration("1mo").unwrap(), 2_592_000);
+        assert_eq!(parse_duration("1y").unwrap(), 31_536_000);
+        assert_eq!(parse_duration("2w").unwrap(), 1_209_600);
+        assert_eq!(parse_duration("1h30m").unwrap(), 5_400);
+        assert_eq!(parse_duration("1D 12H").unwrap(), 129_600);
+
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3 days").is_err());
+        assert!(parse_duration("99999999999999999999y").is_err());
+
+        // Both formatters round-trip
+        for seconds in [0, 30, 90, 3_661, 90_061, 2_592_000] {
+            assert_eq!(parse_duration(&format_duration(seconds)).unwrap(), seconds);
+            assert_eq!(parse_duration(&format_period(seconds)).unwrap(), seconds);
+        }
+    }
+
+    #[test]
+    fn test_format_period() {
+        assert_eq!(format_period(2_592_000), "1mo");
+        assert_eq!(format_period(31_536_000), "1y");
+        assert_eq!(format_period(1_209_600), "2w");
+        assert_eq!(format_period(86_400), "1d");
+        assert_eq!(format_period(90), "90s");
+        assert_eq!(format_period(0), "0s");
+    }
+
     #[test]
     fn test_calculate_next_payment() {
         let start = 1000_i64;
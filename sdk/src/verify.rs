@@ -0,0 +1,508 @@
+//! Discriminator verification against the deployed program
+//!
+//! The transaction builders hard-code Anchor instruction discriminators and the event
+//! parser recognises events by discriminator, so a program upgrade that renames an
+//! instruction or event breaks the SDK without any compile error. [`check_discriminators`]
+//! reads the IDL the program published on chain (`anchor idl init`/`anchor idl upgrade`)
+//! and reports every discriminator the SDK uses that the deployed program doesn't match:
+//!
+//! ```no_run
+//! use anchor_client::solana_client::rpc_client::RpcClient;
+//! use tally_sdk::verify::check_discriminators;
+//!
+//! # fn main() -> tally_sdk::Result<()> {
+//! let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+//! let report = check_discriminators(&rpc, &tally_sdk::program_id())?;
+//! for mismatch in &report.mismatches {
+//!     eprintln!("{mismatch}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SimpleTallyClient`](crate::SimpleTallyClient) runs the same check on startup when
+//! [`ClientOptions::verify_discriminators`](crate::ClientOptions::verify_discriminators)
+//! is set.
+
+#![forbid(unsafe_code)]
+
+use crate::error::{Result, TallyError};
+use crate::events::get_event_discriminators;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_lang::solana_program::hash;
+use flate2::read::ZlibDecoder;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+/// Seed of the IDL account, derived from the program's empty-seed PDA
+const IDL_SEED: &str = "anchor:idl";
+
+/// Header of the IDL account: discriminator (8), authority (32) and data length (4)
+const IDL_HEADER_LEN: usize = 44;
+
+/// Instruction discriminators encoded by the transaction builders, by instruction name
+pub const INSTRUCTION_DISCRIMINATORS: [(&str, [u8; 8]); 37] = [
+    ("start_agreement", [174, 25, 237, 147, 127, 156, 238, 34]),
+    ("pause_agreement", [130, 90, 85, 99, 205, 60, 132, 245]),
+    ("resume_agreement", [158, 1, 240, 85, 78, 170, 184, 23]),
+    (
+        "resume_after_unfreeze",
+        [47, 253, 126, 127, 182, 81, 189, 59],
+    ),
+    ("init_payee", [145, 253, 226, 173, 120, 41, 140, 49]),
+    (
+        "create_payment_terms",
+        [220, 74, 165, 113, 140, 252, 204, 241],
+    ),
+    (
+        "schedule_terms_update",
+        [32, 116, 115, 11, 103, 95, 151, 193],
+    ),
+    (
+        "deactivate_payment_terms",
+        [33, 182, 63, 230, 115, 204, 125, 143],
+    ),
+    ("migrate_account", [177, 228, 60, 125, 13, 116, 44, 84]),
+    ("sweep_accrued", [57, 220, 131, 162, 187, 194, 8, 196]),
+    (
+        "admin_withdraw_fees",
+        [236, 186, 208, 151, 204, 142, 168, 30],
+    ),
+    ("init_config", [23, 235, 115, 232, 168, 96, 1, 231]),
+    ("execute_payment", [86, 4, 7, 7, 120, 139, 232, 139]),
+    ("close_agreement", [48, 34, 42, 18, 144, 209, 198, 55]),
+    (
+        "close_agreements_batch",
+        [202, 174, 50, 59, 60, 11, 6, 16],
+    ),
+    (
+        "schedule_cancellation",
+        [141, 114, 46, 221, 173, 128, 100, 145],
+    ),
+    ("reserve_slot", [109, 148, 20, 186, 66, 121, 242, 72]),
+    (
+        "initiate_agreement_transfer",
+        [208, 126, 130, 181, 213, 186, 250, 200],
+    ),
+    (
+        "accept_agreement_transfer",
+        [200, 45, 22, 115, 91, 45, 150, 37],
+    ),
+    ("refund_payment", [121, 205, 211, 181, 202, 147, 45, 248]),
+    ("confirm_activation", [226, 158, 162, 48, 18, 108, 131, 224]),
+    ("refund_escrow", [107, 186, 89, 99, 26, 194, 23, 204]),
+    ("claim_deposit", [201, 106, 1, 224, 122, 144, 210, 155]),
+    ("set_keeper_policy", [113, 131, 98, 244, 25, 146, 140, 188]),
+    (
+        "transfer_payee_authority",
+        [158, 105, 60, 223, 133, 232, 77, 152],
+    ),
+    (
+        "accept_payee_authority",
+        [226, 96, 208, 121, 93, 75, 227, 251],
+    ),
+    (
+        "cancel_payee_authority_transfer",
+        [4, 104, 244, 236, 138, 214, 187, 45],
+    ),
+    ("transfer_authority", [48, 169, 76, 72, 229, 180, 55, 161]),
+    ("accept_authority", [107, 86, 198, 91, 33, 12, 107, 160]),
+    (
+        "cancel_authority_transfer",
+        [94, 131, 125, 184, 183, 24, 125, 229],
+    ),
+    ("pause", [211, 22, 221, 251, 74, 121, 193, 47]),
+    ("unpause", [169, 144, 4, 38, 10, 141, 188, 255]),
+    ("freeze_payee", [156, 126, 104, 149, 244, 68, 176, 95]),
+    ("unfreeze_payee", [83, 40, 102, 144, 194, 101, 157, 195]),
+    ("set_fee_holiday", [12, 30, 9, 225, 238, 1, 36, 239]),
+    ("set_keeper_sol_rate", [39, 1, 201, 33, 122, 210, 202, 103]),
+    ("update_config", [29, 158, 252, 191, 10, 83, 219, 99]),
+];
+
+/// Instruction discriminators of programs built with the `test-clock` feature
+///
+/// Only checked when the deployed IDL lists the instruction, since mainnet builds
+/// never include it.
+pub const TEST_CLOCK_INSTRUCTION_DISCRIMINATORS: [(&str, [u8; 8]); 1] =
+    [("advance_test_clock", [219, 83, 45, 165, 9, 127, 222, 135])];
+
+/// Whether a discriminator belongs to an instruction or an event
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiscriminatorKind {
+    Instruction,
+    Event,
+}
+
+impl fmt::Display for DiscriminatorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Instruction => f.write_str("instruction"),
+            Self::Event => f.write_str("event"),
+        }
+    }
+}
+
+/// An instruction or event whose SDK discriminator the deployed program doesn't match
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscriminatorMismatch {
+    /// Instruction or event
+    pub kind: DiscriminatorKind,
+    /// Instruction name (`snake_case`) or event name (`PascalCase`)
+    pub name: String,
+    /// Discriminator the SDK uses
+    pub sdk: [u8; 8],
+    /// Discriminator in the deployed IDL, or `None` if the program has no such
+    /// instruction or event
+    pub deployed: Option<[u8; 8]>,
+}
+
+impl fmt::Display for DiscriminatorMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.deployed {
+            Some(deployed) => write!(
+                f,
+                "{} {}: SDK uses {:?}, deployed program uses {deployed:?}",
+                self.kind, self.name, self.sdk
+            ),
+            None => write!(
+                f,
+                "{} {}: not found in the deployed program",
+                self.kind, self.name
+            ),
+        }
+    }
+}
+
+/// Result of comparing the SDK's discriminators with a deployed IDL
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiscriminatorReport {
+    /// Number of discriminators compared
+    pub checked: usize,
+    /// Discriminators that don't match the deployed program
+    pub mismatches: Vec<DiscriminatorMismatch>,
+}
+
+impl DiscriminatorReport {
+    /// Whether every discriminator the SDK uses matches the deployed program
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for DiscriminatorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} discriminators mismatched",
+            self.mismatches.len(),
+            self.checked
+        )?;
+        for mismatch in &self.mismatches {
+            write!(f, "; {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Address of the IDL account Anchor publishes for `program_id`
+#[must_use]
+pub fn idl_address(program_id: &Pubkey) -> Pubkey {
+    let (base, _) = Pubkey::find_program_address(&[], program_id);
+    // The seed is a constant well under the 32-byte limit
+    Pubkey::create_with_seed(&base, IDL_SEED, program_id).expect("valid IDL seed")
+}
+
+/// Compare the SDK's discriminators with the IDL published by the deployed program
+///
+/// # Errors
+/// Returns an error if the RPC call fails, the program has no IDL account or the IDL
+/// can't be decoded
+pub fn check_discriminators(rpc: &RpcClient, program_id: &Pubkey) -> Result<DiscriminatorReport> {
+    let address = idl_address(program_id);
+    let account = rpc
+        .get_account_with_commitment(&address, CommitmentConfig::confirmed())
+        .map_err(|e| TallyError::RpcError(format!("Failed to fetch IDL account: {e}")))?
+        .value
+        .ok_or_else(|| TallyError::AccountNotFound(format!("IDL account {address}")))?;
+
+    check_idl_account(&account.data)
+}
+
+/// Compare the SDK's discriminators with the raw data of an IDL account
+///
+/// # Errors
+/// Returns an error if the account data isn't a zlib-compressed JSON IDL
+pub fn check_idl_account(data: &[u8]) -> Result<DiscriminatorReport> {
+    let idl = decode_idl_account(data)?;
+    Ok(check_idl(&idl))
+}
+
+/// Compare the SDK's discriminators with an IDL
+///
+/// Accepts IDLs with explicit discriminators (Anchor 0.30+) as well as legacy IDLs,
+/// whose discriminators are derived from the instruction and event names. Test clock
+/// instructions are only checked when the IDL lists them.
+#[must_use]
+pub fn check_idl(idl: &Value) -> DiscriminatorReport {
+    let instructions = deployed_discriminators(idl, "instructions", "global");
+    let events = deployed_discriminators(idl, "events", "event");
+
+    let sdk_instructions = INSTRUCTION_DISCRIMINATORS
+        .iter()
+        .chain(
+            TEST_CLOCK_INSTRUCTION_DISCRIMINATORS
+                .iter()
+                .filter(|(name, _)| instructions.contains_key(*name)),
+        )
+        .map(|(name, discriminator)| (DiscriminatorKind::Instruction, *name, *discriminator));
+    let mut sdk_events: Vec<_> = get_event_discriminators()
+        .into_iter()
+        .map(|(discriminator, name)| (DiscriminatorKind::Event, name, discriminator))
+        .collect();
+    sdk_events.sort_unstable_by_key(|(_, name, _)| *name);
+
+    let mut report = DiscriminatorReport::default();
+    for (kind, name, sdk) in sdk_instructions.chain(sdk_events) {
+        report.checked = report.checked.saturating_add(1);
+        let deployed = match kind {
+            DiscriminatorKind::Instruction => instructions.get(name),
+            DiscriminatorKind::Event => events.get(name),
+        }
+        .copied();
+        if deployed != Some(sdk) {
+            report.mismatches.push(DiscriminatorMismatch {
+                kind,
+                name: name.to_string(),
+                sdk,
+                deployed,
+            });
+        }
+    }
+    report
+}
+
+/// Decompress the JSON IDL stored after the IDL account header
+fn decode_idl_account(data: &[u8]) -> Result<Value> {
+    let header = data
+        .get(..IDL_HEADER_LEN)
+        .ok_or_else(|| TallyError::ParseError("IDL account data too short".to_string()))?;
+    let mut len_bytes = [0u8; 4];
+    len_bytes.copy_from_slice(&header[40..]);
+    let data_len = usize::try_from(u32::from_le_bytes(len_bytes))
+        .map_err(|_| TallyError::ParseError("IDL data length overflow".to_string()))?;
+    let compressed = data
+        .get(IDL_HEADER_LEN..IDL_HEADER_LEN.saturating_add(data_len))
+        .ok_or_else(|| TallyError::ParseError("IDL data truncated".to_string()))?;
+
+    let mut json = Vec::new();
+    ZlibDecoder::new(compressed)
+        .read_to_end(&mut json)
+        .map_err(|e| TallyError::ParseError(format!("Failed to decompress IDL: {e}")))?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Discriminators of the IDL's instructions or events, keyed by SDK name
+fn deployed_discriminators(
+    idl: &Value,
+    section: &str,
+    namespace: &str,
+) -> HashMap<String, [u8; 8]> {
+    idl.get(section)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.get("name")?.as_str()?;
+            // Legacy IDLs name instructions in camelCase
+            let name = if namespace == "global" {
+                to_snake_case(name)
+            } else {
+                name.to_string()
+            };
+            let discriminator = entry
+                .get("discriminator")
+                .and_then(|value| serde_json::from_value::<[u8; 8]>(value.clone()).ok())
+                .unwrap_or_else(|| sighash(namespace, &name));
+            Some((name, discriminator))
+        })
+        .collect()
+}
+
+/// Anchor discriminator: the first 8 bytes of `sha256("<namespace>:<name>")`
+fn sighash(namespace: &str, name: &str) -> [u8; 8] {
+    let preimage = format!("{namespace}:{name}");
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash::hash(preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !snake.is_empty() {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::ZlibEncoder, Compression};
+    use serde_json::json;
+    use std::io::Write;
+
+    fn deployed_idl() -> Value {
+        let instructions: Vec<Value> = INSTRUCTION_DISCRIMINATORS
+            .iter()
+            .map(|(name, discriminator)| json!({ "name": name, "discriminator": discriminator }))
+            .collect();
+        let events: Vec<Value> = get_event_discriminators()
+            .into_iter()
+            .map(|(discriminator, name)| json!({ "name": name, "discriminator": discriminator }))
+            .collect();
+        json!({ "instructions": instructions, "events": events })
+    }
+
+    #[test]
+    fn test_instruction_discriminators_match_names() {
+        for (name, discriminator) in INSTRUCTION_DISCRIMINATORS
+            .into_iter()
+            .chain(TEST_CLOCK_INSTRUCTION_DISCRIMINATORS)
+        {
+            assert_eq!(sighash("global", name), discriminator, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_builders_use_listed_discriminators() {
+        // Every discriminator hard-coded in the builders must be covered by the check
+        let source = include_str!("transaction_builder.rs");
+        let mut lines = source.lines();
+        let mut found = 0;
+        while let Some(line) = lines.next() {
+            let Some(rest) = line
+                .trim()
+                .strip_prefix("// Instruction discriminator (computed from \"")
+            else {
+                continue;
+            };
+            let name = rest.trim_end_matches("\")").trim_start_matches("global:");
+            let bytes = lines.next().unwrap();
+            let expected = INSTRUCTION_DISCRIMINATORS
+                .iter()
+                .chain(&TEST_CLOCK_INSTRUCTION_DISCRIMINATORS)
+                .find(|(listed, _)| *listed == name)
+                .unwrap_or_else(|| panic!("{name} is not listed"));
+            let listed = format!("{:?}", expected.1);
+            assert!(bytes.contains(&listed), "{name}: {bytes}");
+            found += 1;
+        }
+        assert_eq!(
+            found,
+            INSTRUCTION_DISCRIMINATORS.len() + TEST_CLOCK_INSTRUCTION_DISCRIMINATORS.len()
+        );
+    }
+
+    #[test]
+    fn test_check_idl_matching() {
+        let report = check_idl(&deployed_idl());
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.checked, INSTRUCTION_DISCRIMINATORS.len() + 19);
+    }
+
+    #[test]
+    fn test_check_idl_test_clock_build() {
+        // Test clock instructions are only checked when the program was built with them
+        let mut idl = deployed_idl();
+        idl["instructions"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({
+                "name": "advance_test_clock",
+                "discriminator": [0, 0, 0, 0, 0, 0, 0, 0],
+            }));
+
+        let report = check_idl(&idl);
+        assert_eq!(report.checked, INSTRUCTION_DISCRIMINATORS.len() + 20);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].name, "advance_test_clock");
+    }
+
+    #[test]
+    fn test_check_idl_reports_mismatches() {
+        let mut idl = deployed_idl();
+        let instructions = idl["instructions"].as_array_mut().unwrap();
+        instructions.retain(|ix| ix["name"] != "refund_escrow");
+        instructions[0]["discriminator"] = json!([0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let report = check_idl(&idl);
+        assert!(!report.is_ok());
+        assert_eq!(report.mismatches.len(), 2);
+        assert_eq!(
+            report.mismatches[0],
+            DiscriminatorMismatch {
+                kind: DiscriminatorKind::Instruction,
+                name: "start_agreement".to_string(),
+                sdk: INSTRUCTION_DISCRIMINATORS[0].1,
+                deployed: Some([0; 8]),
+            }
+        );
+        assert_eq!(report.mismatches[1].name, "refund_escrow");
+        assert_eq!(report.mismatches[1].deployed, None);
+        assert!(report
+            .to_string()
+            .contains("not found in the deployed program"));
+    }
+
+    #[test]
+    fn test_check_legacy_idl() {
+        // Legacy IDLs have camelCase instruction names and no discriminators
+        let idl = json!({
+            "instructions": [{ "name": "startAgreement" }, { "name": "executePayment" }],
+            "events": [{ "name": "PaymentExecuted" }],
+        });
+
+        let report = check_idl(&idl);
+        let missing: Vec<&str> = report.mismatches.iter().map(|m| m.name.as_str()).collect();
+        assert!(!missing.contains(&"start_agreement"));
+        assert!(!missing.contains(&"execute_payment"));
+        assert!(!missing.contains(&"PaymentExecuted"));
+        assert!(missing.contains(&"refund_payment"));
+    }
+
+    #[test]
+    fn test_check_idl_account_data() {
+        let json = serde_json::to_vec(&deployed_idl()).unwrap();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&json).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut data = vec![0u8; 40];
+        data.extend_from_slice(&u32::try_from(compressed.len()).unwrap().to_le_bytes());
+        data.extend_from_slice(&compressed);
+        assert!(check_idl_account(&data).unwrap().is_ok());
+
+        assert!(check_idl_account(&data[..20]).is_err());
+        assert!(check_idl_account(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_idl_address_is_deterministic() {
+        let program_id = Pubkey::new_unique();
+        assert_eq!(idl_address(&program_id), idl_address(&program_id));
+        assert_ne!(idl_address(&program_id), program_id);
+    }
+}
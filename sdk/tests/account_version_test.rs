@@ -0,0 +1,243 @@
+//! Tests decoding program accounts across layout versions
+//!
+//! Accounts end with a `version` byte since the program added `migrate_account`.
+//! These tests write accounts with `tally_protocol::state`, in the current layout, in
+//! the unversioned one that ended at `bump` and in version 1 of the agreement layout,
+//! before streaming accrual, and check what `decode_versioned_account` makes of them,
+//! including stale bytes an option that shrank left past their data.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, AnchorSerialize, Discriminator};
+use tally_protocol::state as program;
+use tally_sdk::program_types::{
+    decode_versioned_account, Config, Payee, PaymentAgreement, PaymentTerms, VersionedAccount,
+};
+use tally_sdk::TallyError;
+
+fn program_config() -> program::Config {
+    program::Config {
+        platform_authority: Pubkey::new_unique(),
+        pending_authority: None,
+        max_platform_fee_bps: 1000,
+        min_platform_fee_bps: 50,
+        min_period_seconds: 86_400,
+        default_allowance_periods: 3,
+        allowed_mint: Pubkey::new_unique(),
+        max_withdrawal_amount: 1_000_000_000,
+        max_grace_period_seconds: 604_800,
+        paused: false,
+        keeper_fee_bps: 25,
+        pause_reason: [0; 64],
+        auto_unpause_ts: None,
+        keeper_sol_rate: None,
+        renewal_tolerance_secs: 30,
+        bump: 255,
+        version: 1,
+    }
+}
+
+fn program_payee() -> program::Payee {
+    let authority = Pubkey::new_unique();
+    program::Payee {
+        authority,
+        usdc_mint: Pubkey::new_unique(),
+        treasury_ata: Pubkey::new_unique(),
+        volume_tier: program::VolumeTier::Standard,
+        monthly_volume_usdc: 0,
+        last_volume_update_ts: 0,
+        frozen: false,
+        open_execution: true,
+        authorized_keepers: vec![Pubkey::new_unique()],
+        original_authority: authority,
+        pending_authority: None,
+        fee_holiday: None,
+        active_agreements: 4,
+        lifetime_revenue_usdc: 40_000_000,
+        lifetime_renewals: 4,
+        bump: 254,
+        version: 1,
+    }
+}
+
+fn program_agreement() -> program::PaymentAgreement {
+    program::PaymentAgreement {
+        payment_terms: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        next_payment_ts: 1_700_000_000,
+        active: true,
+        payment_count: 3,
+        created_ts: 1_700_000_000,
+        last_amount: 10_000_000,
+        last_payment_ts: 1_700_000_000,
+        last_pull_period_index: 2,
+        cancel_at_period_end: false,
+        pending_payer: None,
+        refunded_amount: 0,
+        max_periods: Some(12),
+        periods_paid: 3,
+        external_ref_hash: None,
+        paused_at_ts: None,
+        credit_amount: 0,
+        escrow: None,
+        period_index: 2,
+        suspension: None,
+        deposit_held: 0,
+        bump: 253,
+        version: 2,
+        last_sweep_ts: 0,
+    }
+}
+
+/// Account data after the discriminator, allocated at `len` bytes
+fn account_data<T: AccountSerialize>(account: &T, len: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(len, 0);
+    data.split_off(8)
+}
+
+#[test]
+fn test_current_accounts_decode() {
+    let config = program_config();
+    let data = account_data(&config, program::Config::SPACE);
+    let decoded = decode_versioned_account::<Config>(&data).unwrap();
+    assert_eq!(decoded.version, Config::VERSION);
+    assert_eq!(decoded.platform_authority, config.platform_authority);
+    assert_eq!(decoded.renewal_tolerance_secs, 30);
+
+    let payee = program_payee();
+    let data = account_data(&payee, program::Payee::SPACE);
+    let decoded = decode_versioned_account::<Payee>(&data).unwrap();
+    assert_eq!(decoded.version, Payee::VERSION);
+    assert_eq!(decoded.authorized_keepers, payee.authorized_keepers);
+    assert_eq!(decoded.bump, 254);
+}
+
+#[test]
+fn test_unversioned_accounts_decode_as_version_zero() {
+    // The unversioned payee layout ended at `bump`, 114 bytes after the discriminator
+    let payee = program_payee();
+    let mut data = Vec::new();
+    payee.authority.serialize(&mut data).unwrap();
+    payee.usdc_mint.serialize(&mut data).unwrap();
+    payee.treasury_ata.serialize(&mut data).unwrap();
+    payee.volume_tier.serialize(&mut data).unwrap();
+    payee.monthly_volume_usdc.serialize(&mut data).unwrap();
+    payee.last_volume_update_ts.serialize(&mut data).unwrap();
+    payee.bump.serialize(&mut data).unwrap();
+    assert_eq!(data.len(), 114);
+
+    let decoded = decode_versioned_account::<Payee>(&data).unwrap();
+    assert_eq!(decoded.version, 0);
+    assert_eq!(decoded.authority, payee.authority);
+    assert_eq!(decoded.bump, 254);
+    assert_eq!(decoded.original_authority, payee.authority);
+    assert!(decoded.open_execution);
+    assert!(decoded.authorized_keepers.is_empty());
+
+    // A config whose pending authority was accepted kept the tail of its old data
+    let mut config = program_config();
+    config.pending_authority = Some(Pubkey::new_unique());
+    let mut data = vec![0xAA; 130];
+    let mut shrunk = Vec::new();
+    config.platform_authority.serialize(&mut shrunk).unwrap();
+    None::<Pubkey>.serialize(&mut shrunk).unwrap();
+    config.max_platform_fee_bps.serialize(&mut shrunk).unwrap();
+    config.min_platform_fee_bps.serialize(&mut shrunk).unwrap();
+    config.min_period_seconds.serialize(&mut shrunk).unwrap();
+    config.default_allowance_periods.serialize(&mut shrunk).unwrap();
+    config.allowed_mint.serialize(&mut shrunk).unwrap();
+    config.max_withdrawal_amount.serialize(&mut shrunk).unwrap();
+    config.max_grace_period_seconds.serialize(&mut shrunk).unwrap();
+    config.paused.serialize(&mut shrunk).unwrap();
+    config.keeper_fee_bps.serialize(&mut shrunk).unwrap();
+    config.bump.serialize(&mut shrunk).unwrap();
+    data[..shrunk.len()].copy_from_slice(&shrunk);
+
+    let decoded = decode_versioned_account::<Config>(&data).unwrap();
+    assert_eq!(decoded.version, 0);
+    assert_eq!(decoded.pending_authority, None);
+    assert_eq!(decoded.bump, 255);
+    assert_eq!(decoded.pause_reason, [0; 64]);
+    assert_eq!(decoded.keeper_sol_rate, None);
+    assert_eq!(decoded.renewal_tolerance_secs, 0);
+}
+
+#[test]
+fn test_version_one_agreement_decodes() {
+    // Version 1 ends at the version byte, eight bytes short of `last_sweep_ts`
+    let mut agreement = program_agreement();
+    agreement.version = 1;
+    let mut data = Vec::new();
+    agreement.try_serialize(&mut data).unwrap();
+    data.truncate(data.len() - 8);
+    let data = data.split_off(8);
+
+    let decoded = decode_versioned_account::<PaymentAgreement>(&data).unwrap();
+    assert_eq!(decoded.version, 1);
+    assert_eq!(decoded.payer, agreement.payer);
+    assert_eq!(decoded.max_periods, Some(12));
+    assert_eq!(decoded.last_sweep_ts, 0);
+
+    // An agreement whose pending payer was cleared kept the tail of its old data
+    agreement.pending_payer = Some(Pubkey::new_unique());
+    agreement.credit_amount = 4_000_000;
+    agreement.deposit_held = 5_000_000;
+    let mut data = Vec::new();
+    agreement.try_serialize(&mut data).unwrap();
+    data.truncate(data.len() - 8);
+    agreement.pending_payer = None;
+    let mut shrunk = Vec::new();
+    agreement.try_serialize(&mut shrunk).unwrap();
+    shrunk.truncate(shrunk.len() - 8);
+    data[..shrunk.len()].copy_from_slice(&shrunk);
+    assert!(data[shrunk.len()..].iter().any(|byte| *byte != 0));
+    let data = data.split_off(8);
+
+    let decoded = decode_versioned_account::<PaymentAgreement>(&data).unwrap();
+    assert_eq!(decoded.version, 1);
+    assert_eq!(decoded.pending_payer, None);
+    assert_eq!(decoded.deposit_held, 5_000_000);
+    assert_eq!(decoded.last_sweep_ts, 0);
+}
+
+#[test]
+fn test_newer_version_rejected() {
+    let mut config = program_config();
+    config.version = Config::VERSION + 1;
+    let data = account_data(&config, program::Config::SPACE);
+
+    match decode_versioned_account::<Config>(&data) {
+        Err(TallyError::UnsupportedAccountVersion {
+            account,
+            version,
+            supported,
+        }) => {
+            assert_eq!(account, "config");
+            assert_eq!(version, Config::VERSION + 1);
+            assert_eq!(supported, Config::VERSION);
+        }
+        other => panic!("Expected UnsupportedAccountVersion, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_discriminators_match_program() {
+    // Account scans filter on these, so they must match every layout the program writes
+    assert_eq!(Config::DISCRIMINATOR, *program::Config::DISCRIMINATOR);
+    assert_eq!(Payee::DISCRIMINATOR, *program::Payee::DISCRIMINATOR);
+    assert_eq!(PaymentTerms::DISCRIMINATOR, *program::PaymentTerms::DISCRIMINATOR);
+    assert_eq!(
+        PaymentAgreement::DISCRIMINATOR,
+        *program::PaymentAgreement::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn test_agreement_space_matches_program() {
+    // Preflight quotes rent for a new agreement at this size
+    assert_eq!(
+        tally_sdk::preflight::PAYMENT_AGREEMENT_SPACE,
+        program::PaymentAgreement::SPACE
+    );
+}
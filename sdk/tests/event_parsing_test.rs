@@ -59,17 +59,19 @@ impl EventTestFixture {
         discriminator
     }
 
-    /// Create a PaymentAgreementStarted event
+    /// Create a `PaymentAgreementStarted` event
     const fn create_payment_agreement_started_event(&self, amount: u64) -> PaymentAgreementStarted {
         PaymentAgreementStarted {
             payee: self.payee,
             payment_terms: self.payment_terms,
             payer: self.payer,
             amount,
+            external_ref_hash: None,
+            delegated_amount: 0,
         }
     }
 
-    /// Create a PaymentExecuted event
+    /// Create a `PaymentExecuted` event
     const fn create_payment_executed_event(&self, amount: u64, keeper: Pubkey, keeper_fee: u64) -> PaymentExecuted {
         PaymentExecuted {
             payee: self.payee,
@@ -78,10 +80,11 @@ impl EventTestFixture {
             amount,
             keeper,
             keeper_fee,
+            period_index: 1,
         }
     }
 
-    /// Create a PaymentAgreementPaused event
+    /// Create a `PaymentAgreementPaused` event
     const fn create_agreement_paused_event(&self) -> PaymentAgreementPaused {
         PaymentAgreementPaused {
             payee: self.payee,
@@ -393,6 +396,21 @@ async fn test_multiple_events_in_logs() {
             TallyEvent::ConfigUpdated(_) => "ConfigUpdated",
             TallyEvent::VolumeTierUpgraded(_) => "VolumeTierUpgraded",
             TallyEvent::PaymentTermsUpdated(_) => "PaymentTermsUpdated",
+            TallyEvent::PayeeAuthorityTransferInitiated(_) => "PayeeAuthorityTransferInitiated",
+            TallyEvent::PayeeAuthorityTransferred(_) => "PayeeAuthorityTransferred",
+            TallyEvent::PayeeAuthorityTransferCancelled(_) => "PayeeAuthorityTransferCancelled",
+            TallyEvent::CreditApplied(_) => "CreditApplied",
+            TallyEvent::TermsSunset(_) => "TermsSunset",
+            TallyEvent::EscrowReleased(_) => "EscrowReleased",
+            TallyEvent::EscrowRefunded(_) => "EscrowRefunded",
+            TallyEvent::KeeperPaidInSol(_) => "KeeperPaidInSol",
+            TallyEvent::AgreementSuspended(_) => "AgreementSuspended",
+            TallyEvent::AgreementUnsuspended(_) => "AgreementUnsuspended",
+            TallyEvent::DepositHeld(_) => "DepositHeld",
+            TallyEvent::DepositClaimed(_) => "DepositClaimed",
+            TallyEvent::DepositReleased(_) => "DepositReleased",
+            TallyEvent::Swept(_) => "Swept",
+            TallyEvent::AgreementSnapshot(_) => "AgreementSnapshot",
         })
         .collect();
 
@@ -601,6 +619,7 @@ async fn test_receipt_event_getters() {
         logs: vec![],
         compute_units_consumed: Some(15000),
         fee: 5000,
+        memo: None,
     };
 
     // Test event getters
@@ -0,0 +1,209 @@
+//! Property tests checking SDK fee math against the on-chain program
+//!
+//! The SDK's `fees` module re-implements the program's integer math so frontends
+//! can display exact amounts. These tests sweep amounts and basis point rates with a
+//! deterministic pseudo-random generator and compare every result against
+//! `tally_protocol::utils`, the same functions the program's handlers call. SOL keeper
+//! fees are checked against `tally_protocol::state::KeeperSolRate` and the treasury
+//! split of `execute_payment`.
+
+use anchor_lang::prelude::Pubkey;
+use tally_sdk::fees::{
+    apply_gate_discount, compute_initial_payment_breakdown, compute_payment_breakdown,
+};
+use tally_sdk::program_types::{Config, KeeperSolRate, Payee, VolumeTier};
+
+const ITERATIONS: usize = 10_000;
+const TIERS: [VolumeTier; 3] = [VolumeTier::Standard, VolumeTier::Growth, VolumeTier::Scale];
+
+/// Platform fee the program charges a payee at `tier` with no volume in the current
+/// window: the payment's own volume may promote the tier before the split
+fn program_platform_fee_bps(tier: VolumeTier, amount: u64) -> u16 {
+    use tally_protocol::state::VolumeTier as ProgramTier;
+
+    let tier = match tier {
+        VolumeTier::Standard => ProgramTier::Standard,
+        VolumeTier::Growth => ProgramTier::Growth,
+        VolumeTier::Scale => ProgramTier::Scale,
+    };
+    tier.max(ProgramTier::from_monthly_volume(amount)).platform_fee_bps()
+}
+
+/// Minimal xorshift generator so the sweep is reproducible without extra dependencies
+struct XorShift(u64);
+
+impl XorShift {
+    const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Amounts up to the maximum plan price, biased towards small values where rounding matters
+    const fn amount(&mut self) -> u64 {
+        let max = tally_protocol::constants::MAX_PLAN_PRICE_USDC;
+        match self.next() % 3 {
+            0 => self.next() % 1_000,
+            1 => self.next() % 1_000_000_000,
+            _ => self.next() % max,
+        }
+    }
+}
+
+fn payee(volume_tier: VolumeTier) -> Payee {
+    let authority = Pubkey::new_unique();
+    Payee {
+        authority,
+        usdc_mint: Pubkey::new_unique(),
+        treasury_ata: Pubkey::new_unique(),
+        volume_tier,
+        monthly_volume_usdc: 0,
+        last_volume_update_ts: 0,
+        frozen: false,
+        open_execution: true,
+        authorized_keepers: Vec::new(),
+        original_authority: authority,
+        pending_authority: None,
+        fee_holiday: None,
+        active_agreements: 0,
+        lifetime_revenue_usdc: 0,
+        lifetime_renewals: 0,
+        bump: 255,
+        version: 1,
+    }
+}
+
+fn config(keeper_fee_bps: u16) -> Config {
+    Config {
+        platform_authority: Pubkey::new_unique(),
+        pending_authority: None,
+        max_platform_fee_bps: 50,
+        min_platform_fee_bps: 10,
+        min_period_seconds: 86_400,
+        default_allowance_periods: 3,
+        allowed_mint: Pubkey::new_unique(),
+        max_withdrawal_amount: 1_000_000_000,
+        max_grace_period_seconds: 604_800,
+        paused: false,
+        keeper_fee_bps,
+        pause_reason: [0; 64],
+        auto_unpause_ts: None,
+        keeper_sol_rate: None,
+        renewal_tolerance_secs: 0,
+        bump: 255,
+        version: 1,
+    }
+}
+
+#[test]
+fn test_payment_breakdown_matches_program() {
+    let mut rng = XorShift(0x5EED_F00D_CAFE_BABE);
+
+    for _ in 0..ITERATIONS {
+        let amount = rng.amount();
+        let keeper_fee_bps = u16::try_from(rng.next() % 101).unwrap();
+        let tier = TIERS[usize::try_from(rng.next() % 3).unwrap()];
+
+        let sdk =
+            compute_payment_breakdown(amount, &payee(tier), &config(keeper_fee_bps), 0, false)
+                .unwrap();
+        let program = tally_protocol::utils::calculate_fee_split(
+            amount,
+            keeper_fee_bps,
+            program_platform_fee_bps(tier, amount),
+        )
+        .unwrap();
+
+        assert_eq!(sdk.keeper_fee, program.keeper_fee, "keeper fee for {amount}");
+        assert_eq!(sdk.platform_fee, program.platform_fee, "platform fee for {amount}");
+        assert_eq!(sdk.payee_amount, program.payee_amount, "payee amount for {amount}");
+        assert_eq!(sdk.total(), amount, "breakdown must sum to {amount}");
+    }
+}
+
+#[test]
+fn test_sol_keeper_fee_breakdown_matches_program() {
+    let mut rng = XorShift(0x501_4EE9_E4F3_E5A7);
+
+    for _ in 0..ITERATIONS {
+        let amount = rng.amount();
+        let keeper_fee_bps = u16::try_from(rng.next() % 101).unwrap();
+        let tier = TIERS[usize::try_from(rng.next() % 3).unwrap()];
+        let lamports_per_usdc = rng.next() % 100_000_000;
+        let now = i64::try_from(rng.next() % 86_400).unwrap();
+
+        let mut config = config(keeper_fee_bps);
+        config.keeper_sol_rate = Some(KeeperSolRate {
+            lamports_per_usdc,
+            updated_ts: 0,
+        });
+        let sdk = compute_payment_breakdown(amount, &payee(tier), &config, now, true).unwrap();
+
+        // execute_payment sends the USDC keeper fee to the platform treasury and pays
+        // the keeper from the vault at the config's rate
+        let program = tally_protocol::utils::calculate_fee_split(
+            amount,
+            keeper_fee_bps,
+            program_platform_fee_bps(tier, amount),
+        )
+        .unwrap();
+        let rate = tally_protocol::state::KeeperSolRate {
+            lamports_per_usdc,
+            updated_ts: 0,
+        };
+
+        assert_eq!(sdk.keeper_fee, 0, "keeper USDC fee for {amount}");
+        assert_eq!(
+            sdk.platform_fee,
+            program.platform_fee + program.keeper_fee,
+            "platform treasury amount for {amount}"
+        );
+        assert_eq!(sdk.payee_amount, program.payee_amount, "payee amount for {amount}");
+        assert_eq!(
+            Some(sdk.keeper_fee_lamports),
+            rate.lamports_for(program.keeper_fee, now),
+            "keeper lamports for {amount}"
+        );
+        assert_eq!(sdk.total(), amount, "breakdown must sum to {amount}");
+    }
+}
+
+#[test]
+fn test_initial_payment_breakdown_matches_program() {
+    let mut rng = XorShift(0x0DDB_A11C_0FFE_E000);
+
+    for _ in 0..ITERATIONS {
+        let amount = rng.amount();
+        let tier = TIERS[usize::try_from(rng.next() % 3).unwrap()];
+
+        let sdk = compute_initial_payment_breakdown(amount, &payee(tier), 0).unwrap();
+        let program = tally_protocol::utils::calculate_fee_split(
+            amount,
+            0,
+            program_platform_fee_bps(tier, amount),
+        )
+        .unwrap();
+
+        assert_eq!(sdk.keeper_fee, 0);
+        assert_eq!(sdk.platform_fee, program.platform_fee, "platform fee for {amount}");
+        assert_eq!(sdk.payee_amount, program.payee_amount, "payee amount for {amount}");
+    }
+}
+
+#[test]
+fn test_gate_discount_matches_program() {
+    let mut rng = XorShift(0x6A7E_D15C_0947_0001);
+    let max_discount = u64::from(tally_protocol::constants::MAX_GATE_DISCOUNT_BPS);
+
+    for _ in 0..ITERATIONS {
+        let amount = rng.amount();
+        let discount_bps = u16::try_from(rng.next() % (max_discount + 1)).unwrap();
+
+        assert_eq!(
+            apply_gate_discount(amount, discount_bps).unwrap(),
+            tally_protocol::utils::apply_gate_discount(amount, discount_bps).unwrap(),
+            "gate discount of {discount_bps} bps on {amount}"
+        );
+    }
+}
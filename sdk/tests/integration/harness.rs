@@ -0,0 +1,355 @@
+//! Test validator setup and helpers bridging the SDK's Solana 2.x types
+//!
+//! The SDK builders return `anchor-client` (Solana 2.x) instructions while
+//! `solana-program-test` runs on Solana 3.x types, so keys and instructions are
+//! converted byte for byte at this boundary.
+
+use anchor_lang::prelude::Pubkey;
+// Note: bpf_loader_upgradeable is deprecated but matches the SDK's Solana 2.x types
+use anchor_client::solana_sdk::instruction::Instruction as SdkInstruction;
+#[allow(deprecated)]
+use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::AnchorDeserialize;
+use solana_program_test::{find_file, read_file, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey as BanksPubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use tally_sdk::ata::{get_associated_token_address_with_program, TokenProgram};
+use tally_sdk::{pda, TallyEvent};
+
+/// USDC base units in one USDC
+pub const ONE_USDC: u64 = 1_000_000;
+/// Lamports given to every funded keypair
+const FUNDED_LAMPORTS: u64 = 10_000_000_000;
+
+/// Convert an SDK key to a test validator key
+pub const fn to_banks(pubkey: &Pubkey) -> BanksPubkey {
+    BanksPubkey::new_from_array(pubkey.to_bytes())
+}
+
+/// Convert a test validator key to an SDK key
+pub const fn to_sdk(pubkey: &BanksPubkey) -> Pubkey {
+    Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+/// SDK key of a keypair
+pub fn key(keypair: &Keypair) -> Pubkey {
+    to_sdk(&keypair.pubkey())
+}
+
+/// Convert an SDK instruction to a test validator instruction
+fn to_banks_instruction(instruction: SdkInstruction) -> Instruction {
+    Instruction {
+        program_id: to_banks(&instruction.program_id),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: to_banks(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: instruction.data,
+    }
+}
+
+/// Participants of a test run, all funded with SOL
+pub struct Actors {
+    pub upgrade_authority: Keypair,
+    pub platform_authority: Keypair,
+    pub payee_authority: Keypair,
+    pub payer: Keypair,
+    /// Wallet an agreement is transferred to
+    pub new_payer: Keypair,
+    pub keeper: Keypair,
+}
+
+impl Actors {
+    fn new() -> Self {
+        Self {
+            upgrade_authority: Keypair::new(),
+            platform_authority: Keypair::new(),
+            payee_authority: Keypair::new(),
+            payer: Keypair::new(),
+            new_payer: Keypair::new(),
+            keeper: Keypair::new(),
+        }
+    }
+
+    const fn all(&self) -> [&Keypair; 6] {
+        [
+            &self.upgrade_authority,
+            &self.platform_authority,
+            &self.payee_authority,
+            &self.payer,
+            &self.new_payer,
+            &self.keeper,
+        ]
+    }
+}
+
+/// Running test validator with the program, a USDC mint and funded token accounts
+pub struct Harness {
+    pub context: ProgramTestContext,
+    pub program_id: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub actors: Actors,
+}
+
+impl Harness {
+    /// Start a validator with the compiled program deployed under an upgrade authority
+    /// the harness controls, so `init_config` can be signed
+    ///
+    /// The payer and the new payer start with `payer_usdc` USDC; every other actor and
+    /// the program delegate's escrow have an empty USDC ATA.
+    pub async fn start(payer_usdc: u64) -> Self {
+        let program_id = tally_sdk::program_id();
+        let usdc_mint = Pubkey::new_unique();
+        let actors = Actors::new();
+
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        add_upgradeable_program(
+            &mut program_test,
+            &program_id,
+            &key(&actors.upgrade_authority),
+        );
+
+        for actor in actors.all() {
+            program_test.add_account(
+                actor.pubkey(),
+                // The system program ID is all zeros
+                Account::new(FUNDED_LAMPORTS, 0, &BanksPubkey::default()),
+            );
+        }
+
+        program_test.add_account(
+            to_banks(&usdc_mint),
+            mint_account(&key(&actors.upgrade_authority)),
+        );
+        for (owner, amount) in [
+            (key(&actors.platform_authority), 0),
+            (key(&actors.payee_authority), 0),
+            (key(&actors.payer), payer_usdc),
+            (key(&actors.new_payer), payer_usdc),
+            (key(&actors.keeper), 0),
+            (pda::delegate_address_with_program_id(&program_id), 0),
+        ] {
+            program_test.add_account(
+                to_banks(&usdc_ata(&owner, &usdc_mint)),
+                token_account(&usdc_mint, &owner, amount),
+            );
+        }
+
+        let context = program_test.start_with_context().await;
+        Self {
+            context,
+            program_id,
+            usdc_mint,
+            actors,
+        }
+    }
+
+    /// Execute SDK instructions in one transaction, returning the events it emitted
+    ///
+    /// The test validator's fee payer pays the fee; `signers` sign for the accounts
+    /// the instructions require.
+    pub async fn send(
+        &mut self,
+        instructions: Vec<SdkInstruction>,
+        signers: &[&Keypair],
+    ) -> Result<Vec<TallyEvent>, String> {
+        let instructions: Vec<Instruction> =
+            instructions.into_iter().map(to_banks_instruction).collect();
+        let blockhash = self
+            .context
+            .get_new_latest_blockhash()
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut all_signers: Vec<&Keypair> = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let outcome = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .map_err(|e| e.to_string())?;
+        let logs = outcome
+            .metadata
+            .map(|metadata| metadata.log_messages)
+            .unwrap_or_default();
+        outcome
+            .result
+            .map_err(|e| format!("{e}\n{}", logs.join("\n")))?;
+        tally_sdk::parse_events_from_logs(&logs, &self.program_id).map_err(|e| e.to_string())
+    }
+
+    /// Deserialize a program account with the SDK's account types
+    ///
+    /// Accounts are allocated for their largest layout, so trailing bytes after a
+    /// `None` option or a short vector are ignored.
+    pub async fn account<T: AnchorDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(to_banks(address))
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Account {address} not found"));
+        T::deserialize(&mut &account.data[8..]).unwrap()
+    }
+
+    /// Whether an account exists
+    pub async fn exists(&self, address: &Pubkey) -> bool {
+        self.context
+            .banks_client
+            .get_account(to_banks(address))
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    /// USDC balance of `owner`'s ATA
+    pub async fn usdc_balance(&self, owner: &Pubkey) -> u64 {
+        let ata = usdc_ata(owner, &self.usdc_mint);
+        let account = self
+            .context
+            .banks_client
+            .get_account(to_banks(&ata))
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Token account {ata} not found"));
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    /// Current validator time
+    pub async fn now(&self) -> i64 {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    /// Move the validator clock to `unix_timestamp`
+    pub async fn warp_to(&self, unix_timestamp: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+}
+
+/// Canonical USDC ATA of `owner`
+pub fn usdc_ata(owner: &Pubkey, usdc_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program(owner, usdc_mint, TokenProgram::Token).unwrap()
+}
+
+/// Deploy the compiled program under the upgradeable loader with `upgrade_authority`
+fn add_upgradeable_program(
+    program_test: &mut ProgramTest,
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+) {
+    let elf = read_file(
+        find_file("tally_protocol.so")
+            .expect("tally_protocol.so not found; run `anchor build` and set BPF_OUT_DIR"),
+    );
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+
+    let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+    let mut programdata_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*upgrade_authority),
+    })
+    .unwrap();
+    programdata_data.extend_from_slice(&elf);
+
+    let loader = to_banks(&bpf_loader_upgradeable::id());
+    program_test.add_genesis_account(
+        to_banks(program_id),
+        Account {
+            lamports: FUNDED_LAMPORTS,
+            data: program_data,
+            owner: loader,
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+    program_test.add_genesis_account(
+        to_banks(&programdata_address),
+        Account {
+            lamports: FUNDED_LAMPORTS,
+            data: programdata_data,
+            owner: loader,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Initialized 6-decimal mint
+fn mint_account(mint_authority: &Pubkey) -> Account {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(
+        spl_token::state::Mint {
+            mint_authority: COption::Some(*mint_authority),
+            supply: u64::MAX / 2,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        &mut data,
+    )
+    .unwrap();
+    token_program_account(data)
+}
+
+/// Initialized token account holding `amount`
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account::pack(
+        spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        &mut data,
+    )
+    .unwrap();
+    token_program_account(data)
+}
+
+const fn token_program_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: FUNDED_LAMPORTS,
+        data,
+        owner: to_banks(&spl_token::id()),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
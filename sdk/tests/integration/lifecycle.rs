@@ -0,0 +1,694 @@
+//! Payment agreement lifecycle driven entirely by the SDK builders
+
+use crate::harness::{key, usdc_ata, Harness, ONE_USDC};
+use anchor_lang::prelude::Pubkey;
+use tally_sdk::program_types::{
+    Config, CreatePaymentTermsArgs, InitConfigArgs, Payee, PaymentAgreement, PaymentTerms,
+    RenewalQueue,
+};
+use tally_sdk::{pda, transaction_builder, TallyEvent};
+
+const THIRTY_DAYS: u64 = 2_592_000;
+const THIRTY_DAYS_SECS: i64 = 2_592_000;
+const PRICE: u64 = 10 * ONE_USDC;
+const KEEPER_FEE_BPS: u16 = 25;
+/// Accrual rate of the streaming terms, pricing thirty days at 10.368 USDC
+const RATE_PER_SEC: u64 = 4;
+
+fn terms_id_bytes(terms_id: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[..terms_id.len()].copy_from_slice(terms_id.as_bytes());
+    bytes
+}
+
+/// Program-wide accounts set up before any payment terms exist
+struct Deployment {
+    program_id: Pubkey,
+    config: Pubkey,
+    payee: Pubkey,
+    platform_treasury: Pubkey,
+}
+
+impl Deployment {
+    /// Initialize the config and the payee of the harness actors
+    async fn init(h: &mut Harness) -> Self {
+        let program_id = h.program_id;
+        let upgrade_authority = key(&h.actors.upgrade_authority);
+        let platform_authority = key(&h.actors.platform_authority);
+        let payee_authority = key(&h.actors.payee_authority);
+
+        let ix = transaction_builder::init_config()
+            .authority(upgrade_authority)
+            .payer(upgrade_authority)
+            .config_args(InitConfigArgs {
+                platform_authority,
+                max_platform_fee_bps: 1000,
+                min_platform_fee_bps: 10,
+                min_period_seconds: 86_400,
+                default_allowance_periods: 3,
+                allowed_mint: h.usdc_mint,
+                max_withdrawal_amount: 1_000_000 * ONE_USDC,
+                max_grace_period_seconds: 604_800,
+                keeper_fee_bps: KEEPER_FEE_BPS,
+            })
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        let signer = h.actors.upgrade_authority.insecure_clone();
+        let events = h.send(vec![ix], &[&signer]).await.unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [TallyEvent::ConfigInitialized(_)]
+        ));
+        let config_pda = pda::config_address_with_program_id(&program_id);
+        let config: Config = h.account(&config_pda).await;
+        assert_eq!(config.platform_authority, platform_authority);
+        assert_eq!(config.keeper_fee_bps, KEEPER_FEE_BPS);
+
+        let ix = transaction_builder::init_payee()
+            .authority(payee_authority)
+            .payer(payee_authority)
+            .usdc_mint(h.usdc_mint)
+            .treasury_ata(usdc_ata(&payee_authority, &h.usdc_mint))
+            .program_id(program_id)
+            .build_instruction()
+            .unwrap();
+        let signer = h.actors.payee_authority.insecure_clone();
+        let events = h.send(vec![ix], &[&signer]).await.unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [TallyEvent::PayeeInitialized(_)]
+        ));
+
+        Self {
+            program_id,
+            config: config_pda,
+            payee: pda::payee_address_with_program_id(&payee_authority, &program_id),
+            platform_treasury: usdc_ata(&platform_authority, &h.usdc_mint),
+        }
+    }
+
+    /// Create payment terms from `args`, returning their PDA
+    async fn create_terms(&self, h: &mut Harness, args: CreatePaymentTermsArgs) -> Pubkey {
+        let payee_authority = key(&h.actors.payee_authority);
+        let terms_id_bytes = args.terms_id_bytes;
+        let ix = transaction_builder::create_payment_terms()
+            .authority(payee_authority)
+            .payer(payee_authority)
+            .payment_terms_args(args)
+            .program_id(self.program_id)
+            .build_instruction()
+            .unwrap();
+        let signer = h.actors.payee_authority.insecure_clone();
+        let events = h.send(vec![ix], &[&signer]).await.unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [TallyEvent::PaymentTermsCreated(_)]
+        ));
+        pda::payment_terms_address_with_program_id(&self.payee, &terms_id_bytes, &self.program_id)
+    }
+
+    /// Start an agreement for the harness payer on `terms_pda`, returning its PDA
+    #[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+    async fn start(&self, h: &mut Harness, terms_pda: Pubkey) -> Pubkey {
+        let payer = key(&h.actors.payer);
+        let payee: Payee = h.account(&self.payee).await;
+        let terms: PaymentTerms = h.account(&terms_pda).await;
+        let period = i64::try_from(terms.period_secs).unwrap();
+        let next_payment_ts = h.now().await.checked_add(period).unwrap();
+        let renewal_bucket = pda::renewal_bucket(next_payment_ts).unwrap();
+        let ixs = transaction_builder::start_agreement()
+            .payment_terms(terms_pda)
+            .payer(payer)
+            .allowance_periods(3)
+            .renewal_bucket(renewal_bucket)
+            .program_id(self.program_id)
+            .build_instructions(&payee, &terms, &self.platform_treasury)
+            .unwrap();
+        let signer = h.actors.payer.insecure_clone();
+        let events = h.send(ixs, &[&signer]).await.unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, TallyEvent::PaymentAgreementStarted(_))));
+        pda::payment_agreement_address_with_program_id(&terms_pda, &payer, &self.program_id)
+    }
+
+    /// Pause the harness payer's agreement on `terms_pda`
+    async fn pause(&self, h: &mut Harness, terms_pda: Pubkey) {
+        let payee: Payee = h.account(&self.payee).await;
+        let ixs = transaction_builder::pause_agreement()
+            .payment_terms(terms_pda)
+            .payer(key(&h.actors.payer))
+            .program_id(self.program_id)
+            .build_instructions(&payee)
+            .unwrap();
+        let signer = h.actors.payer.insecure_clone();
+        let events = h.send(ixs, &[&signer]).await.unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, TallyEvent::PaymentAgreementPaused(_))));
+    }
+}
+
+/// Monthly terms at `PRICE` with every optional feature off
+fn monthly_terms(terms_id: &str) -> CreatePaymentTermsArgs {
+    CreatePaymentTermsArgs {
+        terms_id: terms_id.to_string(),
+        terms_id_bytes: terms_id_bytes(terms_id),
+        amount_usdc: PRICE,
+        period_secs: THIRTY_DAYS,
+        gate_mint: None,
+        gate_discount_bps: 0,
+        max_subscribers: None,
+        escrow_window_secs: None,
+        deposit_usdc: None,
+        accrual_rate_per_sec: None,
+    }
+}
+
+/// Test the full lifecycle: init config → init payee → create terms → start →
+/// execute → pause → close
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+#[allow(clippy::too_many_lines)]
+async fn test_agreement_lifecycle() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let program_id = d.program_id;
+    let config_pda = d.config;
+    let payee_pda = d.payee;
+    let platform_authority = key(&h.actors.platform_authority);
+    let payee_authority = key(&h.actors.payee_authority);
+    let payer = key(&h.actors.payer);
+    let keeper = key(&h.actors.keeper);
+    let terms_pda = d.create_terms(&mut h, monthly_terms("pro-monthly")).await;
+
+    // Start the agreement, charging the first period
+    let payee: Payee = h.account(&payee_pda).await;
+    let platform_treasury = d.platform_treasury;
+    let first =
+        tally_sdk::compute_initial_payment_breakdown(PRICE, &payee, h.now().await).unwrap();
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+
+    assert_eq!(h.usdc_balance(&payer).await, 100 * ONE_USDC - PRICE);
+    assert_eq!(h.usdc_balance(&payee_authority).await, first.payee_amount);
+    assert_eq!(
+        h.usdc_balance(&platform_authority).await,
+        first.platform_fee
+    );
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+    assert!(agreement.active);
+    assert_eq!(agreement.payer, payer);
+
+    // Execute the next payment once it is due
+    h.warp_to(agreement.next_payment_ts).await;
+    let payee: Payee = h.account(&payee_pda).await;
+    let terms: PaymentTerms = h.account(&terms_pda).await;
+    let config: Config = h.account(&config_pda).await;
+    let renewal =
+        tally_sdk::compute_payment_breakdown(PRICE, &payee, &config, agreement.next_payment_ts, false)
+            .unwrap();
+    let ix = transaction_builder::execute_payment()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .keeper(keeper)
+        .keeper_ata(usdc_ata(&keeper, &h.usdc_mint))
+        .next_payment_ts(agreement.next_payment_ts)
+        .program_id(program_id)
+        .build_instruction(&payee, &terms, &platform_treasury)
+        .unwrap();
+    let keeper_signer = h.actors.keeper.insecure_clone();
+    let events = h.send(vec![ix], &[&keeper_signer]).await.unwrap();
+    let executed = events
+        .iter()
+        .find_map(|event| match event {
+            TallyEvent::PaymentExecuted(executed) => Some(executed),
+            _ => None,
+        })
+        .expect("PaymentExecuted event");
+    assert_eq!(executed.payer, payer);
+    assert_eq!(executed.amount, PRICE);
+    assert_eq!(executed.keeper, keeper);
+    assert_eq!(executed.keeper_fee, renewal.keeper_fee);
+    assert_eq!(executed.period_index, 1);
+
+    assert_eq!(h.usdc_balance(&payer).await, 100 * ONE_USDC - 2 * PRICE);
+    assert_eq!(
+        h.usdc_balance(&payee_authority).await,
+        first.payee_amount + renewal.payee_amount
+    );
+    assert_eq!(
+        h.usdc_balance(&platform_authority).await,
+        first.platform_fee + renewal.platform_fee
+    );
+    assert_eq!(h.usdc_balance(&keeper).await, renewal.keeper_fee);
+    let renewed: PaymentAgreement = h.account(&agreement_pda).await;
+    assert_eq!(
+        renewed.next_payment_ts,
+        agreement.next_payment_ts + THIRTY_DAYS_SECS
+    );
+    assert_eq!(renewed.payment_count, agreement.payment_count + 1);
+    assert_eq!(renewed.period_index, 1);
+
+    // Pause, then close to reclaim rent
+    d.pause(&mut h, terms_pda).await;
+    let paused: PaymentAgreement = h.account(&agreement_pda).await;
+    assert!(!paused.active);
+
+    let ix = transaction_builder::close_agreement()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .program_id(program_id)
+        .build_instruction(&payee)
+        .unwrap();
+    let payer_signer = h.actors.payer.insecure_clone();
+    let events = h.send(vec![ix], &[&payer_signer]).await.unwrap();
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, TallyEvent::PaymentAgreementClosed(_))));
+    assert!(!h.exists(&agreement_pda).await);
+
+    // Pausing and closing move no funds
+    assert_eq!(h.usdc_balance(&payer).await, 100 * ONE_USDC - 2 * PRICE);
+}
+
+/// Streaming terms accruing `RATE_PER_SEC`, with the first thirty days prepaid
+fn streaming_terms(terms_id: &str) -> CreatePaymentTermsArgs {
+    CreatePaymentTermsArgs {
+        amount_usdc: RATE_PER_SEC * THIRTY_DAYS,
+        accrual_rate_per_sec: Some(RATE_PER_SEC),
+        ..monthly_terms(terms_id)
+    }
+}
+
+/// Sweep the harness payer's agreement on streaming `terms_pda` with the keeper
+async fn sweep(
+    h: &mut Harness,
+    d: &Deployment,
+    terms_pda: Pubkey,
+) -> Result<Vec<TallyEvent>, String> {
+    let payee: Payee = h.account(&d.payee).await;
+    let terms: PaymentTerms = h.account(&terms_pda).await;
+    let ix = transaction_builder::sweep_accrued()
+        .payment_terms(terms_pda)
+        .payer(key(&h.actors.payer))
+        .keeper(key(&h.actors.keeper))
+        .program_id(d.program_id)
+        .build_instruction(&payee, &terms, &d.platform_treasury)
+        .unwrap();
+    let signer = h.actors.keeper.insecure_clone();
+    h.send(vec![ix], &[&signer]).await
+}
+
+/// The `Swept` event among `events`
+fn swept_event(events: &[TallyEvent]) -> &tally_sdk::events::Swept {
+    events
+        .iter()
+        .find_map(|event| match event {
+            TallyEvent::Swept(swept) => Some(swept),
+            _ => None,
+        })
+        .expect("Swept event")
+}
+
+/// Pausing halfway through a period credits the unused half, which resuming applies
+/// against its charge
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+async fn test_pause_and_resume_apply_credit() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let payee_authority = key(&h.actors.payee_authority);
+    let payer = key(&h.actors.payer);
+    let terms_pda = d.create_terms(&mut h, monthly_terms("pro-monthly")).await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+
+    h.warp_to(agreement.next_payment_ts - THIRTY_DAYS_SECS / 2).await;
+    d.pause(&mut h, terms_pda).await;
+    let paused: PaymentAgreement = h.account(&agreement_pda).await;
+    let paused_at = paused.paused_at_ts.expect("pause time recorded");
+    let remaining = u64::try_from(agreement.next_payment_ts - paused_at).unwrap();
+    let credit = PRICE * remaining / THIRTY_DAYS;
+    assert!(!paused.active);
+    assert!(credit > 0);
+    assert_eq!(paused.credit_amount, credit);
+
+    // Resume a day later; only the price minus the credit is charged
+    let resume_ts = paused_at + 86_400;
+    h.warp_to(resume_ts).await;
+    let payee: Payee = h.account(&d.payee).await;
+    let terms: PaymentTerms = h.account(&terms_pda).await;
+    let charged = PRICE - credit;
+    let split = tally_sdk::compute_initial_payment_breakdown(charged, &payee, resume_ts).unwrap();
+    let payer_before = h.usdc_balance(&payer).await;
+    let payee_before = h.usdc_balance(&payee_authority).await;
+    let ixs = transaction_builder::resume_agreement()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .renewal_bucket(pda::renewal_bucket(resume_ts + THIRTY_DAYS_SECS).unwrap())
+        .program_id(d.program_id)
+        .build_instructions(&payee, &terms, &d.platform_treasury)
+        .unwrap();
+    let signer = h.actors.payer.insecure_clone();
+    let events = h.send(ixs, &[&signer]).await.unwrap();
+    let applied = events
+        .iter()
+        .find_map(|event| match event {
+            TallyEvent::CreditApplied(applied) => Some(applied),
+            _ => None,
+        })
+        .expect("CreditApplied event");
+    assert_eq!(applied.credit_applied, credit);
+    assert_eq!(applied.amount_charged, charged);
+    assert_eq!(applied.paused_at_ts, paused_at);
+
+    assert_eq!(h.usdc_balance(&payer).await, payer_before - charged);
+    assert_eq!(
+        h.usdc_balance(&payee_authority).await,
+        payee_before + split.payee_amount
+    );
+    let resumed: PaymentAgreement = h.account(&agreement_pda).await;
+    assert!(resumed.active);
+    assert_eq!(resumed.credit_amount, 0);
+    assert_eq!(resumed.paused_at_ts, None);
+    assert_eq!(
+        resumed.next_payment_ts,
+        resumed.last_payment_ts + THIRTY_DAYS_SECS
+    );
+}
+
+/// A keeper sweeps the accrual of a streaming agreement once its prepaid first
+/// period has run out
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+async fn test_sweep_accrued_streaming_agreement() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let payee_authority = key(&h.actors.payee_authority);
+    let payer = key(&h.actors.payer);
+    let keeper = key(&h.actors.keeper);
+    let terms_pda = d.create_terms(&mut h, streaming_terms("metered")).await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+    assert_eq!(agreement.last_sweep_ts, agreement.next_payment_ts);
+
+    h.warp_to(agreement.last_sweep_ts + 1_000).await;
+    let payee: Payee = h.account(&d.payee).await;
+    let terms: PaymentTerms = h.account(&terms_pda).await;
+    let config: Config = h.account(&d.config).await;
+    let payer_before = h.usdc_balance(&payer).await;
+    let payee_before = h.usdc_balance(&payee_authority).await;
+    let events = sweep(&mut h, &d, terms_pda).await.unwrap();
+    let swept = swept_event(&events);
+    assert_eq!(swept.from_ts, agreement.last_sweep_ts);
+    assert!(swept.to_ts >= agreement.last_sweep_ts + 1_000);
+    assert_eq!(
+        Some(swept.amount),
+        agreement.accrued_at(&terms, swept.to_ts)
+    );
+
+    let split =
+        tally_sdk::compute_payment_breakdown(swept.amount, &payee, &config, swept.to_ts, false)
+            .unwrap();
+    assert_eq!(swept.keeper_fee, split.keeper_fee);
+    assert_eq!(h.usdc_balance(&payer).await, payer_before - swept.amount);
+    assert_eq!(
+        h.usdc_balance(&payee_authority).await,
+        payee_before + split.payee_amount
+    );
+    assert_eq!(h.usdc_balance(&keeper).await, split.keeper_fee);
+    let after: PaymentAgreement = h.account(&agreement_pda).await;
+    assert_eq!(after.last_sweep_ts, swept.to_ts);
+}
+
+/// Pausing a streaming agreement keeps the delegate, so a keeper still sweeps the
+/// accrual up to the pause and nothing after it
+#[tokio::test]
+async fn test_sweep_after_pause_collects_accrual_up_to_pause() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let terms_pda = d.create_terms(&mut h, streaming_terms("metered")).await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+
+    h.warp_to(agreement.last_sweep_ts + 1_000).await;
+    d.pause(&mut h, terms_pda).await;
+    let paused: PaymentAgreement = h.account(&agreement_pda).await;
+    let paused_at = paused.paused_at_ts.expect("pause time recorded");
+
+    h.warp_to(paused_at + 5_000).await;
+    let events = sweep(&mut h, &d, terms_pda).await.unwrap();
+    let swept = swept_event(&events);
+    assert_eq!(swept.from_ts, agreement.last_sweep_ts);
+    assert_eq!(swept.to_ts, paused_at);
+    let accrued_secs = u64::try_from(paused_at - agreement.last_sweep_ts).unwrap();
+    assert_eq!(swept.amount, accrued_secs * RATE_PER_SEC);
+
+    // Nothing accrues while paused
+    let error = sweep(&mut h, &d, terms_pda).await.unwrap_err();
+    assert!(error.contains("NothingToSweep"), "{error}");
+}
+
+/// Resuming a streaming agreement charges the accrual up to the pause that no keeper
+/// swept along with the new period
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+async fn test_resume_collects_accrual_left_unswept_at_pause() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let payer = key(&h.actors.payer);
+    let terms_pda = d.create_terms(&mut h, streaming_terms("metered")).await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+
+    h.warp_to(agreement.last_sweep_ts + 1_000).await;
+    d.pause(&mut h, terms_pda).await;
+    let paused: PaymentAgreement = h.account(&agreement_pda).await;
+    let paused_at = paused.paused_at_ts.expect("pause time recorded");
+    // The prepaid period is over, so there is no pause credit
+    assert_eq!(paused.credit_amount, 0);
+    let unswept = u64::try_from(paused_at - agreement.last_sweep_ts).unwrap() * RATE_PER_SEC;
+
+    let resume_ts = paused_at + 86_400;
+    h.warp_to(resume_ts).await;
+    let payee: Payee = h.account(&d.payee).await;
+    let terms: PaymentTerms = h.account(&terms_pda).await;
+    let payer_before = h.usdc_balance(&payer).await;
+    let ixs = transaction_builder::resume_agreement()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .renewal_bucket(pda::renewal_bucket(resume_ts + THIRTY_DAYS_SECS).unwrap())
+        .program_id(d.program_id)
+        .build_instructions(&payee, &terms, &d.platform_treasury)
+        .unwrap();
+    let signer = h.actors.payer.insecure_clone();
+    let events = h.send(ixs, &[&signer]).await.unwrap();
+    let swept = swept_event(&events);
+    assert_eq!(swept.amount, unswept);
+    assert_eq!(swept.from_ts, agreement.last_sweep_ts);
+    assert_eq!(swept.to_ts, paused_at);
+    assert_eq!(swept.keeper_fee, 0);
+
+    assert_eq!(
+        h.usdc_balance(&payer).await,
+        payer_before - terms.amount_usdc - unswept
+    );
+    let resumed: PaymentAgreement = h.account(&agreement_pda).await;
+    assert!(resumed.active);
+    assert_eq!(resumed.last_sweep_ts, resumed.next_payment_ts);
+}
+
+/// Moving an agreement to a new wallet closes the old account and re-indexes the
+/// renewal under the new agreement address
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+async fn test_agreement_transfer_moves_agreement_and_renewal() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let program_id = d.program_id;
+    let payee_authority = key(&h.actors.payee_authority);
+    let payer = key(&h.actors.payer);
+    let new_payer = key(&h.actors.new_payer);
+    let terms_pda = d.create_terms(&mut h, monthly_terms("pro-monthly")).await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+
+    let ix = transaction_builder::initiate_agreement_transfer()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .new_payer(new_payer)
+        .payee_authority(payee_authority)
+        .program_id(program_id)
+        .build_instruction()
+        .unwrap();
+    let signer = h.actors.payer.insecure_clone();
+    h.send(vec![ix], &[&signer]).await.unwrap();
+    let pending: PaymentAgreement = h.account(&agreement_pda).await;
+    assert_eq!(pending.pending_payer, Some(new_payer));
+
+    let payee: Payee = h.account(&d.payee).await;
+    let terms: PaymentTerms = h.account(&terms_pda).await;
+    let ixs = transaction_builder::accept_agreement_transfer()
+        .payment_terms(terms_pda)
+        .old_payer(payer)
+        .new_payer(new_payer)
+        .next_payment_ts(agreement.next_payment_ts)
+        .program_id(program_id)
+        .build_instructions(&payee, &terms)
+        .unwrap();
+    let signer = h.actors.new_payer.insecure_clone();
+    h.send(ixs, &[&signer]).await.unwrap();
+
+    assert!(!h.exists(&agreement_pda).await);
+    let new_agreement_pda =
+        pda::payment_agreement_address_with_program_id(&terms_pda, &new_payer, &program_id);
+    let moved: PaymentAgreement = h.account(&new_agreement_pda).await;
+    assert!(moved.active);
+    assert_eq!(moved.payer, new_payer);
+    assert_eq!(moved.pending_payer, None);
+    assert_eq!(moved.next_payment_ts, agreement.next_payment_ts);
+    assert_eq!(moved.payment_count, agreement.payment_count);
+    assert_eq!(moved.created_ts, agreement.created_ts);
+
+    let bucket = pda::renewal_bucket(agreement.next_payment_ts).unwrap();
+    let old_queue_pda =
+        pda::agreement_renewal_queue_address_with_program_id(bucket, &agreement_pda, &program_id);
+    let new_queue_pda = pda::agreement_renewal_queue_address_with_program_id(
+        bucket,
+        &new_agreement_pda,
+        &program_id,
+    );
+    let new_queue: RenewalQueue = h.account(&new_queue_pda).await;
+    assert!(new_queue.agreements.contains(&new_agreement_pda));
+    assert!(!new_queue.agreements.contains(&agreement_pda));
+    if old_queue_pda != new_queue_pda {
+        let old_queue: RenewalQueue = h.account(&old_queue_pda).await;
+        assert!(!old_queue.agreements.contains(&agreement_pda));
+    }
+}
+
+/// The payee cannot claim an active agreement's deposit, claims part of it once the
+/// agreement is paused, and closing the agreement refunds the rest
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+async fn test_claim_deposit_then_close_refunds_rest() {
+    const DEPOSIT: u64 = 5 * ONE_USDC;
+    const CLAIM: u64 = 2 * ONE_USDC;
+
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let payee_authority = key(&h.actors.payee_authority);
+    let payer = key(&h.actors.payer);
+    let escrow = pda::delegate_address_with_program_id(&d.program_id);
+    let terms_pda = d
+        .create_terms(
+            &mut h,
+            CreatePaymentTermsArgs {
+                deposit_usdc: Some(DEPOSIT),
+                ..monthly_terms("rental")
+            },
+        )
+        .await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+    assert_eq!(agreement.deposit_held, DEPOSIT);
+    assert_eq!(h.usdc_balance(&escrow).await, DEPOSIT);
+    assert_eq!(h.usdc_balance(&payer).await, 100 * ONE_USDC - PRICE - DEPOSIT);
+
+    let payee: Payee = h.account(&d.payee).await;
+    let claim = transaction_builder::claim_deposit()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .amount(CLAIM)
+        .program_id(d.program_id)
+        .build_instruction(&payee)
+        .unwrap();
+    let signer = h.actors.payee_authority.insecure_clone();
+    let error = h
+        .send(vec![claim.clone()], &[&signer])
+        .await
+        .unwrap_err();
+    assert!(error.contains("AlreadyActive"), "{error}");
+
+    d.pause(&mut h, terms_pda).await;
+    let payee_before = h.usdc_balance(&payee_authority).await;
+    let events = h.send(vec![claim], &[&signer]).await.unwrap();
+    let claimed = events
+        .iter()
+        .find_map(|event| match event {
+            TallyEvent::DepositClaimed(claimed) => Some(claimed),
+            _ => None,
+        })
+        .expect("DepositClaimed event");
+    assert_eq!(claimed.amount, CLAIM);
+    assert_eq!(claimed.remaining, DEPOSIT - CLAIM);
+    assert_eq!(h.usdc_balance(&payee_authority).await, payee_before + CLAIM);
+    assert_eq!(h.usdc_balance(&escrow).await, DEPOSIT - CLAIM);
+
+    // The rest of the deposit is returned once the paid period is over
+    h.warp_to(agreement.next_payment_ts).await;
+    let payer_before = h.usdc_balance(&payer).await;
+    let ix = transaction_builder::close_agreement()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .program_id(d.program_id)
+        .build_instruction(&payee)
+        .unwrap();
+    let signer = h.actors.payer.insecure_clone();
+    h.send(vec![ix], &[&signer]).await.unwrap();
+    assert!(!h.exists(&agreement_pda).await);
+    assert_eq!(h.usdc_balance(&escrow).await, 0);
+    assert_eq!(
+        h.usdc_balance(&payer).await,
+        payer_before + DEPOSIT - CLAIM
+    );
+}
+
+/// A frozen payee cannot release an escrowed first payment to its treasury
+#[tokio::test]
+#[allow(clippy::similar_names)] // payer and payee are distinct payment domain terms
+async fn test_frozen_payee_cannot_confirm_activation() {
+    let mut h = Harness::start(100 * ONE_USDC).await;
+    let d = Deployment::init(&mut h).await;
+    let payee_authority = key(&h.actors.payee_authority);
+    let payer = key(&h.actors.payer);
+    let escrow = pda::delegate_address_with_program_id(&d.program_id);
+    let terms_pda = d
+        .create_terms(
+            &mut h,
+            CreatePaymentTermsArgs {
+                escrow_window_secs: Some(259_200),
+                ..monthly_terms("onboarding")
+            },
+        )
+        .await;
+    let agreement_pda = d.start(&mut h, terms_pda).await;
+    assert_eq!(h.usdc_balance(&escrow).await, PRICE);
+
+    let ix = transaction_builder::freeze_payee()
+        .platform_authority(key(&h.actors.platform_authority))
+        .payee_authority(payee_authority)
+        .program_id(d.program_id)
+        .build_instruction()
+        .unwrap();
+    let signer = h.actors.platform_authority.insecure_clone();
+    h.send(vec![ix], &[&signer]).await.unwrap();
+
+    let payee: Payee = h.account(&d.payee).await;
+    assert!(payee.frozen);
+    let ix = transaction_builder::confirm_activation()
+        .payment_terms(terms_pda)
+        .payer(payer)
+        .program_id(d.program_id)
+        .build_instruction(&payee, &d.platform_treasury)
+        .unwrap();
+    let signer = h.actors.payee_authority.insecure_clone();
+    let error = h.send(vec![ix], &[&signer]).await.unwrap_err();
+    assert!(error.contains("PayeeFrozen"), "{error}");
+
+    let agreement: PaymentAgreement = h.account(&agreement_pda).await;
+    assert!(agreement.escrow_pending());
+    assert_eq!(h.usdc_balance(&escrow).await, PRICE);
+    assert_eq!(h.usdc_balance(&payee_authority).await, 0);
+}
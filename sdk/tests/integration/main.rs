@@ -0,0 +1,31 @@
+//! End-to-end tests driving the compiled program with the SDK builders
+//!
+//! Every instruction is built with the SDK exactly as integrators build it, then
+//! executed by `solana-program-test` against the compiled program. Builder account
+//! order, signer flags and argument layouts therefore meet the real program before
+//! deploy instead of only the SDK's own unit tests.
+//!
+//! Test coverage:
+//! - Full lifecycle: init config → init payee → create terms → start → execute →
+//!   pause → close
+//! - Pause credit applied by resume
+//! - Keeper sweep of a streaming agreement, including up to its pause
+//! - Resume collecting streaming accrual left unswept at the pause
+//! - Agreement transfer to a new wallet and its renewal queue entry
+//! - Security deposit claim followed by the refund on close
+//! - Frozen payees cannot confirm an escrowed activation
+//! - Treasury, platform and keeper balances after each payment
+//! - Events parsed from the transaction logs with the SDK event parser
+//!
+//! Running:
+//! The harness loads `tally_protocol.so`, so build the program first and point
+//! `BPF_OUT_DIR` at it:
+//! ```bash
+//! anchor build
+//! BPF_OUT_DIR=target/deploy cargo test -p tally-sdk --features test-sbf --test integration
+//! ```
+
+#![cfg(feature = "test-sbf")]
+
+mod harness;
+mod lifecycle;